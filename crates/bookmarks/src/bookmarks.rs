@@ -0,0 +1,329 @@
+use std::{path::Path, sync::Arc};
+
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Model, Render,
+    Subscription, Task, View, ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use project::Project;
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{
+    bookmarks::{BookmarkId, BookmarkStore},
+    ModalView, Workspace,
+};
+
+actions!(bookmarks, [Toggle, RemoveSelected]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(BookmarksView::register).detach();
+}
+
+pub struct BookmarksView {
+    picker: View<Picker<BookmarksViewDelegate>>,
+}
+
+impl BookmarksView {
+    fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+        workspace.register_action(|workspace, _: &Toggle, cx| {
+            let bookmark_store = workspace.bookmark_store().clone();
+            let project = workspace.project().clone();
+            let handle = cx.view().downgrade();
+            workspace.toggle_modal(cx, move |cx| {
+                BookmarksView::new(handle, bookmark_store, project, cx)
+            });
+        });
+    }
+
+    fn new(
+        workspace: WeakView<Workspace>,
+        bookmark_store: Model<BookmarkStore>,
+        project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let delegate =
+            BookmarksViewDelegate::new(cx.view().downgrade(), workspace, bookmark_store, project);
+        let picker = cx.new_view(|cx| {
+            let mut picker = Picker::uniform_list(delegate, cx);
+            picker.delegate.bookmark_store_subscription = Some(cx.observe(
+                &picker.delegate.bookmark_store,
+                |picker, _, cx| {
+                    picker.delegate.update_candidates(cx);
+                    picker.refresh(cx);
+                },
+            ));
+            picker
+        });
+        Self { picker }
+    }
+
+    fn remove_selected(&mut self, _: &RemoveSelected, cx: &mut ViewContext<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            if let Some(id) = picker.delegate.selected_bookmark() {
+                picker
+                    .delegate
+                    .bookmark_store
+                    .update(cx, |bookmark_store, cx| bookmark_store.remove(id, cx));
+            }
+        });
+    }
+}
+
+impl Render for BookmarksView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("Bookmarks")
+            .w(rems(34.))
+            .on_action(cx.listener(Self::remove_selected))
+            .child(self.picker.clone())
+    }
+}
+
+impl FocusableView for BookmarksView {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for BookmarksView {}
+impl ModalView for BookmarksView {}
+
+struct BookmarkCandidate {
+    id: BookmarkId,
+    label: Option<SharedString>,
+    path: String,
+}
+
+pub struct BookmarksViewDelegate {
+    bookmarks_view: WeakView<BookmarksView>,
+    workspace: WeakView<Workspace>,
+    bookmark_store: Model<BookmarkStore>,
+    bookmark_store_subscription: Option<Subscription>,
+    project: Model<Project>,
+    candidates: Vec<BookmarkCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    last_query: String,
+    /// The bookmark that was last selected, which is labeled when confirming the query
+    /// as input. It is kept when the query no longer matches it.
+    last_selected: Option<BookmarkId>,
+}
+
+impl BookmarksViewDelegate {
+    fn new(
+        bookmarks_view: WeakView<BookmarksView>,
+        workspace: WeakView<Workspace>,
+        bookmark_store: Model<BookmarkStore>,
+        project: Model<Project>,
+    ) -> Self {
+        Self {
+            bookmarks_view,
+            workspace,
+            bookmark_store,
+            bookmark_store_subscription: None,
+            project,
+            candidates: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+            last_query: String::new(),
+            last_selected: None,
+        }
+    }
+
+    fn update_candidates(&mut self, cx: &AppContext) {
+        let project = self.project.read(cx);
+        self.candidates = self
+            .bookmark_store
+            .read(cx)
+            .bookmarks()
+            .iter()
+            .map(|bookmark| {
+                let path = project
+                    .find_local_worktree(&bookmark.abs_path, cx)
+                    .map(|(worktree, relative_path)| {
+                        if project.visible_worktrees(cx).count() > 1 {
+                            Path::new(worktree.read(cx).root_name()).join(relative_path)
+                        } else {
+                            relative_path
+                        }
+                    })
+                    .unwrap_or_else(|| bookmark.abs_path.to_path_buf());
+                BookmarkCandidate {
+                    id: bookmark.id,
+                    label: bookmark.label.clone(),
+                    path: format!("{}:{}", path.to_string_lossy(), bookmark.row() + 1),
+                }
+            })
+            .collect();
+    }
+
+    fn selected_bookmark(&self) -> Option<BookmarkId> {
+        let mat = self.matches.get(self.selected_index)?;
+        Some(self.candidates.get(mat.candidate_id)?.id)
+    }
+}
+
+impl PickerDelegate for BookmarksViewDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Search bookmarks, or type a label and press alt-enter...".into()
+    }
+
+    fn no_matches_text(&self, _cx: &mut WindowContext) -> SharedString {
+        "No bookmarks".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+        if let Some(id) = self.selected_bookmark() {
+            self.last_selected = Some(id);
+        }
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        if self.candidates.is_empty() {
+            self.update_candidates(cx);
+        }
+        self.last_query = query.clone();
+        let candidates = self
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(id, candidate)| {
+                let string = match &candidate.label {
+                    Some(label) => format!("{label} {}", candidate.path),
+                    None => candidate.path.clone(),
+                };
+                StringMatchCandidate::new(id, string)
+            })
+            .collect::<Vec<_>>();
+        let background = cx.background_executor().clone();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                if let Some(id) = delegate.selected_bookmark() {
+                    delegate.last_selected = Some(id);
+                }
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(id) = self.selected_bookmark() else {
+            return;
+        };
+        let Some((abs_path, row)) = self
+            .bookmark_store
+            .read(cx)
+            .bookmark(id)
+            .map(|bookmark| (bookmark.abs_path.clone(), bookmark.row()))
+        else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                editor::bookmarks::open_bookmark(workspace, &abs_path, row, cx)
+                    .detach_and_log_err(cx);
+            })
+            .log_err();
+        self.dismissed(cx);
+    }
+
+    fn confirm_input(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(id) = self.last_selected else {
+            return;
+        };
+        let label = self.last_query.trim();
+        let label = (!label.is_empty()).then(|| SharedString::from(label.to_string()));
+        self.bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.set_label(id, label, cx)
+        });
+        cx.defer(|picker, cx| picker.set_query("", cx));
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.bookmarks_view
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let candidate = self.candidates.get(mat.candidate_id)?;
+        let label_len = candidate.label.as_ref().map_or(0, |label| label.len() + 1);
+        let (label_positions, path_positions): (Vec<_>, Vec<_>) = mat
+            .positions
+            .iter()
+            .partition(|position| **position < label_len);
+        let path_positions = path_positions
+            .into_iter()
+            .map(|position| position - label_len)
+            .collect();
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .start_slot(
+                    Icon::new(IconName::Bookmark)
+                        .size(IconSize::Small)
+                        .color(Color::Accent),
+                )
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .when_some(candidate.label.clone(), |this, label| {
+                            this.child(HighlightedLabel::new(label, label_positions))
+                        })
+                        .child(
+                            HighlightedLabel::new(candidate.path.clone(), path_positions)
+                                .color(Color::Muted),
+                        ),
+                ),
+        )
+    }
+}
@@ -9,7 +9,7 @@ use std::sync::Arc;
 pub use channel_buffer::{ChannelBuffer, ChannelBufferEvent, ACKNOWLEDGE_DEBOUNCE_INTERVAL};
 pub use channel_chat::{
     mentions_to_proto, ChannelChat, ChannelChatEvent, ChannelMessage, ChannelMessageId,
-    MessageParams,
+    ChannelMessageReaction, ChannelThread, MessageParams,
 };
 pub use channel_store::{Channel, ChannelEvent, ChannelMembership, ChannelStore};
 
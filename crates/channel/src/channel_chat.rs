@@ -5,7 +5,7 @@ use client::{
     user::{User, UserStore},
     ChannelId, Client, Subscription, TypedEnvelope, UserId,
 };
-use collections::HashSet;
+use collections::{HashMap, HashSet};
 use futures::lock::Mutex;
 use gpui::{
     AppContext, AsyncAppContext, Context, EventEmitter, Model, ModelContext, Task, WeakModel,
@@ -32,6 +32,7 @@ pub struct ChannelChat {
     rpc: Arc<Client>,
     outgoing_messages_lock: Arc<Mutex<()>>,
     rng: StdRng,
+    threads: HashMap<u64, ChannelThread>,
     _subscription: Subscription,
 }
 
@@ -52,6 +53,26 @@ pub struct ChannelMessage {
     pub mentions: Vec<(Range<usize>, UserId)>,
     pub reply_to_message_id: Option<u64>,
     pub edited_at: Option<OffsetDateTime>,
+    pub reactions: Vec<ChannelMessageReaction>,
+    pub reply_count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChannelMessageReaction {
+    pub emoji: String,
+    pub user_ids: Vec<UserId>,
+}
+
+/// A message along with the messages that reply to it.
+///
+/// Threads are loaded on demand, and kept up to date while they're open.
+#[derive(Clone, Debug)]
+pub struct ChannelThread {
+    pub root: ChannelMessage,
+    pub replies: Vec<ChannelMessage>,
+    /// Whether the earliest replies are loaded, or more can be loaded with
+    /// [`ChannelChat::load_more_thread_replies`].
+    pub loaded_all_replies: bool,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -92,6 +113,9 @@ pub enum ChannelChatEvent {
         channel_id: ChannelId,
         message_id: u64,
     },
+    ThreadUpdated {
+        root_message_id: u64,
+    },
 }
 
 impl EventEmitter<ChannelChatEvent> for ChannelChat {}
@@ -99,6 +123,7 @@ pub fn init(client: &Arc<Client>) {
     client.add_model_message_handler(ChannelChat::handle_message_sent);
     client.add_model_message_handler(ChannelChat::handle_message_removed);
     client.add_model_message_handler(ChannelChat::handle_message_updated);
+    client.add_model_message_handler(ChannelChat::handle_message_reactions_updated);
 }
 
 impl ChannelChat {
@@ -133,6 +158,7 @@ impl ChannelChat {
                 last_acknowledged_id: None,
                 rng: StdRng::from_entropy(),
                 first_loaded_message_id: None,
+                threads: Default::default(),
                 _subscription: subscription.set_model(&cx.handle(), &mut cx.to_async()),
             }
         })?;
@@ -167,6 +193,10 @@ impl ChannelChat {
         &self.rpc
     }
 
+    pub fn user_store(&self) -> &Model<UserStore> {
+        &self.user_store
+    }
+
     pub fn send_message(
         &mut self,
         message: MessageParams,
@@ -196,6 +226,8 @@ impl ChannelChat {
                     nonce,
                     reply_to_message_id: message.reply_to_message_id,
                     edited_at: None,
+                    reactions: Vec::new(),
+                    reply_count: 0,
                 },
                 &(),
             ),
@@ -221,7 +253,7 @@ impl ChannelChat {
             let id = response.id;
             let message = ChannelMessage::from_proto(response, &user_store, &mut cx).await?;
             this.update(&mut cx, |this, cx| {
-                this.insert_messages(SumTree::from_item(message, &()), cx);
+                this.insert_new_message(message, cx);
                 if this.first_loaded_message_id.is_none() {
                     this.first_loaded_message_id = Some(id);
                 }
@@ -306,6 +338,141 @@ impl ChannelChat {
         }))
     }
 
+    /// Adds the current user's reaction to a message, or removes it if they've
+    /// already reacted with the same emoji.
+    pub fn toggle_reaction(
+        &mut self,
+        message_id: u64,
+        emoji: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let request = self.rpc.request(proto::ToggleChannelMessageReaction {
+            channel_id: self.channel_id.0,
+            message_id,
+            emoji,
+        });
+        cx.spawn(move |this, mut cx| async move {
+            let response = request.await?;
+            this.update(&mut cx, |this, cx| {
+                this.reactions_updated(message_id, response.reactions, cx)
+            })?;
+            Ok(())
+        })
+    }
+
+    /// Searches the channel's history for messages matching `query`, starting from
+    /// the most recent match before `before_message_id`. The messages are returned
+    /// oldest first, along with whether there are no earlier matches.
+    pub fn search_messages(
+        &mut self,
+        query: String,
+        before_message_id: Option<u64>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<(Vec<ChannelMessage>, bool)>> {
+        let request = self.rpc.request(proto::SearchChannelMessages {
+            channel_id: self.channel_id.0,
+            query,
+            before_message_id,
+        });
+        let user_store = self.user_store.clone();
+        cx.spawn(move |_, mut cx| async move {
+            let response = request.await?;
+            let messages =
+                ChannelMessage::from_proto_vec(response.messages, &user_store, &mut cx).await?;
+            Ok((messages, response.done))
+        })
+    }
+
+    /// Loads the most recent replies to a message, and keeps them up to date
+    /// until the thread is closed.
+    pub fn open_thread(
+        &mut self,
+        message_id: u64,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let request = self.rpc.request(proto::GetChannelMessageThread {
+            channel_id: self.channel_id.0,
+            message_id,
+            before_message_id: None,
+        });
+        let user_store = self.user_store.clone();
+        cx.spawn(move |this, mut cx| async move {
+            let response = request.await?;
+            let mut messages =
+                ChannelMessage::from_proto_vec(response.messages, &user_store, &mut cx)
+                    .await?
+                    .into_iter();
+            let root = messages.next().ok_or_else(|| anyhow!("no such message"))?;
+            this.update(&mut cx, |this, cx| {
+                this.threads.insert(
+                    message_id,
+                    ChannelThread {
+                        root,
+                        replies: messages.collect(),
+                        loaded_all_replies: response.done,
+                    },
+                );
+                cx.emit(ChannelChatEvent::ThreadUpdated {
+                    root_message_id: message_id,
+                });
+                cx.notify();
+            })
+        })
+    }
+
+    /// Loads the replies of an open thread that precede the ones already loaded.
+    pub fn load_more_thread_replies(
+        &mut self,
+        root_message_id: u64,
+        cx: &mut ModelContext<Self>,
+    ) -> Option<Task<Option<()>>> {
+        let thread = self
+            .threads
+            .get(&root_message_id)
+            .filter(|thread| !thread.loaded_all_replies)?;
+        let before_message_id = thread.replies.iter().find_map(|reply| match reply.id {
+            ChannelMessageId::Saved(id) => Some(id),
+            ChannelMessageId::Pending(_) => None,
+        })?;
+
+        let request = self.rpc.request(proto::GetChannelMessageThread {
+            channel_id: self.channel_id.0,
+            message_id: root_message_id,
+            before_message_id: Some(before_message_id),
+        });
+        let user_store = self.user_store.clone();
+        Some(cx.spawn(move |this, mut cx| {
+            async move {
+                let response = request.await?;
+                let replies =
+                    ChannelMessage::from_proto_vec(response.messages, &user_store, &mut cx).await?;
+                this.update(&mut cx, |this, cx| {
+                    let Some(thread) = this.threads.get_mut(&root_message_id) else {
+                        return;
+                    };
+                    let earlier_replies = replies
+                        .into_iter()
+                        .skip(1)
+                        .filter(|reply| !thread.replies.iter().any(|loaded| loaded.id == reply.id))
+                        .collect::<Vec<_>>();
+                    thread.replies.splice(0..0, earlier_replies);
+                    thread.loaded_all_replies = response.done;
+                    cx.emit(ChannelChatEvent::ThreadUpdated { root_message_id });
+                    cx.notify();
+                })
+            }
+            .log_err()
+        }))
+    }
+
+    pub fn close_thread(&mut self, message_id: u64) {
+        self.threads.remove(&message_id);
+    }
+
+    pub fn thread(&self, message_id: u64) -> Option<&ChannelThread> {
+        self.threads.get(&message_id)
+    }
+
     pub fn first_loaded_message_id(&mut self) -> Option<u64> {
         self.first_loaded_message_id
     }
@@ -477,10 +644,18 @@ impl ChannelChat {
                     )
                     .await?;
                     this.update(&mut cx, |this, cx| {
-                        this.insert_messages(SumTree::from_item(message, &()), cx);
+                        this.insert_new_message(message, cx);
                     })?;
                 }
 
+                let thread_ids = this.update(&mut cx, |this, _| {
+                    this.threads.keys().copied().collect::<Vec<_>>()
+                })?;
+                for thread_id in thread_ids {
+                    this.update(&mut cx, |this, cx| this.open_thread(thread_id, cx))?
+                        .await?;
+                }
+
                 anyhow::Ok(())
             }
             .log_err()
@@ -539,7 +714,7 @@ impl ChannelChat {
 
         let message = ChannelMessage::from_proto(message, &user_store, &mut cx).await?;
         this.update(&mut cx, |this, cx| {
-            this.insert_messages(SumTree::from_item(message, &()), cx);
+            this.insert_new_message(message, cx);
             cx.emit(ChannelChatEvent::NewMessage {
                 channel_id: this.channel_id,
                 message_id,
@@ -585,6 +760,39 @@ impl ChannelChat {
         Ok(())
     }
 
+    async fn handle_message_reactions_updated(
+        this: Model<Self>,
+        message: TypedEnvelope<proto::ChannelMessageReactionsUpdated>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            this.reactions_updated(message.payload.message_id, message.payload.reactions, cx)
+        })?;
+        Ok(())
+    }
+
+    /// Inserts a message that was just sent, counting it as a reply to the
+    /// message it replies to.
+    fn insert_new_message(&mut self, message: ChannelMessage, cx: &mut ModelContext<Self>) {
+        let is_new = match message.id {
+            ChannelMessageId::Saved(id) => self.find_loaded_message(id).is_none(),
+            ChannelMessageId::Pending(_) => true,
+        };
+        if let Some(root_id) = message.reply_to_message_id.filter(|_| is_new) {
+            if let Some(thread) = self.threads.get_mut(&root_id) {
+                if !thread.replies.iter().any(|reply| reply.id == message.id) {
+                    thread.replies.push(message.clone());
+                }
+            }
+            self.modify_message(
+                ChannelMessageId::Saved(root_id),
+                |root| root.reply_count += 1,
+                cx,
+            );
+        }
+        self.insert_messages(SumTree::from_item(message, &()), cx);
+    }
+
     fn insert_messages(&mut self, messages: SumTree<ChannelMessage>, cx: &mut ModelContext<Self>) {
         if let Some((first_message, last_message)) = messages.first().zip(messages.last()) {
             let nonces = messages
@@ -645,10 +853,12 @@ impl ChannelChat {
     }
 
     fn message_removed(&mut self, id: u64, cx: &mut ModelContext<Self>) {
+        let mut reply_to_message_id = None;
         let mut cursor = self.messages.cursor::<ChannelMessageId>();
         let mut messages = cursor.slice(&ChannelMessageId::Saved(id), Bias::Left, &());
         if let Some(item) = cursor.item() {
             if item.id == ChannelMessageId::Saved(id) {
+                reply_to_message_id = item.reply_to_message_id;
                 let deleted_message_ix = messages.summary().count;
                 cursor.next(&());
                 messages.append(cursor.suffix(&()), &());
@@ -674,6 +884,42 @@ impl ChannelChat {
                 });
             }
         }
+
+        if self.threads.remove(&id).is_some() {
+            cx.emit(ChannelChatEvent::ThreadUpdated {
+                root_message_id: id,
+            });
+        }
+        for (root_id, thread) in &mut self.threads {
+            let reply_count = thread.replies.len();
+            thread
+                .replies
+                .retain(|reply| reply.id != ChannelMessageId::Saved(id));
+            if thread.replies.len() < reply_count {
+                reply_to_message_id = Some(*root_id);
+            }
+        }
+        if let Some(root_id) = reply_to_message_id {
+            self.modify_message(
+                ChannelMessageId::Saved(root_id),
+                |root| root.reply_count = root.reply_count.saturating_sub(1),
+                cx,
+            );
+        }
+    }
+
+    fn reactions_updated(
+        &mut self,
+        message_id: u64,
+        reactions: Vec<proto::ChatReaction>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let reactions = reactions_from_proto(reactions);
+        self.modify_message(
+            ChannelMessageId::Saved(message_id),
+            |message| message.reactions = reactions.clone(),
+            cx,
+        );
     }
 
     fn message_update(
@@ -683,27 +929,62 @@ impl ChannelChat {
         mentions: Vec<(Range<usize>, u64)>,
         edited_at: Option<OffsetDateTime>,
         cx: &mut ModelContext<Self>,
+    ) {
+        self.modify_message(
+            id,
+            |message| {
+                message.body = body.clone();
+                message.mentions = mentions.clone();
+                message.edited_at = edited_at;
+            },
+            cx,
+        );
+    }
+
+    /// Applies `update` to the message with the given id, both in the chat's
+    /// history and in any open threads that contain it.
+    fn modify_message(
+        &mut self,
+        id: ChannelMessageId,
+        mut update: impl FnMut(&mut ChannelMessage),
+        cx: &mut ModelContext<Self>,
     ) {
         let mut cursor = self.messages.cursor::<ChannelMessageId>();
         let mut messages = cursor.slice(&id, Bias::Left, &());
         let ix = messages.summary().count;
+        let mut found = false;
 
-        if let Some(mut message_to_update) = cursor.item().cloned() {
-            message_to_update.body = body;
-            message_to_update.mentions = mentions;
-            message_to_update.edited_at = edited_at;
+        if let Some(mut message_to_update) = cursor.item().filter(|m| m.id == id).cloned() {
+            update(&mut message_to_update);
             messages.push(message_to_update, &());
             cursor.next(&());
+            found = true;
         }
 
         messages.append(cursor.suffix(&()), &());
         drop(cursor);
         self.messages = messages;
 
-        cx.emit(ChannelChatEvent::UpdateMessage {
-            message_ix: ix,
-            message_id: id,
-        });
+        if found {
+            cx.emit(ChannelChatEvent::UpdateMessage {
+                message_ix: ix,
+                message_id: id,
+            });
+        }
+
+        for (root_id, thread) in &mut self.threads {
+            let message = if thread.root.id == id {
+                Some(&mut thread.root)
+            } else {
+                thread.replies.iter_mut().find(|reply| reply.id == id)
+            };
+            if let Some(message) = message {
+                update(message);
+                cx.emit(ChannelChatEvent::ThreadUpdated {
+                    root_message_id: *root_id,
+                });
+            }
+        }
 
         cx.notify();
     }
@@ -759,6 +1040,8 @@ impl ChannelMessage {
                 .into(),
             reply_to_message_id: message.reply_to_message_id,
             edited_at,
+            reactions: reactions_from_proto(message.reactions),
+            reply_count: message.reply_count as usize,
         })
     }
 
@@ -804,6 +1087,16 @@ pub fn mentions_to_proto(mentions: &[(Range<usize>, UserId)]) -> Vec<proto::Chat
         .collect()
 }
 
+fn reactions_from_proto(reactions: Vec<proto::ChatReaction>) -> Vec<ChannelMessageReaction> {
+    reactions
+        .into_iter()
+        .map(|reaction| ChannelMessageReaction {
+            emoji: reaction.emoji,
+            user_ids: reaction.user_ids,
+        })
+        .collect()
+}
+
 impl sum_tree::Item for ChannelMessage {
     type Summary = ChannelMessageSummary;
 
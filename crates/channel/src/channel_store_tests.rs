@@ -187,6 +187,8 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     nonce: Some(1.into()),
                     reply_to_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
+                    reply_count: 0,
                 },
                 proto::ChannelMessage {
                     id: 11,
@@ -197,6 +199,8 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     nonce: Some(2.into()),
                     reply_to_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
+                    reply_count: 0,
                 },
             ],
             done: false,
@@ -246,6 +250,8 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
             nonce: Some(3.into()),
             reply_to_message_id: None,
             edited_at: None,
+            reactions: vec![],
+            reply_count: 0,
         }),
     });
 
@@ -301,6 +307,8 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     mentions: vec![],
                     reply_to_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
+                    reply_count: 0,
                 },
                 proto::ChannelMessage {
                     id: 9,
@@ -311,6 +319,8 @@ async fn test_channel_messages(cx: &mut TestAppContext) {
                     mentions: vec![],
                     reply_to_message_id: None,
                     edited_at: None,
+                    reactions: vec![],
+                    reply_count: 0,
                 },
             ],
         },
@@ -226,6 +226,7 @@ CREATE TABLE IF NOT EXISTS "channel_messages" (
 );
 CREATE INDEX "index_channel_messages_on_channel_id" ON "channel_messages" ("channel_id");
 CREATE UNIQUE INDEX "index_channel_messages_on_sender_id_nonce" ON "channel_messages" ("sender_id", "nonce");
+CREATE INDEX "index_channel_messages_on_reply_to_message_id" ON "channel_messages" ("reply_to_message_id");
 
 CREATE TABLE "channel_message_mentions" (
     "message_id" INTEGER NOT NULL REFERENCES channel_messages (id) ON DELETE CASCADE,
@@ -235,6 +236,14 @@ CREATE TABLE "channel_message_mentions" (
     PRIMARY KEY(message_id, start_offset)
 );
 
+CREATE TABLE "channel_message_reactions" (
+    "message_id" INTEGER NOT NULL REFERENCES channel_messages (id) ON DELETE CASCADE,
+    "user_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "emoji" VARCHAR NOT NULL,
+    "created_at" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY(message_id, user_id, emoji)
+);
+
 CREATE TABLE "channel_members" (
     "id" INTEGER PRIMARY KEY AUTOINCREMENT,
     "channel_id" INTEGER NOT NULL REFERENCES channels (id) ON DELETE CASCADE,
@@ -416,3 +425,24 @@ CREATE TABLE dev_server_projects (
     dev_server_id INTEGER NOT NULL REFERENCES dev_servers(id),
     path TEXT NOT NULL
 );
+
+CREATE TABLE review_threads (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    host_user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    worktree_root_path TEXT NOT NULL,
+    path TEXT NOT NULL,
+    anchor_range BLOB NOT NULL,
+    start_row INTEGER NOT NULL,
+    end_row INTEGER NOT NULL,
+    resolved BOOLEAN NOT NULL DEFAULT FALSE
+);
+CREATE INDEX idx_review_threads_on_host_user_id_and_worktree_root_path ON review_threads (host_user_id, worktree_root_path);
+
+CREATE TABLE review_comments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    thread_id INTEGER NOT NULL REFERENCES review_threads(id) ON DELETE CASCADE,
+    sender_id INTEGER NOT NULL REFERENCES users(id),
+    body TEXT NOT NULL,
+    sent_at TIMESTAMP NOT NULL
+);
+CREATE INDEX idx_review_comments_on_thread_id ON review_comments (thread_id);
@@ -0,0 +1,20 @@
+CREATE TABLE review_threads (
+    id INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    host_user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    worktree_root_path TEXT NOT NULL,
+    path TEXT NOT NULL,
+    anchor_range BYTEA NOT NULL,
+    start_row INTEGER NOT NULL,
+    end_row INTEGER NOT NULL,
+    resolved BOOLEAN NOT NULL DEFAULT FALSE
+);
+CREATE INDEX idx_review_threads_on_host_user_id_and_worktree_root_path ON review_threads (host_user_id, worktree_root_path);
+
+CREATE TABLE review_comments (
+    id INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    thread_id INTEGER NOT NULL REFERENCES review_threads(id) ON DELETE CASCADE,
+    sender_id INTEGER NOT NULL REFERENCES users(id),
+    body TEXT NOT NULL,
+    sent_at TIMESTAMP WITHOUT TIME ZONE NOT NULL
+);
+CREATE INDEX idx_review_comments_on_thread_id ON review_comments (thread_id);
@@ -0,0 +1,10 @@
+CREATE TABLE "channel_message_reactions" (
+    "message_id" INTEGER NOT NULL REFERENCES channel_messages (id) ON DELETE CASCADE,
+    "user_id" INTEGER NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    "emoji" VARCHAR NOT NULL,
+    "created_at" TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY(message_id, user_id, emoji)
+);
+
+CREATE INDEX "index_channel_messages_on_reply_to_message_id" ON "channel_messages" ("reply_to_message_id");
+CREATE INDEX "index_channel_messages_on_body_search" ON "channel_messages" USING GIN (to_tsvector('english', "body"));
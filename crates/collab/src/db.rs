@@ -523,6 +523,11 @@ pub struct UpdatedChannelMessage {
     pub updated_mention_notifications: Vec<rpc::proto::Notification>,
 }
 
+pub struct UpdatedChannelMessageReactions {
+    pub reactions: Vec<rpc::proto::ChatReaction>,
+    pub participant_connection_ids: Vec<ConnectionId>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, FromQueryResult, Serialize, Deserialize)]
 pub struct Invite {
     pub email_address: String,
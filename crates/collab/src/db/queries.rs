@@ -14,6 +14,7 @@ pub mod messages;
 pub mod notifications;
 pub mod projects;
 pub mod rate_buckets;
+pub mod review_threads;
 pub mod rooms;
 pub mod servers;
 pub mod users;
@@ -166,6 +166,8 @@ impl Database {
                     edited_at: row
                         .edited_at
                         .map(|t| t.assume_utc().unix_timestamp() as u64),
+                    reactions: Vec::new(),
+                    reply_count: 0,
                 }
             })
             .collect::<Vec<_>>();
@@ -199,10 +201,222 @@ impl Database {
                 }
             }
         }
+        drop(mentions);
+
+        let mut reactions_by_message_id = HashMap::<u64, Vec<_>>::default();
+        let mut reactions = channel_message_reaction::Entity::find()
+            .filter(
+                channel_message_reaction::Column::MessageId.is_in(messages.iter().map(|m| m.id)),
+            )
+            .order_by_asc(channel_message_reaction::Column::CreatedAt)
+            .stream(tx)
+            .await?;
+        while let Some(reaction) = reactions.next().await {
+            let reaction = reaction?;
+            reactions_by_message_id
+                .entry(reaction.message_id.to_proto())
+                .or_default()
+                .push(reaction);
+        }
+        drop(reactions);
+
+        #[derive(Copy, Clone, Debug, EnumIter, DeriveColumn)]
+        enum QueryReplyCounts {
+            ReplyToMessageId,
+            ReplyCount,
+        }
+
+        let mut reply_counts = HashMap::<u64, i64>::default();
+        let mut rows = channel_message::Entity::find()
+            .select_only()
+            .column(channel_message::Column::ReplyToMessageId)
+            .column_as(
+                Expr::col(channel_message::Column::Id).count(),
+                QueryReplyCounts::ReplyCount,
+            )
+            .filter(channel_message::Column::ReplyToMessageId.is_in(messages.iter().map(|m| m.id)))
+            .group_by(channel_message::Column::ReplyToMessageId)
+            .into_values::<_, QueryReplyCounts>()
+            .stream(tx)
+            .await?;
+        while let Some(row) = rows.next().await {
+            let (message_id, count): (MessageId, i64) = row?;
+            reply_counts.insert(message_id.to_proto(), count);
+        }
+        drop(rows);
+
+        for message in &mut messages {
+            if let Some(reactions) = reactions_by_message_id.remove(&message.id) {
+                message.reactions = reactions_to_proto(reactions);
+            }
+            if let Some(count) = reply_counts.get(&message.id) {
+                message.reply_count = *count as u32;
+            }
+        }
 
         Ok(messages)
     }
 
+    /// Returns a message followed by the most recent of its replies, oldest first.
+    ///
+    /// Use `before_message_id` to paginate through the replies.
+    pub async fn get_channel_message_thread(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+        message_id: MessageId,
+        count: usize,
+        before_message_id: Option<MessageId>,
+    ) -> Result<Vec<proto::ChannelMessage>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let root = channel_message::Entity::find_by_id(message_id)
+                .filter(channel_message::Column::ChannelId.eq(channel_id))
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such message"))?;
+
+            let mut condition = Condition::all()
+                .add(channel_message::Column::ChannelId.eq(channel_id))
+                .add(channel_message::Column::ReplyToMessageId.eq(message_id));
+            if let Some(before_message_id) = before_message_id {
+                condition = condition.add(channel_message::Column::Id.lt(before_message_id));
+            }
+
+            let mut rows = channel_message::Entity::find()
+                .filter(condition)
+                .order_by_desc(channel_message::Column::Id)
+                .limit(count as u64)
+                .all(&*tx)
+                .await?;
+            rows.push(root);
+
+            self.load_channel_messages(rows, &tx).await
+        })
+        .await
+    }
+
+    /// Searches the text of the specified channel's messages, returning the most recent matches.
+    ///
+    /// Use `before_message_id` to paginate through the matches.
+    pub async fn search_channel_messages(
+        &self,
+        channel_id: ChannelId,
+        user_id: UserId,
+        query: &str,
+        count: usize,
+        before_message_id: Option<MessageId>,
+    ) -> Result<Vec<proto::ChannelMessage>> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let mut condition =
+                Condition::all().add(channel_message::Column::ChannelId.eq(channel_id));
+            if let Some(before_message_id) = before_message_id {
+                condition = condition.add(channel_message::Column::Id.lt(before_message_id));
+            }
+            if cfg!(any(test, feature = "sqlite"))
+                && self.pool.get_database_backend() == DbBackend::Sqlite
+            {
+                condition = condition.add(Expr::cust_with_values(
+                    "UPPER(body) LIKE ?",
+                    [format!("%{}%", query.to_uppercase())],
+                ));
+            } else {
+                condition = condition.add(Expr::cust_with_values(
+                    "to_tsvector('english', body) @@ websearch_to_tsquery('english', $1)",
+                    [query.to_string()],
+                ));
+            }
+
+            let rows = channel_message::Entity::find()
+                .filter(condition)
+                .order_by_desc(channel_message::Column::Id)
+                .limit(count as u64)
+                .all(&*tx)
+                .await?;
+
+            self.load_channel_messages(rows, &tx).await
+        })
+        .await
+    }
+
+    /// Adds the user's reaction to a channel message, or removes it if they've already reacted
+    /// with the same emoji.
+    pub async fn toggle_channel_message_reaction(
+        &self,
+        channel_id: ChannelId,
+        message_id: MessageId,
+        user_id: UserId,
+        emoji: &str,
+    ) -> Result<UpdatedChannelMessageReactions> {
+        self.transaction(|tx| async move {
+            let channel = self.get_channel_internal(channel_id, &tx).await?;
+            self.check_user_is_channel_participant(&channel, user_id, &tx)
+                .await?;
+
+            let mut rows = channel_chat_participant::Entity::find()
+                .filter(channel_chat_participant::Column::ChannelId.eq(channel_id))
+                .stream(&*tx)
+                .await?;
+
+            let mut is_participant = false;
+            let mut participant_connection_ids = Vec::new();
+            while let Some(row) = rows.next().await {
+                let row = row?;
+                if row.user_id == user_id {
+                    is_participant = true;
+                }
+                participant_connection_ids.push(row.connection());
+            }
+            drop(rows);
+
+            if !is_participant {
+                Err(anyhow!("not a chat participant"))?;
+            }
+
+            channel_message::Entity::find_by_id(message_id)
+                .filter(channel_message::Column::ChannelId.eq(channel_id))
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such message"))?;
+
+            let result = channel_message_reaction::Entity::delete_many()
+                .filter(channel_message_reaction::Column::MessageId.eq(message_id))
+                .filter(channel_message_reaction::Column::UserId.eq(user_id))
+                .filter(channel_message_reaction::Column::Emoji.eq(emoji))
+                .exec(&*tx)
+                .await?;
+            if result.rows_affected == 0 {
+                channel_message_reaction::Entity::insert(channel_message_reaction::ActiveModel {
+                    message_id: ActiveValue::Set(message_id),
+                    user_id: ActiveValue::Set(user_id),
+                    emoji: ActiveValue::Set(emoji.to_string()),
+                    created_at: ActiveValue::NotSet,
+                })
+                .exec_without_returning(&*tx)
+                .await?;
+            }
+
+            let reactions = channel_message_reaction::Entity::find()
+                .filter(channel_message_reaction::Column::MessageId.eq(message_id))
+                .order_by_asc(channel_message_reaction::Column::CreatedAt)
+                .all(&*tx)
+                .await?;
+
+            Ok(UpdatedChannelMessageReactions {
+                reactions: reactions_to_proto(reactions),
+                participant_connection_ids,
+            })
+        })
+        .await
+    }
+
     fn format_mentions_to_entities(
         &self,
         message_id: MessageId,
@@ -723,3 +937,19 @@ impl Database {
         .await
     }
 }
+
+/// Groups a message's reactions by emoji, in the order that each emoji was first used.
+fn reactions_to_proto(reactions: Vec<channel_message_reaction::Model>) -> Vec<proto::ChatReaction> {
+    let mut result = Vec::<proto::ChatReaction>::new();
+    for reaction in reactions {
+        let user_id = reaction.user_id.to_proto();
+        match result.iter_mut().find(|r| r.emoji == reaction.emoji) {
+            Some(existing) => existing.user_ids.push(user_id),
+            None => result.push(proto::ChatReaction {
+                emoji: reaction.emoji,
+                user_ids: vec![user_id],
+            }),
+        }
+    }
+    result
+}
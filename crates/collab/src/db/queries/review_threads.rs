@@ -0,0 +1,327 @@
+use super::*;
+use prost::Message;
+use time::OffsetDateTime;
+
+impl Database {
+    /// Returns all of the review threads in the given project.
+    pub async fn get_review_threads(
+        &self,
+        project_id: ProjectId,
+        connection_id: ConnectionId,
+        user_id: UserId,
+    ) -> Result<Vec<proto::ReviewThread>> {
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(
+                    project_id,
+                    connection_id,
+                    PrincipalId::UserId(user_id),
+                    Capability::ReadOnly,
+                    &tx,
+                )
+                .await?;
+            let host_user_id = self.review_thread_host_user_id(&project, &tx).await?;
+            let worktree_ids_by_root_path = worktree::Entity::find()
+                .filter(worktree::Column::ProjectId.eq(project_id))
+                .all(&*tx)
+                .await?
+                .into_iter()
+                .map(|worktree| (worktree.abs_path, worktree.id as u64))
+                .collect::<HashMap<_, _>>();
+
+            let threads = review_thread::Entity::find()
+                .filter(review_thread::Column::HostUserId.eq(host_user_id))
+                .filter(
+                    review_thread::Column::WorktreeRootPath
+                        .is_in(worktree_ids_by_root_path.keys().cloned()),
+                )
+                .order_by_asc(review_thread::Column::Id)
+                .all(&*tx)
+                .await?;
+            let mut comments_by_thread_id = HashMap::<ReviewThreadId, Vec<_>>::default();
+            let mut comments = review_comment::Entity::find()
+                .filter(
+                    review_comment::Column::ThreadId.is_in(threads.iter().map(|thread| thread.id)),
+                )
+                .order_by_asc(review_comment::Column::Id)
+                .stream(&*tx)
+                .await?;
+            while let Some(comment) = comments.next().await {
+                let comment = comment?;
+                comments_by_thread_id
+                    .entry(comment.thread_id)
+                    .or_default()
+                    .push(comment);
+            }
+
+            threads
+                .into_iter()
+                .map(|thread| {
+                    let worktree_id = worktree_ids_by_root_path[&thread.worktree_root_path];
+                    let comments = comments_by_thread_id.remove(&thread.id).unwrap_or_default();
+                    review_thread_to_proto(thread, worktree_id, comments)
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .await
+        .map(|guard| guard.into_inner())
+    }
+
+    /// Starts a review thread on a range of a project buffer. Returns the new thread,
+    /// along with the connections that should be notified about it.
+    pub async fn create_review_thread(
+        &self,
+        request: &proto::CreateReviewThread,
+        body: &str,
+        timestamp: OffsetDateTime,
+        connection_id: ConnectionId,
+        user_id: UserId,
+    ) -> Result<TransactionGuard<(proto::ReviewThread, Vec<ConnectionId>)>> {
+        let project_id = ProjectId::from_proto(request.project_id);
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(
+                    project_id,
+                    connection_id,
+                    PrincipalId::UserId(user_id),
+                    Capability::ReadOnly,
+                    &tx,
+                )
+                .await?;
+            let range = request
+                .range
+                .as_ref()
+                .ok_or_else(|| anyhow!("invalid range"))?;
+            let host_user_id = self.review_thread_host_user_id(&project, &tx).await?;
+            let worktree = worktree::Entity::find()
+                .filter(worktree::Column::ProjectId.eq(project_id))
+                .filter(worktree::Column::Id.eq(request.worktree_id as i64))
+                .one(&*tx)
+                .await?
+                .ok_or_else(|| anyhow!("no such worktree"))?;
+
+            let thread = review_thread::ActiveModel {
+                id: ActiveValue::NotSet,
+                host_user_id: ActiveValue::Set(host_user_id),
+                worktree_root_path: ActiveValue::Set(worktree.abs_path),
+                path: ActiveValue::Set(request.path.clone()),
+                anchor_range: ActiveValue::Set(range.encode_to_vec()),
+                start_row: ActiveValue::Set(request.start_row as i32),
+                end_row: ActiveValue::Set(request.end_row.max(request.start_row) as i32),
+                resolved: ActiveValue::Set(false),
+            }
+            .insert(&*tx)
+            .await?;
+            let comment = self
+                .insert_review_comment(thread.id, user_id, body, timestamp, &tx)
+                .await?;
+
+            let connection_ids = self
+                .review_thread_connection_ids(&project, connection_id, &tx)
+                .await?;
+            Ok((
+                review_thread_to_proto(thread, worktree.id as u64, vec![comment])?,
+                connection_ids,
+            ))
+        })
+        .await
+    }
+
+    /// Replies to a review thread. Returns the updated thread, along with the
+    /// connections that should be notified about it.
+    pub async fn add_review_comment(
+        &self,
+        project_id: ProjectId,
+        thread_id: ReviewThreadId,
+        body: &str,
+        timestamp: OffsetDateTime,
+        connection_id: ConnectionId,
+        user_id: UserId,
+    ) -> Result<TransactionGuard<(proto::ReviewThread, Vec<ConnectionId>)>> {
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(
+                    project_id,
+                    connection_id,
+                    PrincipalId::UserId(user_id),
+                    Capability::ReadOnly,
+                    &tx,
+                )
+                .await?;
+            let (thread, worktree_id) = self
+                .get_review_thread_internal(&project, thread_id, &tx)
+                .await?;
+            self.insert_review_comment(thread.id, user_id, body, timestamp, &tx)
+                .await?;
+
+            let thread = self
+                .review_thread_with_comments(thread, worktree_id, &tx)
+                .await?;
+            let connection_ids = self
+                .review_thread_connection_ids(&project, connection_id, &tx)
+                .await?;
+            Ok((thread, connection_ids))
+        })
+        .await
+    }
+
+    /// Marks a review thread as resolved, or reopens it. Returns the updated thread,
+    /// along with the connections that should be notified about it.
+    pub async fn set_review_thread_resolved(
+        &self,
+        project_id: ProjectId,
+        thread_id: ReviewThreadId,
+        resolved: bool,
+        connection_id: ConnectionId,
+        user_id: UserId,
+    ) -> Result<TransactionGuard<(proto::ReviewThread, Vec<ConnectionId>)>> {
+        self.project_transaction(project_id, |tx| async move {
+            let (project, _) = self
+                .access_project(
+                    project_id,
+                    connection_id,
+                    PrincipalId::UserId(user_id),
+                    Capability::ReadOnly,
+                    &tx,
+                )
+                .await?;
+            let (thread, worktree_id) = self
+                .get_review_thread_internal(&project, thread_id, &tx)
+                .await?;
+            let thread = review_thread::ActiveModel {
+                resolved: ActiveValue::Set(resolved),
+                ..thread.into_active_model()
+            }
+            .update(&*tx)
+            .await?;
+
+            let thread = self
+                .review_thread_with_comments(thread, worktree_id, &tx)
+                .await?;
+            let connection_ids = self
+                .review_thread_connection_ids(&project, connection_id, &tx)
+                .await?;
+            Ok((thread, connection_ids))
+        })
+        .await
+    }
+
+    /// Returns the review thread with the given id, along with the id of the worktree
+    /// containing it in the given project, if the project shows that thread.
+    async fn get_review_thread_internal(
+        &self,
+        project: &project::Model,
+        thread_id: ReviewThreadId,
+        tx: &DatabaseTransaction,
+    ) -> Result<(review_thread::Model, u64)> {
+        let thread = review_thread::Entity::find_by_id(thread_id)
+            .one(tx)
+            .await?
+            .ok_or_else(|| anyhow!("no such review thread"))?;
+        if thread.host_user_id != self.review_thread_host_user_id(project, tx).await? {
+            return Err(anyhow!("no such review thread"))?;
+        }
+        let worktree = worktree::Entity::find()
+            .filter(worktree::Column::ProjectId.eq(project.id))
+            .filter(worktree::Column::AbsPath.eq(thread.worktree_root_path.as_str()))
+            .one(tx)
+            .await?
+            .ok_or_else(|| anyhow!("no such review thread"))?;
+        Ok((thread, worktree.id as u64))
+    }
+
+    /// Returns the user whose review threads are shown in the given project: its host, or
+    /// the owner of the dev server hosting it.
+    async fn review_thread_host_user_id(
+        &self,
+        project: &project::Model,
+        tx: &DatabaseTransaction,
+    ) -> Result<UserId> {
+        if let Some(host_user_id) = project.host_user_id {
+            return Ok(host_user_id);
+        }
+
+        let dev_server_project_id = project
+            .dev_server_project_id
+            .ok_or_else(|| anyhow!("project has no host"))?;
+        self.owner_for_dev_server_project(dev_server_project_id, tx)
+            .await
+    }
+
+    async fn insert_review_comment(
+        &self,
+        thread_id: ReviewThreadId,
+        user_id: UserId,
+        body: &str,
+        timestamp: OffsetDateTime,
+        tx: &DatabaseTransaction,
+    ) -> Result<review_comment::Model> {
+        let timestamp = time::PrimitiveDateTime::new(timestamp.date(), timestamp.time());
+        Ok(review_comment::ActiveModel {
+            id: ActiveValue::NotSet,
+            thread_id: ActiveValue::Set(thread_id),
+            sender_id: ActiveValue::Set(user_id),
+            body: ActiveValue::Set(body.to_string()),
+            sent_at: ActiveValue::Set(timestamp),
+        }
+        .insert(tx)
+        .await?)
+    }
+
+    async fn review_thread_with_comments(
+        &self,
+        thread: review_thread::Model,
+        worktree_id: u64,
+        tx: &DatabaseTransaction,
+    ) -> Result<proto::ReviewThread> {
+        let comments = review_comment::Entity::find()
+            .filter(review_comment::Column::ThreadId.eq(thread.id))
+            .order_by_asc(review_comment::Column::Id)
+            .all(tx)
+            .await?;
+        review_thread_to_proto(thread, worktree_id, comments)
+    }
+
+    /// Returns the connections in the project other than `connection_id`. Dev servers
+    /// don't display review threads, so they're excluded.
+    async fn review_thread_connection_ids(
+        &self,
+        project: &project::Model,
+        connection_id: ConnectionId,
+        tx: &DatabaseTransaction,
+    ) -> Result<Vec<ConnectionId>> {
+        let mut connection_ids = self.project_guest_connection_ids(project.id, tx).await?;
+        if project.dev_server_project_id.is_none() {
+            connection_ids.extend(project.host_connection().ok());
+        }
+        connection_ids.retain(|id| *id != connection_id);
+        Ok(connection_ids)
+    }
+}
+
+fn review_thread_to_proto(
+    thread: review_thread::Model,
+    worktree_id: u64,
+    comments: Vec<review_comment::Model>,
+) -> Result<proto::ReviewThread> {
+    let range = proto::AnchorRange::decode(thread.anchor_range.as_slice())
+        .map_err(|error| anyhow!("{}", error))?;
+    Ok(proto::ReviewThread {
+        id: thread.id.to_proto(),
+        worktree_id,
+        path: thread.path,
+        range: Some(range),
+        start_row: thread.start_row as u32,
+        end_row: thread.end_row as u32,
+        resolved: thread.resolved,
+        comments: comments
+            .into_iter()
+            .map(|comment| proto::ReviewComment {
+                id: comment.id.to_proto(),
+                sender_id: comment.sender_id.to_proto(),
+                body: comment.body,
+                timestamp: comment.sent_at.assume_utc().unix_timestamp() as u64,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,46 @@
+use crate::db::{ReviewThreadId, UserId};
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "review_threads")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: ReviewThreadId,
+    /// The user hosting the projects the thread is shown in. Threads outlive the projects
+    /// they were started in, so they're shown again when the host shares the same worktree.
+    pub host_user_id: UserId,
+    /// The absolute path of the worktree containing the thread's file, on the host.
+    pub worktree_root_path: String,
+    pub path: String,
+    /// The thread's range, as an encoded `proto::AnchorRange`.
+    pub anchor_range: Vec<u8>,
+    pub start_row: i32,
+    pub end_row: i32,
+    pub resolved: bool,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::user::Entity",
+        from = "Column::HostUserId",
+        to = "super::user::Column::Id"
+    )]
+    HostUser,
+    #[sea_orm(has_many = "super::review_comment::Entity")]
+    Comments,
+}
+
+impl Related<super::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::HostUser.def()
+    }
+}
+
+impl Related<super::review_comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comments.def()
+    }
+}
@@ -8,6 +8,7 @@ mod embedding_tests;
 mod extension_tests;
 mod feature_flag_tests;
 mod message_tests;
+mod review_thread_tests;
 
 use super::*;
 use gpui::BackgroundExecutor;
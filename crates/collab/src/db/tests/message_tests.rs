@@ -4,6 +4,7 @@ use crate::{
     test_both_dbs,
 };
 use channel::mentions_to_proto;
+use rpc::proto;
 use std::sync::Arc;
 use time::OffsetDateTime;
 
@@ -419,3 +420,270 @@ async fn test_channel_message_mentions(db: &Arc<Database>) {
         ]
     );
 }
+
+test_both_dbs!(
+    test_channel_message_reactions,
+    test_channel_message_reactions_postgres,
+    test_channel_message_reactions_sqlite
+);
+
+async fn test_channel_message_reactions(db: &Arc<Database>) {
+    let user_a = new_test_user(db, "user_a@example.com").await;
+    let user_b = new_test_user(db, "user_b@example.com").await;
+    let user_c = new_test_user(db, "user_c@example.com").await;
+    let channel = db.create_root_channel("channel", user_a).await.unwrap();
+    db.invite_channel_member(channel, user_b, user_a, ChannelRole::Member)
+        .await
+        .unwrap();
+    db.respond_to_channel_invite(channel, user_b, true)
+        .await
+        .unwrap();
+
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    db.join_channel_chat(channel, rpc::ConnectionId { owner_id, id: 0 }, user_a)
+        .await
+        .unwrap();
+    db.join_channel_chat(channel, rpc::ConnectionId { owner_id, id: 1 }, user_b)
+        .await
+        .unwrap();
+
+    let message_id = db
+        .create_channel_message(
+            channel,
+            user_a,
+            "shipped it",
+            &[],
+            OffsetDateTime::now_utc(),
+            1,
+            None,
+        )
+        .await
+        .unwrap()
+        .message_id;
+
+    let updated = db
+        .toggle_channel_message_reaction(channel, message_id, user_a, "🎉")
+        .await
+        .unwrap();
+    assert_eq!(updated.participant_connection_ids.len(), 2);
+    let updated = db
+        .toggle_channel_message_reaction(channel, message_id, user_b, "🎉")
+        .await
+        .unwrap();
+    assert_eq!(
+        updated.reactions,
+        &[proto::ChatReaction {
+            emoji: "🎉".into(),
+            user_ids: vec![user_a.to_proto(), user_b.to_proto()],
+        }]
+    );
+    db.toggle_channel_message_reaction(channel, message_id, user_b, "👀")
+        .await
+        .unwrap();
+
+    // Reacting with the same emoji again takes the reaction back.
+    let updated = db
+        .toggle_channel_message_reaction(channel, message_id, user_a, "🎉")
+        .await
+        .unwrap();
+    let mut reactions = updated.reactions;
+    reactions.sort_by(|a, b| a.emoji.cmp(&b.emoji));
+    assert_eq!(
+        reactions,
+        &[
+            proto::ChatReaction {
+                emoji: "👀".into(),
+                user_ids: vec![user_b.to_proto()],
+            },
+            proto::ChatReaction {
+                emoji: "🎉".into(),
+                user_ids: vec![user_b.to_proto()],
+            },
+        ]
+    );
+
+    let mut reactions = db
+        .get_channel_messages(channel, user_a, 1, None)
+        .await
+        .unwrap()
+        .remove(0)
+        .reactions;
+    reactions.sort_by(|a, b| a.emoji.cmp(&b.emoji));
+    assert_eq!(reactions.len(), 2);
+    assert_eq!(reactions[1].user_ids, &[user_b.to_proto()]);
+
+    // Users outside of the channel can't react.
+    db.toggle_channel_message_reaction(channel, message_id, user_c, "🎉")
+        .await
+        .unwrap_err();
+}
+
+test_both_dbs!(
+    test_channel_message_threads,
+    test_channel_message_threads_postgres,
+    test_channel_message_threads_sqlite
+);
+
+async fn test_channel_message_threads(db: &Arc<Database>) {
+    let user = new_test_user(db, "user@example.com").await;
+    let channel = db.create_root_channel("channel", user).await.unwrap();
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    db.join_channel_chat(channel, rpc::ConnectionId { owner_id, id: 0 }, user)
+        .await
+        .unwrap();
+
+    let send = |body: &'static str, nonce: u128, reply_to_message_id: Option<MessageId>| async move {
+        db.create_channel_message(
+            channel,
+            user,
+            body,
+            &[],
+            OffsetDateTime::now_utc(),
+            nonce,
+            reply_to_message_id,
+        )
+        .await
+        .unwrap()
+        .message_id
+    };
+    let root = send("what should we name it?", 1, None).await;
+    let other = send("unrelated", 2, None).await;
+    let reply_1 = send("zed", 3, Some(root)).await;
+    send("nested", 4, Some(reply_1)).await;
+    let reply_2 = send("zed!", 5, Some(root)).await;
+
+    let thread = db
+        .get_channel_message_thread(channel, user, root, 10, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        thread
+            .iter()
+            .map(|message| (message.id, message.reply_count))
+            .collect::<Vec<_>>(),
+        &[
+            (root.to_proto(), 2),
+            (reply_1.to_proto(), 1),
+            (reply_2.to_proto(), 0)
+        ]
+    );
+
+    let messages = db
+        .get_channel_messages(channel, user, 5, None)
+        .await
+        .unwrap();
+    assert_eq!(messages[1].id, other.to_proto());
+    assert_eq!(messages[1].reply_count, 0);
+
+    // Replies are paginated, most recent first, and always preceded by the root message.
+    let thread_ids = |before_message_id: Option<MessageId>| async move {
+        db.get_channel_message_thread(channel, user, root, 1, before_message_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|message| message.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        thread_ids(None).await,
+        &[root.to_proto(), reply_2.to_proto()]
+    );
+    assert_eq!(
+        thread_ids(Some(reply_2)).await,
+        &[root.to_proto(), reply_1.to_proto()]
+    );
+    assert_eq!(thread_ids(Some(reply_1)).await, &[root.to_proto()]);
+
+    db.get_channel_message_thread(channel, user, MessageId::from_proto(1000), 10, None)
+        .await
+        .unwrap_err();
+    let thread = db
+        .get_channel_message_thread(channel, user, other, 10, None)
+        .await
+        .unwrap();
+    assert_eq!(
+        thread.iter().map(|message| message.id).collect::<Vec<_>>(),
+        &[other.to_proto()]
+    );
+}
+
+test_both_dbs!(
+    test_channel_message_search,
+    test_channel_message_search_postgres,
+    test_channel_message_search_sqlite
+);
+
+async fn test_channel_message_search(db: &Arc<Database>) {
+    let user_a = new_test_user(db, "user_a@example.com").await;
+    let user_b = new_test_user(db, "user_b@example.com").await;
+    let channel = db.create_root_channel("channel", user_a).await.unwrap();
+    let other_channel = db
+        .create_root_channel("other-channel", user_a)
+        .await
+        .unwrap();
+
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    let connection_id = rpc::ConnectionId { owner_id, id: 0 };
+    db.join_channel_chat(channel, connection_id, user_a)
+        .await
+        .unwrap();
+    db.join_channel_chat(other_channel, connection_id, user_a)
+        .await
+        .unwrap();
+
+    let mut message_ids = Vec::new();
+    for (ix, (channel, body)) in [
+        (channel, "the staging server is down"),
+        (channel, "lunch?"),
+        (other_channel, "restarting the server"),
+        (channel, "Server is back up"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        message_ids.push(
+            db.create_channel_message(
+                channel,
+                user_a,
+                body,
+                &[],
+                OffsetDateTime::now_utc(),
+                ix as u128,
+                None,
+            )
+            .await
+            .unwrap()
+            .message_id,
+        );
+    }
+
+    let results = db
+        .search_channel_messages(channel, user_a, "server", 10, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|message| message.body)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        &["the staging server is down", "Server is back up"]
+    );
+
+    // Results are paginated from the most recent match.
+    let results = db
+        .search_channel_messages(channel, user_a, "server", 1, None)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, message_ids[3].to_proto());
+    let results = db
+        .search_channel_messages(channel, user_a, "server", 1, Some(message_ids[3]))
+        .await
+        .unwrap();
+    assert_eq!(results[0].id, message_ids[0].to_proto());
+
+    // Users outside of the channel can't search it.
+    db.search_channel_messages(channel, user_b, "server", 10, None)
+        .await
+        .unwrap_err();
+}
@@ -0,0 +1,197 @@
+use super::new_test_user;
+use crate::{
+    db::{Database, ReviewThreadId, RoomId},
+    test_both_dbs,
+};
+use rpc::{proto, ConnectionId};
+use std::sync::Arc;
+use time::OffsetDateTime;
+
+test_both_dbs!(
+    test_review_threads,
+    test_review_threads_postgres,
+    test_review_threads_sqlite
+);
+
+async fn test_review_threads(db: &Arc<Database>) {
+    let user_a = new_test_user(db, "user_a@example.com").await;
+    let user_b = new_test_user(db, "user_b@example.com").await;
+    let user_c = new_test_user(db, "user_c@example.com").await;
+    let owner_id = db.create_server("test").await.unwrap().0 as u32;
+    let connection_a = ConnectionId { owner_id, id: 0 };
+    let connection_b = ConnectionId { owner_id, id: 1 };
+    let connection_c = ConnectionId { owner_id, id: 2 };
+
+    let room_id = RoomId::from_proto(db.create_room(user_a, connection_a, "").await.unwrap().id);
+    db.call(room_id, user_a, connection_a, user_b, None)
+        .await
+        .unwrap();
+    db.join_room(room_id, user_b, connection_b).await.unwrap();
+    let worktree = proto::WorktreeMetadata {
+        id: 1,
+        root_name: "crate".into(),
+        visible: true,
+        abs_path: "/code/crate".into(),
+    };
+    let project_id = db
+        .share_project(room_id, connection_a, &[worktree.clone()], None)
+        .await
+        .unwrap()
+        .0;
+    db.join_project(project_id, connection_b, user_b)
+        .await
+        .unwrap();
+
+    let range = proto::AnchorRange {
+        start: Some(proto::Anchor {
+            replica_id: 0,
+            timestamp: 1,
+            offset: 4,
+            bias: proto::Bias::Left as i32,
+            buffer_id: Some(1),
+        }),
+        end: Some(proto::Anchor {
+            replica_id: 0,
+            timestamp: 1,
+            offset: 10,
+            bias: proto::Bias::Right as i32,
+            buffer_id: Some(1),
+        }),
+    };
+    let (thread, connection_ids) = db
+        .create_review_thread(
+            &proto::CreateReviewThread {
+                project_id: project_id.to_proto(),
+                worktree_id: 1,
+                path: "src/main.rs".into(),
+                range: Some(range.clone()),
+                start_row: 2,
+                end_row: 3,
+                body: String::new(),
+            },
+            "is this needed?",
+            OffsetDateTime::now_utc(),
+            connection_b,
+            user_b,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(connection_ids, &[connection_a]);
+    assert_eq!(thread.range, Some(range));
+    assert_eq!((thread.start_row, thread.end_row), (2, 3));
+    assert!(!thread.resolved);
+    assert_eq!(comment_bodies(&thread), &["is this needed?"]);
+    let thread_id = ReviewThreadId::from_proto(thread.id);
+
+    let (thread, connection_ids) = db
+        .add_review_comment(
+            project_id,
+            thread_id,
+            "yes, see the caller",
+            OffsetDateTime::now_utc(),
+            connection_a,
+            user_a,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(connection_ids, &[connection_b]);
+    assert_eq!(
+        comment_bodies(&thread),
+        &["is this needed?", "yes, see the caller"]
+    );
+    assert_eq!(thread.comments[1].sender_id, user_a.to_proto());
+
+    let (thread, _) = db
+        .set_review_thread_resolved(project_id, thread_id, true, connection_b, user_b)
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(thread.resolved);
+
+    let threads = db
+        .get_review_threads(project_id, connection_a, user_a)
+        .await
+        .unwrap();
+    assert_eq!(threads, &[thread.clone()]);
+
+    // Users outside of the project can't read or reply to its threads.
+    assert!(db
+        .get_review_threads(project_id, connection_c, user_c)
+        .await
+        .is_err());
+    assert!(db
+        .add_review_comment(
+            project_id,
+            thread_id,
+            "hi",
+            OffsetDateTime::now_utc(),
+            connection_c,
+            user_c,
+        )
+        .await
+        .is_err());
+
+    // Threads can't be accessed through a project that doesn't contain their worktree.
+    let other_project_id = db
+        .share_project(
+            room_id,
+            connection_a,
+            &[proto::WorktreeMetadata {
+                id: 1,
+                root_name: "other".into(),
+                visible: true,
+                abs_path: "/code/other".into(),
+            }],
+            None,
+        )
+        .await
+        .unwrap()
+        .0;
+    assert!(db
+        .get_review_threads(other_project_id, connection_a, user_a)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(db
+        .set_review_thread_resolved(other_project_id, thread_id, false, connection_a, user_a)
+        .await
+        .is_err());
+
+    // Threads outlive the project they were started in, and are shown again when the
+    // host shares the same worktree.
+    db.unshare_project(project_id, connection_a, Some(user_a))
+        .await
+        .unwrap();
+    db.delete_project(project_id).await.unwrap();
+    let reshared_project_id = db
+        .share_project(
+            room_id,
+            connection_a,
+            &[proto::WorktreeMetadata { id: 7, ..worktree }],
+            None,
+        )
+        .await
+        .unwrap()
+        .0;
+    let threads = db
+        .get_review_threads(reshared_project_id, connection_a, user_a)
+        .await
+        .unwrap();
+    assert_eq!(
+        threads,
+        &[proto::ReviewThread {
+            worktree_id: 7,
+            ..thread
+        }]
+    );
+}
+
+fn comment_bodies(thread: &proto::ReviewThread) -> Vec<&str> {
+    thread
+        .comments
+        .iter()
+        .map(|comment| comment.body.as_str())
+        .collect()
+}
@@ -6,8 +6,9 @@ use crate::{
         self, dev_server, BufferId, Capability, Channel, ChannelId, ChannelRole, ChannelsForUser,
         CreatedChannelMessage, Database, DevServerId, DevServerProjectId, InviteMemberResult,
         MembershipUpdated, MessageId, NotificationId, PrincipalId, Project, ProjectId,
-        RejoinedProject, RemoveChannelMemberResult, ReplicaId, RespondToChannelInvite, RoomId,
-        ServerId, UpdatedChannelMessage, User, UserId,
+        RejoinedProject, RemoveChannelMemberResult, ReplicaId, RespondToChannelInvite,
+        ReviewThreadId, RoomId, ServerId, UpdatedChannelMessage, UpdatedChannelMessageReactions,
+        User, UserId,
     },
     executor::Executor,
     AppState, Error, RateLimit, RateLimiter, Result,
@@ -84,6 +85,7 @@ pub const CLEANUP_TIMEOUT: Duration = Duration::from_secs(15);
 
 const MESSAGE_COUNT_PER_PAGE: usize = 100;
 const MAX_MESSAGE_LEN: usize = 1024;
+const MAX_REACTION_LEN: usize = 32;
 const NOTIFICATION_COUNT_PER_PAGE: usize = 50;
 
 type MessageHandler =
@@ -585,6 +587,9 @@ impl Server {
             .add_request_handler(user_handler(update_channel_message))
             .add_request_handler(user_handler(get_channel_messages))
             .add_request_handler(user_handler(get_channel_messages_by_id))
+            .add_request_handler(user_handler(get_channel_message_thread))
+            .add_request_handler(user_handler(search_channel_messages))
+            .add_request_handler(user_handler(toggle_channel_message_reaction))
             .add_request_handler(user_handler(get_notifications))
             .add_request_handler(user_handler(mark_notification_as_read))
             .add_request_handler(user_handler(move_channel))
@@ -603,6 +608,18 @@ impl Server {
             ))
             .add_message_handler(broadcast_project_message_from_host::<proto::AdvertiseContexts>)
             .add_message_handler(update_context)
+            .add_request_handler(user_handler(
+                forward_read_only_project_request::<proto::OpenSharedTerminal>,
+            ))
+            .add_message_handler(
+                broadcast_project_message_from_host::<proto::AdvertiseSharedTerminals>,
+            )
+            .add_message_handler(broadcast_project_message_from_host::<proto::UpdateSharedTerminal>)
+            .add_message_handler(user_message_handler(forward_shared_terminal_input))
+            .add_request_handler(user_handler(get_review_threads))
+            .add_request_handler(user_handler(create_review_thread))
+            .add_request_handler(user_handler(add_review_comment))
+            .add_request_handler(user_handler(set_review_thread_resolved))
             .add_streaming_request_handler({
                 let app_state = app_state.clone();
                 move |request, response, session| {
@@ -3114,6 +3131,153 @@ async fn update_context(message: proto::UpdateContext, session: Session) -> Resu
     Ok(())
 }
 
+/// Forward a guest's keystrokes in a shared terminal to the project host. Guests with
+/// read-only access to the project can't write to terminals.
+async fn forward_shared_terminal_input(
+    message: proto::SharedTerminalInput,
+    session: UserSession,
+) -> Result<()> {
+    let project_id = ProjectId::from_proto(message.project_id);
+    let host_connection_id = session
+        .db()
+        .await
+        .host_for_mutating_project_request(project_id, session.connection_id, session.user_id())
+        .await?;
+    session
+        .peer
+        .forward_send(session.connection_id, host_connection_id, message)?;
+    Ok(())
+}
+
+/// Returns the review threads in a shared project.
+async fn get_review_threads(
+    request: proto::GetReviewThreads,
+    response: Response<proto::GetReviewThreads>,
+    session: UserSession,
+) -> Result<()> {
+    let threads = session
+        .db()
+        .await
+        .get_review_threads(
+            ProjectId::from_proto(request.project_id),
+            session.connection_id,
+            session.user_id(),
+        )
+        .await?;
+    response.send(proto::GetReviewThreadsResponse { threads })?;
+    Ok(())
+}
+
+/// Starts a review thread on a range of a buffer in a shared project.
+async fn create_review_thread(
+    request: proto::CreateReviewThread,
+    response: Response<proto::CreateReviewThread>,
+    session: UserSession,
+) -> Result<()> {
+    let body = validate_review_comment_body(&request.body)?;
+    let result = session
+        .db()
+        .await
+        .create_review_thread(
+            &request,
+            &body,
+            OffsetDateTime::now_utc(),
+            session.connection_id,
+            session.user_id(),
+        )
+        .await?;
+    let (thread, connection_ids) = &*result;
+    broadcast_review_thread(request.project_id, thread, connection_ids, &session);
+    response.send(proto::ReviewThreadResponse {
+        thread: Some(thread.clone()),
+    })?;
+    Ok(())
+}
+
+/// Replies to a review thread in a shared project.
+async fn add_review_comment(
+    request: proto::AddReviewComment,
+    response: Response<proto::AddReviewComment>,
+    session: UserSession,
+) -> Result<()> {
+    let body = validate_review_comment_body(&request.body)?;
+    let result = session
+        .db()
+        .await
+        .add_review_comment(
+            ProjectId::from_proto(request.project_id),
+            ReviewThreadId::from_proto(request.thread_id),
+            &body,
+            OffsetDateTime::now_utc(),
+            session.connection_id,
+            session.user_id(),
+        )
+        .await?;
+    let (thread, connection_ids) = &*result;
+    broadcast_review_thread(request.project_id, thread, connection_ids, &session);
+    response.send(proto::ReviewThreadResponse {
+        thread: Some(thread.clone()),
+    })?;
+    Ok(())
+}
+
+/// Resolves or reopens a review thread in a shared project.
+async fn set_review_thread_resolved(
+    request: proto::SetReviewThreadResolved,
+    response: Response<proto::SetReviewThreadResolved>,
+    session: UserSession,
+) -> Result<()> {
+    let result = session
+        .db()
+        .await
+        .set_review_thread_resolved(
+            ProjectId::from_proto(request.project_id),
+            ReviewThreadId::from_proto(request.thread_id),
+            request.resolved,
+            session.connection_id,
+            session.user_id(),
+        )
+        .await?;
+    let (thread, connection_ids) = &*result;
+    broadcast_review_thread(request.project_id, thread, connection_ids, &session);
+    response.send(proto::ReviewThreadResponse {
+        thread: Some(thread.clone()),
+    })?;
+    Ok(())
+}
+
+fn validate_review_comment_body(body: &str) -> Result<String> {
+    let body = body.trim().to_string();
+    if body.len() > MAX_MESSAGE_LEN {
+        return Err(anyhow!("comment is too long"))?;
+    }
+    if body.is_empty() {
+        return Err(anyhow!("comment can't be blank"))?;
+    }
+    Ok(body)
+}
+
+fn broadcast_review_thread(
+    project_id: u64,
+    thread: &proto::ReviewThread,
+    connection_ids: &[ConnectionId],
+    session: &UserSession,
+) {
+    broadcast(
+        Some(session.connection_id),
+        connection_ids.iter().copied(),
+        |connection_id| {
+            session.peer.send(
+                connection_id,
+                proto::UpdateReviewThread {
+                    project_id,
+                    thread: Some(thread.clone()),
+                },
+            )
+        },
+    );
+}
+
 /// Notify other participants that a project has been updated.
 async fn broadcast_project_message_from_host<T: EntityMessage<Entity = ShareProject>>(
     request: T,
@@ -4225,6 +4389,8 @@ async fn send_channel_message(
         nonce: Some(nonce),
         reply_to_message_id: request.reply_to_message_id,
         edited_at: None,
+        reactions: Vec::new(),
+        reply_count: 0,
     };
     broadcast(
         Some(session.connection_id),
@@ -4349,6 +4515,8 @@ async fn update_channel_message(
         nonce: Some(nonce),
         reply_to_message_id: reply_to_message_id.map(|id| id.to_proto()),
         edited_at: Some(updated_at.unix_timestamp() as u64),
+        reactions: Vec::new(),
+        reply_count: 0,
     };
 
     response.send(proto::Ack {})?;
@@ -4393,6 +4561,46 @@ async fn update_channel_message(
     Ok(())
 }
 
+/// React to a channel message, or take back a reaction
+async fn toggle_channel_message_reaction(
+    request: proto::ToggleChannelMessageReaction,
+    response: Response<proto::ToggleChannelMessageReaction>,
+    session: UserSession,
+) -> Result<()> {
+    let emoji = request.emoji.trim();
+    if emoji.is_empty() || emoji.len() > MAX_REACTION_LEN {
+        return Err(anyhow!("invalid reaction"))?;
+    }
+
+    let channel_id = ChannelId::from_proto(request.channel_id);
+    let message_id = MessageId::from_proto(request.message_id);
+    let UpdatedChannelMessageReactions {
+        reactions,
+        participant_connection_ids,
+    } = session
+        .db()
+        .await
+        .toggle_channel_message_reaction(channel_id, message_id, session.user_id(), emoji)
+        .await?;
+
+    broadcast(
+        Some(session.connection_id),
+        participant_connection_ids,
+        |connection| {
+            session.peer.send(
+                connection,
+                proto::ChannelMessageReactionsUpdated {
+                    channel_id: channel_id.to_proto(),
+                    message_id: message_id.to_proto(),
+                    reactions: reactions.clone(),
+                },
+            )
+        },
+    );
+    response.send(proto::ToggleChannelMessageReactionResponse { reactions })?;
+    Ok(())
+}
+
 /// Mark a channel message as read
 async fn acknowledge_channel_message(
     request: proto::AckChannelMessage,
@@ -4998,6 +5206,60 @@ async fn get_channel_messages_by_id(
     Ok(())
 }
 
+/// Retrieve a chat message along with a page of its replies
+async fn get_channel_message_thread(
+    request: proto::GetChannelMessageThread,
+    response: Response<proto::GetChannelMessageThread>,
+    session: UserSession,
+) -> Result<()> {
+    let messages = session
+        .db()
+        .await
+        .get_channel_message_thread(
+            ChannelId::from_proto(request.channel_id),
+            session.user_id(),
+            MessageId::from_proto(request.message_id),
+            MESSAGE_COUNT_PER_PAGE,
+            request.before_message_id.map(MessageId::from_proto),
+        )
+        .await?;
+    // The thread's root message is always included, in addition to the page of replies.
+    response.send(proto::GetChannelMessagesResponse {
+        done: messages.len() <= MESSAGE_COUNT_PER_PAGE,
+        messages,
+    })?;
+    Ok(())
+}
+
+/// Search the chat history for a channel
+async fn search_channel_messages(
+    request: proto::SearchChannelMessages,
+    response: Response<proto::SearchChannelMessages>,
+    session: UserSession,
+) -> Result<()> {
+    let query = request.query.trim();
+    if query.is_empty() {
+        return Err(anyhow!("search query can't be blank"))?;
+    }
+
+    let messages = session
+        .db()
+        .await
+        .search_channel_messages(
+            ChannelId::from_proto(request.channel_id),
+            session.user_id(),
+            query,
+            MESSAGE_COUNT_PER_PAGE,
+            request.before_message_id.map(MessageId::from_proto),
+        )
+        .await?;
+    response.send(proto::SearchChannelMessagesResponse {
+        done: messages.len() < MESSAGE_COUNT_PER_PAGE,
+        messages,
+    })?;
+    Ok(())
+}
+
 /// Retrieve the current users notifications
 async fn get_notifications(
     request: proto::GetNotifications,
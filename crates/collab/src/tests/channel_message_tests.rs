@@ -723,3 +723,126 @@ async fn test_chat_editing(cx_a: &mut TestAppContext, cx_b: &mut TestAppContext)
         assert_eq!(store.notification_count(), 1);
     });
 }
+
+#[gpui::test]
+async fn test_channel_message_reactions_and_threads(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+
+    let channel_id = server
+        .make_channel(
+            "the-channel",
+            None,
+            (&client_a, cx_a),
+            &mut [(&client_b, cx_b)],
+        )
+        .await;
+
+    let channel_chat_a = client_a
+        .channel_store()
+        .update(cx_a, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+    let channel_chat_b = client_b
+        .channel_store()
+        .update(cx_b, |store, cx| store.open_channel_chat(channel_id, cx))
+        .await
+        .unwrap();
+
+    let root_id = channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.send_message("which release should we cut?".into(), cx)
+                .unwrap()
+        })
+        .await
+        .unwrap();
+    channel_chat_a
+        .update(cx_a, |c, cx| {
+            c.send_message("unrelated".into(), cx).unwrap()
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    // Client B opens the thread and replies in it.
+    channel_chat_b
+        .update(cx_b, |c, cx| c.open_thread(root_id, cx))
+        .await
+        .unwrap();
+    channel_chat_b
+        .update(cx_b, |c, cx| {
+            c.send_message(
+                MessageParams {
+                    text: "the stable one".into(),
+                    mentions: Vec::new(),
+                    reply_to_message_id: Some(root_id),
+                },
+                cx,
+            )
+            .unwrap()
+        })
+        .await
+        .unwrap();
+
+    // Both reactions to the root message are visible to both clients.
+    channel_chat_a
+        .update(cx_a, |c, cx| c.toggle_reaction(root_id, "👍".into(), cx))
+        .await
+        .unwrap();
+    channel_chat_b
+        .update(cx_b, |c, cx| c.toggle_reaction(root_id, "👍".into(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    for (chat, cx) in [(&channel_chat_a, &mut *cx_a), (&channel_chat_b, &mut *cx_b)] {
+        chat.read_with(cx, |chat, _| {
+            let root = chat.message(0);
+            assert_eq!(root.reply_count, 1);
+            assert_eq!(root.reactions.len(), 1);
+            assert_eq!(root.reactions[0].emoji, "👍");
+            assert_eq!(root.reactions[0].user_ids, [client_a.id(), client_b.id()]);
+        });
+    }
+    channel_chat_b.read_with(cx_b, |chat, _| {
+        let thread = chat.thread(root_id).unwrap();
+        assert_eq!(thread.root.reactions[0].user_ids.len(), 2);
+        assert_eq!(
+            thread
+                .replies
+                .iter()
+                .map(|reply| reply.body.as_str())
+                .collect::<Vec<_>>(),
+            ["the stable one"]
+        );
+    });
+
+    // Reacting again with the same emoji takes the reaction back.
+    channel_chat_b
+        .update(cx_b, |c, cx| c.toggle_reaction(root_id, "👍".into(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    channel_chat_a.read_with(cx_a, |chat, _| {
+        assert_eq!(chat.message(0).reactions[0].user_ids, [client_a.id()]);
+    });
+
+    // Searching finds matches anywhere in the channel's history.
+    let (results, done) = channel_chat_a
+        .update(cx_a, |c, cx| c.search_messages("stable".into(), None, cx))
+        .await
+        .unwrap();
+    assert!(done);
+    assert_eq!(
+        results
+            .iter()
+            .map(|message| message.body.as_str())
+            .collect::<Vec<_>>(),
+        ["the stable one"]
+    );
+}
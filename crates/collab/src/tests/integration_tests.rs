@@ -9,6 +9,7 @@ use anyhow::{anyhow, Result};
 use assistant::ContextStore;
 use call::{room, ActiveCall, ParticipantLocation, Room};
 use client::{User, RECEIVE_TIMEOUT};
+use collab_ui::review_comments::ReviewThreadStore;
 use collections::{HashMap, HashSet};
 use fs::{FakeFs, Fs as _, RemoveOptions};
 use futures::{channel::mpsc, StreamExt as _};
@@ -18,7 +19,7 @@ use gpui::{
     TestAppContext, UpdateGlobal,
 };
 use language::{
-    language_settings::{AllLanguageSettings, Formatter, PrettierSettings},
+    language_settings::{language_settings, AllLanguageSettings, Formatter, PrettierSettings},
     tree_sitter_rust, Diagnostic, DiagnosticEntry, FakeLspAdapter, Language, LanguageConfig,
     LanguageMatcher, LineEnding, OffsetRangeExt, Point, Rope,
 };
@@ -43,6 +44,8 @@ use std::{
     },
     time::Duration,
 };
+use terminal::Terminal;
+use terminal_view::shared_terminals::SharedTerminals;
 use unindent::Unindent as _;
 use workspace::Pane;
 
@@ -3414,6 +3417,105 @@ async fn test_local_settings(
     });
 }
 
+#[gpui::test(iterations = 10)]
+async fn test_local_editorconfigs(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    // As client A, open a project with `.editorconfig` files inside and above its root.
+    client_a
+        .fs()
+        .insert_tree(
+            "/code",
+            json!({
+                ".editorconfig": "root = true\n[*]\nindent_size = 3\n",
+                "dir": {
+                    ".editorconfig": "[*.md]\nindent_size = 2\n",
+                    "a.txt": "a-contents",
+                    "b.md": "b-contents",
+                }
+            }),
+        )
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/code/dir", cx_a).await;
+    executor.run_until_parked();
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    // As client B, join that project and observe the `.editorconfig` files.
+    let project_b = client_b.build_dev_server_project(project_id, cx_b).await;
+    let worktree_b = project_b.read_with(cx_b, |project, _| project.worktrees().next().unwrap());
+    executor.run_until_parked();
+    cx_b.read(|cx| {
+        let store = cx.global::<SettingsStore>();
+        assert_eq!(
+            store
+                .local_editorconfigs(worktree_b.read(cx).id().to_usize())
+                .collect::<Vec<_>>(),
+            &[
+                (
+                    Path::new("").into(),
+                    "[*.md]\nindent_size = 2\n".to_string()
+                ),
+                (
+                    Path::new("../dir").into(),
+                    "root = true\n[*]\nindent_size = 3\n".to_string()
+                ),
+            ]
+        )
+    });
+
+    let buffer_a_txt = project_b
+        .update(cx_b, |p, cx| p.open_buffer((worktree_id, "a.txt"), cx))
+        .await
+        .unwrap();
+    let buffer_b_md = project_b
+        .update(cx_b, |p, cx| p.open_buffer((worktree_id, "b.md"), cx))
+        .await
+        .unwrap();
+    executor.run_until_parked();
+    cx_b.read(|cx| {
+        let tab_size = |buffer: &Model<language::Buffer>| {
+            language_settings(None, buffer.read(cx).file(), cx)
+                .tab_size
+                .get()
+        };
+        assert_eq!(tab_size(&buffer_a_txt), 3);
+        assert_eq!(tab_size(&buffer_b_md), 2);
+    });
+
+    // As client A, update the `.editorconfig` file. As client B, see the changed settings.
+    client_a
+        .fs()
+        .insert_file(
+            "/code/dir/.editorconfig",
+            "[*.txt]\nindent_size = 5\n".into(),
+        )
+        .await;
+    executor.run_until_parked();
+    cx_b.read(|cx| {
+        let tab_size = |buffer: &Model<language::Buffer>| {
+            language_settings(None, buffer.read(cx).file(), cx)
+                .tab_size
+                .get()
+        };
+        assert_eq!(tab_size(&buffer_a_txt), 5);
+        assert_eq!(tab_size(&buffer_b_md), 3);
+    });
+}
+
 #[gpui::test(iterations = 10)]
 async fn test_buffer_conflict_after_save(
     executor: BackgroundExecutor,
@@ -6570,3 +6672,201 @@ async fn test_context_collaboration_with_reconnect(
         assert!(context.buffer().read(cx).read_only());
     });
 }
+
+#[gpui::test]
+async fn test_shared_terminals(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a.fs().insert_tree("/a", json!({})).await;
+    let (project_a, _) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.build_dev_server_project(project_id, cx_b).await;
+    executor.run_until_parked();
+
+    let shared_terminals_a = cx_a.update(|cx| SharedTerminals::new(project_a.clone(), cx));
+    let shared_terminals_b = cx_b.update(|cx| SharedTerminals::new(project_b.clone(), cx));
+
+    // Client A shares a terminal, which client B opens.
+    let (input_tx, mut input_rx) = mpsc::unbounded();
+    let terminal_a = cx_a.new_model(|_| Terminal::test(input_tx));
+    terminal_a.update(cx_a, |terminal, cx| {
+        terminal.write_output(b"$ cargo test\r\n", cx)
+    });
+    shared_terminals_a.update(cx_a, |shared_terminals, cx| {
+        shared_terminals.share(&terminal_a, cx)
+    });
+    executor.run_until_parked();
+
+    let terminal_b = shared_terminals_b
+        .update(cx_b, |shared_terminals, cx| {
+            let host_terminals = shared_terminals.host_terminals().to_vec();
+            assert_eq!(host_terminals.len(), 1);
+            assert!(!host_terminals[0].guests_can_write);
+            shared_terminals.open_remote_terminal(host_terminals[0].id, cx)
+        })
+        .await
+        .unwrap();
+    assert_eq!(terminal_screen_text(&terminal_b, cx_b), "$ cargo test");
+
+    // Output on the host's terminal reaches the guest.
+    terminal_a.update(cx_a, |terminal, cx| {
+        terminal.write_output(b"running 1 test\r\n", cx)
+    });
+    executor.advance_clock(Duration::from_secs(1));
+    executor.run_until_parked();
+    assert_eq!(
+        terminal_screen_text(&terminal_b, cx_b),
+        "$ cargo test\nrunning 1 test"
+    );
+
+    // The guest's input is discarded until the host lets guests write to the terminal.
+    terminal_b.update(cx_b, |terminal, _| {
+        assert!(terminal.is_read_only());
+        terminal.input("q".into());
+    });
+    executor.run_until_parked();
+    assert!(input_rx.try_next().is_err());
+
+    shared_terminals_a.update(cx_a, |shared_terminals, cx| {
+        shared_terminals.set_guests_can_write(&terminal_a, true, cx)
+    });
+    executor.run_until_parked();
+    terminal_b.update(cx_b, |terminal, _| {
+        assert!(!terminal.is_read_only());
+        terminal.input("ls\r".into());
+    });
+    executor.run_until_parked();
+    assert_eq!(input_rx.try_next().unwrap(), Some(b"ls\r".to_vec()));
+
+    // When the host stops sharing the terminal, the guest's copy stops receiving output
+    // and becomes read-only.
+    shared_terminals_a.update(cx_a, |shared_terminals, cx| {
+        shared_terminals.unshare(&terminal_a, cx)
+    });
+    executor.run_until_parked();
+    shared_terminals_b.read_with(cx_b, |shared_terminals, _| {
+        assert!(shared_terminals.host_terminals().is_empty());
+    });
+    terminal_b.read_with(cx_b, |terminal, _| assert!(terminal.is_read_only()));
+
+    terminal_a.update(cx_a, |terminal, cx| {
+        terminal.write_output(b"test result: ok\r\n", cx)
+    });
+    executor.advance_clock(Duration::from_secs(1));
+    executor.run_until_parked();
+    assert_eq!(
+        terminal_screen_text(&terminal_b, cx_b),
+        "$ cargo test\nrunning 1 test"
+    );
+}
+
+fn terminal_screen_text(terminal: &Model<Terminal>, cx: &TestAppContext) -> String {
+    terminal.read_with(cx, |terminal, _| {
+        let snapshot = terminal.screen_snapshot();
+        let lines = snapshot
+            .rows
+            .iter()
+            .map(|row| {
+                let line = row.iter().map(|cell| cell.c).collect::<String>();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>();
+        lines.join("\n").trim_end().to_string()
+    })
+}
+
+#[gpui::test]
+async fn test_review_threads_outlive_shared_project(
+    executor: BackgroundExecutor,
+    cx_a: &mut TestAppContext,
+    cx_b: &mut TestAppContext,
+) {
+    let mut server = TestServer::start(executor.clone()).await;
+    let client_a = server.create_client(cx_a, "user_a").await;
+    let client_b = server.create_client(cx_b, "user_b").await;
+    server
+        .create_room(&mut [(&client_a, cx_a), (&client_b, cx_b)])
+        .await;
+    let active_call_a = cx_a.read(ActiveCall::global);
+
+    client_a
+        .fs()
+        .insert_tree("/a", json!({ "main.rs": "fn main() {}\n" }))
+        .await;
+    let (project_a, worktree_id) = client_a.build_local_project("/a", cx_a).await;
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b = client_b.build_dev_server_project(project_id, cx_b).await;
+    let store_a = cx_a.update(|cx| ReviewThreadStore::new(project_a.clone(), cx));
+    let store_b = cx_b.update(|cx| ReviewThreadStore::new(project_b.clone(), cx));
+    executor.run_until_parked();
+
+    // Client B starts a thread on the host's file.
+    let buffer_b = project_b
+        .update(cx_b, |p, cx| p.open_buffer((worktree_id, "main.rs"), cx))
+        .await
+        .unwrap();
+    let range = buffer_b.read_with(cx_b, |buffer, _| {
+        buffer.anchor_before(Point::new(0, 0))..buffer.anchor_after(Point::new(0, 12))
+    });
+    store_b
+        .update(cx_b, |store, cx| {
+            store.create_thread(&buffer_b, range, "Needs a comment".into(), cx)
+        })
+        .await
+        .unwrap();
+    executor.run_until_parked();
+
+    // The thread survives client A unsharing the project and sharing it again.
+    active_call_a
+        .update(cx_a, |call, cx| call.unshare_project(project_a.clone(), cx))
+        .unwrap();
+    executor.run_until_parked();
+    assert!(store_a.read_with(cx_a, |store, _| store.threads().is_empty()));
+
+    let project_id = active_call_a
+        .update(cx_a, |call, cx| call.share_project(project_a.clone(), cx))
+        .await
+        .unwrap();
+    let project_b2 = client_b.build_dev_server_project(project_id, cx_b).await;
+    let store_b2 = cx_b.update(|cx| ReviewThreadStore::new(project_b2.clone(), cx));
+    executor.run_until_parked();
+
+    for (store, cx) in [(&store_a, &*cx_a), (&store_b2, &*cx_b)] {
+        store.read_with(cx, |store, _| {
+            let threads = store.threads();
+            assert_eq!(threads.len(), 1);
+            assert_eq!(
+                threads[0].project_path,
+                ProjectPath {
+                    worktree_id,
+                    path: Path::new("main.rs").into(),
+                }
+            );
+            assert_eq!(threads[0].rows, 0..=0);
+            assert_eq!(
+                threads[0]
+                    .comments
+                    .iter()
+                    .map(|comment| comment.body.as_str())
+                    .collect::<Vec<_>>(),
+                ["Needs a comment"]
+            );
+        });
+    }
+}
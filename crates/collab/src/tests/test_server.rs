@@ -296,6 +296,7 @@ impl TestServer {
             settings::KeymapFile::load_asset(os_keymap, cx).unwrap();
             assistant::FakeCompletionProvider::setup_test(cx);
             assistant::context_store::init(&client);
+            terminal_view::shared_terminals::init(&client);
         });
 
         client
@@ -7,10 +7,10 @@ use collections::HashMap;
 use db::kvp::KEY_VALUE_STORE;
 use editor::{actions, Editor};
 use gpui::{
-    actions, div, list, prelude::*, px, Action, AppContext, AsyncWindowContext, ClipboardItem,
-    CursorStyle, DismissEvent, ElementId, EventEmitter, FocusHandle, FocusableView, FontWeight,
-    HighlightStyle, ListOffset, ListScrollEvent, ListState, Model, Render, Stateful, Subscription,
-    Task, View, ViewContext, VisualContext, WeakView,
+    actions, div, list, prelude::*, px, Action, AnyElement, AppContext, AsyncWindowContext,
+    ClipboardItem, CursorStyle, DismissEvent, ElementId, EventEmitter, FocusHandle, FocusableView,
+    FontWeight, HighlightStyle, ListOffset, ListScrollEvent, ListState, Model, Render, Stateful,
+    Subscription, Task, View, ViewContext, VisualContext, WeakView,
 };
 use language::LanguageRegistry;
 use menu::Confirm;
@@ -23,7 +23,7 @@ use std::{sync::Arc, time::Duration};
 use time::{OffsetDateTime, UtcOffset};
 use ui::{
     prelude::*, Avatar, Button, ContextMenu, IconButton, IconName, KeyBinding, Label, PopoverMenu,
-    TabBar, Tooltip,
+    TabBar, TintColor, Tooltip,
 };
 use util::{ResultExt, TryFutureExt};
 use workspace::{
@@ -32,9 +32,13 @@ use workspace::{
 };
 
 mod message_editor;
+mod message_search;
+
+use message_search::{MessageSearch, MessageSearchEvent};
 
 const MESSAGE_LOADING_THRESHOLD: usize = 50;
 const CHAT_PANEL_KEY: &str = "ChatPanel";
+const QUICK_REACTIONS: [&str; 6] = ["👍", "❤️", "😄", "🎉", "👀", "🚀"];
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(|workspace: &mut Workspace, _| {
@@ -64,6 +68,14 @@ pub struct ChatPanel {
     open_context_menu: Option<(u64, Subscription)>,
     highlighted_message: Option<(u64, Task<()>)>,
     last_acknowledged_message_id: Option<u64>,
+    message_search: Option<(View<MessageSearch>, Subscription)>,
+    open_thread: Option<OpenThread>,
+}
+
+/// A thread that's displayed in place of the chat's messages.
+struct OpenThread {
+    root_message_id: u64,
+    list: ListState,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -71,7 +83,7 @@ struct SerializedChatPanel {
     width: Option<Pixels>,
 }
 
-actions!(chat_panel, [ToggleFocus]);
+actions!(chat_panel, [ToggleFocus, SearchMessages]);
 
 impl ChatPanel {
     pub fn new(workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
@@ -130,6 +142,8 @@ impl ChatPanel {
                 open_context_menu: None,
                 highlighted_message: None,
                 last_acknowledged_message_id: None,
+                message_search: None,
+                open_thread: None,
             };
 
             if let Some(channel_id) = ActiveCall::global(cx)
@@ -233,6 +247,8 @@ impl ChatPanel {
 
     fn set_active_chat(&mut self, chat: Model<ChannelChat>, cx: &mut ViewContext<Self>) {
         if self.active_chat.as_ref().map(|e| &e.0) != Some(&chat) {
+            self.close_thread(cx);
+            self.message_search = None;
             self.markdown_data.clear();
             self.message_list.reset(chat.read(cx).message_count());
             self.message_editor.update(cx, |editor, cx| {
@@ -248,7 +264,7 @@ impl ChatPanel {
 
     fn channel_did_change(
         &mut self,
-        _: Model<ChannelChat>,
+        chat: Model<ChannelChat>,
         event: &ChannelChatEvent,
         cx: &mut ViewContext<Self>,
     ) {
@@ -279,6 +295,26 @@ impl ChatPanel {
                     })
                 }
             }
+            ChannelChatEvent::ThreadUpdated { root_message_id } => {
+                let Some(open_thread) = self
+                    .open_thread
+                    .as_ref()
+                    .filter(|thread| thread.root_message_id == *root_message_id)
+                else {
+                    return;
+                };
+                // The thread is gone when its first message was deleted.
+                let Some(thread) = chat.read(cx).thread(*root_message_id) else {
+                    self.close_thread(cx);
+                    return;
+                };
+                for message in std::iter::once(&thread.root).chain(&thread.replies) {
+                    self.markdown_data.remove(&message.id);
+                }
+                let scroll_top = open_thread.list.logical_scroll_top();
+                open_thread.list.reset(1 + thread.replies.len());
+                open_thread.list.scroll_to(scroll_top);
+            }
         }
         cx.notify();
     }
@@ -390,12 +426,7 @@ impl ChatPanel {
 
                 let last_message = active_chat.message(ix.saturating_sub(1));
                 let this_message = active_chat.message(ix).clone();
-
-                let duration_since_last_message = this_message.timestamp - last_message.timestamp;
-                let is_continuation_from_previous = last_message.sender.id
-                    == this_message.sender.id
-                    && last_message.id != this_message.id
-                    && duration_since_last_message < Duration::from_secs(5 * 60);
+                let is_continuation_from_previous = is_continuation(last_message, &this_message);
 
                 if let ChannelMessageId::Saved(id) = this_message.id {
                     if this_message
@@ -410,6 +441,55 @@ impl ChatPanel {
                 (this_message, is_continuation_from_previous, is_admin)
             });
 
+        self.render_message_entry(message, is_continuation_from_previous, is_admin, false, cx)
+    }
+
+    /// Renders the `ix`th message of the open thread, where the first message is
+    /// the one that's being replied to.
+    fn render_thread_message(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let Some((active_chat, root_message_id)) = self.active_chat().zip(
+            self.open_thread
+                .as_ref()
+                .map(|thread| thread.root_message_id),
+        ) else {
+            return div().into_any_element();
+        };
+        let active_chat = active_chat.read(cx);
+        let is_admin = self
+            .channel_store
+            .read(cx)
+            .is_channel_admin(active_chat.channel_id);
+        let Some(thread) = active_chat.thread(root_message_id) else {
+            return div().into_any_element();
+        };
+
+        let thread_message = |ix: usize| match ix {
+            0 => Some(&thread.root),
+            ix => thread.replies.get(ix - 1),
+        };
+        let Some(message) = thread_message(ix).cloned() else {
+            return div().into_any_element();
+        };
+        let is_continuation_from_previous = ix
+            .checked_sub(1)
+            .and_then(thread_message)
+            .map_or(false, |last_message| {
+                is_continuation(last_message, &message)
+            });
+
+        self.render_message_entry(message, is_continuation_from_previous, is_admin, true, cx)
+            .into_any_element()
+    }
+
+    fn render_message_entry(
+        &mut self,
+        message: ChannelMessage,
+        is_continuation_from_previous: bool,
+        is_admin: bool,
+        in_thread: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let active_chat = &self.active_chat.as_ref().unwrap().0;
         let _is_pending = message.is_pending();
 
         let belongs_to_user = Some(message.sender.id) == self.client.user_id();
@@ -452,6 +532,8 @@ impl ChatPanel {
         };
 
         let reply_to_message_id = self.message_editor.read(cx).reply_to_message_id();
+        // Replies in a thread all reply to its first message, so there's no need to quote it.
+        let shows_replied_to_message = message.reply_to_message_id.is_some() && !in_thread;
 
         v_flex()
             .w_full()
@@ -476,7 +558,7 @@ impl ChatPanel {
                     .when(!self.has_open_menu(message_id), |this| {
                         this.hover(|style| style.bg(cx.theme().colors().element_hover))
                     })
-                    .when(message.reply_to_message_id.is_some(), |el| {
+                    .when(shows_replied_to_message, |el| {
                         el.child(self.render_replied_to_message(
                             Some(message.id),
                             &reply_to_message,
@@ -485,7 +567,7 @@ impl ChatPanel {
                         .when(is_continuation_from_previous, |this| this.mt_2())
                     })
                     .when(
-                        !is_continuation_from_previous || message.reply_to_message_id.is_some(),
+                        !is_continuation_from_previous || shows_replied_to_message,
                         |this| {
                             this.child(
                                 h_flex()
@@ -534,11 +616,21 @@ impl ChatPanel {
                         .when(self.has_open_menu(message_id), |el| {
                             el.bg(cx.theme().colors().element_selected)
                         })
+                    })
+                    .when(!message.reactions.is_empty(), |el| {
+                        el.children(self.render_reactions(&message, cx))
+                    })
+                    .when(!in_thread && message.reply_count > 0, |el| {
+                        el.children(message_id.map(|message_id| {
+                            self.render_thread_link(message_id, message.reply_count, cx)
+                        }))
                     }),
             )
             .when(
-                self.last_acknowledged_message_id
-                    .is_some_and(|l| Some(l) == message_id),
+                !in_thread
+                    && self
+                        .last_acknowledged_message_id
+                        .is_some_and(|l| Some(l) == message_id),
                 |this| {
                     this.child(
                         h_flex()
@@ -559,8 +651,14 @@ impl ChatPanel {
                 },
             )
             .child(
-                self.render_popover_buttons(&cx, message_id, can_delete_message, can_edit_message)
-                    .mt_neg_2p5(),
+                self.render_popover_buttons(
+                    &cx,
+                    message_id,
+                    can_delete_message,
+                    can_edit_message,
+                    in_thread,
+                )
+                .mt_neg_2p5(),
             )
     }
 
@@ -585,6 +683,7 @@ impl ChatPanel {
         message_id: Option<u64>,
         can_delete_message: bool,
         can_edit_message: bool,
+        in_thread: bool,
     ) -> Div {
         h_flex()
             .absolute()
@@ -598,6 +697,49 @@ impl ChatPanel {
             })
             .bg(cx.theme().colors().element_background)
             .when_some(message_id, |el, message_id| {
+                let this = cx.view().clone();
+
+                el.child(
+                    self.render_popover_button(
+                        cx,
+                        div()
+                            .child(
+                                PopoverMenu::new(("react-menu", message_id))
+                                    .trigger(IconButton::new(
+                                        ("react", message_id),
+                                        IconName::Sparkle,
+                                    ))
+                                    .menu(move |cx| {
+                                        Some(Self::render_reaction_menu(&this, message_id, cx))
+                                    }),
+                            )
+                            .id("react")
+                            .tooltip(|cx| Tooltip::text("Add reaction", cx)),
+                    ),
+                )
+            })
+            .when(!in_thread, |el| {
+                el.when_some(message_id, |el, message_id| {
+                    el.child(
+                        self.render_popover_button(
+                            cx,
+                            div()
+                                .id("reply-in-thread")
+                                .child(
+                                    IconButton::new(
+                                        ("reply-in-thread", message_id),
+                                        IconName::MessageBubbles,
+                                    )
+                                    .on_click(cx.listener(
+                                        move |this, _, cx| this.open_thread(message_id, cx),
+                                    )),
+                                )
+                                .tooltip(|cx| Tooltip::text("Reply in thread", cx)),
+                        ),
+                    )
+                })
+            })
+            .when_some(message_id.filter(|_| !in_thread), |el, message_id| {
                 el.child(
                     self.render_popover_button(
                         cx,
@@ -731,6 +873,129 @@ impl ChatPanel {
         menu
     }
 
+    fn render_reaction_menu(
+        this: &View<Self>,
+        message_id: u64,
+        cx: &mut WindowContext,
+    ) -> View<ContextMenu> {
+        let menu = ContextMenu::build(cx, move |menu, cx| {
+            QUICK_REACTIONS.into_iter().fold(menu, |menu, emoji| {
+                menu.entry(
+                    emoji,
+                    None,
+                    cx.handler_for(&this, move |this, cx| {
+                        this.toggle_reaction(message_id, emoji.to_string(), cx)
+                    }),
+                )
+            })
+        });
+        this.update(cx, |this, cx| {
+            let subscription = cx.subscribe(&menu, |this: &mut Self, _, _: &DismissEvent, _| {
+                this.open_context_menu = None;
+            });
+            this.open_context_menu = Some((message_id, subscription));
+        });
+        menu
+    }
+
+    fn render_reactions(
+        &self,
+        message: &ChannelMessage,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<impl IntoElement> {
+        let ChannelMessageId::Saved(message_id) = message.id else {
+            return None;
+        };
+        let current_user_id = self.client.user_id();
+        let user_store = self.active_chat()?.read(cx).user_store().clone();
+
+        Some(h_flex().flex_wrap().gap_1().pt_0p5().children(
+            message.reactions.iter().enumerate().map(|(ix, reaction)| {
+                let reacted =
+                    current_user_id.map_or(false, |user_id| reaction.user_ids.contains(&user_id));
+                let reactors = reaction
+                    .user_ids
+                    .iter()
+                    .filter_map(|user_id| user_store.read(cx).get_cached_user(*user_id))
+                    .map(|user| format!("@{}", user.github_login))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let emoji = reaction.emoji.clone();
+
+                Button::new(
+                    ElementId::NamedInteger(format!("reaction-{message_id}").into(), ix),
+                    format!("{} {}", reaction.emoji, reaction.user_ids.len()),
+                )
+                .size(ButtonSize::Compact)
+                .label_size(LabelSize::Small)
+                .style(if reacted {
+                    ButtonStyle::Tinted(TintColor::Accent)
+                } else {
+                    ButtonStyle::Filled
+                })
+                .tooltip(move |cx| Tooltip::text(reactors.clone(), cx))
+                .on_click(cx.listener(move |this, _, cx| {
+                    this.toggle_reaction(message_id, emoji.clone(), cx)
+                }))
+            }),
+        ))
+    }
+
+    fn render_thread_link(
+        &self,
+        message_id: u64,
+        reply_count: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let label = if reply_count == 1 {
+            "1 reply".to_string()
+        } else {
+            format!("{reply_count} replies")
+        };
+
+        div().pt_0p5().child(
+            Button::new(("open-thread", message_id), label)
+                .size(ButtonSize::Compact)
+                .label_size(LabelSize::Small)
+                .color(Color::Accent)
+                .icon(IconName::MessageBubbles)
+                .icon_size(IconSize::Small)
+                .icon_color(Color::Accent)
+                .icon_position(IconPosition::Start)
+                .on_click(cx.listener(move |this, _, cx| this.open_thread(message_id, cx))),
+        )
+    }
+
+    fn render_thread_header(&self, root_message_id: u64, cx: &mut ViewContext<Self>) -> Div {
+        let reply_count = self.active_chat().and_then(|chat| {
+            chat.read(cx)
+                .thread(root_message_id)
+                .map(|thread| thread.root.reply_count)
+        });
+
+        h_flex()
+            .py_1()
+            .gap_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(
+                IconButton::new("close-thread", IconName::ArrowLeft)
+                    .shape(ui::IconButtonShape::Square)
+                    .tooltip(|cx| Tooltip::text("Back to chat", cx))
+                    .on_click(cx.listener(|this, _, cx| this.close_thread(cx))),
+            )
+            .child(Label::new("Thread").weight(FontWeight::BOLD))
+            .child(
+                Label::new(match reply_count {
+                    None => "Loading…".to_string(),
+                    Some(1) => "1 reply".to_string(),
+                    Some(count) => format!("{count} replies"),
+                })
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+            )
+    }
+
     fn render_markdown_with_mentions(
         language_registry: &Arc<LanguageRegistry>,
         current_user_id: u64,
@@ -786,7 +1051,7 @@ impl ChatPanel {
 
     fn send(&mut self, _: &Confirm, cx: &mut ViewContext<Self>) {
         if let Some((chat, _)) = self.active_chat.as_ref() {
-            let message = self
+            let mut message = self
                 .message_editor
                 .update(cx, |editor, cx| editor.take_message(cx));
 
@@ -802,6 +1067,9 @@ impl ChatPanel {
                     task.detach();
                 }
             } else {
+                if let Some(thread) = self.open_thread.as_ref() {
+                    message.reply_to_message_id = Some(thread.root_message_id);
+                }
                 if let Some(task) = chat
                     .update(cx, |chat, cx| chat.send_message(message, cx))
                     .log_err()
@@ -818,6 +1086,98 @@ impl ChatPanel {
         }
     }
 
+    fn toggle_reaction(&mut self, message_id: u64, emoji: String, cx: &mut ViewContext<Self>) {
+        if let Some((chat, _)) = self.active_chat.as_ref() {
+            chat.update(cx, |chat, cx| chat.toggle_reaction(message_id, emoji, cx))
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn open_thread(&mut self, root_message_id: u64, cx: &mut ViewContext<Self>) {
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        self.close_thread(cx);
+        self.cancel_edit_message(cx);
+        self.close_reply_preview(cx);
+
+        let view = cx.view().downgrade();
+        let list = ListState::new(0, gpui::ListAlignment::Top, px(1000.), move |ix, cx| {
+            if let Some(view) = view.upgrade() {
+                view.update(cx, |view, cx| view.render_thread_message(ix, cx))
+            } else {
+                div().into_any()
+            }
+        });
+        list.set_scroll_handler(cx.listener(move |this, event: &ListScrollEvent, cx| {
+            if event.visible_range.start < MESSAGE_LOADING_THRESHOLD {
+                if let Some(chat) = this.active_chat() {
+                    chat.update(cx, |chat, cx| {
+                        if let Some(task) = chat.load_more_thread_replies(root_message_id, cx) {
+                            task.detach();
+                        }
+                    })
+                }
+            }
+        }));
+        self.open_thread = Some(OpenThread {
+            root_message_id,
+            list,
+        });
+        chat.update(cx, |chat, cx| chat.open_thread(root_message_id, cx))
+            .detach_and_log_err(cx);
+        self.message_editor.focus_handle(cx).focus(cx);
+        cx.notify();
+    }
+
+    fn close_thread(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(thread) = self.open_thread.take() {
+            if let Some(chat) = self.active_chat() {
+                chat.update(cx, |chat, _| chat.close_thread(thread.root_message_id));
+            }
+            cx.notify();
+        }
+    }
+
+    fn search_messages(&mut self, _: &SearchMessages, cx: &mut ViewContext<Self>) {
+        let Some(chat) = self.active_chat() else {
+            return;
+        };
+        if let Some((message_search, _)) = self.message_search.as_ref() {
+            cx.focus_view(message_search);
+            return;
+        }
+
+        let message_search = cx.new_view(|cx| MessageSearch::new(chat, cx));
+        let subscription = cx.subscribe(&message_search, |this, _, event, cx| {
+            this.message_search = None;
+            match event {
+                MessageSearchEvent::SelectMessage(message_id) => {
+                    this.close_thread(cx);
+                    if let Some(channel_id) = this.channel_id(cx) {
+                        this.select_channel(channel_id, Some(*message_id), cx)
+                            .detach_and_log_err(cx);
+                    }
+                }
+                MessageSearchEvent::Dismissed => {}
+            }
+            this.message_editor.focus_handle(cx).focus(cx);
+            cx.notify();
+        });
+        cx.focus_view(&message_search);
+        self.message_search = Some((message_search, subscription));
+        cx.notify();
+    }
+
+    fn toggle_message_search(&mut self, cx: &mut ViewContext<Self>) {
+        if self.message_search.take().is_some() {
+            self.message_editor.focus_handle(cx).focus(cx);
+            cx.notify();
+        } else {
+            self.search_messages(&SearchMessages, cx);
+        }
+    }
+
     fn load_more_messages(&mut self, cx: &mut ViewContext<Self>) {
         if let Some((chat, _)) = self.active_chat.as_ref() {
             chat.update(cx, |channel, cx| {
@@ -935,6 +1295,7 @@ impl Render for ChatPanel {
             .track_focus(&self.focus_handle)
             .size_full()
             .on_action(cx.listener(Self::send))
+            .on_action(cx.listener(Self::search_messages))
             .child(
                 h_flex().child(
                     TabBar::new("chat_header").child(
@@ -942,6 +1303,7 @@ impl Render for ChatPanel {
                             .w_full()
                             .h(rems(ui::Tab::CONTAINER_HEIGHT_IN_REMS))
                             .px_2()
+                            .justify_between()
                             .child(Label::new(
                                 self.active_chat
                                     .as_ref()
@@ -949,12 +1311,41 @@ impl Render for ChatPanel {
                                         Some(format!("#{}", c.0.read(cx).channel(cx)?.name))
                                     })
                                     .unwrap_or("Chat".to_string()),
-                            )),
+                            ))
+                            .when(self.active_chat.is_some(), |el| {
+                                el.child(
+                                    IconButton::new(
+                                        "toggle-message-search",
+                                        IconName::MagnifyingGlass,
+                                    )
+                                    .icon_size(IconSize::Small)
+                                    .selected(self.message_search.is_some())
+                                    .tooltip(|cx| {
+                                        Tooltip::for_action("Search Messages", &SearchMessages, cx)
+                                    })
+                                    .on_click(
+                                        cx.listener(|this, _, cx| this.toggle_message_search(cx)),
+                                    ),
+                                )
+                            }),
                     ),
                 ),
             )
             .child(div().flex_grow().px_2().map(|this| {
-                if self.active_chat.is_some() {
+                if let Some((message_search, _)) = self.message_search.as_ref() {
+                    this.child(message_search.clone())
+                } else if let Some(open_thread) = self.open_thread.as_ref() {
+                    this.child(
+                        v_flex()
+                            .size_full()
+                            .child(self.render_thread_header(open_thread.root_message_id, cx))
+                            .child(
+                                div()
+                                    .flex_grow()
+                                    .child(list(open_thread.list.clone()).size_full()),
+                            ),
+                    )
+                } else if self.active_chat.is_some() {
                     this.child(list(self.message_list.clone()).size_full())
                 } else {
                     this.child(
@@ -1080,7 +1471,7 @@ impl Render for ChatPanel {
                         }))
                         .map(|el| el.child(self.message_editor.clone())),
                 )
-                .filter(|_| self.active_chat.is_some()),
+                .filter(|_| self.active_chat.is_some() && self.message_search.is_none()),
             )
             .into_any()
     }
@@ -1088,7 +1479,9 @@ impl Render for ChatPanel {
 
 impl FocusableView for ChatPanel {
     fn focus_handle(&self, cx: &AppContext) -> gpui::FocusHandle {
-        if self.active_chat.is_some() {
+        if let Some((message_search, _)) = self.message_search.as_ref() {
+            message_search.focus_handle(cx)
+        } else if self.active_chat.is_some() {
             self.message_editor.read(cx).focus_handle(cx)
         } else {
             self.focus_handle.clone()
@@ -1155,6 +1548,13 @@ impl Panel for ChatPanel {
 
 impl EventEmitter<PanelEvent> for ChatPanel {}
 
+fn is_continuation(last_message: &ChannelMessage, message: &ChannelMessage) -> bool {
+    let duration_since_last_message = message.timestamp - last_message.timestamp;
+    last_message.sender.id == message.sender.id
+        && last_message.id != message.id
+        && duration_since_last_message < Duration::from_secs(5 * 60)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1181,6 +1581,8 @@ mod tests {
             mentions: vec![(ranges[0].clone(), 101), (ranges[1].clone(), 102)],
             reply_to_message_id: None,
             edited_at: None,
+            reactions: Vec::new(),
+            reply_count: 0,
         };
 
         let message = ChatPanel::render_markdown_with_mentions(
@@ -1235,6 +1637,8 @@ mod tests {
             mentions: Vec::new(),
             reply_to_message_id: None,
             edited_at: None,
+            reactions: Vec::new(),
+            reply_count: 0,
         };
 
         let message = ChatPanel::render_markdown_with_mentions(
@@ -1282,6 +1686,8 @@ mod tests {
             mentions: Vec::new(),
             reply_to_message_id: None,
             edited_at: None,
+            reactions: Vec::new(),
+            reply_count: 0,
         };
 
         let message = ChatPanel::render_markdown_with_mentions(
@@ -0,0 +1,222 @@
+use channel::{ChannelChat, ChannelMessage, ChannelMessageId};
+use editor::{Editor, EditorEvent};
+use gpui::{
+    EventEmitter, FocusHandle, FocusableView, FontWeight, Model, Subscription, Task, View,
+    ViewContext,
+};
+use std::time::Duration;
+use time::{OffsetDateTime, UtcOffset};
+use ui::{prelude::*, Avatar, Button};
+use util::ResultExt;
+
+const SEARCH_DEBOUNCE_INTERVAL: Duration = Duration::from_millis(250);
+
+pub enum MessageSearchEvent {
+    /// A result was clicked, and the chat should scroll to it.
+    SelectMessage(u64),
+    Dismissed,
+}
+
+/// Searches the whole history of a channel's chat, listing the matching messages
+/// newest first.
+pub struct MessageSearch {
+    chat: Model<ChannelChat>,
+    query_editor: View<Editor>,
+    results: Vec<ChannelMessage>,
+    has_more_results: bool,
+    local_timezone: UtcOffset,
+    pending_search: Option<Task<()>>,
+    _subscription: Subscription,
+}
+
+impl EventEmitter<MessageSearchEvent> for MessageSearch {}
+
+impl MessageSearch {
+    pub fn new(chat: Model<ChannelChat>, cx: &mut ViewContext<Self>) -> Self {
+        let query_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_placeholder_text("Search messages…", cx);
+            editor
+        });
+        let subscription = cx.subscribe(&query_editor, |this, _, event, cx| {
+            if let EditorEvent::BufferEdited = event {
+                this.query_changed(cx);
+            }
+        });
+
+        Self {
+            chat,
+            query_editor,
+            results: Vec::new(),
+            has_more_results: false,
+            local_timezone: cx.local_timezone(),
+            pending_search: None,
+            _subscription: subscription,
+        }
+    }
+
+    fn query(&self, cx: &AppContext) -> String {
+        self.query_editor.read(cx).text(cx).trim().to_string()
+    }
+
+    fn query_changed(&mut self, cx: &mut ViewContext<Self>) {
+        self.results.clear();
+        self.has_more_results = false;
+        let query = self.query(cx);
+        if query.is_empty() {
+            self.pending_search = None;
+        } else {
+            self.pending_search = Some(cx.spawn(|this, mut cx| async move {
+                cx.background_executor()
+                    .timer(SEARCH_DEBOUNCE_INTERVAL)
+                    .await;
+                this.update(&mut cx, |this, cx| this.search(query, None, cx))
+                    .ok();
+            }));
+        }
+        cx.notify();
+    }
+
+    fn search(
+        &mut self,
+        query: String,
+        before_message_id: Option<u64>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let search = self.chat.update(cx, |chat, cx| {
+            chat.search_messages(query, before_message_id, cx)
+        });
+        self.pending_search = Some(cx.spawn(|this, mut cx| async move {
+            let results = search.await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_search = None;
+                if let Some((messages, done)) = results.log_err() {
+                    this.results.extend(messages.into_iter().rev());
+                    this.has_more_results = !done;
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn load_more(&mut self, cx: &mut ViewContext<Self>) {
+        if self.pending_search.is_some() {
+            return;
+        }
+        if let Some(ChannelMessageId::Saved(oldest_result_id)) =
+            self.results.last().map(|message| message.id)
+        {
+            let query = self.query(cx);
+            self.search(query, Some(oldest_result_id), cx);
+            cx.notify();
+        }
+    }
+
+    fn dismiss(&mut self, _: &editor::actions::Cancel, cx: &mut ViewContext<Self>) {
+        cx.emit(MessageSearchEvent::Dismissed);
+    }
+
+    fn render_result(&self, message: &ChannelMessage, cx: &ViewContext<Self>) -> impl IntoElement {
+        let message_id = match message.id {
+            ChannelMessageId::Saved(id) => id,
+            ChannelMessageId::Pending(id) => id as u64,
+        };
+
+        v_flex()
+            .id(("search-result", message_id))
+            .px_1p5()
+            .py_1()
+            .rounded_md()
+            .cursor_pointer()
+            .hover(|style| style.bg(cx.theme().colors().element_hover))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .text_ui_sm(cx)
+                    .child(Avatar::new(message.sender.avatar_uri.clone()).size(rems(1.)))
+                    .child(
+                        Label::new(message.sender.github_login.clone())
+                            .size(LabelSize::Small)
+                            .weight(FontWeight::BOLD),
+                    )
+                    .child(
+                        Label::new(time_format::format_localized_timestamp(
+                            message.timestamp,
+                            OffsetDateTime::now_utc(),
+                            self.local_timezone,
+                            time_format::TimestampFormat::EnhancedAbsolute,
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .child(
+                div()
+                    .text_ui_sm(cx)
+                    .overflow_hidden()
+                    .child(message.body.replace('\n', " ")),
+            )
+            .on_click(
+                cx.listener(move |_, _, cx| cx.emit(MessageSearchEvent::SelectMessage(message_id))),
+            )
+    }
+}
+
+impl Render for MessageSearch {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let is_searching = self.pending_search.is_some();
+        let has_query = !self.query(cx).is_empty();
+
+        v_flex()
+            .key_context("MessageSearch")
+            .on_action(cx.listener(Self::dismiss))
+            .size_full()
+            .child(
+                h_flex()
+                    .p_2()
+                    .gap_2()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Icon::new(IconName::MagnifyingGlass).color(Color::Muted))
+                    .child(self.query_editor.clone()),
+            )
+            .child(
+                v_flex()
+                    .id("message-search-results")
+                    .flex_1()
+                    .overflow_y_scroll()
+                    .p_2()
+                    .gap_1()
+                    .children(
+                        self.results
+                            .iter()
+                            .map(|message| self.render_result(message, cx)),
+                    )
+                    .when(self.has_more_results, |this| {
+                        this.child(
+                            Button::new("load-more-results", "Load More")
+                                .full_width()
+                                .disabled(is_searching)
+                                .on_click(cx.listener(|this, _, cx| this.load_more(cx))),
+                        )
+                    })
+                    .when(
+                        has_query && !is_searching && self.results.is_empty(),
+                        |this| {
+                            this.child(
+                                Label::new("No messages found.")
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                        },
+                    ),
+            )
+    }
+}
+
+impl FocusableView for MessageSearch {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.query_editor.focus_handle(cx)
+    }
+}
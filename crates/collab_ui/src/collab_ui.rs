@@ -4,6 +4,7 @@ pub mod collab_panel;
 pub mod notification_panel;
 pub mod notifications;
 mod panel_settings;
+pub mod review_comments;
 
 use std::{rc::Rc, sync::Arc};
 
@@ -32,6 +33,7 @@ pub fn init(app_state: &Arc<AppState>, cx: &mut AppContext) {
     collab_panel::init(cx);
     notification_panel::init(cx);
     notifications::init(&app_state, cx);
+    review_comments::init(&app_state.client, cx);
     title_bar::init(cx);
     vcs_menu::init(cx);
 }
@@ -0,0 +1,352 @@
+mod review_thread_store;
+mod review_thread_view;
+
+use client::Client;
+use collections::{HashMap, HashSet};
+use editor::{
+    display_map::{
+        BlockContext, BlockDisposition, BlockId, BlockProperties, BlockStyle, RenderBlock,
+    },
+    Anchor, Editor, EditorEvent,
+};
+use gpui::{
+    actions, AppContext, EntityId, Global, Model, Subscription, UpdateGlobal, View,
+    VisualContext as _, WeakView, WindowContext,
+};
+use project::Item as _;
+pub use review_thread_store::{
+    ReviewComment, ReviewThread, ReviewThreadStore, ReviewThreadStoreEvent,
+};
+use review_thread_view::{ReviewThreadView, ReviewThreadViewEvent};
+use std::{ops::Range, sync::Arc};
+use ui::prelude::*;
+use workspace::{notifications::NotificationId, Toast, Workspace};
+
+actions!(review_comments, [AddComment]);
+
+pub fn init(client: &Arc<Client>, cx: &mut AppContext) {
+    review_thread_store::init(client);
+    cx.set_global(ReviewComments::default());
+
+    cx.observe_new_views(|workspace: &mut Workspace, cx| {
+        let project = workspace.project().clone();
+        let project_id = project.entity_id();
+        let store = ReviewThreadStore::new(project, cx);
+        cx.global_mut::<ReviewComments>()
+            .stores
+            .insert(project_id, store.clone());
+        cx.on_release(move |_, _, cx| {
+            cx.global_mut::<ReviewComments>().stores.remove(&project_id);
+        })
+        .detach();
+
+        let workspace_handle = cx.view().clone();
+        cx.subscribe(&workspace_handle, move |workspace, _, event, cx| {
+            if let workspace::Event::ActiveItemChanged = event {
+                if let Some(editor) = workspace.active_item_as::<Editor>(cx) {
+                    let store = store.clone();
+                    ReviewComments::update_global(cx, |this, cx| {
+                        this.register_editor(&editor, store, cx)
+                    });
+                }
+            }
+        })
+        .detach();
+
+        workspace.register_action(|workspace, _: &AddComment, cx| {
+            ReviewComments::update_global(cx, |this, cx| this.add_comment(workspace, cx))
+        });
+    })
+    .detach();
+}
+
+/// Displays the review threads of shared projects in the editors that show the commented
+/// files, below the lines they're about.
+#[derive(Default)]
+struct ReviewComments {
+    stores: HashMap<EntityId, Model<ReviewThreadStore>>,
+    editors: HashMap<WeakView<Editor>, EditorReviewThreads>,
+}
+
+impl Global for ReviewComments {}
+
+struct EditorReviewThreads {
+    store: Model<ReviewThreadStore>,
+    blocks: HashMap<u64, ThreadBlock>,
+    /// The thread that's being written, which isn't on the server yet.
+    new_thread: Option<ThreadBlock>,
+    _subscriptions: Vec<Subscription>,
+}
+
+struct ThreadBlock {
+    block_id: BlockId,
+    view: View<ReviewThreadView>,
+    _subscription: Subscription,
+}
+
+enum ReviewThreadHighlight {}
+
+impl ReviewComments {
+    fn register_editor(
+        &mut self,
+        editor: &View<Editor>,
+        store: Model<ReviewThreadStore>,
+        cx: &mut WindowContext,
+    ) {
+        self.editors.retain(|editor, _| editor.upgrade().is_some());
+        if self.editors.contains_key(&editor.downgrade()) {
+            return;
+        }
+
+        let weak_editor = editor.downgrade();
+        let subscriptions = vec![
+            cx.subscribe(&store, {
+                let editor = weak_editor.clone();
+                move |_, _: &ReviewThreadStoreEvent, cx| {
+                    if let Some(editor) = editor.upgrade() {
+                        ReviewComments::update_global(cx, |this, cx| {
+                            this.refresh_editor(&editor, cx)
+                        });
+                    }
+                }
+            }),
+            cx.subscribe(editor, |editor, event: &EditorEvent, cx| match event {
+                EditorEvent::ExcerptsAdded { .. }
+                | EditorEvent::ExcerptsRemoved { .. }
+                | EditorEvent::ExcerptsExpanded { .. } => {
+                    ReviewComments::update_global(cx, |this, cx| this.refresh_editor(&editor, cx));
+                }
+                _ => {}
+            }),
+        ];
+        self.editors.insert(
+            weak_editor,
+            EditorReviewThreads {
+                store,
+                blocks: HashMap::default(),
+                new_thread: None,
+                _subscriptions: subscriptions,
+            },
+        );
+        self.refresh_editor(editor, cx);
+    }
+
+    /// Starts a thread on the newest selection of the active editor.
+    fn add_comment(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Workspace>) {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let Some(store) = self.stores.get(&workspace.project().entity_id()).cloned() else {
+            return;
+        };
+        if !store.read(cx).is_available() {
+            struct ReviewCommentsUnavailable;
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ReviewCommentsUnavailable>(),
+                    "Review comments can only be added to shared projects",
+                )
+                .autohide(),
+                cx,
+            );
+            return;
+        }
+
+        let target = editor.update(cx, |editor, cx| {
+            let selection = editor.selections.newest::<usize>(cx);
+            let multibuffer = editor.buffer().read(cx);
+            let snapshot = multibuffer.snapshot(cx);
+            let (buffer, range, excerpt_id) = multibuffer
+                .range_to_buffer_ranges(selection.range(), cx)
+                .into_iter()
+                .next()?;
+            buffer.read(cx).project_path(cx)?;
+            let buffer_snapshot = buffer.read(cx);
+            let range =
+                buffer_snapshot.anchor_before(range.start)..buffer_snapshot.anchor_after(range.end);
+            let position = snapshot.anchor_in_excerpt(excerpt_id, range.end)?;
+            Some((buffer, range, position))
+        });
+        let Some((buffer, range, position)) = target else {
+            return;
+        };
+
+        self.register_editor(&editor, store.clone(), cx);
+        let Some(editor_threads) = self.editors.get_mut(&editor.downgrade()) else {
+            return;
+        };
+        if let Some(new_thread) = editor_threads.new_thread.take() {
+            remove_blocks(&editor, [new_thread.block_id], cx);
+        }
+
+        let view = cx.new_view(|cx| ReviewThreadView::new_thread(store, buffer, range, cx));
+        editor_threads.new_thread = Some(insert_thread_block(&editor, position, view.clone(), cx));
+        cx.focus_view(&view);
+    }
+
+    /// Updates the blocks of an editor to show the threads on the files it displays.
+    fn refresh_editor(&mut self, editor: &View<Editor>, cx: &mut WindowContext) {
+        let Some(editor_threads) = self.editors.get_mut(&editor.downgrade()) else {
+            return;
+        };
+
+        let mut thread_ranges = HashMap::<u64, Range<Anchor>>::default();
+        let store = editor_threads.store.read(cx);
+        let multibuffer = editor.read(cx).buffer().read(cx);
+        let snapshot = multibuffer.snapshot(cx);
+        for buffer in multibuffer.all_buffers() {
+            let Some(project_path) = buffer.read(cx).project_path(cx) else {
+                continue;
+            };
+            let buffer_snapshot = buffer.read(cx).snapshot();
+            let excerpts = multibuffer.excerpts_for_buffer(&buffer, cx);
+            for thread in store.threads_for_path(&project_path) {
+                let range = thread.range_in_buffer(&buffer_snapshot);
+                let Some(excerpt_id) = excerpts.iter().find_map(|(excerpt_id, excerpt_range)| {
+                    let context = &excerpt_range.context;
+                    (context.start.cmp(&range.end, &buffer_snapshot).is_le()
+                        && context.end.cmp(&range.end, &buffer_snapshot).is_ge())
+                    .then_some(*excerpt_id)
+                }) else {
+                    continue;
+                };
+                let start = snapshot.anchor_in_excerpt(excerpt_id, range.start);
+                let end = snapshot.anchor_in_excerpt(excerpt_id, range.end);
+                if let Some((start, end)) = start.zip(end) {
+                    thread_ranges.insert(thread.id, start..end);
+                }
+            }
+        }
+        let unresolved_ranges = thread_ranges
+            .iter()
+            .filter(|(thread_id, _)| {
+                store
+                    .thread(**thread_id)
+                    .map_or(false, |thread| !thread.resolved)
+            })
+            .map(|(_, range)| range.clone())
+            .collect::<Vec<_>>();
+
+        let removed_block_ids = editor_threads
+            .blocks
+            .iter()
+            .filter(|(thread_id, _)| !thread_ranges.contains_key(thread_id))
+            .map(|(_, block)| block.block_id)
+            .collect::<Vec<_>>();
+        editor_threads
+            .blocks
+            .retain(|thread_id, _| thread_ranges.contains_key(thread_id));
+        remove_blocks(editor, removed_block_ids, cx);
+
+        let store = editor_threads.store.clone();
+        for (thread_id, range) in thread_ranges {
+            if editor_threads.blocks.contains_key(&thread_id) {
+                continue;
+            }
+            let store = store.clone();
+            let view = cx.new_view(|cx| ReviewThreadView::existing_thread(store, thread_id, cx));
+            let block = insert_thread_block(editor, range.end, view, cx);
+            editor_threads.blocks.insert(thread_id, block);
+        }
+        editor.update(cx, |editor, cx| {
+            editor.highlight_background::<ReviewThreadHighlight>(
+                &unresolved_ranges,
+                |colors| colors.editor_document_highlight_read_background,
+                cx,
+            )
+        });
+    }
+
+    fn handle_thread_view_event(
+        &mut self,
+        editor: &View<Editor>,
+        view: View<ReviewThreadView>,
+        event: &ReviewThreadViewEvent,
+        cx: &mut WindowContext,
+    ) {
+        let Some(editor_threads) = self.editors.get_mut(&editor.downgrade()) else {
+            return;
+        };
+        let block = match view.read(cx).thread_id() {
+            Some(thread_id) => editor_threads.blocks.get(&thread_id),
+            None => editor_threads.new_thread.as_ref(),
+        };
+        let Some(block) = block.filter(|block| block.view == view) else {
+            return;
+        };
+
+        match event {
+            ReviewThreadViewEvent::Resized => {
+                let height = view.read(cx).height();
+                let mut blocks = HashMap::default();
+                blocks.insert(block.block_id, (Some(height), build_thread_renderer(&view)));
+                editor.update(cx, |editor, cx| editor.replace_blocks(blocks, None, cx));
+            }
+            ReviewThreadViewEvent::Created | ReviewThreadViewEvent::Dismissed => {
+                if let Some(new_thread) = editor_threads.new_thread.take() {
+                    remove_blocks(editor, [new_thread.block_id], cx);
+                }
+                editor.focus_handle(cx).focus(cx);
+            }
+        }
+    }
+}
+
+fn insert_thread_block(
+    editor: &View<Editor>,
+    position: Anchor,
+    view: View<ReviewThreadView>,
+    cx: &mut WindowContext,
+) -> ThreadBlock {
+    let height = view.read(cx).height();
+    let block_id = editor.update(cx, |editor, cx| {
+        editor.insert_blocks(
+            [BlockProperties {
+                position,
+                height,
+                style: BlockStyle::Sticky,
+                render: build_thread_renderer(&view),
+                disposition: BlockDisposition::Below,
+            }],
+            None,
+            cx,
+        )[0]
+    });
+    let weak_editor = editor.downgrade();
+    let subscription = cx.subscribe(&view, move |view, event, cx| {
+        if let Some(editor) = weak_editor.upgrade() {
+            ReviewComments::update_global(cx, |this, cx| {
+                this.handle_thread_view_event(&editor, view, event, cx)
+            });
+        }
+    });
+
+    ThreadBlock {
+        block_id,
+        view,
+        _subscription: subscription,
+    }
+}
+
+fn remove_blocks(
+    editor: &View<Editor>,
+    block_ids: impl IntoIterator<Item = BlockId>,
+    cx: &mut WindowContext,
+) {
+    let block_ids = block_ids.into_iter().collect::<HashSet<_>>();
+    if !block_ids.is_empty() {
+        editor.update(cx, |editor, cx| editor.remove_blocks(block_ids, None, cx));
+    }
+}
+
+fn build_thread_renderer(view: &View<ReviewThreadView>) -> RenderBlock {
+    let view = view.clone();
+    Box::new(move |cx: &mut BlockContext| {
+        div()
+            .w_full()
+            .pl(cx.gutter_dimensions.full_width())
+            .pr_4()
+            .child(view.clone())
+            .into_any_element()
+    })
+}
@@ -0,0 +1,364 @@
+use anyhow::{anyhow, Context as _, Result};
+use client::{proto, Client, TypedEnvelope, UserStore};
+use gpui::{
+    AppContext, AsyncAppContext, Context as _, EventEmitter, Model, ModelContext, Subscription,
+    Task,
+};
+use language::{
+    proto::{deserialize_anchor_range, serialize_anchor_range},
+    Anchor, Buffer, BufferSnapshot, Point, ToPoint,
+};
+use project::{Item as _, Project, ProjectPath, WorktreeId};
+use std::{ops::Range, ops::RangeInclusive, path::Path, sync::Arc};
+use time::OffsetDateTime;
+use util::{ResultExt, TryFutureExt};
+
+pub fn init(client: &Arc<Client>) {
+    client.add_model_message_handler(ReviewThreadStore::handle_update_review_thread);
+}
+
+/// A conversation about a range of a file in a shared project.
+#[derive(Clone, Debug)]
+pub struct ReviewThread {
+    pub id: u64,
+    pub project_path: ProjectPath,
+    /// The commented range, in the buffer that was open when the thread was started.
+    pub range: Range<Anchor>,
+    /// The commented rows when the thread was started. They're used to place the thread
+    /// when `range` can't be resolved, e.g. because the host reopened the file since.
+    pub rows: RangeInclusive<u32>,
+    pub resolved: bool,
+    pub comments: Vec<ReviewComment>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReviewComment {
+    pub id: u64,
+    pub sender_id: u64,
+    pub body: String,
+    pub timestamp: OffsetDateTime,
+}
+
+pub enum ReviewThreadStoreEvent {
+    ThreadsChanged,
+}
+
+/// The review threads of a shared project, which are stored on the server and kept in
+/// sync between everyone in the project.
+pub struct ReviewThreadStore {
+    project: Model<Project>,
+    client: Arc<Client>,
+    user_store: Model<UserStore>,
+    threads: Vec<ReviewThread>,
+    project_id: Option<u64>,
+    client_subscription: Option<client::Subscription>,
+    load_threads: Option<Task<Option<()>>>,
+    _project_subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<ReviewThreadStoreEvent> for ReviewThreadStore {}
+
+impl ReviewThread {
+    /// Returns the commented range in `buffer`, which must be the file this thread is on.
+    pub fn range_in_buffer(&self, buffer: &BufferSnapshot) -> Range<Anchor> {
+        if buffer.can_resolve(&self.range.start) && buffer.can_resolve(&self.range.end) {
+            return self.range.clone();
+        }
+
+        let max_row = buffer.max_point().row;
+        let start_row = (*self.rows.start()).min(max_row);
+        let end_row = (*self.rows.end()).clamp(start_row, max_row);
+        buffer.anchor_before(Point::new(start_row, 0))
+            ..buffer.anchor_after(Point::new(end_row, buffer.line_len(end_row)))
+    }
+}
+
+impl ReviewThreadStore {
+    pub fn new(project: Model<Project>, cx: &mut AppContext) -> Model<Self> {
+        cx.new_model(|cx| {
+            let mut this = Self {
+                client: project.read(cx).client(),
+                user_store: project.read(cx).user_store(),
+                threads: Vec::new(),
+                project_id: None,
+                client_subscription: None,
+                load_threads: None,
+                _project_subscriptions: vec![
+                    cx.observe(&project, Self::handle_project_changed),
+                    cx.subscribe(&project, Self::handle_project_event),
+                ],
+                project: project.clone(),
+            };
+            this.handle_project_changed(project, cx);
+            this
+        })
+    }
+
+    pub fn project(&self) -> &Model<Project> {
+        &self.project
+    }
+
+    pub fn user_store(&self) -> &Model<UserStore> {
+        &self.user_store
+    }
+
+    /// Whether threads can be started, which requires the project to be shared.
+    pub fn is_available(&self) -> bool {
+        self.project_id.is_some()
+    }
+
+    pub fn threads(&self) -> &[ReviewThread] {
+        &self.threads
+    }
+
+    pub fn thread(&self, thread_id: u64) -> Option<&ReviewThread> {
+        self.threads
+            .binary_search_by_key(&thread_id, |thread| thread.id)
+            .ok()
+            .map(|ix| &self.threads[ix])
+    }
+
+    pub fn threads_for_path<'a>(
+        &'a self,
+        project_path: &'a ProjectPath,
+    ) -> impl 'a + Iterator<Item = &'a ReviewThread> {
+        self.threads
+            .iter()
+            .filter(move |thread| thread.project_path == *project_path)
+    }
+
+    /// Starts a thread on `range` of `buffer`, returning the id of the new thread.
+    pub fn create_thread(
+        &mut self,
+        buffer: &Model<Buffer>,
+        range: Range<Anchor>,
+        body: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<u64>> {
+        let Some(project_id) = self.project_id else {
+            return Task::ready(Err(anyhow!("project is not shared")));
+        };
+        let buffer = buffer.read(cx);
+        let Some(project_path) = buffer.project_path(cx) else {
+            return Task::ready(Err(anyhow!("buffer has no file")));
+        };
+        let start_row = range.start.to_point(buffer).row;
+        let end_row = range.end.to_point(buffer).row;
+
+        let request = self.client.request(proto::CreateReviewThread {
+            project_id,
+            worktree_id: project_path.worktree_id.to_proto(),
+            path: project_path.path.to_string_lossy().to_string(),
+            range: Some(serialize_anchor_range(range)),
+            start_row,
+            end_row,
+            body,
+        });
+        cx.spawn(|this, mut cx| async move {
+            let response = request.await?;
+            let thread = response.thread.context("invalid thread")?;
+            this.update(&mut cx, |this, cx| {
+                let thread_id = thread.id;
+                this.insert_thread(thread, cx)?;
+                Ok(thread_id)
+            })?
+        })
+    }
+
+    pub fn reply(
+        &mut self,
+        thread_id: u64,
+        body: String,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some(project_id) = self.project_id else {
+            return Task::ready(Err(anyhow!("project is not shared")));
+        };
+        let request = self.client.request(proto::AddReviewComment {
+            project_id,
+            thread_id,
+            body,
+        });
+        self.update_thread_with_response(request, cx)
+    }
+
+    pub fn set_resolved(
+        &mut self,
+        thread_id: u64,
+        resolved: bool,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some(project_id) = self.project_id else {
+            return Task::ready(Err(anyhow!("project is not shared")));
+        };
+        let request = self.client.request(proto::SetReviewThreadResolved {
+            project_id,
+            thread_id,
+            resolved,
+        });
+        self.update_thread_with_response(request, cx)
+    }
+
+    fn update_thread_with_response(
+        &mut self,
+        request: impl 'static + std::future::Future<Output = Result<proto::ReviewThreadResponse>>,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        cx.spawn(|this, mut cx| async move {
+            let response = request.await?;
+            let thread = response.thread.context("invalid thread")?;
+            this.update(&mut cx, |this, cx| this.insert_thread(thread, cx))?
+        })
+    }
+
+    /// Adds the given thread, replacing the existing thread with the same id.
+    fn insert_thread(
+        &mut self,
+        thread: proto::ReviewThread,
+        cx: &mut ModelContext<Self>,
+    ) -> Result<()> {
+        let thread = deserialize_thread(thread)?;
+        self.load_senders(
+            thread
+                .comments
+                .iter()
+                .map(|comment| comment.sender_id)
+                .collect(),
+            cx,
+        );
+        match self
+            .threads
+            .binary_search_by_key(&thread.id, |existing| existing.id)
+        {
+            Ok(ix) => self.threads[ix] = thread,
+            Err(ix) => self.threads.insert(ix, thread),
+        }
+        cx.emit(ReviewThreadStoreEvent::ThreadsChanged);
+        cx.notify();
+        Ok(())
+    }
+
+    fn load_threads(&mut self, cx: &mut ModelContext<Self>) {
+        let Some(project_id) = self.project_id else {
+            return;
+        };
+
+        let request = self.client.request(proto::GetReviewThreads { project_id });
+        self.load_threads = Some(cx.spawn(|this, mut cx| {
+            async move {
+                let response = request.await?;
+                this.update(&mut cx, |this, cx| {
+                    this.threads = response
+                        .threads
+                        .into_iter()
+                        .filter_map(|thread| deserialize_thread(thread).log_err())
+                        .collect();
+                    let sender_ids = this
+                        .threads
+                        .iter()
+                        .flat_map(|thread| &thread.comments)
+                        .map(|comment| comment.sender_id)
+                        .collect();
+                    this.load_senders(sender_ids, cx);
+                    cx.emit(ReviewThreadStoreEvent::ThreadsChanged);
+                    cx.notify();
+                })
+            }
+            .log_err()
+        }));
+    }
+
+    fn load_senders(&mut self, mut sender_ids: Vec<u64>, cx: &mut ModelContext<Self>) {
+        sender_ids.sort_unstable();
+        sender_ids.dedup();
+        let load_users = self
+            .user_store
+            .update(cx, |user_store, cx| user_store.get_users(sender_ids, cx));
+        cx.spawn(|this, mut cx| async move {
+            load_users.await?;
+            // Rerender the threads with the senders' names and avatars.
+            this.update(&mut cx, |_, cx| {
+                cx.emit(ReviewThreadStoreEvent::ThreadsChanged);
+                cx.notify();
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    async fn handle_update_review_thread(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::UpdateReviewThread>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        let thread = envelope.payload.thread.context("invalid thread")?;
+        this.update(&mut cx, |this, cx| this.insert_thread(thread, cx))?
+    }
+
+    fn handle_project_changed(&mut self, _: Model<Project>, cx: &mut ModelContext<Self>) {
+        let project = self.project.read(cx);
+        let project_id = if project.is_shared() {
+            project.remote_id()
+        } else {
+            None
+        };
+        if project_id == self.project_id {
+            return;
+        }
+
+        self.project_id = project_id;
+        self.threads.clear();
+        self.load_threads = None;
+        self.client_subscription = project_id.and_then(|project_id| {
+            self.client
+                .subscribe_to_entity(project_id)
+                .log_err()
+                .map(|subscription| subscription.set_model(&cx.handle(), &mut cx.to_async()))
+        });
+        self.load_threads(cx);
+        cx.emit(ReviewThreadStoreEvent::ThreadsChanged);
+        cx.notify();
+    }
+
+    fn handle_project_event(
+        &mut self,
+        _: Model<Project>,
+        event: &project::Event,
+        cx: &mut ModelContext<Self>,
+    ) {
+        match event {
+            // Threads may have changed while we were disconnected.
+            project::Event::Reshared | project::Event::HostReshared | project::Event::Rejoined => {
+                self.load_threads(cx)
+            }
+            _ => {}
+        }
+    }
+}
+
+fn deserialize_thread(thread: proto::ReviewThread) -> Result<ReviewThread> {
+    let range = deserialize_anchor_range(thread.range.context("invalid range")?)?;
+    let comments = thread
+        .comments
+        .into_iter()
+        .map(|comment| {
+            Ok(ReviewComment {
+                id: comment.id,
+                sender_id: comment.sender_id,
+                body: comment.body,
+                timestamp: OffsetDateTime::from_unix_timestamp(comment.timestamp as i64)?,
+            })
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(ReviewThread {
+        id: thread.id,
+        project_path: ProjectPath {
+            worktree_id: WorktreeId::from_proto(thread.worktree_id),
+            path: Path::new(&thread.path).into(),
+        },
+        range,
+        rows: thread.start_row..=thread.end_row,
+        resolved: thread.resolved,
+        comments,
+    })
+}
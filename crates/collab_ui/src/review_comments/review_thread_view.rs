@@ -0,0 +1,380 @@
+use super::{ReviewThread, ReviewThreadStore};
+use editor::{Editor, EditorEvent};
+use gpui::{
+    AppContext, EventEmitter, FocusHandle, FocusableView, FontWeight, Model, Subscription, Task,
+    View, ViewContext,
+};
+use language::{Anchor, Buffer};
+use std::ops::Range;
+use time::{OffsetDateTime, UtcOffset};
+use ui::{prelude::*, Avatar, Button, Tooltip};
+use util::ResultExt;
+
+/// The most lines the reply editor grows to before it scrolls.
+const MAX_REPLY_LINES: usize = 6;
+/// Comments are estimated to wrap after this many characters when sizing the thread's block.
+const ESTIMATED_LINE_WIDTH: usize = 100;
+
+pub enum ReviewThreadViewEvent {
+    /// The thread needs a different number of lines in the editor.
+    Resized,
+    /// A new thread was posted to the server.
+    Created,
+    /// A new thread was discarded before it was posted.
+    Dismissed,
+}
+
+enum ThreadTarget {
+    New {
+        buffer: Model<Buffer>,
+        range: Range<Anchor>,
+    },
+    Existing {
+        thread_id: u64,
+    },
+}
+
+/// Displays a review thread below the commented lines of an editor, along with an editor
+/// to reply to it. A new thread only has the editor, until its first comment is posted.
+pub struct ReviewThreadView {
+    store: Model<ReviewThreadStore>,
+    target: ThreadTarget,
+    reply_editor: View<Editor>,
+    expanded: bool,
+    height: u8,
+    local_timezone: UtcOffset,
+    pending_submit: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<ReviewThreadViewEvent> for ReviewThreadView {}
+
+impl ReviewThreadView {
+    pub fn new_thread(
+        store: Model<ReviewThreadStore>,
+        buffer: Model<Buffer>,
+        range: Range<Anchor>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        Self::new(store, ThreadTarget::New { buffer, range }, cx)
+    }
+
+    pub fn existing_thread(
+        store: Model<ReviewThreadStore>,
+        thread_id: u64,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        Self::new(store, ThreadTarget::Existing { thread_id }, cx)
+    }
+
+    fn new(
+        store: Model<ReviewThreadStore>,
+        target: ThreadTarget,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let reply_editor = cx.new_view(|cx| {
+            let mut editor = Editor::auto_height(MAX_REPLY_LINES, cx);
+            let placeholder = match target {
+                ThreadTarget::New { .. } => "Add a comment…",
+                ThreadTarget::Existing { .. } => "Reply…",
+            };
+            editor.set_placeholder_text(placeholder, cx);
+            editor
+        });
+        let subscriptions = vec![
+            cx.observe(&store, |this, _, cx| {
+                this.update_height(cx);
+                cx.notify();
+            }),
+            cx.subscribe(&reply_editor, |this, _, event, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_height(cx);
+                }
+            }),
+        ];
+
+        let mut this = Self {
+            store,
+            target,
+            reply_editor,
+            expanded: false,
+            height: 0,
+            local_timezone: cx.local_timezone(),
+            pending_submit: None,
+            _subscriptions: subscriptions,
+        };
+        this.height = this.compute_height(cx);
+        this
+    }
+
+    pub fn thread_id(&self) -> Option<u64> {
+        match self.target {
+            ThreadTarget::New { .. } => None,
+            ThreadTarget::Existing { thread_id } => Some(thread_id),
+        }
+    }
+
+    /// The number of editor lines that the thread's block should take up.
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    fn thread<'a>(&self, cx: &'a AppContext) -> Option<&'a ReviewThread> {
+        self.store.read(cx).thread(self.thread_id()?)
+    }
+
+    fn is_collapsed(&self, cx: &AppContext) -> bool {
+        !self.expanded && self.thread(cx).map_or(false, |thread| thread.resolved)
+    }
+
+    fn update_height(&mut self, cx: &mut ViewContext<Self>) {
+        let height = self.compute_height(cx);
+        if height != self.height {
+            self.height = height;
+            cx.emit(ReviewThreadViewEvent::Resized);
+        }
+    }
+
+    fn compute_height(&self, cx: &AppContext) -> u8 {
+        // The header, and the border and padding around the thread.
+        let mut lines = 2;
+        if self.is_collapsed(cx) {
+            return lines;
+        }
+
+        if let Some(thread) = self.thread(cx) {
+            for comment in &thread.comments {
+                lines += 1;
+                lines += comment
+                    .body
+                    .lines()
+                    .map(|line| line.chars().count().div_ceil(ESTIMATED_LINE_WIDTH).max(1))
+                    .sum::<usize>();
+            }
+        }
+        let reply_lines = self.reply_editor.read(cx).text(cx).lines().count();
+        // The reply editor, and the buttons below it.
+        lines += reply_lines.clamp(1, MAX_REPLY_LINES) + 2;
+        lines.min(u8::MAX as usize) as u8
+    }
+
+    fn submit(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        let body = self.reply_editor.read(cx).text(cx).trim().to_string();
+        if body.is_empty() || self.pending_submit.is_some() {
+            return;
+        }
+
+        let task = match &self.target {
+            ThreadTarget::New { buffer, range } => self.store.update(cx, |store, cx| {
+                store.create_thread(buffer, range.clone(), body, cx)
+            }),
+            ThreadTarget::Existing { thread_id } => {
+                let thread_id = *thread_id;
+                let reply = self
+                    .store
+                    .update(cx, |store, cx| store.reply(thread_id, body, cx));
+                cx.background_executor().spawn(async move {
+                    reply.await?;
+                    anyhow::Ok(thread_id)
+                })
+            }
+        };
+        self.pending_submit = Some(cx.spawn(|this, mut cx| async move {
+            let result = task.await;
+            this.update(&mut cx, |this, cx| {
+                this.pending_submit = None;
+                if result.log_err().is_some() {
+                    this.reply_editor
+                        .update(cx, |editor, cx| editor.set_text("", cx));
+                    if let ThreadTarget::New { .. } = this.target {
+                        cx.emit(ReviewThreadViewEvent::Created);
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+        cx.notify();
+    }
+
+    fn cancel(&mut self, _: &editor::actions::Cancel, cx: &mut ViewContext<Self>) {
+        match self.target {
+            ThreadTarget::New { .. } => cx.emit(ReviewThreadViewEvent::Dismissed),
+            ThreadTarget::Existing { .. } => cx.propagate(),
+        }
+    }
+
+    fn toggle_resolved(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(thread) = self.thread(cx) else {
+            return;
+        };
+        let (thread_id, resolved) = (thread.id, !thread.resolved);
+        self.expanded = false;
+        self.store
+            .update(cx, |store, cx| store.set_resolved(thread_id, resolved, cx))
+            .detach_and_log_err(cx);
+    }
+
+    fn toggle_expanded(&mut self, cx: &mut ViewContext<Self>) {
+        self.expanded = !self.expanded;
+        self.update_height(cx);
+        cx.notify();
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let thread = self.thread(cx);
+        let title = match thread {
+            None => "New Comment".to_string(),
+            Some(thread) if thread.resolved => "Resolved".to_string(),
+            Some(thread) if thread.comments.len() == 1 => "1 Comment".to_string(),
+            Some(thread) => format!("{} Comments", thread.comments.len()),
+        };
+        let resolved = thread.map(|thread| thread.resolved);
+
+        h_flex()
+            .gap_2()
+            .child(Icon::new(IconName::MessageBubbles).color(Color::Muted))
+            .child(Label::new(title).size(LabelSize::Small).color(Color::Muted))
+            .child(div().flex_1())
+            .when(resolved == Some(true), |this| {
+                this.child(
+                    IconButton::new(
+                        "toggle-expanded",
+                        if self.expanded {
+                            IconName::ChevronUp
+                        } else {
+                            IconName::ChevronDown
+                        },
+                    )
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Toggle Resolved Thread", cx))
+                    .on_click(cx.listener(|this, _, cx| this.toggle_expanded(cx))),
+                )
+            })
+            .when_some(resolved, |this, resolved| {
+                this.child(
+                    Button::new(
+                        "toggle-resolved",
+                        if resolved { "Reopen" } else { "Resolve" },
+                    )
+                    .label_size(LabelSize::Small)
+                    .on_click(cx.listener(|this, _, cx| this.toggle_resolved(cx))),
+                )
+            })
+    }
+
+    fn render_comments(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let store = self.store.read(cx);
+        let user_store = store.user_store().read(cx);
+        let now = OffsetDateTime::now_utc();
+
+        v_flex().gap_1().children(
+            self.thread(cx)
+                .into_iter()
+                .flat_map(|thread| &thread.comments)
+                .map(|comment| {
+                    let sender = user_store.get_cached_user(comment.sender_id);
+                    v_flex()
+                        .child(
+                            h_flex()
+                                .gap_2()
+                                .text_ui_sm(cx)
+                                .children(sender.as_ref().map(|sender| {
+                                    Avatar::new(sender.avatar_uri.clone()).size(rems(1.))
+                                }))
+                                .child(
+                                    Label::new(
+                                        sender.as_ref().map_or("Unknown".into(), |sender| {
+                                            sender.github_login.clone()
+                                        }),
+                                    )
+                                    .size(LabelSize::Small)
+                                    .weight(FontWeight::BOLD),
+                                )
+                                .child(
+                                    Label::new(time_format::format_localized_timestamp(
+                                        comment.timestamp,
+                                        now,
+                                        self.local_timezone,
+                                        time_format::TimestampFormat::EnhancedAbsolute,
+                                    ))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                                ),
+                        )
+                        .child(div().text_ui_sm(cx).child(comment.body.clone()))
+                }),
+        )
+    }
+
+    fn render_reply(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let is_new = matches!(self.target, ThreadTarget::New { .. });
+        let is_submitting = self.pending_submit.is_some();
+
+        v_flex()
+            .gap_1()
+            .child(
+                div()
+                    .px_1()
+                    .py_0p5()
+                    .rounded_md()
+                    .border_1()
+                    .border_color(cx.theme().colors().border_variant)
+                    .bg(cx.theme().colors().editor_background)
+                    .child(self.reply_editor.clone()),
+            )
+            .child(
+                h_flex()
+                    .gap_1()
+                    .justify_end()
+                    .when(is_new, |this| {
+                        this.child(
+                            Button::new("cancel", "Cancel")
+                                .label_size(LabelSize::Small)
+                                .on_click(cx.listener(|_, _, cx| {
+                                    cx.emit(ReviewThreadViewEvent::Dismissed)
+                                })),
+                        )
+                    })
+                    .child(
+                        Button::new("submit", if is_new { "Comment" } else { "Reply" })
+                            .label_size(LabelSize::Small)
+                            .style(ButtonStyle::Filled)
+                            .disabled(is_submitting)
+                            .tooltip(|cx| Tooltip::for_action("Submit", &menu::Confirm, cx))
+                            .on_click(cx.listener(|this, _, cx| this.submit(&menu::Confirm, cx))),
+                    ),
+            )
+    }
+}
+
+impl Render for ReviewThreadView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let is_collapsed = self.is_collapsed(cx);
+
+        v_flex()
+            .key_context("ReviewThread")
+            .on_action(cx.listener(Self::submit))
+            .on_action(cx.listener(Self::cancel))
+            .w_full()
+            .max_w(rems(48.))
+            .my_1()
+            .p_2()
+            .gap_2()
+            .rounded_md()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().elevated_surface_background)
+            .child(self.render_header(cx))
+            .when(!is_collapsed, |this| {
+                this.child(self.render_comments(cx))
+                    .child(self.render_reply(cx))
+            })
+    }
+}
+
+impl FocusableView for ReviewThreadView {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.reply_editor.focus_handle(cx)
+    }
+}
@@ -0,0 +1,1201 @@
+mod line_diff;
+
+use std::{
+    any::{Any, TypeId},
+    mem,
+    ops::Range,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context as _, Result};
+use collections::HashSet;
+use editor::{
+    display_map::{BlockDisposition, BlockId, BlockProperties, BlockStyle},
+    Editor, EditorEvent, ExcerptId, ExcerptRange, MultiBuffer,
+};
+use gpui::{
+    actions, AnyElement, AnyView, AppContext, EventEmitter, FocusHandle, FocusableView, Hsla,
+    Model, PathPromptOptions, Render, Subscription, Task, View, ViewContext, VisualContext,
+    WindowContext,
+};
+use language::{Anchor, Buffer, Capability, Language, LanguageRegistry, Point};
+use project::Project;
+use ui::{prelude::*, Divider, IconButtonShape, Tooltip};
+use util::ResultExt;
+use workspace::{
+    item::{Item, ItemEvent, ItemHandle as _, TabContentParams},
+    ItemNavHistory, Workspace,
+};
+
+pub use line_diff::{line_diff, LineHunk};
+
+actions!(
+    diff_view,
+    [
+        CompareWithSaved,
+        CompareWithFile,
+        CompareClipboardWithSelection,
+        ToggleLayout,
+        CopyHunkToLeft,
+        CopyHunkToRight,
+    ]
+);
+
+const RECALCULATE_DIFF_DEBOUNCE: Duration = Duration::from_millis(50);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace
+            .register_action(DiffView::compare_with_saved)
+            .register_action(DiffView::compare_with_file)
+            .register_action(DiffView::compare_clipboard_with_selection);
+    })
+    .detach();
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DiffLayout {
+    /// Both texts next to each other, with their lines aligned and their scroll positions synchronized.
+    #[default]
+    SideBySide,
+    /// A single multibuffer, showing the removed lines of each hunk above the added ones.
+    Inline,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+enum DiffRowHighlight {}
+
+struct DiffSide {
+    buffer: Model<Buffer>,
+    title: SharedString,
+    editor: View<Editor>,
+    filler_blocks: HashSet<BlockId>,
+}
+
+/// A hunk of the diff, anchored in both buffers so that it can be copied from one
+/// side to the other even if the buffers were edited since it was computed.
+#[derive(Clone, Debug)]
+struct DiffHunk {
+    left_rows: Range<u32>,
+    right_rows: Range<u32>,
+    left_range: Range<Anchor>,
+    right_range: Range<Anchor>,
+}
+
+impl DiffHunk {
+    fn rows(&self, side: Side) -> &Range<u32> {
+        match side {
+            Side::Left => &self.left_rows,
+            Side::Right => &self.right_rows,
+        }
+    }
+}
+
+/// Shows the line-based differences between two buffers, the right one being
+/// considered as the newer version of the left one.
+pub struct DiffView {
+    left: DiffSide,
+    right: DiffSide,
+    inline_buffer: Model<MultiBuffer>,
+    inline_editor: View<Editor>,
+    layout: DiffLayout,
+    hunks: Vec<DiffHunk>,
+    focused_side: Side,
+    /// Whether the left buffer holds the saved contents of the right buffer's file,
+    /// and should be reloaded when that file changes.
+    left_is_saved_text: bool,
+    recalculate_diff_task: Option<Task<()>>,
+    reload_saved_text_task: Option<Task<Result<()>>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DiffView {
+    pub fn new(
+        left_buffer: Model<Buffer>,
+        left_title: impl Into<SharedString>,
+        right_buffer: Model<Buffer>,
+        right_title: impl Into<SharedString>,
+        project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let left_editor =
+            cx.new_view(|cx| Editor::for_buffer(left_buffer.clone(), Some(project.clone()), cx));
+        let right_editor =
+            cx.new_view(|cx| Editor::for_buffer(right_buffer.clone(), Some(project.clone()), cx));
+        let inline_buffer = cx.new_model(|_| MultiBuffer::new(0, Capability::ReadWrite));
+        let inline_editor = cx.new_view(|cx| {
+            Editor::for_multibuffer(inline_buffer.clone(), Some(project.clone()), false, cx)
+        });
+
+        let left_focus_handle = left_editor.focus_handle(cx);
+        let right_focus_handle = right_editor.focus_handle(cx);
+        let subscriptions = vec![
+            cx.subscribe(&left_buffer, |this, _, event, cx| {
+                this.handle_buffer_event(Side::Left, event, cx)
+            }),
+            cx.subscribe(&right_buffer, |this, _, event, cx| {
+                this.handle_buffer_event(Side::Right, event, cx)
+            }),
+            cx.subscribe(&left_editor, |this, _, event, cx| {
+                this.handle_editor_event(Side::Left, event, cx)
+            }),
+            cx.subscribe(&right_editor, |this, _, event, cx| {
+                this.handle_editor_event(Side::Right, event, cx)
+            }),
+            cx.on_focus(&left_focus_handle, |this, _| this.focused_side = Side::Left),
+            cx.on_focus(&right_focus_handle, |this, _| {
+                this.focused_side = Side::Right
+            }),
+        ];
+
+        let mut this = Self {
+            left: DiffSide {
+                buffer: left_buffer,
+                title: left_title.into(),
+                editor: left_editor,
+                filler_blocks: HashSet::default(),
+            },
+            right: DiffSide {
+                buffer: right_buffer,
+                title: right_title.into(),
+                editor: right_editor,
+                filler_blocks: HashSet::default(),
+            },
+            inline_buffer,
+            inline_editor,
+            layout: DiffLayout::default(),
+            hunks: Vec::new(),
+            focused_side: Side::Right,
+            left_is_saved_text: false,
+            recalculate_diff_task: None,
+            reload_saved_text_task: None,
+            _subscriptions: subscriptions,
+        };
+        this.recalculate_diff(Duration::ZERO, cx);
+        this
+    }
+
+    /// Compares the active editor's buffer with the contents of its file on disk.
+    fn compare_with_saved(
+        workspace: &mut Workspace,
+        _: &CompareWithSaved,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let Some(buffer) = active_buffer(workspace, cx) else {
+            return;
+        };
+        let Some(file) = buffer.read(cx).file().and_then(|file| file.as_local()) else {
+            return;
+        };
+        let load_saved_text = file.load(cx);
+        let title = file.file_name(cx).to_string_lossy().to_string();
+        let language = buffer.read(cx).language().cloned();
+        let project = workspace.project().clone();
+        let language_registry = project.read(cx).languages().clone();
+
+        cx.spawn(|workspace, mut cx| async move {
+            let saved_text = load_saved_text.await?;
+            workspace.update(&mut cx, |workspace, cx| {
+                let saved_buffer = scratch_buffer(saved_text, language, language_registry, cx);
+                saved_buffer.update(cx, |buffer, cx| {
+                    buffer.set_capability(Capability::ReadOnly, cx)
+                });
+                let diff_view = cx.new_view(|cx| {
+                    let mut diff_view = DiffView::new(
+                        saved_buffer,
+                        format!("{title} (saved)"),
+                        buffer,
+                        title,
+                        project,
+                        cx,
+                    );
+                    diff_view.left_is_saved_text = true;
+                    diff_view
+                });
+                workspace.add_item_to_active_pane(Box::new(diff_view), None, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Prompts for a file, and compares it with the active editor's buffer.
+    fn compare_with_file(
+        workspace: &mut Workspace,
+        _: &CompareWithFile,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let Some(buffer) = active_buffer(workspace, cx) else {
+            return;
+        };
+        let title = buffer_title(&buffer, cx);
+        let project = workspace.project().clone();
+        let paths = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        });
+
+        cx.spawn(|workspace, mut cx| async move {
+            let Some(abs_path) = paths.await?.and_then(|paths| paths.into_iter().next()) else {
+                return Ok(());
+            };
+            let open_buffer = project.update(&mut cx, |project, cx| {
+                let find_worktree = project.find_or_create_local_worktree(&abs_path, false, cx);
+                cx.spawn(|project, mut cx| async move {
+                    let (worktree, relative_path) = find_worktree.await?;
+                    let worktree_id = worktree.update(&mut cx, |worktree, _| worktree.id())?;
+                    project
+                        .update(&mut cx, |project, cx| {
+                            project.open_buffer((worktree_id, relative_path), cx)
+                        })?
+                        .await
+                })
+            })?;
+            let other_buffer = open_buffer
+                .await
+                .with_context(|| format!("opening {abs_path:?} for comparison"))?;
+            workspace.update(&mut cx, |workspace, cx| {
+                let other_title = buffer_title(&other_buffer, cx);
+                let diff_view = cx.new_view(|cx| {
+                    DiffView::new(other_buffer, other_title, buffer, title, project, cx)
+                });
+                workspace.add_item_to_active_pane(Box::new(diff_view), None, cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Compares the clipboard's contents with the newest selection of the active editor,
+    /// or with the whole buffer if that selection is empty.
+    fn compare_clipboard_with_selection(
+        workspace: &mut Workspace,
+        _: &CompareClipboardWithSelection,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+            return;
+        };
+        let Some(clipboard) = cx.read_from_clipboard() else {
+            return;
+        };
+        let project = workspace.project().clone();
+        let language_registry = project.read(cx).languages().clone();
+        let (selected_text, language, singleton_buffer) = editor.update(cx, |editor, cx| {
+            let buffer = editor.buffer().read(cx);
+            let snapshot = buffer.snapshot(cx);
+            let selection = editor.selections.newest::<usize>(cx);
+            let selected_text = (!selection.is_empty()).then(|| {
+                snapshot
+                    .text_for_range(selection.range())
+                    .collect::<String>()
+            });
+            let language = snapshot.language_at(selection.head()).cloned();
+            (selected_text, language, buffer.as_singleton())
+        });
+
+        let (right_buffer, right_title) = match (selected_text, singleton_buffer) {
+            (Some(selected_text), _) => (
+                scratch_buffer(
+                    selected_text,
+                    language.clone(),
+                    language_registry.clone(),
+                    cx,
+                ),
+                SharedString::from("Selection"),
+            ),
+            (None, Some(buffer)) => {
+                let title = buffer_title(&buffer, cx);
+                (buffer, title)
+            }
+            (None, None) => return,
+        };
+        let clipboard_buffer =
+            scratch_buffer(clipboard.text().clone(), language, language_registry, cx);
+        let diff_view = cx.new_view(|cx| {
+            DiffView::new(
+                clipboard_buffer,
+                "Clipboard",
+                right_buffer,
+                right_title,
+                project,
+                cx,
+            )
+        });
+        workspace.add_item_to_active_pane(Box::new(diff_view), None, cx);
+    }
+
+    fn side(&self, side: Side) -> &DiffSide {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
+    fn handle_buffer_event(
+        &mut self,
+        side: Side,
+        event: &language::Event,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            language::Event::Edited | language::Event::Reloaded => {
+                self.recalculate_diff(RECALCULATE_DIFF_DEBOUNCE, cx)
+            }
+            language::Event::Saved | language::Event::FileHandleChanged
+                if side == Side::Right && self.left_is_saved_text =>
+            {
+                self.reload_saved_text(cx)
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_editor_event(&mut self, side: Side, event: &EditorEvent, cx: &mut ViewContext<Self>) {
+        if let EditorEvent::ScrollPositionChanged { local: true, .. } = event {
+            self.sync_scroll(side, cx);
+        }
+        if side == Side::Right {
+            cx.emit(event.clone());
+        }
+    }
+
+    fn reload_saved_text(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(file) = self
+            .right
+            .buffer
+            .read(cx)
+            .file()
+            .and_then(|file| file.as_local())
+        else {
+            return;
+        };
+        let load_saved_text = file.load(cx);
+        let saved_buffer = self.left.buffer.clone();
+        self.reload_saved_text_task = Some(cx.spawn(|_, mut cx| async move {
+            let saved_text = load_saved_text.await?;
+            saved_buffer.update(&mut cx, |buffer, cx| {
+                if buffer.text() != saved_text {
+                    buffer.set_text(saved_text, cx);
+                }
+            })
+        }));
+    }
+
+    /// Keeps the other side scrolled to the same position, which shows the same hunks
+    /// thanks to the filler blocks aligning both sides.
+    fn sync_scroll(&mut self, source: Side, cx: &mut ViewContext<Self>) {
+        if self.layout != DiffLayout::SideBySide {
+            return;
+        }
+        let (source, target) = match source {
+            Side::Left => (&self.left.editor, &self.right.editor),
+            Side::Right => (&self.right.editor, &self.left.editor),
+        };
+        let position = source.update(cx, |editor, cx| editor.scroll_position(cx));
+        target.update(cx, |editor, cx| {
+            let target_position = editor.scroll_position(cx);
+            if (target_position.x - position.x).abs() > f32::EPSILON
+                || (target_position.y - position.y).abs() > f32::EPSILON
+            {
+                editor.set_scroll_position(position, cx);
+            }
+        });
+    }
+
+    fn recalculate_diff(&mut self, debounce: Duration, cx: &mut ViewContext<Self>) {
+        let left_snapshot = self.left.buffer.read(cx).text_snapshot();
+        let right_snapshot = self.right.buffer.read(cx).text_snapshot();
+        self.recalculate_diff_task = Some(cx.spawn(|this, mut cx| async move {
+            if !debounce.is_zero() {
+                cx.background_executor().timer(debounce).await;
+            }
+            let hunks = cx
+                .background_executor()
+                .spawn(async move {
+                    let left_text = left_snapshot.text();
+                    let right_text = right_snapshot.text();
+                    line_diff(&left_text, &right_text)
+                        .into_iter()
+                        .map(|hunk| DiffHunk {
+                            left_range: rows_to_anchor_range(&hunk.old_rows, &left_snapshot),
+                            right_range: rows_to_anchor_range(&hunk.new_rows, &right_snapshot),
+                            left_rows: hunk.old_rows,
+                            right_rows: hunk.new_rows,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .await;
+            this.update(&mut cx, |this, cx| this.set_hunks(hunks, cx))
+                .log_err();
+        }));
+    }
+
+    fn set_hunks(&mut self, hunks: Vec<DiffHunk>, cx: &mut ViewContext<Self>) {
+        let hunk_rows_changed = self.hunks.len() != hunks.len()
+            || self.hunks.iter().zip(&hunks).any(|(old_hunk, new_hunk)| {
+                old_hunk.left_rows != new_hunk.left_rows
+                    || old_hunk.right_rows != new_hunk.right_rows
+            });
+        self.hunks = hunks;
+        self.update_side(Side::Left, cx);
+        self.update_side(Side::Right, cx);
+        if hunk_rows_changed && self.layout == DiffLayout::Inline {
+            self.rebuild_inline_buffer(cx);
+        }
+        cx.notify();
+    }
+
+    /// Highlights the changed rows of one side, and inserts filler blocks wherever the
+    /// other side has more lines so that both sides stay aligned.
+    fn update_side(&mut self, side: Side, cx: &mut ViewContext<Self>) {
+        let highlight_color = match side {
+            Side::Left => removed_color(cx),
+            Side::Right => added_color(cx),
+        };
+        let filler_color = cx.theme().colors().editor_subheader_background;
+        let other_side = match side {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        };
+        let hunks = self
+            .hunks
+            .iter()
+            .map(|hunk| {
+                let other_rows = hunk.rows(other_side);
+                (hunk.rows(side).clone(), other_rows.end - other_rows.start)
+            })
+            .collect::<Vec<_>>();
+
+        let diff_side = match side {
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+        };
+        let old_blocks = mem::take(&mut diff_side.filler_blocks);
+        diff_side.filler_blocks = diff_side.editor.update(cx, |editor, cx| {
+            editor.remove_blocks(old_blocks, None, cx);
+            editor.clear_row_highlights::<DiffRowHighlight>();
+
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            let max_row = snapshot.max_point().row;
+            let mut blocks = Vec::new();
+            for (rows, other_row_count) in hunks {
+                if !rows.is_empty() {
+                    let start = snapshot.anchor_before(Point::new(rows.start, 0));
+                    let end = snapshot.anchor_before(Point::new(rows.end - 1, 0));
+                    editor.highlight_rows::<DiffRowHighlight>(
+                        start..=end,
+                        Some(highlight_color),
+                        false,
+                        cx,
+                    );
+                }
+
+                let mut filler_height = other_row_count.saturating_sub(rows.end - rows.start);
+                let (row, disposition) = if rows.end <= max_row {
+                    (rows.end, BlockDisposition::Above)
+                } else {
+                    (max_row, BlockDisposition::Below)
+                };
+                let position = snapshot.anchor_before(Point::new(row, 0));
+                while filler_height > 0 {
+                    let height = filler_height.min(u8::MAX as u32) as u8;
+                    filler_height -= height as u32;
+                    blocks.push(BlockProperties {
+                        position,
+                        height,
+                        style: BlockStyle::Flex,
+                        render: Box::new(move |_| {
+                            div().size_full().bg(filler_color).into_any_element()
+                        }),
+                        disposition,
+                    });
+                }
+            }
+            editor.insert_blocks(blocks, None, cx).into_iter().collect()
+        });
+    }
+
+    /// Fills the inline multibuffer with the right buffer, inserting the removed lines
+    /// of each hunk from the left buffer above the lines that replaced them.
+    fn rebuild_inline_buffer(&mut self, cx: &mut ViewContext<Self>) {
+        let right_row_count = self.right.buffer.read(cx).max_point().row + 1;
+        let mut excerpt_rows = Vec::new();
+        let mut right_start = 0;
+        for hunk in &self.hunks {
+            if hunk.left_rows.is_empty() {
+                continue;
+            }
+            if right_start < hunk.right_rows.start {
+                excerpt_rows.push((Side::Right, right_start..hunk.right_rows.start));
+            }
+            excerpt_rows.push((Side::Left, hunk.left_rows.clone()));
+            right_start = hunk.right_rows.start;
+        }
+        if right_start < right_row_count {
+            excerpt_rows.push((Side::Right, right_start..right_row_count));
+        }
+
+        let scroll_position = self
+            .inline_editor
+            .update(cx, |editor, cx| editor.scroll_position(cx));
+        let left_buffer = self.left.buffer.clone();
+        let right_buffer = self.right.buffer.clone();
+        let excerpts = self.inline_buffer.update(cx, |multibuffer, cx| {
+            multibuffer.clear(cx);
+            excerpt_rows
+                .into_iter()
+                .filter_map(|(side, rows)| {
+                    let buffer = match side {
+                        Side::Left => left_buffer.clone(),
+                        Side::Right => right_buffer.clone(),
+                    };
+                    let snapshot = buffer.read(cx).text_snapshot();
+                    let end_row = (rows.end - 1).min(snapshot.max_point().row);
+                    let context = snapshot.anchor_before(Point::new(rows.start, 0))
+                        ..snapshot.anchor_after(Point::new(end_row, snapshot.line_len(end_row)));
+                    let excerpt_id = multibuffer
+                        .push_excerpts(
+                            buffer,
+                            [ExcerptRange {
+                                context: context.clone(),
+                                primary: None,
+                            }],
+                            cx,
+                        )
+                        .pop()?;
+                    Some((side, rows, excerpt_id, context))
+                })
+                .collect::<Vec<(Side, Range<u32>, ExcerptId, Range<Anchor>)>>()
+        });
+
+        let added_color = added_color(cx);
+        let removed_color = removed_color(cx);
+        let right_snapshot = self.right.buffer.read(cx).text_snapshot();
+        let hunks = self.hunks.clone();
+        let inline_buffer = self.inline_buffer.clone();
+        self.inline_editor.update(cx, |editor, cx| {
+            editor.clear_row_highlights::<DiffRowHighlight>();
+            let snapshot = inline_buffer.read(cx).snapshot(cx);
+            for (side, rows, excerpt_id, context) in &excerpts {
+                let highlights = match side {
+                    Side::Left => vec![(context.clone(), removed_color)],
+                    Side::Right => hunks
+                        .iter()
+                        .filter(|hunk| {
+                            !hunk.right_rows.is_empty()
+                                && rows.start <= hunk.right_rows.start
+                                && hunk.right_rows.end <= rows.end
+                        })
+                        .map(|hunk| {
+                            let start =
+                                right_snapshot.anchor_before(Point::new(hunk.right_rows.start, 0));
+                            let end = right_snapshot
+                                .anchor_before(Point::new(hunk.right_rows.end - 1, 0));
+                            (start..end, added_color)
+                        })
+                        .collect(),
+                };
+                for (range, color) in highlights {
+                    let (Some(start), Some(end)) = (
+                        snapshot.anchor_in_excerpt(*excerpt_id, range.start),
+                        snapshot.anchor_in_excerpt(*excerpt_id, range.end),
+                    ) else {
+                        continue;
+                    };
+                    editor.highlight_rows::<DiffRowHighlight>(start..=end, Some(color), false, cx);
+                }
+            }
+            editor.set_scroll_position(scroll_position, cx);
+        });
+    }
+
+    fn toggle_layout(&mut self, _: &ToggleLayout, cx: &mut ViewContext<Self>) {
+        self.layout = match self.layout {
+            DiffLayout::SideBySide => DiffLayout::Inline,
+            DiffLayout::Inline => DiffLayout::SideBySide,
+        };
+        if self.layout == DiffLayout::Inline {
+            self.rebuild_inline_buffer(cx);
+        }
+        cx.focus_self();
+        cx.notify();
+    }
+
+    fn copy_hunk_to_left(&mut self, _: &CopyHunkToLeft, cx: &mut ViewContext<Self>) {
+        self.copy_hunk(Side::Right, cx);
+    }
+
+    fn copy_hunk_to_right(&mut self, _: &CopyHunkToRight, cx: &mut ViewContext<Self>) {
+        self.copy_hunk(Side::Left, cx);
+    }
+
+    /// Replaces the lines of the hunk under the cursor with the ones from the given side.
+    fn copy_hunk(&mut self, source: Side, cx: &mut ViewContext<Self>) {
+        let Some(hunk) = self.hunk_at_cursor(cx) else {
+            return;
+        };
+        let (source_buffer, source_range, target_buffer, target_range) = match source {
+            Side::Left => (
+                &self.left.buffer,
+                hunk.left_range,
+                &self.right.buffer,
+                hunk.right_range,
+            ),
+            Side::Right => (
+                &self.right.buffer,
+                hunk.right_range,
+                &self.left.buffer,
+                hunk.left_range,
+            ),
+        };
+        if target_buffer.read(cx).read_only() {
+            return;
+        }
+        let text = source_buffer
+            .read(cx)
+            .text_for_range(source_range)
+            .collect::<String>();
+        target_buffer.update(cx, |buffer, cx| {
+            buffer.edit([(target_range, text)], None, cx);
+        });
+    }
+
+    fn hunk_at_cursor(&self, cx: &AppContext) -> Option<DiffHunk> {
+        let (side, row) = match self.layout {
+            DiffLayout::SideBySide => {
+                let editor = self.side(self.focused_side).editor.read(cx);
+                let head = editor.selections.newest::<Point>(cx).head();
+                (self.focused_side, head.row)
+            }
+            DiffLayout::Inline => {
+                let editor = self.inline_editor.read(cx);
+                let head = editor.selections.newest::<Point>(cx).head();
+                let (buffer, offset, _) = self
+                    .inline_buffer
+                    .read(cx)
+                    .point_to_buffer_offset(head, cx)?;
+                let side = if buffer == self.left.buffer {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+                (side, buffer.read(cx).offset_to_point(offset).row)
+            }
+        };
+        self.hunks
+            .iter()
+            .find(|hunk| {
+                let rows = hunk.rows(side);
+                rows.contains(&row) || (rows.is_empty() && rows.start == row)
+            })
+            .cloned()
+    }
+
+    fn active_editor(&self) -> &View<Editor> {
+        match self.layout {
+            DiffLayout::SideBySide => &self.side(self.focused_side).editor,
+            DiffLayout::Inline => &self.inline_editor,
+        }
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let layout_tooltip = match self.layout {
+            DiffLayout::SideBySide => "Show Inline Diff",
+            DiffLayout::Inline => "Show Side-by-Side Diff",
+        };
+        let change_count = match self.hunks.len() {
+            0 => "No changes".to_string(),
+            1 => "1 change".to_string(),
+            count => format!("{count} changes"),
+        };
+
+        h_flex()
+            .w_full()
+            .px_2()
+            .py_1()
+            .gap_2()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .bg(cx.theme().colors().toolbar_background)
+            .child(
+                h_flex()
+                    .flex_1()
+                    .gap_2()
+                    .child(Label::new(self.left.title.clone()))
+                    .when(self.layout == DiffLayout::Inline, |this| {
+                        this.child(Icon::new(IconName::ArrowRight).size(IconSize::Small))
+                            .child(Label::new(self.right.title.clone()))
+                    }),
+            )
+            .when(self.layout == DiffLayout::SideBySide, |this| {
+                this.child(
+                    h_flex()
+                        .flex_1()
+                        .child(Label::new(self.right.title.clone())),
+                )
+            })
+            .child(Label::new(change_count).color(Color::Muted))
+            .child(
+                IconButton::new("copy-hunk-to-left", IconName::ArrowLeft)
+                    .shape(IconButtonShape::Square)
+                    .disabled(self.left.buffer.read(cx).read_only())
+                    .tooltip(|cx| Tooltip::for_action("Copy Hunk to Left", &CopyHunkToLeft, cx))
+                    .on_click(|_, cx| cx.dispatch_action(Box::new(CopyHunkToLeft))),
+            )
+            .child(
+                IconButton::new("copy-hunk-to-right", IconName::ArrowRight)
+                    .shape(IconButtonShape::Square)
+                    .disabled(self.right.buffer.read(cx).read_only())
+                    .tooltip(|cx| Tooltip::for_action("Copy Hunk to Right", &CopyHunkToRight, cx))
+                    .on_click(|_, cx| cx.dispatch_action(Box::new(CopyHunkToRight))),
+            )
+            .child(
+                IconButton::new("toggle-diff-layout", IconName::Split)
+                    .shape(IconButtonShape::Square)
+                    .selected(self.layout == DiffLayout::Inline)
+                    .tooltip(move |cx| Tooltip::for_action(layout_tooltip, &ToggleLayout, cx))
+                    .on_click(|_, cx| cx.dispatch_action(Box::new(ToggleLayout))),
+            )
+    }
+}
+
+impl EventEmitter<EditorEvent> for DiffView {}
+
+impl FocusableView for DiffView {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.active_editor().focus_handle(cx)
+    }
+}
+
+impl Render for DiffView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("DiffView")
+            .size_full()
+            .on_action(cx.listener(Self::toggle_layout))
+            .on_action(cx.listener(Self::copy_hunk_to_left))
+            .on_action(cx.listener(Self::copy_hunk_to_right))
+            .child(self.render_header(cx))
+            .child(match self.layout {
+                DiffLayout::SideBySide => h_flex()
+                    .flex_1()
+                    .size_full()
+                    .child(div().flex_1().h_full().child(self.left.editor.clone()))
+                    .child(Divider::vertical())
+                    .child(div().flex_1().h_full().child(self.right.editor.clone()))
+                    .into_any_element(),
+                DiffLayout::Inline => div()
+                    .flex_1()
+                    .size_full()
+                    .child(self.inline_editor.clone())
+                    .into_any_element(),
+            })
+    }
+}
+
+impl Item for DiffView {
+    type Event = EditorEvent;
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> AnyElement {
+        Label::new(format!("{} ↔ {}", self.left.title, self.right.title))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
+        Some(format!("Diff of {} and {}", self.left.title, self.right.title).into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("diff view")
+    }
+
+    fn deactivated(&mut self, cx: &mut ViewContext<Self>) {
+        for editor in [&self.left.editor, &self.right.editor, &self.inline_editor] {
+            editor.update(cx, |editor, cx| editor.deactivated(cx));
+        }
+    }
+
+    fn navigate(&mut self, data: Box<dyn Any>, cx: &mut ViewContext<Self>) -> bool {
+        self.right
+            .editor
+            .update(cx, |editor, cx| editor.navigate(data, cx))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &AppContext,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::Item),
+    ) {
+        self.right.editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(&mut self, nav_history: ItemNavHistory, cx: &mut ViewContext<Self>) {
+        self.right.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn is_dirty(&self, cx: &AppContext) -> bool {
+        self.right.buffer.read(cx).is_dirty()
+    }
+
+    fn has_conflict(&self, cx: &AppContext) -> bool {
+        self.right.buffer.read(cx).has_conflict()
+    }
+
+    fn can_save(&self, cx: &AppContext) -> bool {
+        self.right.buffer.read(cx).file().is_some()
+    }
+
+    fn save(
+        &mut self,
+        format: bool,
+        project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        self.right.editor.save(format, project, cx)
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a View<Self>,
+        _: &'a AppContext,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.active_editor().to_any())
+        } else {
+            None
+        }
+    }
+
+    fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
+        for editor in [&self.left.editor, &self.right.editor, &self.inline_editor] {
+            editor.update(cx, |editor, cx| editor.added_to_workspace(workspace, cx));
+        }
+    }
+}
+
+fn active_buffer(workspace: &Workspace, cx: &AppContext) -> Option<Model<Buffer>> {
+    workspace
+        .active_item_as::<Editor>(cx)?
+        .read(cx)
+        .buffer()
+        .read(cx)
+        .as_singleton()
+}
+
+fn buffer_title(buffer: &Model<Buffer>, cx: &AppContext) -> SharedString {
+    buffer
+        .read(cx)
+        .file()
+        .map(|file| file.file_name(cx).to_string_lossy().to_string().into())
+        .unwrap_or_else(|| "untitled".into())
+}
+
+fn scratch_buffer(
+    text: String,
+    language: Option<Arc<Language>>,
+    language_registry: Arc<LanguageRegistry>,
+    cx: &mut WindowContext,
+) -> Model<Buffer> {
+    cx.new_model(|cx| {
+        let mut buffer = Buffer::local(text, cx);
+        buffer.set_language_registry(language_registry);
+        buffer.set_language(language, cx);
+        buffer
+    })
+}
+
+fn rows_to_anchor_range(rows: &Range<u32>, snapshot: &text::BufferSnapshot) -> Range<Anchor> {
+    let max_point = snapshot.max_point();
+    let point_for_row = |row: u32| {
+        if row > max_point.row {
+            max_point
+        } else {
+            Point::new(row, 0)
+        }
+    };
+    snapshot.anchor_before(point_for_row(rows.start))
+        ..snapshot.anchor_before(point_for_row(rows.end))
+}
+
+fn added_color(cx: &AppContext) -> Hsla {
+    let mut color = cx.theme().status().git().created;
+    color.fade_out(0.7);
+    color
+}
+
+fn removed_color(cx: &AppContext) -> Hsla {
+    let mut color = cx.theme().status().git().deleted;
+    color.fade_out(0.7);
+    color
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Fs as _};
+    use std::path::Path;
+    use workspace::AppState;
+
+    #[gpui::test]
+    async fn test_layouts(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let left_buffer = cx.new_model(|cx| Buffer::local("one\ntwo\nthree\nfour\n", cx));
+        let right_buffer = cx.new_model(|cx| Buffer::local("one\nTWO\nthree\nfive\nsix\n", cx));
+        let (diff_view, cx) = cx.add_window_view(|cx| {
+            DiffView::new(
+                left_buffer.clone(),
+                "left",
+                right_buffer.clone(),
+                "right",
+                project,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        // Diffs open side by side, with the right side active.
+        diff_view.update(cx, |diff_view, cx| {
+            assert_eq!(diff_view.layout, DiffLayout::SideBySide);
+            assert!(diff_view.active_editor() == &diff_view.right.editor);
+            assert_eq!(hunk_rows(diff_view), vec![(1..2, 1..2), (3..4, 3..5)]);
+            assert_eq!(diff_view.inline_buffer.read(cx).snapshot(cx).text(), "");
+        });
+
+        // The inline layout shows the removed lines above the ones replacing them.
+        diff_view.update(cx, |diff_view, cx| {
+            diff_view.toggle_layout(&ToggleLayout, cx)
+        });
+        diff_view.update(cx, |diff_view, cx| {
+            assert_eq!(diff_view.layout, DiffLayout::Inline);
+            assert!(diff_view.active_editor() == &diff_view.inline_editor);
+            assert_eq!(
+                diff_view.inline_buffer.read(cx).snapshot(cx).text(),
+                "one\ntwo\nTWO\nthree\nfour\nfive\nsix\n"
+            );
+
+            diff_view.inline_editor.update(cx, |editor, cx| {
+                editor.change_selections(None, cx, |s| {
+                    s.select_ranges([Point::new(4, 0)..Point::new(4, 0)])
+                })
+            });
+            let hunk = diff_view.hunk_at_cursor(cx).unwrap();
+            assert_eq!((hunk.left_rows, hunk.right_rows), (3..4, 3..5));
+        });
+
+        // Edits are reflected in the inline layout.
+        right_buffer.update(cx, |buffer, cx| buffer.edit([(4..7, "two")], None, cx));
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+        diff_view.update(cx, |diff_view, cx| {
+            assert_eq!(hunk_rows(diff_view), vec![(3..4, 3..5)]);
+            assert_eq!(
+                diff_view.inline_buffer.read(cx).snapshot(cx).text(),
+                "one\ntwo\nthree\nfour\nfive\nsix\n"
+            );
+        });
+
+        diff_view.update(cx, |diff_view, cx| {
+            diff_view.toggle_layout(&ToggleLayout, cx)
+        });
+        diff_view.update(cx, |diff_view, _| {
+            assert_eq!(diff_view.layout, DiffLayout::SideBySide);
+            assert!(diff_view.active_editor() == &diff_view.right.editor);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_compare_with_saved(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        fs.create_dir(Path::new("/dir")).await.unwrap();
+        fs.insert_file("/dir/a.txt", b"one\ntwo\n".to_vec()).await;
+        let project = Project::test(fs, ["/dir".as_ref()], cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project, cx));
+
+        let editor = workspace
+            .update(cx, |workspace, cx| {
+                workspace.open_abs_path("/dir/a.txt".into(), true, cx)
+            })
+            .await
+            .unwrap()
+            .downcast::<Editor>()
+            .unwrap();
+        let buffer = editor.update(cx, |editor, cx| {
+            editor.buffer().read(cx).as_singleton().unwrap()
+        });
+        buffer.update(cx, |buffer, cx| {
+            buffer.edit([(4..7, "TWO\nthree")], None, cx)
+        });
+
+        workspace.update(cx, |workspace, cx| {
+            DiffView::compare_with_saved(workspace, &CompareWithSaved, cx)
+        });
+        cx.run_until_parked();
+
+        let diff_view = workspace
+            .update(cx, |workspace, cx| workspace.active_item_as::<DiffView>(cx))
+            .unwrap();
+        diff_view.update(cx, |diff_view, cx| {
+            assert!(diff_view.left_is_saved_text);
+            assert_eq!(diff_view.left.title.as_ref(), "a.txt (saved)");
+            assert_eq!(diff_view.right.title.as_ref(), "a.txt");
+            assert_eq!(diff_view.right.buffer, buffer);
+
+            let saved_buffer = diff_view.left.buffer.read(cx);
+            assert_eq!(saved_buffer.text(), "one\ntwo\n");
+            assert!(saved_buffer.read_only());
+            assert_eq!(hunk_rows(diff_view), vec![(1..2, 1..3)]);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_copy_hunks(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let left_buffer = cx.new_model(|cx| Buffer::local("one\ntwo\nthree\nfour\n", cx));
+        let right_buffer = cx.new_model(|cx| Buffer::local("one\nTWO\nthree\nfive\nsix\n", cx));
+        let (diff_view, cx) = cx.add_window_view(|cx| {
+            DiffView::new(
+                left_buffer.clone(),
+                "left",
+                right_buffer.clone(),
+                "right",
+                project,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+        diff_view.update(cx, |diff_view, _| {
+            assert_eq!(hunk_rows(diff_view), vec![(1..2, 1..2), (3..4, 3..5)]);
+            assert_eq!(diff_view.left.filler_blocks.len(), 1);
+            assert!(diff_view.right.filler_blocks.is_empty());
+        });
+
+        diff_view.update(cx, |diff_view, cx| {
+            diff_view.right.editor.update(cx, |editor, cx| {
+                editor.change_selections(None, cx, |s| {
+                    s.select_ranges([Point::new(4, 0)..Point::new(4, 0)])
+                })
+            });
+            diff_view.focused_side = Side::Right;
+            diff_view.copy_hunk_to_left(&CopyHunkToLeft, cx);
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            left_buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\ntwo\nthree\nfive\nsix\n"
+        );
+        diff_view.update(cx, |diff_view, _| {
+            assert_eq!(hunk_rows(diff_view), vec![(1..2, 1..2)]);
+            assert!(diff_view.left.filler_blocks.is_empty());
+        });
+
+        diff_view.update(cx, |diff_view, cx| {
+            diff_view.left.editor.update(cx, |editor, cx| {
+                editor.change_selections(None, cx, |s| {
+                    s.select_ranges([Point::new(1, 2)..Point::new(1, 2)])
+                })
+            });
+            diff_view.focused_side = Side::Left;
+            diff_view.copy_hunk_to_right(&CopyHunkToRight, cx);
+        });
+        cx.executor().advance_clock(RECALCULATE_DIFF_DEBOUNCE);
+        cx.run_until_parked();
+        assert_eq!(
+            right_buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\ntwo\nthree\nfive\nsix\n"
+        );
+        diff_view.update(cx, |diff_view, _| assert!(diff_view.hunks.is_empty()));
+    }
+
+    #[gpui::test]
+    async fn test_copy_to_read_only_side(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let left_buffer = cx.new_model(|cx| {
+            let mut buffer = Buffer::local("one\ntwo\n", cx);
+            buffer.set_capability(Capability::ReadOnly, cx);
+            buffer
+        });
+        let right_buffer = cx.new_model(|cx| Buffer::local("one\n", cx));
+        let (diff_view, cx) = cx.add_window_view(|cx| {
+            DiffView::new(
+                left_buffer.clone(),
+                "saved",
+                right_buffer.clone(),
+                "current",
+                project,
+                cx,
+            )
+        });
+        cx.run_until_parked();
+
+        diff_view.update(cx, |diff_view, cx| {
+            assert_eq!(hunk_rows(diff_view), vec![(1..2, 1..1)]);
+            assert!(diff_view.left.filler_blocks.is_empty());
+            assert_eq!(diff_view.right.filler_blocks.len(), 1);
+
+            diff_view.right.editor.update(cx, |editor, cx| {
+                editor.change_selections(None, cx, |s| {
+                    s.select_ranges([Point::new(1, 0)..Point::new(1, 0)])
+                })
+            });
+            diff_view.copy_hunk_to_left(&CopyHunkToLeft, cx);
+            diff_view.copy_hunk_to_right(&CopyHunkToRight, cx);
+        });
+        assert_eq!(
+            left_buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\ntwo\n"
+        );
+        assert_eq!(
+            right_buffer.read_with(cx, |buffer, _| buffer.text()),
+            "one\ntwo\n"
+        );
+    }
+
+    fn hunk_rows(diff_view: &DiffView) -> Vec<(Range<u32>, Range<u32>)> {
+        diff_view
+            .hunks
+            .iter()
+            .map(|hunk| (hunk.left_rows.clone(), hunk.right_rows.clone()))
+            .collect()
+    }
+
+    fn init_test(cx: &mut TestAppContext) -> Arc<AppState> {
+        cx.update(|cx| {
+            let state = AppState::test(cx);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            workspace::init_settings(cx);
+            Project::init_settings(cx);
+            state
+        })
+    }
+}
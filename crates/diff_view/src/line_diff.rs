@@ -0,0 +1,108 @@
+use std::ops::Range;
+
+use similar::{DiffOp, TextDiff};
+
+/// A range of lines that differ between two texts.
+///
+/// Either range may be empty: an empty `old_rows` means the lines were inserted
+/// before `old_rows.start`, and an empty `new_rows` means they were removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineHunk {
+    pub old_rows: Range<u32>,
+    pub new_rows: Range<u32>,
+}
+
+/// Computes the line-based differences between two texts, merging adjacent
+/// deletions and insertions into a single hunk.
+pub fn line_diff(old_text: &str, new_text: &str) -> Vec<LineHunk> {
+    let diff = TextDiff::from_lines(old_text, new_text);
+    let mut hunks: Vec<LineHunk> = Vec::new();
+    for op in diff.ops() {
+        let (old_rows, new_rows) = match *op {
+            DiffOp::Equal { .. } => continue,
+            DiffOp::Delete {
+                old_index,
+                old_len,
+                new_index,
+            } => (old_index..old_index + old_len, new_index..new_index),
+            DiffOp::Insert {
+                old_index,
+                new_index,
+                new_len,
+            } => (old_index..old_index, new_index..new_index + new_len),
+            DiffOp::Replace {
+                old_index,
+                old_len,
+                new_index,
+                new_len,
+            } => (
+                old_index..old_index + old_len,
+                new_index..new_index + new_len,
+            ),
+        };
+        let old_rows = old_rows.start as u32..old_rows.end as u32;
+        let new_rows = new_rows.start as u32..new_rows.end as u32;
+
+        if let Some(last_hunk) = hunks.last_mut() {
+            if last_hunk.old_rows.end == old_rows.start && last_hunk.new_rows.end == new_rows.start
+            {
+                last_hunk.old_rows.end = old_rows.end;
+                last_hunk.new_rows.end = new_rows.end;
+                continue;
+            }
+        }
+        hunks.push(LineHunk { old_rows, new_rows });
+    }
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff() {
+        assert_eq!(line_diff("a\nb\nc\n", "a\nb\nc\n"), Vec::new());
+
+        assert_eq!(
+            line_diff("a\nb\nc\n", "a\nB\nc\n"),
+            vec![LineHunk {
+                old_rows: 1..2,
+                new_rows: 1..2,
+            }]
+        );
+
+        assert_eq!(
+            line_diff("a\nc\n", "a\nb1\nb2\nc\n"),
+            vec![LineHunk {
+                old_rows: 1..1,
+                new_rows: 1..3,
+            }]
+        );
+
+        assert_eq!(
+            line_diff("a\nb\nc\nd\n", "b\nc\n"),
+            vec![
+                LineHunk {
+                    old_rows: 0..1,
+                    new_rows: 0..0,
+                },
+                LineHunk {
+                    old_rows: 3..4,
+                    new_rows: 2..2,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_diff_merges_adjacent_changes() {
+        assert_eq!(
+            line_diff("a\nb\nc\nd\n", "a\nx\ny\nz\nd\n"),
+            vec![LineHunk {
+                old_rows: 1..3,
+                new_rows: 1..4,
+            }]
+        );
+    }
+}
@@ -171,6 +171,7 @@ gpui::actions!(
         Backspace,
         Cancel,
         CancelLanguageServerWork,
+        ClearBookmarks,
         ConfirmRename,
         ContextMenuFirst,
         ContextMenuLast,
@@ -214,6 +215,8 @@ gpui::actions!(
         GoToHunk,
         GoToImplementation,
         GoToImplementationSplit,
+        GoToNextBookmark,
+        GoToPrevBookmark,
         GoToPrevDiagnostic,
         GoToPrevHunk,
         GoToTypeDefinition,
@@ -294,6 +297,7 @@ gpui::actions!(
         Tab,
         TabPrev,
         ToggleAutoSignatureHelp,
+        ToggleBookmark,
         ToggleGitBlame,
         ToggleGitBlameInline,
         ToggleSelectionMenu,
@@ -0,0 +1,207 @@
+use std::{path::Path, sync::Arc};
+
+use anyhow::Result;
+use gpui::{AppContext, Model, SharedString, Task, ViewContext};
+use language::Point;
+use multi_buffer::MultiBufferRow;
+use ui::{prelude::*, IconButton, IconButtonShape, Tooltip};
+use workspace::{
+    bookmarks::{buffer_abs_path, BookmarkStore},
+    Workspace,
+};
+
+use crate::{
+    actions::{ClearBookmarks, GoToNextBookmark, GoToPrevBookmark, ToggleBookmark},
+    display_map::{DisplayRow, ToDisplayPoint},
+    scroll::Autoscroll,
+    Direction, DisplayPoint, Editor, EditorSnapshot,
+};
+
+impl Editor {
+    pub(crate) fn set_bookmark_store(
+        &mut self,
+        bookmark_store: Model<BookmarkStore>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let buffers = self.buffer.read(cx).all_buffers();
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            for buffer in &buffers {
+                bookmark_store.register_buffer(buffer, cx);
+            }
+        });
+        self.bookmark_store_subscription =
+            Some(cx.observe(&bookmark_store, |_, _, cx| cx.notify()));
+        self.bookmark_store = Some(bookmark_store);
+    }
+
+    pub fn toggle_bookmark(&mut self, _: &ToggleBookmark, cx: &mut ViewContext<Self>) {
+        let head = self.selections.newest::<Point>(cx).head();
+        self.toggle_bookmark_at(head, cx);
+    }
+
+    pub fn clear_bookmarks(&mut self, _: &ClearBookmarks, cx: &mut ViewContext<Self>) {
+        if let Some(bookmark_store) = &self.bookmark_store {
+            bookmark_store.update(cx, |bookmark_store, cx| bookmark_store.clear(cx));
+        }
+    }
+
+    pub(crate) fn toggle_bookmark_at_display_row(
+        &mut self,
+        row: DisplayRow,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let snapshot = self.snapshot(cx);
+        let point = DisplayPoint::new(row, 0).to_point(&snapshot.display_snapshot);
+        self.toggle_bookmark_at(point, cx);
+    }
+
+    fn toggle_bookmark_at(&mut self, position: Point, cx: &mut ViewContext<Self>) {
+        let Some(bookmark_store) = self.bookmark_store.clone() else {
+            return;
+        };
+        let Some((buffer, buffer_offset, _)) =
+            self.buffer.read(cx).point_to_buffer_offset(position, cx)
+        else {
+            return;
+        };
+        let row = buffer.read(cx).offset_to_point(buffer_offset).row;
+        bookmark_store.update(cx, |bookmark_store, cx| {
+            bookmark_store.toggle(&buffer, row, None, cx)
+        });
+    }
+
+    /// Returns the path and buffer row of the newest cursor, used as the starting point
+    /// when navigating between bookmarks.
+    fn bookmark_position(&self, cx: &AppContext) -> Option<(Arc<Path>, u32)> {
+        let head = self.selections.newest::<Point>(cx).head();
+        let (buffer, buffer_offset, _) = self.buffer.read(cx).point_to_buffer_offset(head, cx)?;
+        let buffer = buffer.read(cx);
+        Some((
+            buffer_abs_path(buffer, cx)?,
+            buffer.offset_to_point(buffer_offset).row,
+        ))
+    }
+
+    /// Returns the display rows of all bookmarks visible in this editor, along with
+    /// their labels.
+    pub(crate) fn bookmarked_display_rows(
+        &self,
+        snapshot: &EditorSnapshot,
+        cx: &AppContext,
+    ) -> Vec<(DisplayRow, Option<SharedString>)> {
+        let Some(bookmark_store) = &self.bookmark_store else {
+            return Vec::new();
+        };
+        let bookmark_store = bookmark_store.read(cx);
+        let multi_buffer = self.buffer.read(cx);
+        let mut rows = Vec::new();
+        for (excerpt_id, buffer_snapshot, excerpt_range) in snapshot.buffer_snapshot.excerpts() {
+            let Some(buffer) = multi_buffer.buffer(buffer_snapshot.remote_id()) else {
+                continue;
+            };
+            for (bookmark, anchor) in bookmark_store.bookmarks_in_buffer(&buffer) {
+                let context = &excerpt_range.context;
+                if context.start.cmp(&anchor, buffer_snapshot).is_gt()
+                    || context.end.cmp(&anchor, buffer_snapshot).is_lt()
+                {
+                    continue;
+                }
+                let Some(anchor) = snapshot
+                    .buffer_snapshot
+                    .anchor_in_excerpt(excerpt_id, anchor)
+                else {
+                    continue;
+                };
+                let display_point = anchor.to_display_point(&snapshot.display_snapshot);
+                let buffer_row = display_point.to_point(&snapshot.display_snapshot).row;
+                if snapshot.is_line_folded(MultiBufferRow(buffer_row)) {
+                    continue;
+                }
+                rows.push((display_point.row(), bookmark.label.clone()));
+            }
+        }
+        rows
+    }
+
+    pub(crate) fn render_bookmark_indicator(
+        &self,
+        row: DisplayRow,
+        label: Option<SharedString>,
+        cx: &mut ViewContext<Self>,
+    ) -> IconButton {
+        IconButton::new(("bookmark_indicator", row.0 as usize), IconName::Bookmark)
+            .shape(IconButtonShape::Square)
+            .icon_size(IconSize::XSmall)
+            .icon_color(Color::Accent)
+            .when_some(label, |button, label| {
+                button.tooltip(move |cx| Tooltip::text(label.clone(), cx))
+            })
+            .on_click(cx.listener(move |editor, _, cx| {
+                editor.toggle_bookmark_at_display_row(row, cx);
+            }))
+    }
+}
+
+pub(crate) fn go_to_next_bookmark(
+    workspace: &mut Workspace,
+    _: &GoToNextBookmark,
+    cx: &mut ViewContext<Workspace>,
+) {
+    go_to_adjacent_bookmark(workspace, Direction::Next, cx);
+}
+
+pub(crate) fn go_to_prev_bookmark(
+    workspace: &mut Workspace,
+    _: &GoToPrevBookmark,
+    cx: &mut ViewContext<Workspace>,
+) {
+    go_to_adjacent_bookmark(workspace, Direction::Prev, cx);
+}
+
+fn go_to_adjacent_bookmark(
+    workspace: &mut Workspace,
+    direction: Direction,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let position = workspace
+        .active_item_as::<Editor>(cx)
+        .and_then(|editor| editor.read(cx).bookmark_position(cx));
+    let position = position
+        .as_ref()
+        .map(|(abs_path, row)| (abs_path.as_ref(), *row));
+
+    let bookmark_store = workspace.bookmark_store().read(cx);
+    let bookmark = match direction {
+        Direction::Next => bookmark_store.next_bookmark(position),
+        Direction::Prev => bookmark_store.previous_bookmark(position),
+    };
+    let Some((abs_path, row)) =
+        bookmark.map(|bookmark| (bookmark.abs_path.clone(), bookmark.row()))
+    else {
+        return;
+    };
+
+    open_bookmark(workspace, &abs_path, row, cx).detach_and_log_err(cx);
+}
+
+/// Opens the file at the given path and moves the cursor to the start of the given row.
+pub fn open_bookmark(
+    workspace: &mut Workspace,
+    abs_path: &Path,
+    row: u32,
+    cx: &mut ViewContext<Workspace>,
+) -> Task<Result<()>> {
+    let open_task = workspace.open_abs_path(abs_path.to_path_buf(), true, cx);
+    cx.spawn(|_, mut cx| async move {
+        let item = open_task.await?;
+        if let Some(editor) = cx.update(|cx| item.act_as::<Editor>(cx))? {
+            editor.update(&mut cx, |editor, cx| {
+                let point = Point::new(row, 0);
+                editor.change_selections(Some(Autoscroll::center()), cx, |s| {
+                    s.select_ranges([point..point])
+                });
+            })?;
+        }
+        Ok(())
+    })
+}
@@ -15,6 +15,7 @@
 pub mod actions;
 mod blame_entry_tooltip;
 mod blink_manager;
+pub mod bookmarks;
 mod debounced_delay;
 pub mod display_map;
 mod editor_settings;
@@ -57,10 +58,10 @@ use debounced_delay::DebouncedDelay;
 use display_map::*;
 pub use display_map::{DisplayPoint, FoldPlaceholder};
 pub use editor_settings::{CurrentLineHighlight, EditorSettings};
-use element::LineWithInvisibles;
 pub use element::{
     CursorLayout, EditorElement, HighlightedRange, HighlightedRangeLine, PointForPosition,
 };
+use element::{LineWithInvisibles, StickyScrollOutline};
 use futures::FutureExt;
 use fuzzy::{StringMatch, StringMatchCandidate};
 use git::blame::GitBlame;
@@ -141,7 +142,7 @@ use sum_tree::TreeMap;
 use text::{BufferId, OffsetUtf16, Rope};
 use theme::{
     observe_buffer_font_size_adjustment, ActiveTheme, PlayerColor, StatusColors, SyntaxTheme,
-    ThemeColors, ThemeSettings,
+    ThemeColors, ThemeRegistry, ThemeSettings,
 };
 use ui::{
     h_flex, prelude::*, ButtonSize, ButtonStyle, Disclosure, IconButton, IconName, IconSize,
@@ -151,7 +152,8 @@ use util::{defer, maybe, post_inc, RangeExt, ResultExt, TryFutureExt};
 use workspace::item::{ItemHandle, PreviewTabsSettings};
 use workspace::notifications::{DetachAndPromptErr, NotificationId};
 use workspace::{
-    searchable::SearchEvent, ItemNavHistory, SplitDirection, ViewId, Workspace, WorkspaceId,
+    bookmarks::BookmarkStore, searchable::SearchEvent, ItemNavHistory, SplitDirection, ViewId,
+    Workspace, WorkspaceId,
 };
 use workspace::{OpenInTerminal, OpenTerminal, TabBarSettings, Toast};
 
@@ -277,6 +279,8 @@ pub fn init(cx: &mut AppContext) {
         |workspace: &mut Workspace, _cx: &mut ViewContext<Workspace>| {
             workspace.register_action(Editor::new_file);
             workspace.register_action(Editor::new_file_in_direction);
+            workspace.register_action(bookmarks::go_to_next_bookmark);
+            workspace.register_action(bookmarks::go_to_prev_bookmark);
         },
     )
     .detach();
@@ -553,6 +557,8 @@ pub struct Editor {
     show_selection_menu: Option<bool>,
     blame: Option<Model<GitBlame>>,
     blame_subscription: Option<Subscription>,
+    bookmark_store: Option<Model<BookmarkStore>>,
+    bookmark_store_subscription: Option<Subscription>,
     custom_context_menu: Option<
         Box<
             dyn 'static
@@ -566,6 +572,15 @@ pub struct Editor {
     previous_search_ranges: Option<Arc<[Range<Anchor>]>>,
     file_header_size: u8,
     breadcrumb_header: Option<String>,
+    sticky_scroll_outline: Option<StickyScrollOutline>,
+    syntax_theme_cache: Option<SyntaxThemeCache>,
+}
+
+/// The active theme's syntax theme restyled with a language's `syntax_theme`.
+struct SyntaxThemeCache {
+    base: Arc<SyntaxTheme>,
+    theme_name: String,
+    syntax: Arc<SyntaxTheme>,
 }
 
 #[derive(Clone)]
@@ -1876,6 +1891,8 @@ impl Editor {
             git_blame_inline_enabled: ProjectSettings::get_global(cx).git.inline_blame_enabled(),
             blame: None,
             blame_subscription: None,
+            bookmark_store: None,
+            bookmark_store_subscription: None,
             file_header_size,
             tasks: Default::default(),
             _subscriptions: vec![
@@ -1901,6 +1918,8 @@ impl Editor {
             linked_edit_ranges: Default::default(),
             previous_search_ranges: None,
             breadcrumb_header: None,
+            sticky_scroll_outline: None,
+            syntax_theme_cache: None,
         };
         this.tasks_update_task = Some(this.refresh_runnables(cx));
         this._subscriptions.extend(project_subscriptions);
@@ -6695,11 +6714,12 @@ impl Editor {
     }
 
     pub fn move_left(&mut self, _: &MoveLeft, cx: &mut ViewContext<Self>) {
+        let text_layout_details = &self.text_layout_details(cx);
         self.change_selections(Some(Autoscroll::fit()), cx, |s| {
             let line_mode = s.line_mode;
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() && !line_mode {
-                    movement::left(map, selection.start)
+                    movement::visual_left(map, selection.start, text_layout_details)
                 } else {
                     selection.start
                 };
@@ -6709,17 +6729,24 @@ impl Editor {
     }
 
     pub fn select_left(&mut self, _: &SelectLeft, cx: &mut ViewContext<Self>) {
+        let text_layout_details = &self.text_layout_details(cx);
         self.change_selections(Some(Autoscroll::fit()), cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::left(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| {
+                (
+                    movement::visual_left(map, head, text_layout_details),
+                    SelectionGoal::None,
+                )
+            });
         })
     }
 
     pub fn move_right(&mut self, _: &MoveRight, cx: &mut ViewContext<Self>) {
+        let text_layout_details = &self.text_layout_details(cx);
         self.change_selections(Some(Autoscroll::fit()), cx, |s| {
             let line_mode = s.line_mode;
             s.move_with(|map, selection| {
                 let cursor = if selection.is_empty() && !line_mode {
-                    movement::right(map, selection.end)
+                    movement::visual_right(map, selection.end, text_layout_details)
                 } else {
                     selection.end
                 };
@@ -6729,8 +6756,14 @@ impl Editor {
     }
 
     pub fn select_right(&mut self, _: &SelectRight, cx: &mut ViewContext<Self>) {
+        let text_layout_details = &self.text_layout_details(cx);
         self.change_selections(Some(Autoscroll::fit()), cx, |s| {
-            s.move_heads_with(|map, head, _| (movement::right(map, head), SelectionGoal::None));
+            s.move_heads_with(|map, head, _| {
+                (
+                    movement::visual_right(map, head, text_layout_details),
+                    SelectionGoal::None,
+                )
+            });
         })
     }
 
@@ -10240,6 +10273,38 @@ impl Editor {
         wrap_guides
     }
 
+    /// Returns the syntax theme to highlight this editor's buffer with, taking the
+    /// `syntax_theme` setting of the buffer's language into account.
+    fn syntax_theme(&mut self, cx: &WindowContext) -> Arc<SyntaxTheme> {
+        let syntax = cx.theme().syntax();
+        let buffer = self.buffer.read(cx);
+        if !buffer.is_singleton() {
+            return syntax.clone();
+        }
+
+        let Some(theme_name) = buffer.settings_at(0, cx).syntax_theme.clone() else {
+            return syntax.clone();
+        };
+        if let Some(cached) = self.syntax_theme_cache.as_ref() {
+            if Arc::ptr_eq(&cached.base, syntax) && cached.theme_name == theme_name {
+                return cached.syntax.clone();
+            }
+        }
+
+        match ThemeRegistry::global(cx).get(&theme_name) {
+            Ok(theme) => {
+                let restyled = Arc::new(syntax.restyled_with(theme.syntax()));
+                self.syntax_theme_cache = Some(SyntaxThemeCache {
+                    base: syntax.clone(),
+                    theme_name,
+                    syntax: restyled.clone(),
+                });
+                restyled
+            }
+            Err(_) => syntax.clone(),
+        }
+    }
+
     pub fn soft_wrap_mode(&self, cx: &AppContext) -> SoftWrap {
         let settings = self.buffer.read(cx).settings_at(0, cx);
         let mode = self
@@ -11173,6 +11238,11 @@ impl Editor {
                 excerpts,
             } => {
                 self.tasks_update_task = Some(self.refresh_runnables(cx));
+                if let Some(bookmark_store) = &self.bookmark_store {
+                    bookmark_store.update(cx, |bookmark_store, cx| {
+                        bookmark_store.register_buffer(buffer, cx)
+                    });
+                }
                 cx.emit(EditorEvent::ExcerptsAdded {
                     buffer: buffer.clone(),
                     predecessor: *predecessor,
@@ -11242,6 +11312,7 @@ impl Editor {
         let editor_settings = EditorSettings::get_global(cx);
         self.scroll_manager.vertical_scroll_margin = editor_settings.vertical_scroll_margin;
         self.show_breadcrumbs = editor_settings.toolbar.breadcrumbs;
+        self.syntax_theme_cache = None;
 
         if self.mode == EditorMode::Full {
             let inline_blame_enabled = ProjectSettings::get_global(cx).git.inline_blame_enabled();
@@ -12311,7 +12382,7 @@ impl Render for Editor {
                 local_player: cx.theme().players().local(),
                 text: text_style,
                 scrollbar_width: EditorElement::SCROLLBAR_WIDTH,
-                syntax: cx.theme().syntax().clone(),
+                syntax: self.syntax_theme(cx),
                 status: cx.theme().status().clone(),
                 inlay_hints_style: HighlightStyle {
                     color: Some(cx.theme().status().hint),
@@ -14,6 +14,8 @@ pub struct EditorSettings {
     pub use_on_type_format: bool,
     pub toolbar: Toolbar,
     pub scrollbar: Scrollbar,
+    pub minimap: Minimap,
+    pub sticky_scroll: StickyScroll,
     pub gutter: Gutter,
     pub scroll_beyond_last_line: ScrollBeyondLastLine,
     pub vertical_scroll_margin: f32,
@@ -95,11 +97,27 @@ pub struct Scrollbar {
     pub cursors: bool,
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct Minimap {
+    pub show: ShowMinimap,
+    pub width: f32,
+    pub git_diff: bool,
+    pub search_results: bool,
+    pub diagnostics: bool,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct StickyScroll {
+    pub enabled: bool,
+    pub max_depth: usize,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Gutter {
     pub line_numbers: bool,
     pub code_actions: bool,
     pub runnables: bool,
+    pub bookmarks: bool,
     pub folds: bool,
 }
 
@@ -120,6 +138,20 @@ pub enum ShowScrollbar {
     Never,
 }
 
+/// When to show the minimap in the editor.
+///
+/// Default: never
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ShowMinimap {
+    /// Always show the minimap, next to the scrollbar.
+    Always,
+    /// Show the minimap over the text when hovering the right edge of the editor.
+    Hover,
+    /// Never show the minimap.
+    Never,
+}
+
 /// The key to use for adding multiple cursors
 ///
 /// Default: alt
@@ -187,6 +219,10 @@ pub struct EditorSettingsContent {
     pub toolbar: Option<ToolbarContent>,
     /// Scrollbar related settings
     pub scrollbar: Option<ScrollbarContent>,
+    /// Minimap related settings
+    pub minimap: Option<MinimapContent>,
+    /// Sticky scroll related settings
+    pub sticky_scroll: Option<StickyScrollContent>,
     /// Gutter related settings
     pub gutter: Option<GutterContent>,
     /// Whether the editor will scroll beyond the last line.
@@ -297,6 +333,45 @@ pub struct ScrollbarContent {
     pub cursors: Option<bool>,
 }
 
+/// Minimap related settings
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct MinimapContent {
+    /// When to show the minimap in the editor.
+    ///
+    /// Default: never
+    pub show: Option<ShowMinimap>,
+    /// The width of the minimap in pixels.
+    ///
+    /// Default: 120
+    pub width: Option<f32>,
+    /// Whether to show git diff indicators in the minimap.
+    ///
+    /// Default: true
+    pub git_diff: Option<bool>,
+    /// Whether to show buffer search results in the minimap.
+    ///
+    /// Default: true
+    pub search_results: Option<bool>,
+    /// Whether to show diagnostics in the minimap.
+    ///
+    /// Default: true
+    pub diagnostics: Option<bool>,
+}
+
+/// Sticky scroll related settings
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct StickyScrollContent {
+    /// Whether to pin the first line of the outline items enclosing the top
+    /// of the viewport, such as functions and impls, at the top of the editor.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The maximum number of enclosing outline items pinned at once.
+    ///
+    /// Default: 5
+    pub max_depth: Option<usize>,
+}
+
 /// Gutter related settings
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct GutterContent {
@@ -312,6 +387,10 @@ pub struct GutterContent {
     ///
     /// Default: true
     pub runnables: Option<bool>,
+    /// Whether to show bookmarks in the gutter, and toggle them by clicking it.
+    ///
+    /// Default: true
+    pub bookmarks: Option<bool>,
     /// Whether to show fold buttons in the gutter.
     ///
     /// Default: true
@@ -13057,6 +13057,206 @@ fn test_crease_insertion_and_rendering(cx: &mut TestAppContext) {
     assert!(!snapshot.is_line_folded(MultiBufferRow(1)));
 }
 
+#[gpui::test]
+async fn test_bookmarks(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/a",
+        json!({
+            "one.rs": "one\ntwo\nthree\nfour\n",
+            "two.rs": "five\nsix\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, ["/a".as_ref()], cx).await;
+    let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = workspace
+        .update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees().next().unwrap().read(cx).id()
+            })
+        })
+        .unwrap();
+
+    let bookmarks = |cx: &mut VisualTestContext| {
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace
+                    .bookmark_store()
+                    .read(cx)
+                    .bookmarks()
+                    .iter()
+                    .map(|bookmark| (bookmark.abs_path.clone(), bookmark.row()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap()
+    };
+
+    let editor_1 = workspace
+        .update(cx, |workspace, cx| {
+            workspace.open_path((worktree_id, "one.rs"), None, true, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    editor_1.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(2, 1)..Point::new(2, 1)])
+        });
+        editor.toggle_bookmark(&ToggleBookmark, cx);
+
+        // Bookmarks follow the line they were added on.
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+        });
+        editor.handle_input("zero\n", cx);
+    });
+
+    let editor_2 = workspace
+        .update(cx, |workspace, cx| {
+            workspace.open_path((worktree_id, "two.rs"), None, true, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    editor_2.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(1, 0)..Point::new(1, 0)])
+        });
+        editor.toggle_bookmark(&ToggleBookmark, cx);
+    });
+    assert_eq!(
+        bookmarks(cx),
+        vec![
+            (Arc::from(Path::new("/a/one.rs")), 3),
+            (Arc::from(Path::new("/a/two.rs")), 1)
+        ]
+    );
+
+    // Navigating past the last bookmark wraps around to the first one.
+    cx.dispatch_action(GoToNextBookmark);
+    cx.run_until_parked();
+    let active_editor = workspace
+        .update(cx, |workspace, cx| workspace.active_item_as::<Editor>(cx))
+        .unwrap()
+        .unwrap();
+    assert_eq!(active_editor.entity_id(), editor_1.entity_id());
+    editor_1.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.selections.newest::<Point>(cx).head(),
+            Point::new(3, 0)
+        );
+    });
+
+    cx.dispatch_action(GoToPrevBookmark);
+    cx.run_until_parked();
+    let active_editor = workspace
+        .update(cx, |workspace, cx| workspace.active_item_as::<Editor>(cx))
+        .unwrap()
+        .unwrap();
+    assert_eq!(active_editor.entity_id(), editor_2.entity_id());
+    editor_2.update(cx, |editor, cx| {
+        assert_eq!(
+            editor.selections.newest::<Point>(cx).head(),
+            Point::new(1, 0)
+        );
+        editor.toggle_bookmark(&ToggleBookmark, cx);
+    });
+    assert_eq!(bookmarks(cx), vec![(Arc::from(Path::new("/a/one.rs")), 3)]);
+}
+
+#[gpui::test]
+async fn test_bookmarks_restored_in_open_buffers(cx: &mut gpui::TestAppContext) {
+    init_test(cx, |_| {});
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/a",
+        json!({
+            "one.rs": "one\ntwo\nthree\nfour\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, ["/a".as_ref()], cx).await;
+    let workspace = cx.add_window(|cx| Workspace::test_new(project.clone(), cx));
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+    let worktree_id = workspace
+        .update(cx, |workspace, cx| {
+            workspace.project().update(cx, |project, cx| {
+                project.worktrees().next().unwrap().read(cx).id()
+            })
+        })
+        .unwrap();
+
+    let editor = workspace
+        .update(cx, |workspace, cx| {
+            workspace.open_path((worktree_id, "one.rs"), None, true, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap()
+        .downcast::<Editor>()
+        .unwrap();
+    let toggle_bookmark = |row: u32, cx: &mut VisualTestContext| {
+        editor.update(cx, |editor, cx| {
+            editor.change_selections(None, cx, |s| {
+                s.select_ranges([Point::new(row, 0)..Point::new(row, 0)])
+            });
+            editor.toggle_bookmark(&ToggleBookmark, cx);
+        });
+    };
+    let bookmarked_rows = |cx: &mut VisualTestContext| {
+        editor.update(cx, |editor, cx| {
+            let snapshot = editor.snapshot(cx);
+            editor
+                .bookmarked_display_rows(&snapshot, cx)
+                .into_iter()
+                .map(|(row, _)| row.0)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    toggle_bookmark(1, cx);
+    toggle_bookmark(3, cx);
+    assert_eq!(bookmarked_rows(cx), vec![1, 3]);
+
+    // Restoring the workspace loads the bookmarks after the editors were deserialized.
+    workspace
+        .update(cx, |workspace, cx| {
+            workspace
+                .bookmark_store()
+                .update(cx, |bookmark_store, cx| bookmark_store.reload(cx))
+        })
+        .unwrap();
+    assert_eq!(bookmarked_rows(cx), vec![1, 3]);
+
+    // The restored bookmarks follow edits and are removed when toggled again.
+    editor.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |s| {
+            s.select_ranges([Point::new(0, 0)..Point::new(0, 0)])
+        });
+        editor.handle_input("zero\n", cx);
+    });
+    assert_eq!(bookmarked_rows(cx), vec![2, 4]);
+    toggle_bookmark(2, cx);
+    assert_eq!(bookmarked_rows(cx), vec![4]);
+    let bookmark_count = workspace
+        .update(cx, |workspace, cx| {
+            workspace.bookmark_store().read(cx).bookmarks().len()
+        })
+        .unwrap();
+    assert_eq!(bookmark_count, 1);
+}
+
 fn empty_range(row: usize, column: usize) -> Range<DisplayPoint> {
     let point = DisplayPoint::new(DisplayRow(row as u32), column as u32);
     point..point
@@ -5,7 +5,8 @@ use crate::{
         BlockContext, BlockStyle, DisplaySnapshot, HighlightedChunk, ToDisplayPoint, TransformBlock,
     },
     editor_settings::{
-        CurrentLineHighlight, DoubleClickInMultibuffer, MultiCursorModifier, ShowScrollbar,
+        CurrentLineHighlight, DoubleClickInMultibuffer, MultiCursorModifier, ShowMinimap,
+        ShowScrollbar,
     },
     git::{
         blame::{CommitDetails, GitBlame},
@@ -17,7 +18,7 @@ use crate::{
     hunk_status,
     items::BufferSearchHighlights,
     mouse_context_menu::{self, MouseContextMenu},
-    scroll::scroll_amount::ScrollAmount,
+    scroll::{scroll_amount::ScrollAmount, Autoscroll},
     CodeActionsMenu, CursorShape, DisplayPoint, DisplayRow, DocumentHighlightRead,
     DocumentHighlightWrite, Editor, EditorMode, EditorSettings, EditorSnapshot, EditorStyle,
     ExpandExcerpts, GutterDimensions, HalfPageDown, HalfPageUp, HoveredCursor, HunkToExpand,
@@ -29,18 +30,19 @@ use collections::{BTreeMap, HashMap};
 use git::{blame::BlameEntry, diff::DiffHunkStatus, Oid};
 use gpui::{
     anchored, deferred, div, fill, outline, point, px, quad, relative, size, svg,
-    transparent_black, Action, AnchorCorner, AnyElement, AvailableSpace, Bounds, ClipboardItem,
-    ContentMask, Corners, CursorStyle, DispatchPhase, Edges, Element, ElementInputHandler, Entity,
-    FontId, GlobalElementId, Hitbox, Hsla, InteractiveElement, IntoElement, Length,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, PaintQuad,
-    ParentElement, Pixels, ScrollDelta, ScrollWheelEvent, ShapedLine, SharedString, Size,
-    StatefulInteractiveElement, Style, Styled, TextRun, TextStyle, TextStyleRefinement, View,
-    ViewContext, WeakView, WindowContext,
+    transparent_black, AccessibilityNode, AccessibilityRole, Action, AnchorCorner, AnyElement,
+    AvailableSpace, Bounds, ClipboardItem, ContentMask, Corners, CursorStyle, DispatchPhase, Edges,
+    Element, ElementInputHandler, Entity, FontId, GlobalElementId, Hitbox, Hsla,
+    InteractiveElement, IntoElement, Length, ModifiersChangedEvent, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, PaintQuad, ParentElement, Pixels, ScrollDelta, ScrollWheelEvent,
+    ShapedLine, SharedString, Size, StatefulInteractiveElement, Style, Styled, TextRun, TextStyle,
+    TextStyleRefinement, View, ViewContext, WeakView, WindowContext,
 };
 use itertools::Itertools;
 use language::language_settings::{
     IndentGuideBackgroundColoring, IndentGuideColoring, IndentGuideSettings, ShowWhitespaceSetting,
 };
+use language::{BufferId, BufferSnapshot, OffsetRangeExt, ToOffset as _};
 use lsp::DiagnosticSeverity;
 use multi_buffer::{Anchor, MultiBufferPoint, MultiBufferRow};
 use project::{
@@ -333,6 +335,8 @@ impl EditorElement {
         register_action(view, cx, Editor::copy_highlight_json);
         register_action(view, cx, Editor::copy_permalink_to_line);
         register_action(view, cx, Editor::open_permalink_to_line);
+        register_action(view, cx, Editor::toggle_bookmark);
+        register_action(view, cx, Editor::clear_bookmarks);
         register_action(view, cx, Editor::toggle_git_blame);
         register_action(view, cx, Editor::toggle_git_blame_inline);
         register_action(view, cx, Editor::toggle_hunk_diff);
@@ -457,6 +461,24 @@ impl EditorElement {
             cx.notify();
             return;
         } else if gutter_hitbox.is_hovered(cx) {
+            if EditorSettings::get_global(cx).gutter.bookmarks {
+                let gutter_dimensions = editor.gutter_dimensions;
+                let indicator_area_start = gutter_hitbox.origin.x
+                    + gutter_dimensions
+                        .git_blame_entries_width
+                        .unwrap_or(Pixels::ZERO);
+                let indicator_area_end = gutter_hitbox.origin.x
+                    + gutter_dimensions.margin
+                    + gutter_dimensions.left_padding;
+                if (indicator_area_start..indicator_area_end).contains(&event.position.x) {
+                    let row = position_map
+                        .point_for_position(text_hitbox.bounds, event.position)
+                        .previous_valid
+                        .row();
+                    editor.toggle_bookmark_at_display_row(row, cx);
+                    return;
+                }
+            }
             click_count = 3; // Simulate triple-click when clicking the gutter to select lines
         } else if !text_hitbox.is_hovered(cx) {
             return;
@@ -963,9 +985,16 @@ impl EditorElement {
                         [cursor_position.row().minus(visible_display_row_range.start) as usize];
                     let cursor_column = cursor_position.column() as usize;
 
-                    let cursor_character_x = cursor_row_layout.x_for_index(cursor_column);
-                    let mut block_width =
-                        cursor_row_layout.x_for_index(cursor_column + 1) - cursor_character_x;
+                    let mut cursor_character_x = cursor_row_layout.x_for_index(cursor_column);
+                    let next_character_x = cursor_row_layout.x_for_index(cursor_column + 1);
+                    let mut block_width = next_character_x - cursor_character_x;
+                    if block_width < Pixels::ZERO {
+                        // A right-to-left character extends to the left of the cursor.
+                        if selection.cursor_shape != CursorShape::Bar {
+                            cursor_character_x = next_character_x;
+                        }
+                        block_width = block_width.abs();
+                    }
                     if block_width == Pixels::ZERO {
                         block_width = em_width;
                     }
@@ -1142,6 +1171,234 @@ impl EditorElement {
         })
     }
 
+    fn layout_minimap(
+        &self,
+        snapshot: &EditorSnapshot,
+        bounds: Bounds<Pixels>,
+        scroll_position: gpui::Point<f32>,
+        rows_per_page: f32,
+        cx: &mut WindowContext,
+    ) -> Option<MinimapLayout> {
+        let minimap_settings = EditorSettings::get_global(cx).minimap;
+        if snapshot.mode != EditorMode::Full || minimap_settings.show == ShowMinimap::Never {
+            return None;
+        }
+
+        let right = self.scrollbar_left(&bounds);
+        let minimap_bounds = Bounds::from_corners(
+            point(right - px(minimap_settings.width), bounds.origin.y),
+            point(right, bounds.lower_left().y),
+        );
+
+        // Scroll the minimap proportionally to the editor, so that both reach
+        // the end of the buffer at the same time.
+        let total_rows = snapshot.max_point().row().next_row().as_f32();
+        let minimap_rows = bounds.size.height / MinimapLayout::ROW_HEIGHT;
+        let max_scroll_top = (total_rows - rows_per_page).max(0.);
+        let max_minimap_scroll_top = (total_rows - minimap_rows).max(0.);
+        let scroll_ratio = if max_scroll_top > 0. {
+            (max_minimap_scroll_top / max_scroll_top).min(1.)
+        } else {
+            0.
+        };
+        let scroll_top = scroll_position.y.min(max_scroll_top) * scroll_ratio;
+
+        let start_row = DisplayRow(scroll_top.floor() as u32);
+        let end_row = DisplayRow(
+            ((scroll_top + minimap_rows).ceil() as u32)
+                .min(snapshot.max_point().row().next_row().0),
+        );
+
+        let hitbox = cx.insert_hitbox(minimap_bounds, false);
+        let editor = self.editor.read(cx);
+        let visible = minimap_settings.show == ShowMinimap::Always
+            || hitbox.is_hovered(cx)
+            || editor.scroll_manager.is_dragging_minimap();
+
+        let mut markers = Vec::new();
+        if visible && start_row < end_row {
+            let start_point = DisplayPoint::new(start_row, 0).to_point(snapshot);
+            let end_point = DisplayPoint::new(end_row, 0).to_point(snapshot);
+            let buffer_snapshot = &snapshot.buffer_snapshot;
+
+            if minimap_settings.git_diff {
+                let theme = cx.theme();
+                markers.extend(
+                    buffer_snapshot
+                        .git_diff_hunks_in_range(
+                            MultiBufferRow(start_point.row)..MultiBufferRow(end_point.row + 1),
+                        )
+                        .map(|hunk| {
+                            let start = MultiBufferPoint::new(hunk.associated_range.start.0, 0)
+                                .to_display_point(snapshot)
+                                .row();
+                            let mut end = MultiBufferPoint::new(hunk.associated_range.end.0, 0)
+                                .to_display_point(snapshot)
+                                .row();
+                            if end != start {
+                                end.0 -= 1;
+                            }
+                            let color = match hunk_status(&hunk) {
+                                DiffHunkStatus::Added => theme.status().created,
+                                DiffHunkStatus::Modified => theme.status().modified,
+                                DiffHunkStatus::Removed => theme.status().deleted,
+                            };
+                            MinimapMarker {
+                                kind: MinimapMarkerKind::Gutter,
+                                rows: ColoredRange { start, end, color },
+                            }
+                        }),
+                );
+            }
+
+            if minimap_settings.search_results {
+                let start_anchor = buffer_snapshot.anchor_before(start_point);
+                let end_anchor = buffer_snapshot.anchor_after(end_point);
+                let color = cx.theme().colors().search_match_background;
+                markers.extend(
+                    editor
+                        .background_highlight_row_ranges::<BufferSearchHighlights>(
+                            start_anchor..end_anchor,
+                            &snapshot.display_snapshot,
+                            MinimapLayout::MAX_SEARCH_RESULTS,
+                        )
+                        .into_iter()
+                        .map(|range| MinimapMarker {
+                            kind: MinimapMarkerKind::Line,
+                            rows: ColoredRange {
+                                start: range.start().row(),
+                                end: range.end().row(),
+                                color,
+                            },
+                        }),
+                );
+            }
+
+            if minimap_settings.diagnostics {
+                let status = cx.theme().status();
+                markers.extend(
+                    buffer_snapshot
+                        .diagnostics_in_range::<_, Point>(start_point..end_point, false)
+                        // Paint the most severe diagnostics last.
+                        .sorted_by_key(|diagnostic| cmp::Reverse(diagnostic.diagnostic.severity))
+                        .map(|diagnostic| {
+                            let mut color = match diagnostic.diagnostic.severity {
+                                DiagnosticSeverity::ERROR => status.error,
+                                DiagnosticSeverity::WARNING => status.warning,
+                                DiagnosticSeverity::INFORMATION => status.info,
+                                _ => status.hint,
+                            };
+                            color.fade_out(0.5);
+                            MinimapMarker {
+                                kind: MinimapMarkerKind::Line,
+                                rows: ColoredRange {
+                                    start: diagnostic.range.start.to_display_point(snapshot).row(),
+                                    end: diagnostic.range.end.to_display_point(snapshot).row(),
+                                    color,
+                                },
+                            }
+                        }),
+                );
+            }
+        }
+
+        Some(MinimapLayout {
+            hitbox,
+            visible,
+            scroll_top,
+            row_range: start_row..end_row,
+            visible_row_range: scroll_position.y..scroll_position.y + rows_per_page,
+            // Dragging the viewport moves it relative to the minimap, which scrolls too.
+            drag_row_height: (MinimapLayout::ROW_HEIGHT * (1. - scroll_ratio))
+                .max(MinimapLayout::ROW_HEIGHT / 8.),
+            markers,
+        })
+    }
+
+    /// Lays out the first line of each outline item enclosing the top of the
+    /// viewport whose first line was scrolled out of view, outermost first.
+    fn layout_sticky_headers(
+        &self,
+        snapshot: &EditorSnapshot,
+        bounds: Bounds<Pixels>,
+        start_row: DisplayRow,
+        line_height: Pixels,
+        cx: &mut WindowContext,
+    ) -> Vec<StickyHeaderLayout> {
+        let sticky_scroll_settings = EditorSettings::get_global(cx).sticky_scroll;
+        if !sticky_scroll_settings.enabled || snapshot.mode != EditorMode::Full {
+            return Vec::new();
+        }
+
+        let top_point = DisplayPoint::new(start_row, 0).to_point(snapshot);
+        let top_anchor = snapshot.buffer_snapshot.anchor_before(top_point);
+        let excerpt_id = top_anchor.excerpt_id;
+        let Some(buffer) = snapshot.buffer_snapshot.buffer_for_excerpt(excerpt_id) else {
+            return Vec::new();
+        };
+        let outline = self
+            .editor
+            .update(cx, |editor, _| StickyScrollOutline::get(editor, buffer));
+        let top_offset = top_anchor.text_anchor.to_offset(buffer);
+
+        let mut headers: Vec<StickyHeaderLayout> = Vec::new();
+        for item in outline_items_containing(&outline, top_offset) {
+            if headers.len() >= sticky_scroll_settings.max_depth {
+                break;
+            }
+
+            let (Some(start), Some(end)) = (
+                snapshot
+                    .buffer_snapshot
+                    .anchor_in_excerpt(excerpt_id, buffer.anchor_after(item.range.start)),
+                snapshot
+                    .buffer_snapshot
+                    .anchor_in_excerpt(excerpt_id, buffer.anchor_before(item.range.end)),
+            ) else {
+                continue;
+            };
+            let header_row = start.to_display_point(snapshot).row();
+            let end_row = end.to_display_point(snapshot).row();
+            if headers
+                .last()
+                .map_or(false, |header| header.row == header_row)
+            {
+                continue;
+            }
+            // Stop once a header would cover its own first line, or the last line
+            // of its scope.
+            let covered_row = DisplayRow(start_row.0 + headers.len() as u32);
+            if header_row >= covered_row || end_row <= covered_row {
+                break;
+            }
+
+            let Some(line) = Self::layout_lines(
+                header_row..header_row.next_row(),
+                &[],
+                snapshot,
+                &self.style,
+                cx,
+            )
+            .pop() else {
+                break;
+            };
+            let header_bounds = Bounds::new(
+                point(
+                    bounds.origin.x,
+                    bounds.origin.y + line_height * headers.len() as f32,
+                ),
+                size(self.scrollbar_left(&bounds) - bounds.origin.x, line_height),
+            );
+            headers.push(StickyHeaderLayout {
+                row: header_row,
+                line,
+                target: start,
+                hitbox: cx.insert_hitbox(header_bounds, true),
+            });
+        }
+        headers
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn prepaint_gutter_fold_toggles(
         &self,
@@ -1565,7 +1822,7 @@ impl EditorElement {
         gutter_hitbox: &Hitbox,
         snapshot: &EditorSnapshot,
         cx: &mut WindowContext,
-    ) -> Vec<AnyElement> {
+    ) -> Vec<(DisplayRow, AnyElement)> {
         self.editor.update(cx, |editor, cx| {
             let active_task_indicator_row =
                 if let Some(crate::ContextMenu::CodeActions(CodeActionsMenu {
@@ -1608,7 +1865,40 @@ impl EditorElement {
                         gutter_hitbox,
                         cx,
                     );
-                    Some(button)
+                    Some((display_row, button))
+                })
+                .collect_vec()
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn layout_bookmark_indicators(
+        &self,
+        line_height: Pixels,
+        rows: Range<DisplayRow>,
+        occupied_rows: &[DisplayRow],
+        scroll_pixel_position: gpui::Point<Pixels>,
+        gutter_dimensions: &GutterDimensions,
+        gutter_hitbox: &Hitbox,
+        snapshot: &EditorSnapshot,
+        cx: &mut WindowContext,
+    ) -> Vec<AnyElement> {
+        self.editor.update(cx, |editor, cx| {
+            editor
+                .bookmarked_display_rows(snapshot, cx)
+                .into_iter()
+                .filter(|(row, _)| rows.contains(row) && !occupied_rows.contains(row))
+                .map(|(row, label)| {
+                    let button = editor.render_bookmark_indicator(row, label, cx);
+                    prepaint_gutter_button(
+                        button,
+                        row,
+                        line_height,
+                        gutter_dimensions,
+                        scroll_pixel_position,
+                        gutter_hitbox,
+                        cx,
+                    )
                 })
                 .collect_vec()
         })
@@ -3081,8 +3371,12 @@ impl EditorElement {
                 }
             });
 
-            for test_indicators in layout.test_indicators.iter_mut() {
-                test_indicators.paint(cx);
+            for bookmark_indicator in layout.bookmark_indicators.iter_mut() {
+                bookmark_indicator.paint(cx);
+            }
+
+            for (_, test_indicator) in layout.test_indicators.iter_mut() {
+                test_indicator.paint(cx);
             }
 
             if let Some(indicator) = layout.code_actions_indicator.as_mut() {
@@ -3423,6 +3717,295 @@ impl EditorElement {
         }
     }
 
+    fn paint_sticky_headers(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
+        let Some(last_header) = layout.sticky_headers.last() else {
+            return;
+        };
+
+        let line_height = layout.position_map.line_height;
+        let scroll_left = layout.position_map.scroll_pixel_position.x;
+        cx.paint_layer(layout.hitbox.bounds, |cx| {
+            for header in &layout.sticky_headers {
+                let header_bounds = header.hitbox.bounds;
+                cx.paint_quad(fill(header_bounds, self.style.background));
+                cx.with_content_mask(
+                    Some(ContentMask {
+                        bounds: layout.text_hitbox.bounds,
+                    }),
+                    |cx| {
+                        let mut fragment_origin =
+                            point(layout.content_origin.x - scroll_left, header_bounds.top());
+                        for fragment in &header.line.fragments {
+                            match fragment {
+                                LineFragment::Text(line) => {
+                                    line.paint(fragment_origin, line_height, cx).log_err();
+                                    fragment_origin.x += line.width;
+                                }
+                                LineFragment::Element { size, .. } => {
+                                    fragment_origin.x += size.width;
+                                }
+                            }
+                        }
+                    },
+                );
+                cx.set_cursor_style(CursorStyle::PointingHand, &header.hitbox);
+            }
+
+            let last_bounds = last_header.hitbox.bounds;
+            cx.paint_quad(fill(
+                Bounds::from_corners(
+                    point(last_bounds.left(), last_bounds.bottom() - px(1.)),
+                    last_bounds.lower_right(),
+                ),
+                cx.theme().colors().border_variant,
+            ));
+        });
+
+        let headers = layout
+            .sticky_headers
+            .iter()
+            .map(|header| (header.hitbox.clone(), header.target))
+            .collect::<Vec<_>>();
+        cx.on_mouse_event({
+            let editor = self.editor.clone();
+            move |event: &MouseDownEvent, phase, cx| {
+                if phase == DispatchPhase::Capture || event.button != MouseButton::Left {
+                    return;
+                }
+                let Some(target) = headers
+                    .iter()
+                    .find_map(|(hitbox, target)| hitbox.is_hovered(cx).then_some(*target))
+                else {
+                    return;
+                };
+
+                editor.update(cx, |editor, cx| {
+                    editor.change_selections(Some(Autoscroll::fit()), cx, |selections| {
+                        selections.select_anchor_ranges([target..target])
+                    });
+                });
+                cx.stop_propagation();
+            }
+        });
+    }
+
+    fn paint_minimap(&mut self, layout: &mut EditorLayout, cx: &mut WindowContext) {
+        // A hidden minimap is revealed by the editor's own mouse move listener.
+        let Some(minimap_layout) = layout
+            .minimap_layout
+            .as_ref()
+            .filter(|minimap_layout| minimap_layout.visible)
+        else {
+            return;
+        };
+
+        cx.paint_layer(minimap_layout.hitbox.bounds, |cx| {
+            let bounds = minimap_layout.hitbox.bounds;
+            cx.paint_quad(quad(
+                bounds,
+                Corners::default(),
+                cx.theme().colors().editor_background,
+                Edges {
+                    top: Pixels::ZERO,
+                    right: Pixels::ZERO,
+                    bottom: Pixels::ZERO,
+                    left: MinimapLayout::BORDER_WIDTH,
+                },
+                cx.theme().colors().border_variant,
+            ));
+
+            for marker in &minimap_layout.markers {
+                if marker.kind == MinimapMarkerKind::Line {
+                    cx.paint_quad(fill(
+                        minimap_layout.marker_bounds(marker),
+                        marker.rows.color,
+                    ));
+                }
+            }
+
+            self.paint_minimap_text(minimap_layout, &layout.position_map.snapshot, cx);
+
+            for marker in &minimap_layout.markers {
+                if marker.kind == MinimapMarkerKind::Gutter {
+                    cx.paint_quad(fill(
+                        minimap_layout.marker_bounds(marker),
+                        marker.rows.color,
+                    ));
+                }
+            }
+
+            cx.paint_quad(fill(
+                minimap_layout.viewport_bounds(),
+                cx.theme().colors().scrollbar_thumb_background,
+            ));
+        });
+
+        cx.set_cursor_style(CursorStyle::Arrow, &minimap_layout.hitbox);
+
+        let drag_row_height = minimap_layout.drag_row_height;
+        cx.on_mouse_event({
+            let editor = self.editor.clone();
+            let hitbox = minimap_layout.hitbox.clone();
+            let mut mouse_position = cx.mouse_position();
+            move |event: &MouseMoveEvent, phase, cx| {
+                if phase == DispatchPhase::Capture {
+                    return;
+                }
+
+                editor.update(cx, |editor, cx| {
+                    if event.pressed_button == Some(MouseButton::Left)
+                        && editor.scroll_manager.is_dragging_minimap()
+                    {
+                        let mut position = editor.scroll_position(cx);
+                        position.y += (event.position.y - mouse_position.y) / drag_row_height;
+                        position.y = position.y.max(0.);
+                        editor.set_scroll_position(position, cx);
+                        cx.stop_propagation();
+                    } else {
+                        editor.scroll_manager.set_is_dragging_minimap(false, cx);
+                        if !hitbox.is_hovered(cx)
+                            && EditorSettings::get_global(cx).minimap.show == ShowMinimap::Hover
+                        {
+                            cx.notify();
+                        }
+                    }
+                    mouse_position = event.position;
+                })
+            }
+        });
+
+        if self.editor.read(cx).scroll_manager.is_dragging_minimap() {
+            cx.on_mouse_event({
+                let editor = self.editor.clone();
+                move |_: &MouseUpEvent, phase, cx| {
+                    if phase == DispatchPhase::Capture {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_is_dragging_minimap(false, cx);
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        } else {
+            cx.on_mouse_event({
+                let editor = self.editor.clone();
+                let hitbox = minimap_layout.hitbox.clone();
+                let viewport_bounds = minimap_layout.viewport_bounds();
+                let scroll_top = minimap_layout.scroll_top;
+                let row_range = minimap_layout.visible_row_range.clone();
+                move |event: &MouseDownEvent, phase, cx| {
+                    if phase == DispatchPhase::Capture
+                        || event.button != MouseButton::Left
+                        || !hitbox.is_hovered(cx)
+                    {
+                        return;
+                    }
+
+                    editor.update(cx, |editor, cx| {
+                        editor.scroll_manager.set_is_dragging_minimap(true, cx);
+
+                        // Center the clicked row in the editor, unless the viewport was clicked.
+                        let y = event.position.y;
+                        if y < viewport_bounds.top() || viewport_bounds.bottom() < y {
+                            let clicked_row =
+                                scroll_top + (y - hitbox.top()) / MinimapLayout::ROW_HEIGHT;
+                            let mut position = editor.scroll_position(cx);
+                            position.y =
+                                (clicked_row - (row_range.end - row_range.start) / 2.).max(0.);
+                            editor.set_scroll_position(position, cx);
+                        }
+
+                        cx.stop_propagation();
+                    });
+                }
+            });
+        }
+    }
+
+    /// Paints the text in the minimap as one block per word, colored with the
+    /// syntax highlighting of the text.
+    fn paint_minimap_text(
+        &self,
+        minimap_layout: &MinimapLayout,
+        snapshot: &EditorSnapshot,
+        cx: &mut WindowContext,
+    ) {
+        let bounds = minimap_layout.hitbox.bounds;
+        let text_left = bounds.left() + MinimapLayout::BORDER_WIDTH + MinimapLayout::PADDING;
+        let max_columns =
+            ((bounds.right() - text_left) / MinimapLayout::COLUMN_WIDTH).floor() as u32;
+        let fade_out = |mut color: Hsla| {
+            color.fade_out(MinimapLayout::TEXT_FADE_OUT);
+            color
+        };
+
+        let mut row = minimap_layout.row_range.start;
+        let mut column = 0;
+        let mut word: Option<(u32, u32, Hsla)> = None;
+        let mut paint_word = |row: DisplayRow, word: Option<(u32, u32, Hsla)>| {
+            if let Some((start_column, end_column, color)) = word {
+                let end_column = end_column.min(max_columns);
+                if start_column < end_column {
+                    let top = minimap_layout.y_for_row(row.as_f32());
+                    cx.paint_quad(fill(
+                        Bounds::from_corners(
+                            point(
+                                text_left + start_column as f32 * MinimapLayout::COLUMN_WIDTH,
+                                top,
+                            ),
+                            point(
+                                text_left + end_column as f32 * MinimapLayout::COLUMN_WIDTH,
+                                top + MinimapLayout::TEXT_HEIGHT,
+                            ),
+                        ),
+                        color,
+                    ));
+                }
+            }
+        };
+
+        for chunk in
+            snapshot.highlighted_chunks(minimap_layout.row_range.clone(), true, &self.style)
+        {
+            let color = fade_out(
+                chunk
+                    .style
+                    .and_then(|style| style.color)
+                    .unwrap_or(self.style.text.color),
+            );
+            for character in chunk.text.chars() {
+                if character == '\n' {
+                    paint_word(row, word.take());
+                    row.0 += 1;
+                    column = 0;
+                    continue;
+                }
+
+                if column >= max_columns {
+                    continue;
+                }
+
+                if character.is_whitespace() {
+                    paint_word(row, word.take());
+                } else {
+                    if let Some((_, end_column, _)) = word
+                        .as_mut()
+                        .filter(|(_, _, word_color)| *word_color == color)
+                    {
+                        *end_column = column + 1;
+                    } else {
+                        paint_word(row, word.take());
+                        word = Some((column, column + 1, color));
+                    }
+                }
+                column += 1;
+            }
+        }
+        paint_word(row, word.take());
+    }
+
     fn collect_fast_scrollbar_markers(
         &self,
         layout: &EditorLayout,
@@ -3623,6 +4206,58 @@ impl EditorElement {
                     ..cmp::min(range.end.row().next_row(), end_row)
             };
 
+            let line_layouts = &layout.position_map.line_layouts;
+            if row_range
+                .iter_rows()
+                .any(|row| line_layouts[row.minus(start_row) as usize].has_rtl())
+            {
+                // Bidirectional text can split the range into several extents per row,
+                // so paint each of them separately.
+                let origin_x =
+                    layout.content_origin.x - layout.position_map.scroll_pixel_position.x;
+                for row in row_range.iter_rows() {
+                    let line_layout = &line_layouts[row.minus(start_row) as usize];
+                    let start_index = if row == range.start.row() {
+                        range.start.column() as usize
+                    } else {
+                        0
+                    };
+                    let x_ranges = if row == range.end.row() {
+                        line_layout
+                            .x_ranges_for_index_range(start_index..range.end.column() as usize)
+                    } else {
+                        let mut x_ranges =
+                            line_layout.x_ranges_for_index_range(start_index..line_layout.len);
+                        let line_end = line_layout.width..line_layout.width + line_end_overshoot;
+                        match x_ranges.last_mut() {
+                            Some(x_range) if x_range.end == line_end.start => {
+                                x_range.end = line_end.end
+                            }
+                            _ => x_ranges.push(line_end),
+                        }
+                        x_ranges
+                    };
+
+                    let start_y = layout.content_origin.y
+                        + row.as_f32() * layout.position_map.line_height
+                        - layout.position_map.scroll_pixel_position.y;
+                    for x_range in x_ranges {
+                        HighlightedRange {
+                            color,
+                            line_height: layout.position_map.line_height,
+                            corner_radius,
+                            start_y,
+                            lines: vec![HighlightedRangeLine {
+                                start_x: origin_x + x_range.start,
+                                end_x: origin_x + x_range.end,
+                            }],
+                        }
+                        .paint(layout.text_hitbox.bounds, cx);
+                    }
+                }
+                return;
+            }
+
             let highlighted_range = HighlightedRange {
                 color,
                 line_height: layout.position_map.line_height,
@@ -3681,6 +4316,42 @@ impl EditorElement {
         }
     }
 
+    fn accessibility_node(
+        &self,
+        bounds: Bounds<Pixels>,
+        layout: &EditorLayout,
+        cx: &WindowContext,
+    ) -> AccessibilityNode {
+        let snapshot = &layout.position_map.snapshot;
+        let (role, value) = match layout.mode {
+            EditorMode::SingleLine { .. } | EditorMode::AutoHeight { .. } => (
+                AccessibilityRole::TextInput,
+                snapshot.buffer_snapshot.text(),
+            ),
+            EditorMode::Full => {
+                // Large buffers are expensive to describe in full, so only expose the visible rows.
+                let start = DisplayPoint::new(layout.visible_display_row_range.start, 0);
+                let end = DisplayPoint::new(layout.visible_display_row_range.end, 0)
+                    .min(snapshot.max_point());
+                let range = start.to_point(snapshot)..end.to_point(snapshot);
+                (
+                    AccessibilityRole::TextEditor,
+                    snapshot.buffer_snapshot.text_for_range(range).collect(),
+                )
+            }
+        };
+
+        let editor = self.editor.read(cx);
+        let mut node = AccessibilityNode::new(role, bounds)
+            .value(value)
+            .focusable(true)
+            .focused(editor.focus_handle.is_focused(cx));
+        if let Some(placeholder) = editor.placeholder_text(cx) {
+            node = node.name(placeholder.to_string());
+        }
+        node
+    }
+
     fn paint_scroll_wheel_listener(&mut self, layout: &EditorLayout, cx: &mut WindowContext) {
         cx.on_mouse_event({
             let position_map = layout.position_map.clone();
@@ -3802,10 +4473,22 @@ impl EditorElement {
             let editor = self.editor.clone();
             let text_hitbox = layout.text_hitbox.clone();
             let gutter_hitbox = layout.gutter_hitbox.clone();
+            let hidden_minimap_hitbox = layout
+                .minimap_layout
+                .as_ref()
+                .filter(|minimap_layout| !minimap_layout.visible)
+                .map(|minimap_layout| minimap_layout.hitbox.clone());
 
             move |event: &MouseMoveEvent, phase, cx| {
                 if phase == DispatchPhase::Bubble {
                     editor.update(cx, |editor, cx| {
+                        // Reveal the minimap when it's hovered.
+                        if hidden_minimap_hitbox
+                            .as_ref()
+                            .map_or(false, |hitbox| hitbox.is_hovered(cx))
+                        {
+                            cx.notify();
+                        }
                         if editor.hover_state.focused(cx) {
                             return;
                         }
@@ -4421,6 +5104,56 @@ impl LineWithInvisibles {
         };
     }
 
+    pub fn has_rtl(&self) -> bool {
+        self.fragments.iter().any(|fragment| match fragment {
+            LineFragment::Text(shaped_line) => shaped_line.has_rtl(),
+            LineFragment::Element { .. } => false,
+        })
+    }
+
+    pub fn x_ranges_for_index_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        let mut x_ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        let mut push_x_range = |x_range: Range<Pixels>| match x_ranges.last_mut() {
+            Some(last_x_range) if last_x_range.end == x_range.start => {
+                last_x_range.end = x_range.end
+            }
+            _ => x_ranges.push(x_range),
+        };
+
+        let mut fragment_start_x = Pixels::ZERO;
+        let mut fragment_start_index = 0;
+        for fragment in &self.fragments {
+            match fragment {
+                LineFragment::Text(shaped_line) => {
+                    let fragment_end_index = fragment_start_index + shaped_line.len;
+                    let start = range.start.max(fragment_start_index);
+                    let end = range.end.min(fragment_end_index);
+                    if start < end {
+                        for x_range in shaped_line.x_ranges_for_index_range(
+                            start - fragment_start_index..end - fragment_start_index,
+                        ) {
+                            push_x_range(
+                                fragment_start_x + x_range.start..fragment_start_x + x_range.end,
+                            );
+                        }
+                    }
+                    fragment_start_x += shaped_line.width;
+                    fragment_start_index = fragment_end_index;
+                }
+                LineFragment::Element { len, size, .. } => {
+                    let fragment_end_index = fragment_start_index + len;
+                    if range.start < fragment_end_index && fragment_start_index < range.end {
+                        push_x_range(fragment_start_x..fragment_start_x + size.width);
+                    }
+                    fragment_start_x += size.width;
+                    fragment_start_index = fragment_end_index;
+                }
+            }
+        }
+
+        x_ranges
+    }
+
     pub fn x_for_index(&self, index: usize) -> Pixels {
         let mut fragment_start_x = Pixels::ZERO;
         let mut fragment_start_index = 0;
@@ -4699,7 +5432,13 @@ impl Element for EditorElement {
                     let text_width = bounds.size.width - gutter_dimensions.width;
 
                     let right_margin = if snapshot.mode == EditorMode::Full {
-                        EditorElement::SCROLLBAR_WIDTH
+                        let minimap_settings = EditorSettings::get_global(cx).minimap;
+                        let minimap_width = if minimap_settings.show == ShowMinimap::Always {
+                            px(minimap_settings.width)
+                        } else {
+                            px(0.)
+                        };
+                        EditorElement::SCROLLBAR_WIDTH + minimap_width
                     } else {
                         px(0.)
                     };
@@ -5074,6 +5813,15 @@ impl Element for EditorElement {
                         non_visible_cursors,
                         cx,
                     );
+                    let minimap_layout = self.layout_minimap(
+                        &snapshot,
+                        bounds,
+                        scroll_position,
+                        height_in_lines,
+                        cx,
+                    );
+                    let sticky_headers =
+                        self.layout_sticky_headers(&snapshot, bounds, start_row, line_height, cx);
 
                     let gutter_settings = EditorSettings::get_global(cx).gutter;
 
@@ -5138,6 +5886,31 @@ impl Element for EditorElement {
                         vec![]
                     };
 
+                    let bookmark_indicators = if gutter_settings.bookmarks {
+                        let occupied_rows = test_indicators
+                            .iter()
+                            .map(|(row, _)| *row)
+                            .chain(
+                                code_actions_indicator
+                                    .as_ref()
+                                    .and(newest_selection_head)
+                                    .map(|head| head.row()),
+                            )
+                            .collect::<Vec<_>>();
+                        self.layout_bookmark_indicators(
+                            line_height,
+                            start_row..end_row,
+                            &occupied_rows,
+                            scroll_pixel_position,
+                            &gutter_dimensions,
+                            &gutter_hitbox,
+                            &snapshot,
+                            cx,
+                        )
+                    } else {
+                        vec![]
+                    };
+
                     self.layout_signature_help(
                         &hitbox,
                         content_origin,
@@ -5232,6 +6005,8 @@ impl Element for EditorElement {
                         gutter_dimensions,
                         content_origin,
                         scrollbar_layout,
+                        minimap_layout,
+                        sticky_headers,
                         active_rows,
                         highlighted_rows,
                         highlighted_ranges,
@@ -5248,6 +6023,7 @@ impl Element for EditorElement {
                         selections,
                         mouse_context_menu,
                         test_indicators,
+                        bookmark_indicators,
                         code_actions_indicator,
                         gutter_fold_toggles,
                         crease_trailers,
@@ -5311,34 +6087,42 @@ impl Element for EditorElement {
                 }
             });
         let rem_size = self.rem_size(cx);
-        cx.with_rem_size(rem_size, |cx| {
-            cx.with_text_style(Some(text_style), |cx| {
-                cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
-                    self.paint_mouse_listeners(layout, hovered_hunk, cx);
-                    self.paint_background(layout, cx);
-                    self.paint_indent_guides(layout, cx);
-
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_blamed_display_rows(layout, cx);
-                        self.paint_line_numbers(layout, cx);
-                    }
+        // Describing the buffer's contents isn't free, so only do it when something reads them.
+        let accessibility_node = cx
+            .is_accessibility_enabled()
+            .then(|| self.accessibility_node(bounds, layout, cx));
+        cx.with_accessibility_node(accessibility_node, |cx| {
+            cx.with_rem_size(rem_size, |cx| {
+                cx.with_text_style(Some(text_style), |cx| {
+                    cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+                        self.paint_mouse_listeners(layout, hovered_hunk, cx);
+                        self.paint_background(layout, cx);
+                        self.paint_indent_guides(layout, cx);
+
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_blamed_display_rows(layout, cx);
+                            self.paint_line_numbers(layout, cx);
+                        }
 
-                    self.paint_text(layout, cx);
+                        self.paint_text(layout, cx);
 
-                    if !layout.blocks.is_empty() {
-                        cx.with_element_namespace("blocks", |cx| {
-                            self.paint_blocks(layout, cx);
-                        });
-                    }
+                        if !layout.blocks.is_empty() {
+                            cx.with_element_namespace("blocks", |cx| {
+                                self.paint_blocks(layout, cx);
+                            });
+                        }
 
-                    if layout.gutter_hitbox.size.width > Pixels::ZERO {
-                        self.paint_gutter_highlights(layout, cx);
-                        self.paint_gutter_indicators(layout, cx);
-                    }
+                        if layout.gutter_hitbox.size.width > Pixels::ZERO {
+                            self.paint_gutter_highlights(layout, cx);
+                            self.paint_gutter_indicators(layout, cx);
+                        }
 
-                    self.paint_scrollbar(layout, cx);
-                    self.paint_mouse_context_menu(layout, cx);
-                });
+                        self.paint_sticky_headers(layout, cx);
+                        self.paint_minimap(layout, cx);
+                        self.paint_scrollbar(layout, cx);
+                        self.paint_mouse_context_menu(layout, cx);
+                    });
+                })
             })
         })
     }
@@ -5360,6 +6144,8 @@ pub struct EditorLayout {
     gutter_dimensions: GutterDimensions,
     content_origin: gpui::Point<Pixels>,
     scrollbar_layout: Option<ScrollbarLayout>,
+    minimap_layout: Option<MinimapLayout>,
+    sticky_headers: Vec<StickyHeaderLayout>,
     mode: EditorMode,
     wrap_guides: SmallVec<[(Pixels, bool); 2]>,
     indent_guides: Option<Vec<IndentGuideLayout>>,
@@ -5379,7 +6165,8 @@ pub struct EditorLayout {
     visible_cursors: Vec<CursorLayout>,
     selections: Vec<(PlayerColor, Vec<SelectionLayout>)>,
     code_actions_indicator: Option<AnyElement>,
-    test_indicators: Vec<AnyElement>,
+    test_indicators: Vec<(DisplayRow, AnyElement)>,
+    bookmark_indicators: Vec<AnyElement>,
     gutter_fold_toggles: Vec<Option<AnyElement>>,
     crease_trailers: Vec<Option<CreaseTrailerLayout>>,
     mouse_context_menu: Option<AnyElement>,
@@ -5505,6 +6292,146 @@ impl ScrollbarLayout {
     }
 }
 
+/// The outline of a buffer, which sticky headers are derived from. It's cached on the editor
+/// for the version of the buffer it was computed for, as computing it queries the whole buffer.
+pub(crate) struct StickyScrollOutline {
+    buffer_id: BufferId,
+    version: clock::Global,
+    non_text_state_update_count: usize,
+    items: Arc<[StickyScrollOutlineItem]>,
+}
+
+struct StickyScrollOutlineItem {
+    range: Range<usize>,
+    depth: usize,
+}
+
+impl StickyScrollOutline {
+    fn get(editor: &mut Editor, buffer: &BufferSnapshot) -> Arc<[StickyScrollOutlineItem]> {
+        if let Some(outline) = editor.sticky_scroll_outline.as_ref() {
+            if outline.buffer_id == buffer.remote_id()
+                && outline.version == *buffer.version()
+                && outline.non_text_state_update_count == buffer.non_text_state_update_count()
+            {
+                return outline.items.clone();
+            }
+        }
+
+        let items = buffer
+            .outline(None)
+            .map(|outline| {
+                outline
+                    .items
+                    .into_iter()
+                    .map(|item| StickyScrollOutlineItem {
+                        range: item.range.to_offset(buffer),
+                        depth: item.depth,
+                    })
+                    .collect::<Arc<[_]>>()
+            })
+            .unwrap_or_else(|| Arc::from([]));
+        editor.sticky_scroll_outline = Some(Self {
+            buffer_id: buffer.remote_id(),
+            version: buffer.version().clone(),
+            non_text_state_update_count: buffer.non_text_state_update_count(),
+            items: items.clone(),
+        });
+        items
+    }
+}
+
+/// Returns the outline items containing the given offset, outermost first, skipping any that
+/// aren't nested within the previous one.
+fn outline_items_containing(
+    items: &[StickyScrollOutlineItem],
+    offset: usize,
+) -> impl Iterator<Item = &StickyScrollOutlineItem> {
+    let mut prev_depth = None;
+    items
+        .iter()
+        .filter(move |item| item.range.start <= offset && offset <= item.range.end)
+        .filter(move |item| {
+            let is_nested = prev_depth.map_or(true, |prev_depth| item.depth > prev_depth);
+            prev_depth = Some(item.depth);
+            is_nested
+        })
+}
+
+struct StickyHeaderLayout {
+    row: DisplayRow,
+    line: LineWithInvisibles,
+    /// The start of the outline item, where clicking the header moves the cursor.
+    target: Anchor,
+    hitbox: Hitbox,
+}
+
+struct MinimapLayout {
+    hitbox: Hitbox,
+    /// Whether the contents of the minimap are painted.
+    visible: bool,
+    /// The display row at the top of the minimap, as a fraction of rows.
+    scroll_top: f32,
+    row_range: Range<DisplayRow>,
+    /// The display rows visible in the editor.
+    visible_row_range: Range<f32>,
+    /// The distance the mouse must be dragged to scroll the editor by one row.
+    drag_row_height: Pixels,
+    markers: Vec<MinimapMarker>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MinimapMarkerKind {
+    /// Drawn behind the text, across the whole width of the minimap.
+    Line,
+    /// Drawn on the left edge of the minimap.
+    Gutter,
+}
+
+struct MinimapMarker {
+    kind: MinimapMarkerKind,
+    rows: ColoredRange<DisplayRow>,
+}
+
+impl MinimapLayout {
+    const BORDER_WIDTH: Pixels = px(1.0);
+    const PADDING: Pixels = px(4.0);
+    const ROW_HEIGHT: Pixels = px(3.0);
+    const TEXT_HEIGHT: Pixels = px(2.0);
+    const COLUMN_WIDTH: Pixels = px(1.5);
+    const GUTTER_MARKER_WIDTH: Pixels = px(3.0);
+    const TEXT_FADE_OUT: f32 = 0.4;
+    const MAX_SEARCH_RESULTS: usize = 10_000;
+
+    fn y_for_row(&self, row: f32) -> Pixels {
+        self.hitbox.top() + (row - self.scroll_top) * Self::ROW_HEIGHT
+    }
+
+    fn viewport_bounds(&self) -> Bounds<Pixels> {
+        Bounds::from_corners(
+            point(
+                self.hitbox.left() + Self::BORDER_WIDTH,
+                self.y_for_row(self.visible_row_range.start),
+            ),
+            point(
+                self.hitbox.right(),
+                self.y_for_row(self.visible_row_range.end),
+            ),
+        )
+    }
+
+    fn marker_bounds(&self, marker: &MinimapMarker) -> Bounds<Pixels> {
+        let left = self.hitbox.left() + Self::BORDER_WIDTH;
+        let right = match marker.kind {
+            MinimapMarkerKind::Line => self.hitbox.right(),
+            MinimapMarkerKind::Gutter => left + Self::GUTTER_MARKER_WIDTH,
+        };
+        Bounds::from_corners(
+            point(left, self.y_for_row(marker.rows.start.as_f32())),
+            point(right, self.y_for_row(marker.rows.end.as_f32() + 1.)),
+        )
+    }
+}
+
 struct CreaseTrailerLayout {
     element: AnyElement,
     bounds: Bounds<Pixels>,
@@ -5882,12 +6809,14 @@ mod tests {
     use super::*;
     use crate::{
         display_map::{BlockDisposition, BlockProperties},
+        editor_settings::{MinimapContent, StickyScrollContent},
         editor_tests::{init_test, update_test_language_settings},
         Editor, MultiBuffer,
     };
     use gpui::{TestAppContext, VisualTestContext};
-    use language::language_settings;
+    use language::{language_settings, Buffer, Language, LanguageConfig};
     use log::info;
+    use settings::SettingsStore;
     use std::num::NonZeroU32;
     use ui::Context;
     use util::test::sample_text;
@@ -6165,6 +7094,138 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_minimap_layout(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.minimap = Some(MinimapContent {
+                        show: Some(ShowMinimap::Always),
+                        width: None,
+                        git_diff: None,
+                        search_results: None,
+                        diagnostics: None,
+                    });
+                });
+            });
+        });
+
+        let window = cx.add_window(|cx| {
+            let buffer = MultiBuffer::build_simple(&sample_text(1000, 10, 'a'), cx);
+            Editor::new(EditorMode::Full, buffer, None, true, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+
+        let (_, state) = cx.draw(point(px(500.), px(500.)), size(px(500.), px(500.)), |_| {
+            EditorElement::new(&editor, style.clone())
+        });
+        let minimap = state.minimap_layout.as_ref().unwrap();
+        assert!(minimap.visible);
+        assert_eq!(minimap.row_range.start, DisplayRow(0));
+        assert!(minimap.row_range.end < DisplayRow(1000));
+        assert_eq!(minimap.viewport_bounds().top(), minimap.hitbox.top());
+
+        // The minimap scrolls along with the editor, reaching the end of the buffer
+        // when the editor does.
+        window
+            .update(cx, |editor, cx| {
+                editor.set_scroll_position(gpui::Point::new(0., 1000.), cx)
+            })
+            .unwrap();
+        let (_, state) = cx.draw(point(px(500.), px(500.)), size(px(500.), px(500.)), |_| {
+            EditorElement::new(&editor, style.clone())
+        });
+        let minimap = state.minimap_layout.as_ref().unwrap();
+        assert!(minimap.row_range.start > DisplayRow(0));
+        assert_eq!(minimap.row_range.end, DisplayRow(1000));
+    }
+
+    #[gpui::test]
+    async fn test_sticky_headers(cx: &mut TestAppContext) {
+        init_test(cx, |_| {});
+        cx.update(|cx| {
+            cx.update_global::<SettingsStore, _>(|settings, cx| {
+                settings.update_user_settings::<EditorSettings>(cx, |settings| {
+                    settings.sticky_scroll = Some(StickyScrollContent {
+                        enabled: Some(true),
+                        max_depth: None,
+                    });
+                });
+            });
+        });
+
+        let language = Arc::new(
+            Language::new(
+                LanguageConfig {
+                    name: "Rust".into(),
+                    ..Default::default()
+                },
+                Some(tree_sitter_rust::language()),
+            )
+            .with_outline_query(
+                r#"
+                (impl_item "impl" @context type: (_) @name) @item
+                (function_item "fn" @context name: (_) @name) @item
+                "#,
+            )
+            .unwrap(),
+        );
+        let body = "        let x = 1;\n".repeat(50);
+        let text = format!(
+            "impl Foo {{\n    fn one() {{\n{body}    }}\n\n    fn two() {{\n{body}    }}\n}}\n"
+        );
+        let buffer = cx.new_model(|cx| Buffer::local(text, cx).with_language(language, cx));
+        cx.condition(&buffer, |buffer, _| !buffer.is_parsing())
+            .await;
+
+        let window = cx.add_window(|cx| {
+            let buffer = cx.new_model(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+            Editor::new(EditorMode::Full, buffer, None, true, cx)
+        });
+        let cx = &mut VisualTestContext::from_window(*window, cx);
+        let editor = window.root(cx).unwrap();
+        let style = cx.update(|cx| editor.read(cx).style().unwrap().clone());
+        let sticky_rows_at = |scroll_top: f32, cx: &mut VisualTestContext| {
+            window
+                .update(cx, |editor, cx| {
+                    editor.set_scroll_position(gpui::Point::new(0., scroll_top), cx)
+                })
+                .unwrap();
+            let (_, state) = cx.draw(point(px(500.), px(500.)), size(px(500.), px(500.)), |_| {
+                EditorElement::new(&editor, style.clone())
+            });
+            state
+                .sticky_headers
+                .iter()
+                .map(|header| header.row.0)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sticky_rows_at(0., cx), Vec::<u32>::new());
+        assert_eq!(sticky_rows_at(1., cx), vec![0]);
+        assert_eq!(sticky_rows_at(10., cx), vec![0, 1]);
+        // The header of the first function is dropped when reaching its end.
+        assert_eq!(sticky_rows_at(51., cx), vec![0]);
+        assert_eq!(sticky_rows_at(70., cx), vec![0, 54]);
+
+        // The outline is only recomputed once the buffer changes.
+        let cached_outline = |cx: &mut VisualTestContext| {
+            editor.update(cx, |editor, _| {
+                editor.sticky_scroll_outline.as_ref().unwrap().items.clone()
+            })
+        };
+        let outline = cached_outline(cx);
+        sticky_rows_at(10., cx);
+        assert!(Arc::ptr_eq(&outline, &cached_outline(cx)));
+        buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+        cx.run_until_parked();
+        assert_eq!(sticky_rows_at(10., cx), vec![1, 2]);
+        assert!(!Arc::ptr_eq(&outline, &cached_outline(cx)));
+    }
+
     #[gpui::test]
     fn test_all_invisibles_drawing(cx: &mut TestAppContext) {
         const TAB_SIZE: u32 = 4;
@@ -848,6 +848,7 @@ impl Item for Editor {
 
     fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
         self.workspace = Some((workspace.weak_handle(), workspace.database_id()));
+        self.set_bookmark_store(workspace.bookmark_store().clone(), cx);
         let Some(workspace_id) = workspace.database_id() else {
             return;
         };
@@ -1086,19 +1087,10 @@ impl SearchableItem for Editor {
         query: &SearchQuery,
         cx: &mut ViewContext<Self>,
     ) {
-        let text = self.buffer.read(cx);
-        let text = text.snapshot(cx);
-        let text = text.text_for_range(identifier.clone()).collect::<Vec<_>>();
-        let text: Cow<_> = if text.len() == 1 {
-            text.first().cloned().unwrap().into()
-        } else {
-            let joined_chunks = text.join("");
-            joined_chunks.into()
-        };
-
-        if let Some(replacement) = query.replacement_for(&text) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        if let Some(replacement) = replacement_for_match(query, &snapshot, identifier) {
             self.transact(cx, |this, cx| {
-                this.edit([(identifier.clone(), Arc::from(&*replacement))], cx);
+                this.edit([(identifier.clone(), replacement)], cx);
             });
         }
     }
@@ -1108,20 +1100,11 @@ impl SearchableItem for Editor {
         query: &SearchQuery,
         cx: &mut ViewContext<Self>,
     ) {
-        let text = self.buffer.read(cx);
-        let text = text.snapshot(cx);
+        let snapshot = self.buffer.read(cx).snapshot(cx);
         let mut edits = vec![];
         for m in matches {
-            let text = text.text_for_range(m.clone()).collect::<Vec<_>>();
-            let text: Cow<_> = if text.len() == 1 {
-                text.first().cloned().unwrap().into()
-            } else {
-                let joined_chunks = text.join("");
-                joined_chunks.into()
-            };
-
-            if let Some(replacement) = query.replacement_for(&text) {
-                edits.push((m.clone(), Arc::from(&*replacement)));
+            if let Some(replacement) = replacement_for_match(query, &snapshot, m) {
+                edits.push((m.clone(), replacement));
             }
         }
 
@@ -1292,6 +1275,22 @@ pub fn active_match_index(
     }
 }
 
+/// Returns the replacement for a search match, which always lies within a single excerpt.
+fn replacement_for_match(
+    query: &SearchQuery,
+    snapshot: &MultiBufferSnapshot,
+    range: &Range<Anchor>,
+) -> Option<Arc<str>> {
+    let buffer = snapshot.buffer_for_excerpt(range.start.excerpt_id)?;
+    let buffer_range = buffer.summary_for_anchor::<usize>(&range.start.text_anchor)
+        ..buffer.summary_for_anchor::<usize>(&range.end.text_anchor);
+    let text = buffer
+        .text_for_range(buffer_range.clone())
+        .collect::<Cow<str>>();
+    let replacement = query.replacement_for(buffer.remote_id(), buffer_range, &text)?;
+    Some(Arc::from(&*replacement))
+}
+
 pub fn entry_label_color(selected: bool) -> Color {
     if selected {
         Color::Default
@@ -78,6 +78,45 @@ pub fn saturating_right(map: &DisplaySnapshot, mut point: DisplayPoint) -> Displ
     map.clip_point(point, Bias::Right)
 }
 
+/// Returns the point visually to the left of the current point, which differs from [`left`]
+/// in lines containing right-to-left text. Wraps to the end of the previous line if the point
+/// is already at the left edge of its line.
+pub fn visual_left(
+    map: &DisplaySnapshot,
+    point: DisplayPoint,
+    text_layout_details: &TextLayoutDetails,
+) -> DisplayPoint {
+    let line = map.layout_row(point.row(), text_layout_details);
+    if !line.has_rtl() {
+        return left(map, point);
+    }
+    match line.visual_index_left_of(point.column() as usize) {
+        Some(column) => map.clip_point(DisplayPoint::new(point.row(), column as u32), Bias::Left),
+        None => left(map, DisplayPoint::new(point.row(), 0)),
+    }
+}
+
+/// Returns the point visually to the right of the current point, which differs from [`right`]
+/// in lines containing right-to-left text. Wraps to the start of the next line if the point
+/// is already at the right edge of its line.
+pub fn visual_right(
+    map: &DisplaySnapshot,
+    point: DisplayPoint,
+    text_layout_details: &TextLayoutDetails,
+) -> DisplayPoint {
+    let line = map.layout_row(point.row(), text_layout_details);
+    if !line.has_rtl() {
+        return right(map, point);
+    }
+    match line.visual_index_right_of(point.column() as usize) {
+        Some(column) => map.clip_point(DisplayPoint::new(point.row(), column as u32), Bias::Right),
+        None => right(
+            map,
+            DisplayPoint::new(point.row(), map.line_len(point.row())),
+        ),
+    }
+}
+
 /// Returns a display point for the preceding displayed line (which might be a soft-wrapped line).
 pub fn up(
     map: &DisplaySnapshot,
@@ -577,7 +616,8 @@ mod tests {
     use crate::{
         display_map::Inlay,
         test::{editor_test_context::EditorTestContext, marked_display_snapshot},
-        Buffer, DisplayMap, DisplayRow, ExcerptRange, FoldPlaceholder, InlayId, MultiBuffer,
+        Buffer, DisplayMap, DisplayRow, ExcerptRange, FoldPlaceholder, InlayId, MoveLeft,
+        MoveRight, MultiBuffer, SelectRight,
     };
     use gpui::{font, Context as _};
     use language::Capability;
@@ -1058,6 +1098,41 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_visual_left_and_right(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            init_test(cx);
+        });
+
+        // "א" and "ב" are laid out right-to-left, so the line reads "aבאc" on screen.
+        let mut cx = EditorTestContext::new(cx).await;
+        cx.set_state("ˇaאבc\nd");
+
+        cx.update_editor(|editor, cx| editor.move_right(&MoveRight, cx));
+        cx.assert_editor_state("aאˇבc\nd");
+        cx.update_editor(|editor, cx| editor.move_right(&MoveRight, cx));
+        cx.assert_editor_state("aˇאבc\nd");
+        cx.update_editor(|editor, cx| editor.move_right(&MoveRight, cx));
+        cx.assert_editor_state("aאבcˇ\nd");
+        cx.update_editor(|editor, cx| editor.move_right(&MoveRight, cx));
+        cx.assert_editor_state("aאבc\nˇd");
+
+        cx.update_editor(|editor, cx| editor.move_left(&MoveLeft, cx));
+        cx.assert_editor_state("aאבcˇ\nd");
+        cx.update_editor(|editor, cx| editor.move_left(&MoveLeft, cx));
+        cx.assert_editor_state("aˇאבc\nd");
+        cx.update_editor(|editor, cx| editor.move_left(&MoveLeft, cx));
+        cx.assert_editor_state("aאˇבc\nd");
+        cx.update_editor(|editor, cx| editor.move_left(&MoveLeft, cx));
+        cx.assert_editor_state("ˇaאבc\nd");
+
+        cx.update_editor(|editor, cx| {
+            editor.select_right(&SelectRight, cx);
+            editor.select_right(&SelectRight, cx);
+        });
+        cx.assert_editor_state("«aˇ»אבc\nd");
+    }
+
     fn init_test(cx: &mut gpui::AppContext) {
         let settings_store = SettingsStore::test(cx);
         cx.set_global(settings_store);
@@ -1,11 +1,13 @@
 #[cfg(test)]
 mod file_finder_tests;
 
+mod file_finder_settings;
 mod new_path_prompt;
 
 use collections::{BTreeSet, HashMap};
 use editor::{scroll::Autoscroll, Bias, Editor};
-use fuzzy::{CharBag, PathMatch, PathMatchCandidate};
+use file_finder_settings::FileFinderSettings;
+use fuzzy::{CharBag, PathMatch, PathMatchCandidate, PathMatchOptions, PathScoreBoosts};
 use gpui::{
     actions, rems, Action, AnyElement, AppContext, DismissEvent, EventEmitter, FocusHandle,
     FocusableView, Model, Modifiers, ModifiersChangedEvent, ParentElement, Render, Styled, Task,
@@ -39,6 +41,7 @@ pub struct FileFinder {
 }
 
 pub fn init(cx: &mut AppContext) {
+    FileFinderSettings::register(cx);
     cx.observe_new_views(FileFinder::register).detach();
     cx.observe_new_views(NewPathPrompt::register).detach();
 }
@@ -83,6 +86,10 @@ impl FileFinder {
             .map(|(history_path, abs_path)| FoundPath::new(history_path, abs_path))
             .collect::<Vec<_>>();
 
+        let score_boosts = FileFinderSettings::get_global(cx)
+            .frecency_ranking
+            .then(|| Arc::new(frecency_score_boosts(workspace, cx)));
+
         let project = workspace.project().clone();
         let weak_workspace = cx.view().downgrade();
         workspace.toggle_modal(cx, |cx| {
@@ -93,6 +100,7 @@ impl FileFinder {
                 currently_opened_path,
                 history_items,
                 separate_history,
+                score_boosts,
                 cx,
             );
 
@@ -163,6 +171,7 @@ pub struct FileFinderDelegate {
     cancel_flag: Arc<AtomicBool>,
     history_items: Vec<FoundPath>,
     separate_history: bool,
+    score_boosts: Option<Arc<PathScoreBoosts>>,
 }
 
 /// Use a custom ordering for file finder: the regular one
@@ -224,12 +233,17 @@ impl Matches {
         history_items: impl IntoIterator<Item = &'a FoundPath> + Clone,
         currently_opened: Option<&'a FoundPath>,
         query: Option<&PathLikeWithPosition<FileSearchQuery>>,
+        score_boosts: Option<&PathScoreBoosts>,
         new_search_matches: impl Iterator<Item = ProjectPanelOrdMatch>,
         extend_old_matches: bool,
     ) {
         let no_history_score = 0;
-        let matching_history_paths =
-            matching_history_item_paths(history_items.clone(), currently_opened, query);
+        let matching_history_paths = matching_history_item_paths(
+            history_items.clone(),
+            currently_opened,
+            query,
+            score_boosts,
+        );
         let new_search_matches = new_search_matches
             .filter(|path_match| !matching_history_paths.contains_key(&path_match.0.path))
             .map(Match::Search)
@@ -301,6 +315,7 @@ fn matching_history_item_paths<'a>(
     history_items: impl IntoIterator<Item = &'a FoundPath>,
     currently_opened: Option<&'a FoundPath>,
     query: Option<&PathLikeWithPosition<FileSearchQuery>>,
+    score_boosts: Option<&PathScoreBoosts>,
 ) -> HashMap<Arc<Path>, Option<ProjectPanelOrdMatch>> {
     let Some(query) = query else {
         return history_items
@@ -353,7 +368,12 @@ fn matching_history_item_paths<'a>(
                 max_results,
             )
             .into_iter()
-            .map(|path_match| {
+            .map(|mut path_match| {
+                if let Some(boost) = score_boosts
+                    .and_then(|boosts| boosts.get(&path_match.worktree_id)?.get(&path_match.path))
+                {
+                    path_match.score *= boost;
+                }
                 (
                     Arc::clone(&path_match.path),
                     Some(ProjectPanelOrdMatch(path_match)),
@@ -378,6 +398,52 @@ impl FoundPath {
 
 const MAX_RECENT_SELECTIONS: usize = 20;
 
+/// The number of removed or swapped query characters tolerated when typo
+/// tolerance is enabled.
+const MAX_TYPOS: usize = 2;
+
+/// How much the frecency of a file can raise its score, relative to its score.
+const FRECENCY_WEIGHT: f64 = 0.5;
+
+/// Boosts the scores of the files in the workspace's visible worktrees by how
+/// frequently and recently they were opened.
+fn frecency_score_boosts(workspace: &Workspace, cx: &AppContext) -> PathScoreBoosts {
+    let frecencies = workspace.file_frecencies();
+    let mut score_boosts = PathScoreBoosts::default();
+    if frecencies.is_empty() {
+        return score_boosts;
+    }
+
+    let worktrees = workspace
+        .project()
+        .read(cx)
+        .visible_worktrees(cx)
+        .map(|worktree| {
+            let worktree = worktree.read(cx);
+            (worktree.id(), worktree.abs_path())
+        })
+        .collect::<Vec<_>>();
+    for (abs_path, frecency) in frecencies {
+        let Some((worktree_id, path)) =
+            worktrees
+                .iter()
+                .find_map(|(worktree_id, worktree_abs_path)| {
+                    Some((*worktree_id, abs_path.strip_prefix(worktree_abs_path).ok()?))
+                })
+        else {
+            continue;
+        };
+        score_boosts
+            .entry(worktree_id.to_usize())
+            .or_default()
+            .insert(
+                Arc::from(path),
+                1. + FRECENCY_WEIGHT * frecency / (frecency + 1.),
+            );
+    }
+    score_boosts
+}
+
 #[cfg(not(test))]
 fn history_file_exists(abs_path: &PathBuf) -> bool {
     abs_path.exists()
@@ -416,6 +482,7 @@ impl FileFinderDelegate {
         currently_opened_path: Option<FoundPath>,
         history_items: Vec<FoundPath>,
         separate_history: bool,
+        score_boosts: Option<Arc<PathScoreBoosts>>,
         cx: &mut ViewContext<FileFinder>,
     ) -> Self {
         Self::subscribe_to_updates(&project, cx);
@@ -434,6 +501,7 @@ impl FileFinderDelegate {
             cancel_flag: Arc::new(AtomicBool::new(false)),
             history_items,
             separate_history,
+            score_boosts,
         }
     }
 
@@ -481,17 +549,27 @@ impl FileFinderDelegate {
             })
             .collect::<Vec<_>>();
 
+        let options = PathMatchOptions {
+            smart_case: false,
+            max_results: 100,
+            max_typos: if FileFinderSettings::get_global(cx).typo_tolerance {
+                MAX_TYPOS
+            } else {
+                0
+            },
+            score_boosts: self.score_boosts.clone(),
+        };
+
         let search_id = util::post_inc(&mut self.search_count);
         self.cancel_flag.store(true, atomic::Ordering::Relaxed);
         self.cancel_flag = Arc::new(AtomicBool::new(false));
         let cancel_flag = self.cancel_flag.clone();
         cx.spawn(|picker, mut cx| async move {
-            let matches = fuzzy::match_path_sets(
+            let matches = fuzzy::match_path_sets_with_options(
                 candidate_sets.as_slice(),
                 query.path_like.path_query(),
                 relative_to,
-                false,
-                100,
+                &options,
                 &cancel_flag,
                 cx.background_executor().clone(),
             )
@@ -529,6 +607,7 @@ impl FileFinderDelegate {
                 &self.history_items,
                 self.currently_opened_path.as_ref(),
                 Some(&query),
+                self.score_boosts.as_deref(),
                 matches.into_iter(),
                 extend_old_matches,
             );
@@ -785,6 +864,7 @@ impl PickerDelegate for FileFinderDelegate {
                 }),
                 self.currently_opened_path.as_ref(),
                 None,
+                None,
                 None.into_iter(),
                 false,
             );
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FileFinderSettings {
+    pub typo_tolerance: bool,
+    pub frecency_ranking: bool,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct FileFinderSettingsContent {
+    /// Whether to match files whose paths contain the query with a few of its
+    /// characters missing or swapped.
+    ///
+    /// Default: false
+    pub typo_tolerance: Option<bool>,
+    /// Whether to rank the files that were opened frequently and recently in
+    /// the workspace above the other matches.
+    ///
+    /// Default: true
+    pub frecency_ranking: Option<bool>,
+}
+
+impl Settings for FileFinderSettings {
+    const KEY: Option<&'static str> = Some("file_finder");
+
+    type FileContent = FileFinderSettingsContent;
+
+    fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+        sources.json_merge()
+    }
+}
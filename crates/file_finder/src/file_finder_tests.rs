@@ -3,10 +3,11 @@ use std::{assert_eq, future::IntoFuture, path::Path, time::Duration};
 use super::*;
 use editor::Editor;
 use gpui::{Entity, TestAppContext, VisualTestContext};
-use menu::{Confirm, SelectNext, SelectPrev};
+use menu::{Cancel, Confirm, SelectNext, SelectPrev};
 use project::FS_WATCH_LATENCY;
 use serde_json::json;
-use workspace::{AppState, ToggleFileFinder, Workspace};
+use settings::SettingsStore;
+use workspace::{AppState, ToggleFileFinder, Workspace, WORKSPACE_DB};
 
 #[ctor::ctor]
 fn init_logger() {
@@ -559,6 +560,149 @@ async fn test_path_distance_ordering(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+async fn test_typo_tolerance(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "src": {
+                    "workspace.rs": "",
+                    "pane.rs": "",
+                }
+            }),
+        )
+        .await;
+
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+    let (picker, _workspace, cx) = build_find_picker(project, cx);
+
+    picker
+        .update(cx, |f, cx| {
+            f.delegate.spawn_search(test_path_like("wrokspace"), cx)
+        })
+        .await;
+    picker.update(cx, |picker, _| {
+        assert_eq!(picker.delegate.matches.len(), 0);
+    });
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<FileFinderSettings>(cx, |settings| {
+                settings.typo_tolerance = Some(true);
+            });
+        });
+    });
+    picker
+        .update(cx, |f, cx| {
+            f.delegate.spawn_search(test_path_like("wrokspace"), cx)
+        })
+        .await;
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("src/workspace.rs")]
+        );
+    });
+}
+
+#[gpui::test]
+async fn test_frecency_ranking(cx: &mut TestAppContext) {
+    let app_state = init_test(cx);
+    cx.executor().allow_parking();
+    app_state
+        .fs
+        .as_fake()
+        .insert_tree(
+            "/root",
+            json!({
+                "a": { "main.rs": "" },
+                "b": { "main.rs": "" },
+            }),
+        )
+        .await;
+    let workspace_id = WORKSPACE_DB.next_id().await.unwrap();
+
+    // Open b/main.rs in an earlier session of the workspace.
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+    let window =
+        cx.add_window(|cx| Workspace::new(Some(workspace_id), project, app_state.clone(), cx));
+    window
+        .update(cx, |workspace, cx| {
+            workspace.open_abs_path(PathBuf::from("/root/b/main.rs"), true, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+    cx.run_until_parked();
+    // Writes to the database are queued in order, so once this one is done the
+    // opening of b/main.rs has been recorded.
+    WORKSPACE_DB.next_id().await.unwrap();
+
+    // Without any navigation history, b/main.rs is ranked first in a new session.
+    let project = Project::test(app_state.fs.clone(), ["/root".as_ref()], cx).await;
+    let (workspace, cx) = cx.add_window_view(|cx| {
+        cx.activate_window();
+        let workspace = Workspace::new(Some(workspace_id), project, app_state.clone(), cx);
+        workspace
+            .active_pane()
+            .update(cx, |pane, cx| pane.focus(cx));
+        workspace
+    });
+    // Let the file history load in the background.
+    cx.run_until_parked();
+    let picker = open_file_picker(&workspace, cx);
+    picker
+        .update(cx, |f, cx| {
+            f.delegate.spawn_search(test_path_like("main"), cx)
+        })
+        .await;
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("b/main.rs"), PathBuf::from("a/main.rs")]
+        );
+    });
+
+    cx.update(|cx| {
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store.update_user_settings::<FileFinderSettings>(cx, |settings| {
+                settings.frecency_ranking = Some(false);
+            });
+        });
+    });
+    cx.dispatch_action(Cancel);
+    let picker = open_file_picker(&workspace, cx);
+    picker
+        .update(cx, |f, cx| {
+            f.delegate.spawn_search(test_path_like("main"), cx)
+        })
+        .await;
+    picker.update(cx, |picker, _| {
+        assert_eq!(
+            collect_search_matches(picker).search_paths_only(),
+            vec![PathBuf::from("a/main.rs"), PathBuf::from("b/main.rs")]
+        );
+    });
+    cx.dispatch_action(Cancel);
+
+    // Opening a file updates the frecencies without waiting for the database.
+    workspace
+        .update(cx, |workspace, cx| {
+            workspace.open_abs_path(PathBuf::from("/root/a/main.rs"), true, cx)
+        })
+        .await
+        .unwrap();
+    workspace.update(cx, |workspace, _| {
+        let frecencies = workspace.file_frecencies();
+        assert_eq!(frecencies.len(), 2);
+        assert!((frecencies[Path::new("/root/a/main.rs")] - 1.).abs() < 0.01);
+    });
+}
+
 #[gpui::test]
 async fn test_search_worktree_without_files(cx: &mut TestAppContext) {
     let app_state = init_test(cx);
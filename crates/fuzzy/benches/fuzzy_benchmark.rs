@@ -0,0 +1,110 @@
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+use criterion::{black_box, BenchmarkId, Criterion, Throughput};
+use fuzzy::{match_path_sets_with_options, CharBag, PathMatchCandidate, PathMatchOptions};
+use gpui::BackgroundExecutor;
+
+const PATH_COUNT: usize = 100_000;
+
+struct PathSet {
+    paths: Vec<(PathBuf, CharBag)>,
+}
+
+impl<'a> fuzzy::PathMatchCandidateSet<'a> for PathSet {
+    type Candidates = Box<dyn 'a + Iterator<Item = PathMatchCandidate<'a>>>;
+
+    fn id(&self) -> usize {
+        0
+    }
+
+    fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    fn prefix(&self) -> Arc<str> {
+        "".into()
+    }
+
+    fn candidates(&'a self, start: usize) -> Self::Candidates {
+        Box::new(
+            self.paths[start..]
+                .iter()
+                .map(|(path, char_bag)| PathMatchCandidate {
+                    path,
+                    char_bag: *char_bag,
+                }),
+        )
+    }
+}
+
+fn generate_path_set(path_count: usize) -> PathSet {
+    let paths = (0..path_count)
+        .map(|ix| {
+            let path = PathBuf::from(format!(
+                "crates/crate_{}/src/module_{}/file_{ix}.rs",
+                ix % 97,
+                ix % 31
+            ));
+            let char_bag = CharBag::from_iter(path.to_string_lossy().to_lowercase().chars());
+            (path, char_bag)
+        })
+        .collect();
+    PathSet { paths }
+}
+
+fn path_matching_benchmarks(c: &mut Criterion, executor: &BackgroundExecutor) {
+    let path_sets = [generate_path_set(PATH_COUNT)];
+    let cancel_flag = AtomicBool::new(false);
+
+    let mut group = c.benchmark_group("match_path_sets");
+    group.throughput(Throughput::Elements(PATH_COUNT as u64));
+    for (query, max_typos) in [
+        ("modfile42", 0),
+        ("modfile42", 2),
+        ("mdofile42", 2),
+        ("mdofiel42", 2),
+    ] {
+        let options = PathMatchOptions {
+            smart_case: false,
+            max_results: 100,
+            max_typos,
+            score_boosts: None,
+        };
+        group.bench_with_input(
+            BenchmarkId::new(query, format!("{max_typos} typos")),
+            &options,
+            |b, options| {
+                b.iter(|| {
+                    black_box(executor.block(match_path_sets_with_options(
+                        &path_sets,
+                        query,
+                        None,
+                        options,
+                        &cancel_flag,
+                        executor.clone(),
+                    )))
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn main() {
+    // Path matching runs on gpui's background executor, which is only available to
+    // benchmarks in test mode.
+    gpui::run_test(
+        1,
+        0,
+        &mut |dispatcher, _| {
+            let executor = BackgroundExecutor::new(Arc::new(dispatcher));
+            let mut criterion = Criterion::default().configure_from_args();
+            path_matching_benchmarks(&mut criterion, &executor);
+            criterion.final_summary();
+        },
+        None,
+    );
+}
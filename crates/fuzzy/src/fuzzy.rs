@@ -4,7 +4,9 @@ mod paths;
 mod strings;
 
 pub use char_bag::CharBag;
+pub use matcher::QueryVariant;
 pub use paths::{
-    match_fixed_path_set, match_path_sets, PathMatch, PathMatchCandidate, PathMatchCandidateSet,
+    match_fixed_path_set, match_path_sets, match_path_sets_with_options, PathMatch,
+    PathMatchCandidate, PathMatchCandidateSet, PathMatchOptions, PathScoreBoosts,
 };
 pub use strings::{match_strings, StringMatch, StringMatchCandidate};
@@ -1,5 +1,6 @@
 use std::{
     borrow::Cow,
+    collections::HashSet,
     sync::atomic::{self, AtomicBool},
 };
 
@@ -9,12 +10,19 @@ const BASE_DISTANCE_PENALTY: f64 = 0.6;
 const ADDITIONAL_DISTANCE_PENALTY: f64 = 0.05;
 const MIN_DISTANCE_PENALTY: f64 = 0.2;
 
+/// The factor applied to the score of a match for every typo in the query.
+const TYPO_PENALTY: f64 = 0.5;
+/// The number of query characters for which one typo is tolerated.
+const CHARS_PER_TYPO: usize = 4;
+
 pub struct Matcher<'a> {
     query: &'a [char],
     lowercase_query: &'a [char],
     query_char_bag: CharBag,
+    query_variants: &'a [QueryVariant],
     smart_case: bool,
     max_results: usize,
+    max_score_boost: f64,
     min_score: f64,
     match_positions: Vec<usize>,
     last_positions: Vec<usize>,
@@ -22,6 +30,58 @@ pub struct Matcher<'a> {
     best_position_matrix: Vec<usize>,
 }
 
+/// A version of the query with some of its characters removed or swapped, which is
+/// matched against the candidates that the query itself doesn't match.
+pub struct QueryVariant {
+    query: Vec<char>,
+    lowercase_query: Vec<char>,
+    char_bag: CharBag,
+    typo_count: usize,
+}
+
+impl QueryVariant {
+    /// Returns the versions of the query with up to `max_typos` characters removed or
+    /// swapped with the next one, fewest typos first. Only one typo is tolerated for
+    /// every four characters of the query.
+    pub fn for_typos(query: &[char], lowercase_query: &[char], max_typos: usize) -> Vec<Self> {
+        let max_typos = max_typos.min(query.len() / CHARS_PER_TYPO);
+        if max_typos == 0 || query.len() != lowercase_query.len() {
+            return Vec::new();
+        }
+
+        let mut seen = HashSet::from([lowercase_query.to_vec()]);
+        let mut variants = Vec::new();
+        let mut previous = vec![(query.to_vec(), lowercase_query.to_vec())];
+        for typo_count in 1..=max_typos {
+            let mut edits = Vec::new();
+            for (query, lowercase_query) in &previous {
+                for ix in 0..query.len() {
+                    let mut removed = (query.clone(), lowercase_query.clone());
+                    removed.0.remove(ix);
+                    removed.1.remove(ix);
+                    edits.push(removed);
+
+                    if ix + 1 < query.len() && lowercase_query[ix] != lowercase_query[ix + 1] {
+                        let mut swapped = (query.clone(), lowercase_query.clone());
+                        swapped.0.swap(ix, ix + 1);
+                        swapped.1.swap(ix, ix + 1);
+                        edits.push(swapped);
+                    }
+                }
+            }
+            edits.retain(|(_, lowercase_query)| seen.insert(lowercase_query.clone()));
+            variants.extend(edits.iter().map(|(query, lowercase_query)| QueryVariant {
+                query: query.clone(),
+                lowercase_query: lowercase_query.clone(),
+                char_bag: CharBag::from(&lowercase_query[..]),
+                typo_count,
+            }));
+            previous = edits;
+        }
+        variants
+    }
+}
+
 pub trait Match: Ord {
     fn score(&self) -> f64;
     fn set_positions(&mut self, positions: Vec<usize>);
@@ -44,6 +104,8 @@ impl<'a> Matcher<'a> {
             query,
             lowercase_query,
             query_char_bag,
+            query_variants: &[],
+            max_score_boost: 1.0,
             min_score: 0.0,
             last_positions: vec![0; lowercase_query.len()],
             match_positions: vec![0; query.len()],
@@ -54,6 +116,20 @@ impl<'a> Matcher<'a> {
         }
     }
 
+    /// Matches the candidates that the query doesn't match with the given variants of
+    /// the query instead, penalizing their scores for each typo.
+    pub fn with_query_variants(mut self, query_variants: &'a [QueryVariant]) -> Self {
+        self.query_variants = query_variants;
+        self
+    }
+
+    /// Sets the largest factor by which the built matches may multiply the scores
+    /// passed to them, so that candidates aren't skipped for scoring too low.
+    pub fn with_max_score_boost(mut self, max_score_boost: f64) -> Self {
+        self.max_score_boost = max_score_boost.max(1.0);
+        self
+    }
+
     pub fn match_candidates<C: MatchCandidate, R, F>(
         &mut self,
         prefix: &[char],
@@ -70,7 +146,13 @@ impl<'a> Matcher<'a> {
         let mut lowercase_candidate_chars = Vec::new();
 
         for candidate in candidates {
-            if !candidate.has_chars(self.query_char_bag) {
+            let has_query_chars = candidate.has_chars(self.query_char_bag);
+            if !has_query_chars
+                && !self
+                    .query_variants
+                    .iter()
+                    .any(|variant| candidate.has_chars(variant.char_bag))
+            {
                 continue;
             }
 
@@ -85,32 +167,41 @@ impl<'a> Matcher<'a> {
                 lowercase_candidate_chars.append(&mut c.to_lowercase().collect::<Vec<_>>());
             }
 
-            if !self.find_last_positions(lowercase_prefix, &lowercase_candidate_chars) {
-                continue;
+            let mut score = 0.0;
+            if has_query_chars {
+                score = self.score_candidate(
+                    &candidate_chars,
+                    &lowercase_candidate_chars,
+                    prefix,
+                    lowercase_prefix,
+                    1.0,
+                );
+            }
+            let mut variant_positions = None;
+            if score <= 0.0 && !self.query_variants.is_empty() {
+                let (variant_score, positions) = self.score_query_variants(
+                    &candidate,
+                    &candidate_chars,
+                    &lowercase_candidate_chars,
+                    prefix,
+                    lowercase_prefix,
+                );
+                score = variant_score;
+                variant_positions = Some(positions);
             }
-
-            let matrix_len = self.query.len() * (prefix.len() + candidate_chars.len());
-            self.score_matrix.clear();
-            self.score_matrix.resize(matrix_len, None);
-            self.best_position_matrix.clear();
-            self.best_position_matrix.resize(matrix_len, 0);
-
-            let score = self.score_match(
-                &candidate_chars,
-                &lowercase_candidate_chars,
-                prefix,
-                lowercase_prefix,
-            );
 
             if score > 0.0 {
                 let mut mat = build_match(&candidate, score);
                 if let Err(i) = results.binary_search_by(|m| mat.cmp(m)) {
+                    let positions = variant_positions
+                        .take()
+                        .unwrap_or_else(|| self.match_positions.clone());
                     if results.len() < self.max_results {
-                        mat.set_positions(self.match_positions.clone());
+                        mat.set_positions(positions);
                         results.insert(i, mat);
                     } else if i < results.len() {
                         results.pop();
-                        mat.set_positions(self.match_positions.clone());
+                        mat.set_positions(positions);
                         results.insert(i, mat);
                     }
                     if results.len() == self.max_results {
@@ -121,6 +212,83 @@ impl<'a> Matcher<'a> {
         }
     }
 
+    /// Scores the candidate against the current query, multiplying the score by
+    /// `score_multiplier`. Returns zero if the candidate doesn't match.
+    fn score_candidate(
+        &mut self,
+        candidate_chars: &[char],
+        lowercase_candidate_chars: &[char],
+        prefix: &[char],
+        lowercase_prefix: &[char],
+        score_multiplier: f64,
+    ) -> f64 {
+        if !self.find_last_positions(lowercase_prefix, lowercase_candidate_chars) {
+            return 0.0;
+        }
+
+        let matrix_len = self.query.len() * (prefix.len() + candidate_chars.len());
+        self.score_matrix.clear();
+        self.score_matrix.resize(matrix_len, None);
+        self.best_position_matrix.clear();
+        self.best_position_matrix.resize(matrix_len, 0);
+
+        self.score_match(
+            candidate_chars,
+            lowercase_candidate_chars,
+            prefix,
+            lowercase_prefix,
+            score_multiplier,
+        )
+    }
+
+    /// Returns the best score of the candidate against the query variants with the fewest
+    /// typos that match it, along with the positions of the matched characters.
+    fn score_query_variants<C: MatchCandidate>(
+        &mut self,
+        candidate: &C,
+        candidate_chars: &[char],
+        lowercase_candidate_chars: &[char],
+        prefix: &[char],
+        lowercase_prefix: &[char],
+    ) -> (f64, Vec<usize>) {
+        let (query, lowercase_query) = (self.query, self.lowercase_query);
+        let mut best_score = 0.0;
+        let mut best_positions = Vec::new();
+        let mut best_typo_count = 0;
+        for variant in self.query_variants {
+            // Variants are ordered by their number of typos, so once one matches, the
+            // variants with more typos aren't worth scoring.
+            if best_score > 0.0 && variant.typo_count > best_typo_count {
+                break;
+            }
+            if !candidate.has_chars(variant.char_bag) {
+                continue;
+            }
+            self.set_query(&variant.query, &variant.lowercase_query);
+            let score = self.score_candidate(
+                candidate_chars,
+                lowercase_candidate_chars,
+                prefix,
+                lowercase_prefix,
+                TYPO_PENALTY.powi(variant.typo_count as i32),
+            );
+            if score > best_score {
+                best_score = score;
+                best_typo_count = variant.typo_count;
+                best_positions.clone_from(&self.match_positions);
+            }
+        }
+        self.set_query(query, lowercase_query);
+        (best_score, best_positions)
+    }
+
+    fn set_query(&mut self, query: &'a [char], lowercase_query: &'a [char]) {
+        self.query = query;
+        self.lowercase_query = lowercase_query;
+        self.last_positions.resize(lowercase_query.len(), 0);
+        self.match_positions.resize(query.len(), 0);
+    }
+
     fn find_last_positions(
         &mut self,
         lowercase_prefix: &[char],
@@ -146,16 +314,12 @@ impl<'a> Matcher<'a> {
         path_cased: &[char],
         prefix: &[char],
         lowercase_prefix: &[char],
+        score_multiplier: f64,
     ) -> f64 {
-        let score = self.recursive_score_match(
-            path,
-            path_cased,
-            prefix,
-            lowercase_prefix,
-            0,
-            0,
-            self.query.len() as f64,
-        ) * self.query.len() as f64;
+        let max_score = self.query.len() as f64 * score_multiplier;
+        let score =
+            self.recursive_score_match(path, path_cased, prefix, lowercase_prefix, 0, 0, max_score)
+                * max_score;
 
         if score <= 0.0 {
             return 0.0;
@@ -273,7 +437,7 @@ impl<'a> Matcher<'a> {
                 if self.min_score > 0.0 {
                     next_score = cur_score * multiplier;
                     // Scores only decrease. If we can't pass the previous best, bail
-                    if next_score < self.min_score {
+                    if next_score * self.max_score_boost < self.min_score {
                         // Ensure that score is non-zero so we use it in the memo table.
                         if score == 0.0 {
                             score = 1e-18;
@@ -422,10 +586,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_match_path_entries_with_typos() {
+        let paths = vec![
+            "src/workspace.rs",
+            "src/wrokspace.rs",
+            "src/pane.rs",
+            "tests/work.rs",
+        ];
+
+        // Without typo tolerance, only the paths containing the query in order match.
+        assert_eq!(
+            match_single_path_query("wrokspace", false, &paths),
+            vec![("src/wrokspace.rs", vec![4, 5, 6, 7, 8, 9, 10, 11, 12])]
+        );
+
+        // Exact matches rank above the matches with typos.
+        let results = match_single_path_query_with_typos("wrokspace", false, 2, &paths);
+        assert_eq!(
+            results.iter().map(|(path, _)| *path).collect::<Vec<_>>(),
+            vec!["src/wrokspace.rs", "src/workspace.rs"]
+        );
+
+        // A missing character in the path is tolerated.
+        let results = match_single_path_query_with_typos("panne", false, 1, &paths);
+        assert_eq!(results, vec![("src/pane.rs", vec![4, 5, 6, 7])]);
+
+        // Short queries don't tolerate any typos.
+        assert!(match_single_path_query_with_typos("pna", false, 2, &paths).is_empty());
+    }
+
+    #[test]
+    fn test_query_variants() {
+        let query = "abcd".chars().collect::<Vec<_>>();
+        let variants = QueryVariant::for_typos(&query, &query, 5);
+        let mut variant_queries = variants
+            .iter()
+            .map(|variant| {
+                assert_eq!(variant.typo_count, 1);
+                variant.query.iter().collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        variant_queries.sort();
+        assert_eq!(
+            variant_queries,
+            vec!["abc", "abd", "abdc", "acbd", "acd", "bacd", "bcd"]
+        );
+
+        let query = "aaaa".chars().collect::<Vec<_>>();
+        let variants = QueryVariant::for_typos(&query, &query, 1);
+        assert_eq!(variants.len(), 1);
+        assert_eq!(variants[0].query, vec!['a', 'a', 'a']);
+    }
+
     fn match_single_path_query<'a>(
         query: &str,
         smart_case: bool,
         paths: &[&'a str],
+    ) -> Vec<(&'a str, Vec<usize>)> {
+        match_single_path_query_with_typos(query, smart_case, 0, paths)
+    }
+
+    fn match_single_path_query_with_typos<'a>(
+        query: &str,
+        smart_case: bool,
+        max_typos: usize,
+        paths: &[&'a str],
     ) -> Vec<(&'a str, Vec<usize>)> {
         let lowercase_query = query.to_lowercase().chars().collect::<Vec<_>>();
         let query = query.chars().collect::<Vec<_>>();
@@ -445,7 +671,9 @@ mod tests {
             });
         }
 
-        let mut matcher = Matcher::new(&query, &lowercase_query, query_chars, smart_case, 100);
+        let query_variants = QueryVariant::for_typos(&query, &lowercase_query, max_typos);
+        let mut matcher = Matcher::new(&query, &lowercase_query, query_chars, smart_case, 100)
+            .with_query_variants(&query_variants);
 
         let cancel_flag = AtomicBool::new(false);
         let mut results = Vec::new();
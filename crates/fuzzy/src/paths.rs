@@ -1,3 +1,4 @@
+use collections::HashMap;
 use gpui::BackgroundExecutor;
 use std::{
     borrow::Cow,
@@ -7,7 +8,7 @@ use std::{
 };
 
 use crate::{
-    matcher::{Match, MatchCandidate, Matcher},
+    matcher::{Match, MatchCandidate, Matcher, QueryVariant},
     CharBag,
 };
 
@@ -29,6 +30,39 @@ pub struct PathMatch {
     pub distance_to_relative_ancestor: usize,
 }
 
+/// Factors by which the scores of matching paths are multiplied, keyed by the id of
+/// their candidate set.
+pub type PathScoreBoosts = HashMap<usize, HashMap<Arc<Path>, f64>>;
+
+#[derive(Clone, Debug)]
+pub struct PathMatchOptions {
+    pub smart_case: bool,
+    pub max_results: usize,
+    /// The number of removed or swapped query characters that are tolerated.
+    pub max_typos: usize,
+    pub score_boosts: Option<Arc<PathScoreBoosts>>,
+}
+
+impl PathMatchOptions {
+    fn score_boost(&self, worktree_id: usize, path: &Path) -> f64 {
+        self.score_boosts
+            .as_ref()
+            .and_then(|boosts| boosts.get(&worktree_id)?.get(path))
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    fn max_score_boost(&self) -> f64 {
+        self.score_boosts.as_ref().map_or(1.0, |boosts| {
+            boosts
+                .values()
+                .flat_map(|boosts| boosts.values())
+                .copied()
+                .fold(1.0, f64::max)
+        })
+    }
+}
+
 pub trait PathMatchCandidateSet<'a>: Send + Sync {
     type Candidates: Iterator<Item = PathMatchCandidate<'a>>;
     fn id(&self) -> usize;
@@ -136,6 +170,34 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     cancel_flag: &AtomicBool,
     executor: BackgroundExecutor,
 ) -> Vec<PathMatch> {
+    let options = PathMatchOptions {
+        smart_case,
+        max_results,
+        max_typos: 0,
+        score_boosts: None,
+    };
+    match_path_sets_with_options(
+        candidate_sets,
+        query,
+        relative_to,
+        &options,
+        cancel_flag,
+        executor,
+    )
+    .await
+}
+
+/// Like [`match_path_sets`], but optionally tolerates typos in the query and
+/// multiplies the scores of the matching paths by their boosts.
+pub async fn match_path_sets_with_options<'a, Set: PathMatchCandidateSet<'a>>(
+    candidate_sets: &'a [Set],
+    query: &str,
+    relative_to: Option<Arc<Path>>,
+    options: &PathMatchOptions,
+    cancel_flag: &AtomicBool,
+    executor: BackgroundExecutor,
+) -> Vec<PathMatch> {
+    let max_results = options.max_results;
     let path_count: usize = candidate_sets.iter().map(|s| s.len()).sum();
     if path_count == 0 {
         return Vec::new();
@@ -147,6 +209,9 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
     let lowercase_query = &lowercase_query;
     let query = &query;
     let query_char_bag = CharBag::from(&lowercase_query[..]);
+    let query_variants = QueryVariant::for_typos(query, lowercase_query, options.max_typos);
+    let query_variants = &query_variants;
+    let max_score_boost = options.max_score_boost();
 
     let num_cpus = executor.num_cpus().min(path_count);
     let segment_size = (path_count + num_cpus - 1) / num_cpus;
@@ -165,9 +230,11 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                         query,
                         lowercase_query,
                         query_char_bag,
-                        smart_case,
+                        options.smart_case,
                         max_results,
-                    );
+                    )
+                    .with_query_variants(query_variants)
+                    .with_max_score_boost(max_score_boost);
 
                     let mut tree_start = 0;
                     for candidate_set in candidate_sets {
@@ -191,7 +258,7 @@ pub async fn match_path_sets<'a, Set: PathMatchCandidateSet<'a>>(
                                 results,
                                 cancel_flag,
                                 |candidate, score| PathMatch {
-                                    score,
+                                    score: score * options.score_boost(worktree_id, candidate.path),
                                     worktree_id,
                                     positions: Vec::new(),
                                     path: Arc::from(candidate.path),
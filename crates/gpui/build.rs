@@ -92,6 +92,8 @@ mod macos {
             "Pixels".into(),
             "PointF".into(),
             "Hsla".into(),
+            "BackgroundKind".into(),
+            "Background".into(),
             "ContentMask".into(),
             "Uniforms".into(),
             "AtlasTile".into(),
@@ -0,0 +1,72 @@
+use gpui::*;
+
+struct GradientViewer;
+
+impl Render for GradientViewer {
+    fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .flex()
+            .flex_col()
+            .gap_4()
+            .p_4()
+            .size_full()
+            .bg(rgb(0x1e1e1e))
+            .child(
+                div()
+                    .h_16()
+                    .rounded_lg()
+                    .bg_linear_gradient(90., [color_stop(red(), 0.), color_stop(blue(), 1.)]),
+            )
+            .child(
+                div()
+                    .h_16()
+                    .rounded_lg()
+                    .border_2()
+                    .border_color(white())
+                    .bg(linear_gradient(
+                        135.,
+                        [
+                            color_stop(rgb(0xf9a825), 0.),
+                            color_stop(rgb(0xe53935), 0.5),
+                            color_stop(rgb(0x8e24aa), 1.),
+                        ],
+                    )),
+            )
+            .child(div().h_32().rounded_full().bg_radial_gradient([
+                color_stop(white(), 0.),
+                color_stop(green(), 0.4),
+                color_stop(transparent_black(), 1.),
+            ]))
+            .child(
+                canvas(
+                    |_, _| {},
+                    |bounds, _, cx| {
+                        let mut path = Path::new(bounds.lower_left());
+                        path.line_to(point(bounds.center().x, bounds.origin.y));
+                        path.line_to(bounds.lower_right());
+                        path.line_to(bounds.lower_left());
+                        cx.paint_path(
+                            path,
+                            linear_gradient(0., [color_stop(yellow(), 0.), color_stop(red(), 1.)]),
+                        );
+                    },
+                )
+                .h_32()
+                .w_full(),
+            )
+    }
+}
+
+fn main() {
+    App::new().run(|cx: &mut AppContext| {
+        let bounds = Bounds::centered(None, size(px(400.0), px(480.0)), cx);
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..Default::default()
+            },
+            |cx| cx.new_view(|_cx| GradientViewer),
+        )
+        .unwrap();
+    });
+}
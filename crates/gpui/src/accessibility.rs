@@ -0,0 +1,290 @@
+//! Elements describe themselves to assistive technologies, such as screen readers, by inserting
+//! [`AccessibilityNode`]s while they paint. The nodes inserted during a frame form an
+//! [`AccessibilityTree`], which can be queried with [`WindowContext::accessibility_tree`] and is
+//! exposed to the platform's accessibility APIs where they are supported.
+
+use crate::{Bounds, Pixels, SharedString};
+use smallvec::SmallVec;
+
+/// The kind of user interface element that an accessibility node describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AccessibilityRole {
+    /// A container for other elements.
+    Group,
+    /// An element that performs an action when clicked.
+    Button,
+    /// An element that can be checked or unchecked.
+    Checkbox,
+    /// Text that can't be edited.
+    StaticText,
+    /// An editable, single line of text.
+    TextInput,
+    /// An editable, multi-line text document.
+    TextEditor,
+    /// A list of items.
+    List,
+    /// An item in a list.
+    ListItem,
+    /// A list of tabs.
+    TabList,
+    /// A tab that selects the content shown in a tab panel.
+    Tab,
+    /// A menu of actions.
+    Menu,
+    /// An item in a menu.
+    MenuItem,
+    /// A window on top of the rest of the interface, such as a modal.
+    Dialog,
+    /// An image.
+    Image,
+    /// A link to another location.
+    Link,
+}
+
+/// A description of an element for assistive technologies.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AccessibilityNode {
+    /// The kind of element this node describes.
+    pub role: AccessibilityRole,
+    /// The label of the element, such as the text of a button.
+    pub name: Option<SharedString>,
+    /// The current value of the element, such as the contents of a text input.
+    pub value: Option<SharedString>,
+    /// The bounds of the element, in window coordinates.
+    pub bounds: Bounds<Pixels>,
+    /// Whether the element can receive keyboard focus.
+    pub focusable: bool,
+    /// Whether the element has keyboard focus.
+    pub focused: bool,
+}
+
+impl AccessibilityNode {
+    /// Creates a node with the given role and bounds.
+    pub fn new(role: AccessibilityRole, bounds: Bounds<Pixels>) -> Self {
+        Self {
+            role,
+            name: None,
+            value: None,
+            bounds,
+            focusable: false,
+            focused: false,
+        }
+    }
+
+    /// Sets the name of this node.
+    pub fn name(mut self, name: impl Into<SharedString>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the value of this node.
+    pub fn value(mut self, value: impl Into<SharedString>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets whether this node can receive keyboard focus.
+    pub fn focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    /// Sets whether this node has keyboard focus.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+/// Identifies a node in an [`AccessibilityTree`]. Ids are only meaningful for the
+/// tree they were obtained from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AccessibilityNodeId(pub(crate) usize);
+
+/// The accessibility nodes inserted by the elements painted in a frame.
+#[derive(Clone, Debug, Default)]
+pub struct AccessibilityTree {
+    nodes: Vec<AccessibilityNode>,
+    parents: Vec<Option<AccessibilityNodeId>>,
+    children: Vec<SmallVec<[AccessibilityNodeId; 4]>>,
+    roots: SmallVec<[AccessibilityNodeId; 4]>,
+}
+
+impl AccessibilityTree {
+    /// Builds a tree from nodes listed in paint order, each with its depth in the tree.
+    pub(crate) fn new(nodes: &[(usize, AccessibilityNode)]) -> Self {
+        let mut tree = Self::default();
+        let mut ancestors = Vec::<AccessibilityNodeId>::new();
+        for (depth, node) in nodes {
+            ancestors.truncate(*depth);
+            let id = AccessibilityNodeId(tree.nodes.len());
+            let parent = ancestors.last().copied();
+            match parent {
+                Some(parent) => tree.children[parent.0].push(id),
+                None => tree.roots.push(id),
+            }
+            tree.nodes.push(node.clone());
+            tree.parents.push(parent);
+            tree.children.push(SmallVec::new());
+            ancestors.push(id);
+        }
+        tree
+    }
+
+    /// The number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the tree has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The nodes that have no parent.
+    pub fn roots(&self) -> &[AccessibilityNodeId] {
+        &self.roots
+    }
+
+    /// Returns the node with the given id.
+    pub fn node(&self, id: AccessibilityNodeId) -> &AccessibilityNode {
+        &self.nodes[id.0]
+    }
+
+    /// Returns the parent of the node with the given id.
+    pub fn parent(&self, id: AccessibilityNodeId) -> Option<AccessibilityNodeId> {
+        self.parents[id.0]
+    }
+
+    /// Returns the children of the node with the given id, in paint order.
+    pub fn children(&self, id: AccessibilityNodeId) -> &[AccessibilityNodeId] {
+        &self.children[id.0]
+    }
+
+    /// Iterates over all of the nodes in the tree, parents before their children.
+    pub fn iter(&self) -> impl Iterator<Item = (AccessibilityNodeId, &AccessibilityNode)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(ix, node)| (AccessibilityNodeId(ix), node))
+    }
+
+    /// Returns the first node that matches the given predicate.
+    pub fn find(
+        &self,
+        mut predicate: impl FnMut(&AccessibilityNode) -> bool,
+    ) -> Option<AccessibilityNodeId> {
+        self.iter()
+            .find(|(_, node)| predicate(node))
+            .map(|(id, _)| id)
+    }
+
+    /// Returns the first node with the given role and name.
+    pub fn find_by_name(&self, role: AccessibilityRole, name: &str) -> Option<AccessibilityNodeId> {
+        self.find(|node| node.role == role && node.name.as_deref() == Some(name))
+    }
+
+    /// Returns the node that has keyboard focus, if any.
+    pub fn focused(&self) -> Option<AccessibilityNodeId> {
+        // Focus is tracked by every element of the focused view, so prefer the innermost node.
+        self.iter()
+            .filter(|(_, node)| node.focused)
+            .last()
+            .map(|(id, _)| id)
+    }
+
+    /// The text of the static text nodes below the given node, which describes nodes
+    /// that don't have a name of their own, such as buttons that contain a label.
+    pub fn text_content(&self, id: AccessibilityNodeId) -> String {
+        let mut text = String::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let node = self.node(id);
+            if node.role == AccessibilityRole::StaticText {
+                if let Some(name) = &node.name {
+                    if !text.is_empty() {
+                        text.push(' ');
+                    }
+                    text.push_str(name);
+                }
+            }
+            stack.extend(self.children(id).iter().rev());
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        div, px, InteractiveElement as _, IntoElement, ParentElement as _, Render,
+        StatefulInteractiveElement as _, Styled as _, TestAppContext, ViewContext,
+    };
+
+    #[test]
+    fn test_accessibility_tree() {
+        let node = |role| AccessibilityNode::new(role, Bounds::default());
+        let tree = AccessibilityTree::new(&[
+            (0, node(AccessibilityRole::List)),
+            (1, node(AccessibilityRole::ListItem)),
+            (2, node(AccessibilityRole::StaticText).name("one")),
+            (1, node(AccessibilityRole::ListItem).focused(true)),
+            (2, node(AccessibilityRole::StaticText).name("two")),
+            (0, node(AccessibilityRole::Button).focused(true)),
+        ]);
+
+        let list = AccessibilityNodeId(0);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.roots(), &[list, AccessibilityNodeId(5)]);
+        assert_eq!(
+            tree.children(list),
+            &[AccessibilityNodeId(1), AccessibilityNodeId(3)]
+        );
+        assert_eq!(
+            tree.parent(AccessibilityNodeId(4)),
+            Some(AccessibilityNodeId(3))
+        );
+        assert_eq!(tree.text_content(list), "one two");
+        assert_eq!(
+            tree.find_by_name(AccessibilityRole::StaticText, "two"),
+            Some(AccessibilityNodeId(4))
+        );
+        assert_eq!(tree.focused(), Some(AccessibilityNodeId(5)));
+    }
+
+    #[crate::test]
+    fn test_accessibility_tree_from_elements(cx: &mut TestAppContext) {
+        struct TestView;
+
+        impl Render for TestView {
+            fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+                div()
+                    .size(px(100.))
+                    .child(div().id("button").on_click(|_, _| {}).child("Save"))
+                    .child(div().id("details").focusable().child("Details"))
+            }
+        }
+
+        let (_, cx) = cx.add_window_view(|_| TestView);
+        cx.run_until_parked();
+        let tree = cx.update(|cx| cx.accessibility_tree());
+
+        let text = tree
+            .find_by_name(AccessibilityRole::StaticText, "Save")
+            .unwrap();
+        let button = tree.parent(text).unwrap();
+        assert_eq!(tree.node(button).role, AccessibilityRole::Button);
+        assert_eq!(tree.text_content(button), "Save");
+        assert_eq!(tree.parent(button), None);
+        assert!(!tree.node(button).focusable);
+
+        let text = tree
+            .find_by_name(AccessibilityRole::StaticText, "Details")
+            .unwrap();
+        let details = tree.node(tree.parent(text).unwrap());
+        assert_eq!(details.role, AccessibilityRole::Group);
+        assert!(details.focusable);
+        assert!(!details.focused);
+    }
+}
@@ -1,14 +1,16 @@
 use crate::{
-    Action, AnyView, AnyWindowHandle, AppCell, AppContext, AsyncAppContext, AvailableSpace,
-    BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem, Context, DrawPhase, Drawable,
-    Element, Empty, Entity, EventEmitter, ForegroundExecutor, Global, InputEvent, Keystroke, Model,
-    ModelContext, Modifiers, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, Platform, Point, Render, Result, Size, Task, TestDispatcher,
-    TestPlatform, TestWindow, TextSystem, View, ViewContext, VisualContext, WindowBounds,
-    WindowContext, WindowHandle, WindowOptions,
+    rasterize_scene, snapshot_difference, Action, AnyView, AnyWindowHandle, AppCell, AppContext,
+    AsyncAppContext, AvailableSpace, BackgroundExecutor, BorrowAppContext, Bounds, ClipboardItem,
+    Context, DevicePixels, DrawPhase, Drawable, Element, Empty, Entity, EventEmitter,
+    ForegroundExecutor, Global, InputEvent, Keystroke, Model, ModelContext, Modifiers,
+    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels,
+    Platform, Point, Render, Result, Size, Task, TestDispatcher, TestPlatform, TestWindow,
+    TextSystem, View, ViewContext, VisualContext, WindowBounds, WindowContext, WindowHandle,
+    WindowOptions,
 };
 use anyhow::{anyhow, bail};
 use futures::{channel::oneshot, Stream, StreamExt};
+use image::RgbaImage;
 use std::{cell::RefCell, future::Future, ops::Deref, rc::Rc, sync::Arc, time::Duration};
 
 /// A TestAppContext is provided to tests created with `#[gpui::test]`, it provides
@@ -762,6 +764,65 @@ impl VisualTestContext {
         self.simulate_window_resize(self.window, size)
     }
 
+    /// Renders the last frame drawn in the window into an image. This uses a software
+    /// rasterizer, so it works on machines without a GPU.
+    pub fn snapshot(&mut self) -> RgbaImage {
+        self.run_until_parked();
+        let atlas = self.test_window(self.window).0.lock().sprite_atlas.clone();
+        self.update(|cx| {
+            let scale_factor = cx.scale_factor();
+            let viewport_size = cx.viewport_size();
+            let size = Size {
+                width: DevicePixels((viewport_size.width.0 * scale_factor).ceil() as i32),
+                height: DevicePixels((viewport_size.height.0 * scale_factor).ceil() as i32),
+            };
+            rasterize_scene(&cx.window.rendered_frame.scene, &atlas, size)
+        })
+    }
+
+    /// Compares a snapshot of the window with the PNG file at the given path.
+    ///
+    /// The snapshot matches if the fraction of its pixels that differ noticeably from the
+    /// golden file is at most `tolerance`, between 0 and 1. The golden file is written instead
+    /// when the `UPDATE_SNAPSHOTS` environment variable is set, and a missing golden file fails
+    /// the assertion otherwise. On a mismatch, the snapshot is written next to the golden file
+    /// for inspection.
+    pub fn assert_snapshot(&mut self, golden_path: impl AsRef<std::path::Path>, tolerance: f32) {
+        let golden_path = golden_path.as_ref();
+        let snapshot = self.snapshot();
+
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            if let Some(parent) = golden_path.parent() {
+                std::fs::create_dir_all(parent).unwrap();
+            }
+            snapshot
+                .save(golden_path)
+                .unwrap_or_else(|error| panic!("failed to write {golden_path:?}: {error}"));
+            return;
+        }
+        if !golden_path.exists() {
+            panic!(
+                "golden file {golden_path:?} doesn't exist. \
+                 Run the test with UPDATE_SNAPSHOTS=1 to create it."
+            );
+        }
+
+        let expected = image::open(golden_path)
+            .unwrap_or_else(|error| panic!("failed to read {golden_path:?}: {error}"))
+            .into_rgba8();
+        let difference = snapshot_difference(&expected, &snapshot);
+        if difference > tolerance {
+            let actual_path = golden_path.with_extension("actual.png");
+            snapshot.save(&actual_path).ok();
+            panic!(
+                "snapshot differs from {golden_path:?} for {:.2}% of its pixels, \
+                 which exceeds the tolerance of {:.2}%. The snapshot was written to {actual_path:?}.",
+                difference * 100.,
+                tolerance * 100.,
+            );
+        }
+    }
+
     /// debug_bounds returns the bounds of the element with the given selector.
     pub fn debug_bounds(&mut self, selector: &'static str) -> Option<Bounds<Pixels>> {
         self.update(|cx| cx.window.rendered_frame.debug_bounds.get(selector).copied())
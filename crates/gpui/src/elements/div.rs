@@ -16,12 +16,12 @@
 //! constructed by combining these two systems into an all-in-one element.
 
 use crate::{
-    point, px, size, Action, AnyDrag, AnyElement, AnyTooltip, AnyView, AppContext, Bounds,
-    ClickEvent, DispatchPhase, Element, ElementId, FocusHandle, Global, GlobalElementId, Hitbox,
-    HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent, KeyUpEvent, LayoutId,
-    ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size, Style,
-    StyleRefinement, Styled, Task, TooltipId, View, Visibility, WindowContext,
+    point, px, size, AccessibilityNode, AccessibilityRole, Action, AnyDrag, AnyElement, AnyTooltip,
+    AnyView, AppContext, Bounds, ClickEvent, DispatchPhase, Element, ElementId, FocusHandle,
+    Global, GlobalElementId, Hitbox, HitboxId, IntoElement, IsZero, KeyContext, KeyDownEvent,
+    KeyUpEvent, LayoutId, ModifiersChangedEvent, MouseButton, MouseDownEvent, MouseMoveEvent,
+    MouseUpEvent, ParentElement, Pixels, Point, Render, ScrollWheelEvent, SharedString, Size,
+    Style, StyleRefinement, Styled, Task, TooltipId, View, Visibility, WindowContext,
 };
 use collections::HashMap;
 use refineable::Refineable;
@@ -545,6 +545,26 @@ pub trait InteractiveElement: Sized {
         Focusable { element: self }
     }
 
+    /// Describe this element to assistive technologies with the given role. Clickable
+    /// elements are described as buttons, and focusable ones as groups, by default.
+    fn accessibility_role(mut self, role: AccessibilityRole) -> Self {
+        self.interactivity().accessibility_role = Some(role);
+        self
+    }
+
+    /// Set the name assistive technologies use to describe this element, for elements
+    /// whose label isn't conveyed by the text they contain.
+    fn accessibility_name(mut self, name: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_name = Some(name.into());
+        self
+    }
+
+    /// Set the value assistive technologies report for this element.
+    fn accessibility_value(mut self, value: impl Into<SharedString>) -> Self {
+        self.interactivity().accessibility_value = Some(value.into());
+        self
+    }
+
     /// Set the keymap context for this element. This will be used to determine
     /// which action to dispatch from the keymap.
     fn key_context<C, E>(mut self, key_context: C) -> Self
@@ -1276,6 +1296,9 @@ pub struct Interactivity {
     pub(crate) hover_listener: Option<Box<dyn Fn(&bool, &mut WindowContext)>>,
     pub(crate) tooltip_builder: Option<TooltipBuilder>,
     pub(crate) occlude_mouse: bool,
+    pub(crate) accessibility_role: Option<AccessibilityRole>,
+    pub(crate) accessibility_name: Option<SharedString>,
+    pub(crate) accessibility_value: Option<SharedString>,
 
     #[cfg(debug_assertions)]
     pub(crate) location: Option<core::panic::Location<'static>>,
@@ -1497,6 +1520,8 @@ impl Interactivity {
                     return ((), element_state);
                 }
 
+                let accessibility_node = self.accessibility_node(bounds, cx);
+
                 style.paint(bounds, cx, |cx: &mut WindowContext| {
                     cx.with_text_style(style.text_style().cloned(), |cx| {
                         cx.with_content_mask(style.overflow_mask(bounds, cx.rem_size()), |cx| {
@@ -1519,7 +1544,7 @@ impl Interactivity {
                             }
 
                             self.paint_keyboard_listeners(cx);
-                            f(&style, cx);
+                            cx.with_accessibility_node(accessibility_node, |cx| f(&style, cx));
 
                             if hitbox.is_some() {
                                 if let Some(group) = self.group.as_ref() {
@@ -1535,6 +1560,34 @@ impl Interactivity {
         );
     }
 
+    fn accessibility_node(
+        &self,
+        bounds: Bounds<Pixels>,
+        cx: &WindowContext,
+    ) -> Option<AccessibilityNode> {
+        let role = self.accessibility_role.or_else(|| {
+            if !self.click_listeners.is_empty() {
+                Some(AccessibilityRole::Button)
+            } else if self.focusable {
+                Some(AccessibilityRole::Group)
+            } else {
+                None
+            }
+        })?;
+
+        Some(AccessibilityNode {
+            role,
+            name: self.accessibility_name.clone(),
+            value: self.accessibility_value.clone(),
+            bounds,
+            focusable: self.focusable,
+            focused: self
+                .tracked_focus_handle
+                .as_ref()
+                .map_or(false, |focus_handle| focus_handle.is_focused(cx)),
+        })
+    }
+
     #[cfg(debug_assertions)]
     fn paint_debug_info(
         &mut self,
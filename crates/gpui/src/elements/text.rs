@@ -1,8 +1,8 @@
 use crate::{
-    ActiveTooltip, AnyTooltip, AnyView, Bounds, DispatchPhase, Element, ElementId, GlobalElementId,
-    HighlightStyle, Hitbox, IntoElement, LayoutId, MouseDownEvent, MouseMoveEvent, MouseUpEvent,
-    Pixels, Point, SharedString, Size, TextRun, TextStyle, WhiteSpace, WindowContext, WrappedLine,
-    TOOLTIP_DELAY,
+    AccessibilityNode, AccessibilityRole, ActiveTooltip, AnyTooltip, AnyView, Bounds,
+    DispatchPhase, Element, ElementId, GlobalElementId, HighlightStyle, Hitbox, IntoElement,
+    LayoutId, MouseDownEvent, MouseMoveEvent, MouseUpEvent, Pixels, Point, SharedString, Size,
+    TextRun, TextStyle, WhiteSpace, WindowContext, WrappedLine, TOOLTIP_DELAY,
 };
 use anyhow::anyhow;
 use parking_lot::{Mutex, MutexGuard};
@@ -52,7 +52,7 @@ impl Element for &'static str {
         _: &mut (),
         cx: &mut WindowContext,
     ) {
-        text_layout.paint(self, cx)
+        text_layout.paint(&SharedString::from(*self), cx)
     }
 }
 
@@ -110,7 +110,7 @@ impl Element for SharedString {
         _: &mut Self::PrepaintState,
         cx: &mut WindowContext,
     ) {
-        text_layout.paint(self.as_ref(), cx)
+        text_layout.paint(self, cx)
     }
 }
 
@@ -339,7 +339,7 @@ impl TextLayout {
         element_state.bounds = Some(bounds);
     }
 
-    fn paint(&mut self, text: &str, cx: &mut WindowContext) {
+    fn paint(&mut self, text: &SharedString, cx: &mut WindowContext) {
         let element_state = self.lock();
         let element_state = element_state
             .as_ref()
@@ -350,6 +350,10 @@ impl TextLayout {
             .ok_or_else(|| anyhow!("prepaint has not been performed on {:?}", text))
             .unwrap();
 
+        cx.insert_accessibility_node(
+            AccessibilityNode::new(AccessibilityRole::StaticText, bounds).name(text.clone()),
+        );
+
         let line_height = element_state.line_height;
         let mut line_origin = bounds.origin;
         for line in &element_state.lines {
@@ -5,10 +5,10 @@
 //! elements with uniform height.
 
 use crate::{
-    point, px, size, AnyElement, AvailableSpace, Bounds, ContentMask, Element, ElementId,
-    GlobalElementId, Hitbox, InteractiveElement, Interactivity, IntoElement, LayoutId,
-    ListSizingBehavior, Pixels, Render, ScrollHandle, Size, StyleRefinement, Styled, View,
-    ViewContext, WindowContext,
+    point, px, size, AccessibilityNode, AccessibilityRole, AnyElement, AvailableSpace, Bounds,
+    ContentMask, Element, ElementId, GlobalElementId, Hitbox, InteractiveElement, Interactivity,
+    IntoElement, LayoutId, ListSizingBehavior, Pixels, Render, ScrollHandle, Size, StyleRefinement,
+    Styled, View, ViewContext, WindowContext,
 };
 use smallvec::SmallVec;
 use std::{cell::RefCell, cmp, ops::Range, rc::Rc};
@@ -49,6 +49,7 @@ where
         interactivity: Interactivity {
             element_id: Some(id),
             base_style: Box::new(base_style),
+            accessibility_role: Some(AccessibilityRole::List),
 
             #[cfg(debug_assertions)]
             location: Some(*core::panic::Location::caller()),
@@ -74,7 +75,7 @@ pub struct UniformList {
 /// Frame state used by the [UniformList].
 pub struct UniformListFrameState {
     item_size: Size<Pixels>,
-    items: SmallVec<[AnyElement; 32]>,
+    items: SmallVec<[(AnyElement, Bounds<Pixels>); 32]>,
 }
 
 /// A handle for controlling the scroll position of a uniform list.
@@ -270,7 +271,11 @@ impl Element for UniformList {
                             );
                             item.layout_as_root(available_space, cx);
                             item.prepaint_at(item_origin, cx);
-                            frame_state.items.push(item);
+                            let item_bounds = Bounds {
+                                origin: item_origin,
+                                size: size(padded_bounds.size.width, item_height),
+                            };
+                            frame_state.items.push((item, item_bounds));
                         }
                     });
                 }
@@ -290,8 +295,14 @@ impl Element for UniformList {
     ) {
         self.interactivity
             .paint(global_id, bounds, hitbox.as_ref(), cx, |_, cx| {
-                for item in &mut request_layout.items {
-                    item.paint(cx);
+                for (item, item_bounds) in &mut request_layout.items {
+                    cx.with_accessibility_node(
+                        Some(AccessibilityNode::new(
+                            AccessibilityRole::ListItem,
+                            *item_bounds,
+                        )),
+                        |cx| item.paint(cx),
+                    );
                 }
             })
     }
@@ -3,9 +3,9 @@
 
 use super::{BladeAtlas, PATH_TEXTURE_FORMAT};
 use crate::{
-    AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels, Hsla, MonochromeSprite, Path,
-    PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow, Size,
-    Underline,
+    AtlasTextureKind, AtlasTile, Background, Bounds, ContentMask, DevicePixels, MonochromeSprite,
+    Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad, ScaledPixels, Scene, Shadow,
+    Size, Underline,
 };
 use bytemuck::{Pod, Zeroable};
 use collections::HashMap;
@@ -173,7 +173,8 @@ struct ShaderSurfacesData {
 #[repr(C)]
 struct PathSprite {
     bounds: Bounds<ScaledPixels>,
-    color: Hsla,
+    path_bounds: Bounds<ScaledPixels>,
+    background: Background,
     tile: AtlasTile,
 }
 
@@ -596,7 +597,8 @@ impl BladeRenderer {
                                     origin: origin.map(|p| p.floor()),
                                     size: tile.bounds.size.map(Into::into),
                                 },
-                                color: path.color,
+                                path_bounds: path.bounds,
+                                background: path.background,
                                 tile: (*tile).clone(),
                             }];
 
@@ -34,6 +34,17 @@ struct Hsla {
     a: f32,
 }
 
+struct Background {
+    // 0u: solid, 1u: linear gradient, 2u: radial gradient
+    kind: u32,
+    stop_count: u32,
+    angle: f32,
+    radius: f32,
+    center: vec2<f32>,
+    colors: array<Hsla, 4>,
+    stops: array<f32, 4>,
+}
+
 struct AtlasTextureId {
     index: u32,
     kind: u32,
@@ -135,6 +146,37 @@ fn hsla_to_rgba(hsla: Hsla) -> vec4<f32> {
     return vec4<f32>(linear, a);
 }
 
+// Returns the color of a gradient background at the given position within the filled bounds.
+fn gradient_color(background: Background, bounds: Bounds, position: vec2<f32>) -> vec4<f32> {
+    var t = 0.0;
+    if (background.kind == 1u) {
+        // As in CSS, an angle of 0 points up and angles grow clockwise, and the
+        // gradient line is long enough for the corners to get the end colors.
+        let direction = vec2<f32>(sin(background.angle), -cos(background.angle));
+        let line_length = abs(bounds.size.x * direction.x) + abs(bounds.size.y * direction.y);
+        let center = bounds.origin + bounds.size / 2.0;
+        t = dot(position - center, direction) / max(line_length, 0.0001) + 0.5;
+    } else {
+        let center = bounds.origin + bounds.size * background.center;
+        let to_near_corner = abs(center - bounds.origin);
+        let to_far_corner = abs(bounds.origin + bounds.size - center);
+        let farthest_corner = length(max(to_near_corner, to_far_corner));
+        t = length(position - center) / max(farthest_corner * background.radius, 0.0001);
+    }
+
+    // Copy the stops into local variables so that they can be indexed dynamically.
+    var colors = background.colors;
+    var stops = background.stops;
+    var color = hsla_to_rgba(colors[0]);
+    for (var i = 1u; i < background.stop_count; i += 1u) {
+        let start = stops[i - 1u];
+        let end = stops[i];
+        let amount = saturate((t - start) / max(end - start, 0.0001));
+        color = mix(color, hsla_to_rgba(colors[i]), amount);
+    }
+    return color;
+}
+
 fn over(below: vec4<f32>, above: vec4<f32>) -> vec4<f32> {
     let alpha = above.a + below.a * (1.0 - above.a);
     let color = (above.rgb * above.a + below.rgb * below.a * (1.0 - above.a)) / alpha;
@@ -204,7 +246,7 @@ struct Quad {
     pad: u32,
     bounds: Bounds,
     content_mask: Bounds,
-    background: Hsla,
+    background: Background,
     border_color: Hsla,
     corner_radii: Corners,
     border_widths: Edges,
@@ -227,7 +269,7 @@ fn vs_quad(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) insta
 
     var out = QuadVarying();
     out.position = to_device_position(unit_vertex, quad.bounds);
-    out.background_color = hsla_to_rgba(quad.background);
+    out.background_color = hsla_to_rgba(quad.background.colors[0]);
     out.border_color = hsla_to_rgba(quad.border_color);
     out.quad_id = instance_id;
     out.clip_distances = distance_from_clip_rect(unit_vertex, quad.bounds, quad.content_mask);
@@ -242,13 +284,18 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
     }
 
     let quad = b_quads[input.quad_id];
+    var background_color = input.background_color;
+    if (quad.background.kind != 0u) {
+        background_color = gradient_color(quad.background, quad.bounds, input.position.xy);
+    }
+
     // Fast path when the quad is not rounded and doesn't have any border.
     if (quad.corner_radii.top_left == 0.0 && quad.corner_radii.bottom_left == 0.0 &&
         quad.corner_radii.top_right == 0.0 &&
         quad.corner_radii.bottom_right == 0.0 && quad.border_widths.top == 0.0 &&
         quad.border_widths.left == 0.0 && quad.border_widths.right == 0.0 &&
         quad.border_widths.bottom == 0.0) {
-        return blend_color(input.background_color, 1.0);
+        return blend_color(background_color, 1.0);
     }
 
     let half_size = quad.bounds.size / 2.0;
@@ -277,13 +324,13 @@ fn fs_quad(input: QuadVarying) -> @location(0) vec4<f32> {
         border_width = vertical_border;
     }
 
-    var color = input.background_color;
+    var color = background_color;
     if (border_width > 0.0) {
         let inset_distance = distance + border_width;
         // Blend the border on top of the background and then linearly interpolate
         // between the two as we slide inside the background.
-        let blended_border = over(input.background_color, input.border_color);
-        color = mix(blended_border, input.background_color,
+        let blended_border = over(background_color, input.border_color);
+        color = mix(blended_border, background_color,
                     saturate(0.5 - inset_distance));
     }
 
@@ -408,7 +455,8 @@ fn fs_path_rasterization(input: PathRasterizationVarying) -> @location(0) f32 {
 
 struct PathSprite {
     bounds: Bounds,
-    color: Hsla,
+    path_bounds: Bounds,
+    background: Background,
     tile: AtlasTile,
 }
 var<storage, read> b_path_sprites: array<PathSprite>;
@@ -416,7 +464,8 @@ var<storage, read> b_path_sprites: array<PathSprite>;
 struct PathVarying {
     @builtin(position) position: vec4<f32>,
     @location(0) tile_position: vec2<f32>,
-    @location(1) color: vec4<f32>,
+    @location(1) @interpolate(flat) color: vec4<f32>,
+    @location(2) @interpolate(flat) sprite_id: u32,
 }
 
 @vertex
@@ -428,7 +477,8 @@ fn vs_path(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) insta
     var out = PathVarying();
     out.position = to_device_position(unit_vertex, sprite.bounds);
     out.tile_position = to_tile_position(unit_vertex, sprite.tile);
-    out.color = hsla_to_rgba(sprite.color);
+    out.color = hsla_to_rgba(sprite.background.colors[0]);
+    out.sprite_id = instance_id;
     return out;
 }
 
@@ -436,7 +486,12 @@ fn vs_path(@builtin(vertex_index) vertex_id: u32, @builtin(instance_index) insta
 fn fs_path(input: PathVarying) -> @location(0) vec4<f32> {
     let sample = textureSample(t_sprite, s_sprite, input.tile_position).r;
     let mask = 1.0 - abs(1.0 - sample % 2.0);
-    return blend_color(input.color, mask);
+    let sprite = b_path_sprites[input.sprite_id];
+    var color = input.color;
+    if (sprite.background.kind != 0u) {
+        color = gradient_color(sprite.background, sprite.path_bounds, input.position.xy);
+    }
+    return blend_color(color, mask);
 }
 
 // --- underlines --- //
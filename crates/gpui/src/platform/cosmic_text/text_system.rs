@@ -426,8 +426,15 @@ impl CosmicTextSystemState {
                 is_emoji,
             });
 
-            runs.push(crate::ShapedRun { font_id, glyphs });
+            runs.push(crate::ShapedRun {
+                font_id,
+                glyphs,
+                is_rtl: glyph.level.is_rtl(),
+            });
         }
+        // Glyphs in right-to-left text are laid out from right to left, but runs are expected
+        // in visual order.
+        runs.sort_by_key(|run| run.glyphs[0].position.x);
 
         LineLayout {
             font_size,
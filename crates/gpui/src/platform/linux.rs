@@ -1,3 +1,4 @@
+mod atspi;
 mod dispatcher;
 mod headless;
 mod platform;
@@ -5,6 +6,7 @@ mod wayland;
 mod x11;
 mod xdg_desktop_portal;
 
+pub(crate) use atspi::*;
 pub(crate) use dispatcher::*;
 pub(crate) use headless::*;
 pub(crate) use platform::*;
@@ -0,0 +1,627 @@
+//! Exposes the accessibility trees of gpui windows to assistive technologies through [AT-SPI]
+//!
+//! This module uses the [zbus] crate to serve the AT-SPI interfaces on the accessibility bus.
+//! The accessible objects are addressed as follows:
+//!
+//! - `/org/a11y/atspi/accessible/root` is the application, whose children are its windows.
+//! - `/org/a11y/atspi/accessible/<window>` is a window, whose children are the roots of its tree.
+//! - `/org/a11y/atspi/accessible/<window>/<node>` is a node in the tree of a window.
+//!
+//! Node paths are only valid until the next frame is drawn in their window.
+//!
+//! [AT-SPI]: https://gitlab.gnome.org/GNOME/at-spi2-core
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering::SeqCst};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context as _};
+use parking_lot::Mutex;
+use smol::stream::StreamExt;
+use util::{ResultExt, TryFutureExt};
+use zbus::message::Type as MessageType;
+use zbus::zvariant::{ObjectPath, OwnedObjectPath, Value};
+use zbus::{Connection, Message, MessageStream, Proxy};
+
+use crate::{
+    AccessibilityNodeId, AccessibilityRole, AccessibilityTree, BackgroundExecutor, Bounds, Pixels,
+    Point,
+};
+
+const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+const PATH_PREFIX: &str = "/org/a11y/atspi/accessible/";
+const NULL_PATH: &str = "/org/a11y/atspi/null";
+
+// Values of the `AtspiRole` enumeration.
+const ROLE_CHECK_BOX: u32 = 7;
+const ROLE_DIALOG: u32 = 16;
+const ROLE_FRAME: u32 = 23;
+const ROLE_IMAGE: u32 = 27;
+const ROLE_LABEL: u32 = 29;
+const ROLE_LIST: u32 = 31;
+const ROLE_LIST_ITEM: u32 = 32;
+const ROLE_MENU: u32 = 33;
+const ROLE_MENU_ITEM: u32 = 35;
+const ROLE_PAGE_TAB: u32 = 37;
+const ROLE_PAGE_TAB_LIST: u32 = 38;
+const ROLE_PANEL: u32 = 39;
+const ROLE_PUSH_BUTTON: u32 = 43;
+const ROLE_TEXT: u32 = 61;
+const ROLE_APPLICATION: u32 = 75;
+const ROLE_ENTRY: u32 = 79;
+const ROLE_LINK: u32 = 88;
+
+// Values of the `AtspiStateType` enumeration.
+const STATE_ACTIVE: u32 = 1;
+const STATE_EDITABLE: u32 = 7;
+const STATE_ENABLED: u32 = 8;
+const STATE_FOCUSABLE: u32 = 11;
+const STATE_FOCUSED: u32 = 12;
+const STATE_MULTI_LINE: u32 = 17;
+const STATE_SENSITIVE: u32 = 24;
+const STATE_SHOWING: u32 = 25;
+const STATE_SINGLE_LINE: u32 = 26;
+const STATE_VISIBLE: u32 = 30;
+
+// Values of the `AtspiCoordType` enumeration.
+const COORD_TYPE_SCREEN: u32 = 0;
+
+/// A reference to an accessible object, as the bus name of the application that owns it and
+/// its object path.
+type ObjectReference = (String, OwnedObjectPath);
+
+/// Serves the accessibility trees of the application's windows on the AT-SPI bus.
+#[derive(Clone)]
+pub(crate) struct AtspiBridge {
+    executor: BackgroundExecutor,
+    enabled: Arc<AtomicBool>,
+    state: Arc<Mutex<AtspiState>>,
+}
+
+#[derive(Default)]
+struct AtspiState {
+    connection: Option<Connection>,
+    windows: BTreeMap<u64, AccessibleWindow>,
+}
+
+#[derive(Default)]
+struct AccessibleWindow {
+    title: String,
+    origin: Point<Pixels>,
+    tree: AccessibilityTree,
+    focused: Option<AccessibilityNodeId>,
+}
+
+#[derive(Clone, Copy)]
+enum AccessibleObject {
+    Application,
+    Window(u64),
+    Node(u64, AccessibilityNodeId),
+}
+
+impl AtspiBridge {
+    pub fn new(executor: &BackgroundExecutor) -> Self {
+        let this = Self {
+            executor: executor.clone(),
+            enabled: Arc::default(),
+            state: Arc::default(),
+        };
+
+        let bridge = this.clone();
+        executor
+            .spawn(async move { bridge.connect().await.log_err() })
+            .detach();
+
+        this
+    }
+
+    /// Whether an assistive technology is listening, so that windows should publish their trees.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(SeqCst) && self.state.lock().connection.is_some()
+    }
+
+    pub fn set_window_title(&self, window_id: u64, title: &str) {
+        let mut state = self.state.lock();
+        state.windows.entry(window_id).or_default().title = title.to_string();
+    }
+
+    pub fn update_window(&self, window_id: u64, origin: Point<Pixels>, tree: AccessibilityTree) {
+        let mut state = self.state.lock();
+        let connection = state.connection.clone();
+        let window = state.windows.entry(window_id).or_default();
+        let focused = tree.focused();
+        let focus_changed = focused != window.focused;
+        window.origin = origin;
+        window.tree = tree;
+        window.focused = focused;
+
+        if let Some((connection, focused)) = connection.zip(focused).filter(|_| focus_changed) {
+            let path = AccessibleObject::Node(window_id, focused).path();
+            self.executor
+                .spawn(
+                    async move {
+                        let signal = Message::signal(
+                            path.as_str(),
+                            "org.a11y.atspi.Event.Object",
+                            "StateChanged",
+                        )?
+                        .build(&(
+                            "focused",
+                            1i32,
+                            0i32,
+                            Value::from(0i32),
+                            HashMap::<&str, Value>::new(),
+                        ))?;
+                        connection.send(&signal).await?;
+                        anyhow::Ok(())
+                    }
+                    .log_err(),
+                )
+                .detach();
+        }
+    }
+
+    pub fn remove_window(&self, window_id: u64) {
+        self.state.lock().windows.remove(&window_id);
+    }
+
+    /// Connects to the accessibility bus and serves the application's accessible objects.
+    async fn connect(&self) -> anyhow::Result<()> {
+        let session = Connection::session().await?;
+        self.watch_enabled(&session).await?;
+
+        let address: String = session
+            .call_method(
+                Some("org.a11y.Bus"),
+                "/org/a11y/bus",
+                Some("org.a11y.Bus"),
+                "GetAddress",
+                &(),
+            )
+            .await?
+            .body()
+            .deserialize()?;
+        let connection = zbus::connection::Builder::address(address.as_str())?
+            .build()
+            .await?;
+        let bus_name = connection
+            .unique_name()
+            .context("accessibility bus connection has no unique name")?
+            .to_string();
+
+        // Subscribe before embedding, as the registry queries the application as soon
+        // as it's embedded.
+        let messages = MessageStream::from(&connection);
+        connection
+            .call_method(
+                Some("org.a11y.atspi.Registry"),
+                ROOT_PATH,
+                Some("org.a11y.atspi.Socket"),
+                "Embed",
+                &((bus_name.as_str(), ObjectPath::try_from(ROOT_PATH)?),),
+            )
+            .await?;
+        self.state.lock().connection = Some(connection.clone());
+
+        self.serve(connection, bus_name, messages).await
+    }
+
+    /// Tracks whether assistive technologies are enabled in the session, as building the
+    /// trees has a cost that's only worth paying when something reads them.
+    async fn watch_enabled(&self, session: &Connection) -> anyhow::Result<()> {
+        let status =
+            Proxy::new(session, "org.a11y.Bus", "/org/a11y/bus", "org.a11y.Status").await?;
+        self.enabled
+            .store(status.get_property::<bool>("IsEnabled").await?, SeqCst);
+
+        let mut changes = status.receive_property_changed::<bool>("IsEnabled").await;
+        let enabled = self.enabled.clone();
+        self.executor
+            .spawn(
+                async move {
+                    while let Some(change) = changes.next().await {
+                        enabled.store(change.get().await?, SeqCst);
+                    }
+                    anyhow::Ok(())
+                }
+                .log_err(),
+            )
+            .detach();
+        Ok(())
+    }
+
+    async fn serve(
+        &self,
+        connection: Connection,
+        bus_name: String,
+        mut messages: MessageStream,
+    ) -> anyhow::Result<()> {
+        while let Some(message) = messages.next().await {
+            let message = message?;
+            if message.message_type() != MessageType::MethodCall {
+                continue;
+            }
+
+            let reply = self.state.lock().handle_method_call(&message, &bus_name);
+            let reply = match reply {
+                Ok(reply) => reply,
+                Err(error) => Message::method_error(&message, "org.freedesktop.DBus.Error.Failed")?
+                    .build(&(error.to_string(),))?,
+            };
+            connection.send(&reply).await.log_err();
+        }
+        Ok(())
+    }
+}
+
+impl AtspiState {
+    fn handle_method_call(&self, call: &Message, bus_name: &str) -> anyhow::Result<Message> {
+        let header = call.header();
+        let path = header.path().context("method call has no path")?;
+        let interface = header.interface().context("method call has no interface")?;
+        let member = header.member().context("method call has no member")?;
+        let object = AccessibleObject::from_path(path.as_str())
+            .filter(|object| self.contains(*object))
+            .ok_or_else(|| anyhow!("no accessible object at {}", path.as_str()))?;
+        let reply = Message::method_reply(call)?;
+        let reference = |object: AccessibleObject| (bus_name.to_string(), object.path());
+
+        let reply = match (interface.as_str(), member.as_str()) {
+            ("org.freedesktop.DBus.Properties", "Get") => {
+                let (interface, property): (String, String) = call.body().deserialize()?;
+                let value = self
+                    .properties(object, &interface, bus_name)
+                    .remove(property.as_str())
+                    .ok_or_else(|| anyhow!("unknown property {interface}.{property}"))?;
+                reply.build(&value)?
+            }
+            ("org.freedesktop.DBus.Properties", "GetAll") => {
+                let interface: String = call.body().deserialize()?;
+                reply.build(&self.properties(object, &interface, bus_name))?
+            }
+            // The only writable property is the application's id, which isn't needed.
+            ("org.freedesktop.DBus.Properties", "Set") => reply.build(&())?,
+            ("org.a11y.atspi.Accessible", "GetChildAtIndex") => {
+                let index: i32 = call.body().deserialize()?;
+                let child = usize::try_from(index)
+                    .ok()
+                    .and_then(|index| self.children(object).get(index).copied());
+                let child = match child {
+                    Some(child) => reference(child),
+                    None => (bus_name.to_string(), OwnedObjectPath::try_from(NULL_PATH)?),
+                };
+                reply.build(&(child,))?
+            }
+            ("org.a11y.atspi.Accessible", "GetChildren") => {
+                let children = self
+                    .children(object)
+                    .into_iter()
+                    .map(reference)
+                    .collect::<Vec<_>>();
+                reply.build(&(children,))?
+            }
+            ("org.a11y.atspi.Accessible", "GetIndexInParent") => {
+                let index = self.parent(object).and_then(|parent| {
+                    self.children(parent)
+                        .iter()
+                        .position(|child| child.path() == object.path())
+                });
+                reply.build(&index.map_or(-1, |index| index as i32))?
+            }
+            ("org.a11y.atspi.Accessible", "GetRelationSet") => {
+                reply.build(&(Vec::<(u32, Vec<ObjectReference>)>::new(),))?
+            }
+            ("org.a11y.atspi.Accessible", "GetRole") => reply.build(&self.role(object))?,
+            ("org.a11y.atspi.Accessible", "GetRoleName")
+            | ("org.a11y.atspi.Accessible", "GetLocalizedRoleName") => {
+                reply.build(&role_name(self.role(object)))?
+            }
+            ("org.a11y.atspi.Accessible", "GetState") => {
+                let states = self.states(object);
+                reply.build(&(vec![states as u32, (states >> 32) as u32],))?
+            }
+            ("org.a11y.atspi.Accessible", "GetAttributes") => {
+                let mut attributes = HashMap::new();
+                attributes.insert("toolkit", "gpui");
+                reply.build(&(attributes,))?
+            }
+            ("org.a11y.atspi.Accessible", "GetApplication") => {
+                reply.build(&(reference(AccessibleObject::Application),))?
+            }
+            ("org.a11y.atspi.Accessible", "GetInterfaces") => {
+                reply.build(&(self.interfaces(object),))?
+            }
+            ("org.a11y.atspi.Component", "GetExtents") => {
+                let coord_type: u32 = call.body().deserialize()?;
+                let bounds = self.bounds(object, coord_type == COORD_TYPE_SCREEN);
+                reply.build(&((
+                    bounds.origin.x.0 as i32,
+                    bounds.origin.y.0 as i32,
+                    bounds.size.width.0 as i32,
+                    bounds.size.height.0 as i32,
+                ),))?
+            }
+            ("org.a11y.atspi.Text", "GetText") => {
+                let (start, end): (i32, i32) = call.body().deserialize()?;
+                let value = self.value(object);
+                let chars = value.chars().count();
+                let start = usize::try_from(start).unwrap_or(0).min(chars);
+                // An end offset of -1 refers to the end of the text.
+                let end = usize::try_from(end).unwrap_or(chars).clamp(start, chars);
+                let text = value
+                    .chars()
+                    .skip(start)
+                    .take(end - start)
+                    .collect::<String>();
+                reply.build(&text)?
+            }
+            _ => return Err(anyhow!("unknown method {}.{}", interface, member)),
+        };
+        Ok(reply)
+    }
+
+    fn contains(&self, object: AccessibleObject) -> bool {
+        match object {
+            AccessibleObject::Application => true,
+            AccessibleObject::Window(window_id) => self.windows.contains_key(&window_id),
+            AccessibleObject::Node(window_id, node_id) => self
+                .windows
+                .get(&window_id)
+                .map_or(false, |window| node_id.0 < window.tree.len()),
+        }
+    }
+
+    fn parent(&self, object: AccessibleObject) -> Option<AccessibleObject> {
+        match object {
+            AccessibleObject::Application => None,
+            AccessibleObject::Window(_) => Some(AccessibleObject::Application),
+            AccessibleObject::Node(window_id, node_id) => {
+                match self.windows[&window_id].tree.parent(node_id) {
+                    Some(parent_id) => Some(AccessibleObject::Node(window_id, parent_id)),
+                    None => Some(AccessibleObject::Window(window_id)),
+                }
+            }
+        }
+    }
+
+    fn children(&self, object: AccessibleObject) -> Vec<AccessibleObject> {
+        match object {
+            AccessibleObject::Application => self
+                .windows
+                .keys()
+                .map(|window_id| AccessibleObject::Window(*window_id))
+                .collect(),
+            AccessibleObject::Window(window_id) => self.windows[&window_id]
+                .tree
+                .roots()
+                .iter()
+                .map(|node_id| AccessibleObject::Node(window_id, *node_id))
+                .collect(),
+            AccessibleObject::Node(window_id, node_id) => self.windows[&window_id]
+                .tree
+                .children(node_id)
+                .iter()
+                .map(|child_id| AccessibleObject::Node(window_id, *child_id))
+                .collect(),
+        }
+    }
+
+    fn name(&self, object: AccessibleObject) -> String {
+        match object {
+            AccessibleObject::Application => std::env::current_exe()
+                .ok()
+                .and_then(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+                .unwrap_or_default(),
+            AccessibleObject::Window(window_id) => self.windows[&window_id].title.clone(),
+            AccessibleObject::Node(window_id, node_id) => {
+                let tree = &self.windows[&window_id].tree;
+                match &tree.node(node_id).name {
+                    Some(name) => name.to_string(),
+                    None => tree.text_content(node_id),
+                }
+            }
+        }
+    }
+
+    fn value(&self, object: AccessibleObject) -> String {
+        match object {
+            AccessibleObject::Node(window_id, node_id) => {
+                let node = self.windows[&window_id].tree.node(node_id);
+                node.value
+                    .as_ref()
+                    .or(node.name.as_ref())
+                    .map(ToString::to_string)
+                    .unwrap_or_default()
+            }
+            _ => String::new(),
+        }
+    }
+
+    fn role(&self, object: AccessibleObject) -> u32 {
+        match object {
+            AccessibleObject::Application => ROLE_APPLICATION,
+            AccessibleObject::Window(_) => ROLE_FRAME,
+            AccessibleObject::Node(window_id, node_id) => {
+                match self.windows[&window_id].tree.node(node_id).role {
+                    AccessibilityRole::Group => ROLE_PANEL,
+                    AccessibilityRole::Button => ROLE_PUSH_BUTTON,
+                    AccessibilityRole::Checkbox => ROLE_CHECK_BOX,
+                    AccessibilityRole::StaticText => ROLE_LABEL,
+                    AccessibilityRole::TextInput => ROLE_ENTRY,
+                    AccessibilityRole::TextEditor => ROLE_TEXT,
+                    AccessibilityRole::List => ROLE_LIST,
+                    AccessibilityRole::ListItem => ROLE_LIST_ITEM,
+                    AccessibilityRole::TabList => ROLE_PAGE_TAB_LIST,
+                    AccessibilityRole::Tab => ROLE_PAGE_TAB,
+                    AccessibilityRole::Menu => ROLE_MENU,
+                    AccessibilityRole::MenuItem => ROLE_MENU_ITEM,
+                    AccessibilityRole::Dialog => ROLE_DIALOG,
+                    AccessibilityRole::Image => ROLE_IMAGE,
+                    AccessibilityRole::Link => ROLE_LINK,
+                }
+            }
+        }
+    }
+
+    fn states(&self, object: AccessibleObject) -> u64 {
+        let mut states = [STATE_ENABLED, STATE_SENSITIVE, STATE_SHOWING, STATE_VISIBLE]
+            .into_iter()
+            .fold(0u64, |states, state| states | (1 << state));
+        match object {
+            AccessibleObject::Application => {}
+            AccessibleObject::Window(_) => states |= 1 << STATE_ACTIVE,
+            AccessibleObject::Node(window_id, node_id) => {
+                let window = &self.windows[&window_id];
+                let node = window.tree.node(node_id);
+                match node.role {
+                    AccessibilityRole::TextInput => {
+                        states |= (1 << STATE_EDITABLE) | (1 << STATE_SINGLE_LINE)
+                    }
+                    AccessibilityRole::TextEditor => {
+                        states |= (1 << STATE_EDITABLE) | (1 << STATE_MULTI_LINE)
+                    }
+                    _ => {}
+                }
+                if node.focusable || node.focused {
+                    states |= 1 << STATE_FOCUSABLE;
+                }
+                if window.focused == Some(node_id) {
+                    states |= 1 << STATE_FOCUSED;
+                }
+            }
+        }
+        states
+    }
+
+    fn bounds(&self, object: AccessibleObject, screen: bool) -> Bounds<Pixels> {
+        match object {
+            AccessibleObject::Application => Bounds::default(),
+            AccessibleObject::Window(window_id) => {
+                let window = &self.windows[&window_id];
+                let mut bounds = window
+                    .tree
+                    .roots()
+                    .iter()
+                    .map(|root| window.tree.node(*root).bounds)
+                    .fold(Bounds::default(), |bounds, root| bounds.union(&root));
+                if screen {
+                    bounds.origin += window.origin;
+                }
+                bounds
+            }
+            AccessibleObject::Node(window_id, node_id) => {
+                let window = &self.windows[&window_id];
+                let mut bounds = window.tree.node(node_id).bounds;
+                if screen {
+                    bounds.origin += window.origin;
+                }
+                bounds
+            }
+        }
+    }
+
+    fn interfaces(&self, object: AccessibleObject) -> Vec<&'static str> {
+        let mut interfaces = vec!["org.a11y.atspi.Accessible"];
+        match object {
+            AccessibleObject::Application => interfaces.push("org.a11y.atspi.Application"),
+            AccessibleObject::Window(_) => interfaces.push("org.a11y.atspi.Component"),
+            AccessibleObject::Node(_, _) => {
+                interfaces.push("org.a11y.atspi.Component");
+                interfaces.push("org.a11y.atspi.Text");
+            }
+        }
+        interfaces
+    }
+
+    fn properties(
+        &self,
+        object: AccessibleObject,
+        interface: &str,
+        bus_name: &str,
+    ) -> HashMap<&'static str, Value<'static>> {
+        let mut properties = HashMap::new();
+        match interface {
+            "org.a11y.atspi.Accessible" => {
+                let parent = self.parent(object).map_or_else(
+                    // The registry is the parent of the application.
+                    || ("org.a11y.atspi.Registry".to_string(), root_path()),
+                    |parent| (bus_name.to_string(), parent.path()),
+                );
+                properties.insert("Name", Value::from(self.name(object)));
+                properties.insert("Description", Value::from(String::new()));
+                properties.insert("Parent", Value::from(parent));
+                properties.insert(
+                    "ChildCount",
+                    Value::from(self.children(object).len() as i32),
+                );
+                properties.insert("Locale", Value::from(String::new()));
+                properties.insert("AccessibleId", Value::from(String::new()));
+            }
+            "org.a11y.atspi.Application" => {
+                properties.insert("ToolkitName", Value::from("gpui"));
+                properties.insert("Version", Value::from(env!("CARGO_PKG_VERSION")));
+                properties.insert("AtspiVersion", Value::from("2.1"));
+                properties.insert("Id", Value::from(0i32));
+            }
+            "org.a11y.atspi.Text" => {
+                let character_count = self.value(object).chars().count() as i32;
+                properties.insert("CharacterCount", Value::from(character_count));
+                properties.insert("CaretOffset", Value::from(0i32));
+            }
+            _ => {}
+        }
+        properties
+    }
+}
+
+impl AccessibleObject {
+    fn from_path(path: &str) -> Option<Self> {
+        if path == ROOT_PATH {
+            return Some(Self::Application);
+        }
+
+        let mut components = path.strip_prefix(PATH_PREFIX)?.split('/');
+        let window_id = components.next()?.parse().ok()?;
+        let object = match components.next() {
+            Some(node_id) => Self::Node(window_id, AccessibilityNodeId(node_id.parse().ok()?)),
+            None => Self::Window(window_id),
+        };
+        components.next().is_none().then_some(object)
+    }
+
+    fn path(&self) -> OwnedObjectPath {
+        let path = match self {
+            Self::Application => ROOT_PATH.to_string(),
+            Self::Window(window_id) => format!("{PATH_PREFIX}{window_id}"),
+            Self::Node(window_id, node_id) => format!("{PATH_PREFIX}{window_id}/{}", node_id.0),
+        };
+        OwnedObjectPath::try_from(path).expect("accessible object paths are valid")
+    }
+}
+
+fn root_path() -> OwnedObjectPath {
+    OwnedObjectPath::try_from(ROOT_PATH).expect("root path is valid")
+}
+
+fn role_name(role: u32) -> &'static str {
+    match role {
+        ROLE_CHECK_BOX => "check box",
+        ROLE_DIALOG => "dialog",
+        ROLE_FRAME => "frame",
+        ROLE_IMAGE => "image",
+        ROLE_LABEL => "label",
+        ROLE_LIST => "list",
+        ROLE_LIST_ITEM => "list item",
+        ROLE_MENU => "menu",
+        ROLE_MENU_ITEM => "menu item",
+        ROLE_PAGE_TAB => "page tab",
+        ROLE_PAGE_TAB_LIST => "page tab list",
+        ROLE_PANEL => "panel",
+        ROLE_PUSH_BUTTON => "push button",
+        ROLE_TEXT => "text",
+        ROLE_APPLICATION => "application",
+        ROLE_ENTRY => "entry",
+        ROLE_LINK => "link",
+        _ => "unknown",
+    }
+}
@@ -25,10 +25,10 @@ use crate::platform::linux::wayland::serial::SerialKind;
 use crate::platform::{PlatformAtlas, PlatformInputHandler, PlatformWindow};
 use crate::scene::Scene;
 use crate::{
-    px, size, AnyWindowHandle, Bounds, Decorations, Globals, Modifiers, Output, Pixels,
-    PlatformDisplay, PlatformInput, Point, PromptLevel, ResizeEdge, Size, Tiling,
-    WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
-    WindowControls, WindowDecorations, WindowParams,
+    px, size, AccessibilityTree, AnyWindowHandle, AtspiBridge, Bounds, Decorations, Globals,
+    Modifiers, Output, Pixels, PlatformDisplay, PlatformInput, Point, PromptLevel, ResizeEdge,
+    Size, Tiling, WaylandClientStatePtr, WindowAppearance, WindowBackgroundAppearance,
+    WindowBounds, WindowControls, WindowDecorations, WindowParams,
 };
 
 #[derive(Default)]
@@ -109,6 +109,7 @@ pub struct WaylandWindowState {
 pub struct WaylandWindowStatePtr {
     state: Rc<RefCell<WaylandWindowState>>,
     callbacks: Rc<RefCell<Callbacks>>,
+    accessibility: AtspiBridge,
 }
 
 impl WaylandWindowState {
@@ -215,6 +216,9 @@ impl Drop for WaylandWindow {
         let mut state = self.0.state.borrow_mut();
         let surface_id = state.surface.id();
         let client = state.client.clone();
+        self.0
+            .accessibility
+            .remove_window(state.handle.window_id().as_u64());
 
         state.renderer.destroy();
         if let Some(decoration) = &state.decoration {
@@ -258,6 +262,7 @@ impl WaylandWindow {
         client: WaylandClientStatePtr,
         params: WindowParams,
         appearance: WindowAppearance,
+        accessibility: AtspiBridge,
     ) -> anyhow::Result<(Self, ObjectId)> {
         let surface = globals.compositor.create_surface(&globals.qh, ());
         let xdg_surface = globals
@@ -297,6 +302,7 @@ impl WaylandWindow {
                 params,
             )?)),
             callbacks: Rc::new(RefCell::new(Callbacks::default())),
+            accessibility,
         });
 
         // Kick things off
@@ -859,7 +865,11 @@ impl PlatformWindow for WaylandWindow {
     }
 
     fn set_title(&mut self, title: &str) {
-        self.borrow().toplevel.set_title(title.to_string());
+        let state = self.borrow();
+        state.toplevel.set_title(title.to_string());
+        self.0
+            .accessibility
+            .set_window_title(state.handle.window_id().as_u64(), title);
     }
 
     fn set_app_id(&mut self, app_id: &str) {
@@ -1007,6 +1017,18 @@ impl PlatformWindow for WaylandWindow {
             update_window(state);
         }
     }
+
+    fn is_accessibility_enabled(&self) -> bool {
+        self.0.accessibility.is_enabled()
+    }
+
+    fn update_accessibility_tree(&self, tree: AccessibilityTree) {
+        // Wayland doesn't expose the position of windows, so the window's origin is unknown.
+        let window_id = self.borrow().handle.window_id().as_u64();
+        self.0
+            .accessibility
+            .update_window(window_id, Point::default(), tree);
+    }
 }
 
 fn update_window(mut state: RefMut<WaylandWindowState>) {
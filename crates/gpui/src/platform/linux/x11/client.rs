@@ -1090,6 +1090,7 @@ impl LinuxClient for X11Client {
             &state.atoms,
             state.scale_factor,
             state.common.appearance,
+            state.common.accessibility.clone(),
         )?;
 
         let screen_resources = state
@@ -2,11 +2,11 @@ use anyhow::Context;
 
 use crate::{
     platform::blade::{BladeRenderer, BladeSurfaceConfig},
-    px, size, AnyWindowHandle, Bounds, Decorations, DevicePixels, ForegroundExecutor, Modifiers,
-    Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler, PlatformWindow,
-    Point, PromptLevel, ResizeEdge, Scene, Size, Tiling, WindowAppearance,
-    WindowBackgroundAppearance, WindowBounds, WindowDecorations, WindowKind, WindowParams,
-    X11ClientStatePtr,
+    px, size, AccessibilityTree, AnyWindowHandle, AtspiBridge, Bounds, Decorations, DevicePixels,
+    ForegroundExecutor, Modifiers, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PromptLevel, ResizeEdge, Scene, Size, Tiling,
+    WindowAppearance, WindowBackgroundAppearance, WindowBounds, WindowDecorations, WindowKind,
+    WindowParams, X11ClientStatePtr,
 };
 
 use blade_graphics as gpu;
@@ -260,6 +260,7 @@ pub(crate) struct X11WindowStatePtr {
     pub(crate) callbacks: Rc<RefCell<Callbacks>>,
     xcb_connection: Rc<XCBConnection>,
     x_window: xproto::Window,
+    accessibility: AtspiBridge,
 }
 
 impl rwh::HasWindowHandle for RawWindow {
@@ -543,6 +544,9 @@ impl Drop for X11Window {
     fn drop(&mut self) {
         let mut state = self.0.state.borrow_mut();
         state.renderer.destroy();
+        self.0
+            .accessibility
+            .remove_window(state.handle.window_id().as_u64());
 
         let destroy_x_window = maybe!({
             self.0.xcb_connection.unmap_window(self.0.x_window)?;
@@ -594,6 +598,7 @@ impl X11Window {
         atoms: &XcbAtoms,
         scale_factor: f32,
         appearance: WindowAppearance,
+        accessibility: AtspiBridge,
     ) -> anyhow::Result<Self> {
         let ptr = X11WindowStatePtr {
             state: Rc::new(RefCell::new(X11WindowState::new(
@@ -612,6 +617,7 @@ impl X11Window {
             callbacks: Rc::new(RefCell::new(Callbacks::default())),
             xcb_connection: xcb_connection.clone(),
             x_window,
+            accessibility,
         };
 
         let state = ptr.state.borrow_mut();
@@ -1092,6 +1098,9 @@ impl PlatformWindow for X11Window {
             )
             .unwrap();
         self.0.xcb_connection.flush().unwrap();
+
+        let window_id = self.0.state.borrow().handle.window_id().as_u64();
+        self.0.accessibility.set_window_title(window_id, title);
     }
 
     fn set_app_id(&mut self, app_id: &str) {
@@ -1385,4 +1394,17 @@ impl PlatformWindow for X11Window {
             appearance_changed();
         }
     }
+
+    fn is_accessibility_enabled(&self) -> bool {
+        self.0.accessibility.is_enabled()
+    }
+
+    fn update_accessibility_tree(&self, tree: AccessibilityTree) {
+        let state = self.0.state.borrow();
+        self.0.accessibility.update_window(
+            state.handle.window_id().as_u64(),
+            state.bounds.origin,
+            tree,
+        );
+    }
 }
@@ -1,8 +1,8 @@
 use super::metal_atlas::MetalAtlas;
 use crate::{
-    point, size, AtlasTextureId, AtlasTextureKind, AtlasTile, Bounds, ContentMask, DevicePixels,
-    Hsla, MonochromeSprite, Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch, Quad,
-    ScaledPixels, Scene, Shadow, Size, Surface, Underline,
+    point, size, AtlasTextureId, AtlasTextureKind, AtlasTile, Background, Bounds, ContentMask,
+    DevicePixels, MonochromeSprite, Path, PathId, PathVertex, PolychromeSprite, PrimitiveBatch,
+    Quad, ScaledPixels, Scene, Shadow, Size, Surface, Underline,
 };
 use anyhow::{anyhow, Result};
 use block::ConcreteBlock;
@@ -739,7 +739,8 @@ impl MetalRenderer {
                             origin: origin.map(|p| p.floor()),
                             size: tile.bounds.size.map(Into::into),
                         },
-                        color: path.color,
+                        path_bounds: path.bounds,
+                        background: path.background,
                         tile: (*tile).clone(),
                     });
                     paths_and_tiles.next();
@@ -1242,7 +1243,8 @@ enum PathRasterizationInputIndex {
 #[repr(C)]
 pub struct PathSprite {
     pub bounds: Bounds<ScaledPixels>,
-    pub color: Hsla,
+    pub path_bounds: Bounds<ScaledPixels>,
+    pub background: Background,
     pub tile: AtlasTile,
 }
 
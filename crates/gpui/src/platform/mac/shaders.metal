@@ -21,6 +21,8 @@ float2 erf(float2 x);
 float blur_along_x(float x, float y, float sigma, float corner,
                    float2 half_size);
 float4 over(float4 below, float4 above);
+float4 gradient_color(Background background, Bounds_ScaledPixels bounds,
+                      float2 position);
 
 struct QuadVertexOutput {
   float4 position [[position]];
@@ -51,7 +53,7 @@ vertex QuadVertexOutput quad_vertex(uint unit_vertex_id [[vertex_id]],
       to_device_position(unit_vertex, quad.bounds, viewport_size);
   float4 clip_distance = distance_from_clip_rect(unit_vertex, quad.bounds,
                                                  quad.content_mask.bounds);
-  float4 background_color = hsla_to_rgba(quad.background);
+  float4 background_color = hsla_to_rgba(quad.background.colors[0]);
   float4 border_color = hsla_to_rgba(quad.border_color);
   return QuadVertexOutput{
       device_position,
@@ -65,6 +67,11 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
                               constant Quad *quads
                               [[buffer(QuadInputIndex_Quads)]]) {
   Quad quad = quads[input.quad_id];
+  float4 background_color = input.background_color;
+  if (quad.background.kind != BackgroundKind_Solid) {
+    background_color =
+        gradient_color(quad.background, quad.bounds, input.position.xy);
+  }
 
   // Fast path when the quad is not rounded and doesn't have any border.
   if (quad.corner_radii.top_left == 0. && quad.corner_radii.bottom_left == 0. &&
@@ -72,7 +79,7 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
       quad.corner_radii.bottom_right == 0. && quad.border_widths.top == 0. &&
       quad.border_widths.left == 0. && quad.border_widths.right == 0. &&
       quad.border_widths.bottom == 0.) {
-    return input.background_color;
+    return background_color;
   }
 
   float2 half_size =
@@ -120,13 +127,13 @@ fragment float4 quad_fragment(QuadFragmentInput input [[stage_in]],
 
   float4 color;
   if (border_width == 0.) {
-    color = input.background_color;
+    color = background_color;
   } else {
     float inset_distance = distance + border_width;
     // Blend the border on top of the background and then linearly interpolate
     // between the two as we slide inside the background.
-    float4 blended_border = over(input.background_color, input.border_color);
-    color = mix(blended_border, input.background_color,
+    float4 blended_border = over(background_color, input.border_color);
+    color = mix(blended_border, background_color,
                 saturate(0.5 - inset_distance));
   }
 
@@ -438,6 +445,7 @@ struct PathSpriteVertexOutput {
   float4 position [[position]];
   float2 tile_position;
   float4 color [[flat]];
+  uint sprite_id [[flat]];
 };
 
 vertex PathSpriteVertexOutput path_sprite_vertex(
@@ -456,8 +464,9 @@ vertex PathSpriteVertexOutput path_sprite_vertex(
   float4 device_position =
       to_device_position(unit_vertex, sprite.bounds, viewport_size);
   float2 tile_position = to_tile_position(unit_vertex, sprite.tile, atlas_size);
-  float4 color = hsla_to_rgba(sprite.color);
-  return PathSpriteVertexOutput{device_position, tile_position, color};
+  float4 color = hsla_to_rgba(sprite.background.colors[0]);
+  return PathSpriteVertexOutput{device_position, tile_position, color,
+                                sprite_id};
 }
 
 fragment float4 path_sprite_fragment(
@@ -469,7 +478,12 @@ fragment float4 path_sprite_fragment(
   float4 sample =
       atlas_texture.sample(atlas_texture_sampler, input.tile_position);
   float mask = 1. - abs(1. - fmod(sample.r, 2.));
+  PathSprite sprite = sprites[input.sprite_id];
   float4 color = input.color;
+  if (sprite.background.kind != BackgroundKind_Solid) {
+    color = gradient_color(sprite.background, sprite.path_bounds,
+                           input.position.xy);
+  }
   color.a *= mask;
   return color;
 }
@@ -691,3 +705,38 @@ float4 over(float4 below, float4 above) {
   result.a = alpha;
   return result;
 }
+
+// Returns the color of a gradient background at the given position within the
+// filled bounds.
+float4 gradient_color(Background background, Bounds_ScaledPixels bounds,
+                      float2 position) {
+  float2 origin = float2(bounds.origin.x, bounds.origin.y);
+  float2 size = float2(bounds.size.width, bounds.size.height);
+  float t;
+  if (background.kind == BackgroundKind_LinearGradient) {
+    // As in CSS, an angle of 0 points up and angles grow clockwise, and the
+    // gradient line is long enough for the corners to get the end colors.
+    float2 direction = float2(sin(background.angle), -cos(background.angle));
+    float line_length =
+        fabs(size.x * direction.x) + fabs(size.y * direction.y);
+    float2 center = origin + size / 2.;
+    t = dot(position - center, direction) / max(line_length, 0.0001) + 0.5;
+  } else {
+    float2 center =
+        origin + size * float2(background.center.x, background.center.y);
+    float2 to_near_corner = fabs(center - origin);
+    float2 to_far_corner = fabs(origin + size - center);
+    float farthest_corner = length(max(to_near_corner, to_far_corner));
+    t = length(position - center) /
+        max(farthest_corner * background.radius, 0.0001);
+  }
+
+  float4 color = hsla_to_rgba(background.colors[0]);
+  for (uint i = 1; i < background.stop_count; i++) {
+    float start = background.stops[i - 1];
+    float end = background.stops[i];
+    float amount = saturate((t - start) / max(end - start, 0.0001));
+    color = mix(color, hsla_to_rgba(background.colors[i]), amount);
+  }
+  return color;
+}
@@ -23,6 +23,7 @@ use core_text::{
         kCTFontSlantTrait, kCTFontSymbolicTrait, kCTFontWeightTrait, kCTFontWidthTrait,
     },
     line::CTLine,
+    run::CTRunRef,
     string_attributes::kCTFontAttributeName,
 };
 use font_kit::{
@@ -48,6 +49,13 @@ use super::open_type;
 #[allow(non_upper_case_globals)]
 const kCGImageAlphaOnly: u32 = 7;
 
+const K_CT_RUN_STATUS_RIGHT_TO_LEFT: u32 = 1 << 0;
+
+#[link(name = "CoreText", kind = "framework")]
+extern "C" {
+    fn CTRunGetStatus(run: CTRunRef) -> u32;
+}
+
 pub(crate) struct MacTextSystem(RwLock<MacTextSystemState>);
 
 #[derive(Clone, PartialEq, Eq, Hash)]
@@ -484,6 +492,9 @@ impl MacTextSystemState {
                     .unwrap()
             };
             let font_id = self.id_for_native_font(font);
+            let is_rtl = unsafe {
+                CTRunGetStatus(run.as_concrete_TypeRef()) & K_CT_RUN_STATUS_RIGHT_TO_LEFT != 0
+            };
 
             let mut ix_converter = StringIndexConverter::new(text);
             let mut glyphs = SmallVec::new();
@@ -494,6 +505,10 @@ impl MacTextSystemState {
                 .zip(run.string_indices().iter())
             {
                 let glyph_utf16_ix = usize::try_from(*glyph_utf16_ix).unwrap();
+                // Glyphs in right-to-left runs have decreasing string indices.
+                if glyph_utf16_ix < ix_converter.utf16_ix {
+                    ix_converter = StringIndexConverter::new(text);
+                }
                 ix_converter.advance_to_utf16_ix(glyph_utf16_ix);
                 glyphs.push(ShapedGlyph {
                     id: GlyphId(*glyph_id as u32),
@@ -503,7 +518,11 @@ impl MacTextSystemState {
                 });
             }
 
-            runs.push(ShapedRun { font_id, glyphs })
+            runs.push(ShapedRun {
+                font_id,
+                glyphs,
+                is_rtl,
+            })
         }
 
         let typographic_bounds = line.get_typographic_bounds();
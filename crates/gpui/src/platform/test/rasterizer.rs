@@ -0,0 +1,682 @@
+//! A software rasterizer for scenes, used to snapshot test windows on machines without a GPU.
+//!
+//! It follows the shaders of the GPU renderers closely enough for visual regression tests,
+//! but isn't pixel-identical to them: paths aren't anti-aliased and surfaces aren't drawn.
+
+use super::TestAtlas;
+use crate::{
+    AtlasTile, Background, BackgroundKind, Bounds, ContentMask, Corners, DevicePixels, Hsla,
+    MonochromeSprite, Path, PolychromeSprite, PrimitiveBatch, Quad, Rgba, ScaledPixels, Scene,
+    Shadow, Size, Underline,
+};
+use image::RgbaImage;
+use std::{f32::consts::PI, ops::Range};
+
+/// A color with straight alpha, with components between 0 and 1.
+type Color = [f32; 4];
+
+/// Rasterizes a scene into an image of the given size.
+pub(crate) fn rasterize_scene(
+    scene: &Scene,
+    atlas: &TestAtlas,
+    size: Size<DevicePixels>,
+) -> RgbaImage {
+    let mut canvas = Canvas::new(size);
+    for batch in scene.batches() {
+        match batch {
+            PrimitiveBatch::Shadows(shadows) => {
+                for shadow in shadows {
+                    canvas.draw_shadow(shadow);
+                }
+            }
+            PrimitiveBatch::Quads(quads) => {
+                for quad in quads {
+                    canvas.draw_quad(quad);
+                }
+            }
+            PrimitiveBatch::Paths(paths) => {
+                for path in paths {
+                    canvas.draw_path(path);
+                }
+            }
+            PrimitiveBatch::Underlines(underlines) => {
+                for underline in underlines {
+                    canvas.draw_underline(underline);
+                }
+            }
+            PrimitiveBatch::MonochromeSprites { sprites, .. } => {
+                for sprite in sprites {
+                    if let Some(bytes) = atlas.tile_bytes(&sprite.tile) {
+                        canvas.draw_monochrome_sprite(sprite, &bytes);
+                    }
+                }
+            }
+            PrimitiveBatch::PolychromeSprites { sprites, .. } => {
+                for sprite in sprites {
+                    if let Some(bytes) = atlas.tile_bytes(&sprite.tile) {
+                        canvas.draw_polychrome_sprite(sprite, &bytes);
+                    }
+                }
+            }
+            PrimitiveBatch::Surfaces(_) => {}
+        }
+    }
+    canvas.into_image()
+}
+
+struct Canvas {
+    width: usize,
+    height: usize,
+    /// The pixels of the canvas, with premultiplied alpha.
+    pixels: Vec<Color>,
+}
+
+impl Canvas {
+    fn new(size: Size<DevicePixels>) -> Self {
+        let width = size.width.0.max(0) as usize;
+        let height = size.height.0.max(0) as usize;
+        Self {
+            width,
+            height,
+            pixels: vec![[0.; 4]; width * height],
+        }
+    }
+
+    /// Blends a color over a pixel, scaling its alpha by the given coverage.
+    fn blend(&mut self, x: usize, y: usize, color: Color, coverage: f32) {
+        let alpha = (color[3] * coverage).clamp(0., 1.);
+        if alpha == 0. {
+            return;
+        }
+        let pixel = &mut self.pixels[y * self.width + x];
+        for channel in 0..3 {
+            pixel[channel] = color[channel] * alpha + pixel[channel] * (1. - alpha);
+        }
+        pixel[3] = alpha + pixel[3] * (1. - alpha);
+    }
+
+    /// Returns the pixels whose centers are within the given bounds and content mask.
+    fn pixels_within(
+        &self,
+        bounds: &Bounds<ScaledPixels>,
+        content_mask: &ContentMask<ScaledPixels>,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let bounds = bounds.intersect(&content_mask.bounds);
+        let columns = pixel_range(
+            bounds.origin.x.0,
+            bounds.origin.x.0 + bounds.size.width.0,
+            self.width,
+        );
+        let rows = pixel_range(
+            bounds.origin.y.0,
+            bounds.origin.y.0 + bounds.size.height.0,
+            self.height,
+        );
+        rows.flat_map(move |y| columns.clone().map(move |x| (x, y)))
+    }
+
+    fn draw_quad(&mut self, quad: &Quad) {
+        let border_color = rgba(quad.border_color);
+        let has_border = quad.border_widths.top.0 > 0.
+            || quad.border_widths.right.0 > 0.
+            || quad.border_widths.bottom.0 > 0.
+            || quad.border_widths.left.0 > 0.;
+        let half_size = [
+            quad.bounds.size.width.0 / 2.,
+            quad.bounds.size.height.0 / 2.,
+        ];
+        let center = [
+            quad.bounds.origin.x.0 + half_size[0],
+            quad.bounds.origin.y.0 + half_size[1],
+        ];
+
+        for (x, y) in self
+            .pixels_within(&quad.bounds, &quad.content_mask)
+            .collect::<Vec<_>>()
+        {
+            let position = pixel_center(x, y);
+            let background_color = background_color(&quad.background, &quad.bounds, position);
+            let center_to_point = [position[0] - center[0], position[1] - center[1]];
+            let corner_radius = pick_corner_radius(center_to_point, &quad.corner_radii);
+            let distance = rounded_rect_distance(center_to_point, half_size, corner_radius);
+
+            let mut color = background_color;
+            if has_border {
+                let vertical_border = if center_to_point[0] <= 0. {
+                    quad.border_widths.left.0
+                } else {
+                    quad.border_widths.right.0
+                };
+                let horizontal_border = if center_to_point[1] <= 0. {
+                    quad.border_widths.top.0
+                } else {
+                    quad.border_widths.bottom.0
+                };
+                let inset_size = [
+                    half_size[0] - corner_radius - vertical_border,
+                    half_size[1] - corner_radius - horizontal_border,
+                ];
+                let point_to_inset_corner = [
+                    center_to_point[0].abs() - inset_size[0],
+                    center_to_point[1].abs() - inset_size[1],
+                ];
+                let border_width = if point_to_inset_corner[0] < 0. && point_to_inset_corner[1] < 0.
+                {
+                    0.
+                } else if point_to_inset_corner[1] > point_to_inset_corner[0] {
+                    horizontal_border
+                } else {
+                    vertical_border
+                };
+                if border_width > 0. {
+                    // Blend the border on top of the background and then linearly interpolate
+                    // between the two as we slide inside the background.
+                    let blended_border = over(background_color, border_color);
+                    let inset_distance = distance + border_width;
+                    color = mix(
+                        blended_border,
+                        background_color,
+                        saturate(0.5 - inset_distance),
+                    );
+                }
+            }
+            self.blend(x, y, color, saturate(0.5 - distance));
+        }
+    }
+
+    fn draw_shadow(&mut self, shadow: &Shadow) {
+        let color = rgba(shadow.color);
+        let blur_radius = shadow.blur_radius.0;
+        // Expand the bounds to make room for the blur.
+        let margin = ScaledPixels(3. * blur_radius);
+        let mut bounds = shadow.bounds;
+        bounds.origin.x -= margin;
+        bounds.origin.y -= margin;
+        bounds.size.width += margin + margin;
+        bounds.size.height += margin + margin;
+
+        let half_size = [
+            shadow.bounds.size.width.0 / 2.,
+            shadow.bounds.size.height.0 / 2.,
+        ];
+        let center = [
+            shadow.bounds.origin.x.0 + half_size[0],
+            shadow.bounds.origin.y.0 + half_size[1],
+        ];
+
+        for (x, y) in self
+            .pixels_within(&bounds, &shadow.content_mask)
+            .collect::<Vec<_>>()
+        {
+            let position = pixel_center(x, y);
+            let center_to_point = [position[0] - center[0], position[1] - center[1]];
+            let corner_radius = pick_corner_radius(center_to_point, &shadow.corner_radii);
+
+            let alpha = if blur_radius == 0. {
+                let distance = rounded_rect_distance(center_to_point, half_size, corner_radius);
+                saturate(0.5 - distance)
+            } else {
+                // The signal is only non-zero in a limited range, so don't waste samples.
+                let low = center_to_point[1] - half_size[1];
+                let high = center_to_point[1] + half_size[1];
+                let start = (-3. * blur_radius).clamp(low, high);
+                let end = (3. * blur_radius).clamp(low, high);
+
+                let step = (end - start) / 4.;
+                let mut sample_y = start + step * 0.5;
+                let mut alpha = 0.;
+                for _ in 0..4 {
+                    let blur = blur_along_x(
+                        center_to_point[0],
+                        center_to_point[1] - sample_y,
+                        blur_radius,
+                        corner_radius,
+                        half_size,
+                    );
+                    alpha += blur * gaussian(sample_y, blur_radius) * step;
+                    sample_y += step;
+                }
+                alpha
+            };
+            self.blend(x, y, color, alpha);
+        }
+    }
+
+    fn draw_path(&mut self, path: &Path<ScaledPixels>) {
+        let triangles = path
+            .vertices
+            .chunks_exact(3)
+            .map(|vertices| {
+                [
+                    (
+                        [vertices[0].xy_position.x.0, vertices[0].xy_position.y.0],
+                        [vertices[0].st_position.x, vertices[0].st_position.y],
+                    ),
+                    (
+                        [vertices[1].xy_position.x.0, vertices[1].xy_position.y.0],
+                        [vertices[1].st_position.x, vertices[1].st_position.y],
+                    ),
+                    (
+                        [vertices[2].xy_position.x.0, vertices[2].xy_position.y.0],
+                        [vertices[2].st_position.x, vertices[2].st_position.y],
+                    ),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        for (x, y) in self
+            .pixels_within(&path.bounds, &path.content_mask)
+            .collect::<Vec<_>>()
+        {
+            let position = pixel_center(x, y);
+            // Like the GPU renderers, count how many triangles cover the pixel
+            // and fill the pixels that are covered an odd number of times.
+            let coverage = triangles
+                .iter()
+                .filter(|triangle| {
+                    barycentric_st(position, triangle).map_or(false, |st| st[0] * st[0] <= st[1])
+                })
+                .count();
+            if coverage % 2 == 1 {
+                let color = background_color(&path.background, &path.bounds, position);
+                self.blend(x, y, color, 1.);
+            }
+        }
+    }
+
+    fn draw_underline(&mut self, underline: &Underline) {
+        let color = rgba(underline.color);
+        let thickness = underline.thickness.0;
+        let bounds = underline.bounds;
+
+        for (x, y) in self
+            .pixels_within(&bounds, &underline.content_mask)
+            .collect::<Vec<_>>()
+        {
+            if !underline.wavy {
+                self.blend(x, y, color, color[3]);
+                continue;
+            }
+
+            let position = pixel_center(x, y);
+            let half_thickness = thickness * 0.5;
+            let height = bounds.size.height.0;
+            let st = [
+                (position[0] - bounds.origin.x.0) / height,
+                (position[1] - bounds.origin.y.0) / height - 0.5,
+            ];
+            let frequency = PI * 3. * thickness / 8.;
+            let amplitude = 1. / (2. * thickness);
+            let sine = (st[0] * frequency).sin() * amplitude;
+            let d_sine = (st[0] * frequency).cos() * amplitude * frequency;
+            let distance = (st[1] - sine) / (1. + d_sine * d_sine).sqrt();
+            let distance_in_pixels = distance * height;
+            let distance_from_top_border = distance_in_pixels - half_thickness;
+            let distance_from_bottom_border = distance_in_pixels + half_thickness;
+            let alpha =
+                saturate(0.5 - (-distance_from_bottom_border).max(distance_from_top_border));
+            self.blend(x, y, color, alpha * color[3]);
+        }
+    }
+
+    fn draw_monochrome_sprite(&mut self, sprite: &MonochromeSprite, bytes: &[u8]) {
+        let color = rgba(sprite.color);
+        let [[a, b], [c, d]] = sprite.transformation.rotation_scale;
+        let [tx, ty] = sprite.transformation.translation;
+        let determinant = a * d - b * c;
+        if determinant == 0. {
+            return;
+        }
+
+        // Iterate over the bounding box of the transformed sprite, mapping
+        // each pixel back to the untransformed sprite to sample the tile.
+        let origin = [sprite.bounds.origin.x.0, sprite.bounds.origin.y.0];
+        let size = [sprite.bounds.size.width.0, sprite.bounds.size.height.0];
+        let corners = [
+            origin,
+            [origin[0] + size[0], origin[1]],
+            [origin[0], origin[1] + size[1]],
+            [origin[0] + size[0], origin[1] + size[1]],
+        ]
+        .map(|[x, y]| [a * x + b * y + tx, c * x + d * y + ty]);
+        let min = corners.iter().fold([f32::MAX; 2], |min, corner| {
+            [min[0].min(corner[0]), min[1].min(corner[1])]
+        });
+        let max = corners.iter().fold([f32::MIN; 2], |max, corner| {
+            [max[0].max(corner[0]), max[1].max(corner[1])]
+        });
+
+        for y in pixel_range(min[1], max[1], self.height) {
+            for x in pixel_range(min[0], max[0], self.width) {
+                let [px, py] = pixel_center(x, y);
+                let (px, py) = (px - tx, py - ty);
+                let position = [
+                    (d * px - b * py) / determinant,
+                    (a * py - c * px) / determinant,
+                ];
+                if !contains(&sprite.bounds, position)
+                    || !contains(&sprite.content_mask.bounds, position)
+                {
+                    continue;
+                }
+                let Some(offset) = tile_offset(&sprite.tile, origin, position) else {
+                    continue;
+                };
+                let Some(&sample) = bytes.get(offset) else {
+                    continue;
+                };
+                self.blend(x, y, color, sample as f32 / 255.);
+            }
+        }
+    }
+
+    fn draw_polychrome_sprite(&mut self, sprite: &PolychromeSprite, bytes: &[u8]) {
+        let origin = [sprite.bounds.origin.x.0, sprite.bounds.origin.y.0];
+        let half_size = [
+            sprite.bounds.size.width.0 / 2.,
+            sprite.bounds.size.height.0 / 2.,
+        ];
+        let center = [origin[0] + half_size[0], origin[1] + half_size[1]];
+
+        for (x, y) in self
+            .pixels_within(&sprite.bounds, &sprite.content_mask)
+            .collect::<Vec<_>>()
+        {
+            let position = pixel_center(x, y);
+            let Some(offset) = tile_offset(&sprite.tile, origin, position) else {
+                continue;
+            };
+            let Some(&[b, g, r, a]) = bytes.get(offset * 4..offset * 4 + 4) else {
+                continue;
+            };
+            let mut color = [r, g, b, a].map(|channel| channel as f32 / 255.);
+            if sprite.grayscale {
+                let grayscale = 0.2126 * color[0] + 0.7152 * color[1] + 0.0722 * color[2];
+                color = [grayscale, grayscale, grayscale, color[3]];
+            }
+
+            let center_to_point = [position[0] - center[0], position[1] - center[1]];
+            let corner_radius = pick_corner_radius(center_to_point, &sprite.corner_radii);
+            let distance = rounded_rect_distance(center_to_point, half_size, corner_radius);
+            self.blend(x, y, color, saturate(0.5 - distance));
+        }
+    }
+
+    fn into_image(self) -> RgbaImage {
+        let mut image = RgbaImage::new(self.width as u32, self.height as u32);
+        for (pixel, color) in image.pixels_mut().zip(self.pixels) {
+            let alpha = color[3];
+            let unpremultiply = |channel: f32| {
+                if alpha == 0. {
+                    0
+                } else {
+                    ((channel / alpha).clamp(0., 1.) * 255.).round() as u8
+                }
+            };
+            *pixel = image::Rgba([
+                unpremultiply(color[0]),
+                unpremultiply(color[1]),
+                unpremultiply(color[2]),
+                (alpha.clamp(0., 1.) * 255.).round() as u8,
+            ]);
+        }
+        image
+    }
+}
+
+/// The largest difference in any channel for two pixels to be considered the same.
+const SNAPSHOT_CHANNEL_TOLERANCE: u8 = 8;
+
+/// Returns the fraction of pixels that differ between two snapshots,
+/// which is 1 when their sizes differ.
+pub(crate) fn snapshot_difference(expected: &RgbaImage, actual: &RgbaImage) -> f32 {
+    if expected.dimensions() != actual.dimensions() {
+        return 1.;
+    }
+    let pixel_count = expected.pixels().len();
+    if pixel_count == 0 {
+        return 0.;
+    }
+    let differing_pixels =
+        expected
+            .pixels()
+            .zip(actual.pixels())
+            .filter(|(expected, actual)| {
+                expected.0.iter().zip(actual.0).any(|(expected, actual)| {
+                    expected.abs_diff(actual) > SNAPSHOT_CHANNEL_TOLERANCE
+                })
+            })
+            .count();
+    differing_pixels as f32 / pixel_count as f32
+}
+
+/// Returns the pixels whose centers are between `start` and `end`.
+fn pixel_range(start: f32, end: f32, limit: usize) -> Range<usize> {
+    let start = (start - 0.5).ceil().clamp(0., limit as f32) as usize;
+    let end = (end - 0.5).ceil().clamp(0., limit as f32) as usize;
+    start..end.max(start)
+}
+
+fn pixel_center(x: usize, y: usize) -> [f32; 2] {
+    [x as f32 + 0.5, y as f32 + 0.5]
+}
+
+fn contains(bounds: &Bounds<ScaledPixels>, position: [f32; 2]) -> bool {
+    position[0] >= bounds.origin.x.0
+        && position[0] < bounds.origin.x.0 + bounds.size.width.0
+        && position[1] >= bounds.origin.y.0
+        && position[1] < bounds.origin.y.0 + bounds.size.height.0
+}
+
+/// Returns the index of the tile pixel under the given position, for a tile drawn at `origin`.
+fn tile_offset(tile: &AtlasTile, origin: [f32; 2], position: [f32; 2]) -> Option<usize> {
+    let width = tile.bounds.size.width.0;
+    let height = tile.bounds.size.height.0;
+    let x = (position[0] - origin[0]).floor() as i32;
+    let y = (position[1] - origin[1]).floor() as i32;
+    if x < 0 || y < 0 || x >= width || y >= height {
+        return None;
+    }
+    Some((y * width + x) as usize)
+}
+
+fn rgba(color: Hsla) -> Color {
+    let color = Rgba::from(color);
+    [color.r, color.g, color.b, color.a]
+}
+
+fn saturate(value: f32) -> f32 {
+    value.clamp(0., 1.)
+}
+
+fn mix(from: Color, to: Color, amount: f32) -> Color {
+    [0, 1, 2, 3].map(|channel| from[channel] + (to[channel] - from[channel]) * amount)
+}
+
+fn over(below: Color, above: Color) -> Color {
+    let alpha = above[3] + below[3] * (1. - above[3]);
+    if alpha == 0. {
+        return [0.; 4];
+    }
+    let channel = |channel: usize| {
+        (above[channel] * above[3] + below[channel] * below[3] * (1. - above[3])) / alpha
+    };
+    [channel(0), channel(1), channel(2), alpha]
+}
+
+fn background_color(
+    background: &Background,
+    bounds: &Bounds<ScaledPixels>,
+    position: [f32; 2],
+) -> Color {
+    let mut color = rgba(background.colors[0]);
+    let origin = [bounds.origin.x.0, bounds.origin.y.0];
+    let size = [bounds.size.width.0, bounds.size.height.0];
+    let t = match background.kind {
+        BackgroundKind::Solid => return color,
+        BackgroundKind::LinearGradient => {
+            let direction = [background.angle.sin(), -background.angle.cos()];
+            let line_length = (size[0] * direction[0]).abs() + (size[1] * direction[1]).abs();
+            let center = [origin[0] + size[0] / 2., origin[1] + size[1] / 2.];
+            ((position[0] - center[0]) * direction[0] + (position[1] - center[1]) * direction[1])
+                / line_length.max(0.0001)
+                + 0.5
+        }
+        BackgroundKind::RadialGradient => {
+            let center = [
+                origin[0] + size[0] * background.center.x,
+                origin[1] + size[1] * background.center.y,
+            ];
+            let farthest_corner = f32::hypot(
+                (center[0] - origin[0]).max(origin[0] + size[0] - center[0]),
+                (center[1] - origin[1]).max(origin[1] + size[1] - center[1]),
+            );
+            f32::hypot(position[0] - center[0], position[1] - center[1])
+                / (farthest_corner * background.radius).max(0.0001)
+        }
+    };
+
+    for ix in 1..background.stop_count as usize {
+        let start = background.stops[ix - 1];
+        let end = background.stops[ix];
+        let amount = saturate((t - start) / (end - start).max(0.0001));
+        color = mix(color, rgba(background.colors[ix]), amount);
+    }
+    color
+}
+
+fn pick_corner_radius(center_to_point: [f32; 2], radii: &Corners<ScaledPixels>) -> f32 {
+    match (center_to_point[0] < 0., center_to_point[1] < 0.) {
+        (true, true) => radii.top_left.0,
+        (true, false) => radii.bottom_left.0,
+        (false, true) => radii.top_right.0,
+        (false, false) => radii.bottom_right.0,
+    }
+}
+
+/// Returns the signed distance from a point to the edge of a rounded rectangle.
+fn rounded_rect_distance(
+    center_to_point: [f32; 2],
+    half_size: [f32; 2],
+    corner_radius: f32,
+) -> f32 {
+    let rounded_edge_to_point = [
+        center_to_point[0].abs() - half_size[0] + corner_radius,
+        center_to_point[1].abs() - half_size[1] + corner_radius,
+    ];
+    f32::hypot(
+        rounded_edge_to_point[0].max(0.),
+        rounded_edge_to_point[1].max(0.),
+    ) + rounded_edge_to_point[0]
+        .max(rounded_edge_to_point[1])
+        .min(0.)
+        - corner_radius
+}
+
+/// Returns the interpolated `st` coordinates of a point inside a triangle of a path.
+fn barycentric_st(position: [f32; 2], triangle: &[([f32; 2], [f32; 2]); 3]) -> Option<[f32; 2]> {
+    let [(a, st_a), (b, st_b), (c, st_c)] = *triangle;
+    let determinant = (b[1] - c[1]) * (a[0] - c[0]) + (c[0] - b[0]) * (a[1] - c[1]);
+    if determinant == 0. {
+        return None;
+    }
+    let wa =
+        ((b[1] - c[1]) * (position[0] - c[0]) + (c[0] - b[0]) * (position[1] - c[1])) / determinant;
+    let wb =
+        ((c[1] - a[1]) * (position[0] - c[0]) + (a[0] - c[0]) * (position[1] - c[1])) / determinant;
+    let wc = 1. - wa - wb;
+    if wa < 0. || wb < 0. || wc < 0. {
+        return None;
+    }
+    Some([
+        wa * st_a[0] + wb * st_b[0] + wc * st_c[0],
+        wa * st_a[1] + wb * st_b[1] + wc * st_c[1],
+    ])
+}
+
+/// A standard gaussian function, used for weighting samples.
+fn gaussian(x: f32, sigma: f32) -> f32 {
+    (-(x * x) / (2. * sigma * sigma)).exp() / ((2. * PI).sqrt() * sigma)
+}
+
+/// Approximates the error function, needed for the gaussian integral.
+fn erf(value: f32) -> f32 {
+    let sign = value.signum();
+    let value = value.abs();
+    let r1 = 1. + (0.278393 + (0.230389 + 0.078108 * (value * value)) * value) * value;
+    let r2 = r1 * r1;
+    sign - sign / (r2 * r2)
+}
+
+fn blur_along_x(x: f32, y: f32, sigma: f32, corner: f32, half_size: [f32; 2]) -> f32 {
+    let delta = (half_size[1] - corner - y.abs()).min(0.);
+    let curved = half_size[0] - corner + (corner * corner - delta * delta).max(0.).sqrt();
+    let scale = 0.5f32.sqrt() / sigma;
+    let start = 0.5 + 0.5 * erf((x - curved) * scale);
+    let end = 0.5 + 0.5 * erf((x + curved) * scale);
+    end - start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{point, size, Edges};
+
+    #[test]
+    fn test_rasterize_quads() {
+        let mut scene = Scene::default();
+        let bounds = Bounds {
+            origin: point(ScaledPixels(2.), ScaledPixels(2.)),
+            size: size(ScaledPixels(4.), ScaledPixels(4.)),
+        };
+        scene.insert_primitive(Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            background: crate::red().into(),
+            ..Default::default()
+        });
+        let bounds = Bounds {
+            origin: point(ScaledPixels(4.), ScaledPixels(4.)),
+            size: size(ScaledPixels(4.), ScaledPixels(4.)),
+        };
+        scene.insert_primitive(Quad {
+            bounds,
+            content_mask: ContentMask { bounds },
+            background: crate::transparent_black().into(),
+            border_color: crate::blue(),
+            border_widths: Edges::all(ScaledPixels(1.)),
+            ..Default::default()
+        });
+        scene.finish();
+
+        let image = rasterize_scene(
+            &scene,
+            &TestAtlas::new(),
+            size(DevicePixels(8), DevicePixels(8)),
+        );
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 0, 0]);
+        assert_eq!(image.get_pixel(2, 2).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(5, 5).0, [255, 0, 0, 255]);
+        // The border of the second quad is drawn over the first one.
+        assert_eq!(image.get_pixel(4, 4).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(7, 5).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(6, 6).0, [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_snapshot_difference() {
+        let expected = RgbaImage::from_pixel(4, 4, image::Rgba([10, 20, 30, 255]));
+        let mut actual = expected.clone();
+        assert_eq!(snapshot_difference(&expected, &actual), 0.);
+
+        actual.put_pixel(0, 0, image::Rgba([14, 20, 30, 255]));
+        assert_eq!(snapshot_difference(&expected, &actual), 0.);
+
+        actual.put_pixel(1, 0, image::Rgba([10, 20, 30, 0]));
+        actual.put_pixel(2, 0, image::Rgba([10, 120, 30, 255]));
+        assert_eq!(snapshot_difference(&expected, &actual), 2. / 16.);
+
+        let smaller = RgbaImage::new(2, 2);
+        assert_eq!(snapshot_difference(&expected, &smaller), 1.);
+    }
+}
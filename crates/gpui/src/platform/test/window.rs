@@ -19,7 +19,7 @@ pub(crate) struct TestWindowState {
     pub(crate) title: Option<String>,
     pub(crate) edited: bool,
     platform: Weak<TestPlatform>,
-    sprite_atlas: Arc<dyn PlatformAtlas>,
+    pub(crate) sprite_atlas: Arc<TestAtlas>,
     pub(crate) should_close_handler: Option<Box<dyn FnMut() -> bool>>,
     input_callback: Option<Box<dyn FnMut(PlatformInput) -> DispatchEventResult>>,
     active_status_change_callback: Option<Box<dyn FnMut(bool)>>,
@@ -257,6 +257,11 @@ impl PlatformWindow for TestWindow {
         self.0.lock().sprite_atlas.clone()
     }
 
+    // Tests query the accessibility tree directly, so always build it.
+    fn is_accessibility_enabled(&self) -> bool {
+        true
+    }
+
     fn as_test(&mut self) -> Option<&mut TestWindow> {
         Some(self)
     }
@@ -278,6 +283,8 @@ impl PlatformWindow for TestWindow {
 pub(crate) struct TestAtlasState {
     next_id: u32,
     tiles: HashMap<AtlasKey, AtlasTile>,
+    /// The pixels of each tile, so that scenes can be rasterized on the CPU.
+    textures: HashMap<AtlasTextureId, Arc<[u8]>>,
 }
 
 pub(crate) struct TestAtlas(Mutex<TestAtlasState>);
@@ -287,8 +294,15 @@ impl TestAtlas {
         TestAtlas(Mutex::new(TestAtlasState {
             next_id: 0,
             tiles: HashMap::default(),
+            textures: HashMap::default(),
         }))
     }
+
+    /// Returns the pixels of a tile, with one byte per pixel for monochrome
+    /// tiles and four bytes in BGRA order for polychrome tiles.
+    pub(crate) fn tile_bytes(&self, tile: &AtlasTile) -> Option<Arc<[u8]>> {
+        self.0.lock().textures.get(&tile.texture_id).cloned()
+    }
 }
 
 impl PlatformAtlas for TestAtlas {
@@ -305,7 +319,7 @@ impl PlatformAtlas for TestAtlas {
         }
         drop(state);
 
-        let Some((size, _)) = build()? else {
+        let Some((size, bytes)) = build()? else {
             return Ok(None);
         };
 
@@ -314,14 +328,16 @@ impl PlatformAtlas for TestAtlas {
         let texture_id = state.next_id;
         state.next_id += 1;
         let tile_id = state.next_id;
+        let texture_id = AtlasTextureId {
+            index: texture_id,
+            kind: key.texture_kind(),
+        };
+        state.textures.insert(texture_id, bytes.into());
 
         state.tiles.insert(
             key.clone(),
             crate::AtlasTile {
-                texture_id: AtlasTextureId {
-                    index: texture_id,
-                    kind: crate::AtlasTextureKind::Path,
-                },
+                texture_id,
                 tile_id: TileId(tile_id),
                 padding: 0,
                 bounds: crate::Bounds {
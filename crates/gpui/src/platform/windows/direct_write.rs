@@ -998,7 +998,9 @@ impl IDWriteTextRenderer_Impl for TextRenderer {
             } else {
                 context.text_system.select_font(&font_struct)
             };
-            let mut glyphs = SmallVec::new();
+            let is_rtl = glyphrun.bidiLevel % 2 == 1;
+            let mut glyphs = SmallVec::<[ShapedGlyph; 8]>::new();
+            let mut advances = SmallVec::<[f32; 8]>::new();
             for index in 0..glyph_count {
                 let id = GlyphId(*glyphrun.glyphIndices.add(index) as u32);
                 context
@@ -1006,14 +1008,28 @@ impl IDWriteTextRenderer_Impl for TextRenderer {
                     .advance_to_utf16_ix(context.utf16_index);
                 glyphs.push(ShapedGlyph {
                     id,
-                    position: point(px(context.width), px(0.0)),
+                    position: point(px(0.0), px(0.0)),
                     index: context.index_converter.utf8_ix,
                     is_emoji,
                 });
+                advances.push(*glyphrun.glyphAdvances.add(index));
                 context.utf16_index += utf16_length_per_glyph;
-                context.width += *glyphrun.glyphAdvances.add(index);
             }
-            context.runs.push(ShapedRun { font_id, glyphs });
+            // Glyphs in right-to-left runs are reported in logical order, but runs are
+            // expected in visual order.
+            if is_rtl {
+                glyphs.reverse();
+                advances.reverse();
+            }
+            for (glyph, advance) in glyphs.iter_mut().zip(advances) {
+                glyph.position.x = px(context.width);
+                context.width += advance;
+            }
+            context.runs.push(ShapedRun {
+                font_id,
+                glyphs,
+                is_rtl,
+            });
         }
         Ok(())
     }
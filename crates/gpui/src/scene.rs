@@ -421,7 +421,7 @@ pub(crate) struct Quad {
     pub pad: u32, // align to 8 bytes
     pub bounds: Bounds<ScaledPixels>,
     pub content_mask: ContentMask<ScaledPixels>,
-    pub background: Hsla,
+    pub background: Background,
     pub border_color: Hsla,
     pub corner_radii: Corners<ScaledPixels>,
     pub border_widths: Edges<ScaledPixels>,
@@ -445,6 +445,50 @@ impl From<Quad> for Primitive {
     }
 }
 
+/// The maximum number of color stops of a gradient that are drawn.
+pub(crate) const MAX_GRADIENT_STOPS: usize = 4;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[repr(u32)]
+pub(crate) enum BackgroundKind {
+    #[default]
+    Solid = 0,
+    LinearGradient = 1,
+    RadialGradient = 2,
+}
+
+/// A [`crate::Fill`] laid out for the shaders.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[repr(C)]
+pub(crate) struct Background {
+    pub kind: BackgroundKind,
+    pub stop_count: u32,
+    /// The direction of a linear gradient, in radians.
+    pub angle: f32,
+    /// The radius of a radial gradient, relative to the distance from its center to the farthest corner.
+    pub radius: f32,
+    /// The center of a radial gradient, relative to the filled bounds.
+    pub center: Point<f32>,
+    // The array lengths are spelled out for the generated shader bindings,
+    // and must match `MAX_GRADIENT_STOPS`.
+    pub colors: [Hsla; 4],
+    pub stops: [f32; 4],
+}
+
+impl Eq for Background {}
+
+impl From<Hsla> for Background {
+    fn from(color: Hsla) -> Self {
+        let mut colors = [Hsla::default(); MAX_GRADIENT_STOPS];
+        colors[0] = color;
+        Background {
+            stop_count: 1,
+            colors,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[repr(C)]
 pub(crate) struct Underline {
@@ -710,7 +754,7 @@ pub struct Path<P: Clone + Default + Debug> {
     pub(crate) bounds: Bounds<P>,
     pub(crate) content_mask: ContentMask<P>,
     pub(crate) vertices: Vec<PathVertex<P>>,
-    pub(crate) color: Hsla,
+    pub(crate) background: Background,
     start: Point<P>,
     current: Point<P>,
     contour_count: usize,
@@ -730,7 +774,7 @@ impl Path<Pixels> {
                 size: Default::default(),
             },
             content_mask: Default::default(),
-            color: Default::default(),
+            background: Default::default(),
             contour_count: 0,
         }
     }
@@ -750,7 +794,7 @@ impl Path<Pixels> {
             start: self.start.map(|start| start.scale(factor)),
             current: self.current.scale(factor),
             contour_count: self.contour_count,
-            color: self.color,
+            background: self.background,
         }
     }
 
@@ -5,17 +5,17 @@ use std::{
 };
 
 use crate::{
-    black, phi, point, quad, rems, AbsoluteLength, Bounds, ContentMask, Corners, CornersRefinement,
-    CursorStyle, DefiniteLength, Edges, EdgesRefinement, Font, FontFeatures, FontStyle, FontWeight,
-    Hsla, Length, Pixels, Point, PointRefinement, Rgba, SharedString, Size, SizeRefinement, Styled,
-    TextRun, WindowContext,
+    black, phi, point, quad, rems, AbsoluteLength, Background, BackgroundKind, Bounds, ContentMask,
+    Corners, CornersRefinement, CursorStyle, DefiniteLength, Edges, EdgesRefinement, Font,
+    FontFeatures, FontStyle, FontWeight, Hsla, Length, Pixels, Point, PointRefinement, Rems, Rgba,
+    SharedString, Size, SizeRefinement, Styled, TextRun, WindowContext, MAX_GRADIENT_STOPS,
 };
 use collections::HashSet;
 use refineable::Refineable;
 use smallvec::SmallVec;
 pub use taffy::style::{
-    AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, JustifyContent,
-    Overflow, Position,
+    AlignContent, AlignItems, AlignSelf, Display, FlexDirection, FlexWrap, GridAutoFlow,
+    JustifyContent, Overflow, Position,
 };
 
 /// Use this struct for interfacing with the 'debug_below' styling from your own elements.
@@ -84,7 +84,7 @@ pub struct Style {
     pub align_content: Option<AlignContent>,
     /// How should contained within this item be aligned in the main/inline axis
     pub justify_content: Option<JustifyContent>,
-    /// How large should the gaps between items in a flex container be?
+    /// How large should the gaps between items in a flex or grid container be?
     #[refineable]
     pub gap: Size<DefiniteLength>,
 
@@ -100,6 +100,24 @@ pub struct Style {
     /// The relative rate at which this item shrinks when it is contracting to fit into space, 1.0 is the default value, and this value must be positive.
     pub flex_shrink: f32,
 
+    // Grid properties
+    /// The sizes of the columns of this grid container
+    pub grid_template_columns: Vec<GridTrack>,
+    /// The sizes of the rows of this grid container
+    pub grid_template_rows: Vec<GridTrack>,
+    /// The sizes of columns that are created implicitly, when items are placed outside of the template
+    pub grid_auto_columns: Vec<TrackSize>,
+    /// The sizes of rows that are created implicitly, when items are placed outside of the template
+    pub grid_auto_rows: Vec<TrackSize>,
+    /// How items without an explicit placement are placed in this grid container
+    pub grid_auto_flow: GridAutoFlow,
+    /// Which rows of its parent grid this item spans
+    #[refineable]
+    pub grid_row: GridLines,
+    /// Which columns of its parent grid this item spans
+    #[refineable]
+    pub grid_column: GridLines,
+
     /// The fill color of this element
     pub background: Option<Fill>,
 
@@ -144,6 +162,197 @@ pub enum Visibility {
     Hidden,
 }
 
+/// A size for a row or column of a grid, or for the minimum or maximum of that size
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrackBreadth {
+    /// A fixed length
+    Length(DefiniteLength),
+    /// A share of the space left over after the other tracks have been sized, like CSS `fr`
+    Fraction(f32),
+    /// Sized to fit the content of the track, stretching if there is space left over
+    Auto,
+    /// The smallest size that fits the content of the track
+    MinContent,
+    /// The size of the content of the track, without wrapping
+    MaxContent,
+}
+
+/// The size of a row or column of a grid, between a minimum and a maximum, similar to CSS `minmax()`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackSize {
+    /// The smallest size the track can shrink to
+    pub min: TrackBreadth,
+    /// The largest size the track can grow to
+    pub max: TrackBreadth,
+}
+
+impl Default for TrackSize {
+    fn default() -> Self {
+        Self {
+            min: TrackBreadth::Auto,
+            max: TrackBreadth::Auto,
+        }
+    }
+}
+
+/// The rows or columns defined by a grid template, similar to an entry of the CSS
+/// `grid-template-columns` and `grid-template-rows` properties
+#[derive(Clone, Debug, PartialEq)]
+pub enum GridTrack {
+    /// A single track
+    Single(TrackSize),
+    /// A list of tracks repeated a number of times, similar to CSS `repeat()`
+    Repeat(GridRepetition, SmallVec<[TrackSize; 1]>),
+}
+
+impl GridTrack {
+    /// Repeats the given tracks `count` times.
+    pub fn repeat(count: u16, tracks: impl IntoIterator<Item = TrackSize>) -> Self {
+        Self::Repeat(GridRepetition::Count(count), tracks.into_iter().collect())
+    }
+
+    /// Repeats the given tracks as many times as they fit in the grid container, like CSS `auto-fill`.
+    pub fn auto_fill(tracks: impl IntoIterator<Item = TrackSize>) -> Self {
+        Self::Repeat(GridRepetition::AutoFill, tracks.into_iter().collect())
+    }
+
+    /// Repeats the given tracks as many times as they fit in the grid container, collapsing
+    /// the repetitions that end up empty, like CSS `auto-fit`.
+    pub fn auto_fit(tracks: impl IntoIterator<Item = TrackSize>) -> Self {
+        Self::Repeat(GridRepetition::AutoFit, tracks.into_iter().collect())
+    }
+}
+
+/// How many times the tracks of a [`GridTrack::Repeat`] are repeated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GridRepetition {
+    /// A fixed number of times
+    Count(u16),
+    /// As many times as fit in the grid container
+    AutoFill,
+    /// As many times as fit in the grid container, collapsing empty repetitions
+    AutoFit,
+}
+
+/// Where one edge of a grid item is placed along an axis of its grid
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GridPlacement {
+    /// Placed automatically, according to the grid's auto flow
+    #[default]
+    Auto,
+    /// Placed at the given grid line. Lines are numbered from 1, and negative numbers count
+    /// backwards from the last line.
+    Line(i16),
+    /// Placed the given number of tracks away from the other edge
+    Span(u16),
+}
+
+/// The start and end placement of a grid item along one axis of its grid, similar to the CSS
+/// `grid-row` and `grid-column` properties
+#[derive(Refineable, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[refineable(Debug)]
+pub struct GridLines {
+    /// The line where the item starts
+    pub start: GridPlacement,
+    /// The line where the item ends
+    pub end: GridPlacement,
+}
+
+/// A grid track breadth that takes the given share of the leftover space, like CSS `fr`.
+pub fn fr(fraction: f32) -> TrackBreadth {
+    TrackBreadth::Fraction(fraction)
+}
+
+/// A grid track size between the given minimum and maximum, like CSS `minmax()`.
+pub fn minmax(min: impl Into<TrackBreadth>, max: impl Into<TrackBreadth>) -> TrackSize {
+    TrackSize {
+        min: min.into(),
+        max: max.into(),
+    }
+}
+
+impl From<DefiniteLength> for TrackBreadth {
+    fn from(length: DefiniteLength) -> Self {
+        Self::Length(length)
+    }
+}
+
+impl From<AbsoluteLength> for TrackBreadth {
+    fn from(length: AbsoluteLength) -> Self {
+        Self::Length(length.into())
+    }
+}
+
+impl From<Pixels> for TrackBreadth {
+    fn from(pixels: Pixels) -> Self {
+        Self::Length(pixels.into())
+    }
+}
+
+impl From<Rems> for TrackBreadth {
+    fn from(rems: Rems) -> Self {
+        Self::Length(rems.into())
+    }
+}
+
+impl From<TrackBreadth> for TrackSize {
+    fn from(breadth: TrackBreadth) -> Self {
+        match breadth {
+            // Like CSS, a flexible track can't be smaller than its content.
+            TrackBreadth::Fraction(_) => minmax(TrackBreadth::Auto, breadth),
+            _ => minmax(breadth, breadth),
+        }
+    }
+}
+
+impl From<DefiniteLength> for TrackSize {
+    fn from(length: DefiniteLength) -> Self {
+        TrackBreadth::from(length).into()
+    }
+}
+
+impl From<Pixels> for TrackSize {
+    fn from(pixels: Pixels) -> Self {
+        TrackBreadth::from(pixels).into()
+    }
+}
+
+impl From<Rems> for TrackSize {
+    fn from(rems: Rems) -> Self {
+        TrackBreadth::from(rems).into()
+    }
+}
+
+impl From<TrackSize> for GridTrack {
+    fn from(size: TrackSize) -> Self {
+        Self::Single(size)
+    }
+}
+
+impl From<TrackBreadth> for GridTrack {
+    fn from(breadth: TrackBreadth) -> Self {
+        Self::Single(breadth.into())
+    }
+}
+
+impl From<DefiniteLength> for GridTrack {
+    fn from(length: DefiniteLength) -> Self {
+        Self::Single(length.into())
+    }
+}
+
+impl From<Pixels> for GridTrack {
+    fn from(pixels: Pixels) -> Self {
+        Self::Single(pixels.into())
+    }
+}
+
+impl From<Rems> for GridTrack {
+    fn from(rems: Rems) -> Self {
+        Self::Single(rems.into())
+    }
+}
+
 /// The possible values of the box-shadow property
 #[derive(Clone, Debug)]
 pub struct BoxShadow {
@@ -432,14 +641,17 @@ impl Style {
             &self.box_shadow,
         );
 
-        let background_color = self.background.as_ref().and_then(Fill::color);
-        if background_color.map_or(false, |color| !color.is_transparent()) {
-            let mut border_color = background_color.unwrap_or_default();
+        if let Some(background) = self
+            .background
+            .as_ref()
+            .filter(|background| !background.is_transparent())
+        {
+            let mut border_color = background.color().unwrap_or_default();
             border_color.a = 0.;
             cx.paint_quad(quad(
                 bounds,
                 self.corner_radii.to_pixels(bounds.size, rem_size),
-                background_color.unwrap_or_default(),
+                background.clone(),
                 Edges::default(),
                 border_color,
             ));
@@ -553,6 +765,14 @@ impl Default for Style {
             flex_grow: 0.0,
             flex_shrink: 1.0,
             flex_basis: Length::Auto,
+            // Grid
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+            grid_auto_columns: Vec::new(),
+            grid_auto_rows: Vec::new(),
+            grid_auto_flow: GridAutoFlow::Row,
+            grid_row: GridLines::default(),
+            grid_column: GridLines::default(),
             background: None,
             border_color: None,
             corner_radii: Corners::default(),
@@ -594,10 +814,70 @@ pub struct StrikethroughStyle {
 }
 
 /// The kinds of fill that can be applied to a shape.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Fill {
     /// A solid color fill.
     Color(Hsla),
+    /// A gradient whose colors change along a straight line.
+    LinearGradient(LinearGradient),
+    /// A gradient whose colors change outwards from a center point.
+    RadialGradient(RadialGradient),
+}
+
+/// A color at a position along a gradient.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ColorStop {
+    /// The color at this stop.
+    pub color: Hsla,
+    /// The position of this stop along the gradient, between 0 and 1.
+    pub percentage: f32,
+}
+
+/// A gradient whose colors change along a straight line through the center of the filled area.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearGradient {
+    /// The direction of the gradient in degrees. As in CSS, 0 points up and angles grow clockwise.
+    pub angle: f32,
+    /// The colors of the gradient. Only the first four stops are drawn.
+    pub stops: Vec<ColorStop>,
+}
+
+/// A gradient whose colors change outwards from a center point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RadialGradient {
+    /// The center of the gradient, relative to the size of the filled area.
+    pub center: Point<f32>,
+    /// The radius of the gradient, relative to the distance from its center
+    /// to the farthest corner of the filled area.
+    pub radius: f32,
+    /// The colors of the gradient. Only the first four stops are drawn.
+    pub stops: Vec<ColorStop>,
+}
+
+/// Creates a color stop at the given position along a gradient, between 0 and 1.
+pub fn color_stop(color: impl Into<Hsla>, percentage: f32) -> ColorStop {
+    ColorStop {
+        color: color.into(),
+        percentage,
+    }
+}
+
+/// Creates a linear gradient fill with the given angle in degrees, where 0 points up.
+pub fn linear_gradient(angle: f32, stops: impl IntoIterator<Item = ColorStop>) -> Fill {
+    Fill::LinearGradient(LinearGradient {
+        angle,
+        stops: stops.into_iter().collect(),
+    })
+}
+
+/// Creates a radial gradient fill that starts at the center of the filled area
+/// and ends at its farthest corner.
+pub fn radial_gradient(stops: impl IntoIterator<Item = ColorStop>) -> Fill {
+    Fill::RadialGradient(RadialGradient {
+        center: point(0.5, 0.5),
+        radius: 1.,
+        stops: stops.into_iter().collect(),
+    })
 }
 
 impl Fill {
@@ -605,6 +885,18 @@ impl Fill {
     pub fn color(&self) -> Option<Hsla> {
         match self {
             Fill::Color(color) => Some(*color),
+            Fill::LinearGradient(_) | Fill::RadialGradient(_) => None,
+        }
+    }
+
+    /// Returns whether this fill doesn't paint anything.
+    pub fn is_transparent(&self) -> bool {
+        match self {
+            Fill::Color(color) => color.is_transparent(),
+            Fill::LinearGradient(LinearGradient { stops, .. })
+            | Fill::RadialGradient(RadialGradient { stops, .. }) => {
+                stops.iter().all(|stop| stop.color.is_transparent())
+            }
         }
     }
 }
@@ -627,6 +919,57 @@ impl From<Rgba> for Fill {
     }
 }
 
+impl From<LinearGradient> for Fill {
+    fn from(gradient: LinearGradient) -> Self {
+        Self::LinearGradient(gradient)
+    }
+}
+
+impl From<RadialGradient> for Fill {
+    fn from(gradient: RadialGradient) -> Self {
+        Self::RadialGradient(gradient)
+    }
+}
+
+impl From<&Fill> for Background {
+    fn from(fill: &Fill) -> Self {
+        let (kind, stops) = match fill {
+            Fill::Color(color) => return (*color).into(),
+            Fill::LinearGradient(gradient) => (BackgroundKind::LinearGradient, &gradient.stops),
+            Fill::RadialGradient(gradient) => (BackgroundKind::RadialGradient, &gradient.stops),
+        };
+
+        let mut stops = stops
+            .iter()
+            .take(MAX_GRADIENT_STOPS)
+            .copied()
+            .collect::<SmallVec<[ColorStop; MAX_GRADIENT_STOPS]>>();
+        if stops.is_empty() {
+            return Hsla::transparent_black().into();
+        }
+        stops.sort_by(|a, b| a.percentage.total_cmp(&b.percentage));
+
+        let mut background = Background {
+            kind,
+            stop_count: stops.len() as u32,
+            ..Default::default()
+        };
+        for (ix, stop) in stops.iter().enumerate() {
+            background.colors[ix] = stop.color;
+            background.stops[ix] = stop.percentage.clamp(0., 1.);
+        }
+        match fill {
+            Fill::LinearGradient(gradient) => background.angle = gradient.angle.to_radians(),
+            Fill::RadialGradient(gradient) => {
+                background.center = gradient.center;
+                background.radius = gradient.radius;
+            }
+            Fill::Color(_) => {}
+        }
+        background
+    }
+}
+
 impl From<TextStyle> for HighlightStyle {
     fn from(other: TextStyle) -> Self {
         Self::from(&other)
@@ -782,6 +1125,38 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_gradient_background() {
+        let background = Background::from(&linear_gradient(
+            90.,
+            [
+                color_stop(blue(), 1.5),
+                color_stop(red(), 0.),
+                color_stop(green(), 0.5),
+                color_stop(yellow(), 0.75),
+                color_stop(black(), 0.8),
+            ],
+        ));
+        assert_eq!(background.kind, BackgroundKind::LinearGradient);
+        assert_eq!(background.angle, 90f32.to_radians());
+        assert_eq!(background.stop_count, 4);
+        assert_eq!(background.colors, [red(), green(), yellow(), blue()]);
+        assert_eq!(background.stops, [0., 0.5, 0.75, 1.]);
+
+        let background = Background::from(&radial_gradient([color_stop(red(), 0.)]));
+        assert_eq!(background.kind, BackgroundKind::RadialGradient);
+        assert_eq!(background.center, point(0.5, 0.5));
+        assert_eq!(background.radius, 1.);
+        assert_eq!(background.stop_count, 1);
+
+        let background = Background::from(&Fill::Color(green()));
+        assert_eq!(background.kind, BackgroundKind::Solid);
+        assert_eq!(background.colors[0], green());
+
+        assert!(linear_gradient(0., []).is_transparent());
+        assert!(!radial_gradient([color_stop(red(), 0.)]).is_transparent());
+    }
+
     #[test]
     fn test_combine_highlights() {
         assert_eq!(
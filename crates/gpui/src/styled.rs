@@ -1,8 +1,9 @@
 use crate::TextStyleRefinement;
 use crate::{
-    self as gpui, px, relative, rems, AbsoluteLength, AlignItems, CursorStyle, DefiniteLength,
-    Fill, FlexDirection, FlexWrap, Font, FontStyle, FontWeight, Hsla, JustifyContent, Length,
-    SharedString, StyleRefinement, WhiteSpace,
+    self as gpui, fr, linear_gradient, minmax, px, radial_gradient, relative, rems, AbsoluteLength,
+    AlignItems, ColorStop, CursorStyle, DefiniteLength, Fill, FlexDirection, FlexWrap, Font,
+    FontStyle, FontWeight, GridAutoFlow, GridPlacement, GridTrack, Hsla, JustifyContent, Length,
+    SharedString, StyleRefinement, TrackSize, WhiteSpace,
 };
 pub use gpui_macros::{
     box_shadow_style_methods, cursor_style_methods, margin_style_methods, overflow_style_methods,
@@ -39,6 +40,13 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the display type of the element to `grid`.
+    /// [Docs](https://tailwindcss.com/docs/display)
+    fn grid(mut self) -> Self {
+        self.style().display = Some(Display::Grid);
+        self
+    }
+
     /// Sets the whitespace of the element to `normal`.
     /// [Docs](https://tailwindcss.com/docs/whitespace#normal)
     fn whitespace_normal(mut self) -> Self {
@@ -169,6 +177,144 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the grid to have the given number of columns, all of the same width.
+    /// [Docs](https://tailwindcss.com/docs/grid-template-columns)
+    fn grid_cols(mut self, count: u16) -> Self {
+        self.style().grid_template_columns =
+            Some(vec![GridTrack::repeat(count, [minmax(px(0.), fr(1.))])]);
+        self
+    }
+
+    /// Sets the grid to have the given number of rows, all of the same height.
+    /// [Docs](https://tailwindcss.com/docs/grid-template-rows)
+    fn grid_rows(mut self, count: u16) -> Self {
+        self.style().grid_template_rows =
+            Some(vec![GridTrack::repeat(count, [minmax(px(0.), fr(1.))])]);
+        self
+    }
+
+    /// Sets the sizes of the columns of the grid.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-columns)
+    fn grid_template_columns(mut self, tracks: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.style().grid_template_columns = Some(tracks.into_iter().collect());
+        self
+    }
+
+    /// Sets the sizes of the rows of the grid.
+    /// [Docs](https://developer.mozilla.org/en-US/docs/Web/CSS/grid-template-rows)
+    fn grid_template_rows(mut self, tracks: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.style().grid_template_rows = Some(tracks.into_iter().collect());
+        self
+    }
+
+    /// Sets the size of the columns the grid creates for items placed outside of its template.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-columns)
+    fn grid_auto_columns(mut self, size: impl Into<TrackSize>) -> Self {
+        self.style().grid_auto_columns = Some(vec![size.into()]);
+        self
+    }
+
+    /// Sets the size of the rows the grid creates for items placed outside of its template.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-rows)
+    fn grid_auto_rows(mut self, size: impl Into<TrackSize>) -> Self {
+        self.style().grid_auto_rows = Some(vec![size.into()]);
+        self
+    }
+
+    /// Sets the grid to place items without an explicit placement by filling each row in turn.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-flow)
+    fn grid_flow_row(mut self) -> Self {
+        self.style().grid_auto_flow = Some(GridAutoFlow::Row);
+        self
+    }
+
+    /// Sets the grid to place items without an explicit placement by filling each column in turn.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-flow)
+    fn grid_flow_col(mut self) -> Self {
+        self.style().grid_auto_flow = Some(GridAutoFlow::Column);
+        self
+    }
+
+    /// Sets the grid to place items without an explicit placement by filling each row in turn,
+    /// filling in holes left by earlier items when later items fit.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-flow)
+    fn grid_flow_row_dense(mut self) -> Self {
+        self.style().grid_auto_flow = Some(GridAutoFlow::RowDense);
+        self
+    }
+
+    /// Sets the grid to place items without an explicit placement by filling each column in turn,
+    /// filling in holes left by earlier items when later items fit.
+    /// [Docs](https://tailwindcss.com/docs/grid-auto-flow)
+    fn grid_flow_col_dense(mut self) -> Self {
+        self.style().grid_auto_flow = Some(GridAutoFlow::ColumnDense);
+        self
+    }
+
+    /// Sets the grid item to span the given number of columns.
+    /// [Docs](https://tailwindcss.com/docs/grid-column#spanning-columns)
+    fn col_span(mut self, count: u16) -> Self {
+        self.style().grid_column.start = Some(GridPlacement::Span(count));
+        self.style().grid_column.end = Some(GridPlacement::Auto);
+        self
+    }
+
+    /// Sets the grid item to span all of the columns of the grid.
+    /// [Docs](https://tailwindcss.com/docs/grid-column#spanning-columns)
+    fn col_span_full(mut self) -> Self {
+        self.style().grid_column.start = Some(GridPlacement::Line(1));
+        self.style().grid_column.end = Some(GridPlacement::Line(-1));
+        self
+    }
+
+    /// Sets the grid item to start at the given column line. Lines are numbered from 1,
+    /// and negative numbers count backwards from the last line.
+    /// [Docs](https://tailwindcss.com/docs/grid-column#starting-and-ending-lines)
+    fn col_start(mut self, line: i16) -> Self {
+        self.style().grid_column.start = Some(GridPlacement::Line(line));
+        self
+    }
+
+    /// Sets the grid item to end at the given column line. Lines are numbered from 1,
+    /// and negative numbers count backwards from the last line.
+    /// [Docs](https://tailwindcss.com/docs/grid-column#starting-and-ending-lines)
+    fn col_end(mut self, line: i16) -> Self {
+        self.style().grid_column.end = Some(GridPlacement::Line(line));
+        self
+    }
+
+    /// Sets the grid item to span the given number of rows.
+    /// [Docs](https://tailwindcss.com/docs/grid-row#spanning-rows)
+    fn row_span(mut self, count: u16) -> Self {
+        self.style().grid_row.start = Some(GridPlacement::Span(count));
+        self.style().grid_row.end = Some(GridPlacement::Auto);
+        self
+    }
+
+    /// Sets the grid item to span all of the rows of the grid.
+    /// [Docs](https://tailwindcss.com/docs/grid-row#spanning-rows)
+    fn row_span_full(mut self) -> Self {
+        self.style().grid_row.start = Some(GridPlacement::Line(1));
+        self.style().grid_row.end = Some(GridPlacement::Line(-1));
+        self
+    }
+
+    /// Sets the grid item to start at the given row line. Lines are numbered from 1,
+    /// and negative numbers count backwards from the last line.
+    /// [Docs](https://tailwindcss.com/docs/grid-row#starting-and-ending-lines)
+    fn row_start(mut self, line: i16) -> Self {
+        self.style().grid_row.start = Some(GridPlacement::Line(line));
+        self
+    }
+
+    /// Sets the grid item to end at the given row line. Lines are numbered from 1,
+    /// and negative numbers count backwards from the last line.
+    /// [Docs](https://tailwindcss.com/docs/grid-row#starting-and-ending-lines)
+    fn row_end(mut self, line: i16) -> Self {
+        self.style().grid_row.end = Some(GridPlacement::Line(line));
+        self
+    }
+
     /// Sets the element to align flex items to the start of the container's cross axis.
     /// [Docs](https://tailwindcss.com/docs/align-items#start)
     fn items_start(mut self) -> Self {
@@ -303,6 +449,26 @@ pub trait Styled: Sized {
         self
     }
 
+    /// Sets the background of the element to a linear gradient.
+    /// The angle is in degrees: 0 points up and angles grow clockwise.
+    fn bg_linear_gradient(mut self, angle: f32, stops: impl IntoIterator<Item = ColorStop>) -> Self
+    where
+        Self: Sized,
+    {
+        self.style().background = Some(linear_gradient(angle, stops));
+        self
+    }
+
+    /// Sets the background of the element to a radial gradient that starts
+    /// at its center and ends at its farthest corner.
+    fn bg_radial_gradient(mut self, stops: impl IntoIterator<Item = ColorStop>) -> Self
+    where
+        Self: Sized,
+    {
+        self.style().background = Some(radial_gradient(stops));
+        self
+    }
+
     /// Sets the border color of the element.
     fn border_color<C>(mut self, border_color: C) -> Self
     where
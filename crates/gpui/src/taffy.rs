@@ -1,13 +1,18 @@
 use crate::{
-    AbsoluteLength, Bounds, DefiniteLength, Edges, Length, Pixels, Point, Size, Style,
-    WindowContext,
+    AbsoluteLength, Bounds, DefiniteLength, Edges, GridLines, GridPlacement, GridRepetition,
+    GridTrack, Length, Pixels, Point, Size, Style, TrackBreadth, TrackSize, WindowContext,
 };
 use collections::{FxHashMap, FxHashSet};
 use smallvec::SmallVec;
 use std::fmt::Debug;
 use taffy::{
-    geometry::{Point as TaffyPoint, Rect as TaffyRect, Size as TaffySize},
-    style::AvailableSpace as TaffyAvailableSpace,
+    geometry::{Line, MinMax, Point as TaffyPoint, Rect as TaffyRect, Size as TaffySize},
+    style::{
+        AvailableSpace as TaffyAvailableSpace, GridPlacement as TaffyGridPlacement,
+        GridTrackRepetition, MaxTrackSizingFunction, MinTrackSizingFunction,
+        NonRepeatedTrackSizingFunction, TrackSizingFunction,
+    },
+    style_helpers::{line, span},
     tree::NodeId,
     TaffyTree, TraversePartialTree as _,
 };
@@ -271,7 +276,102 @@ impl ToTaffy<taffy::style::Style> for Style {
             flex_basis: self.flex_basis.to_taffy(rem_size),
             flex_grow: self.flex_grow,
             flex_shrink: self.flex_shrink,
-            ..Default::default() // Ignore grid properties for now
+            grid_template_columns: self
+                .grid_template_columns
+                .iter()
+                .map(|track| track.to_taffy(rem_size))
+                .collect(),
+            grid_template_rows: self
+                .grid_template_rows
+                .iter()
+                .map(|track| track.to_taffy(rem_size))
+                .collect(),
+            grid_auto_columns: self
+                .grid_auto_columns
+                .iter()
+                .map(|size| size.to_taffy(rem_size))
+                .collect(),
+            grid_auto_rows: self
+                .grid_auto_rows
+                .iter()
+                .map(|size| size.to_taffy(rem_size))
+                .collect(),
+            grid_auto_flow: self.grid_auto_flow,
+            grid_row: self.grid_row.into(),
+            grid_column: self.grid_column.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl ToTaffy<TrackSizingFunction> for GridTrack {
+    fn to_taffy(&self, rem_size: Pixels) -> TrackSizingFunction {
+        match self {
+            GridTrack::Single(size) => TrackSizingFunction::Single(size.to_taffy(rem_size)),
+            GridTrack::Repeat(repetition, sizes) => TrackSizingFunction::Repeat(
+                match repetition {
+                    GridRepetition::Count(count) => GridTrackRepetition::Count(*count),
+                    GridRepetition::AutoFill => GridTrackRepetition::AutoFill,
+                    GridRepetition::AutoFit => GridTrackRepetition::AutoFit,
+                },
+                sizes.iter().map(|size| size.to_taffy(rem_size)).collect(),
+            ),
+        }
+    }
+}
+
+impl ToTaffy<NonRepeatedTrackSizingFunction> for TrackSize {
+    fn to_taffy(&self, rem_size: Pixels) -> NonRepeatedTrackSizingFunction {
+        MinMax {
+            min: self.min.to_taffy(rem_size),
+            max: self.max.to_taffy(rem_size),
+        }
+    }
+}
+
+impl ToTaffy<MinTrackSizingFunction> for TrackBreadth {
+    fn to_taffy(&self, rem_size: Pixels) -> MinTrackSizingFunction {
+        match self {
+            TrackBreadth::Length(length) => {
+                MinTrackSizingFunction::Fixed(length.to_taffy(rem_size))
+            }
+            // Flexible minimums aren't valid in CSS either, and are treated as `auto`.
+            TrackBreadth::Fraction(_) | TrackBreadth::Auto => MinTrackSizingFunction::Auto,
+            TrackBreadth::MinContent => MinTrackSizingFunction::MinContent,
+            TrackBreadth::MaxContent => MinTrackSizingFunction::MaxContent,
+        }
+    }
+}
+
+impl ToTaffy<MaxTrackSizingFunction> for TrackBreadth {
+    fn to_taffy(&self, rem_size: Pixels) -> MaxTrackSizingFunction {
+        match self {
+            TrackBreadth::Length(length) => {
+                MaxTrackSizingFunction::Fixed(length.to_taffy(rem_size))
+            }
+            TrackBreadth::Fraction(fraction) => MaxTrackSizingFunction::Fraction(*fraction),
+            TrackBreadth::Auto => MaxTrackSizingFunction::Auto,
+            TrackBreadth::MinContent => MaxTrackSizingFunction::MinContent,
+            TrackBreadth::MaxContent => MaxTrackSizingFunction::MaxContent,
+        }
+    }
+}
+
+impl From<GridPlacement> for TaffyGridPlacement {
+    fn from(placement: GridPlacement) -> Self {
+        match placement {
+            GridPlacement::Auto => TaffyGridPlacement::Auto,
+            GridPlacement::Line(index) => line(index),
+            GridPlacement::Span(count) => span(count),
+        }
+    }
+}
+
+impl From<GridLines> for Line<TaffyGridPlacement> {
+    fn from(lines: GridLines) -> Self {
+        Line {
+            start: lines.start.into(),
+            end: lines.end.into(),
         }
     }
 }
@@ -498,3 +598,63 @@ impl From<Size<Pixels>> for Size<AvailableSpace> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fr, px, StyleRefinement, Styled as _};
+    use refineable::Refineable as _;
+
+    #[test]
+    fn test_grid_layout() {
+        let rem_size = px(16.);
+        let mut taffy = TaffyTree::<()>::new();
+        let mut add_node = |refinement: StyleRefinement, children: &[NodeId]| {
+            let mut style = Style::default();
+            style.refine(&refinement);
+            taffy
+                .new_with_children(style.to_taffy(rem_size), children)
+                .unwrap()
+        };
+
+        let first = add_node(StyleRefinement::default().h(px(10.)), &[]);
+        let wide = add_node(StyleRefinement::default().h(px(10.)).col_span(2), &[]);
+        let last = add_node(
+            StyleRefinement::default()
+                .h(px(10.))
+                .col_start(-2)
+                .row_start(2),
+            &[],
+        );
+        let container = add_node(
+            StyleRefinement::default()
+                .grid()
+                .grid_template_columns([
+                    px(50.).into(),
+                    fr(1.).into(),
+                    GridTrack::repeat(2, [px(20.).into()]),
+                ])
+                .w(px(200.))
+                .gap_x(px(10.))
+                .gap_y(px(5.)),
+            &[first, wide, last],
+        );
+        taffy
+            .compute_layout(container, TaffySize::MAX_CONTENT)
+            .unwrap();
+
+        let bounds = |node| {
+            let layout = taffy.layout(node).unwrap();
+            (
+                layout.location.x,
+                layout.location.y,
+                layout.size.width,
+                layout.size.height,
+            )
+        };
+        // The flexible column takes the width left over by the fixed columns and the gaps.
+        assert_eq!(bounds(first), (0., 0., 50., 10.));
+        assert_eq!(bounds(wide), (60., 0., 110., 10.));
+        assert_eq!(bounds(last), (180., 15., 20., 10.));
+    }
+}
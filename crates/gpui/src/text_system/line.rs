@@ -4,7 +4,7 @@ use crate::{
 };
 use derive_more::{Deref, DerefMut};
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::{ops::Range, sync::Arc};
 
 /// Set the text decoration for a run of text.
 #[derive(Debug, Clone)]
@@ -119,9 +119,8 @@ fn paint_line(
     cx.paint_layer(line_bounds, |cx| {
         let padding_top = (line_height - layout.ascent - layout.descent) / 2.;
         let baseline_offset = point(px(0.), padding_top + layout.ascent);
-        let mut decoration_runs = decoration_runs.iter();
         let mut wraps = wrap_boundaries.iter().peekable();
-        let mut run_end = 0;
+        let mut run_range = 0..0;
         let mut color = black();
         let mut current_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
         let mut current_strikethrough: Option<(Point<Pixels>, StrikethroughStyle)> = None;
@@ -178,8 +177,12 @@ fn paint_line(
                 let mut finished_background: Option<(Point<Pixels>, Hsla)> = None;
                 let mut finished_underline: Option<(Point<Pixels>, UnderlineStyle)> = None;
                 let mut finished_strikethrough: Option<(Point<Pixels>, StrikethroughStyle)> = None;
-                if glyph.index >= run_end {
-                    if let Some(style_run) = decoration_runs.next() {
+                // Glyphs are in visual order, so in bidirectional text the decoration run
+                // for a glyph isn't necessarily the one following the previous glyph's run.
+                if !run_range.contains(&glyph.index) {
+                    if let Some((style_run_range, style_run)) =
+                        decoration_run_for_index(decoration_runs, glyph.index)
+                    {
                         if let Some((_, background_color)) = &mut current_background {
                             if style_run.background_color.as_ref() != Some(background_color) {
                                 finished_background = current_background.take();
@@ -229,10 +232,10 @@ fn paint_line(
                             ));
                         }
 
-                        run_end += style_run.len as usize;
+                        run_range = style_run_range;
                         color = style_run.color;
                     } else {
-                        run_end = layout.len;
+                        run_range = glyph.index..layout.len;
                         finished_background = current_background.take();
                         finished_underline = current_underline.take();
                         finished_strikethrough = current_strikethrough.take();
@@ -328,3 +331,19 @@ fn paint_line(
         Ok(())
     })
 }
+
+/// Returns the decoration run containing the given index, along with its range.
+fn decoration_run_for_index(
+    decoration_runs: &[DecorationRun],
+    index: usize,
+) -> Option<(Range<usize>, &DecorationRun)> {
+    let mut run_start = 0;
+    for run in decoration_runs {
+        let run_end = run_start + run.len as usize;
+        if index < run_end {
+            return Some((run_start..run_end, run));
+        }
+        run_start = run_end;
+    }
+    None
+}
@@ -1,13 +1,15 @@
 use crate::{point, px, FontId, GlyphId, Pixels, PlatformTextSystem, Point, Size};
 use collections::FxHashMap;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 use std::{
     borrow::Borrow,
     hash::{Hash, Hasher},
+    iter,
     ops::Range,
     sync::Arc,
 };
+use unicode_bidi::{BidiInfo, Level};
 
 /// A laid out and styled line of text
 #[derive(Default, Debug)]
@@ -33,6 +35,9 @@ pub struct ShapedRun {
     pub font_id: FontId,
     /// The glyphs that make up this run
     pub glyphs: SmallVec<[ShapedGlyph; 8]>,
+    /// Whether this run is right-to-left. Glyphs are always stored in visual order,
+    /// so the indices of the glyphs in a right-to-left run decrease from left to right.
+    pub is_rtl: bool,
 }
 
 /// A single glyph, ready to paint.
@@ -52,6 +57,12 @@ pub struct ShapedGlyph {
 }
 
 impl LineLayout {
+    /// Whether this line contains any right-to-left runs, in which case its glyphs
+    /// aren't in the same order as the text.
+    pub fn has_rtl(&self) -> bool {
+        self.runs.iter().any(|run| run.is_rtl)
+    }
+
     /// The index for the character at the given x coordinate
     pub fn index_for_x(&self, x: Pixels) -> Option<usize> {
         if x >= self.width {
@@ -71,6 +82,10 @@ impl LineLayout {
     /// closest_index_for_x returns the character boundary closest to the given x coordinate
     /// (e.g. to handle aligning up/down arrow keys)
     pub fn closest_index_for_x(&self, x: Pixels) -> usize {
+        if self.has_rtl() {
+            return self.bidi_closest_index_for_x(x);
+        }
+
         let mut prev_index = 0;
         let mut prev_x = px(0.);
 
@@ -91,8 +106,13 @@ impl LineLayout {
         self.len
     }
 
-    /// The x position of the character at the given index
+    /// The x position of the character at the given index. In a right-to-left run this is
+    /// the right edge of the character, where a cursor placed before it is drawn.
     pub fn x_for_index(&self, index: usize) -> Pixels {
+        if self.has_rtl() {
+            return self.bidi_x_for_index(index);
+        }
+
         for run in &self.runs {
             for glyph in &run.glyphs {
                 if glyph.index >= index {
@@ -103,6 +123,135 @@ impl LineLayout {
         self.width
     }
 
+    /// The index of the caret position visually to the left of the caret at the given index,
+    /// or `None` if that caret is already at the left edge of the line.
+    pub fn visual_index_left_of(&self, index: usize) -> Option<usize> {
+        let x = self.x_for_index(index);
+        self.caret_stops()
+            .into_iter()
+            .rev()
+            .find(|(stop_x, _)| *stop_x < x)
+            .map(|(_, index)| index)
+    }
+
+    /// The index of the caret position visually to the right of the caret at the given index,
+    /// or `None` if that caret is already at the right edge of the line.
+    pub fn visual_index_right_of(&self, index: usize) -> Option<usize> {
+        let x = self.x_for_index(index);
+        self.caret_stops()
+            .into_iter()
+            .find(|(stop_x, _)| *stop_x > x)
+            .map(|(_, index)| index)
+    }
+
+    /// The distinct caret positions in this line from left to right, along with the lowest
+    /// index drawn at each of them.
+    fn caret_stops(&self) -> SmallVec<[(Pixels, usize); 32]> {
+        let mut stops = self
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(|glyph| glyph.index))
+            .chain([self.len])
+            .map(|index| (self.x_for_index(index), index))
+            .collect::<SmallVec<[(Pixels, usize); 32]>>();
+        stops.sort_unstable();
+        stops.dedup_by_key(|(x, _)| *x);
+        stops
+    }
+
+    /// The horizontal extents covered by the characters in the given range, from left to right.
+    /// The range always covers a single extent in left-to-right text, but bidirectional text
+    /// can place a contiguous range of characters in several disjoint extents.
+    pub fn x_ranges_for_index_range(&self, range: Range<usize>) -> SmallVec<[Range<Pixels>; 1]> {
+        if !self.has_rtl() {
+            return smallvec![self.x_for_index(range.start)..self.x_for_index(range.end)];
+        }
+
+        let mut x_ranges = SmallVec::<[Range<Pixels>; 1]>::new();
+        for (_, glyph, right_x) in self.glyphs_with_right_edges() {
+            if range.contains(&glyph.index) {
+                match x_ranges.last_mut() {
+                    Some(x_range) if x_range.end == glyph.position.x => x_range.end = right_x,
+                    _ => x_ranges.push(glyph.position.x..right_x),
+                }
+            }
+        }
+        x_ranges
+    }
+
+    fn bidi_x_for_index(&self, index: usize) -> Pixels {
+        let mut closest: Option<(usize, Pixels)> = None;
+        for (run, glyph, right_x) in self.glyphs_with_right_edges() {
+            if glyph.index < index {
+                continue;
+            }
+            let is_closer = closest.map_or(true, |(closest_index, _)| {
+                // Of the glyphs in a right-to-left cluster, the rightmost one is last.
+                glyph.index < closest_index || (glyph.index == closest_index && run.is_rtl)
+            });
+            if is_closer {
+                let x = if run.is_rtl {
+                    right_x
+                } else {
+                    glyph.position.x
+                };
+                closest = Some((glyph.index, x));
+            }
+        }
+        closest.map_or(self.width, |(_, x)| x)
+    }
+
+    fn bidi_closest_index_for_x(&self, x: Pixels) -> usize {
+        let mut cluster_starts = self
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(|glyph| glyph.index))
+            .collect::<SmallVec<[usize; 32]>>();
+        cluster_starts.sort_unstable();
+        cluster_starts.dedup();
+        let cluster_end = |index: usize| {
+            let ix = cluster_starts.partition_point(|start| *start <= index);
+            cluster_starts.get(ix).copied().unwrap_or(self.len)
+        };
+
+        let mut closest_index = self.len;
+        let mut closest_distance = Pixels::MAX;
+        for (run, glyph, right_x) in self.glyphs_with_right_edges() {
+            let end = cluster_end(glyph.index);
+            let (left_index, right_index) = if run.is_rtl {
+                (end, glyph.index)
+            } else {
+                (glyph.index, end)
+            };
+            for (edge_x, index) in [(glyph.position.x, left_index), (right_x, right_index)] {
+                let distance = (edge_x - x).abs();
+                if distance < closest_distance {
+                    closest_distance = distance;
+                    closest_index = index;
+                }
+            }
+        }
+        closest_index
+    }
+
+    /// Iterates over the glyphs in visual order, along with the x coordinate of their right edge.
+    fn glyphs_with_right_edges(
+        &self,
+    ) -> impl Iterator<Item = (&ShapedRun, &ShapedGlyph, Pixels)> + '_ {
+        let mut glyphs = self
+            .runs
+            .iter()
+            .flat_map(|run| run.glyphs.iter().map(move |glyph| (run, glyph)))
+            .peekable();
+        iter::from_fn(move || {
+            let (run, glyph) = glyphs.next()?;
+            let right_x = glyphs
+                .peek()
+                .map_or(self.width, |(_, next_glyph)| next_glyph.position.x);
+            Some((run, glyph, right_x))
+        })
+    }
+
     /// The corresponding Font at the given index
     pub fn font_id_for_index(&self, index: usize) -> Option<FontId> {
         for run in &self.runs {
@@ -486,7 +635,12 @@ impl LineLayoutCache {
             current_frame.used_lines.push(key);
             layout
         } else {
-            let layout = Arc::new(self.platform_text_system.layout_line(text, font_size, runs));
+            let layout = Arc::new(layout_bidi_line(
+                self.platform_text_system.as_ref(),
+                text,
+                font_size,
+                runs,
+            ));
             let key = Arc::new(CacheKey {
                 text: text.into(),
                 font_size,
@@ -500,6 +654,77 @@ impl LineLayoutCache {
     }
 }
 
+/// Lays out a line of text, applying the Unicode bidirectional algorithm with a left-to-right
+/// base direction. Each directional run is shaped on its own, and the resulting runs are then
+/// placed in visual order.
+fn layout_bidi_line(
+    platform_text_system: &dyn PlatformTextSystem,
+    text: &str,
+    font_size: Pixels,
+    runs: &[FontRun],
+) -> LineLayout {
+    if text.is_ascii() {
+        return platform_text_system.layout_line(text, font_size, runs);
+    }
+    let bidi_info = BidiInfo::new(text, Some(Level::ltr()));
+    if !bidi_info.levels.iter().any(|level| level.is_rtl()) {
+        return platform_text_system.layout_line(text, font_size, runs);
+    }
+
+    let mut layout = LineLayout {
+        font_size,
+        len: text.len(),
+        ..Default::default()
+    };
+    for paragraph in &bidi_info.paragraphs {
+        let (levels, level_runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+        for level_run in level_runs {
+            if level_run.is_empty() {
+                continue;
+            }
+
+            let is_rtl = levels[level_run.start].is_rtl();
+            let run_layout = platform_text_system.layout_line(
+                &text[level_run.clone()],
+                font_size,
+                &font_runs_in_range(runs, level_run.clone()),
+            );
+            for mut shaped_run in run_layout.runs {
+                for glyph in &mut shaped_run.glyphs {
+                    glyph.position.x += layout.width;
+                    glyph.index += level_run.start;
+                }
+                shaped_run.is_rtl = is_rtl;
+                layout.runs.push(shaped_run);
+            }
+            layout.width += run_layout.width;
+            layout.ascent = layout.ascent.max(run_layout.ascent);
+            layout.descent = layout.descent.max(run_layout.descent);
+        }
+    }
+    layout
+}
+
+/// Returns the portions of the given font runs that overlap the given byte range,
+/// relative to the start of that range.
+fn font_runs_in_range(runs: &[FontRun], range: Range<usize>) -> SmallVec<[FontRun; 1]> {
+    let mut runs_in_range = SmallVec::new();
+    let mut run_start = 0;
+    for run in runs {
+        let run_end = run_start + run.len;
+        let start = run_start.max(range.start);
+        let end = run_end.min(range.end);
+        if start < end {
+            runs_in_range.push(FontRun {
+                len: end - start,
+                font_id: run.font_id,
+            });
+        }
+        run_start = run_end;
+    }
+    runs_in_range
+}
+
 /// A run of text with a single font.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct FontRun {
@@ -575,3 +800,150 @@ impl<'a> AsCacheKeyRef for CacheKeyRef<'a> {
         *self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as gpui, font, TestAppContext};
+
+    fn glyph(index: usize, x: f32) -> ShapedGlyph {
+        ShapedGlyph {
+            id: GlyphId(0),
+            position: point(px(x), px(0.)),
+            index,
+            is_emoji: false,
+        }
+    }
+
+    #[test]
+    fn test_bidi_line_layout() {
+        // "abאב", where the Hebrew letters are laid out right-to-left.
+        let layout = LineLayout {
+            font_size: px(16.),
+            width: px(40.),
+            runs: vec![
+                ShapedRun {
+                    font_id: FontId(0),
+                    glyphs: SmallVec::from_vec(vec![glyph(0, 0.), glyph(1, 10.)]),
+                    is_rtl: false,
+                },
+                ShapedRun {
+                    font_id: FontId(0),
+                    glyphs: SmallVec::from_vec(vec![glyph(4, 20.), glyph(2, 30.)]),
+                    is_rtl: true,
+                },
+            ],
+            len: 6,
+            ..Default::default()
+        };
+
+        assert!(layout.has_rtl());
+        assert_eq!(layout.x_for_index(0), px(0.));
+        assert_eq!(layout.x_for_index(1), px(10.));
+        assert_eq!(layout.x_for_index(2), px(40.));
+        assert_eq!(layout.x_for_index(4), px(30.));
+        assert_eq!(layout.x_for_index(6), px(40.));
+
+        assert_eq!(layout.index_for_x(px(35.)), Some(2));
+        assert_eq!(layout.index_for_x(px(25.)), Some(4));
+        assert_eq!(layout.closest_index_for_x(px(12.)), 1);
+        assert_eq!(layout.closest_index_for_x(px(31.)), 4);
+        assert_eq!(layout.closest_index_for_x(px(39.)), 2);
+
+        assert_eq!(
+            layout.x_ranges_for_index_range(1..4).as_slice(),
+            &[px(10.)..px(20.), px(30.)..px(40.)]
+        );
+        assert_eq!(
+            layout.x_ranges_for_index_range(2..6).as_slice(),
+            &[px(20.)..px(40.)]
+        );
+
+        assert_eq!(layout.visual_index_right_of(0), Some(1));
+        assert_eq!(layout.visual_index_right_of(1), Some(4));
+        assert_eq!(layout.visual_index_right_of(4), Some(2));
+        assert_eq!(layout.visual_index_right_of(2), None);
+        assert_eq!(layout.visual_index_right_of(6), None);
+        assert_eq!(layout.visual_index_left_of(6), Some(4));
+        assert_eq!(layout.visual_index_left_of(2), Some(4));
+        assert_eq!(layout.visual_index_left_of(4), Some(1));
+        assert_eq!(layout.visual_index_left_of(1), Some(0));
+        assert_eq!(layout.visual_index_left_of(0), None);
+    }
+
+    #[crate::test]
+    fn test_platform_shaper_reports_rtl_runs(cx: &mut TestAppContext) {
+        let text_system = cx.text_system().clone();
+        let font_id = text_system.resolve_font(&font("Helvetica"));
+        let runs = [FontRun { len: 6, font_id }];
+
+        // "aאבc", where "א" and "ב" are two bytes long.
+        let layout = text_system
+            .platform_text_system
+            .layout_line("aאבc", px(16.), &runs[..]);
+        let glyphs = layout
+            .runs
+            .iter()
+            .flat_map(|run| {
+                run.glyphs
+                    .iter()
+                    .map(move |glyph| (glyph.index, run.is_rtl))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            glyphs,
+            [(0, false), (3, true), (1, true), (5, false)],
+            "glyphs should be in visual order, with the Hebrew letters reversed"
+        );
+
+        let layout = LineLayoutCache::new(text_system.platform_text_system.clone()).layout_line(
+            "aאבc",
+            px(16.),
+            &runs[..],
+        );
+        assert!(layout.has_rtl());
+        assert!(layout.x_for_index(1) > layout.x_for_index(3));
+        assert_eq!(layout.visual_index_right_of(0), Some(3));
+        assert_eq!(layout.visual_index_right_of(3), Some(1));
+        assert_eq!(layout.visual_index_right_of(1), Some(6));
+        assert_eq!(layout.visual_index_right_of(6), None);
+        assert_eq!(layout.visual_index_left_of(6), Some(1));
+        assert_eq!(layout.visual_index_left_of(1), Some(3));
+        assert_eq!(layout.visual_index_left_of(3), Some(0));
+        assert_eq!(layout.visual_index_left_of(0), None);
+    }
+
+    #[test]
+    fn test_font_runs_in_range() {
+        let runs = [
+            FontRun {
+                len: 3,
+                font_id: FontId(0),
+            },
+            FontRun {
+                len: 4,
+                font_id: FontId(1),
+            },
+        ];
+        assert_eq!(
+            font_runs_in_range(&runs, 2..5).as_slice(),
+            &[
+                FontRun {
+                    len: 1,
+                    font_id: FontId(0),
+                },
+                FontRun {
+                    len: 2,
+                    font_id: FontId(1),
+                },
+            ]
+        );
+        assert_eq!(
+            font_runs_in_range(&runs, 3..7).as_slice(),
+            &[FontRun {
+                len: 4,
+                font_id: FontId(1),
+            }]
+        );
+    }
+}
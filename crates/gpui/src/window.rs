@@ -1,17 +1,17 @@
 use crate::{
-    hash, point, prelude::*, px, size, transparent_black, Action, AnyDrag, AnyElement, AnyTooltip,
-    AnyView, AppContext, Arena, Asset, AsyncWindowContext, AvailableSpace, Bounds, BoxShadow,
-    Context, Corners, CursorStyle, Decorations, DevicePixels, DispatchActionListener,
-    DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity, EntityId, EventEmitter,
-    FileDropEvent, Flatten, FontId, Global, GlobalElementId, GlyphId, Hsla, ImageData,
-    InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent, KeyMatch, KeymatchResult,
-    Keystroke, KeystrokeEvent, LayoutId, LineLayoutIndex, Model, ModelContext, Modifiers,
-    ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent, MouseMoveEvent, MouseUpEvent,
-    Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput, PlatformInputHandler,
-    PlatformWindow, Point, PolychromeSprite, PromptLevel, Quad, Render, RenderGlyphParams,
-    RenderImageParams, RenderSvgParams, ResizeEdge, ScaledPixels, Scene, Shadow, SharedString,
-    Size, StrikethroughStyle, Style, SubscriberSet, Subscription, TaffyLayoutEngine, Task,
-    TextStyle, TextStyleRefinement, TransformationMatrix, Underline, UnderlineStyle, View,
+    hash, point, prelude::*, px, size, transparent_black, AccessibilityNode, AccessibilityTree,
+    Action, AnyDrag, AnyElement, AnyTooltip, AnyView, AppContext, Arena, Asset, AsyncWindowContext,
+    AvailableSpace, Bounds, BoxShadow, Context, Corners, CursorStyle, Decorations, DevicePixels,
+    DispatchActionListener, DispatchNodeId, DispatchTree, DisplayId, Edges, Effect, Entity,
+    EntityId, EventEmitter, FileDropEvent, Fill, Flatten, FontId, Global, GlobalElementId, GlyphId,
+    Hsla, ImageData, InputHandler, IsZero, KeyBinding, KeyContext, KeyDownEvent, KeyEvent,
+    KeyMatch, KeymatchResult, Keystroke, KeystrokeEvent, LayoutId, LineLayoutIndex, Model,
+    ModelContext, Modifiers, ModifiersChangedEvent, MonochromeSprite, MouseButton, MouseEvent,
+    MouseMoveEvent, MouseUpEvent, Path, Pixels, PlatformAtlas, PlatformDisplay, PlatformInput,
+    PlatformInputHandler, PlatformWindow, Point, PolychromeSprite, PromptLevel, Quad, Render,
+    RenderGlyphParams, RenderImageParams, RenderSvgParams, ResizeEdge, ScaledPixels, Scene, Shadow,
+    SharedString, Size, StrikethroughStyle, Style, SubscriberSet, Subscription, TaffyLayoutEngine,
+    Task, TextStyle, TextStyleRefinement, TransformationMatrix, Underline, UnderlineStyle, View,
     VisualContext, WeakView, WindowAppearance, WindowBackgroundAppearance, WindowBounds,
     WindowControls, WindowDecorations, WindowOptions, WindowParams, WindowTextSystem,
     SUBPIXEL_VARIANTS,
@@ -389,6 +389,7 @@ pub(crate) struct DeferredDraw {
     parent_node: DispatchNodeId,
     element_id_stack: SmallVec<[ElementId; 32]>,
     text_style_stack: Vec<TextStyleRefinement>,
+    pub(crate) accessibility_depth: usize,
     element: Option<AnyElement>,
     absolute_offset: Point<Pixels>,
     prepaint_range: Range<PrepaintStateIndex>,
@@ -408,6 +409,7 @@ pub(crate) struct Frame {
     pub(crate) input_handlers: Vec<Option<PlatformInputHandler>>,
     pub(crate) tooltip_requests: Vec<Option<TooltipRequest>>,
     pub(crate) cursor_styles: Vec<CursorStyleRequest>,
+    pub(crate) accessibility_nodes: Vec<(usize, AccessibilityNode)>,
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) debug_bounds: FxHashMap<String, Bounds<Pixels>>,
 }
@@ -428,6 +430,8 @@ pub(crate) struct PaintIndex {
     mouse_listeners_index: usize,
     input_handlers_index: usize,
     cursor_styles_index: usize,
+    accessibility_nodes_index: usize,
+    accessibility_depth: usize,
     accessed_element_states_index: usize,
     line_layout_index: LineLayoutIndex,
 }
@@ -447,6 +451,7 @@ impl Frame {
             input_handlers: Vec::new(),
             tooltip_requests: Vec::new(),
             cursor_styles: Vec::new(),
+            accessibility_nodes: Vec::new(),
 
             #[cfg(any(test, feature = "test-support"))]
             debug_bounds: FxHashMap::default(),
@@ -462,6 +467,7 @@ impl Frame {
         self.input_handlers.clear();
         self.tooltip_requests.clear();
         self.cursor_styles.clear();
+        self.accessibility_nodes.clear();
         self.hitboxes.clear();
         self.deferred_draws.clear();
     }
@@ -819,6 +825,7 @@ impl Window {
             root_view: None,
             element_id_stack: SmallVec::default(),
             text_style_stack: Vec::new(),
+            accessibility_depth: 0,
             element_offset_stack: Vec::new(),
             content_mask_stack: Vec::new(),
             requested_autoscroll: None,
@@ -1496,6 +1503,12 @@ impl<'a> WindowContext<'a> {
                 .retain(&(), |listener| listener(&event, self));
         }
 
+        if self.is_accessibility_enabled() {
+            self.window
+                .platform_window
+                .update_accessibility_tree(self.accessibility_tree());
+        }
+
         self.reset_cursor_style();
         self.window.refreshing = false;
         self.window.draw_phase = DrawPhase::None;
@@ -1731,6 +1744,8 @@ impl<'a> WindowContext<'a> {
             mouse_listeners_index: self.window.next_frame.mouse_listeners.len(),
             input_handlers_index: self.window.next_frame.input_handlers.len(),
             cursor_styles_index: self.window.next_frame.cursor_styles.len(),
+            accessibility_nodes_index: self.window.next_frame.accessibility_nodes.len(),
+            accessibility_depth: self.window.accessibility_depth,
             accessed_element_states_index: self.window.next_frame.accessed_element_states.len(),
             line_layout_index: self.window.text_system.layout_index(),
         }
@@ -1739,6 +1754,20 @@ impl<'a> WindowContext<'a> {
     pub(crate) fn reuse_paint(&mut self, range: Range<PaintIndex>) {
         let window = &mut self.window;
 
+        // The reused nodes may be nested at a different depth than when they were painted.
+        let accessibility_depth = window.accessibility_depth;
+        window.next_frame.accessibility_nodes.extend(
+            window.rendered_frame.accessibility_nodes
+                [range.start.accessibility_nodes_index..range.end.accessibility_nodes_index]
+                .iter()
+                .map(|(depth, node)| {
+                    (
+                        depth - range.start.accessibility_depth + accessibility_depth,
+                        node.clone(),
+                    )
+                }),
+        );
+
         window.next_frame.cursor_styles.extend(
             window.rendered_frame.cursor_styles
                 [range.start.cursor_styles_index..range.end.cursor_styles_index]
@@ -1813,6 +1842,52 @@ impl<'a> WindowContext<'a> {
             });
     }
 
+    /// Inserts a node describing an element into the accessibility tree, with any nodes
+    /// inserted by `f` as its children. If no node is given, the nodes inserted by `f` are
+    /// added to the current parent. This method should only be called during the paint
+    /// phase of element drawing.
+    pub fn with_accessibility_node<R>(
+        &mut self,
+        node: Option<AccessibilityNode>,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        debug_assert_eq!(
+            self.window.draw_phase,
+            DrawPhase::Paint,
+            "this method can only be called during paint"
+        );
+        if let Some(node) = node {
+            let depth = self.window.accessibility_depth;
+            self.window
+                .next_frame
+                .accessibility_nodes
+                .push((depth, node));
+            self.window.accessibility_depth += 1;
+            let result = f(self);
+            self.window.accessibility_depth -= 1;
+            result
+        } else {
+            f(self)
+        }
+    }
+
+    /// Inserts a node without children into the accessibility tree. This method should only
+    /// be called during the paint phase of element drawing.
+    pub fn insert_accessibility_node(&mut self, node: AccessibilityNode) {
+        self.with_accessibility_node(Some(node), |_| {});
+    }
+
+    /// Whether an assistive technology is reading this window's accessibility tree. Elements
+    /// that are expensive to describe can skip inserting their nodes when it isn't.
+    pub fn is_accessibility_enabled(&self) -> bool {
+        self.window.platform_window.is_accessibility_enabled()
+    }
+
+    /// Returns the accessibility tree of the last frame drawn in this window.
+    pub fn accessibility_tree(&self) -> AccessibilityTree {
+        AccessibilityTree::new(&self.window.rendered_frame.accessibility_nodes)
+    }
+
     /// Sets a tooltip to be rendered for the upcoming frame. This method should only be called
     /// during the paint phase of element drawing.
     pub fn set_tooltip(&mut self, tooltip: AnyTooltip) -> TooltipId {
@@ -2323,7 +2398,7 @@ impl<'a> WindowContext<'a> {
             pad: 0,
             bounds: quad.bounds.scale(scale_factor),
             content_mask: content_mask.scale(scale_factor),
-            background: quad.background,
+            background: (&quad.background).into(),
             border_color: quad.border_color,
             corner_radii: quad.corner_radii.scale(scale_factor),
             border_widths: quad.border_widths.scale(scale_factor),
@@ -2333,7 +2408,7 @@ impl<'a> WindowContext<'a> {
     /// Paint the given `Path` into the scene for the next frame at the current z-index.
     ///
     /// This method should only be called as part of the paint phase of element drawing.
-    pub fn paint_path(&mut self, mut path: Path<Pixels>, color: impl Into<Hsla>) {
+    pub fn paint_path(&mut self, mut path: Path<Pixels>, fill: impl Into<Fill>) {
         debug_assert_eq!(
             self.window.draw_phase,
             DrawPhase::Paint,
@@ -2343,7 +2418,7 @@ impl<'a> WindowContext<'a> {
         let scale_factor = self.scale_factor();
         let content_mask = self.content_mask();
         path.content_mask = content_mask;
-        path.color = color.into();
+        path.background = (&fill.into()).into();
         self.window
             .next_frame
             .scene
@@ -4891,8 +4966,8 @@ pub struct PaintQuad {
     pub bounds: Bounds<Pixels>,
     /// The radii of the quad's corners.
     pub corner_radii: Corners<Pixels>,
-    /// The background color of the quad.
-    pub background: Hsla,
+    /// The fill of the quad's background.
+    pub background: Fill,
     /// The widths of the quad's borders.
     pub border_widths: Edges<Pixels>,
     /// The color of the quad's borders.
@@ -4924,8 +4999,8 @@ impl PaintQuad {
         }
     }
 
-    /// Sets the background color of the quad.
-    pub fn background(self, background: impl Into<Hsla>) -> Self {
+    /// Sets the background fill of the quad.
+    pub fn background(self, background: impl Into<Fill>) -> Self {
         PaintQuad {
             background: background.into(),
             ..self
@@ -4937,7 +5012,7 @@ impl PaintQuad {
 pub fn quad(
     bounds: Bounds<Pixels>,
     corner_radii: impl Into<Corners<Pixels>>,
-    background: impl Into<Hsla>,
+    background: impl Into<Fill>,
     border_widths: impl Into<Edges<Pixels>>,
     border_color: impl Into<Hsla>,
 ) -> PaintQuad {
@@ -4950,8 +5025,8 @@ pub fn quad(
     }
 }
 
-/// Creates a filled quad with the given bounds and background color.
-pub fn fill(bounds: impl Into<Bounds<Pixels>>, background: impl Into<Hsla>) -> PaintQuad {
+/// Creates a filled quad with the given bounds and background fill.
+pub fn fill(bounds: impl Into<Bounds<Pixels>>, background: impl Into<Fill>) -> PaintQuad {
     PaintQuad {
         bounds: bounds.into(),
         corner_radii: (0.).into(),
@@ -4966,7 +5041,7 @@ pub fn outline(bounds: impl Into<Bounds<Pixels>>, border_color: impl Into<Hsla>)
     PaintQuad {
         bounds: bounds.into(),
         corner_radii: (0.).into(),
-        background: transparent_black(),
+        background: transparent_black().into(),
         border_widths: (1.).into(),
         border_color: border_color.into(),
     }
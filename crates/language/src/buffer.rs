@@ -26,6 +26,7 @@ use gpui::{
 use lazy_static::lazy_static;
 use lsp::LanguageServerId;
 use parking_lot::Mutex;
+use postage::watch;
 use similar::{ChangeTag, TextDiff};
 use smallvec::SmallVec;
 use smol::future::yield_now;
@@ -80,6 +81,15 @@ pub enum Capability {
     ReadOnly,
 }
 
+/// Whether a [Buffer]'s syntax tree is up to date with its text.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ParseStatus {
+    /// The syntax tree reflects the latest parse of the buffer.
+    Idle,
+    /// The buffer is being parsed in the background.
+    Parsing,
+}
+
 pub type BufferRow = u32;
 
 /// An in-memory representation of a source code file, including its text,
@@ -104,6 +114,7 @@ pub struct Buffer {
     sync_parse_timeout: Duration,
     syntax_map: Mutex<SyntaxMap>,
     parsing_in_background: bool,
+    parse_status: (watch::Sender<ParseStatus>, watch::Receiver<ParseStatus>),
     non_text_state_update_count: usize,
     diagnostics: SmallVec<[(LanguageServerId, DiagnosticSet); 2]>,
     remote_selections: TreeMap<ReplicaId, SelectionSet>,
@@ -708,6 +719,7 @@ impl Buffer {
             capability,
             syntax_map: Mutex::new(SyntaxMap::new()),
             parsing_in_background: false,
+            parse_status: watch::channel_with(ParseStatus::Idle),
             non_text_state_update_count: 0,
             sync_parse_timeout: Duration::from_millis(1),
             autoindent_requests: Default::default(),
@@ -997,6 +1009,11 @@ impl Buffer {
         self.parsing_in_background
     }
 
+    /// Returns a watch that reports whether the buffer is being parsed in the background.
+    pub fn parse_status(&self) -> watch::Receiver<ParseStatus> {
+        self.parse_status.1.clone()
+    }
+
     /// Indicates whether the buffer contains any regions that may be
     /// written in a language that hasn't been loaded yet.
     pub fn contains_unknown_injections(&self) -> bool {
@@ -1069,6 +1086,7 @@ impl Buffer {
             }
             Err(parse_task) => {
                 self.parsing_in_background = true;
+                *self.parse_status.0.borrow_mut() = ParseStatus::Parsing;
                 cx.spawn(move |this, mut cx| async move {
                     let new_syntax_map = parse_task.await;
                     this.update(&mut cx, move |this, cx| {
@@ -1089,6 +1107,9 @@ impl Buffer {
                         if parse_again {
                             this.reparse(cx);
                         }
+                        if !this.parsing_in_background {
+                            *this.parse_status.0.borrow_mut() = ParseStatus::Idle;
+                        }
                     })
                     .ok();
                 })
@@ -1,6 +1,6 @@
 //! Provides `language`-related settings.
 
-use crate::{File, Language, LanguageServerName};
+use crate::{File, Language, LanguageServerName, LineEnding};
 use anyhow::Result;
 use collections::{HashMap, HashSet};
 use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
@@ -106,6 +106,9 @@ pub struct LanguageSettings {
     pub show_inline_completions: bool,
     /// Whether to show tabs and spaces in the editor.
     pub show_whitespaces: ShowWhitespaceSetting,
+    /// The name of the theme whose syntax highlighting to use instead of
+    /// the active theme's.
+    pub syntax_theme: Option<String>,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     pub extend_comment_on_newline: bool,
     /// Inlay hint related settings.
@@ -118,6 +121,10 @@ pub struct LanguageSettings {
     pub always_treat_brackets_as_autoclosed: bool,
     /// Which code actions to run on save
     pub code_actions_on_format: HashMap<String, bool>,
+    /// The line ending to use when saving files, overriding the one
+    /// they were loaded with. Only set by `.editorconfig` files.
+    #[serde(skip)]
+    pub line_ending: Option<LineEnding>,
     /// Whether to perform linked edits
     pub linked_edits: bool,
     /// Task configuration for this language.
@@ -306,6 +313,13 @@ pub struct LanguageSettingsContent {
     /// Whether to show tabs and spaces in the editor.
     #[serde(default)]
     pub show_whitespaces: Option<ShowWhitespaceSetting>,
+    /// The name of the theme whose syntax highlighting to use instead of
+    /// the active theme's. An explicit `null` resets an override set by a
+    /// less specific setting.
+    ///
+    /// Default: null
+    #[serde(default, deserialize_with = "deserialize_explicit_null")]
+    pub syntax_theme: Option<Option<String>>,
     /// Whether to start a new line with a comment when a previous line is a comment as well.
     ///
     /// Default: true
@@ -337,6 +351,11 @@ pub struct LanguageSettingsContent {
     ///
     /// Default: {} (or {"source.organizeImports": true} for Go).
     pub code_actions_on_format: Option<HashMap<String, bool>>,
+    /// The line ending to use when saving files, overriding the one they
+    /// were loaded with. Only set by `.editorconfig` files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub line_ending: Option<LineEndingSetting>,
     /// Whether to perform linked edits of associated ranges, if the language server supports it.
     /// For example, when editing opening <html> tag, the contents of the closing </html> tag will be edited as well.
     ///
@@ -348,6 +367,25 @@ pub struct LanguageSettingsContent {
     pub tasks: Option<LanguageTaskConfig>,
 }
 
+/// The line ending used when saving files.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEndingSetting {
+    /// Line feeds (`\n`).
+    Unix,
+    /// Carriage returns followed by line feeds (`\r\n`).
+    Windows,
+}
+
+impl From<LineEndingSetting> for LineEnding {
+    fn from(setting: LineEndingSetting) -> Self {
+        match setting {
+            LineEndingSetting::Unix => LineEnding::Unix,
+            LineEndingSetting::Windows => LineEnding::Windows,
+        }
+    }
+}
+
 /// The contents of the inline completion settings.
 #[derive(Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct InlineCompletionSettingsContent {
@@ -812,6 +850,16 @@ impl settings::Settings for AllLanguageSettings {
     }
 }
 
+/// Deserializes a present field as `Some`, so that an explicit `null` can be
+/// told apart from a missing field.
+fn deserialize_explicit_null<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
 fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent) {
     fn merge<T>(target: &mut T, value: Option<T>) {
         if let Some(value) = value {
@@ -835,6 +883,10 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         &mut settings.code_actions_on_format,
         src.code_actions_on_format.clone(),
     );
+    merge(
+        &mut settings.line_ending,
+        src.line_ending.map(|line_ending| Some(line_ending.into())),
+    );
     merge(&mut settings.linked_edits, src.linked_edits);
     merge(&mut settings.tasks, src.tasks.clone());
 
@@ -863,6 +915,7 @@ fn merge_settings(settings: &mut LanguageSettings, src: &LanguageSettingsContent
         src.show_inline_completions,
     );
     merge(&mut settings.show_whitespaces, src.show_whitespaces);
+    merge(&mut settings.syntax_theme, src.syntax_theme.clone());
     merge(
         &mut settings.extend_comment_on_newline,
         src.extend_comment_on_newline,
@@ -897,6 +950,38 @@ pub struct PrettierSettings {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    fn test_language_syntax_theme_null_resets_override(cx: &mut AppContext) {
+        cx.set_global(SettingsStore::test(cx));
+        AllLanguageSettings::register(cx);
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            store
+                .set_user_settings(
+                    r#"{
+                        "syntax_theme": "One Dark",
+                        "languages": {
+                            "Rust": { "tab_size": 2 },
+                            "Markdown": { "syntax_theme": null }
+                        }
+                    }"#,
+                    cx,
+                )
+                .unwrap();
+        });
+
+        let settings = AllLanguageSettings::get_global(cx);
+        assert_eq!(
+            settings.language(None).syntax_theme.as_deref(),
+            Some("One Dark")
+        );
+        assert_eq!(
+            settings.language(Some("Rust")).syntax_theme.as_deref(),
+            Some("One Dark")
+        );
+        assert_eq!(settings.language(Some("Markdown")).syntax_theme, None);
+    }
 
     #[test]
     pub fn test_resolve_language_servers() {
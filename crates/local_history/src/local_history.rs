@@ -0,0 +1,644 @@
+mod local_history_settings;
+mod persistence;
+
+use std::path::PathBuf;
+
+use collections::HashMap;
+
+use anyhow::{Context as _, Result};
+use diff_view::DiffView;
+use editor::{Editor, EditorMode};
+use gpui::{
+    actions, uniform_list, AnyElement, AppContext, Context as _, EntityId, EventEmitter,
+    FocusHandle, FocusableView, Global, Model, ModelContext, Render, Subscription, Task,
+    UniformListScrollHandle, View, ViewContext, VisualContext as _,
+};
+use language::{Buffer, Capability};
+use persistence::LOCAL_HISTORY;
+use project::Project;
+use settings::Settings;
+use time::{OffsetDateTime, UtcOffset};
+use time_format::{format_localized_timestamp, TimestampFormat};
+use ui::{prelude::*, ListItem, ListItemSpacing, Tooltip};
+use workspace::{
+    item::{Item, TabContentParams},
+    Workspace,
+};
+
+pub use local_history_settings::LocalHistorySettings;
+pub use persistence::SnapshotSource;
+
+actions!(local_history, [Show]);
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+pub fn init(cx: &mut AppContext) {
+    LocalHistorySettings::register(cx);
+
+    let local_history = cx.new_model(|_| LocalHistory {
+        project_subscriptions: HashMap::default(),
+    });
+    cx.set_global(GlobalLocalHistory(local_history));
+
+    cx.observe_new_views(|workspace: &mut Workspace, cx| {
+        let project = workspace.project().clone();
+        LocalHistory::global(cx).update(cx, |local_history, cx| {
+            local_history.observe_project(&project, cx)
+        });
+
+        workspace.register_action(LocalHistoryView::show);
+    })
+    .detach();
+
+    // Only the files opened in an editor are recorded, and not the ones loaded in
+    // the background, e.g. by project search or language servers.
+    cx.observe_new_views(|editor: &mut Editor, cx| {
+        if editor.mode() != EditorMode::Full {
+            return;
+        }
+        if let Some(buffer) = editor.buffer().read(cx).as_singleton() {
+            LocalHistory::global(cx).update(cx, |local_history, cx| {
+                local_history.record_snapshot(&buffer, SnapshotSource::Opened, cx)
+            });
+        }
+    })
+    .detach();
+}
+
+struct GlobalLocalHistory(Model<LocalHistory>);
+
+impl Global for GlobalLocalHistory {}
+
+/// Records snapshots of local files when they are opened, saved or reloaded from
+/// disk.
+pub struct LocalHistory {
+    project_subscriptions: HashMap<EntityId, Subscription>,
+}
+
+pub enum Event {
+    SnapshotRecorded { abs_path: PathBuf },
+}
+
+impl EventEmitter<Event> for LocalHistory {}
+
+/// A recorded version of a file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub id: i64,
+    pub timestamp: OffsetDateTime,
+    pub source: SnapshotSource,
+}
+
+impl LocalHistory {
+    pub fn global(cx: &AppContext) -> Model<Self> {
+        cx.global::<GlobalLocalHistory>().0.clone()
+    }
+
+    /// Records snapshots of the project's buffers when they are saved or reloaded.
+    /// Observing a project that is already observed, e.g. from another window, has
+    /// no effect.
+    pub fn observe_project(&mut self, project: &Model<Project>, cx: &mut ModelContext<Self>) {
+        let project_id = project.entity_id();
+        if self.project_subscriptions.contains_key(&project_id) {
+            return;
+        }
+
+        let subscription = cx.subscribe(project, |local_history, _, event, cx| {
+            let (buffer, source) = match event {
+                project::Event::BufferSaved(buffer) => (buffer, SnapshotSource::Saved),
+                project::Event::BufferReloaded(buffer) => (buffer, SnapshotSource::Reloaded),
+                _ => return,
+            };
+            local_history.record_snapshot(buffer, source, cx);
+        });
+        self.project_subscriptions.insert(project_id, subscription);
+        cx.observe_release(project, move |local_history, _, _| {
+            local_history.project_subscriptions.remove(&project_id);
+        })
+        .detach();
+    }
+
+    /// Records the contents of the given buffer, if it belongs to a local file
+    /// that isn't private.
+    pub fn record_snapshot(
+        &mut self,
+        buffer: &Model<Buffer>,
+        source: SnapshotSource,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let settings = *LocalHistorySettings::get_global(cx);
+        if !settings.enabled {
+            return;
+        }
+
+        let buffer = buffer.read(cx);
+        let Some(file) = buffer.file() else {
+            return;
+        };
+        if file.is_private() || buffer.len() > settings.max_file_size {
+            return;
+        }
+        let Some(file) = file.as_local() else {
+            return;
+        };
+
+        let abs_path = file.abs_path(cx);
+        let text = buffer.text();
+        let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+        let oldest_timestamp = timestamp - settings.max_age_days as i64 * SECONDS_PER_DAY;
+        cx.spawn(|this, mut cx| async move {
+            let recorded = LOCAL_HISTORY
+                .record_snapshot(
+                    abs_path.clone(),
+                    text,
+                    timestamp,
+                    source,
+                    settings.max_snapshots_per_file,
+                    oldest_timestamp,
+                )
+                .await?;
+            if recorded {
+                this.update(&mut cx, |_, cx| {
+                    cx.emit(Event::SnapshotRecorded { abs_path })
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Returns the snapshots of the given file, newest first.
+    pub fn snapshots(&self, abs_path: PathBuf, cx: &AppContext) -> Task<Result<Vec<Snapshot>>> {
+        cx.background_executor().spawn(async move {
+            LOCAL_HISTORY
+                .snapshots(abs_path)?
+                .into_iter()
+                .map(|(id, timestamp, source)| {
+                    Ok(Snapshot {
+                        id,
+                        timestamp: OffsetDateTime::from_unix_timestamp(timestamp)?,
+                        source,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// Returns the contents of the file when the given snapshot was recorded.
+    pub fn snapshot_text(&self, id: i64, cx: &AppContext) -> Task<Result<String>> {
+        cx.background_executor().spawn(async move {
+            LOCAL_HISTORY
+                .snapshot_text(id)?
+                .context("local history snapshot not found")
+        })
+    }
+
+    /// Replaces the contents of the buffer with the given snapshot, as a single
+    /// undoable edit.
+    pub fn restore_snapshot(
+        &self,
+        id: i64,
+        buffer: Model<Buffer>,
+        cx: &ModelContext<Self>,
+    ) -> Task<Result<()>> {
+        let text = self.snapshot_text(id, cx);
+        cx.spawn(|_, mut cx| async move {
+            let text = text.await?;
+            let diff = buffer
+                .update(&mut cx, |buffer, cx| buffer.diff(text, cx))?
+                .await;
+            buffer.update(&mut cx, |buffer, cx| buffer.apply_diff(diff, cx))?;
+            Ok(())
+        })
+    }
+}
+
+/// Lists the snapshots of a file, comparing the selected one with the file's
+/// current contents.
+pub struct LocalHistoryView {
+    buffer: Model<Buffer>,
+    abs_path: PathBuf,
+    file_name: SharedString,
+    project: Model<Project>,
+    snapshots: Vec<Snapshot>,
+    selected_index: Option<usize>,
+    diff_view: Option<View<DiffView>>,
+    local_timezone: UtcOffset,
+    scroll_handle: UniformListScrollHandle,
+    focus_handle: FocusHandle,
+    load_snapshots_task: Option<Task<Result<()>>>,
+    open_snapshot_task: Option<Task<Result<()>>>,
+    _subscription: Subscription,
+}
+
+impl LocalHistoryView {
+    fn show(workspace: &mut Workspace, _: &Show, cx: &mut ViewContext<Workspace>) {
+        let Some(buffer) = workspace
+            .active_item_as::<Editor>(cx)
+            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+        else {
+            return;
+        };
+        let Some(abs_path) = buffer
+            .read(cx)
+            .file()
+            .and_then(|file| file.as_local())
+            .map(|file| file.abs_path(cx))
+        else {
+            return;
+        };
+
+        if let Some(existing) = workspace
+            .items_of_type::<LocalHistoryView>(cx)
+            .find(|view| view.read(cx).abs_path == abs_path)
+        {
+            workspace.activate_item(&existing, cx);
+            return;
+        }
+
+        let project = workspace.project().clone();
+        let view = cx.new_view(|cx| LocalHistoryView::new(buffer, abs_path, project, cx));
+        workspace.add_item_to_active_pane(Box::new(view), None, cx);
+    }
+
+    fn new(
+        buffer: Model<Buffer>,
+        abs_path: PathBuf,
+        project: Model<Project>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let file_name = abs_path
+            .file_name()
+            .unwrap_or(abs_path.as_os_str())
+            .to_string_lossy()
+            .to_string()
+            .into();
+        let subscription = cx.subscribe(
+            &LocalHistory::global(cx),
+            |this, _, event, cx| match event {
+                Event::SnapshotRecorded { abs_path } => {
+                    if *abs_path == this.abs_path {
+                        this.load_snapshots(cx);
+                    }
+                }
+            },
+        );
+
+        let mut this = Self {
+            buffer,
+            abs_path,
+            file_name,
+            project,
+            snapshots: Vec::new(),
+            selected_index: None,
+            diff_view: None,
+            local_timezone: cx.local_timezone(),
+            scroll_handle: UniformListScrollHandle::new(),
+            focus_handle: cx.focus_handle(),
+            load_snapshots_task: None,
+            open_snapshot_task: None,
+            _subscription: subscription,
+        };
+        this.load_snapshots(cx);
+        this
+    }
+
+    fn load_snapshots(&mut self, cx: &mut ViewContext<Self>) {
+        let snapshots = LocalHistory::global(cx)
+            .read(cx)
+            .snapshots(self.abs_path.clone(), cx);
+        self.load_snapshots_task = Some(cx.spawn(|this, mut cx| async move {
+            let snapshots = snapshots.await?;
+            this.update(&mut cx, |this, cx| {
+                let selected_id = this.selected_snapshot().map(|snapshot| snapshot.id);
+                this.snapshots = snapshots;
+                this.selected_index = selected_id.and_then(|selected_id| {
+                    this.snapshots
+                        .iter()
+                        .position(|snapshot| snapshot.id == selected_id)
+                });
+                if this.selected_index.is_none() && !this.snapshots.is_empty() {
+                    this.select_snapshot(0, cx);
+                }
+                cx.notify();
+            })
+        }));
+    }
+
+    fn selected_snapshot(&self) -> Option<&Snapshot> {
+        self.snapshots.get(self.selected_index?)
+    }
+
+    fn select_snapshot(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(snapshot) = self.snapshots.get(ix) else {
+            return;
+        };
+        let id = snapshot.id;
+        let title = self.format_timestamp(snapshot, TimestampFormat::EnhancedAbsolute);
+        self.selected_index = Some(ix);
+        self.scroll_handle.scroll_to_item(ix);
+
+        let text = LocalHistory::global(cx).read(cx).snapshot_text(id, cx);
+        self.open_snapshot_task = Some(cx.spawn(|this, mut cx| async move {
+            let text = text.await?;
+            this.update(&mut cx, |this, cx| {
+                let buffer = this.buffer.read(cx);
+                let language = buffer.language().cloned();
+                let language_registry = this.project.read(cx).languages().clone();
+                let snapshot_buffer = cx.new_model(|cx| {
+                    let mut buffer = Buffer::local(text, cx);
+                    buffer.set_language_registry(language_registry);
+                    buffer.set_language(language, cx);
+                    buffer.set_capability(Capability::ReadOnly, cx);
+                    buffer
+                });
+                let file_name = this.file_name.clone();
+                let buffer = this.buffer.clone();
+                let project = this.project.clone();
+                this.diff_view = Some(cx.new_view(|cx| {
+                    DiffView::new(snapshot_buffer, title, buffer, file_name, project, cx)
+                }));
+                cx.notify();
+            })
+        }));
+        cx.notify();
+    }
+
+    /// Replaces the contents of the file's buffer with the given snapshot.
+    fn restore_snapshot(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(id) = self.snapshots.get(ix).map(|snapshot| snapshot.id) else {
+            return;
+        };
+        if self.selected_index != Some(ix) {
+            self.select_snapshot(ix, cx);
+        }
+
+        let buffer = self.buffer.clone();
+        LocalHistory::global(cx)
+            .update(cx, |local_history, cx| {
+                local_history.restore_snapshot(id, buffer, cx)
+            })
+            .detach_and_log_err(cx);
+    }
+
+    fn select_next(&mut self, _: &menu::SelectNext, cx: &mut ViewContext<Self>) {
+        let ix = self
+            .selected_index
+            .map_or(0, |ix| (ix + 1).min(self.snapshots.len().saturating_sub(1)));
+        if Some(ix) != self.selected_index {
+            self.select_snapshot(ix, cx);
+        }
+    }
+
+    fn select_prev(&mut self, _: &menu::SelectPrev, cx: &mut ViewContext<Self>) {
+        let ix = self.selected_index.map_or(0, |ix| ix.saturating_sub(1));
+        if Some(ix) != self.selected_index {
+            self.select_snapshot(ix, cx);
+        }
+    }
+
+    fn confirm(&mut self, _: &menu::Confirm, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.selected_index {
+            self.restore_snapshot(ix, cx);
+        }
+    }
+
+    fn format_timestamp(&self, snapshot: &Snapshot, format: TimestampFormat) -> String {
+        format_localized_timestamp(
+            snapshot.timestamp,
+            OffsetDateTime::now_utc(),
+            self.local_timezone,
+            format,
+        )
+    }
+
+    fn render_snapshot(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let snapshot = &self.snapshots[ix];
+        let source = match snapshot.source {
+            SnapshotSource::Opened => "Opened",
+            SnapshotSource::Saved => "Saved",
+            SnapshotSource::Reloaded => "Changed on disk",
+        };
+
+        ListItem::new(ix)
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .selected(self.selected_index == Some(ix))
+            .child(
+                v_flex()
+                    .child(Label::new(
+                        self.format_timestamp(snapshot, TimestampFormat::Relative),
+                    ))
+                    .child(
+                        Label::new(format!(
+                            "{} · {source}",
+                            self.format_timestamp(snapshot, TimestampFormat::EnhancedAbsolute)
+                        ))
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                    ),
+            )
+            .end_hover_slot(
+                IconButton::new(("restore-snapshot", ix), IconName::RotateCcw)
+                    .icon_size(IconSize::Small)
+                    .tooltip(|cx| Tooltip::text("Restore Snapshot", cx))
+                    .on_click(cx.listener(move |this, _, cx| this.restore_snapshot(ix, cx))),
+            )
+            .on_click(cx.listener(move |this, _, cx| this.select_snapshot(ix, cx)))
+    }
+}
+
+impl Render for LocalHistoryView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .key_context("LocalHistory")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_prev))
+            .on_action(cx.listener(Self::confirm))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .w(rems(18.))
+                    .h_full()
+                    .border_r_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        h_flex()
+                            .p_2()
+                            .gap_2()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(
+                                Icon::new(IconName::HistoryRerun)
+                                    .size(IconSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .child(Label::new(self.file_name.clone())),
+                    )
+                    .map(|this| {
+                        if self.snapshots.is_empty() {
+                            this.child(
+                                div().p_2().child(
+                                    Label::new("No snapshots have been recorded for this file")
+                                        .color(Color::Muted),
+                                ),
+                            )
+                        } else {
+                            this.child(
+                                uniform_list(
+                                    cx.view().clone(),
+                                    "snapshots",
+                                    self.snapshots.len(),
+                                    |this, range, cx| {
+                                        range
+                                            .map(|ix| {
+                                                this.render_snapshot(ix, cx).into_any_element()
+                                            })
+                                            .collect()
+                                    },
+                                )
+                                .flex_grow()
+                                .py_1()
+                                .track_scroll(self.scroll_handle.clone()),
+                            )
+                        }
+                    }),
+            )
+            .child(div().flex_1().h_full().map(|this| {
+                match &self.diff_view {
+                    Some(diff_view) => this.child(diff_view.clone()),
+                    None => this.flex().items_center().justify_center().child(
+                        Label::new("Select a snapshot to compare it with the current contents")
+                            .color(Color::Muted),
+                    ),
+                }
+            }))
+    }
+}
+
+impl FocusableView for LocalHistoryView {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<()> for LocalHistoryView {}
+
+impl Item for LocalHistoryView {
+    type Event = ();
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> AnyElement {
+        Label::new(format!("{} History", self.file_name))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_tooltip_text(&self, _: &AppContext) -> Option<SharedString> {
+        Some(format!("Local history of {}", self.abs_path.display()).into())
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("local history")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use language::LineEnding;
+    use project::FakeFs;
+    use serde_json::json;
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    async fn test_restore_externally_overwritten_file(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            Project::init_settings(cx);
+            editor::init(cx);
+            init(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/dir", json!({ "file.txt": "original\n" }))
+            .await;
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+        let local_history = cx.update(|cx| LocalHistory::global(cx));
+        local_history.update(cx, |local_history, cx| {
+            local_history.observe_project(&project, cx);
+            local_history.observe_project(&project, cx);
+        });
+        let snapshot_sources = |cx: &mut TestAppContext| {
+            let snapshots = local_history.update(cx, |local_history, cx| {
+                local_history.snapshots(PathBuf::from("/dir/file.txt"), cx)
+            });
+            async move {
+                snapshots
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|snapshot| snapshot.source)
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // Buffers that aren't opened in an editor aren't recorded.
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer("/dir/file.txt", cx)
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+        assert_eq!(snapshot_sources(cx).await, vec![]);
+
+        cx.add_window(|cx| Editor::for_buffer(buffer.clone(), Some(project.clone()), cx));
+        cx.run_until_parked();
+        assert_eq!(snapshot_sources(cx).await, vec![SnapshotSource::Opened]);
+
+        fs.save(
+            "/dir/file.txt".as_ref(),
+            &"overwritten\n".into(),
+            LineEnding::Unix,
+        )
+        .await
+        .unwrap();
+        cx.run_until_parked();
+        buffer.update(cx, |buffer, _| assert_eq!(buffer.text(), "overwritten\n"));
+
+        let snapshots = local_history
+            .update(cx, |local_history, cx| {
+                local_history.snapshots(PathBuf::from("/dir/file.txt"), cx)
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|snapshot| snapshot.source)
+                .collect::<Vec<_>>(),
+            vec![SnapshotSource::Reloaded, SnapshotSource::Opened]
+        );
+
+        local_history
+            .update(cx, |local_history, cx| {
+                local_history.restore_snapshot(snapshots[1].id, buffer.clone(), cx)
+            })
+            .await
+            .unwrap();
+        buffer.update(cx, |buffer, _| {
+            assert_eq!(buffer.text(), "original\n");
+            assert!(buffer.is_dirty());
+        });
+    }
+}
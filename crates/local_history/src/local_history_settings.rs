@@ -0,0 +1,46 @@
+use gpui::AppContext;
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct LocalHistorySettings {
+    pub enabled: bool,
+    pub max_snapshots_per_file: usize,
+    pub max_age_days: u32,
+    pub max_file_size: usize,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct LocalHistorySettingsContent {
+    /// Whether to record a snapshot of a file every time it is saved
+    /// or reloaded after changing on disk.
+    ///
+    /// Default: true
+    pub enabled: Option<bool>,
+    /// The maximum number of snapshots kept for each file.
+    ///
+    /// Default: 50
+    pub max_snapshots_per_file: Option<usize>,
+    /// The number of days after which snapshots are removed.
+    ///
+    /// Default: 30
+    pub max_age_days: Option<u32>,
+    /// Files larger than this many bytes are not recorded.
+    ///
+    /// Default: 1048576
+    pub max_file_size: Option<usize>,
+}
+
+impl Settings for LocalHistorySettings {
+    const KEY: Option<&'static str> = Some("local_history");
+
+    type FileContent = LocalHistorySettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut AppContext,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+}
@@ -0,0 +1,257 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+use db::{
+    define_connection, query,
+    sqlez::{
+        bindable::{Bind, Column, StaticColumnCount},
+        statement::Statement,
+    },
+    sqlez_macros::sql,
+};
+
+/// The compression level used for the stored contents of snapshots.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// What caused a snapshot of a file to be recorded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotSource {
+    /// The file was opened in an editor, recorded so that its contents can be
+    /// restored if they are overwritten on disk.
+    Opened,
+    /// The file was saved from a buffer.
+    Saved,
+    /// The file changed on disk and its buffer was reloaded.
+    Reloaded,
+}
+
+impl StaticColumnCount for SnapshotSource {}
+impl Bind for SnapshotSource {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        match self {
+            SnapshotSource::Opened => "Opened",
+            SnapshotSource::Saved => "Saved",
+            SnapshotSource::Reloaded => "Reloaded",
+        }
+        .bind(statement, start_index)
+    }
+}
+
+impl Column for SnapshotSource {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        String::column(statement, start_index).and_then(|(source, next_index)| {
+            Ok((
+                match source.as_str() {
+                    "Opened" => SnapshotSource::Opened,
+                    "Saved" => SnapshotSource::Saved,
+                    "Reloaded" => SnapshotSource::Reloaded,
+                    _ => anyhow::bail!("Stored snapshot source is incorrect"),
+                },
+                next_index,
+            ))
+        })
+    }
+}
+
+define_connection!(pub static ref LOCAL_HISTORY: LocalHistoryDb<()> =
+    &[sql!(
+        CREATE TABLE local_history(
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            abs_path BLOB NOT NULL,
+            timestamp INTEGER NOT NULL, // Seconds since the Unix epoch
+            source TEXT NOT NULL,
+            content BLOB NOT NULL // zstd-compressed UTF-8 text
+        ) STRICT;
+
+        CREATE INDEX local_history_by_path ON local_history(abs_path, id);
+    )];
+);
+
+impl LocalHistoryDb {
+    query! {
+        /// Returns the id, timestamp and source of every snapshot of the given file,
+        /// newest first.
+        pub fn snapshots(abs_path: PathBuf) -> Result<Vec<(i64, i64, SnapshotSource)>> {
+            SELECT id, timestamp, source
+            FROM local_history
+            WHERE abs_path = ?
+            ORDER BY id DESC
+        }
+    }
+
+    query! {
+        fn compressed_content(id: i64) -> Result<Option<Vec<u8>>> {
+            SELECT content FROM local_history WHERE id = ?
+        }
+    }
+
+    /// Returns the text of the given snapshot.
+    pub fn snapshot_text(&self, id: i64) -> Result<Option<String>> {
+        let Some(content) = self.compressed_content(id)? else {
+            return Ok(None);
+        };
+        let content = zstd::decode_all(content.as_slice())
+            .context("failed to decompress local history snapshot")?;
+        Ok(Some(String::from_utf8(content)?))
+    }
+
+    /// Records a snapshot of the given file unless its latest snapshot has the same
+    /// contents, then removes the snapshots of that file exceeding `max_snapshots`
+    /// or recorded before `oldest_timestamp`.
+    ///
+    /// Returns whether a snapshot was recorded.
+    pub async fn record_snapshot(
+        &self,
+        abs_path: PathBuf,
+        text: String,
+        timestamp: i64,
+        source: SnapshotSource,
+        max_snapshots: usize,
+        oldest_timestamp: i64,
+    ) -> Result<bool> {
+        self.write(move |conn| {
+            let content = zstd::stream::encode_all(text.as_bytes(), COMPRESSION_LEVEL)?;
+            conn.with_savepoint("record_snapshot", || {
+                let latest_content = conn.select_row_bound::<&Path, Vec<u8>>(sql!(
+                    SELECT content
+                    FROM local_history
+                    WHERE abs_path = ?
+                    ORDER BY id DESC
+                    LIMIT 1
+                ))?(&abs_path)?;
+
+                let recorded = latest_content.as_ref() != Some(&content);
+                if recorded {
+                    conn.exec_bound(sql!(
+                        INSERT INTO local_history(abs_path, timestamp, source, content)
+                        VALUES (?, ?, ?, ?)
+                    ))?((
+                        abs_path.as_path(),
+                        timestamp,
+                        source,
+                        content.as_slice(),
+                    ))?;
+                }
+
+                conn.exec_bound(sql!(
+                    DELETE FROM local_history
+                    WHERE abs_path = ? AND (
+                        timestamp < ? OR
+                        id NOT IN (
+                            SELECT id
+                            FROM local_history
+                            WHERE abs_path = ?
+                            ORDER BY id DESC
+                            LIMIT ?
+                        )
+                    )
+                ))?((
+                    abs_path.as_path(),
+                    oldest_timestamp,
+                    abs_path.as_path(),
+                    max_snapshots as i64,
+                ))?;
+
+                Ok(recorded)
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_record_snapshots() {
+        let db = LocalHistoryDb(db::open_test_db("test_record_snapshots").await);
+        let path = PathBuf::from("/root/file.txt");
+        let other_path = PathBuf::from("/root/other.txt");
+
+        assert!(db
+            .record_snapshot(path.clone(), "one".into(), 10, SnapshotSource::Saved, 3, 0)
+            .await
+            .unwrap());
+        // Snapshots identical to the latest one aren't recorded.
+        assert!(!db
+            .record_snapshot(path.clone(), "one".into(), 11, SnapshotSource::Saved, 3, 0)
+            .await
+            .unwrap());
+        assert!(db
+            .record_snapshot(
+                path.clone(),
+                "two".into(),
+                12,
+                SnapshotSource::Reloaded,
+                3,
+                0
+            )
+            .await
+            .unwrap());
+        assert!(db
+            .record_snapshot(
+                other_path.clone(),
+                "other".into(),
+                12,
+                SnapshotSource::Saved,
+                3,
+                0
+            )
+            .await
+            .unwrap());
+
+        let snapshots = db.snapshots(path.clone()).unwrap();
+        assert_eq!(
+            snapshots
+                .iter()
+                .map(|(_, timestamp, source)| (*timestamp, *source))
+                .collect::<Vec<_>>(),
+            vec![(12, SnapshotSource::Reloaded), (10, SnapshotSource::Saved)]
+        );
+        assert_eq!(
+            db.snapshot_text(snapshots[0].0).unwrap(),
+            Some("two".to_string())
+        );
+        assert_eq!(
+            db.snapshot_text(snapshots[1].0).unwrap(),
+            Some("one".to_string())
+        );
+
+        // Only the newest snapshots of a file are kept.
+        for (ix, text) in ["three", "four", "five"].into_iter().enumerate() {
+            db.record_snapshot(
+                path.clone(),
+                text.into(),
+                13 + ix as i64,
+                SnapshotSource::Saved,
+                3,
+                0,
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(
+            db.snapshots(path.clone())
+                .unwrap()
+                .into_iter()
+                .map(|(id, _, _)| db.snapshot_text(id).unwrap().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["five", "four", "three"]
+        );
+
+        // Snapshots older than the oldest timestamp are removed.
+        db.record_snapshot(path.clone(), "six".into(), 20, SnapshotSource::Saved, 3, 15)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.snapshots(path.clone())
+                .unwrap()
+                .into_iter()
+                .map(|(_, timestamp, _)| timestamp)
+                .collect::<Vec<_>>(),
+            vec![20, 15]
+        );
+        assert_eq!(db.snapshots(other_path).unwrap().len(), 1);
+    }
+}
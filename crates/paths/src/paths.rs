@@ -198,6 +198,14 @@ pub fn embeddings_dir() -> &'static PathBuf {
     })
 }
 
+/// Returns the path to the project search index directory.
+///
+/// This is where the trigram indices used to speed up project search are stored.
+pub fn search_index_dir() -> &'static PathBuf {
+    static SEARCH_INDEX_DIR: OnceLock<PathBuf> = OnceLock::new();
+    SEARCH_INDEX_DIR.get_or_init(|| support_dir().join("search_index"))
+}
+
 /// Returns the path to the languages directory.
 ///
 /// This is where language servers are downloaded to for languages built-in to Zed.
@@ -11,6 +11,7 @@ pub mod terminals;
 #[cfg(test)]
 mod project_tests;
 pub mod search_history;
+pub mod search_index;
 mod yarn;
 
 use anyhow::{anyhow, bail, Context as _, Result};
@@ -54,8 +55,8 @@ use language::{
     range_from_lsp, Bias, Buffer, BufferSnapshot, CachedLspAdapter, Capability, CodeLabel,
     ContextProvider, Diagnostic, DiagnosticEntry, DiagnosticSet, Diff, Documentation,
     Event as BufferEvent, File as _, Language, LanguageRegistry, LanguageServerName, LocalFile,
-    LspAdapterDelegate, Operation, Patch, PendingLanguageServer, PointUtf16, TextBufferSnapshot,
-    ToOffset, ToPointUtf16, Transaction, Unclipped,
+    LspAdapterDelegate, Operation, ParseStatus, Patch, PendingLanguageServer, PointUtf16,
+    TextBufferSnapshot, ToOffset, ToPointUtf16, Transaction, Unclipped,
 };
 use log::error;
 use lsp::{
@@ -78,8 +79,11 @@ use rand::prelude::*;
 use rpc::{ErrorCode, ErrorExt as _};
 use search::SearchQuery;
 use search_history::SearchHistory;
+use search_index::{CandidateFilter, TrigramIndex, TrigramQuery, WorktreeSearchIndex};
 use serde::Serialize;
-use settings::{watch_config_file, Settings, SettingsLocation, SettingsStore};
+use settings::{
+    watch_config_file, EditorConfig, Settings, SettingsLocation, SettingsStore, EDITORCONFIG_NAME,
+};
 use sha2::{Digest, Sha256};
 use similar::{ChangeTag, TextDiff};
 use smol::channel::{Receiver, Sender};
@@ -215,10 +219,15 @@ pub struct Project {
     opened_buffers: HashMap<BufferId, OpenBuffer>,
     local_buffer_ids_by_path: HashMap<ProjectPath, BufferId>,
     local_buffer_ids_by_entry_id: HashMap<ProjectEntryId, BufferId>,
+    /// The paths registered with the [`SettingsStore`] for each buffer's file.
+    buffer_settings_paths: HashMap<BufferId, (usize, Arc<Path>)>,
     buffer_snapshots: HashMap<BufferId, HashMap<LanguageServerId, Vec<LspBufferSnapshot>>>, // buffer_id -> server_id -> vec of snapshots
     buffers_being_formatted: HashSet<BufferId>,
     buffers_needing_diff: HashSet<WeakModel<Buffer>>,
     git_diff_debouncer: DebouncedDelay,
+    search_indices: HashMap<WorktreeId, WorktreeSearchIndex>,
+    /// The tasks watching the `.editorconfig` files above the root of each local worktree.
+    parent_editorconfig_watchers: HashMap<WorktreeId, Task<()>>,
     nonce: u128,
     _maintain_buffer_languages: Task<()>,
     _maintain_workspace_config: Task<Result<()>>,
@@ -364,6 +373,10 @@ pub enum Event {
     RefreshInlayHints,
     RevealInProjectPanel(ProjectEntryId),
     SnippetEdit(BufferId, Vec<(lsp::Range, Snippet)>),
+    /// A buffer was saved to disk.
+    BufferSaved(Model<Buffer>),
+    /// A buffer was reloaded after its file changed on disk.
+    BufferReloaded(Model<Buffer>),
 }
 
 pub enum LanguageServerState {
@@ -741,8 +754,11 @@ impl Project {
                 shared_buffers: Default::default(),
                 loading_buffers_by_path: Default::default(),
                 loading_local_worktrees: Default::default(),
+                search_indices: Default::default(),
+                parent_editorconfig_watchers: Default::default(),
                 local_buffer_ids_by_path: Default::default(),
                 local_buffer_ids_by_entry_id: Default::default(),
+                buffer_settings_paths: Default::default(),
                 buffer_snapshots: Default::default(),
                 join_project_response_message_id: 0,
                 client_state: ProjectClientState::Local,
@@ -887,8 +903,11 @@ impl Project {
                 shared_buffers: Default::default(),
                 incomplete_remote_buffers: Default::default(),
                 loading_local_worktrees: Default::default(),
+                search_indices: Default::default(),
+                parent_editorconfig_watchers: Default::default(),
                 local_buffer_ids_by_path: Default::default(),
                 local_buffer_ids_by_entry_id: Default::default(),
+                buffer_settings_paths: Default::default(),
                 active_entry: None,
                 collaborators: Default::default(),
                 join_project_response_message_id: response.message_id,
@@ -1240,9 +1259,34 @@ impl Project {
             self.restart_language_servers(worktree, language, cx);
         }
 
+        self.maintain_search_indices(cx);
+
         cx.notify();
     }
 
+    /// Starts or stops indexing local worktrees for project search, according to
+    /// the search index settings.
+    fn maintain_search_indices(&mut self, cx: &mut ModelContext<Self>) {
+        if !ProjectSettings::get_global(cx).search_index.enabled {
+            self.search_indices.clear();
+            return;
+        }
+
+        let worktrees = self.worktrees().collect::<Vec<_>>();
+        self.search_indices.retain(|worktree_id, _| {
+            worktrees
+                .iter()
+                .any(|worktree| worktree.read(cx).id() == *worktree_id)
+        });
+        for worktree in worktrees {
+            let worktree_id = worktree.read(cx).id();
+            if worktree.read(cx).is_local() && !self.search_indices.contains_key(&worktree_id) {
+                let index = WorktreeSearchIndex::new(&worktree, self.fs.clone(), cx);
+                self.search_indices.insert(worktree_id, index);
+            }
+        }
+    }
+
     pub fn buffer_for_id(&self, remote_id: BufferId) -> Option<Model<Buffer>> {
         self.opened_buffers
             .get(&remote_id)
@@ -1592,6 +1636,18 @@ impl Project {
                     })
                     .log_err();
             }
+            for (directory, content) in
+                store.local_editorconfigs(worktree.entity_id().as_u64() as usize)
+            {
+                self.client
+                    .send(proto::UpdateWorktreeSettings {
+                        project_id,
+                        worktree_id,
+                        path: directory.join(EDITORCONFIG_NAME).to_string_lossy().into(),
+                        content: Some(content),
+                    })
+                    .log_err();
+            }
         }
 
         let (updates_tx, mut updates_rx) = mpsc::unbounded();
@@ -2342,7 +2398,9 @@ impl Project {
         let buffer = buffer_handle.read(cx);
         let buffer_id = buffer.remote_id();
         let text = buffer.as_rope().clone();
-        let line_ending = buffer.line_ending();
+        let line_ending = language_settings(buffer.language(), buffer.file(), cx)
+            .line_ending
+            .unwrap_or_else(|| buffer.line_ending());
         let version = buffer.version();
         if buffer.file().is_some_and(|file| !file.is_created()) {
             has_changed_file = true;
@@ -2498,9 +2556,15 @@ impl Project {
             }
         }
 
+        self.register_buffer_settings_path(buffer, cx);
         self.detect_language_for_buffer(buffer, cx);
         self.register_buffer_with_language_servers(buffer, cx);
         cx.observe_release(buffer, |this, buffer, cx| {
+            if let Some((root_id, path)) = this.buffer_settings_paths.remove(&buffer.remote_id()) {
+                cx.update_global::<SettingsStore, _>(|store, _| {
+                    store.unregister_local_file(root_id, &path)
+                });
+            }
             if let Some(file) = File::from_dyn(buffer.file()) {
                 if file.is_local() {
                     let uri = lsp::Url::from_file_path(file.abs_path(cx)).unwrap();
@@ -2527,6 +2591,36 @@ impl Project {
         Ok(())
     }
 
+    /// Registers the path of the buffer's file with the [`SettingsStore`], so that
+    /// the `.editorconfig` properties matching it apply to its settings.
+    fn register_buffer_settings_path(
+        &mut self,
+        buffer: &Model<Buffer>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let buffer = buffer.read(cx);
+        let buffer_id = buffer.remote_id();
+        let settings_path = buffer
+            .file()
+            .map(|file| (file.worktree_id(), file.path().clone()));
+        let old_settings_path = match settings_path.clone() {
+            Some(settings_path) => self.buffer_settings_paths.insert(buffer_id, settings_path),
+            None => self.buffer_settings_paths.remove(&buffer_id),
+        };
+        if old_settings_path == settings_path {
+            return;
+        }
+
+        cx.update_global::<SettingsStore, _>(|store, cx| {
+            if let Some((root_id, path)) = old_settings_path {
+                store.unregister_local_file(root_id, &path);
+            }
+            if let Some((root_id, path)) = settings_path {
+                store.register_local_file(root_id, path, cx).log_err();
+            }
+        });
+    }
+
     fn register_buffer_with_language_servers(
         &mut self,
         buffer_handle: &Model<Buffer>,
@@ -2781,6 +2875,7 @@ impl Project {
             }
 
             BufferEvent::Reloaded => {
+                cx.emit(Event::BufferReloaded(buffer.clone()));
                 if self.is_local() {
                     if let Some(project_id) = self.remote_id() {
                         let buffer = buffer.read(cx);
@@ -2893,6 +2988,7 @@ impl Project {
             }
 
             BufferEvent::Saved => {
+                cx.emit(Event::BufferSaved(buffer.clone()));
                 let file = File::from_dyn(buffer.read(cx).file())?;
                 let worktree_id = file.worktree_id(cx);
                 let abs_path = file.as_local()?.abs_path(cx);
@@ -2917,6 +3013,7 @@ impl Project {
                 }
             }
             BufferEvent::FileHandleChanged => {
+                self.register_buffer_settings_path(&buffer, cx);
                 let Some(file) = File::from_dyn(buffer.read(cx).file()) else {
                     return None;
                 };
@@ -7271,6 +7368,14 @@ impl Project {
             })
             .collect::<Vec<_>>();
         let include_root = snapshots.len() > 1;
+        let search_indices = snapshots
+            .iter()
+            .map(|(snapshot, _)| {
+                self.search_indices
+                    .get(&snapshot.id())
+                    .map(WorktreeSearchIndex::index)
+            })
+            .collect::<Vec<_>>();
 
         let background = cx.background_executor().clone();
         let path_count: usize = snapshots
@@ -7333,6 +7438,7 @@ impl Project {
                 include_root,
                 path_count,
                 snapshots,
+                search_indices,
                 matching_paths_tx,
             ))
             .detach();
@@ -7381,6 +7487,13 @@ impl Project {
 
                     chunk_results.push(cx.spawn(|cx| async move {
                         let buffer = buffer.await?;
+                        if query.is_structural() {
+                            // Structural queries match against the syntax tree, which
+                            // may still be parsing for buffers that were just opened.
+                            let parse_status =
+                                buffer.read_with(&cx, |buffer, _| buffer.parse_status())?;
+                            wait_for_parsed_buffer(parse_status).await;
+                        }
                         let snapshot = buffer.read_with(&cx, |buffer, _| buffer.snapshot())?;
                         let ranges = cx
                             .background_executor()
@@ -7403,6 +7516,11 @@ impl Project {
                 let chunk_results = futures::future::join_all(chunk_results).await;
                 for result in chunk_results {
                     if let Some((buffer, ranges)) = result.log_err() {
+                        // Candidates are picked by an approximate check for structural
+                        // queries and unsaved buffers, so they may not match after all.
+                        if ranges.is_empty() {
+                            continue;
+                        }
                         range_count += ranges.len();
                         result_tx
                             .send(SearchResult::Buffer { buffer, ranges })
@@ -7438,12 +7556,22 @@ impl Project {
         include_root: bool,
         path_count: usize,
         snapshots: Vec<(Snapshot, WorktreeSettings)>,
+        search_indices: Vec<Option<Arc<RwLock<TrigramIndex>>>>,
         matching_paths_tx: Sender<SearchMatchCandidate>,
     ) {
+        // Rule out the indexed files that can't contain a match up front, so that
+        // they don't need to be read.
+        let trigram_query = TrigramQuery::for_search_query(&query);
+        let candidate_filters = search_indices
+            .iter()
+            .map(|index| index.as_ref()?.read().filter(&trigram_query))
+            .collect::<Vec<_>>();
+
         let fs = &fs;
         let query = &query;
         let matching_paths_tx = &matching_paths_tx;
         let snapshots = &snapshots;
+        let candidate_filters = &candidate_filters;
         for buffer in unnamed_buffers {
             matching_paths_tx
                 .send(SearchMatchCandidate::OpenBuffer {
@@ -7479,6 +7607,7 @@ impl Project {
                             let _guard = limiter.acquire().await;
                             search_snapshots(
                                 snapshots,
+                                candidate_filters,
                                 worker_start_ix,
                                 worker_end_ix,
                                 query,
@@ -7847,6 +7976,8 @@ impl Project {
 
     pub fn remove_worktree(&mut self, id_to_remove: WorktreeId, cx: &mut ModelContext<Self>) {
         self.diagnostics.remove(&id_to_remove);
+        self.search_indices.remove(&id_to_remove);
+        self.parent_editorconfig_watchers.remove(&id_to_remove);
         self.diagnostic_summaries.remove(&id_to_remove);
 
         let mut servers_to_remove = HashMap::default();
@@ -7932,6 +8063,9 @@ impl Project {
                         this.update_local_worktree_language_servers(&worktree, changes, cx);
                         this.update_local_worktree_settings(&worktree, changes, cx);
                         this.update_prettier_settings(&worktree, changes, cx);
+                        if let Some(index) = this.search_indices.get(&worktree.read(cx).id()) {
+                            index.update(worktree.read(cx).snapshot(), changes.clone());
+                        }
                     }
 
                     cx.emit(Event::WorktreeUpdatedEntries(
@@ -7991,10 +8125,96 @@ impl Project {
         })
         .detach();
 
+        self.watch_parent_editorconfigs(worktree, cx);
         cx.emit(Event::WorktreeAdded);
+        self.maintain_search_indices(cx);
         self.metadata_changed(cx);
     }
 
+    /// Loads the `.editorconfig` files located above the root of a local
+    /// worktree, up to the first one marked with `root = true`, and reloads
+    /// them whenever one of them is created, changed or removed.
+    fn watch_parent_editorconfigs(
+        &mut self,
+        worktree: &Model<Worktree>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        if !worktree.read(cx).is_local() {
+            return;
+        }
+        let root_id = worktree.entity_id().as_u64() as usize;
+        let worktree_id = worktree.read(cx).id();
+        let abs_path = worktree.read(cx).abs_path();
+        let fs = self.fs.clone();
+        let task = cx.spawn(move |this, mut cx| async move {
+            let (events, _watchers): (Vec<_>, Vec<_>) =
+                join_all(abs_path.ancestors().skip(1).map(|directory| {
+                    fs.watch(&directory.join(EDITORCONFIG_NAME), FS_WATCH_LATENCY)
+                }))
+                .await
+                .into_iter()
+                .unzip();
+            let mut events = futures::stream::select_all(events);
+
+            let mut loaded_editorconfigs = Vec::<(Arc<Path>, String)>::new();
+            loop {
+                let editorconfigs = load_parent_editorconfigs(fs.as_ref(), &abs_path).await;
+                let removed = loaded_editorconfigs
+                    .iter()
+                    .filter(|(directory, _)| {
+                        !editorconfigs
+                            .iter()
+                            .any(|(new_directory, _)| new_directory == directory)
+                    })
+                    .map(|(directory, _)| (directory.clone(), None));
+                let updated = editorconfigs
+                    .iter()
+                    .filter(|editorconfig| !loaded_editorconfigs.contains(editorconfig))
+                    .map(|(directory, content)| (directory.clone(), Some(content.clone())));
+                let changes = removed.chain(updated).collect::<Vec<_>>();
+                if !changes.is_empty() {
+                    let updated = this.update(&mut cx, |this, cx| {
+                        let project_id = this.remote_id();
+                        cx.update_global::<SettingsStore, _>(|store, cx| {
+                            for (directory, content) in changes {
+                                store
+                                    .set_local_editorconfig(
+                                        root_id,
+                                        directory.clone(),
+                                        content.as_deref(),
+                                        cx,
+                                    )
+                                    .log_err();
+                                if let Some(project_id) = project_id {
+                                    this.client
+                                        .send(proto::UpdateWorktreeSettings {
+                                            project_id,
+                                            worktree_id: worktree_id.to_proto(),
+                                            path: directory
+                                                .join(EDITORCONFIG_NAME)
+                                                .to_string_lossy()
+                                                .into_owned(),
+                                            content,
+                                        })
+                                        .log_err();
+                                }
+                            }
+                        });
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                    loaded_editorconfigs = editorconfigs;
+                }
+
+                if events.next().await.is_none() {
+                    break;
+                }
+            }
+        });
+        self.parent_editorconfig_watchers.insert(worktree_id, task);
+    }
+
     fn update_local_worktree_buffers(
         &mut self,
         worktree_handle: &Model<Worktree>,
@@ -8325,6 +8545,7 @@ impl Project {
         let remote_worktree_id = worktree.read(cx).id();
 
         let mut settings_contents = Vec::new();
+        let mut editorconfig_contents = Vec::new();
         for (path, _, change) in changes.iter() {
             let removed = change == &PathChange::Removed;
             let abs_path = match worktree.read(cx).absolutize(path) {
@@ -8352,6 +8573,19 @@ impl Project {
                         },
                     )
                 });
+            } else if path.ends_with(EDITORCONFIG_NAME) {
+                let editorconfig_dir = Arc::from(path.parent().unwrap_or(Path::new("")));
+                let fs = self.fs.clone();
+                editorconfig_contents.push(async move {
+                    (
+                        editorconfig_dir,
+                        if removed {
+                            None
+                        } else {
+                            Some(async move { fs.load(&abs_path).await }.await)
+                        },
+                    )
+                });
             } else if path.ends_with(local_tasks_file_relative_path()) {
                 self.task_inventory().update(cx, |task_inventory, cx| {
                     if removed {
@@ -8401,7 +8635,7 @@ impl Project {
             }
         }
 
-        if settings_contents.is_empty() {
+        if settings_contents.is_empty() && editorconfig_contents.is_empty() {
             return;
         }
 
@@ -8409,8 +8643,34 @@ impl Project {
         cx.spawn(move |_, cx| async move {
             let settings_contents: Vec<(Arc<Path>, _)> =
                 futures::future::join_all(settings_contents).await;
+            let editorconfig_contents: Vec<(Arc<Path>, _)> =
+                futures::future::join_all(editorconfig_contents).await;
             cx.update(|cx| {
                 cx.update_global::<SettingsStore, _>(|store, cx| {
+                    for (directory, file_content) in editorconfig_contents {
+                        let file_content = file_content.and_then(|content| content.log_err());
+                        store
+                            .set_local_editorconfig(
+                                worktree_id.as_u64() as usize,
+                                directory.clone(),
+                                file_content.as_deref(),
+                                cx,
+                            )
+                            .log_err();
+                        if let Some(remote_id) = project_id {
+                            client
+                                .send(proto::UpdateWorktreeSettings {
+                                    project_id: remote_id,
+                                    worktree_id: remote_worktree_id.to_proto(),
+                                    path: directory
+                                        .join(EDITORCONFIG_NAME)
+                                        .to_string_lossy()
+                                        .into_owned(),
+                                    content: file_content,
+                                })
+                                .log_err();
+                        }
+                    }
                     for (directory, file_content) in settings_contents {
                         let file_content = file_content.and_then(|content| content.log_err());
                         store
@@ -8995,15 +9255,31 @@ impl Project {
         this.update(&mut cx, |this, cx| {
             let worktree_id = WorktreeId::from_proto(envelope.payload.worktree_id);
             if let Some(worktree) = this.worktree_for_id(worktree_id, cx) {
+                let root_id = worktree.entity_id().as_u64() as usize;
+                let path = PathBuf::from(&envelope.payload.path);
                 cx.update_global::<SettingsStore, _>(|store, cx| {
-                    store
-                        .set_local_settings(
-                            worktree.entity_id().as_u64() as usize,
-                            PathBuf::from(&envelope.payload.path).into(),
-                            envelope.payload.content.as_deref(),
-                            cx,
-                        )
-                        .log_err();
+                    // `.editorconfig` files are sent with their own path, while
+                    // settings files are sent with the directory they apply to.
+                    if path.file_name() == Some(EDITORCONFIG_NAME.as_ref()) {
+                        let directory = path.parent().unwrap_or(Path::new(""));
+                        store
+                            .set_local_editorconfig(
+                                root_id,
+                                directory.into(),
+                                envelope.payload.content.as_deref(),
+                                cx,
+                            )
+                            .log_err();
+                    } else {
+                        store
+                            .set_local_settings(
+                                root_id,
+                                path.into(),
+                                envelope.payload.content.as_deref(),
+                                cx,
+                            )
+                            .log_err();
+                    }
                 });
             }
             Ok(())
@@ -11337,6 +11613,7 @@ fn deserialize_code_actions(code_actions: &HashMap<String, bool>) -> Vec<lsp::Co
 #[allow(clippy::too_many_arguments)]
 async fn search_snapshots(
     snapshots: &Vec<(Snapshot, WorktreeSettings)>,
+    candidate_filters: &[Option<CandidateFilter>],
     worker_start_ix: usize,
     worker_end_ix: usize,
     query: &SearchQuery,
@@ -11348,7 +11625,7 @@ async fn search_snapshots(
     let mut snapshot_start_ix = 0;
     let mut abs_path = PathBuf::new();
 
-    for (snapshot, _) in snapshots {
+    for ((snapshot, _), candidate_filter) in snapshots.iter().zip(candidate_filters) {
         let snapshot_end_ix = snapshot_start_ix
             + if query.include_ignored() {
                 snapshot.file_count()
@@ -11382,8 +11659,11 @@ async fn search_snapshots(
                 } else {
                     query.file_matches(Some(&entry.path))
                 };
+                let may_match = candidate_filter
+                    .as_ref()
+                    .map_or(true, |filter| filter.may_match(entry));
 
-                let matches = if matched_path {
+                let matches = if matched_path && may_match {
                     abs_path.clear();
                     abs_path.push(&snapshot.abs_path());
                     abs_path.push(&entry.path);
@@ -11851,6 +12131,42 @@ async fn wait_for_loading_buffer(
     }
 }
 
+async fn wait_for_parsed_buffer(mut receiver: postage::watch::Receiver<ParseStatus>) {
+    while *receiver.borrow() != ParseStatus::Idle {
+        if receiver.next().await.is_none() {
+            break;
+        }
+    }
+}
+
+/// Returns the directories and contents of the `.editorconfig` files located above
+/// the given worktree root, up to the first one marked with `root = true`.
+///
+/// Directories are given relative to the worktree root, as expected by
+/// [`SettingsStore::set_local_editorconfig`].
+async fn load_parent_editorconfigs(fs: &dyn Fs, abs_path: &Path) -> Vec<(Arc<Path>, String)> {
+    let mut editorconfigs = Vec::new();
+    let mut path_to_directory = PathBuf::new();
+    for directory in abs_path.ancestors().skip(1) {
+        path_to_directory.push("..");
+        let Ok(content) = fs.load(&directory.join(EDITORCONFIG_NAME)).await else {
+            continue;
+        };
+        let Ok(path_to_root) = abs_path.strip_prefix(directory) else {
+            continue;
+        };
+        let is_root = EditorConfig::parse(&content).is_root;
+        editorconfigs.push((
+            Arc::<Path>::from(path_to_directory.join(path_to_root)),
+            content,
+        ));
+        if is_root {
+            break;
+        }
+    }
+    editorconfigs
+}
+
 fn is_not_found_error(error: &anyhow::Error) -> bool {
     error
         .root_cause()
@@ -20,6 +20,20 @@ pub struct ProjectSettings {
     /// Configuration for Git-related features
     #[serde(default)]
     pub git: GitSettings,
+
+    /// Configuration for the project search index
+    #[serde(default)]
+    pub search_index: SearchIndexSettings,
+}
+
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct SearchIndexSettings {
+    /// Whether to maintain an on-disk trigram index of each local worktree,
+    /// so that project search only reads files that may contain a match.
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub enabled: bool,
 }
 
 #[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema)]
@@ -294,6 +294,111 @@ async fn test_managing_project_specific_settings(cx: &mut gpui::TestAppContext)
     );
 }
 
+#[gpui::test]
+async fn test_editorconfig_settings(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/code",
+        json!({
+            ".editorconfig": "root = true\n[*]\nindent_size = 3\nend_of_line = crlf\n",
+            "the-root": {
+                ".editorconfig": "[*.md]\nindent_size = 2\n[Makefile]\nindent_style = tab\n",
+                ".zed": {
+                    "settings.json": r#"{ "hard_tabs": false }"#,
+                },
+                "a.rs": "fn a() {}",
+                "README.md": "# Hello",
+                "Makefile": "all:",
+                "b": {
+                    ".zed": {
+                        "settings.json": r#"{ "tab_size": 8 }"#,
+                    },
+                    "b.md": "# B",
+                },
+            },
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs.clone(), ["/code/the-root".as_ref()], cx).await;
+    let (buffer_a, buffer_readme, buffer_makefile, buffer_b) = project.update(cx, |project, cx| {
+        (
+            project.open_local_buffer("/code/the-root/a.rs", cx),
+            project.open_local_buffer("/code/the-root/README.md", cx),
+            project.open_local_buffer("/code/the-root/Makefile", cx),
+            project.open_local_buffer("/code/the-root/b/b.md", cx),
+        )
+    });
+    let buffer_a = buffer_a.await.unwrap();
+    let buffer_readme = buffer_readme.await.unwrap();
+    let buffer_makefile = buffer_makefile.await.unwrap();
+    let buffer_b = buffer_b.await.unwrap();
+    cx.executor().run_until_parked();
+
+    cx.update(|cx| {
+        let settings_a = language_settings(None, buffer_a.read(cx).file(), cx);
+        let settings_readme = language_settings(None, buffer_readme.read(cx).file(), cx);
+        let settings_makefile = language_settings(None, buffer_makefile.read(cx).file(), cx);
+        let settings_b = language_settings(None, buffer_b.read(cx).file(), cx);
+
+        // The `.editorconfig` above the worktree root applies to all of its files.
+        assert_eq!(settings_a.tab_size.get(), 3);
+        assert_eq!(settings_a.line_ending, Some(LineEnding::Windows));
+        assert_eq!(settings_readme.tab_size.get(), 2);
+        // Local settings files take precedence over `.editorconfig` files.
+        assert!(!settings_makefile.hard_tabs);
+        assert_eq!(settings_b.tab_size.get(), 8);
+    });
+
+    fs.save(
+        "/code/the-root/.editorconfig".as_ref(),
+        &"[*.rs]\nindent_size = 5\n".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+
+    cx.update(|cx| {
+        assert_eq!(
+            language_settings(None, buffer_a.read(cx).file(), cx)
+                .tab_size
+                .get(),
+            5
+        );
+        assert_eq!(
+            language_settings(None, buffer_readme.read(cx).file(), cx)
+                .tab_size
+                .get(),
+            3
+        );
+    });
+
+    // The `.editorconfig` files above the worktree root are watched too.
+    fs.save(
+        "/code/.editorconfig".as_ref(),
+        &"root = true\n[*]\nindent_size = 7\n".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+
+    cx.update(|cx| {
+        let settings_a = language_settings(None, buffer_a.read(cx).file(), cx);
+        assert_eq!(settings_a.tab_size.get(), 5);
+        assert_eq!(settings_a.line_ending, None);
+        assert_eq!(
+            language_settings(None, buffer_readme.read(cx).file(), cx)
+                .tab_size
+                .get(),
+            7
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_managing_language_servers(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -4402,6 +4507,165 @@ async fn test_search_multiple_worktrees_with_inclusions(cx: &mut gpui::TestAppCo
     );
 }
 
+#[gpui::test]
+async fn test_structural_search(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "one.rs": "fn main() { foo(1, bar(2)); foo(3); }",
+            "two.rs": "fn two() { baz(foo(a, b)); }",
+            "three.rs": "fn three() { foo(x, y, z); }",
+            "four.rs": "fn four() { foo( x ,\n y ); }",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    project.read_with(cx, |project, _| project.languages().add(rust_lang()));
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer("/dir/one.rs", cx)
+        })
+        .await
+        .unwrap();
+    let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+
+    let query =
+        SearchQuery::structural("foo($A, $B)", false, Default::default(), Default::default())
+            .unwrap();
+    assert_eq!(
+        search(&project, query.clone(), cx).await.unwrap(),
+        HashMap::from_iter([
+            ("dir/one.rs".to_string(), vec![12..26]),
+            ("dir/two.rs".to_string(), vec![15..24]),
+            ("dir/four.rs".to_string(), vec![12..25]),
+        ])
+    );
+    assert_eq!(
+        query
+            .clone()
+            .with_replacement("foo($B, $A)".to_string())
+            .replacement_for(buffer_id, 12..26, "foo(1, bar(2))")
+            .as_deref(),
+        Some("foo(bar(2), 1)")
+    );
+    assert_eq!(
+        query
+            .clone()
+            .with_replacement("foo($C)".to_string())
+            .replacement_for(buffer_id, 12..26, "foo(1, bar(2))"),
+        None,
+        "replacements referring to missing captures should be skipped"
+    );
+    assert_eq!(
+        query
+            .clone()
+            .with_replacement("foo($B, $A)".to_string())
+            .replacement_for(buffer_id, 28..34, "foo(3)"),
+        None,
+        "there's no match at this range"
+    );
+
+    // Searching the buffer again after an edit drops the captures of the earlier matches.
+    buffer.update(cx, |buffer, cx| buffer.edit([(3..3, " ")], None, cx));
+    cx.executor().run_until_parked();
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+    assert_eq!(query.search(&snapshot, None).await, vec![13..27]);
+    assert_eq!(
+        query
+            .clone()
+            .with_replacement("foo($B, $A)".to_string())
+            .replacement_for(buffer_id, 12..26, "foo(1, bar(2))"),
+        None
+    );
+    assert_eq!(
+        query
+            .clone()
+            .with_replacement("foo($B, $A)".to_string())
+            .replacement_for(buffer_id, 13..27, "foo(1, bar(2))")
+            .as_deref(),
+        Some("foo(bar(2), 1)")
+    );
+
+    assert_eq!(
+        search(
+            &project,
+            SearchQuery::structural(
+                r#"(call_expression function: (identifier) @name (#eq? @name "bar")) @match"#,
+                false,
+                Default::default(),
+                Default::default()
+            )
+            .unwrap(),
+            cx
+        )
+        .await
+        .unwrap(),
+        HashMap::from_iter([("dir/one.rs".to_string(), vec![19..25])])
+    );
+}
+
+#[gpui::test]
+async fn test_search_with_index(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+    cx.update(|cx| {
+        SettingsStore::update_global(cx, |settings, cx| {
+            settings.update_user_settings::<ProjectSettings>(cx, |settings| {
+                settings.search_index.enabled = true;
+            });
+        });
+    });
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        "/dir",
+        json!({
+            "one.rs": "const ONE: usize = 1;",
+            "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+    cx.executor().run_until_parked();
+    project.read_with(cx, |project, cx| {
+        let worktree_id = project.worktrees().next().unwrap().read(cx).id();
+        assert_eq!(project.search_indices[&worktree_id].index().read().len(), 2);
+    });
+
+    let query = SearchQuery::text(
+        "TWO",
+        false,
+        true,
+        false,
+        Default::default(),
+        Default::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        search(&project, query.clone(), cx).await.unwrap(),
+        HashMap::from_iter([("dir/two.rs".to_string(), vec![6..9])])
+    );
+
+    // Files are re-indexed as they change on disk.
+    fs.save(
+        "/dir/one.rs".as_ref(),
+        &"const ONE: usize = two::TWO;".into(),
+        Default::default(),
+    )
+    .await
+    .unwrap();
+    cx.executor().run_until_parked();
+    assert_eq!(
+        search(&project, query, cx).await.unwrap(),
+        HashMap::from_iter([
+            ("dir/one.rs".to_string(), vec![24..27]),
+            ("dir/two.rs".to_string(), vec![6..9]),
+        ])
+    );
+}
+
 #[gpui::test]
 async fn test_search_in_gitignored_dirs(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -11,8 +11,12 @@ use std::{
     path::Path,
     sync::{Arc, OnceLock},
 };
+pub use structural::StructuralPattern;
+use text::BufferId;
 use util::paths::PathMatcher;
 
+mod structural;
+
 static TEXT_REPLACEMENT_SPECIAL_CHARACTERS_REGEX: OnceLock<Regex> = OnceLock::new();
 
 #[derive(Clone, Debug)]
@@ -53,6 +57,13 @@ pub enum SearchQuery {
         include_ignored: bool,
         inner: SearchInputs,
     },
+
+    Structural {
+        pattern: StructuralPattern,
+        replacement: Option<String>,
+        include_ignored: bool,
+        inner: SearchInputs,
+    },
 }
 
 impl SearchQuery {
@@ -122,8 +133,38 @@ impl SearchQuery {
         })
     }
 
+    /// Creates a query that matches the syntax trees of buffers against a tree-sitter
+    /// query or a code pattern with `$NAME` metavariables.
+    pub fn structural(
+        query: impl ToString,
+        include_ignored: bool,
+        files_to_include: PathMatcher,
+        files_to_exclude: PathMatcher,
+    ) -> Result<Self> {
+        let query = query.to_string();
+        let pattern = StructuralPattern::new(&query)?;
+        let inner = SearchInputs {
+            query: query.into(),
+            files_to_exclude,
+            files_to_include,
+        };
+        Ok(Self::Structural {
+            pattern,
+            replacement: None,
+            include_ignored,
+            inner,
+        })
+    }
+
     pub fn from_proto(message: proto::SearchProject) -> Result<Self> {
-        if message.regex {
+        if message.structural {
+            Self::structural(
+                message.query,
+                message.include_ignored,
+                deserialize_path_matches(&message.files_to_include)?,
+                deserialize_path_matches(&message.files_to_exclude)?,
+            )
+        } else if message.regex {
             Self::regex(
                 message.query,
                 message.whole_word,
@@ -152,6 +193,10 @@ impl SearchQuery {
             | Self::Regex {
                 ref mut replacement,
                 ..
+            }
+            | Self::Structural {
+                ref mut replacement,
+                ..
             } => {
                 *replacement = Some(new_replacement);
                 self
@@ -163,6 +208,7 @@ impl SearchQuery {
             project_id,
             query: self.as_str().to_string(),
             regex: self.is_regex(),
+            structural: self.is_structural(),
             whole_word: self.whole_word(),
             case_sensitive: self.case_sensitive(),
             include_ignored: self.include_ignored(),
@@ -206,39 +252,47 @@ impl SearchQuery {
                     Ok(false)
                 }
             }
+            Self::Structural { pattern, .. } => pattern.detect(stream),
         }
     }
     /// Returns the replacement text for this `SearchQuery`.
     pub fn replacement(&self) -> Option<&str> {
         match self {
-            SearchQuery::Text { replacement, .. } | SearchQuery::Regex { replacement, .. } => {
-                replacement.as_deref()
-            }
+            SearchQuery::Text { replacement, .. }
+            | SearchQuery::Regex { replacement, .. }
+            | SearchQuery::Structural { replacement, .. } => replacement.as_deref(),
         }
     }
     /// Replaces search hits if replacement is set. `text` is assumed to be a string that matches this `SearchQuery` exactly, without any leftovers on either side.
-    pub fn replacement_for<'a>(&self, text: &'a str) -> Option<Cow<'a, str>> {
+    /// `range` is where the hit was found in the buffer with `buffer_id`, which structural queries use to look up the hit's captures.
+    /// Structural queries return `None` if the replacement refers to a capture that the hit doesn't have.
+    pub fn replacement_for<'a>(
+        &self,
+        buffer_id: BufferId,
+        range: Range<usize>,
+        text: &'a str,
+    ) -> Option<Cow<'a, str>> {
         match self {
             SearchQuery::Text { replacement, .. } => replacement.clone().map(Cow::from),
             SearchQuery::Regex {
                 regex, replacement, ..
             } => {
                 if let Some(replacement) = replacement {
-                    let replacement = TEXT_REPLACEMENT_SPECIAL_CHARACTERS_REGEX
-                        .get_or_init(|| Regex::new(r"\\\\|\\n|\\t").unwrap())
-                        .replace_all(replacement, |c: &Captures| {
-                            match c.get(0).unwrap().as_str() {
-                                r"\\" => "\\",
-                                r"\n" => "\n",
-                                r"\t" => "\t",
-                                x => unreachable!("Unexpected escape sequence: {}", x),
-                            }
-                        });
+                    let replacement = unescape_replacement(replacement);
                     Some(regex.replace(text, replacement))
                 } else {
                     None
                 }
             }
+            SearchQuery::Structural {
+                pattern,
+                replacement,
+                ..
+            } => replacement.as_deref().and_then(|replacement| {
+                pattern
+                    .replace(buffer_id, range, &unescape_replacement(replacement))
+                    .map(Cow::Owned)
+            }),
         }
     }
 
@@ -326,6 +380,15 @@ impl SearchQuery {
                     }
                 }
             }
+
+            Self::Structural { pattern, .. } => {
+                let range = range_offset..range_offset + rope.len();
+                matches = pattern
+                    .search(buffer, range)
+                    .into_iter()
+                    .map(|range| range.start - range_offset..range.end - range_offset)
+                    .collect();
+            }
         }
 
         matches
@@ -343,6 +406,7 @@ impl SearchQuery {
         match self {
             Self::Text { whole_word, .. } => *whole_word,
             Self::Regex { whole_word, .. } => *whole_word,
+            Self::Structural { .. } => false,
         }
     }
 
@@ -350,6 +414,7 @@ impl SearchQuery {
         match self {
             Self::Text { case_sensitive, .. } => *case_sensitive,
             Self::Regex { case_sensitive, .. } => *case_sensitive,
+            Self::Structural { .. } => true,
         }
     }
 
@@ -361,6 +426,9 @@ impl SearchQuery {
             Self::Regex {
                 include_ignored, ..
             } => *include_ignored,
+            Self::Structural {
+                include_ignored, ..
+            } => *include_ignored,
         }
     }
 
@@ -368,6 +436,10 @@ impl SearchQuery {
         matches!(self, Self::Regex { .. })
     }
 
+    pub fn is_structural(&self) -> bool {
+        matches!(self, Self::Structural { .. })
+    }
+
     pub fn files_to_include(&self) -> &PathMatcher {
         self.as_inner().files_to_include()
     }
@@ -397,11 +469,26 @@ impl SearchQuery {
     }
     pub fn as_inner(&self) -> &SearchInputs {
         match self {
-            Self::Regex { inner, .. } | Self::Text { inner, .. } => inner,
+            Self::Regex { inner, .. }
+            | Self::Text { inner, .. }
+            | Self::Structural { inner, .. } => inner,
         }
     }
 }
 
+fn unescape_replacement(replacement: &str) -> Cow<'_, str> {
+    TEXT_REPLACEMENT_SPECIAL_CHARACTERS_REGEX
+        .get_or_init(|| Regex::new(r"\\\\|\\n|\\t").unwrap())
+        .replace_all(replacement, |c: &Captures| {
+            match c.get(0).unwrap().as_str() {
+                r"\\" => "\\",
+                r"\n" => "\n",
+                r"\t" => "\t",
+                x => unreachable!("Unexpected escape sequence: {}", x),
+            }
+        })
+}
+
 fn deserialize_path_matches(glob_set: &str) -> anyhow::Result<PathMatcher> {
     let globs = glob_set
         .split(',')
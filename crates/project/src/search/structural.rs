@@ -0,0 +1,432 @@
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use anyhow::Result;
+use collections::HashMap;
+use language::{BufferSnapshot, Language, Node};
+use parking_lot::Mutex;
+use regex::{Captures, Regex};
+use std::{
+    fmt,
+    io::Read,
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
+use text::{BufferId, Rope};
+use tree_sitter::{Query, QueryCursor};
+
+static METAVARIABLE_REGEX: OnceLock<Regex> = OnceLock::new();
+static TEMPLATE_VARIABLE_REGEX: OnceLock<Regex> = OnceLock::new();
+static LITERAL_WORD_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// Identifiers that metavariables are rewritten to before the pattern is parsed,
+/// so that `foo($A)` is valid syntax in the pattern's language.
+const METAVARIABLE_PREFIX: &str = "zed_metavariable_";
+
+/// The capture that determines the matched range of a raw tree-sitter query.
+const MATCH_CAPTURE: &str = "match";
+
+/// A search pattern that is matched against the syntax trees of buffers instead of their text.
+///
+/// The pattern is either a raw tree-sitter query, like `(call_expression) @match`,
+/// or a snippet of code in which `$NAME` metavariables stand for any syntax node,
+/// like `foo($A, $B)`. Patterns are compiled lazily, once for each language they are
+/// matched against.
+#[derive(Clone)]
+pub struct StructuralPattern {
+    source: Arc<str>,
+    literal: Option<Arc<AhoCorasick>>,
+    state: Arc<Mutex<PatternState>>,
+}
+
+#[derive(Default)]
+struct PatternState {
+    compiled: HashMap<Arc<str>, Option<Arc<CompiledPattern>>>,
+    /// Captures of the matches found by the latest search of each buffer, so that
+    /// replacements can refer to them.
+    captures_by_buffer: HashMap<BufferId, BufferCaptures>,
+}
+
+struct BufferCaptures {
+    /// The version of the buffer that the matches were found in.
+    version: clock::Global,
+    captures_by_match: HashMap<Range<usize>, Vec<(String, String)>>,
+}
+
+enum CompiledPattern {
+    Query(Query),
+    Code(PatternNode),
+}
+
+struct PatternNode {
+    kind_id: u16,
+    /// The text of named leaves, which has to match exactly.
+    text: Option<String>,
+    metavariable: Option<String>,
+    children: Vec<PatternNode>,
+}
+
+impl fmt::Debug for StructuralPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructuralPattern")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StructuralPattern {
+    pub fn new(source: &str) -> Result<Self> {
+        let literal = if is_query_source(source) {
+            None
+        } else {
+            let pattern = metavariable_regex().replace_all(source, "");
+            literal_word_regex()
+                .find_iter(&pattern)
+                .map(|word| word.as_str())
+                .max_by_key(|word| word.len())
+                .map(|word| AhoCorasickBuilder::new().build([word]))
+                .transpose()?
+                .map(Arc::new)
+        };
+        Ok(Self {
+            source: source.into(),
+            literal,
+            state: Default::default(),
+        })
+    }
+
+    /// Returns whether the stream could contain a match, based on the longest literal
+    /// word of a code pattern. Raw tree-sitter queries can match anything.
+    pub fn detect<T: Read>(&self, stream: T) -> Result<bool> {
+        let Some(literal) = &self.literal else {
+            return Ok(true);
+        };
+        match literal.stream_find_iter(stream).next() {
+            Some(Ok(_)) => Ok(true),
+            Some(Err(err)) => Err(err.into()),
+            None => Ok(false),
+        }
+    }
+
+    /// Finds all matches within the given range of the buffer, in every syntax layer
+    /// whose language the pattern can be compiled for.
+    pub fn search(&self, buffer: &BufferSnapshot, range: Range<usize>) -> Vec<Range<usize>> {
+        let rope = buffer.as_rope();
+        let mut matches = Vec::new();
+        for layer in buffer.syntax_layers() {
+            let Some(pattern) = self.compiled_for(layer.language) else {
+                continue;
+            };
+            let root = layer.node();
+            if root.end_byte() <= range.start || root.start_byte() >= range.end {
+                continue;
+            }
+
+            let mut layer_matches = match pattern.as_ref() {
+                CompiledPattern::Query(query) => query_matches(query, root, rope, &range),
+                CompiledPattern::Code(pattern) => code_matches(pattern, root, rope, &range),
+            };
+            matches.append(&mut layer_matches);
+        }
+
+        matches.sort_by_key(|(range, _)| (range.start, std::cmp::Reverse(range.end)));
+        let mut state = self.state.lock();
+        let buffer_captures = state
+            .captures_by_buffer
+            .entry(buffer.remote_id())
+            .or_insert_with(|| BufferCaptures {
+                version: buffer.version().clone(),
+                captures_by_match: HashMap::default(),
+            });
+        if buffer_captures.version == *buffer.version() {
+            // This search replaces any previous one of the same range of the buffer.
+            buffer_captures.captures_by_match.retain(|match_range, _| {
+                match_range.end <= range.start || match_range.start >= range.end
+            });
+        } else {
+            // The ranges of matches found before the buffer was edited are stale.
+            buffer_captures.version = buffer.version().clone();
+            buffer_captures.captures_by_match.clear();
+        }
+        let mut result: Vec<Range<usize>> = Vec::with_capacity(matches.len());
+        for (match_range, captures) in matches {
+            if result
+                .last()
+                .map_or(false, |last| last.end > match_range.start)
+            {
+                continue;
+            }
+            buffer_captures
+                .captures_by_match
+                .insert(match_range.clone(), captures);
+            result.push(match_range);
+        }
+        result
+    }
+
+    /// Expands `$NAME` references in the template with the captures of the match that
+    /// the latest search found at `range` of the given buffer. `$$` inserts a literal `$`.
+    ///
+    /// Returns `None` if the template refers to a capture that the match doesn't have,
+    /// including when no match was found at `range`.
+    pub fn replace(
+        &self,
+        buffer_id: BufferId,
+        range: Range<usize>,
+        template: &str,
+    ) -> Option<String> {
+        let state = self.state.lock();
+        let captures = state
+            .captures_by_buffer
+            .get(&buffer_id)
+            .and_then(|buffer_captures| buffer_captures.captures_by_match.get(&range));
+        let mut missing_capture = false;
+        let replaced = template_variable_regex().replace_all(template, |caps: &Captures| {
+            let name = &caps[1];
+            if name == "$" {
+                return "$".to_string();
+            }
+            let capture = captures.and_then(|captures| {
+                captures
+                    .iter()
+                    .find(|(capture, _)| capture == name)
+                    .map(|(_, text)| text.clone())
+            });
+            capture.unwrap_or_else(|| {
+                missing_capture = true;
+                String::new()
+            })
+        });
+        (!missing_capture).then(|| replaced.into_owned())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn compiled_for(&self, language: &Arc<Language>) -> Option<Arc<CompiledPattern>> {
+        let name = language.name();
+        if let Some(compiled) = self.state.lock().compiled.get(&name) {
+            return compiled.clone();
+        }
+
+        // Parsing the pattern borrows this thread's parser, so it's done without holding
+        // the lock that every search of the pattern shares.
+        let compiled = compile(&self.source, language).map(Arc::new);
+        self.state
+            .lock()
+            .compiled
+            .entry(name)
+            .or_insert(compiled)
+            .clone()
+    }
+}
+
+fn compile(source: &str, language: &Arc<Language>) -> Option<CompiledPattern> {
+    let grammar = language.grammar()?;
+    let source = source.trim();
+    if is_query_source(source) {
+        if let Ok(query) = Query::new(&grammar.ts_language, source) {
+            return Some(CompiledPattern::Query(query));
+        }
+    }
+
+    let text = metavariable_regex().replace_all(source, |caps: &Captures| {
+        format!("{METAVARIABLE_PREFIX}{}", &caps[1])
+    });
+    let tree = language::with_parser(|parser| {
+        parser.set_language(&grammar.ts_language).ok()?;
+        parser.parse(text.as_bytes(), None)
+    })?;
+    let node = tree.root_node().descendant_for_byte_range(0, text.len())?;
+    if node.has_error() {
+        return None;
+    }
+    Some(CompiledPattern::Code(PatternNode::new(node, &text)))
+}
+
+impl PatternNode {
+    fn new(node: Node, source: &str) -> Self {
+        let text = &source[node.byte_range()];
+        if node.is_named() {
+            let metavariable = text.strip_prefix(METAVARIABLE_PREFIX).filter(|name| {
+                name.chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            });
+            if let Some(name) = metavariable {
+                return Self {
+                    kind_id: node.kind_id(),
+                    text: None,
+                    metavariable: Some(name.to_string()),
+                    children: Vec::new(),
+                };
+            }
+        }
+
+        let mut cursor = node.walk();
+        let children = node
+            .children(&mut cursor)
+            .filter(|child| !child.is_extra())
+            .map(|child| Self::new(child, source))
+            .collect::<Vec<_>>();
+        Self {
+            kind_id: node.kind_id(),
+            text: (node.is_named() && children.is_empty()).then(|| text.to_string()),
+            metavariable: None,
+            children,
+        }
+    }
+
+    fn matches(&self, node: Node, rope: &Rope, bindings: &mut Vec<(String, Range<usize>)>) -> bool {
+        if let Some(name) = &self.metavariable {
+            if let Some((_, bound)) = bindings.iter().find(|(bound, _)| bound == name) {
+                return bound.len() == node.byte_range().len()
+                    && rope
+                        .chunks_in_range(bound.clone())
+                        .flat_map(str::bytes)
+                        .eq(rope.chunks_in_range(node.byte_range()).flat_map(str::bytes));
+            }
+            bindings.push((name.clone(), node.byte_range()));
+            return true;
+        }
+
+        if node.kind_id() != self.kind_id {
+            return false;
+        }
+        if let Some(text) = &self.text {
+            return node.child_count() == 0 && rope_range_eq(rope, node.byte_range(), text);
+        }
+
+        let mut cursor = node.walk();
+        let mut children = node.children(&mut cursor).filter(|child| !child.is_extra());
+        for pattern in &self.children {
+            match children.next() {
+                Some(child) if pattern.matches(child, rope, bindings) => {}
+                _ => return false,
+            }
+        }
+        children.next().is_none()
+    }
+}
+
+fn code_matches(
+    pattern: &PatternNode,
+    root: Node,
+    rope: &Rope,
+    range: &Range<usize>,
+) -> Vec<(Range<usize>, Vec<(String, String)>)> {
+    let mut matches = Vec::new();
+    let mut cursor = root.walk();
+    loop {
+        let node = cursor.node();
+        let mut descend = node.start_byte() < range.end && node.end_byte() > range.start;
+        if descend && range.start <= node.start_byte() && node.end_byte() <= range.end {
+            let mut bindings = Vec::new();
+            if pattern.matches(node, rope, &mut bindings) {
+                let captures = bindings
+                    .into_iter()
+                    .map(|(name, range)| (name, rope.chunks_in_range(range).collect()))
+                    .collect();
+                matches.push((node.byte_range(), captures));
+                descend = false;
+            }
+        }
+
+        if descend && cursor.goto_first_child() {
+            continue;
+        }
+        while !cursor.goto_next_sibling() {
+            if !cursor.goto_parent() {
+                return matches;
+            }
+        }
+    }
+}
+
+fn query_matches(
+    query: &Query,
+    root: Node,
+    rope: &Rope,
+    range: &Range<usize>,
+) -> Vec<(Range<usize>, Vec<(String, String)>)> {
+    let capture_names = query.capture_names();
+    let match_capture_ix = capture_names.iter().position(|name| *name == MATCH_CAPTURE);
+
+    let mut matches = Vec::new();
+    let mut cursor = QueryCursor::new();
+    cursor.set_byte_range(range.clone());
+    for mat in cursor.matches(query, root, RopeText(rope)) {
+        let match_range = if let Some(ix) = match_capture_ix {
+            mat.captures
+                .iter()
+                .find(|capture| capture.index as usize == ix)
+                .map(|capture| capture.node.byte_range())
+        } else {
+            mat.captures
+                .iter()
+                .map(|capture| capture.node.byte_range())
+                .reduce(|a, b| a.start.min(b.start)..a.end.max(b.end))
+        };
+        let Some(match_range) = match_range else {
+            continue;
+        };
+        if match_range.start < range.start || match_range.end > range.end {
+            continue;
+        }
+
+        let captures = mat
+            .captures
+            .iter()
+            .map(|capture| {
+                (
+                    capture_names[capture.index as usize].to_string(),
+                    rope.chunks_in_range(capture.node.byte_range()).collect(),
+                )
+            })
+            .collect();
+        matches.push((match_range, captures));
+    }
+    matches
+}
+
+fn rope_range_eq(rope: &Rope, range: Range<usize>, text: &str) -> bool {
+    range.len() == text.len()
+        && rope
+            .chunks_in_range(range)
+            .flat_map(str::bytes)
+            .eq(text.bytes())
+}
+
+fn is_query_source(source: &str) -> bool {
+    source.trim_start().starts_with(['(', '['])
+}
+
+fn metavariable_regex() -> &'static Regex {
+    METAVARIABLE_REGEX.get_or_init(|| Regex::new(r"\$([A-Z][A-Z0-9_]*)").unwrap())
+}
+
+fn template_variable_regex() -> &'static Regex {
+    TEMPLATE_VARIABLE_REGEX.get_or_init(|| Regex::new(r"\$(\$|[A-Za-z_][A-Za-z0-9_]*)").unwrap())
+}
+
+fn literal_word_regex() -> &'static Regex {
+    LITERAL_WORD_REGEX.get_or_init(|| Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap())
+}
+
+struct RopeText<'a>(&'a Rope);
+
+struct ByteChunks<'a>(text::Chunks<'a>);
+
+impl<'a> tree_sitter::TextProvider<&'a [u8]> for RopeText<'a> {
+    type I = ByteChunks<'a>;
+
+    fn text(&mut self, node: tree_sitter::Node) -> Self::I {
+        ByteChunks(self.0.chunks_in_range(node.byte_range()))
+    }
+}
+
+impl<'a> Iterator for ByteChunks<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(str::as_bytes)
+    }
+}
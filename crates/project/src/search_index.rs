@@ -0,0 +1,931 @@
+//! An optional, persistent trigram index of a local worktree's files.
+//!
+//! Project search uses the index to skip files that cannot possibly contain a
+//! match before reading them from disk. The index is only ever used as a
+//! pre-filter: files that haven't been indexed yet, or whose modification time
+//! differs from the indexed one, are always scanned, so searches stay correct
+//! while the index is being built or caught up with changes on disk.
+
+use crate::search::SearchQuery;
+use anyhow::{anyhow, Result};
+use collections::{HashMap, HashSet};
+use fs::{Fs, RenameOptions};
+use futures::{channel::mpsc, FutureExt, StreamExt};
+use gpui::{AppContext, BackgroundExecutor, Model, Task};
+use parking_lot::RwLock;
+use regex_syntax::hir::{Class, Hir, HirKind};
+use sha2::{Digest, Sha256};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use util::ResultExt;
+use worktree::{Entry, Snapshot, UpdatedEntriesSet, Worktree};
+
+const INDEX_MAGIC: &[u8; 4] = b"ZTRI";
+const INDEX_VERSION: u32 = 1;
+/// Larger files are never indexed, and so are always scanned.
+const MAX_INDEXED_FILE_SIZE: usize = 1024 * 1024;
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(10);
+/// Bounds the number of alternative literals tracked while planning a regex.
+const MAX_EXACT_SET_SIZE: usize = 64;
+const MAX_CLASS_SIZE: u32 = 16;
+
+type Trigram = u32;
+type FileId = u32;
+
+/// Maintains the [`TrigramIndex`] of a local worktree in the background, persisting it
+/// between sessions.
+pub struct WorktreeSearchIndex {
+    index: Arc<RwLock<TrigramIndex>>,
+    changes_tx: mpsc::UnboundedSender<(Snapshot, UpdatedEntriesSet)>,
+    _maintain_index: Task<()>,
+}
+
+impl WorktreeSearchIndex {
+    pub fn new(worktree: &Model<Worktree>, fs: Arc<dyn Fs>, cx: &mut AppContext) -> Self {
+        let index_path = index_path(&worktree.read(cx).abs_path());
+        let index = Arc::new(RwLock::new(TrigramIndex::default()));
+        let (changes_tx, changes_rx) = mpsc::unbounded();
+        let scan_complete = worktree
+            .read(cx)
+            .as_local()
+            .map(|worktree| worktree.scan_complete());
+        let worktree = worktree.downgrade();
+        let maintain_index = cx.spawn({
+            let index = index.clone();
+            |cx| async move {
+                // Files that haven't been scanned yet would be pruned from the persisted
+                // index, so it's only reconciled with the worktree once the scan is done.
+                if let Some(scan_complete) = scan_complete {
+                    scan_complete.await;
+                }
+                let Ok(snapshot) = worktree.read_with(&cx, |tree, _| tree.snapshot()) else {
+                    return;
+                };
+                let executor = cx.background_executor().clone();
+                executor
+                    .spawn(maintain_index(
+                        index,
+                        snapshot,
+                        index_path,
+                        fs,
+                        executor.clone(),
+                        changes_rx,
+                    ))
+                    .await
+            }
+        });
+        Self {
+            index,
+            changes_tx,
+            _maintain_index: maintain_index,
+        }
+    }
+
+    /// Schedules the changed entries to be re-indexed.
+    pub fn update(&self, snapshot: Snapshot, changes: UpdatedEntriesSet) {
+        self.changes_tx.unbounded_send((snapshot, changes)).ok();
+    }
+
+    pub fn index(&self) -> Arc<RwLock<TrigramIndex>> {
+        self.index.clone()
+    }
+}
+
+fn index_path(worktree_abs_path: &Path) -> PathBuf {
+    paths::search_index_dir().join(format!(
+        "{:x}.idx",
+        Sha256::digest(worktree_abs_path.to_string_lossy().as_bytes())
+    ))
+}
+
+async fn maintain_index(
+    index: Arc<RwLock<TrigramIndex>>,
+    snapshot: Snapshot,
+    index_path: PathBuf,
+    fs: Arc<dyn Fs>,
+    executor: BackgroundExecutor,
+    mut changes_rx: mpsc::UnboundedReceiver<(Snapshot, UpdatedEntriesSet)>,
+) {
+    if let Ok(bytes) = fs.load_bytes(&index_path).await {
+        match TrigramIndex::deserialize(&bytes) {
+            Ok(loaded) => *index.write() = loaded,
+            Err(error) => log::warn!("discarding search index {index_path:?}: {error}"),
+        }
+    }
+
+    let mut dirty = false;
+    let stale_paths = index
+        .read()
+        .paths()
+        .filter(|path| !snapshot.entry_for_path(path).map_or(false, is_indexable))
+        .cloned()
+        .collect::<Vec<_>>();
+    for path in stale_paths {
+        dirty |= index.write().remove(&path);
+    }
+    for entry in snapshot.files(false, 0) {
+        dirty |= index_entry(&index, &snapshot, entry, fs.as_ref()).await;
+    }
+
+    // Save once `SAVE_DEBOUNCE` has passed since the index first became dirty, even
+    // if it keeps changing in the meantime.
+    let mut save_deadline = dirty.then(|| executor.timer(SAVE_DEBOUNCE));
+    loop {
+        let changes = match save_deadline.as_mut() {
+            Some(deadline) => futures::select_biased! {
+                changes = changes_rx.next().fuse() => Some(changes),
+                _ = deadline.fuse() => None,
+            },
+            None => Some(changes_rx.next().await),
+        };
+        let Some(changes) = changes else {
+            save_index(&index, &index_path, fs.as_ref()).await.log_err();
+            save_deadline = None;
+            continue;
+        };
+        let Some((snapshot, changes)) = changes else {
+            break;
+        };
+
+        let mut dirty = false;
+        for (path, _, _) in changes.iter() {
+            match snapshot.entry_for_path(path) {
+                Some(entry) if is_indexable(entry) => {
+                    dirty |= index_entry(&index, &snapshot, entry, fs.as_ref()).await;
+                }
+                _ => dirty |= index.write().remove(path),
+            }
+        }
+        if dirty && save_deadline.is_none() {
+            save_deadline = Some(executor.timer(SAVE_DEBOUNCE));
+        }
+    }
+
+    if save_deadline.is_some() {
+        save_index(&index, &index_path, fs.as_ref()).await.log_err();
+    }
+}
+
+fn is_indexable(entry: &Entry) -> bool {
+    entry.is_file() && !entry.is_ignored && !entry.is_external
+}
+
+/// Indexes the entry's current contents, unless they are already indexed. Returns
+/// whether the index changed.
+async fn index_entry(
+    index: &RwLock<TrigramIndex>,
+    snapshot: &Snapshot,
+    entry: &Entry,
+    fs: &dyn Fs,
+) -> bool {
+    if !is_indexable(entry) || index.read().is_indexed(&entry.path, entry.mtime) {
+        return false;
+    }
+
+    let abs_path = snapshot.abs_path().join(&entry.path);
+    match fs.load_bytes(&abs_path).await {
+        Ok(content) if content.len() <= MAX_INDEXED_FILE_SIZE => {
+            index
+                .write()
+                .insert(entry.path.clone(), entry.mtime, &content);
+            true
+        }
+        _ => index.write().remove(&entry.path),
+    }
+}
+
+async fn save_index(index: &RwLock<TrigramIndex>, index_path: &Path, fs: &dyn Fs) -> Result<()> {
+    let bytes = {
+        let mut index = index.write();
+        index.compact();
+        index.serialize()
+    };
+    if let Some(dir) = index_path.parent() {
+        fs.create_dir(dir).await?;
+    }
+    let temp_path = index_path.with_extension("tmp");
+    let mut content = futures::io::Cursor::new(bytes);
+    fs.create_file_with(&temp_path, std::pin::Pin::new(&mut content))
+        .await?;
+    fs.rename(
+        &temp_path,
+        index_path,
+        RenameOptions {
+            overwrite: true,
+            ignore_if_exists: false,
+        },
+    )
+    .await
+}
+
+/// An inverted index from the trigrams of files' contents to the files containing them.
+///
+/// Trigrams are computed over ASCII-lowercased bytes, so that the same index serves
+/// both case-sensitive and case-insensitive searches. Updating a file appends it under
+/// a new id and leaves a tombstone behind, which keeps posting lists sorted without
+/// having to remember each file's trigrams; tombstones are dropped by [`Self::compact`].
+#[derive(Default)]
+pub struct TrigramIndex {
+    files: Vec<Option<IndexedFile>>,
+    file_ids: HashMap<Arc<Path>, FileId>,
+    postings: HashMap<Trigram, Vec<FileId>>,
+    removed_count: usize,
+}
+
+struct IndexedFile {
+    path: Arc<Path>,
+    mtime: Option<SystemTime>,
+}
+
+/// Indexed files that are known not to match a query, along with the modification
+/// times they were indexed at.
+pub struct CandidateFilter {
+    excluded: HashMap<Arc<Path>, Option<SystemTime>>,
+}
+
+impl CandidateFilter {
+    /// Returns whether the entry has to be scanned, as it is either unindexed, changed
+    /// since it was indexed or may contain a match.
+    pub fn may_match(&self, entry: &Entry) -> bool {
+        self.excluded
+            .get(&entry.path)
+            .map_or(true, |mtime| *mtime != entry.mtime)
+    }
+}
+
+impl TrigramIndex {
+    pub fn len(&self) -> usize {
+        self.file_ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.file_ids.is_empty()
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Arc<Path>> {
+        self.file_ids.keys()
+    }
+
+    pub fn is_indexed(&self, path: &Path, mtime: Option<SystemTime>) -> bool {
+        self.file_ids
+            .get(path)
+            .and_then(|id| self.files[*id as usize].as_ref())
+            .map_or(false, |file| file.mtime == mtime)
+    }
+
+    pub fn insert(&mut self, path: Arc<Path>, mtime: Option<SystemTime>, content: &[u8]) {
+        self.remove(&path);
+        let id = self.files.len() as FileId;
+        let mut trigrams = content
+            .windows(3)
+            .map(|window| trigram(window[0], window[1], window[2]))
+            .collect::<Vec<_>>();
+        trigrams.sort_unstable();
+        trigrams.dedup();
+        for trigram in trigrams {
+            self.postings.entry(trigram).or_default().push(id);
+        }
+        self.files.push(Some(IndexedFile {
+            path: path.clone(),
+            mtime,
+        }));
+        self.file_ids.insert(path, id);
+
+        if self.removed_count > 1024 && self.removed_count > self.files.len() / 2 {
+            self.compact();
+        }
+    }
+
+    /// Removes the file from the index. Returns whether it was indexed.
+    pub fn remove(&mut self, path: &Path) -> bool {
+        if let Some(id) = self.file_ids.remove(path) {
+            self.files[id as usize] = None;
+            self.removed_count += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns a filter of the files that can't match the query, or `None` if the
+    /// query can't be used to rule out any file.
+    pub fn filter(&self, query: &TrigramQuery) -> Option<CandidateFilter> {
+        let candidates = self.evaluate(query)?;
+        let excluded = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| candidates.binary_search(&(*id as FileId)).is_err())
+            .filter_map(|(_, file)| {
+                let file = file.as_ref()?;
+                Some((file.path.clone(), file.mtime))
+            })
+            .collect();
+        Some(CandidateFilter { excluded })
+    }
+
+    /// Returns the sorted ids of the files that may match, or `None` if all of them may.
+    fn evaluate(&self, query: &TrigramQuery) -> Option<Vec<FileId>> {
+        match query {
+            TrigramQuery::All => None,
+            TrigramQuery::Trigram(trigram) => {
+                Some(self.postings.get(trigram).cloned().unwrap_or_default())
+            }
+            TrigramQuery::And(queries) => queries
+                .iter()
+                .filter_map(|query| self.evaluate(query))
+                .reduce(|a, b| {
+                    let b = b.into_iter().collect::<HashSet<_>>();
+                    a.into_iter().filter(|id| b.contains(id)).collect()
+                }),
+            TrigramQuery::Or(queries) => {
+                let mut ids = Vec::new();
+                for query in queries {
+                    ids.extend(self.evaluate(query)?);
+                }
+                ids.sort_unstable();
+                ids.dedup();
+                Some(ids)
+            }
+        }
+    }
+
+    /// Drops tombstones left behind by removed and updated files, renumbering the rest.
+    pub fn compact(&mut self) {
+        if self.removed_count == 0 {
+            return;
+        }
+
+        let mut new_ids = vec![None; self.files.len()];
+        let mut files = Vec::with_capacity(self.file_ids.len());
+        for (old_id, file) in self.files.drain(..).enumerate() {
+            if let Some(file) = file {
+                let new_id = files.len() as FileId;
+                new_ids[old_id] = Some(new_id);
+                self.file_ids.insert(file.path.clone(), new_id);
+                files.push(Some(file));
+            }
+        }
+        self.files = files;
+        self.postings.retain(|_, ids| {
+            ids.retain_mut(|id| match new_ids[*id as usize] {
+                Some(new_id) => {
+                    *id = new_id;
+                    true
+                }
+                None => false,
+            });
+            !ids.is_empty()
+        });
+        self.removed_count = 0;
+    }
+
+    /// Serializes the index, which must have been compacted first.
+    fn serialize(&self) -> Vec<u8> {
+        debug_assert_eq!(self.removed_count, 0);
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(INDEX_MAGIC);
+        bytes.extend_from_slice(&INDEX_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.files.len() as u32).to_le_bytes());
+        for file in self.files.iter().flatten() {
+            let path = file.path.to_string_lossy();
+            bytes.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(path.as_bytes());
+            match file
+                .mtime
+                .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+            {
+                Some(mtime) => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&mtime.as_secs().to_le_bytes());
+                    bytes.extend_from_slice(&mtime.subsec_nanos().to_le_bytes());
+                }
+                None => bytes.push(0),
+            }
+        }
+        bytes.extend_from_slice(&(self.postings.len() as u32).to_le_bytes());
+        for (trigram, ids) in &self.postings {
+            bytes.extend_from_slice(&trigram.to_le_bytes());
+            bytes.extend_from_slice(&(ids.len() as u32).to_le_bytes());
+            for id in ids {
+                bytes.extend_from_slice(&id.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self> {
+        let mut reader = Reader(bytes);
+        if reader.take(4)? != INDEX_MAGIC || reader.read_u32()? != INDEX_VERSION {
+            return Err(anyhow!("unsupported index format"));
+        }
+
+        let mut index = Self::default();
+        let file_count = reader.read_u32()?;
+        for id in 0..file_count {
+            let path_len = reader.read_u32()? as usize;
+            let path: Arc<Path> = Path::new(std::str::from_utf8(reader.take(path_len)?)?).into();
+            let mtime = match reader.take(1)?[0] {
+                0 => None,
+                _ => {
+                    let secs = reader.read_u64()?;
+                    let nanos = reader.read_u32()?;
+                    Some(UNIX_EPOCH + Duration::new(secs, nanos))
+                }
+            };
+            index.file_ids.insert(path.clone(), id);
+            index.files.push(Some(IndexedFile { path, mtime }));
+        }
+
+        let posting_count = reader.read_u32()?;
+        for _ in 0..posting_count {
+            let trigram = reader.read_u32()?;
+            let len = reader.read_u32()? as usize;
+            let mut ids = Vec::with_capacity(len.min(file_count as usize));
+            for _ in 0..len {
+                let id = reader.read_u32()?;
+                if id >= file_count {
+                    return Err(anyhow!("file id {id} out of bounds"));
+                }
+                ids.push(id);
+            }
+            index.postings.insert(trigram, ids);
+        }
+        Ok(index)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(anyhow!("unexpected end of index"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+}
+
+fn trigram(a: u8, b: u8, c: u8) -> Trigram {
+    u32::from_le_bytes([
+        a.to_ascii_lowercase(),
+        b.to_ascii_lowercase(),
+        c.to_ascii_lowercase(),
+        0,
+    ])
+}
+
+/// A boolean combination of trigrams that every file matching a search must contain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TrigramQuery {
+    /// Any file may match.
+    All,
+    Trigram(Trigram),
+    And(Vec<TrigramQuery>),
+    Or(Vec<TrigramQuery>),
+}
+
+impl TrigramQuery {
+    pub fn for_search_query(query: &SearchQuery) -> Self {
+        match query {
+            SearchQuery::Text { .. } => Self::literal(query.as_str().as_bytes()),
+            SearchQuery::Regex {
+                regex,
+                multiline,
+                case_sensitive,
+                ..
+            } => regex_syntax::ParserBuilder::new()
+                .case_insensitive(!case_sensitive)
+                .multi_line(*multiline)
+                .build()
+                .parse(regex.as_str())
+                .map_or(Self::All, |hir| RegexInfo::new(&hir).into_query()),
+            SearchQuery::Structural { .. } => Self::All,
+        }
+    }
+
+    fn literal(text: &[u8]) -> Self {
+        Self::and(
+            text.windows(3)
+                .map(|window| Self::Trigram(trigram(window[0], window[1], window[2])))
+                .collect(),
+        )
+    }
+
+    fn and(queries: Vec<Self>) -> Self {
+        let mut result = Vec::new();
+        for query in queries {
+            match query {
+                Self::All => {}
+                Self::And(queries) => result.extend(queries),
+                query => result.push(query),
+            }
+        }
+        result.dedup();
+        match result.len() {
+            0 => Self::All,
+            1 => result.pop().unwrap(),
+            _ => Self::And(result),
+        }
+    }
+
+    fn or(queries: Vec<Self>) -> Self {
+        let mut result = Vec::new();
+        for query in queries {
+            match query {
+                Self::All => return Self::All,
+                Self::Or(queries) => result.extend(queries),
+                query => result.push(query),
+            }
+        }
+        result.dedup();
+        match result.len() {
+            0 => Self::All,
+            1 => result.pop().unwrap(),
+            _ => Self::Or(result),
+        }
+    }
+}
+
+/// What is known about the text matched by a regex while planning its [`TrigramQuery`].
+struct RegexInfo {
+    /// Every string the regex can match, if there are few enough of them.
+    exact: Option<Vec<Vec<u8>>>,
+    /// Trigrams required in addition to the exact strings.
+    query: TrigramQuery,
+}
+
+impl RegexInfo {
+    fn new(hir: &Hir) -> Self {
+        match hir.kind() {
+            HirKind::Empty | HirKind::Look(_) => Self::exact(vec![Vec::new()]),
+            HirKind::Literal(literal) => Self::exact(vec![literal.0.to_vec()]),
+            HirKind::Class(class) => Self::class(class),
+            HirKind::Capture(capture) => Self::new(&capture.sub),
+            HirKind::Repetition(repetition) => {
+                if repetition.min == 0 {
+                    Self::any()
+                } else if repetition.max == Some(1) {
+                    Self::new(&repetition.sub)
+                } else {
+                    Self {
+                        exact: None,
+                        query: Self::new(&repetition.sub).into_query(),
+                    }
+                }
+            }
+            HirKind::Concat(hirs) => {
+                let mut result = Self::exact(vec![Vec::new()]);
+                for hir in hirs {
+                    let info = Self::new(hir);
+                    match (result.exact.take(), info.exact) {
+                        (Some(prefixes), Some(suffixes))
+                            if prefixes.len() * suffixes.len() <= MAX_EXACT_SET_SIZE =>
+                        {
+                            let mut exact = Vec::new();
+                            for prefix in &prefixes {
+                                for suffix in &suffixes {
+                                    exact.push([prefix.as_slice(), suffix].concat());
+                                }
+                            }
+                            result.exact = Some(exact);
+                            result.query = TrigramQuery::and(vec![result.query, info.query]);
+                        }
+                        (prefixes, suffixes) => {
+                            result.query = TrigramQuery::and(vec![
+                                result.query,
+                                exact_query(prefixes),
+                                info.query,
+                            ]);
+                            result.exact = suffixes;
+                        }
+                    }
+                }
+                result
+            }
+            HirKind::Alternation(hirs) => {
+                let infos = hirs.iter().map(Self::new).collect::<Vec<_>>();
+                let exact_len = infos
+                    .iter()
+                    .map(|info| info.exact.as_ref().map(Vec::len))
+                    .sum::<Option<usize>>();
+                let all_unconstrained = infos.iter().all(|info| info.query == TrigramQuery::All);
+                if exact_len.map_or(false, |len| len <= MAX_EXACT_SET_SIZE) && all_unconstrained {
+                    Self::exact(
+                        infos
+                            .into_iter()
+                            .flat_map(|info| info.exact.unwrap())
+                            .collect(),
+                    )
+                } else {
+                    Self {
+                        exact: None,
+                        query: TrigramQuery::or(infos.into_iter().map(Self::into_query).collect()),
+                    }
+                }
+            }
+        }
+    }
+
+    fn any() -> Self {
+        Self {
+            exact: None,
+            query: TrigramQuery::All,
+        }
+    }
+
+    fn exact(mut strings: Vec<Vec<u8>>) -> Self {
+        for string in &mut strings {
+            string.make_ascii_lowercase();
+        }
+        strings.sort();
+        strings.dedup();
+        Self {
+            exact: Some(strings),
+            query: TrigramQuery::All,
+        }
+    }
+
+    fn class(class: &Class) -> Self {
+        match class {
+            Class::Unicode(class) => {
+                let size = class
+                    .ranges()
+                    .iter()
+                    .map(|range| range.end() as u32 - range.start() as u32 + 1)
+                    .sum::<u32>();
+                if size > MAX_CLASS_SIZE {
+                    return Self::any();
+                }
+                Self::exact(
+                    class
+                        .ranges()
+                        .iter()
+                        .flat_map(|range| range.start()..=range.end())
+                        .map(|c| c.to_string().into_bytes())
+                        .collect(),
+                )
+            }
+            Class::Bytes(class) => {
+                let size = class
+                    .ranges()
+                    .iter()
+                    .map(|range| range.end() as u32 - range.start() as u32 + 1)
+                    .sum::<u32>();
+                if size > MAX_CLASS_SIZE {
+                    return Self::any();
+                }
+                Self::exact(
+                    class
+                        .ranges()
+                        .iter()
+                        .flat_map(|range| range.start()..=range.end())
+                        .map(|byte| vec![byte])
+                        .collect(),
+                )
+            }
+        }
+    }
+
+    fn into_query(self) -> TrigramQuery {
+        TrigramQuery::and(vec![self.query, exact_query(self.exact)])
+    }
+}
+
+/// Matches files containing any of the strings, or all files if one of them is too
+/// short to contain a trigram.
+fn exact_query(exact: Option<Vec<Vec<u8>>>) -> TrigramQuery {
+    match exact {
+        Some(strings) => TrigramQuery::or(
+            strings
+                .iter()
+                .map(|string| TrigramQuery::literal(string))
+                .collect(),
+        ),
+        None => TrigramQuery::All,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::FakeFs;
+    use gpui::TestAppContext;
+    use serde_json::json;
+    use settings::SettingsStore;
+
+    fn query(text: &str) -> TrigramQuery {
+        TrigramQuery::literal(text.as_bytes())
+    }
+
+    fn regex_query(regex: &str, case_sensitive: bool) -> TrigramQuery {
+        TrigramQuery::for_search_query(
+            &SearchQuery::regex(
+                regex,
+                false,
+                case_sensitive,
+                false,
+                Default::default(),
+                Default::default(),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn matching_paths(index: &TrigramIndex, query: &TrigramQuery) -> Option<Vec<String>> {
+        let mut paths = index
+            .evaluate(query)?
+            .into_iter()
+            .filter_map(|id| index.files[id as usize].as_ref())
+            .map(|file| file.path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        paths.sort();
+        Some(paths)
+    }
+
+    #[test]
+    fn test_regex_planning() {
+        assert_eq!(regex_query("abcd", true), query("abcd"));
+        assert_eq!(regex_query("ABcd", false), query("abcd"));
+        assert_eq!(regex_query("a.*b", true), TrigramQuery::All);
+        assert_eq!(
+            regex_query("foo|barbaz", true),
+            TrigramQuery::or(vec![query("barbaz"), query("foo")])
+        );
+        assert_eq!(
+            regex_query("fo[ox]bar", true),
+            TrigramQuery::or(vec![query("foobar"), query("foxbar")])
+        );
+        assert_eq!(
+            regex_query(r"hello\w+world", true),
+            TrigramQuery::and(vec![query("hello"), query("world")])
+        );
+        assert_eq!(regex_query("(abc)+", true), query("abc"));
+        assert_eq!(regex_query("ab|c", true), TrigramQuery::All);
+    }
+
+    #[test]
+    fn test_index_updates_and_persistence() {
+        let mut index = TrigramIndex::default();
+        index.insert(Path::new("a.rs").into(), None, b"fn hello() {}");
+        index.insert(Path::new("b.rs").into(), None, b"fn world() {}");
+        index.insert(Path::new("c.rs").into(), None, b"HELLO_WORLD");
+        assert_eq!(
+            matching_paths(&index, &query("hello")),
+            Some(vec!["a.rs".into(), "c.rs".into()])
+        );
+        assert_eq!(
+            matching_paths(&index, &regex_query("hello|world", true)),
+            Some(vec!["a.rs".into(), "b.rs".into(), "c.rs".into()])
+        );
+        assert_eq!(matching_paths(&index, &query("fn")), None);
+
+        index.insert(Path::new("a.rs").into(), None, b"fn goodbye() {}");
+        index.remove(Path::new("c.rs"));
+        assert_eq!(matching_paths(&index, &query("hello")), Some(vec![]));
+        assert_eq!(
+            matching_paths(&index, &query("goodbye")),
+            Some(vec!["a.rs".into()])
+        );
+
+        index.compact();
+        let index = TrigramIndex::deserialize(&index.serialize()).unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(
+            matching_paths(&index, &query("goodbye")),
+            Some(vec!["a.rs".into()])
+        );
+        assert_eq!(
+            matching_paths(&index, &query("world")),
+            Some(vec!["b.rs".into()])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_restoring_persisted_index(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            crate::Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = 2;",
+            }),
+        )
+        .await;
+
+        // The index saved by a previous session, since which `one.rs` is unchanged,
+        // `two.rs` was created and `three.rs` was deleted.
+        let one_mtime = fs
+            .metadata(Path::new("/dir/one.rs"))
+            .await
+            .unwrap()
+            .unwrap()
+            .mtime;
+        let mut persisted = TrigramIndex::default();
+        persisted.insert(
+            Path::new("one.rs").into(),
+            Some(one_mtime),
+            b"const ONE: usize = 1;",
+        );
+        persisted.insert(
+            Path::new("three.rs").into(),
+            None,
+            b"const THREE: usize = 3;",
+        );
+        let index_path = index_path(Path::new("/dir"));
+        fs.create_dir(index_path.parent().unwrap()).await.unwrap();
+        fs.insert_file(&index_path, persisted.serialize()).await;
+
+        let worktree = Worktree::local(
+            Path::new("/dir"),
+            true,
+            fs.clone(),
+            Default::default(),
+            &mut cx.to_async(),
+        )
+        .await
+        .unwrap();
+        let search_index = cx.update(|cx| WorktreeSearchIndex::new(&worktree, fs.clone(), cx));
+        cx.executor().run_until_parked();
+
+        // The unchanged file keeps its entry instead of being pruned and re-indexed.
+        let index = search_index.index();
+        let index = index.read();
+        let mut paths = index
+            .paths()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(paths, ["one.rs", "two.rs"]);
+        assert_eq!(index.file_ids.get(Path::new("one.rs")), Some(&0));
+        assert_eq!(
+            matching_paths(&index, &query("two")),
+            Some(vec!["two.rs".into()])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_saving_index_after_debounce(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            crate::Project::init_settings(cx);
+        });
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree("/dir", json!({ "one.rs": "const ONE: usize = 1;" }))
+            .await;
+        let index_path = index_path(Path::new("/dir"));
+        fs.create_dir(index_path.parent().unwrap()).await.unwrap();
+
+        let worktree = Worktree::local(
+            Path::new("/dir"),
+            true,
+            fs.clone(),
+            Default::default(),
+            &mut cx.to_async(),
+        )
+        .await
+        .unwrap();
+        let _search_index = cx.update(|cx| WorktreeSearchIndex::new(&worktree, fs.clone(), cx));
+        cx.executor().run_until_parked();
+        assert!(fs.load_bytes(&index_path).await.is_err());
+
+        // Changes made before the debounce elapses don't postpone the save.
+        cx.executor().advance_clock(SAVE_DEBOUNCE / 2);
+        fs.insert_file("/dir/two.rs", b"const TWO: usize = 2;".to_vec())
+            .await;
+        cx.executor().run_until_parked();
+        cx.executor().advance_clock(SAVE_DEBOUNCE / 2);
+        cx.executor().run_until_parked();
+
+        let saved = TrigramIndex::deserialize(&fs.load_bytes(&index_path).await.unwrap()).unwrap();
+        assert_eq!(
+            matching_paths(&saved, &query("one")),
+            Some(vec!["one.rs".into()])
+        );
+        assert_eq!(
+            matching_paths(&saved, &query("two")),
+            Some(vec!["two.rs".into()])
+        );
+    }
+}
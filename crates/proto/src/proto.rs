@@ -153,6 +153,7 @@ messages!(
     (CancelCall, Foreground),
     (ChannelMessageSent, Foreground),
     (ChannelMessageUpdate, Foreground),
+    (ChannelMessageReactionsUpdated, Foreground),
     (CompleteWithLanguageModel, Background),
     (ComputeEmbeddings, Background),
     (ComputeEmbeddingsResponse, Background),
@@ -186,6 +187,7 @@ messages!(
     (GetChannelMessages, Background),
     (GetChannelMessagesById, Background),
     (GetChannelMessagesResponse, Background),
+    (GetChannelMessageThread, Background),
     (GetCodeActions, Background),
     (GetCodeActionsResponse, Background),
     (GetCompletions, Background),
@@ -276,6 +278,8 @@ messages!(
     (SetChannelVisibility, Foreground),
     (SearchProject, Background),
     (SearchProjectResponse, Background),
+    (SearchChannelMessages, Background),
+    (SearchChannelMessagesResponse, Background),
     (SendChannelMessage, Background),
     (SendChannelMessageResponse, Background),
     (ShareProject, Foreground),
@@ -290,6 +294,8 @@ messages!(
     (TaskTemplates, Background),
     (TaskTemplatesResponse, Background),
     (Test, Foreground),
+    (ToggleChannelMessageReaction, Foreground),
+    (ToggleChannelMessageReactionResponse, Foreground),
     (Unfollow, Foreground),
     (UnshareProject, Foreground),
     (UpdateBuffer, Foreground),
@@ -346,6 +352,18 @@ messages!(
     (UpdateContext, Foreground),
     (SynchronizeContexts, Foreground),
     (SynchronizeContextsResponse, Foreground),
+    (AdvertiseSharedTerminals, Foreground),
+    (OpenSharedTerminal, Foreground),
+    (OpenSharedTerminalResponse, Foreground),
+    (UpdateSharedTerminal, Foreground),
+    (SharedTerminalInput, Foreground),
+    (GetReviewThreads, Foreground),
+    (GetReviewThreadsResponse, Foreground),
+    (CreateReviewThread, Foreground),
+    (AddReviewComment, Foreground),
+    (SetReviewThreadResolved, Foreground),
+    (ReviewThreadResponse, Foreground),
+    (UpdateReviewThread, Foreground),
 );
 
 request_messages!(
@@ -374,6 +392,7 @@ request_messages!(
     (GetChannelMembers, GetChannelMembersResponse),
     (GetChannelMessages, GetChannelMessagesResponse),
     (GetChannelMessagesById, GetChannelMessagesResponse),
+    (GetChannelMessageThread, GetChannelMessagesResponse),
     (GetCodeActions, GetCodeActionsResponse),
     (GetCompletions, GetCompletionsResponse),
     (GetDefinition, GetDefinitionResponse),
@@ -430,6 +449,7 @@ request_messages!(
     (RespondToContactRequest, Ack),
     (SaveBuffer, BufferSaved),
     (SearchProject, SearchProjectResponse),
+    (SearchChannelMessages, SearchChannelMessagesResponse),
     (SendChannelMessage, SendChannelMessageResponse),
     (SetChannelMemberRole, Ack),
     (SetChannelVisibility, Ack),
@@ -438,6 +458,7 @@ request_messages!(
     (TaskContextForLocation, TaskContext),
     (TaskTemplates, TaskTemplatesResponse),
     (Test, Test),
+    (ToggleChannelMessageReaction, ToggleChannelMessageReactionResponse),
     (UpdateBuffer, Ack),
     (UpdateParticipantLocation, Ack),
     (UpdateProject, Ack),
@@ -461,6 +482,11 @@ request_messages!(
     (RestartLanguageServers, Ack),
     (OpenContext, OpenContextResponse),
     (SynchronizeContexts, SynchronizeContextsResponse),
+    (OpenSharedTerminal, OpenSharedTerminalResponse),
+    (GetReviewThreads, GetReviewThreadsResponse),
+    (CreateReviewThread, ReviewThreadResponse),
+    (AddReviewComment, ReviewThreadResponse),
+    (SetReviewThreadResolved, ReviewThreadResponse),
 );
 
 entity_messages!(
@@ -527,12 +553,22 @@ entity_messages!(
     OpenContext,
     UpdateContext,
     SynchronizeContexts,
+    AdvertiseSharedTerminals,
+    OpenSharedTerminal,
+    UpdateSharedTerminal,
+    SharedTerminalInput,
+    GetReviewThreads,
+    CreateReviewThread,
+    AddReviewComment,
+    SetReviewThreadResolved,
+    UpdateReviewThread,
 );
 
 entity_messages!(
     {channel_id, Channel},
     ChannelMessageSent,
     ChannelMessageUpdate,
+    ChannelMessageReactionsUpdated,
     RemoveChannelMessage,
     UpdateChannelMessage,
     UpdateChannelBuffer,
@@ -0,0 +1,338 @@
+use anyhow::Result;
+use db::{
+    define_connection, query,
+    sqlez::{
+        bindable::{Bind, Column, StaticColumnCount},
+        statement::Statement,
+    },
+    sqlez_macros::sql,
+};
+use workspace::{WorkspaceDb, WorkspaceId};
+
+use crate::SearchOptions;
+
+/// How a saved search was recorded.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SavedSearchKind {
+    /// A search saved explicitly by the user, including its query.
+    Saved,
+    /// Options, filters and replacement text saved without a query, applied to
+    /// the query that is being typed.
+    Preset,
+    /// A search that was recently run.
+    Recent,
+}
+
+impl StaticColumnCount for SavedSearchKind {}
+impl Bind for SavedSearchKind {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        match self {
+            SavedSearchKind::Saved => "Saved",
+            SavedSearchKind::Preset => "Preset",
+            SavedSearchKind::Recent => "Recent",
+        }
+        .bind(statement, start_index)
+    }
+}
+
+impl Column for SavedSearchKind {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        String::column(statement, start_index).and_then(|(kind, next_index)| {
+            Ok((
+                match kind.as_str() {
+                    "Saved" => SavedSearchKind::Saved,
+                    "Preset" => SavedSearchKind::Preset,
+                    "Recent" => SavedSearchKind::Recent,
+                    _ => anyhow::bail!("Stored saved search kind is incorrect"),
+                },
+                next_index,
+            ))
+        })
+    }
+}
+
+/// A project search with its full set of options, which can be run again later.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SavedSearch {
+    pub kind: SavedSearchKind,
+    /// Identifies the search among the searches of the same kind in a workspace.
+    pub name: String,
+    pub query: String,
+    pub options: SearchOptions,
+    /// The comma-separated globs of the files to search.
+    pub files_to_include: String,
+    /// The comma-separated globs of the files to skip.
+    pub files_to_exclude: String,
+    pub replacement: Option<String>,
+}
+
+impl SavedSearch {
+    /// Describes the options, filters and replacement of this search.
+    pub fn description(&self) -> String {
+        let mut parts = [
+            SearchOptions::REGEX,
+            SearchOptions::STRUCTURAL,
+            SearchOptions::CASE_SENSITIVE,
+            SearchOptions::WHOLE_WORD,
+            SearchOptions::INCLUDE_IGNORED,
+        ]
+        .into_iter()
+        .filter(|option| self.options.contains(*option))
+        .map(|option| option.label().to_lowercase())
+        .collect::<Vec<_>>();
+        if !self.files_to_include.is_empty() {
+            parts.push(format!("include: {}", self.files_to_include));
+        }
+        if !self.files_to_exclude.is_empty() {
+            parts.push(format!("exclude: {}", self.files_to_exclude));
+        }
+        if let Some(replacement) = &self.replacement {
+            parts.push(format!("replace with: {replacement}"));
+        }
+        parts.join("; ")
+    }
+}
+
+impl StaticColumnCount for SavedSearch {
+    fn column_count() -> usize {
+        7
+    }
+}
+
+impl Bind for SavedSearch {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        (
+            self.kind,
+            self.name.as_str(),
+            self.query.as_str(),
+            self.options.bits() as i32,
+            self.files_to_include.as_str(),
+            self.files_to_exclude.as_str(),
+            self.replacement.as_deref(),
+        )
+            .bind(statement, start_index)
+    }
+}
+
+impl Column for SavedSearch {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (
+            (kind, name, query, options, files_to_include, files_to_exclude, replacement),
+            next_index,
+        ) = <(
+            SavedSearchKind,
+            String,
+            String,
+            i32,
+            String,
+            String,
+            Option<String>,
+        )>::column(statement, start_index)?;
+        Ok((
+            SavedSearch {
+                kind,
+                name,
+                query,
+                options: SearchOptions::from_bits_truncate(options as u8),
+                files_to_include,
+                files_to_exclude,
+                replacement,
+            },
+            next_index,
+        ))
+    }
+}
+
+define_connection! {
+    pub static ref SEARCH_DB: SearchDb<WorkspaceDb> =
+        &[sql!(
+            CREATE TABLE saved_searches(
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                workspace_id INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                name TEXT NOT NULL,
+                query TEXT NOT NULL,
+                options INTEGER NOT NULL, // The bits of `SearchOptions`
+                files_to_include TEXT NOT NULL,
+                files_to_exclude TEXT NOT NULL,
+                replacement TEXT,
+                UNIQUE(workspace_id, kind, name),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+            ) STRICT;
+        )];
+}
+
+impl SearchDb {
+    query! {
+        /// Returns the saved searches of the given workspace, most recently saved first.
+        pub fn saved_searches(workspace_id: WorkspaceId) -> Result<Vec<SavedSearch>> {
+            SELECT kind, name, query, options, files_to_include, files_to_exclude, replacement
+            FROM saved_searches
+            WHERE workspace_id = ?
+            ORDER BY id DESC
+        }
+    }
+
+    query! {
+        /// Saves the given search, replacing the search of the same kind and name.
+        pub async fn save_search(workspace_id: WorkspaceId, search: SavedSearch) -> Result<()> {
+            INSERT OR REPLACE INTO saved_searches(
+                workspace_id,
+                kind,
+                name,
+                query,
+                options,
+                files_to_include,
+                files_to_exclude,
+                replacement
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        }
+    }
+
+    query! {
+        pub async fn delete_saved_search(
+            workspace_id: WorkspaceId,
+            kind: SavedSearchKind,
+            name: String
+        ) -> Result<()> {
+            DELETE FROM saved_searches
+            WHERE workspace_id = ? AND kind = ? AND name = ?
+        }
+    }
+
+    /// Records a search that was run, then removes the recent searches of the
+    /// workspace exceeding `max_recent_searches`.
+    pub async fn record_recent_search(
+        &self,
+        workspace_id: WorkspaceId,
+        search: SavedSearch,
+        max_recent_searches: usize,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("record_recent_search", || {
+                conn.exec_bound(sql!(
+                    INSERT OR REPLACE INTO saved_searches(
+                        workspace_id,
+                        kind,
+                        name,
+                        query,
+                        options,
+                        files_to_include,
+                        files_to_exclude,
+                        replacement
+                    )
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                ))?((workspace_id, search))?;
+                conn.exec_bound(sql!(
+                    DELETE FROM saved_searches
+                    WHERE workspace_id = ? AND kind = ? AND id NOT IN (
+                        SELECT id FROM saved_searches
+                        WHERE workspace_id = ? AND kind = ?
+                        ORDER BY id DESC
+                        LIMIT ?
+                    )
+                ))?((
+                    workspace_id,
+                    SavedSearchKind::Recent,
+                    workspace_id,
+                    SavedSearchKind::Recent,
+                    max_recent_searches,
+                ))
+            })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn next_workspace_id(db: &SearchDb) -> WorkspaceId {
+        db.write(|conn| {
+            conn.select_row::<WorkspaceId>(sql!(
+                INSERT INTO workspaces DEFAULT VALUES RETURNING workspace_id
+            ))?()?
+            .ok_or_else(|| anyhow::anyhow!("failed to create a workspace"))
+        })
+        .await
+        .unwrap()
+    }
+
+    fn search(kind: SavedSearchKind, name: &str) -> SavedSearch {
+        SavedSearch {
+            kind,
+            name: name.to_string(),
+            query: name.to_string(),
+            options: SearchOptions::REGEX | SearchOptions::CASE_SENSITIVE,
+            files_to_include: "src/**".to_string(),
+            files_to_exclude: "*.lock, vendor/*".to_string(),
+            replacement: Some("$1".to_string()),
+        }
+    }
+
+    #[gpui::test]
+    async fn test_saved_searches() {
+        let db = SearchDb(db::open_test_db("test_saved_searches").await);
+        let workspace_id = next_workspace_id(&db).await;
+        let other_workspace_id = next_workspace_id(&db).await;
+
+        db.save_search(workspace_id, search(SavedSearchKind::Saved, "one"))
+            .await
+            .unwrap();
+        db.save_search(workspace_id, search(SavedSearchKind::Preset, "one"))
+            .await
+            .unwrap();
+        db.save_search(other_workspace_id, search(SavedSearchKind::Saved, "two"))
+            .await
+            .unwrap();
+        assert_eq!(
+            db.saved_searches(workspace_id).unwrap(),
+            vec![
+                search(SavedSearchKind::Preset, "one"),
+                search(SavedSearchKind::Saved, "one"),
+            ]
+        );
+
+        // Saving a search with the same kind and name replaces it.
+        let mut replaced = search(SavedSearchKind::Saved, "one");
+        replaced.options = SearchOptions::WHOLE_WORD;
+        replaced.replacement = None;
+        db.save_search(workspace_id, replaced.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            db.saved_searches(workspace_id).unwrap(),
+            vec![replaced, search(SavedSearchKind::Preset, "one")]
+        );
+
+        db.delete_saved_search(workspace_id, SavedSearchKind::Saved, "one".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            db.saved_searches(workspace_id).unwrap(),
+            vec![search(SavedSearchKind::Preset, "one")]
+        );
+
+        // Only the newest recent searches are kept.
+        for name in ["a", "b", "c", "b"] {
+            db.record_recent_search(workspace_id, search(SavedSearchKind::Recent, name), 2)
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            db.saved_searches(workspace_id).unwrap(),
+            vec![
+                search(SavedSearchKind::Recent, "b"),
+                search(SavedSearchKind::Recent, "c"),
+                search(SavedSearchKind::Preset, "one"),
+            ]
+        );
+        assert_eq!(
+            db.saved_searches(other_workspace_id).unwrap(),
+            vec![search(SavedSearchKind::Saved, "two")]
+        );
+    }
+}
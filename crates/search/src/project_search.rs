@@ -1,7 +1,8 @@
 use crate::{
+    persistence::{SavedSearch, SavedSearchKind, SEARCH_DB},
     FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext, SearchOptions,
     SelectNextMatch, SelectPrevMatch, ToggleCaseSensitive, ToggleIncludeIgnored, ToggleRegex,
-    ToggleReplace, ToggleWholeWord,
+    ToggleReplace, ToggleStructural, ToggleWholeWord,
 };
 use collections::{HashMap, HashSet};
 use editor::{
@@ -12,12 +13,13 @@ use editor::{
     MAX_TAB_TITLE_LEN,
 };
 use gpui::{
-    actions, div, Action, AnyElement, AnyView, AppContext, Context as _, Element, EntityId,
-    EventEmitter, FocusHandle, FocusableView, FontStyle, Global, Hsla, InteractiveElement,
-    IntoElement, Model, ModelContext, ParentElement, Point, Render, SharedString, Styled,
-    Subscription, Task, TextStyle, UpdateGlobal, View, ViewContext, VisualContext, WeakModel,
-    WeakView, WhiteSpace, WindowContext,
+    actions, div, Action, AnyElement, AnyView, AppContext, AsyncAppContext, Context as _, Element,
+    EntityId, EventEmitter, FocusHandle, FocusableView, FontStyle, Global, Hsla,
+    InteractiveElement, IntoElement, Model, ModelContext, ParentElement, Point, Render,
+    SharedString, Styled, Subscription, Task, TextStyle, UpdateGlobal, View, ViewContext,
+    VisualContext, WeakModel, WeakView, WhiteSpace, WindowContext,
 };
+use language::{Buffer, OffsetRangeExt as _, ParseStatus};
 use menu::Confirm;
 use project::{search::SearchQuery, search_history::SearchHistoryCursor, Project, ProjectPath};
 use settings::Settings;
@@ -43,10 +45,19 @@ use workspace::{
 
 const MIN_INPUT_WIDTH_REMS: f32 = 15.;
 const MAX_INPUT_WIDTH_REMS: f32 = 30.;
+const MAX_RECENT_SEARCHES: usize = 20;
 
 actions!(
     project_search,
-    [SearchInNew, ToggleFocus, NextField, ToggleFilters]
+    [
+        SearchInNew,
+        SearchInResults,
+        SaveSearch,
+        SaveSearchPreset,
+        ToggleFocus,
+        NextField,
+        ToggleFilters
+    ]
 );
 
 #[derive(Default)]
@@ -72,9 +83,21 @@ pub fn init(cx: &mut AppContext) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, cx);
         });
+        register_workspace_action(workspace, move |search_bar, _: &ToggleStructural, cx| {
+            search_bar.toggle_search_option(SearchOptions::STRUCTURAL, cx);
+        });
         register_workspace_action(workspace, move |search_bar, action: &ToggleReplace, cx| {
             search_bar.toggle_replace(action, cx)
         });
+        register_workspace_action(workspace, move |search_bar, _: &SearchInResults, cx| {
+            search_bar.search_in_results(cx);
+        });
+        register_workspace_action(workspace, move |search_bar, _: &SaveSearch, cx| {
+            search_bar.save_search(SavedSearchKind::Saved, cx);
+        });
+        register_workspace_action(workspace, move |search_bar, _: &SaveSearchPreset, cx| {
+            search_bar.save_search(SavedSearchKind::Preset, cx);
+        });
         register_workspace_action(
             workspace,
             move |search_bar, action: &SelectPrevMatch, cx| {
@@ -148,6 +171,8 @@ pub struct ProjectSearchView {
     excluded_files_editor: View<Editor>,
     filters_enabled: bool,
     replace_enabled: bool,
+    /// The workspace whose saved searches this search is recorded in.
+    workspace_id: Option<WorkspaceId>,
     _subscriptions: Vec<Subscription>,
 }
 
@@ -224,25 +249,7 @@ impl ProjectSearch {
             while let Some(result) = matches.next().await {
                 match result {
                     project::SearchResult::Buffer { buffer, ranges } => {
-                        let mut match_ranges = this
-                            .update(&mut cx, |this, cx| {
-                                this.no_results = Some(false);
-                                this.excerpts.update(cx, |excerpts, cx| {
-                                    excerpts.stream_excerpts_with_context_lines(
-                                        buffer,
-                                        ranges,
-                                        editor::DEFAULT_MULTIBUFFER_CONTEXT,
-                                        cx,
-                                    )
-                                })
-                            })
-                            .ok()?;
-
-                        while let Some(range) = match_ranges.next().await {
-                            this.update(&mut cx, |this, _| this.match_ranges.push(range))
-                                .ok()?;
-                        }
-                        this.update(&mut cx, |_, cx| cx.notify()).ok()?;
+                        Self::add_matches(&this, buffer, ranges, &mut cx).await?;
                     }
                     project::SearchResult::LimitReached => {
                         limit_reached = true;
@@ -261,6 +268,133 @@ impl ProjectSearch {
         }));
         cx.notify();
     }
+
+    /// Runs the query over the buffers that are already in the results, removing
+    /// the buffers that don't match it anymore.
+    fn search_in_results(&mut self, query: SearchQuery, cx: &mut ModelContext<Self>) {
+        let include_root = self.project.read(cx).visible_worktrees(cx).count() > 1;
+        let mut buffers = Vec::new();
+        // Only the excerpts of the current results are searched.
+        let mut excerpt_ranges = HashMap::<_, Vec<_>>::default();
+        {
+            let excerpts = self.excerpts.read(cx);
+            let snapshot = excerpts.snapshot(cx);
+            for (_, buffer_snapshot, excerpt_range) in snapshot.excerpts() {
+                let buffer_id = buffer_snapshot.remote_id();
+                if let Some(ranges) = excerpt_ranges.get_mut(&buffer_id) {
+                    ranges.push(excerpt_range.context);
+                    continue;
+                }
+                excerpt_ranges.insert(buffer_id, vec![excerpt_range.context]);
+                let matched_path = match buffer_snapshot.file() {
+                    Some(file) if include_root => query.file_matches(Some(&file.full_path(cx))),
+                    Some(file) => query.file_matches(Some(file.path())),
+                    None => query.file_matches(None),
+                };
+                if matched_path {
+                    buffers.extend(excerpts.buffer(buffer_id));
+                }
+            }
+        }
+
+        self.project.update(cx, |project, _| {
+            project
+                .search_history_mut()
+                .add(&mut self.search_history_cursor, query.as_str().to_string());
+        });
+        self.last_search_query_text = Some(query.as_str().to_string());
+        self.search_id += 1;
+        self.active_query = Some(query.clone());
+        self.match_ranges.clear();
+        self.pending_search = Some(cx.spawn(|this, mut cx| async move {
+            let this = this.upgrade()?;
+            this.update(&mut cx, |this, cx| {
+                this.match_ranges.clear();
+                this.excerpts.update(cx, |this, cx| this.clear(cx));
+                this.no_results = Some(true);
+                this.limit_reached = false;
+            })
+            .ok()?;
+
+            for buffer in buffers {
+                if query.is_structural() {
+                    let mut parse_status = buffer
+                        .read_with(&cx, |buffer, _| buffer.parse_status())
+                        .ok()?;
+                    while *parse_status.borrow() != ParseStatus::Idle {
+                        if parse_status.next().await.is_none() {
+                            break;
+                        }
+                    }
+                }
+                let snapshot = buffer.read_with(&cx, |buffer, _| buffer.snapshot()).ok()?;
+                let excerpt_ranges = excerpt_ranges
+                    .remove(&snapshot.remote_id())
+                    .unwrap_or_default();
+                let query = query.clone();
+                let ranges = cx
+                    .background_executor()
+                    .spawn(async move {
+                        let mut ranges = Vec::new();
+                        for excerpt_range in excerpt_ranges {
+                            let excerpt_range = excerpt_range.to_offset(&snapshot);
+                            let offset = excerpt_range.start;
+                            ranges.extend(
+                                query
+                                    .search(&snapshot, Some(excerpt_range))
+                                    .await
+                                    .iter()
+                                    .map(|range| {
+                                        snapshot.anchor_before(range.start + offset)
+                                            ..snapshot.anchor_after(range.end + offset)
+                                    }),
+                            );
+                        }
+                        ranges
+                    })
+                    .await;
+                if !ranges.is_empty() {
+                    Self::add_matches(&this, buffer, ranges, &mut cx).await?;
+                }
+            }
+
+            this.update(&mut cx, |this, cx| {
+                this.pending_search.take();
+                cx.notify();
+            })
+            .ok()?;
+
+            None
+        }));
+        cx.notify();
+    }
+
+    async fn add_matches(
+        this: &Model<Self>,
+        buffer: Model<Buffer>,
+        ranges: Vec<Range<language::Anchor>>,
+        cx: &mut AsyncAppContext,
+    ) -> Option<()> {
+        let mut match_ranges = this
+            .update(cx, |this, cx| {
+                this.no_results = Some(false);
+                this.excerpts.update(cx, |excerpts, cx| {
+                    excerpts.stream_excerpts_with_context_lines(
+                        buffer,
+                        ranges,
+                        editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                        cx,
+                    )
+                })
+            })
+            .ok()?;
+
+        while let Some(range) = match_ranges.next().await {
+            this.update(cx, |this, _| this.match_ranges.push(range))
+                .ok()?;
+        }
+        this.update(cx, |_, cx| cx.notify()).ok()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -466,6 +600,7 @@ impl Item for ProjectSearchView {
     }
 
     fn added_to_workspace(&mut self, workspace: &mut Workspace, cx: &mut ViewContext<Self>) {
+        self.workspace_id = workspace.database_id();
         self.results_editor
             .update(cx, |editor, cx| editor.added_to_workspace(workspace, cx));
     }
@@ -542,6 +677,14 @@ impl ProjectSearchView {
 
     fn toggle_search_option(&mut self, option: SearchOptions, cx: &mut ViewContext<Self>) {
         self.search_options.toggle(option);
+        // Structural patterns are neither regular expressions nor plain text.
+        if self.search_options.contains(option) {
+            if option == SearchOptions::STRUCTURAL {
+                self.search_options.remove(SearchOptions::REGEX);
+            } else if option == SearchOptions::REGEX {
+                self.search_options.remove(SearchOptions::STRUCTURAL);
+            }
+        }
         ActiveSettings::update_global(cx, |settings, cx| {
             settings.0.insert(
                 self.model.read(cx).project.downgrade(),
@@ -718,6 +861,7 @@ impl ProjectSearchView {
             excluded_files_editor,
             filters_enabled,
             replace_enabled: false,
+            workspace_id: None,
             _subscriptions: subscriptions,
         };
         this.model_changed(cx);
@@ -852,7 +996,127 @@ impl ProjectSearchView {
     fn search(&mut self, cx: &mut ViewContext<Self>) {
         if let Some(query) = self.build_search_query(cx) {
             self.model.update(cx, |model, cx| model.search(query, cx));
+            self.record_recent_search(cx);
+        }
+    }
+
+    fn search_in_results(&mut self, cx: &mut ViewContext<Self>) {
+        if self.model.read(cx).match_ranges.is_empty() {
+            return;
+        }
+        if let Some(query) = self.build_search_query(cx) {
+            self.model
+                .update(cx, |model, cx| model.search_in_results(query, cx));
+        }
+    }
+
+    /// Returns the search that is being edited, or `None` if its filters are invalid.
+    fn saved_search(&self, kind: SavedSearchKind, cx: &AppContext) -> Option<SavedSearch> {
+        let query = match kind {
+            SavedSearchKind::Preset => String::new(),
+            SavedSearchKind::Saved | SavedSearchKind::Recent => self.query_editor.read(cx).text(cx),
+        };
+        let files_to_include =
+            Self::parse_path_matches(&self.included_files_editor.read(cx).text(cx)).ok()?;
+        let files_to_exclude =
+            Self::parse_path_matches(&self.excluded_files_editor.read(cx).text(cx)).ok()?;
+        let mut search = SavedSearch {
+            kind,
+            name: String::new(),
+            query,
+            options: self.search_options,
+            files_to_include: files_to_include.sources().join(", "),
+            files_to_exclude: files_to_exclude.sources().join(", "),
+            replacement: self.replace_enabled.then(|| self.replacement(cx)),
+        };
+        search.name = match kind {
+            SavedSearchKind::Preset => search.description(),
+            SavedSearchKind::Saved | SavedSearchKind::Recent => search.query.clone(),
+        };
+        Some(search)
+    }
+
+    fn save_search(&mut self, kind: SavedSearchKind, cx: &mut ViewContext<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        let Some(search) = self.saved_search(kind, cx) else {
+            return;
+        };
+        if search.name.is_empty() {
+            return;
         }
+        cx.background_executor()
+            .spawn(async move { SEARCH_DB.save_search(workspace_id, search).await })
+            .detach_and_log_err(cx);
+    }
+
+    fn record_recent_search(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        let Some(search) = self.saved_search(SavedSearchKind::Recent, cx) else {
+            return;
+        };
+        cx.background_executor()
+            .spawn(async move {
+                SEARCH_DB
+                    .record_recent_search(workspace_id, search, MAX_RECENT_SEARCHES)
+                    .await
+            })
+            .detach_and_log_err(cx);
+    }
+
+    /// Fills in the query, options, filters and replacement of the given search,
+    /// then runs it. Presets keep the query that is being typed.
+    fn apply_saved_search(&mut self, search: &SavedSearch, cx: &mut ViewContext<Self>) {
+        if search.kind != SavedSearchKind::Preset {
+            self.set_query(&search.query, cx);
+        }
+        self.search_options = search.options;
+        self.included_files_editor.update(cx, |editor, cx| {
+            editor.set_text(search.files_to_include.as_str(), cx)
+        });
+        self.excluded_files_editor.update(cx, |editor, cx| {
+            editor.set_text(search.files_to_exclude.as_str(), cx)
+        });
+        if !search.files_to_include.is_empty() || !search.files_to_exclude.is_empty() {
+            self.filters_enabled = true;
+        }
+        if let Some(replacement) = &search.replacement {
+            self.replacement_editor
+                .update(cx, |editor, cx| editor.set_text(replacement.as_str(), cx));
+            self.replace_enabled = true;
+        }
+        cx.notify();
+
+        if self.query_editor.read(cx).text(cx).is_empty() {
+            self.focus_query_editor(cx);
+        } else {
+            self.search(cx);
+        }
+    }
+
+    /// Opens the given saved search in the active project search, or in a new one.
+    pub(crate) fn open_saved_search(
+        workspace: &mut Workspace,
+        search: &SavedSearch,
+        cx: &mut ViewContext<Workspace>,
+    ) {
+        let existing = workspace
+            .active_item(cx)
+            .and_then(|item| item.downcast::<ProjectSearchView>());
+        let search_view = if let Some(existing) = existing {
+            existing
+        } else {
+            let model = cx.new_model(|cx| ProjectSearch::new(workspace.project().clone(), cx));
+            let view = cx.new_view(|cx| ProjectSearchView::new(model, cx, None));
+            workspace.add_item_to_active_pane(Box::new(view.clone()), None, cx);
+            view
+        };
+        search_view.update(cx, |search_view, cx| {
+            search_view.apply_saved_search(search, cx)
+        });
     }
 
     fn build_search_query(&mut self, cx: &mut ViewContext<Self>) -> Option<SearchQuery> {
@@ -894,7 +1158,31 @@ impl ProjectSearchView {
                 }
             };
 
-        let query = if self.search_options.contains(SearchOptions::REGEX) {
+        let query = if self.search_options.contains(SearchOptions::STRUCTURAL) {
+            match SearchQuery::structural(
+                text,
+                self.search_options.contains(SearchOptions::INCLUDE_IGNORED),
+                included_files,
+                excluded_files,
+            ) {
+                Ok(query) => {
+                    let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
+                    if should_unmark_error {
+                        cx.notify();
+                    }
+
+                    Some(query)
+                }
+                Err(_e) => {
+                    let should_mark_error = self.panels_with_errors.insert(InputPanel::Query);
+                    if should_mark_error {
+                        cx.notify();
+                    }
+
+                    None
+                }
+            }
+        } else if self.search_options.contains(SearchOptions::REGEX) {
             match SearchQuery::regex(
                 text,
                 self.search_options.contains(SearchOptions::WHOLE_WORD),
@@ -1217,6 +1505,18 @@ impl ProjectSearchBar {
         }
     }
 
+    fn search_in_results(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(search_view) = self.active_project_search.as_ref() {
+            search_view.update(cx, |search_view, cx| search_view.search_in_results(cx));
+        }
+    }
+
+    fn save_search(&mut self, kind: SavedSearchKind, cx: &mut ViewContext<Self>) {
+        if let Some(search_view) = self.active_project_search.as_ref() {
+            search_view.update(cx, |search_view, cx| search_view.save_search(kind, cx));
+        }
+    }
+
     fn move_focus_to_results(&self, cx: &mut ViewContext<Self>) {
         if let Some(search_view) = self.active_project_search.as_ref() {
             search_view.update(cx, |search_view, cx| {
@@ -1376,6 +1676,12 @@ impl Render for ProjectSearchBar {
                         cx.listener(|this, _, cx| {
                             this.toggle_search_option(SearchOptions::REGEX, cx);
                         }),
+                    ))
+                    .child(SearchOptions::STRUCTURAL.as_button(
+                        self.is_option_enabled(SearchOptions::STRUCTURAL, cx),
+                        cx.listener(|this, _, cx| {
+                            this.toggle_search_option(SearchOptions::STRUCTURAL, cx);
+                        }),
                     )),
             );
 
@@ -1406,6 +1712,23 @@ impl Render for ProjectSearchBar {
                                 .unwrap_or_default(),
                         )
                         .tooltip(|cx| Tooltip::for_action("Toggle replace", &ToggleReplace, cx)),
+                )
+                .child(
+                    IconButton::new("project-search-in-results", IconName::ListTree)
+                        .disabled(search.active_match_index.is_none())
+                        .on_click(cx.listener(|this, _, cx| {
+                            this.search_in_results(cx);
+                        }))
+                        .tooltip(|cx| {
+                            Tooltip::for_action("Search in results", &SearchInResults, cx)
+                        }),
+                )
+                .child(
+                    IconButton::new("project-search-save", IconName::Bookmark)
+                        .on_click(cx.listener(|this, _, cx| {
+                            this.save_search(SavedSearchKind::Saved, cx);
+                        }))
+                        .tooltip(|cx| Tooltip::for_action("Save search", &SaveSearch, cx)),
                 ),
         );
 
@@ -1576,6 +1899,9 @@ impl Render for ProjectSearchBar {
                 cx.stop_propagation();
             }))
             .on_action(cx.listener(|this, action, cx| this.confirm(action, cx)))
+            .on_action(cx.listener(|this, _: &SearchInResults, cx| {
+                this.search_in_results(cx);
+            }))
             .on_action(cx.listener(|this, action, cx| {
                 this.toggle_replace(action, cx);
             }))
@@ -3228,6 +3554,129 @@ pub mod tests {
             .expect("unable to update search view");
     }
 
+    #[gpui::test]
+    async fn test_search_in_results_and_saved_searches(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            "/dir",
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+                "three.rs": "const THREE: usize = one::ONE + two::TWO;",
+                "four.rs": "const FOUR: usize = one::ONE + three::THREE;",
+                "five.rs": "const FIVE: usize = two::TWO;\n\n\n\n\nconst SIX: usize = one::ONE;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), ["/dir".as_ref()], cx).await;
+        let search = cx.new_model(|cx| ProjectSearch::new(project, cx));
+        let search_view = cx.add_window(|cx| ProjectSearchView::new(search.clone(), cx, None));
+
+        perform_search(search_view, "TWO", cx);
+
+        // Only the excerpts that are already in the results are searched, and the search
+        // is case-insensitive.
+        search_view
+            .update(cx, |search_view, cx| {
+                search_view
+                    .query_editor
+                    .update(cx, |query_editor, cx| query_editor.set_text("ONE", cx));
+                search_view.search_in_results(cx);
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert_eq!(
+                    search_view
+                        .results_editor
+                        .update(cx, |editor, cx| editor.display_text(cx)),
+                    "\n\n\nconst THREE: usize = one::ONE + two::TWO;\n\n\n\n\nconst TWO: usize = one::ONE + one::ONE;\n"
+                );
+                assert_eq!(search_view.model.read(cx).match_ranges.len(), 6);
+            })
+            .unwrap();
+
+        // Searches that don't match any result leave no results.
+        search_view
+            .update(cx, |search_view, cx| {
+                search_view
+                    .query_editor
+                    .update(cx, |query_editor, cx| query_editor.set_text("FOUR", cx));
+                search_view.search_in_results(cx);
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert!(search_view.model.read(cx).match_ranges.is_empty());
+                assert_eq!(search_view.model.read(cx).no_results, Some(true));
+            })
+            .unwrap();
+
+        // Saved searches bring back their query, options and filters.
+        let saved_search = SavedSearch {
+            kind: SavedSearchKind::Saved,
+            name: "one".into(),
+            query: "one".into(),
+            options: SearchOptions::CASE_SENSITIVE,
+            files_to_include: "t*.rs".into(),
+            files_to_exclude: "three.rs".into(),
+            replacement: None,
+        };
+        search_view
+            .update(cx, |search_view, cx| {
+                search_view.apply_saved_search(&saved_search, cx);
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert!(search_view.filters_enabled);
+                assert_eq!(search_view.search_options, SearchOptions::CASE_SENSITIVE);
+                assert_eq!(
+                    search_view
+                        .results_editor
+                        .update(cx, |editor, cx| editor.display_text(cx)),
+                    "\n\n\nconst TWO: usize = one::ONE + one::ONE;\n"
+                );
+                assert_eq!(
+                    search_view.saved_search(SavedSearchKind::Saved, cx),
+                    Some(saved_search.clone())
+                );
+            })
+            .unwrap();
+
+        // Presets keep the query that is being typed.
+        let preset = SavedSearch {
+            kind: SavedSearchKind::Preset,
+            name: "whole word".into(),
+            query: String::new(),
+            options: SearchOptions::WHOLE_WORD,
+            files_to_include: String::new(),
+            files_to_exclude: String::new(),
+            replacement: None,
+        };
+        search_view
+            .update(cx, |search_view, cx| {
+                search_view
+                    .query_editor
+                    .update(cx, |query_editor, cx| query_editor.set_text("ON", cx));
+                search_view.apply_saved_search(&preset, cx);
+            })
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        search_view
+            .update(cx, |search_view, cx| {
+                assert_eq!(search_view.query_editor.read(cx).text(cx), "ON");
+                assert_eq!(search_view.search_options, SearchOptions::WHOLE_WORD);
+                assert_eq!(search_view.model.read(cx).no_results, Some(true));
+            })
+            .unwrap();
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings = SettingsStore::test(cx);
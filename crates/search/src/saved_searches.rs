@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, Task,
+    View, ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{ModalView, Workspace, WorkspaceId};
+
+use crate::{
+    persistence::{SavedSearch, SavedSearchKind, SEARCH_DB},
+    ProjectSearchView,
+};
+
+actions!(saved_searches, [Toggle, RemoveSelected]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(SavedSearchesView::register).detach();
+}
+
+pub struct SavedSearchesView {
+    picker: View<Picker<SavedSearchesViewDelegate>>,
+}
+
+impl SavedSearchesView {
+    fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+        workspace.register_action(|workspace, _: &Toggle, cx| {
+            let Some(workspace_id) = workspace.database_id() else {
+                return;
+            };
+            let handle = cx.view().downgrade();
+            workspace.toggle_modal(cx, move |cx| {
+                SavedSearchesView::new(handle, workspace_id, cx)
+            });
+        });
+    }
+
+    fn new(
+        workspace: WeakView<Workspace>,
+        workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let delegate =
+            SavedSearchesViewDelegate::new(cx.view().downgrade(), workspace, workspace_id);
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+        Self { picker }
+    }
+
+    fn remove_selected(&mut self, _: &RemoveSelected, cx: &mut ViewContext<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let Some(search) = picker.delegate.selected_search().cloned() else {
+                return;
+            };
+            let workspace_id = picker.delegate.workspace_id;
+            cx.spawn(|picker, mut cx| async move {
+                SEARCH_DB
+                    .delete_saved_search(workspace_id, search.kind, search.name)
+                    .await?;
+                picker.update(&mut cx, |picker, cx| {
+                    picker.delegate.load_searches();
+                    picker.refresh(cx);
+                })
+            })
+            .detach_and_log_err(cx);
+        });
+    }
+}
+
+impl Render for SavedSearchesView {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("SavedSearches")
+            .w(rems(34.))
+            .on_action(cx.listener(Self::remove_selected))
+            .child(self.picker.clone())
+    }
+}
+
+impl FocusableView for SavedSearchesView {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for SavedSearchesView {}
+impl ModalView for SavedSearchesView {}
+
+pub struct SavedSearchesViewDelegate {
+    saved_searches_view: WeakView<SavedSearchesView>,
+    workspace: WeakView<Workspace>,
+    workspace_id: WorkspaceId,
+    searches: Vec<SavedSearch>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl SavedSearchesViewDelegate {
+    fn new(
+        saved_searches_view: WeakView<SavedSearchesView>,
+        workspace: WeakView<Workspace>,
+        workspace_id: WorkspaceId,
+    ) -> Self {
+        let mut this = Self {
+            saved_searches_view,
+            workspace,
+            workspace_id,
+            searches: Vec::new(),
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        this.load_searches();
+        this
+    }
+
+    /// Lists the saved searches and presets before the recent searches.
+    fn load_searches(&mut self) {
+        self.searches = SEARCH_DB
+            .saved_searches(self.workspace_id)
+            .log_err()
+            .unwrap_or_default();
+        self.searches
+            .sort_by_key(|search| search.kind == SavedSearchKind::Recent);
+    }
+
+    fn selected_search(&self) -> Option<&SavedSearch> {
+        let mat = self.matches.get(self.selected_index)?;
+        self.searches.get(mat.candidate_id)
+    }
+}
+
+impl PickerDelegate for SavedSearchesViewDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Search saved and recent searches...".into()
+    }
+
+    fn no_matches_text(&self, _cx: &mut WindowContext) -> SharedString {
+        "No saved searches".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        let candidates = self
+            .searches
+            .iter()
+            .enumerate()
+            .map(|(id, search)| StringMatchCandidate::new(id, search.name.clone()))
+            .collect::<Vec<_>>();
+        let background = cx.background_executor().clone();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(search) = self.selected_search().cloned() else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                ProjectSearchView::open_saved_search(workspace, &search, cx);
+            })
+            .log_err();
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.saved_searches_view
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let search = self.searches.get(mat.candidate_id)?;
+        let icon = match search.kind {
+            SavedSearchKind::Saved => IconName::Bookmark,
+            SavedSearchKind::Preset => IconName::Sliders,
+            SavedSearchKind::Recent => IconName::HistoryRerun,
+        };
+        let description = match search.kind {
+            SavedSearchKind::Preset => String::new(),
+            SavedSearchKind::Saved | SavedSearchKind::Recent => search.description(),
+        };
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .start_slot(Icon::new(icon).size(IconSize::Small).color(Color::Muted))
+                .child(
+                    h_flex()
+                        .gap_2()
+                        .child(HighlightedLabel::new(
+                            search.name.clone(),
+                            mat.positions.clone(),
+                        ))
+                        .when(!description.is_empty(), |this| {
+                            this.child(Label::new(description).color(Color::Muted))
+                        }),
+                ),
+        )
+    }
+}
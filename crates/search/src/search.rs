@@ -9,13 +9,16 @@ use workspace::notifications::NotificationId;
 use workspace::{Toast, Workspace};
 
 pub mod buffer_search;
+mod persistence;
 pub mod project_search;
+pub mod saved_searches;
 pub(crate) mod search_bar;
 
 pub fn init(cx: &mut AppContext) {
     menu::init();
     buffer_search::init(cx);
     project_search::init(cx);
+    saved_searches::init(cx);
 }
 
 actions!(
@@ -26,6 +29,7 @@ actions!(
         ToggleCaseSensitive,
         ToggleIncludeIgnored,
         ToggleRegex,
+        ToggleStructural,
         ToggleReplace,
         ToggleSelection,
         SelectNextMatch,
@@ -46,6 +50,7 @@ bitflags! {
         const CASE_SENSITIVE = 0b010;
         const INCLUDE_IGNORED = 0b100;
         const REGEX = 0b1000;
+        const STRUCTURAL = 0b10000;
     }
 }
 
@@ -56,6 +61,7 @@ impl SearchOptions {
             SearchOptions::CASE_SENSITIVE => "match case",
             SearchOptions::INCLUDE_IGNORED => "include Ignored",
             SearchOptions::REGEX => "regular expression",
+            SearchOptions::STRUCTURAL => "structural search",
             _ => panic!("{:?} is not a named SearchOption", self),
         }
     }
@@ -66,6 +72,7 @@ impl SearchOptions {
             SearchOptions::CASE_SENSITIVE => ui::IconName::CaseSensitive,
             SearchOptions::INCLUDE_IGNORED => ui::IconName::FileGit,
             SearchOptions::REGEX => ui::IconName::Regex,
+            SearchOptions::STRUCTURAL => ui::IconName::Code,
             _ => panic!("{:?} is not a named SearchOption", self),
         }
     }
@@ -76,6 +83,7 @@ impl SearchOptions {
             SearchOptions::CASE_SENSITIVE => Box::new(ToggleCaseSensitive),
             SearchOptions::INCLUDE_IGNORED => Box::new(ToggleIncludeIgnored),
             SearchOptions::REGEX => Box::new(ToggleRegex),
+            SearchOptions::STRUCTURAL => Box::new(ToggleStructural),
             _ => panic!("{:?} is not a named SearchOption", self),
         }
     }
@@ -90,6 +98,7 @@ impl SearchOptions {
         options.set(SearchOptions::CASE_SENSITIVE, query.case_sensitive());
         options.set(SearchOptions::INCLUDE_IGNORED, query.include_ignored());
         options.set(SearchOptions::REGEX, query.is_regex());
+        options.set(SearchOptions::STRUCTURAL, query.is_structural());
         options
     }
 
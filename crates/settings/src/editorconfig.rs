@@ -0,0 +1,376 @@
+use globset::{GlobBuilder, GlobMatcher};
+use std::{num::NonZeroU32, path::Path};
+
+/// The name of the files that hold EditorConfig properties.
+pub const EDITORCONFIG_NAME: &str = ".editorconfig";
+
+/// A parsed `.editorconfig` file.
+///
+/// See <https://spec.editorconfig.org> for the file format.
+#[derive(Debug, Default)]
+pub struct EditorConfig {
+    /// Whether this file is the topmost one, so that files in parent directories are ignored.
+    pub is_root: bool,
+    sections: Vec<(GlobMatcher, EditorConfigProperties)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tab,
+    Space,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndentSize {
+    /// Use the value of `tab_width`.
+    Tab,
+    Columns(NonZeroU32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndOfLine {
+    Lf,
+    CrLf,
+    Cr,
+}
+
+/// The state of a property, as set by the sections that match a file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EditorConfigValue<T> {
+    /// No section sets the property.
+    #[default]
+    Missing,
+    /// The property is explicitly `unset`, discarding the value set by earlier
+    /// sections or by `.editorconfig` files in parent directories.
+    Unset,
+    Value(T),
+}
+
+impl<T: Copy> EditorConfigValue<T> {
+    /// Returns the value of the property, if it is set.
+    pub fn value(&self) -> Option<T> {
+        match self {
+            Self::Value(value) => Some(*value),
+            Self::Missing | Self::Unset => None,
+        }
+    }
+}
+
+/// The properties that apply to a file, merged from every matching section.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EditorConfigProperties {
+    pub indent_style: EditorConfigValue<IndentStyle>,
+    pub indent_size: EditorConfigValue<IndentSize>,
+    pub tab_width: EditorConfigValue<NonZeroU32>,
+    pub end_of_line: EditorConfigValue<EndOfLine>,
+    pub insert_final_newline: EditorConfigValue<bool>,
+    pub trim_trailing_whitespace: EditorConfigValue<bool>,
+    /// `Value(None)` when the line length is explicitly `off`.
+    pub max_line_length: EditorConfigValue<Option<NonZeroU32>>,
+}
+
+impl EditorConfig {
+    /// Parses the content of an `.editorconfig` file.
+    ///
+    /// Parsing is lenient: unknown properties, invalid values and sections
+    /// with invalid globs are ignored.
+    pub fn parse(content: &str) -> Self {
+        let mut config = Self::default();
+        let mut current_section: Option<(GlobMatcher, EditorConfigProperties)> = None;
+        let mut in_invalid_section = false;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(glob) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                config.sections.extend(current_section.take());
+                current_section =
+                    section_matcher(glob).map(|matcher| (matcher, Default::default()));
+                in_invalid_section = current_section.is_none();
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim().to_lowercase();
+            if let Some((_, properties)) = current_section.as_mut() {
+                properties.set(&key, &value);
+            } else if !in_invalid_section && key == "root" {
+                config.is_root = value == "true";
+            }
+        }
+        config.sections.extend(current_section);
+        config
+    }
+
+    /// Returns the merged properties for a path relative to the directory containing this file.
+    pub fn properties_for(&self, relative_path: &Path) -> EditorConfigProperties {
+        let mut properties = EditorConfigProperties::default();
+        for (matcher, section) in &self.sections {
+            if matcher.is_match(relative_path) {
+                properties.merge(section);
+            }
+        }
+        properties
+    }
+}
+
+impl EditorConfigProperties {
+    /// Overrides these properties with the ones that are set or unset in `other`.
+    pub fn merge(&mut self, other: &Self) {
+        fn merge<T: Copy>(target: &mut EditorConfigValue<T>, value: EditorConfigValue<T>) {
+            if !matches!(value, EditorConfigValue::Missing) {
+                *target = value;
+            }
+        }
+
+        merge(&mut self.indent_style, other.indent_style);
+        merge(&mut self.indent_size, other.indent_size);
+        merge(&mut self.tab_width, other.tab_width);
+        merge(&mut self.end_of_line, other.end_of_line);
+        merge(&mut self.insert_final_newline, other.insert_final_newline);
+        merge(
+            &mut self.trim_trailing_whitespace,
+            other.trim_trailing_whitespace,
+        );
+        merge(&mut self.max_line_length, other.max_line_length);
+    }
+
+    /// Returns whether no property is set or unset.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns the number of columns occupied by a tab, falling back to
+    /// `indent_size` as described by the specification.
+    pub fn tab_width(&self) -> Option<NonZeroU32> {
+        self.tab_width.value().or(match self.indent_size {
+            EditorConfigValue::Value(IndentSize::Columns(columns)) => Some(columns),
+            _ => None,
+        })
+    }
+
+    /// Returns the number of columns used for each indentation level.
+    pub fn indent_size(&self) -> Option<NonZeroU32> {
+        match self.indent_size.value()? {
+            IndentSize::Columns(columns) => Some(columns),
+            IndentSize::Tab => self.tab_width.value(),
+        }
+    }
+
+    /// Returns the language settings corresponding to these properties, in
+    /// the format of a settings file.
+    pub fn to_settings(&self) -> serde_json::Value {
+        let mut settings = serde_json::Map::new();
+        if let Some(indent_style) = self.indent_style.value() {
+            settings.insert(
+                "hard_tabs".into(),
+                (indent_style == IndentStyle::Tab).into(),
+            );
+        }
+        let tab_size = if self.indent_style == EditorConfigValue::Value(IndentStyle::Tab) {
+            self.tab_width().or(self.indent_size())
+        } else {
+            self.indent_size().or(self.tab_width())
+        };
+        if let Some(tab_size) = tab_size {
+            settings.insert("tab_size".into(), tab_size.get().into());
+        }
+        if let Some(Some(max_line_length)) = self.max_line_length.value() {
+            settings.insert("preferred_line_length".into(), max_line_length.get().into());
+        }
+        if let Some(insert_final_newline) = self.insert_final_newline.value() {
+            settings.insert(
+                "ensure_final_newline_on_save".into(),
+                insert_final_newline.into(),
+            );
+        }
+        if let Some(trim_trailing_whitespace) = self.trim_trailing_whitespace.value() {
+            settings.insert(
+                "remove_trailing_whitespace_on_save".into(),
+                trim_trailing_whitespace.into(),
+            );
+        }
+        match self.end_of_line.value() {
+            Some(EndOfLine::Lf) => {
+                settings.insert("line_ending".into(), "unix".into());
+            }
+            Some(EndOfLine::CrLf) => {
+                settings.insert("line_ending".into(), "windows".into());
+            }
+            // Lone carriage returns are not supported as line endings.
+            Some(EndOfLine::Cr) | None => {}
+        }
+        settings.into()
+    }
+
+    fn set(&mut self, key: &str, value: &str) {
+        match key {
+            "indent_style" => update(&mut self.indent_style, value, |value| match value {
+                "tab" => Some(IndentStyle::Tab),
+                "space" => Some(IndentStyle::Space),
+                _ => None,
+            }),
+            "indent_size" => update(&mut self.indent_size, value, |value| match value {
+                "tab" => Some(IndentSize::Tab),
+                _ => value.parse().ok().map(IndentSize::Columns),
+            }),
+            "tab_width" => update(&mut self.tab_width, value, |value| value.parse().ok()),
+            "end_of_line" => update(&mut self.end_of_line, value, |value| match value {
+                "lf" => Some(EndOfLine::Lf),
+                "crlf" => Some(EndOfLine::CrLf),
+                "cr" => Some(EndOfLine::Cr),
+                _ => None,
+            }),
+            "insert_final_newline" => update(&mut self.insert_final_newline, value, |value| {
+                value.parse().ok()
+            }),
+            "trim_trailing_whitespace" => {
+                update(&mut self.trim_trailing_whitespace, value, |value| {
+                    value.parse().ok()
+                })
+            }
+            "max_line_length" => update(&mut self.max_line_length, value, |value| match value {
+                "off" => Some(None),
+                _ => value.parse().ok().map(Some),
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// Sets a property to its parsed value, or marks it as unset when the value is
+/// `unset`. Invalid values leave the property untouched.
+fn update<T>(
+    target: &mut EditorConfigValue<T>,
+    value: &str,
+    parse: impl FnOnce(&str) -> Option<T>,
+) {
+    if let Some(value) = parse(value) {
+        *target = EditorConfigValue::Value(value);
+    } else if value == "unset" {
+        *target = EditorConfigValue::Unset;
+    }
+}
+
+/// Globs without a slash match files in any subdirectory, while the
+/// others are relative to the directory containing the `.editorconfig` file.
+fn section_matcher(glob: &str) -> Option<GlobMatcher> {
+    let glob = match glob.strip_prefix('/') {
+        Some(glob) => glob.to_string(),
+        None if glob.contains('/') => glob.to_string(),
+        None => format!("**/{glob}"),
+    };
+    GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()
+        .ok()
+        .map(|glob| glob.compile_matcher())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editorconfig_properties() {
+        let config = EditorConfig::parse(
+            r#"
+            # top-most EditorConfig file
+            root = true
+
+            [*]
+            end_of_line = lf
+            insert_final_newline = true
+
+            [*.{js,py}]
+            indent_style = space
+            indent_size = 4
+
+            ; tabs for makefiles
+            [Makefile]
+            indent_style = tab
+
+            [lib/**/*.js]
+            indent_size = 2
+            max_line_length = off
+
+            [/docs/*.md]
+            trim_trailing_whitespace = false
+            insert_final_newline = unset
+            "#,
+        );
+        assert!(config.is_root);
+
+        let properties = config.properties_for(Path::new("src/main.py"));
+        assert_eq!(properties.indent_style.value(), Some(IndentStyle::Space));
+        assert_eq!(properties.indent_size(), NonZeroU32::new(4));
+        assert_eq!(properties.tab_width(), NonZeroU32::new(4));
+        assert_eq!(properties.end_of_line.value(), Some(EndOfLine::Lf));
+        assert_eq!(properties.insert_final_newline.value(), Some(true));
+
+        let properties = config.properties_for(Path::new("lib/a/b.js"));
+        assert_eq!(properties.indent_size(), NonZeroU32::new(2));
+        assert_eq!(properties.max_line_length.value(), Some(None));
+
+        let properties = config.properties_for(Path::new("src/Makefile"));
+        assert_eq!(properties.indent_style.value(), Some(IndentStyle::Tab));
+        assert_eq!(properties.indent_size, EditorConfigValue::Missing);
+
+        let properties = config.properties_for(Path::new("docs/README.md"));
+        assert_eq!(properties.trim_trailing_whitespace.value(), Some(false));
+        assert_eq!(properties.insert_final_newline, EditorConfigValue::Unset);
+        let properties = config.properties_for(Path::new("src/docs/README.md"));
+        assert_eq!(
+            properties.trim_trailing_whitespace,
+            EditorConfigValue::Missing
+        );
+        assert_eq!(properties.insert_final_newline.value(), Some(true));
+    }
+
+    #[test]
+    fn test_editorconfig_properties_to_settings() {
+        let config = EditorConfig::parse(
+            r#"
+            [*]
+            indent_style = tab
+            indent_size = 2
+            tab_width = 8
+            end_of_line = crlf
+            trim_trailing_whitespace = true
+            max_line_length = 100
+
+            [*.py]
+            indent_style = space
+            end_of_line = cr
+            max_line_length = off
+            "#,
+        );
+        assert_eq!(
+            config.properties_for(Path::new("main.c")).to_settings(),
+            serde_json::json!({
+                "hard_tabs": true,
+                "tab_size": 8,
+                "preferred_line_length": 100,
+                "remove_trailing_whitespace_on_save": true,
+                "line_ending": "windows",
+            })
+        );
+        assert_eq!(
+            config.properties_for(Path::new("main.py")).to_settings(),
+            serde_json::json!({
+                "hard_tabs": false,
+                "tab_size": 2,
+                "remove_trailing_whitespace_on_save": true,
+            })
+        );
+    }
+}
@@ -1,3 +1,4 @@
+mod editorconfig;
 mod keymap_file;
 mod settings_file;
 mod settings_store;
@@ -7,10 +8,12 @@ use rust_embed::RustEmbed;
 use std::{borrow::Cow, str};
 use util::asset_str;
 
+pub use editorconfig::*;
 pub use keymap_file::KeymapFile;
 pub use settings_file::*;
 pub use settings_store::{
-    Settings, SettingsJsonSchemaParams, SettingsLocation, SettingsSources, SettingsStore,
+    Settings, SettingsJsonSchemaParams, SettingsLayer, SettingsLocation, SettingsSources,
+    SettingsStore,
 };
 
 #[derive(RustEmbed)]
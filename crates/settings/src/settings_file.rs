@@ -115,22 +115,68 @@ pub fn update_settings_file<T: Settings>(
         let new_text = cx.read_global(|store: &SettingsStore, _cx| {
             store.new_text_for_update::<T>(old_text, update)
         })?;
-        let initial_path = paths::settings_file().as_path();
-        if fs.is_file(initial_path).await {
-            let resolved_path = fs.canonicalize(initial_path).await.with_context(|| {
-                format!("Failed to canonicalize settings path {:?}", initial_path)
-            })?;
+        write_settings_file(&fs, new_text).await
+    })
+    .detach_and_log_err(cx);
+}
 
-            fs.atomic_write(resolved_path.clone(), new_text)
-                .await
-                .with_context(|| format!("Failed to write settings to file {:?}", resolved_path))?;
-        } else {
-            fs.atomic_write(initial_path.to_path_buf(), new_text)
-                .await
-                .with_context(|| format!("Failed to write settings to file {:?}", initial_path))?;
-        }
+/// Sets the value at the given key path in the user's settings file, through
+/// the content of the registered setting it belongs to.
+pub fn update_settings_file_value(
+    fs: Arc<dyn Fs>,
+    cx: &mut AppContext,
+    key_path: Vec<String>,
+    value: serde_json::Value,
+) {
+    cx.spawn(|cx| async move {
+        let old_text = load_settings(&fs).await?;
+        let new_text = cx.read_global(|store: &SettingsStore, _cx| {
+            let key_path = key_path.iter().map(String::as_str).collect::<Vec<_>>();
+            store.new_text_for_key_path_update(old_text, &key_path, value)
+        })??;
+        write_settings_file(&fs, new_text).await
+    })
+    .detach_and_log_err(cx);
+}
 
-        anyhow::Ok(())
+/// Sets the value at the given key path in a project's settings file at `abs_path`,
+/// like [`update_settings_file_value`] does for the user's settings file.
+pub fn update_local_settings_file_value(
+    fs: Arc<dyn Fs>,
+    abs_path: PathBuf,
+    cx: &mut AppContext,
+    key_path: Vec<String>,
+    value: serde_json::Value,
+) {
+    cx.spawn(|cx| async move {
+        let old_text = fs.load(&abs_path).await?;
+        let new_text = cx.read_global(|store: &SettingsStore, _cx| {
+            let key_path = key_path.iter().map(String::as_str).collect::<Vec<_>>();
+            store.new_text_for_key_path_update(old_text, &key_path, value)
+        })??;
+        fs.atomic_write(abs_path.clone(), new_text)
+            .await
+            .with_context(|| format!("Failed to write settings to file {:?}", abs_path))
     })
     .detach_and_log_err(cx);
 }
+
+async fn write_settings_file(fs: &Arc<dyn Fs>, new_text: String) -> Result<()> {
+    let initial_path = paths::settings_file().as_path();
+    if fs.is_file(initial_path).await {
+        let resolved_path = fs
+            .canonicalize(initial_path)
+            .await
+            .with_context(|| format!("Failed to canonicalize settings path {:?}", initial_path))?;
+
+        fs.atomic_write(resolved_path.clone(), new_text)
+            .await
+            .with_context(|| format!("Failed to write settings to file {:?}", resolved_path))?;
+    } else {
+        fs.atomic_write(initial_path.to_path_buf(), new_text)
+            .await
+            .with_context(|| format!("Failed to write settings to file {:?}", initial_path))?;
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
+use crate::{EditorConfig, EditorConfigProperties};
 use anyhow::{anyhow, Context, Result};
-use collections::{btree_map, hash_map, BTreeMap, HashMap};
+use collections::{btree_map, hash_map, BTreeMap, BTreeSet, HashMap};
 use gpui::{AppContext, AsyncAppContext, BorrowAppContext, Global, UpdateGlobal};
 use lazy_static::lazy_static;
 use schemars::{gen::SchemaGenerator, schema::RootSchema, JsonSchema};
@@ -9,7 +10,7 @@ use std::{
     any::{type_name, Any, TypeId},
     fmt::Debug,
     ops::Range,
-    path::Path,
+    path::{Component, Path, PathBuf},
     str,
     sync::Arc,
 };
@@ -144,6 +145,19 @@ pub struct SettingsLocation<'a> {
     pub path: &'a Path,
 }
 
+/// A layer of JSON files that settings are loaded from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SettingsLayer {
+    /// The default Zed settings.
+    Default,
+    /// Settings provided by extensions.
+    Extension,
+    /// The user settings, including the ones for the current release channel.
+    User,
+    /// The settings of a project's `.zed/settings.json` files.
+    Project,
+}
+
 pub struct SettingsJsonSchemaParams<'a> {
     pub staff_mode: bool,
     pub language_names: &'a [String],
@@ -153,10 +167,14 @@ pub struct SettingsJsonSchemaParams<'a> {
 /// A set of strongly-typed setting values defined via multiple JSON files.
 pub struct SettingsStore {
     setting_values: HashMap<TypeId, Box<dyn AnySettingValue>>,
+    /// The registered setting that owns each top-level key of a settings file.
+    setting_types_by_key: HashMap<String, TypeId>,
     raw_default_settings: serde_json::Value,
     raw_user_settings: serde_json::Value,
     raw_extension_settings: serde_json::Value,
     raw_local_settings: BTreeMap<(usize, Arc<Path>), serde_json::Value>,
+    raw_editorconfigs: BTreeMap<(usize, Arc<Path>), (String, EditorConfig)>,
+    local_file_paths: BTreeSet<(usize, Arc<Path>)>,
     tab_size_callback: Option<(
         TypeId,
         Box<dyn Fn(&dyn Any) -> Option<usize> + Send + Sync + 'static>,
@@ -169,10 +187,13 @@ impl Default for SettingsStore {
     fn default() -> Self {
         SettingsStore {
             setting_values: Default::default(),
+            setting_types_by_key: Default::default(),
             raw_default_settings: serde_json::json!({}),
             raw_user_settings: serde_json::json!({}),
             raw_extension_settings: serde_json::json!({}),
             raw_local_settings: Default::default(),
+            raw_editorconfigs: Default::default(),
+            local_file_paths: Default::default(),
             tab_size_callback: Default::default(),
         }
     }
@@ -188,6 +209,8 @@ trait AnySettingValue: 'static + Send + Sync {
     fn key(&self) -> Option<&'static str>;
     fn setting_type_name(&self) -> &'static str;
     fn deserialize_setting(&self, json: &serde_json::Value) -> Result<DeserializedSetting>;
+    fn default_setting(&self) -> DeserializedSetting;
+    fn serialize_setting(&self, setting: &DeserializedSetting) -> serde_json::Value;
     fn load_setting(
         &self,
         sources: SettingsSources<DeserializedSetting>,
@@ -196,6 +219,7 @@ trait AnySettingValue: 'static + Send + Sync {
     fn value_for_path(&self, path: Option<SettingsLocation>) -> &dyn Any;
     fn set_global_value(&mut self, value: Box<dyn Any>);
     fn set_local_value(&mut self, root_id: usize, path: Arc<Path>, value: Box<dyn Any>);
+    fn remove_local_value(&mut self, root_id: usize, path: &Path);
     fn json_schema(
         &self,
         generator: &mut SchemaGenerator,
@@ -227,6 +251,22 @@ impl SettingsStore {
             local_values: Vec::new(),
         }));
 
+        // Settings with a key always own it. Keyless settings claim the
+        // top-level fields of their content, with the first registration
+        // taking precedence.
+        if let Some(key) = setting_value.key() {
+            self.setting_types_by_key
+                .insert(key.to_string(), setting_type_id);
+        } else if let serde_json::Value::Object(fields) =
+            setting_value.serialize_setting(&setting_value.default_setting())
+        {
+            for key in fields.into_iter().map(|(key, _)| key) {
+                self.setting_types_by_key
+                    .entry(key)
+                    .or_insert(setting_type_id);
+            }
+        }
+
         if let Some(default_settings) = setting_value
             .deserialize_setting(&self.raw_default_settings)
             .log_err()
@@ -351,19 +391,82 @@ impl SettingsStore {
             .setting_values
             .get(&setting_type_id)
             .unwrap_or_else(|| panic!("unregistered setting type {}", type_name::<T>()));
-        let raw_settings = parse_json_with_comments::<serde_json::Value>(text).unwrap_or_default();
-        let old_content = match setting.deserialize_setting(&raw_settings) {
-            Ok(content) => content.0.downcast::<T::FileContent>().unwrap(),
-            Err(_) => Box::<<T as Settings>::FileContent>::default(),
+        self.edits_for_setting_update(setting.as_ref(), text, |content| {
+            update(content.0.downcast_mut::<T::FileContent>().unwrap());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    /// Sets the value at a key path in a JSON file, returning the new text
+    /// for that JSON file.
+    pub fn new_text_for_key_path_update(
+        &self,
+        old_text: String,
+        key_path: &[&str],
+        new_value: serde_json::Value,
+    ) -> Result<String> {
+        let edits = self.edits_for_key_path_update(&old_text, key_path, new_value)?;
+        let mut new_text = old_text;
+        for (range, replacement) in edits.into_iter() {
+            new_text.replace_range(range, &replacement);
+        }
+        Ok(new_text)
+    }
+
+    /// Sets the value at a key path in a JSON file, returning a list of edits
+    /// to apply to the JSON file.
+    ///
+    /// The value is applied to the content of the registered setting that the
+    /// key path belongs to, like [`Self::edits_for_update`] does, and must be
+    /// valid for that setting.
+    pub fn edits_for_key_path_update(
+        &self,
+        text: &str,
+        key_path: &[&str],
+        new_value: serde_json::Value,
+    ) -> Result<Vec<(Range<usize>, String)>> {
+        let (first_key, _) = key_path.split_first().context("empty key path")?;
+        let setting = self
+            .setting_types_by_key
+            .get(*first_key)
+            .and_then(|setting_type_id| self.setting_values.get(setting_type_id))
+            .with_context(|| format!("no setting found for key path {key_path:?}"))?;
+        let content_key_path = match setting.key() {
+            Some(_) => &key_path[1..],
+            None => key_path,
         };
-        let mut new_content = old_content.clone();
-        update(&mut new_content);
 
-        let old_value = serde_json::to_value(&old_content).unwrap();
-        let new_value = serde_json::to_value(new_content).unwrap();
+        self.edits_for_setting_update(setting.as_ref(), text, |content| {
+            let mut json = setting.serialize_setting(content);
+            set_value_at_key_path(&mut json, content_key_path, new_value);
+            if let Some(key) = setting.key() {
+                json = serde_json::Value::Object(serde_json::Map::from_iter([(
+                    key.to_string(),
+                    json,
+                )]));
+            }
+            *content = setting.deserialize_setting(&json)?;
+            Ok(())
+        })
+    }
+
+    fn edits_for_setting_update(
+        &self,
+        setting: &dyn AnySettingValue,
+        text: &str,
+        update: impl FnOnce(&mut DeserializedSetting) -> Result<()>,
+    ) -> Result<Vec<(Range<usize>, String)>> {
+        let raw_settings = parse_json_with_comments::<serde_json::Value>(text).unwrap_or_default();
+        let mut content = setting
+            .deserialize_setting(&raw_settings)
+            .unwrap_or_else(|_| setting.default_setting());
+        let old_value = setting.serialize_setting(&content);
+        update(&mut content)?;
+        let new_value = setting.serialize_setting(&content);
 
         let mut key_path = Vec::new();
-        if let Some(key) = T::KEY {
+        if let Some(key) = setting.key() {
             key_path.push(key);
         }
 
@@ -378,7 +481,53 @@ impl SettingsStore {
             &new_value,
             &mut edits,
         );
-        edits
+        Ok(edits)
+    }
+
+    /// Returns the raw JSON values set at a key path by each layer of settings
+    /// that applies to the given location, ordered from least specific to most specific.
+    pub fn raw_values_for_key_path(
+        &self,
+        key_path: &[&str],
+        location: Option<SettingsLocation>,
+    ) -> Vec<(SettingsLayer, &serde_json::Value)> {
+        fn value_at_key_path<'a>(
+            json: &'a serde_json::Value,
+            key_path: &[&str],
+        ) -> Option<&'a serde_json::Value> {
+            key_path
+                .iter()
+                .try_fold(json, |value, key| value.get(key))
+                .filter(|value| !value.is_null())
+        }
+
+        let release_channel_settings = self
+            .raw_user_settings
+            .get(release_channel::RELEASE_CHANNEL.dev_name());
+        let mut values = [
+            (SettingsLayer::Default, Some(&self.raw_default_settings)),
+            (SettingsLayer::Extension, Some(&self.raw_extension_settings)),
+            (SettingsLayer::User, Some(&self.raw_user_settings)),
+            (SettingsLayer::User, release_channel_settings),
+        ]
+        .into_iter()
+        .filter_map(|(layer, json)| Some((layer, value_at_key_path(json?, key_path)?)))
+        .collect::<Vec<_>>();
+
+        if let Some(location) = location {
+            values.extend(
+                self.raw_local_settings
+                    .range(
+                        (location.worktree_id, Path::new("").into())
+                            ..(location.worktree_id + 1, Path::new("").into()),
+                    )
+                    .filter(|((_, directory), _)| location.path.starts_with(directory))
+                    .filter_map(|(_, json)| {
+                        Some((SettingsLayer::Project, value_at_key_path(json, key_path)?))
+                    }),
+            );
+        }
+        values
     }
 
     /// Configure the tab sized when updating JSON files.
@@ -479,9 +628,125 @@ impl SettingsStore {
         }
     }
 
+    /// Add or remove the `.editorconfig` file located in the given directory.
+    ///
+    /// Directories above the worktree root are given as a path going up to
+    /// that directory, followed by the path going back down to the root. For
+    /// example, `../../code/zed` for the file in `~` when the worktree is
+    /// `~/code/zed`.
+    pub fn set_local_editorconfig(
+        &mut self,
+        root_id: usize,
+        directory: Arc<Path>,
+        editorconfig_content: Option<&str>,
+        cx: &mut AppContext,
+    ) -> Result<()> {
+        if let Some(content) = editorconfig_content {
+            self.raw_editorconfigs.insert(
+                (root_id, directory.clone()),
+                (content.to_string(), EditorConfig::parse(content)),
+            );
+        } else {
+            self.raw_editorconfigs.remove(&(root_id, directory.clone()));
+        }
+        let changed_path = if is_above_root(&directory) {
+            Path::new("")
+        } else {
+            directory.as_ref()
+        };
+        self.recompute_values(Some((root_id, changed_path)), cx)?;
+        Ok(())
+    }
+
+    /// Registers a file whose settings are computed separately from the ones
+    /// of its directory, so that the `.editorconfig` properties matching its
+    /// path apply to it.
+    ///
+    /// Its settings are only recomputed when an `.editorconfig` file applies to it,
+    /// either now or once one is added.
+    pub fn register_local_file(
+        &mut self,
+        root_id: usize,
+        path: Arc<Path>,
+        cx: &mut AppContext,
+    ) -> Result<()> {
+        if !self.local_file_paths.insert((root_id, path.clone())) {
+            return Ok(());
+        }
+        let location = SettingsLocation {
+            worktree_id: root_id,
+            path: &path,
+        };
+        if self.editorconfig_properties(location).is_some() {
+            self.recompute_values(Some((root_id, &path)), cx)?;
+        }
+        Ok(())
+    }
+
+    /// Stops computing settings separately for a file registered with
+    /// [`Self::register_local_file`].
+    pub fn unregister_local_file(&mut self, root_id: usize, path: &Path) {
+        if self.local_file_paths.remove(&(root_id, path.into())) {
+            for setting_value in self.setting_values.values_mut() {
+                setting_value.remove_local_value(root_id, path);
+            }
+        }
+    }
+
+    /// Returns the `.editorconfig` properties that apply to the given path.
+    ///
+    /// Like local settings, files in nested directories take precedence over the
+    /// ones in their ancestors, up to the innermost file marked with `root = true`.
+    pub fn editorconfig_properties(
+        &self,
+        location: SettingsLocation,
+    ) -> Option<EditorConfigProperties> {
+        let mut editorconfigs = self
+            .raw_editorconfigs
+            .range(
+                (location.worktree_id, Path::new("").into())
+                    ..(location.worktree_id + 1, Path::new("").into()),
+            )
+            .filter_map(|((_, directory), (_, editorconfig))| {
+                let relative_path = if is_above_root(directory) {
+                    directory
+                        .components()
+                        .skip_while(|component| component == &Component::ParentDir)
+                        .collect::<PathBuf>()
+                        .join(location.path)
+                } else {
+                    location.path.strip_prefix(directory).ok()?.to_path_buf()
+                };
+                Some((is_above_root(directory), relative_path, editorconfig))
+            })
+            .collect::<Vec<_>>();
+        // Files above the root sort after the root itself, but apply before it.
+        editorconfigs.sort_by_key(|(is_above_root, _, _)| !is_above_root);
+
+        let first_ix = editorconfigs
+            .iter()
+            .rposition(|(_, _, editorconfig)| editorconfig.is_root)
+            .unwrap_or(0);
+        let mut properties = EditorConfigProperties::default();
+        for (_, relative_path, editorconfig) in &editorconfigs[first_ix..] {
+            properties.merge(&editorconfig.properties_for(relative_path));
+        }
+        (!properties.is_empty()).then_some(properties)
+    }
+
     /// Add or remove a set of local settings via a JSON string.
     pub fn clear_local_settings(&mut self, root_id: usize, cx: &mut AppContext) -> Result<()> {
         self.raw_local_settings.retain(|k, _| k.0 != root_id);
+        self.raw_editorconfigs.retain(|k, _| k.0 != root_id);
+        let local_file_paths = self
+            .local_file_paths
+            .iter()
+            .filter(|(id, _)| *id == root_id)
+            .map(|(_, path)| path.clone())
+            .collect::<Vec<_>>();
+        for path in local_file_paths {
+            self.unregister_local_file(root_id, &path);
+        }
         self.recompute_values(Some((root_id, "".as_ref())), cx)?;
         Ok(())
     }
@@ -492,6 +757,17 @@ impl SettingsStore {
             .map(|((_, path), content)| (path.clone(), serde_json::to_string(content).unwrap()))
     }
 
+    /// Returns the directories and contents of the `.editorconfig` files that
+    /// apply to the given root.
+    pub fn local_editorconfigs(
+        &self,
+        root_id: usize,
+    ) -> impl '_ + Iterator<Item = (Arc<Path>, String)> {
+        self.raw_editorconfigs
+            .range((root_id, Path::new("").into())..(root_id + 1, Path::new("").into()))
+            .map(|((_, directory), (content, _))| (directory.clone(), content.clone()))
+    }
+
     pub fn json_schema(
         &self,
         schema_params: &SettingsJsonSchemaParams,
@@ -604,6 +880,48 @@ impl SettingsStore {
         changed_local_path: Option<(usize, &Path)>,
         cx: &mut AppContext,
     ) -> Result<()> {
+        // When a local path changed, only the layers applying to it, to its ancestors
+        // or to its descendants are needed.
+        let is_affected = |root_id: usize, path: &Path| {
+            changed_local_path.map_or(true, |(changed_root_id, changed_local_path)| {
+                root_id == changed_root_id
+                    && (path.starts_with(changed_local_path)
+                        || changed_local_path.starts_with(path))
+            })
+        };
+        let file_editorconfig_settings = self
+            .local_file_paths
+            .iter()
+            .filter(|(root_id, path)| is_affected(*root_id, path))
+            .map(|(root_id, path)| {
+                let properties = self.editorconfig_properties(SettingsLocation {
+                    worktree_id: *root_id,
+                    path,
+                });
+                (
+                    *root_id,
+                    path,
+                    properties.map(|properties| properties.to_settings()),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut local_layers = self
+            .raw_local_settings
+            .iter()
+            .filter(|((root_id, path), _)| is_affected(*root_id, path))
+            .map(|((root_id, path), settings)| (*root_id, path, LocalLayer::Settings(settings)))
+            .chain(
+                file_editorconfig_settings
+                    .iter()
+                    .map(|(root_id, path, settings)| {
+                        (*root_id, *path, LocalLayer::EditorConfig(settings.as_ref()))
+                    }),
+            )
+            .collect::<Vec<_>>();
+        local_layers.sort_by(|(root_a, path_a, _), (root_b, path_b, _)| {
+            (root_a, path_a).cmp(&(root_b, path_b))
+        });
+
         // Reload the global and local values for every setting.
         let mut project_settings_stack = Vec::<DeserializedSetting>::new();
         let mut paths_stack = Vec::<Option<(usize, &Path)>>::new();
@@ -653,11 +971,11 @@ impl SettingsStore {
             // Reload the local values for the setting.
             paths_stack.clear();
             project_settings_stack.clear();
-            for ((root_id, path), local_settings) in &self.raw_local_settings {
+            for &(root_id, path, layer) in &local_layers {
                 // Build a stack of all of the local values for that setting.
                 while let Some(prev_entry) = paths_stack.last() {
                     if let Some((prev_root_id, prev_path)) = prev_entry {
-                        if root_id != prev_root_id || !path.starts_with(prev_path) {
+                        if root_id != *prev_root_id || !path.starts_with(prev_path) {
                             paths_stack.pop();
                             project_settings_stack.pop();
                             continue;
@@ -666,35 +984,66 @@ impl SettingsStore {
                     break;
                 }
 
-                if let Some(local_settings) =
-                    setting_value.deserialize_setting(local_settings).log_err()
-                {
-                    paths_stack.push(Some((*root_id, path.as_ref())));
-                    project_settings_stack.push(local_settings);
-
-                    // If a local settings file changed, then avoid recomputing local
-                    // settings for any path outside of that directory.
-                    if changed_local_path.map_or(false, |(changed_root_id, changed_local_path)| {
-                        *root_id != changed_root_id || !path.starts_with(changed_local_path)
-                    }) {
+                // If a local settings file changed, then avoid recomputing local
+                // settings for any path outside of that directory.
+                let is_unchanged =
+                    changed_local_path.map_or(false, |(changed_root_id, changed_local_path)| {
+                        root_id != changed_root_id || !path.starts_with(changed_local_path)
+                    });
+
+                let editorconfig_settings = match layer {
+                    LocalLayer::Settings(local_settings) => {
+                        let Some(local_settings) =
+                            setting_value.deserialize_setting(local_settings).log_err()
+                        else {
+                            continue;
+                        };
+                        paths_stack.push(Some((root_id, path.as_ref())));
+                        project_settings_stack.push(local_settings);
+                        None
+                    }
+                    // `.editorconfig` properties only map onto settings at the root of the file.
+                    LocalLayer::EditorConfig(_) if setting_value.key().is_some() => continue,
+                    LocalLayer::EditorConfig(_) if is_unchanged => continue,
+                    LocalLayer::EditorConfig(None) => {
+                        setting_value.remove_local_value(root_id, path);
                         continue;
                     }
-
-                    if let Some(value) = setting_value
-                        .load_setting(
-                            SettingsSources {
-                                default: &default_settings,
-                                extensions: extension_settings.as_ref(),
-                                user: user_settings.as_ref(),
-                                release_channel: release_channel_settings.as_ref(),
-                                project: &project_settings_stack.iter().collect::<Vec<_>>(),
-                            },
-                            cx,
-                        )
-                        .log_err()
-                    {
-                        setting_value.set_local_value(*root_id, path.clone(), value);
+                    LocalLayer::EditorConfig(Some(editorconfig_settings)) => {
+                        let Some(editorconfig_settings) = setting_value
+                            .deserialize_setting(editorconfig_settings)
+                            .log_err()
+                        else {
+                            continue;
+                        };
+                        Some(editorconfig_settings)
                     }
+                };
+
+                if is_unchanged {
+                    continue;
+                }
+
+                // The properties of a file's `.editorconfig` files take precedence over the
+                // global settings, but not over the settings files in its ancestor directories.
+                let project_settings = editorconfig_settings
+                    .iter()
+                    .chain(project_settings_stack.iter())
+                    .collect::<Vec<_>>();
+                if let Some(value) = setting_value
+                    .load_setting(
+                        SettingsSources {
+                            default: &default_settings,
+                            extensions: extension_settings.as_ref(),
+                            user: user_settings.as_ref(),
+                            release_channel: release_channel_settings.as_ref(),
+                            project: &project_settings,
+                        },
+                        cx,
+                    )
+                    .log_err()
+                {
+                    setting_value.set_local_value(root_id, path.clone(), value);
                 }
             }
         }
@@ -702,6 +1051,21 @@ impl SettingsStore {
     }
 }
 
+/// A source of local settings, applying to a directory or a single file.
+#[derive(Clone, Copy)]
+enum LocalLayer<'a> {
+    /// The content of a local settings file.
+    Settings(&'a serde_json::Value),
+    /// The settings derived from the `.editorconfig` properties of a registered
+    /// file, or `None` when no property applies to it.
+    EditorConfig(Option<&'a serde_json::Value>),
+}
+
+/// Returns whether the directory of an `.editorconfig` file is above the worktree root.
+fn is_above_root(directory: &Path) -> bool {
+    directory.components().next() == Some(Component::ParentDir)
+}
+
 impl Debug for SettingsStore {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SettingsStore")
@@ -716,6 +1080,7 @@ impl Debug for SettingsStore {
             .field("default_settings", &self.raw_default_settings)
             .field("user_settings", &self.raw_user_settings)
             .field("local_settings", &self.raw_local_settings)
+            .field("editorconfigs", &self.raw_editorconfigs)
             .finish_non_exhaustive()
     }
 }
@@ -770,6 +1135,14 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
         Ok(DeserializedSetting(Box::new(value)))
     }
 
+    fn default_setting(&self) -> DeserializedSetting {
+        DeserializedSetting(Box::<T::FileContent>::default())
+    }
+
+    fn serialize_setting(&self, setting: &DeserializedSetting) -> serde_json::Value {
+        serde_json::to_value(setting.0.downcast_ref::<T::FileContent>().unwrap()).unwrap()
+    }
+
     fn value_for_path(&self, path: Option<SettingsLocation>) -> &dyn Any {
         if let Some(SettingsLocation { worktree_id, path }) = path {
             for (settings_root_id, settings_path, value) in self.local_values.iter().rev() {
@@ -798,6 +1171,15 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
         }
     }
 
+    fn remove_local_value(&mut self, root_id: usize, path: &Path) {
+        if let Ok(ix) = self
+            .local_values
+            .binary_search_by_key(&(root_id, path), |e| (e.0, e.1.as_ref()))
+        {
+            self.local_values.remove(ix);
+        }
+    }
+
     fn json_schema(
         &self,
         generator: &mut SchemaGenerator,
@@ -808,6 +1190,26 @@ impl<T: Settings> AnySettingValue for SettingValue<T> {
     }
 }
 
+/// Sets the value at a key path in a JSON value, creating the objects along the path.
+fn set_value_at_key_path(
+    json: &mut serde_json::Value,
+    key_path: &[&str],
+    value: serde_json::Value,
+) {
+    let mut target = json;
+    for key in key_path {
+        if !target.is_object() {
+            *target = serde_json::Value::Object(Default::default());
+        }
+        target = target
+            .as_object_mut()
+            .unwrap()
+            .entry(key.to_string())
+            .or_insert(serde_json::Value::Null);
+    }
+    *target = value;
+}
+
 fn update_value_in_json_text<'a>(
     text: &mut String,
     key_path: &mut Vec<&'a str>,
@@ -1014,7 +1416,10 @@ pub fn parse_json_with_comments<T: DeserializeOwned>(content: &str) -> Result<T>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{EditorConfigValue, IndentSize, IndentStyle};
     use serde_derive::Deserialize;
+    use serde_json::json;
+    use std::num::NonZeroU32;
     use unindent::Unindent;
 
     #[gpui::test]
@@ -1146,6 +1551,269 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_settings_store_editorconfig(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("").into(),
+                Some("root = true\n[*]\nindent_size = 2\ninsert_final_newline = true\n"),
+                cx,
+            )
+            .unwrap();
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("a").into(),
+                Some("[*.rs]\nindent_size = 4\n[*.md]\ninsert_final_newline = unset\n"),
+                cx,
+            )
+            .unwrap();
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("a/b").into(),
+                Some("root = true\n[*.rs]\nindent_style = tab\n"),
+                cx,
+            )
+            .unwrap();
+
+        let properties = |store: &SettingsStore, path: &str| {
+            store.editorconfig_properties(SettingsLocation {
+                worktree_id: 1,
+                path: Path::new(path),
+            })
+        };
+        let indent_size = |columns| {
+            EditorConfigValue::Value(IndentSize::Columns(NonZeroU32::new(columns).unwrap()))
+        };
+
+        let root = properties(&store, "c.rs").unwrap();
+        assert_eq!(root.indent_size, indent_size(2));
+        assert_eq!(root.insert_final_newline, EditorConfigValue::Value(true));
+
+        let nested = properties(&store, "a/c.rs").unwrap();
+        assert_eq!(nested.indent_size, indent_size(4));
+        assert_eq!(nested.insert_final_newline, EditorConfigValue::Value(true));
+
+        // Unsetting a property discards the value from the ancestors.
+        let nested_unset = properties(&store, "a/c.md").unwrap();
+        assert_eq!(nested_unset.indent_size, indent_size(2));
+        assert_eq!(nested_unset.insert_final_newline, EditorConfigValue::Unset);
+        assert_eq!(nested_unset.to_settings(), json!({ "tab_size": 2 }));
+
+        // A nested root file hides the properties from its ancestors.
+        let nested_root = properties(&store, "a/b/c.rs").unwrap();
+        assert_eq!(
+            nested_root.indent_style,
+            EditorConfigValue::Value(IndentStyle::Tab)
+        );
+        assert_eq!(nested_root.indent_size, EditorConfigValue::Missing);
+        assert_eq!(properties(&store, "a/b/c.md"), None);
+
+        assert_eq!(
+            store.editorconfig_properties(SettingsLocation {
+                worktree_id: 2,
+                path: Path::new("c.rs"),
+            }),
+            None
+        );
+
+        store
+            .set_local_editorconfig(1, Path::new("a/b").into(), None, cx)
+            .unwrap();
+        assert_eq!(
+            properties(&store, "a/b/c.rs").unwrap().indent_size,
+            indent_size(4)
+        );
+    }
+
+    #[gpui::test]
+    fn test_settings_store_editorconfig_above_root(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("../../code/zed").into(),
+                Some("root = true\n[*]\nindent_size = 8\n[code/*/src/*.rs]\nindent_size = 3\n"),
+                cx,
+            )
+            .unwrap();
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("../zed").into(),
+                Some("[*.md]\nindent_size = 2\n"),
+                cx,
+            )
+            .unwrap();
+
+        let properties = |store: &SettingsStore, path: &str| {
+            store.editorconfig_properties(SettingsLocation {
+                worktree_id: 1,
+                path: Path::new(path),
+            })
+        };
+        let indent_size = |columns| {
+            EditorConfigValue::Value(IndentSize::Columns(NonZeroU32::new(columns).unwrap()))
+        };
+
+        assert_eq!(
+            properties(&store, "Cargo.toml").unwrap().indent_size,
+            indent_size(8)
+        );
+        assert_eq!(
+            properties(&store, "src/main.rs").unwrap().indent_size,
+            indent_size(3)
+        );
+        assert_eq!(
+            properties(&store, "README.md").unwrap().indent_size,
+            indent_size(2)
+        );
+
+        // A root file in the worktree hides the ones above it.
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("").into(),
+                Some("root = true\n[*.md]\nindent_size = 4\n"),
+                cx,
+            )
+            .unwrap();
+        assert_eq!(properties(&store, "Cargo.toml"), None);
+        assert_eq!(
+            properties(&store, "README.md").unwrap().indent_size,
+            indent_size(4)
+        );
+    }
+
+    #[gpui::test]
+    fn test_settings_store_editorconfig_precedence(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store.register_setting::<IndentSettings>(cx);
+        store.register_setting::<UserSettings>(cx);
+        store
+            .set_default_settings(
+                r#"{
+                    "tab_size": 4,
+                    "hard_tabs": false,
+                    "user": { "name": "John Doe", "age": 30, "staff": false }
+                }"#,
+                cx,
+            )
+            .unwrap();
+        store.set_user_settings(r#"{ "tab_size": 3 }"#, cx).unwrap();
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("").into(),
+                Some("[*.rs]\nindent_style = tab\nindent_size = 8\n[*.md]\nindent_size = 2\n"),
+                cx,
+            )
+            .unwrap();
+        store
+            .set_local_settings(
+                1,
+                Path::new("docs").into(),
+                Some(r#"{ "tab_size": 6 }"#),
+                cx,
+            )
+            .unwrap();
+        for path in ["main.rs", "README.md", "docs/guide.md"] {
+            store
+                .register_local_file(1, Path::new(path).into(), cx)
+                .unwrap();
+        }
+
+        let indent_settings = |store: &SettingsStore, path: &str| {
+            store
+                .get::<IndentSettings>(Some(SettingsLocation {
+                    worktree_id: 1,
+                    path: Path::new(path),
+                }))
+                .clone()
+        };
+
+        // `.editorconfig` properties take precedence over the user settings...
+        assert_eq!(
+            indent_settings(&store, "main.rs"),
+            IndentSettings {
+                tab_size: 8,
+                hard_tabs: true
+            }
+        );
+        assert_eq!(
+            indent_settings(&store, "README.md"),
+            IndentSettings {
+                tab_size: 2,
+                hard_tabs: false
+            }
+        );
+        assert_eq!(
+            indent_settings(&store, "notes.txt"),
+            IndentSettings {
+                tab_size: 3,
+                hard_tabs: false
+            }
+        );
+        // ...but not over the local settings files.
+        assert_eq!(
+            indent_settings(&store, "docs/guide.md"),
+            IndentSettings {
+                tab_size: 6,
+                hard_tabs: false
+            }
+        );
+        // Keyed settings are unaffected.
+        assert_eq!(
+            store
+                .get::<UserSettings>(Some(SettingsLocation {
+                    worktree_id: 1,
+                    path: Path::new("main.rs"),
+                }))
+                .name,
+            "John Doe"
+        );
+
+        // Changes to the `.editorconfig` files are applied to the registered files.
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("").into(),
+                Some("[*.md]\nindent_size = 5\n"),
+                cx,
+            )
+            .unwrap();
+        assert_eq!(
+            indent_settings(&store, "main.rs"),
+            IndentSettings {
+                tab_size: 3,
+                hard_tabs: false
+            }
+        );
+        assert_eq!(indent_settings(&store, "README.md").tab_size, 5);
+
+        store.unregister_local_file(1, Path::new("README.md"));
+        assert_eq!(indent_settings(&store, "README.md").tab_size, 3);
+
+        // Files registered while no `.editorconfig` property applies to them
+        // pick up the properties of files added later.
+        store
+            .register_local_file(1, Path::new("notes.txt").into(), cx)
+            .unwrap();
+        assert_eq!(indent_settings(&store, "notes.txt").tab_size, 3);
+        store
+            .set_local_editorconfig(
+                1,
+                Path::new("").into(),
+                Some("[*.txt]\nindent_size = 7\n"),
+                cx,
+            )
+            .unwrap();
+        assert_eq!(indent_settings(&store, "notes.txt").tab_size, 7);
+    }
+
     #[gpui::test]
     fn test_setting_store_assign_json_before_register(cx: &mut AppContext) {
         let mut store = SettingsStore::default();
@@ -1309,6 +1977,144 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_setting_store_key_path_update(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store.register_setting::<UserSettings>(cx);
+        store.register_setting::<IndentSettings>(cx);
+        let old_json = r#"{
+            // The user's name
+            "user": {
+                "name": "Max",
+                "age": 36
+            },
+            "tab_size": 4
+        }"#
+        .unindent();
+        let new_json = store
+            .new_text_for_key_path_update(old_json, &["user", "age"], json!(37))
+            .unwrap();
+        pretty_assertions::assert_eq!(
+            new_json,
+            r#"{
+                // The user's name
+                "user": {
+                    "name": "Max",
+                    "age": 37
+                },
+                "tab_size": 4
+            }"#
+            .unindent()
+        );
+
+        let new_json = store
+            .new_text_for_key_path_update(new_json, &["user", "staff"], json!(true))
+            .unwrap();
+        let new_json = store
+            .new_text_for_key_path_update(new_json, &["tab_size"], json!(2))
+            .unwrap();
+        pretty_assertions::assert_eq!(
+            new_json,
+            r#"{
+                // The user's name
+                "user": {
+                    "staff": true,
+                    "name": "Max",
+                    "age": 37
+                },
+                "tab_size": 2
+            }"#
+            .unindent()
+        );
+
+        // Values that are invalid for the setting are rejected.
+        assert!(store
+            .edits_for_key_path_update(&new_json, &["user", "age"], json!("old"))
+            .is_err());
+        assert!(store
+            .edits_for_key_path_update(&new_json, &["unknown"], json!(true))
+            .is_err());
+
+        store.set_user_settings(&new_json, cx).unwrap();
+        assert_eq!(
+            store.raw_values_for_key_path(&["user", "staff"], None),
+            vec![(SettingsLayer::User, &json!(true))]
+        );
+    }
+
+    #[gpui::test]
+    fn test_setting_store_key_path_update_owner(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store.register_setting::<TurboModeSettings>(cx);
+        store.register_setting::<TurboSetting>(cx);
+        store.register_setting::<IndentSettings>(cx);
+
+        // A setting's key is owned by that setting, even when a keyless
+        // setting has a field of the same name.
+        let new_json = store
+            .new_text_for_key_path_update("{}".into(), &["turbo"], json!(true))
+            .unwrap();
+        assert_eq!(
+            parse_json_with_comments::<serde_json::Value>(&new_json).unwrap(),
+            json!({ "turbo": true })
+        );
+
+        // The first keyless setting to register a field owns it.
+        assert!(store
+            .edits_for_key_path_update("{}", &["tab_size"], json!("wide"))
+            .is_ok());
+        assert!(store
+            .edits_for_key_path_update("{}", &["tab_size"], json!(2))
+            .is_err());
+    }
+
+    #[gpui::test]
+    fn test_setting_store_raw_values_for_key_path(cx: &mut AppContext) {
+        let mut store = SettingsStore::default();
+        store
+            .set_default_settings(r#"{ "user": { "name": "John Doe", "age": 30 } }"#, cx)
+            .unwrap();
+        store
+            .set_user_settings(r#"{ "user": { "age": 31 } }"#, cx)
+            .unwrap();
+        store
+            .set_local_settings(
+                1,
+                Path::new("/root1").into(),
+                Some(r#"{ "user": { "age": 42 } }"#),
+                cx,
+            )
+            .unwrap();
+
+        let location = SettingsLocation {
+            worktree_id: 1,
+            path: Path::new("/root1/something"),
+        };
+        assert_eq!(
+            store.raw_values_for_key_path(&["user", "age"], Some(location)),
+            vec![
+                (SettingsLayer::Default, &json!(30)),
+                (SettingsLayer::User, &json!(31)),
+                (SettingsLayer::Project, &json!(42)),
+            ]
+        );
+        assert_eq!(
+            store.raw_values_for_key_path(&["user", "age"], None),
+            vec![
+                (SettingsLayer::Default, &json!(30)),
+                (SettingsLayer::User, &json!(31)),
+            ]
+        );
+        assert_eq!(
+            store.raw_values_for_key_path(&["user", "name"], Some(location)),
+            vec![(SettingsLayer::Default, &json!("John Doe"))]
+        );
+        assert_eq!(
+            store.raw_values_for_key_path(&["user", "staff"], Some(location)),
+            vec![]
+        );
+    }
+
     fn check_settings_update<T: Settings>(
         store: &mut SettingsStore,
         old_json: String,
@@ -1348,6 +2154,27 @@ mod tests {
         }
     }
 
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    struct IndentSettings {
+        tab_size: u32,
+        hard_tabs: bool,
+    }
+
+    #[derive(Default, Clone, Serialize, Deserialize, JsonSchema)]
+    struct IndentSettingsJson {
+        tab_size: Option<u32>,
+        hard_tabs: Option<bool>,
+    }
+
+    impl Settings for IndentSettings {
+        const KEY: Option<&'static str> = None;
+        type FileContent = IndentSettingsJson;
+
+        fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+            sources.json_merge()
+        }
+    }
+
     #[derive(Debug, Deserialize, PartialEq)]
     struct TurboSetting(bool);
 
@@ -1360,6 +2187,25 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Deserialize)]
+    struct TurboModeSettings {}
+
+    #[derive(Clone, Default, Serialize, Deserialize, JsonSchema)]
+    struct TurboModeSettingsJson {
+        turbo: Option<String>,
+        tab_size: Option<String>,
+    }
+
+    impl Settings for TurboModeSettings {
+        const KEY: Option<&'static str> = None;
+
+        type FileContent = TurboModeSettingsJson;
+
+        fn load(sources: SettingsSources<Self::FileContent>, _: &mut AppContext) -> Result<Self> {
+            sources.json_merge()
+        }
+    }
+
     #[derive(Clone, Debug, PartialEq, Deserialize)]
     struct MultiKeySettings {
         #[serde(default)]
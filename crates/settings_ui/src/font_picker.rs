@@ -0,0 +1,171 @@
+use std::{path::PathBuf, sync::Arc};
+
+use fs::Fs;
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, Task, View,
+    ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::ModalView;
+
+use crate::write_setting;
+
+/// A modal that picks one of the installed fonts as the value of a font family setting.
+pub(crate) struct FontPicker {
+    picker: View<Picker<FontPickerDelegate>>,
+}
+
+impl FontPicker {
+    pub fn new(
+        fs: Arc<dyn Fs>,
+        project_settings_path: Option<PathBuf>,
+        key_path: Vec<String>,
+        current_font: Option<String>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let font_names = cx.text_system().all_font_names();
+        let selected_index = current_font
+            .and_then(|current_font| font_names.iter().position(|name| *name == current_font))
+            .unwrap_or(0);
+        let delegate = FontPickerDelegate {
+            font_picker: cx.view().downgrade(),
+            fs,
+            project_settings_path,
+            key_path,
+            candidates: font_names
+                .into_iter()
+                .enumerate()
+                .map(|(id, name)| StringMatchCandidate::new(id, name))
+                .collect(),
+            matches: Vec::new(),
+            selected_index,
+        };
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+        Self { picker }
+    }
+}
+
+impl Render for FontPicker {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+impl FocusableView for FontPicker {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for FontPicker {}
+impl ModalView for FontPicker {}
+
+pub(crate) struct FontPickerDelegate {
+    font_picker: WeakView<FontPicker>,
+    fs: Arc<dyn Fs>,
+    /// The project settings file to write the font to, instead of the user's settings file.
+    project_settings_path: Option<PathBuf>,
+    key_path: Vec<String>,
+    candidates: Vec<StringMatchCandidate>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for FontPickerDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Select a font...".into()
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        let candidates = self.candidates.clone();
+        let background = cx.background_executor().clone();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        if let Some(mat) = self.matches.get(self.selected_index) {
+            write_setting(
+                self.fs.clone(),
+                self.project_settings_path.clone(),
+                self.key_path.clone(),
+                mat.string.clone().into(),
+                cx,
+            );
+        }
+        self.dismissed(cx);
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.font_picker
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(HighlightedLabel::new(
+                    mat.string.clone(),
+                    mat.positions.clone(),
+                )),
+        )
+    }
+}
@@ -0,0 +1,359 @@
+use serde_json::{Map, Value};
+
+/// The group of the settings that are not nested in an object.
+pub(crate) const GENERAL_GROUP: &str = "general";
+
+/// The keys that hold a copy of the whole schema for a release stage.
+const RELEASE_STAGE_KEYS: &[&str] = &["dev", "nightly", "stable", "preview"];
+
+/// The definitions that describe maps keyed by arbitrary names, which are
+/// edited in `settings.json` rather than expanded into one form per name.
+const MAP_DEFINITIONS: &[&str] = &["Languages"];
+
+const MAX_NESTING_DEPTH: usize = 4;
+const MAX_REFERENCE_DEPTH: usize = 8;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum SettingKind {
+    Boolean,
+    Integer {
+        minimum: Option<i64>,
+    },
+    Number,
+    String,
+    Color,
+    FontFamily,
+    Enum(Vec<Value>),
+    /// A setting that can only be edited in `settings.json`, such as a list or a map.
+    Unsupported,
+}
+
+/// A single editable value of the settings schema.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SettingEntry {
+    /// The top-level key that this setting is nested in, or [`GENERAL_GROUP`].
+    pub group: String,
+    /// The path of keys leading to the setting in `settings.json`.
+    pub key_path: Vec<String>,
+    pub description: Option<String>,
+    pub kind: SettingKind,
+}
+
+impl SettingEntry {
+    /// Returns the keys of this setting within its group.
+    pub fn keys_in_group(&self) -> &[String] {
+        if self.group == GENERAL_GROUP {
+            &self.key_path
+        } else {
+            &self.key_path[1..]
+        }
+    }
+
+    /// Returns whether every word of the query appears in the key path or in the description.
+    pub fn matches(&self, query_words: &[String]) -> bool {
+        let key_path = self.key_path.join(".");
+        let haystack = format!(
+            "{key_path} {} {}",
+            key_path.replace('_', " "),
+            self.description.as_deref().unwrap_or("")
+        )
+        .to_lowercase();
+        query_words
+            .iter()
+            .all(|word| haystack.contains(word.as_str()))
+    }
+}
+
+/// Returns a human-readable title for a settings key, such as "Tab Size" for `tab_size`.
+pub(crate) fn humanize_key(key: &str) -> String {
+    key.split(['_', '.'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Flattens a settings JSON schema into the list of settings it describes,
+/// with the general settings first and the other groups ordered by key.
+pub(crate) fn setting_entries(schema: &Value) -> Vec<SettingEntry> {
+    let definitions = schema.get("definitions").and_then(Value::as_object);
+    let mut entries = Vec::new();
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (key, property) in properties {
+            if RELEASE_STAGE_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            let mut key_path = vec![key.clone()];
+            collect_entries(property, definitions, false, &mut key_path, &mut entries);
+        }
+    }
+    entries.sort_by(|a, b| {
+        (a.group != GENERAL_GROUP)
+            .cmp(&(b.group != GENERAL_GROUP))
+            .then_with(|| a.group.cmp(&b.group))
+    });
+    entries
+}
+
+fn collect_entries(
+    schema: &Value,
+    definitions: Option<&Map<String, Value>>,
+    mut is_color: bool,
+    key_path: &mut Vec<String>,
+    entries: &mut Vec<SettingEntry>,
+) {
+    let description = schema.get("description").and_then(Value::as_str);
+    let (schema, definition_name) = resolve(schema, definitions);
+    let description = description
+        .or_else(|| schema.get("description").and_then(Value::as_str))
+        .map(|description| description.trim().to_string());
+    if let Some(name) = definition_name {
+        is_color |= name.contains("Colors") || name == "ThemeStyleContent";
+    }
+
+    let kind = if definition_name == Some("FontFamilies") {
+        SettingKind::FontFamily
+    } else if definition_name.is_some_and(|name| MAP_DEFINITIONS.contains(&name)) {
+        SettingKind::Unsupported
+    } else if let Some(values) = enum_values(schema, definitions) {
+        SettingKind::Enum(values)
+    } else {
+        match instance_type(schema) {
+            Some("boolean") => SettingKind::Boolean,
+            Some("integer") => SettingKind::Integer {
+                minimum: schema
+                    .get("minimum")
+                    .and_then(Value::as_f64)
+                    .map(|minimum| minimum as i64),
+            },
+            Some("number") => SettingKind::Number,
+            Some("string") if is_color => SettingKind::Color,
+            Some("string") => SettingKind::String,
+            Some("object") => match schema.get("properties").and_then(Value::as_object) {
+                Some(properties)
+                    if !properties.is_empty() && key_path.len() < MAX_NESTING_DEPTH =>
+                {
+                    for (key, property) in properties {
+                        key_path.push(key.clone());
+                        collect_entries(property, definitions, is_color, key_path, entries);
+                        key_path.pop();
+                    }
+                    return;
+                }
+                _ => SettingKind::Unsupported,
+            },
+            _ => SettingKind::Unsupported,
+        }
+    };
+
+    let group = if key_path.len() > 1 {
+        key_path[0].clone()
+    } else {
+        GENERAL_GROUP.to_string()
+    };
+    entries.push(SettingEntry {
+        group,
+        key_path: key_path.clone(),
+        description,
+        kind,
+    });
+}
+
+/// Follows the references of a schema, returning the referenced schema
+/// along with the name of the last definition that was referenced.
+fn resolve<'a>(
+    mut schema: &'a Value,
+    definitions: Option<&'a Map<String, Value>>,
+) -> (&'a Value, Option<&'a str>) {
+    let mut definition_name = None;
+    for _ in 0..MAX_REFERENCE_DEPTH {
+        if let Some(name) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/definitions/"))
+        {
+            let Some(definition) = definitions.and_then(|definitions| definitions.get(name)) else {
+                break;
+            };
+            definition_name = Some(name);
+            schema = definition;
+        } else if let Some([subschema]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            schema = subschema;
+        } else {
+            break;
+        }
+    }
+    (schema, definition_name)
+}
+
+/// Returns the values of a schema that only accepts a fixed set of values.
+fn enum_values(schema: &Value, definitions: Option<&Map<String, Value>>) -> Option<Vec<Value>> {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        let values = values
+            .iter()
+            .filter(|value| !value.is_null())
+            .cloned()
+            .collect::<Vec<_>>();
+        return (!values.is_empty()).then_some(values);
+    }
+    if let Some(value) = schema.get("const") {
+        return Some(vec![value.clone()]);
+    }
+
+    let variants = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))?
+        .as_array()?;
+    let mut values = Vec::new();
+    for variant in variants {
+        let (variant, _) = resolve(variant, definitions);
+        if instance_type(variant) == Some("null") {
+            continue;
+        }
+        values.extend(enum_values(variant, definitions)?);
+    }
+    (!values.is_empty()).then_some(values)
+}
+
+fn instance_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(instance_type) => Some(instance_type),
+        Value::Array(instance_types) => instance_types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|instance_type| *instance_type != "null"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_setting_entries() {
+        let schema = json!({
+            "properties": {
+                "tab_size": {
+                    "description": "How many columns a tab should occupy.",
+                    "type": "integer",
+                    "format": "uint32",
+                    "minimum": 1.0
+                },
+                "buffer_font_family": { "$ref": "#/definitions/FontFamilies" },
+                "terminal": {
+                    "allOf": [{ "$ref": "#/definitions/TerminalSettingsContent" }]
+                },
+                "languages": { "$ref": "#/definitions/Languages" },
+                "experimental.theme_overrides": {
+                    "allOf": [{ "$ref": "#/definitions/ThemeStyleContent" }]
+                },
+                "stable": { "properties": { "tab_size": { "type": "integer" } } }
+            },
+            "definitions": {
+                "FontFamilies": { "type": "string", "enum": ["Zed Mono", "Zed Sans"] },
+                "Languages": {
+                    "type": "object",
+                    "properties": { "Rust": { "type": "object" } }
+                },
+                "TerminalSettingsContent": {
+                    "type": "object",
+                    "properties": {
+                        "blinking": {
+                            "description": "Whether the cursor blinks.",
+                            "oneOf": [
+                                { "type": "string", "enum": ["off"], "description": "Never blink." },
+                                { "type": "string", "enum": ["on"], "description": "Always blink." }
+                            ]
+                        },
+                        "line_height": { "type": "number", "format": "float" },
+                        "env": {
+                            "type": "object",
+                            "additionalProperties": { "type": "string" }
+                        },
+                        "toolbar": {
+                            "type": "object",
+                            "properties": { "title": { "type": ["boolean", "null"] } }
+                        }
+                    }
+                },
+                "ThemeStyleContent": {
+                    "type": "object",
+                    "properties": { "editor.background": { "type": ["string", "null"] } }
+                }
+            }
+        });
+
+        let entries = setting_entries(&schema);
+        let summary = entries
+            .iter()
+            .map(|entry| (entry.group.as_str(), entry.key_path.join("/"), &entry.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                (
+                    "general",
+                    "tab_size".into(),
+                    &SettingKind::Integer { minimum: Some(1) }
+                ),
+                (
+                    "general",
+                    "buffer_font_family".into(),
+                    &SettingKind::FontFamily
+                ),
+                ("general", "languages".into(), &SettingKind::Unsupported),
+                (
+                    "experimental.theme_overrides",
+                    "experimental.theme_overrides/editor.background".into(),
+                    &SettingKind::Color
+                ),
+                (
+                    "terminal",
+                    "terminal/blinking".into(),
+                    &SettingKind::Enum(vec![json!("off"), json!("on")])
+                ),
+                (
+                    "terminal",
+                    "terminal/line_height".into(),
+                    &SettingKind::Number
+                ),
+                ("terminal", "terminal/env".into(), &SettingKind::Unsupported),
+                (
+                    "terminal",
+                    "terminal/toolbar/title".into(),
+                    &SettingKind::Boolean
+                ),
+            ]
+        );
+        assert_eq!(
+            entries[4].description.as_deref(),
+            Some("Whether the cursor blinks.")
+        );
+
+        let terminal_toolbar = &entries[7];
+        assert_eq!(terminal_toolbar.keys_in_group(), ["toolbar", "title"]);
+        assert!(terminal_toolbar.matches(&["toolbar".into(), "title".into()]));
+        assert!(!terminal_toolbar.matches(&["toolbar".into(), "blink".into()]));
+        assert!(entries[0].matches(&["tab size".into()]));
+        assert!(entries[0].matches(&["tab_size".into()]));
+        assert!(entries[0].matches(&["columns".into()]));
+    }
+
+    #[test]
+    fn test_humanize_key() {
+        assert_eq!(humanize_key("tab_size"), "Tab Size");
+        assert_eq!(humanize_key("editor.background"), "Editor Background");
+    }
+}
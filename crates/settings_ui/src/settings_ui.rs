@@ -0,0 +1,670 @@
+mod font_picker;
+mod settings_schema;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use collections::HashMap;
+use editor::{Editor, EditorElement, EditorEvent, EditorStyle};
+use feature_flags::FeatureFlagAppExt as _;
+use fs::Fs;
+use gpui::{
+    actions, list, AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, FontStyle,
+    Hsla, ListAlignment, ListState, Render, Rgba, Subscription, TextStyle, View, ViewContext,
+    WeakView, WhiteSpace,
+};
+use serde_json::Value;
+use settings::{
+    Settings, SettingsJsonSchemaParams, SettingsLayer, SettingsLocation, SettingsStore,
+};
+use theme::ThemeSettings;
+use ui::{prelude::*, Checkbox, ContextMenu, NumericStepper, PopoverMenu, Selection, Tooltip};
+use workspace::{
+    item::{Item, ItemEvent, TabContentParams},
+    Workspace, WorkspaceId,
+};
+
+use crate::{
+    font_picker::FontPicker,
+    settings_schema::{humanize_key, setting_entries, SettingEntry, SettingKind},
+};
+
+actions!(zed, [OpenSettingsEditor]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &OpenSettingsEditor, cx| {
+            let existing = workspace
+                .active_pane()
+                .read(cx)
+                .items()
+                .find_map(|item| item.downcast::<SettingsEditor>());
+
+            if let Some(existing) = existing {
+                workspace.activate_item(&existing, cx);
+            } else {
+                let settings_editor = SettingsEditor::new(workspace, cx);
+                workspace.add_item_to_active_pane(Box::new(settings_editor), None, cx)
+            }
+        });
+    })
+    .detach();
+}
+
+enum Row {
+    Group(SharedString),
+    Setting(usize),
+}
+
+/// Edits the user settings through forms generated from the settings JSON schema.
+pub struct SettingsEditor {
+    workspace: WeakView<Workspace>,
+    fs: Arc<dyn Fs>,
+    /// The worktree whose `.zed/settings.json` is shown as the project layer.
+    worktree_id: Option<usize>,
+    /// The absolute path of that `.zed/settings.json`, if the project is local.
+    project_settings_path: Option<PathBuf>,
+    query_editor: View<Editor>,
+    entries: Vec<SettingEntry>,
+    rows: Vec<Row>,
+    list_state: ListState,
+    /// The inputs of the string and number settings, keyed by entry index.
+    text_inputs: HashMap<usize, View<Editor>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl SettingsEditor {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let project = workspace.project().read(cx);
+        let language_names = project.languages().language_names();
+        let worktree = project.visible_worktrees(cx).next();
+        let worktree_id = worktree
+            .as_ref()
+            .map(|worktree| worktree.read(cx).id().to_proto() as usize);
+        let project_settings_path = worktree.filter(|_| project.is_local()).map(|worktree| {
+            worktree
+                .read(cx)
+                .abs_path()
+                .join(paths::local_settings_file_relative_path())
+        });
+        let fs = workspace.app_state().fs.clone();
+        let workspace = workspace.weak_handle();
+
+        cx.new_view(|cx: &mut ViewContext<Self>| {
+            let font_names = cx.text_system().all_font_names();
+            let schema = cx.global::<SettingsStore>().json_schema(
+                &SettingsJsonSchemaParams {
+                    staff_mode: cx.is_staff(),
+                    language_names: &language_names,
+                    font_names: &font_names,
+                },
+                cx,
+            );
+
+            let query_editor = cx.new_view(|cx| {
+                let mut input = Editor::single_line(cx);
+                input.set_placeholder_text("Search settings...", cx);
+                input
+            });
+
+            let view = cx.view().downgrade();
+            let list_state = ListState::new(0, ListAlignment::Top, px(1000.), move |ix, cx| {
+                if let Some(view) = view.upgrade() {
+                    view.update(cx, |this, cx| this.render_row(ix, cx))
+                } else {
+                    div().into_any()
+                }
+            });
+
+            let subscriptions = vec![
+                cx.subscribe(&query_editor, |this, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::Edited { .. } = event {
+                        this.update_matches(cx);
+                    }
+                }),
+                cx.observe_global::<SettingsStore>(|this, cx| {
+                    this.reset_text_inputs(cx);
+                    cx.notify();
+                }),
+            ];
+
+            let mut this = Self {
+                workspace,
+                fs,
+                worktree_id,
+                project_settings_path,
+                query_editor,
+                entries: setting_entries(&schema),
+                rows: Vec::new(),
+                list_state,
+                text_inputs: HashMap::default(),
+                _subscriptions: subscriptions,
+            };
+            this.update_matches(cx);
+            this
+        })
+    }
+
+    fn update_matches(&mut self, cx: &mut ViewContext<Self>) {
+        let query = self.query_editor.read(cx).text(cx).to_lowercase();
+        let query_words = query
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+
+        self.rows.clear();
+        let mut current_group = None;
+        for (ix, entry) in self.entries.iter().enumerate() {
+            if !entry.matches(&query_words) {
+                continue;
+            }
+            if current_group != Some(&entry.group) {
+                current_group = Some(&entry.group);
+                self.rows
+                    .push(Row::Group(humanize_key(&entry.group).into()));
+            }
+            self.rows.push(Row::Setting(ix));
+        }
+        self.list_state.reset(self.rows.len());
+        cx.notify();
+    }
+
+    /// Returns the value of a setting along with the layer it comes from.
+    fn current_value(&self, entry_ix: usize, cx: &AppContext) -> (Option<SettingsLayer>, Value) {
+        let key_path = self.entries[entry_ix]
+            .key_path
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let location = self.worktree_id.map(|worktree_id| SettingsLocation {
+            worktree_id,
+            path: Path::new(""),
+        });
+        cx.global::<SettingsStore>()
+            .raw_values_for_key_path(&key_path, location)
+            .last()
+            .map_or((None, Value::Null), |(layer, value)| {
+                (Some(*layer), (*value).clone())
+            })
+    }
+
+    /// Returns the project settings file that a setting has to be written to, as its value
+    /// is shown from the project layer. Other settings are written to the user's settings file.
+    fn project_settings_path_for(&self, entry_ix: usize, cx: &AppContext) -> Option<PathBuf> {
+        let (layer, _) = self.current_value(entry_ix, cx);
+        self.project_settings_path
+            .clone()
+            .filter(|_| layer == Some(SettingsLayer::Project))
+    }
+
+    fn set_value(&self, entry_ix: usize, value: Value, cx: &mut AppContext) {
+        write_setting(
+            self.fs.clone(),
+            self.project_settings_path_for(entry_ix, cx),
+            self.entries[entry_ix].key_path.clone(),
+            value,
+            cx,
+        );
+    }
+
+    fn text_input(
+        &mut self,
+        entry_ix: usize,
+        value: &Value,
+        cx: &mut ViewContext<Self>,
+    ) -> View<Editor> {
+        if let Some(input) = self.text_inputs.get(&entry_ix) {
+            return input.clone();
+        }
+
+        let text = value_text(value);
+        let input = cx.new_view(|cx| {
+            let mut input = Editor::single_line(cx);
+            input.set_text(text, cx);
+            input
+        });
+        cx.subscribe(&input, move |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::Blurred = event {
+                this.commit_text_input(entry_ix, cx);
+            }
+        })
+        .detach();
+        self.text_inputs.insert(entry_ix, input.clone());
+        input
+    }
+
+    fn commit_text_input(&mut self, entry_ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(input) = self.text_inputs.get(&entry_ix) else {
+            return;
+        };
+        let text = input.read(cx).text(cx);
+        let new_value = match self.entries[entry_ix].kind {
+            SettingKind::Number => text
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            _ => Some(Value::String(text)),
+        };
+
+        let (_, current_value) = self.current_value(entry_ix, cx);
+        match new_value {
+            Some(new_value) if new_value != current_value => {
+                self.set_value(entry_ix, new_value, cx);
+            }
+            Some(_) => {}
+            // Restore the current value when the input can't be parsed.
+            None => input.update(cx, |input, cx| {
+                input.set_text(value_text(&current_value), cx)
+            }),
+        }
+    }
+
+    /// Updates the inputs that aren't being edited with the current settings values.
+    fn reset_text_inputs(&mut self, cx: &mut ViewContext<Self>) {
+        for (entry_ix, input) in &self.text_inputs {
+            if input.focus_handle(cx).is_focused(cx) {
+                continue;
+            }
+            let (_, value) = self.current_value(*entry_ix, cx);
+            let text = value_text(&value);
+            if input.read(cx).text(cx) != text {
+                input.update(cx, |input, cx| input.set_text(text, cx));
+            }
+        }
+    }
+
+    fn pick_font(&mut self, entry_ix: usize, cx: &mut ViewContext<Self>) {
+        let fs = self.fs.clone();
+        let project_settings_path = self.project_settings_path_for(entry_ix, cx);
+        let key_path = self.entries[entry_ix].key_path.clone();
+        let (_, current_font) = self.current_value(entry_ix, cx);
+        let current_font = current_font.as_str().map(str::to_string);
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.toggle_modal(cx, |cx| {
+                    FontPicker::new(fs, project_settings_path, key_path, current_font, cx)
+                });
+            })
+            .ok();
+    }
+
+    fn render_row(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        match self.rows.get(ix) {
+            Some(Row::Group(label)) => h_flex()
+                .pt_6()
+                .pb_1()
+                .child(Headline::new(label.clone()).size(HeadlineSize::Small))
+                .into_any_element(),
+            Some(Row::Setting(entry_ix)) => self.render_setting(*entry_ix, cx),
+            None => div().into_any_element(),
+        }
+    }
+
+    fn render_setting(&mut self, entry_ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let (layer, value) = self.current_value(entry_ix, cx);
+        let entry = &self.entries[entry_ix];
+        let title = entry
+            .keys_in_group()
+            .iter()
+            .map(|key| humanize_key(key))
+            .collect::<Vec<_>>()
+            .join(" › ");
+        let key_path = entry.key_path.join(".");
+        let description = entry.description.clone();
+        let control = self.render_control(entry_ix, &value, cx);
+
+        h_flex()
+            .id(("setting", entry_ix))
+            .w_full()
+            .py_2()
+            .gap_4()
+            .justify_between()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .gap_0p5()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(title))
+                            .child(
+                                Label::new(key_path)
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .children(layer.map(|layer| render_layer_badge(layer, cx))),
+                    )
+                    .children(description.map(|description| {
+                        Label::new(description)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    })),
+            )
+            .child(h_flex().flex_none().child(control))
+            .into_any_element()
+    }
+
+    fn render_control(
+        &mut self,
+        entry_ix: usize,
+        value: &Value,
+        cx: &mut ViewContext<Self>,
+    ) -> AnyElement {
+        match self.entries[entry_ix].kind.clone() {
+            SettingKind::Boolean => Checkbox::new(
+                ("setting-checkbox", entry_ix),
+                if value.as_bool() == Some(true) {
+                    Selection::Selected
+                } else {
+                    Selection::Unselected
+                },
+            )
+            .on_click(cx.listener(move |this, selection: &Selection, cx| {
+                this.set_value(entry_ix, Value::Bool(*selection == Selection::Selected), cx);
+            }))
+            .into_any_element(),
+            SettingKind::Integer { minimum } => {
+                let current = value.as_i64().unwrap_or(0);
+                NumericStepper::new(
+                    current.to_string(),
+                    cx.listener(move |this, _, cx| {
+                        let new_value = minimum.map_or(current - 1, |min| (current - 1).max(min));
+                        this.set_value(entry_ix, new_value.into(), cx);
+                    }),
+                    cx.listener(move |this, _, cx| {
+                        this.set_value(entry_ix, (current + 1).into(), cx);
+                    }),
+                )
+                .into_any_element()
+            }
+            SettingKind::Number | SettingKind::String => self
+                .render_text_input(entry_ix, value, cx)
+                .into_any_element(),
+            SettingKind::Color => {
+                let color = value
+                    .as_str()
+                    .and_then(|color| Rgba::try_from(color).ok())
+                    .map(Hsla::from);
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .size_5()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .when_some(color, |this, color| this.bg(color)),
+                    )
+                    .child(self.render_text_input(entry_ix, value, cx))
+                    .into_any_element()
+            }
+            SettingKind::FontFamily => Button::new(("setting-font", entry_ix), value_text(value))
+                .style(ButtonStyle::Filled)
+                .icon(IconName::ChevronDown)
+                .icon_position(IconPosition::End)
+                .icon_size(IconSize::XSmall)
+                .on_click(cx.listener(move |this, _, cx| this.pick_font(entry_ix, cx)))
+                .into_any_element(),
+            SettingKind::Enum(values) => {
+                let current = value.clone();
+                let this = cx.view().downgrade();
+                PopoverMenu::new(("setting-enum", entry_ix))
+                    .menu(move |cx| {
+                        let this = this.clone();
+                        let values = values.clone();
+                        let current = current.clone();
+                        Some(ContextMenu::build(cx, move |mut menu, _| {
+                            for value in values {
+                                let this = this.clone();
+                                menu = menu.toggleable_entry(
+                                    value_text(&value),
+                                    value == current,
+                                    None,
+                                    move |cx| {
+                                        this.update(cx, |this, cx| {
+                                            this.set_value(entry_ix, value.clone(), cx)
+                                        })
+                                        .ok();
+                                    },
+                                );
+                            }
+                            menu
+                        }))
+                    })
+                    .trigger(
+                        Button::new(("setting-enum-trigger", entry_ix), value_text(value))
+                            .style(ButtonStyle::Filled)
+                            .icon(IconName::ChevronDown)
+                            .icon_position(IconPosition::End)
+                            .icon_size(IconSize::XSmall),
+                    )
+                    .into_any_element()
+            }
+            SettingKind::Unsupported => {
+                Button::new(("setting-edit-json", entry_ix), "Edit in settings.json")
+                    .style(ButtonStyle::Subtle)
+                    .on_click(|_, cx| cx.dispatch_action(Box::new(zed_actions::OpenSettings)))
+                    .into_any_element()
+            }
+        }
+    }
+
+    fn render_text_input(
+        &mut self,
+        entry_ix: usize,
+        value: &Value,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let input = self.text_input(entry_ix, value, cx);
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.ui_font.family.clone(),
+            font_features: settings.ui_font.features.clone(),
+            font_size: rems(0.875).into(),
+            font_weight: settings.ui_font.weight,
+            font_style: FontStyle::Normal,
+            line_height: relative(1.3),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+            white_space: WhiteSpace::Normal,
+        };
+
+        div()
+            .w(rems(16.))
+            .px_2()
+            .py_1()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .rounded_md()
+            .on_action(
+                cx.listener(move |this, _: &menu::Confirm, cx| {
+                    this.commit_text_input(entry_ix, cx)
+                }),
+            )
+            .child(EditorElement::new(
+                &input,
+                EditorStyle {
+                    background: cx.theme().colors().editor_background,
+                    local_player: cx.theme().players().local(),
+                    text: text_style,
+                    ..Default::default()
+                },
+            ))
+    }
+}
+
+fn render_layer_badge(layer: SettingsLayer, cx: &WindowContext) -> impl IntoElement {
+    let (label, tooltip) = match layer {
+        SettingsLayer::Default => ("Default", "Set by the default settings"),
+        SettingsLayer::Extension => ("Extension", "Set by an extension"),
+        SettingsLayer::User => ("User", "Set in your settings.json"),
+        SettingsLayer::Project => (
+            "Project",
+            "Set in the project's .zed/settings.json, which overrides your settings.json \
+            and is where changes to this setting are saved",
+        ),
+    };
+    div()
+        .id(label)
+        .px_1()
+        .rounded_md()
+        .border_1()
+        .border_color(cx.theme().colors().border_variant)
+        .child(Label::new(label).size(LabelSize::XSmall).color(
+            if layer == SettingsLayer::Default {
+                Color::Muted
+            } else {
+                Color::Accent
+            },
+        ))
+        .tooltip(move |cx| Tooltip::text(tooltip, cx))
+}
+
+/// Writes the value at the given key path to the project settings file at
+/// `project_settings_path` if there's one, and to the user's settings file otherwise.
+pub(crate) fn write_setting(
+    fs: Arc<dyn Fs>,
+    project_settings_path: Option<PathBuf>,
+    key_path: Vec<String>,
+    value: Value,
+    cx: &mut AppContext,
+) {
+    match project_settings_path {
+        Some(path) => settings::update_local_settings_file_value(fs, path, cx, key_path, value),
+        None => settings::update_settings_file_value(fs, cx, key_path, value),
+    }
+}
+
+/// Returns the text used to display a settings value in a control.
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+impl Render for SettingsEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.ui_font.family.clone(),
+            font_features: settings.ui_font.features.clone(),
+            font_size: rems(0.875).into(),
+            font_weight: settings.ui_font.weight,
+            font_style: FontStyle::Normal,
+            line_height: relative(1.3),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+            white_space: WhiteSpace::Normal,
+        };
+
+        v_flex()
+            .key_context("SettingsEditor")
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .gap_4()
+                    .p_4()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .justify_between()
+                            .child(Headline::new("Settings").size(HeadlineSize::XLarge))
+                            .child(
+                                Button::new("open-settings-json", "Open settings.json")
+                                    .style(ButtonStyle::Filled)
+                                    .size(ButtonSize::Large)
+                                    .on_click(|_, cx| {
+                                        cx.dispatch_action(Box::new(zed_actions::OpenSettings))
+                                    }),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .px_2()
+                            .py_1()
+                            .gap_2()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .rounded_lg()
+                            .child(Icon::new(IconName::MagnifyingGlass))
+                            .child(EditorElement::new(
+                                &self.query_editor,
+                                EditorStyle {
+                                    background: cx.theme().colors().editor_background,
+                                    local_player: cx.theme().players().local(),
+                                    text: text_style,
+                                    ..Default::default()
+                                },
+                            )),
+                    ),
+            )
+            .child(v_flex().px_4().size_full().map(|this| {
+                if self.rows.is_empty() {
+                    this.py_4()
+                        .child(Label::new("No settings that match your search."))
+                } else {
+                    this.child(list(self.list_state.clone()).size_full())
+                }
+            }))
+    }
+}
+
+impl EventEmitter<ItemEvent> for SettingsEditor {}
+
+impl FocusableView for SettingsEditor {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.query_editor.focus_handle(cx)
+    }
+}
+
+impl Item for SettingsEditor {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> AnyElement {
+        Label::new("Settings")
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("settings editor")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        _: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        None
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
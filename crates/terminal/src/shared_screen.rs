@@ -0,0 +1,158 @@
+//! Snapshots of a terminal's screen, which are diffed to share the terminal with collaborators.
+
+use alacritty_terminal::{
+    grid::Dimensions,
+    index::{Column, Line, Point as AlacPoint},
+    term::{cell::Cell, TermMode},
+    vte::ansi::CursorShape,
+    Term,
+};
+
+/// The rows that are visible on a terminal's screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScreenSnapshot {
+    pub rows: Vec<Vec<Cell>>,
+    pub columns: usize,
+    /// The position of the cursor, relative to the top left of the screen.
+    pub cursor: AlacPoint,
+    pub cursor_shape: CursorShape,
+    pub mode: TermMode,
+    pub title: String,
+}
+
+/// The changes that turn one [`ScreenSnapshot`] into another.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScreenUpdate {
+    pub rows: usize,
+    pub columns: usize,
+    /// The rows that changed, along with their index on the screen.
+    pub changed_rows: Vec<(usize, Vec<Cell>)>,
+    pub cursor: AlacPoint,
+    pub cursor_shape: CursorShape,
+    pub mode: TermMode,
+    pub title: String,
+}
+
+impl ScreenSnapshot {
+    /// Captures the live screen of `term`, regardless of how far back it's scrolled.
+    pub fn new<T>(term: &Term<T>, title: String) -> Self {
+        let grid = term.grid();
+        let columns = term.columns();
+        let rows = (0..term.screen_lines())
+            .map(|row| grid[Line(row as i32)][..Column(columns)].to_vec())
+            .collect();
+        let mode = *term.mode();
+        let cursor_shape = if mode.contains(TermMode::SHOW_CURSOR) {
+            term.cursor_style().shape
+        } else {
+            CursorShape::Hidden
+        };
+
+        Self {
+            rows,
+            columns,
+            cursor: grid.cursor.point,
+            cursor_shape,
+            mode,
+            title,
+        }
+    }
+
+    /// Returns the rows that changed since the `previous` snapshot. Every row is included
+    /// if there's no previous snapshot, or if the screen was resized since.
+    pub fn update_since(&self, previous: Option<&ScreenSnapshot>) -> ScreenUpdate {
+        let previous = previous.filter(|previous| {
+            previous.columns == self.columns && previous.rows.len() == self.rows.len()
+        });
+        let changed_rows = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(index, row)| previous.map_or(true, |previous| previous.rows[*index] != **row))
+            .map(|(index, row)| (index, row.clone()))
+            .collect();
+
+        ScreenUpdate {
+            rows: self.rows.len(),
+            columns: self.columns,
+            changed_rows,
+            cursor: self.cursor,
+            cursor_shape: self.cursor_shape,
+            mode: self.mode,
+            title: self.title.clone(),
+        }
+    }
+}
+
+impl ScreenUpdate {
+    /// Returns whether applying this update would leave the `previous` snapshot unchanged.
+    pub fn is_empty(&self, previous: &ScreenSnapshot) -> bool {
+        self.changed_rows.is_empty()
+            && self.cursor == previous.cursor
+            && self.cursor_shape == previous.cursor_shape
+            && self.mode == previous.mode
+            && self.title == previous.title
+    }
+}
+
+/// Returns the point at `row` and `column` of the screen.
+pub(crate) fn screen_point(row: usize, column: usize) -> AlacPoint {
+    AlacPoint::new(Line(row as i32), Column(column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(lines: &[&str]) -> ScreenSnapshot {
+        let columns = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let rows = lines
+            .iter()
+            .map(|line| {
+                let mut row = vec![Cell::default(); columns];
+                for (cell, c) in row.iter_mut().zip(line.chars()) {
+                    cell.c = c;
+                }
+                row
+            })
+            .collect();
+        ScreenSnapshot {
+            rows,
+            columns,
+            cursor: screen_point(0, 0),
+            cursor_shape: CursorShape::Block,
+            mode: TermMode::default(),
+            title: "zsh".to_string(),
+        }
+    }
+
+    fn changed_row_indices(update: &ScreenUpdate) -> Vec<usize> {
+        update.changed_rows.iter().map(|(row, _)| *row).collect()
+    }
+
+    #[test]
+    fn test_screen_update_since() {
+        let first = snapshot(&["$ ls", "a.rs", "    "]);
+        let full = first.update_since(None);
+        assert_eq!(changed_row_indices(&full), vec![0, 1, 2]);
+
+        let unchanged = first.update_since(Some(&first));
+        assert!(unchanged.changed_rows.is_empty());
+        assert!(unchanged.is_empty(&first));
+
+        let second = snapshot(&["$ ls", "b.rs", "$   "]);
+        let update = second.update_since(Some(&first));
+        assert_eq!(changed_row_indices(&update), vec![1, 2]);
+        assert!(!update.is_empty(&first));
+
+        let mut moved_cursor = first.clone();
+        moved_cursor.cursor = screen_point(2, 1);
+        let update = moved_cursor.update_since(Some(&first));
+        assert!(update.changed_rows.is_empty());
+        assert!(!update.is_empty(&first));
+
+        let resized = snapshot(&["$ ls", "a.rs", "    ", "    "]);
+        let update = resized.update_since(Some(&first));
+        assert_eq!(changed_row_indices(&update), vec![0, 1, 2, 3]);
+    }
+}
@@ -3,6 +3,7 @@ pub mod mappings;
 pub use alacritty_terminal;
 
 mod pty_info;
+pub mod shared_screen;
 pub mod terminal_settings;
 
 use alacritty_terminal::{
@@ -18,7 +19,7 @@ use alacritty_terminal::{
         Config, RenderableCursor, TermMode,
     },
     tty::{self, setup_env},
-    vte::ansi::{ClearMode, Handler, NamedPrivateMode, PrivateMode, Rgb},
+    vte::ansi::{ClearMode, CursorShape, Handler, NamedPrivateMode, PrivateMode, Rgb},
     Term,
 };
 use anyhow::{bail, Result};
@@ -38,6 +39,7 @@ use futures::StreamExt;
 use pty_info::PtyProcessInfo;
 use serde::{Deserialize, Serialize};
 use settings::Settings;
+use shared_screen::{screen_point, ScreenSnapshot, ScreenUpdate};
 use smol::channel::{Receiver, Sender};
 use task::TaskId;
 use terminal_settings::{AlternateScroll, Shell, TerminalBlink, TerminalSettings};
@@ -302,6 +304,9 @@ impl Display for TerminalError {
 const DEFAULT_SCROLL_HISTORY_LINES: usize = 10_000;
 const MAX_SCROLL_HISTORY_LINES: usize = 100_000;
 
+const URL_REGEX: &str = r#"(ipfs:|ipns:|magnet:|mailto:|gemini://|gopher://|https://|http://|news:|file://|git://|ssh:|ftp://)[^\u{0000}-\u{001F}\u{007F}-\u{009F}<>"\s{-}\^⟨⟩`]+"#;
+const WORD_REGEX: &str = r#"[\$\+\w.\[\]:/\\@\-~]+"#;
+
 pub struct TerminalBuilder {
     terminal: Terminal,
     events_rx: UnboundedReceiver<AlacTermEvent>,
@@ -422,12 +427,12 @@ impl TerminalBuilder {
         let pty_tx = event_loop.channel();
         let _io_thread = event_loop.spawn(); // DANGER
 
-        let url_regex = RegexSearch::new(r#"(ipfs:|ipns:|magnet:|mailto:|gemini://|gopher://|https://|http://|news:|file://|git://|ssh:|ftp://)[^\u{0000}-\u{001F}\u{007F}-\u{009F}<>"\s{-}\^⟨⟩`]+"#).unwrap();
-        let word_regex = RegexSearch::new(r#"[\$\+\w.\[\]:/\\@\-~]+"#).unwrap();
+        let url_regex = RegexSearch::new(URL_REGEX).unwrap();
+        let word_regex = RegexSearch::new(WORD_REGEX).unwrap();
 
         let terminal = Terminal {
             task,
-            pty_tx: Notifier(pty_tx),
+            input_tx: InputSender::Pty(Notifier(pty_tx)),
             completion_tx,
             term,
             events: VecDeque::with_capacity(10), //Should never get this high.
@@ -435,7 +440,8 @@ impl TerminalBuilder {
             last_mouse: None,
             matches: Vec::new(),
             selection_head: None,
-            pty_info,
+            pty_info: Some(pty_info),
+            remote: None,
             breadcrumb_text: String::new(),
             scroll_px: px(0.),
             last_mouse_position: None,
@@ -561,7 +567,7 @@ impl Default for TerminalContent {
             selection_text: Default::default(),
             selection: Default::default(),
             cursor: RenderableCursor {
-                shape: alacritty_terminal::vte::ansi::CursorShape::Block,
+                shape: CursorShape::Block,
                 point: AlacPoint::new(Line(0), Column(0)),
             },
             cursor_char: Default::default(),
@@ -577,8 +583,47 @@ pub enum SelectionPhase {
     Ended,
 }
 
+/// Where the input written to a terminal is sent.
+enum InputSender {
+    /// The PTY of a process running on this machine.
+    Pty(Notifier),
+    /// A channel, such as the one forwarding input to the host of a shared terminal.
+    Channel(UnboundedSender<Vec<u8>>),
+}
+
+impl InputSender {
+    fn notify(&self, input: Vec<u8>) {
+        match self {
+            InputSender::Pty(notifier) => notifier.notify(input),
+            InputSender::Channel(sender) => {
+                sender.unbounded_send(input).ok();
+            }
+        }
+    }
+
+    fn resize(&self, size: TerminalSize) {
+        if let InputSender::Pty(notifier) = self {
+            notifier.0.send(Msg::Resize(size.into())).ok();
+        }
+    }
+
+    fn shutdown(&self) {
+        if let InputSender::Pty(notifier) = self {
+            notifier.0.send(Msg::Shutdown).ok();
+        }
+    }
+}
+
+/// The state of a terminal that mirrors one a collaborator shared.
+struct RemoteState {
+    title: String,
+    mode: TermMode,
+    cursor_shape: CursorShape,
+    read_only: bool,
+}
+
 pub struct Terminal {
-    pty_tx: Notifier,
+    input_tx: InputSender,
     completion_tx: Sender<()>,
     term: Arc<FairMutex<Term<ZedListener>>>,
     events: VecDeque<InternalEvent>,
@@ -590,7 +635,7 @@ pub struct Terminal {
     pub last_content: TerminalContent,
     pub selection_head: Option<AlacPoint>,
     pub breadcrumb_text: String,
-    pub pty_info: PtyProcessInfo,
+    pub pty_info: Option<PtyProcessInfo>,
     scroll_px: Pixels,
     next_link_id: usize,
     selection_phase: SelectionPhase,
@@ -599,6 +644,7 @@ pub struct Terminal {
     url_regex: RegexSearch,
     word_regex: RegexSearch,
     task: Option<TaskState>,
+    remote: Option<RemoteState>,
 }
 
 pub struct TaskState {
@@ -672,7 +718,11 @@ impl Terminal {
             AlacTermEvent::Wakeup => {
                 cx.emit(Event::Wakeup);
 
-                if self.pty_info.has_changed() {
+                if self
+                    .pty_info
+                    .as_mut()
+                    .map_or(false, |pty_info| pty_info.has_changed())
+                {
                     cx.emit(Event::TitleChanged);
                 }
             }
@@ -691,7 +741,11 @@ impl Terminal {
     }
 
     pub fn get_cwd(&self) -> Option<PathBuf> {
-        self.pty_info.current.as_ref().map(|info| info.cwd.clone())
+        self.pty_info
+            .as_ref()?
+            .current
+            .as_ref()
+            .map(|info| info.cwd.clone())
     }
 
     ///Takes events from Alacritty and translates them to behavior on this view
@@ -714,9 +768,11 @@ impl Terminal {
 
                 self.last_content.size = new_size;
 
-                self.pty_tx.0.send(Msg::Resize(new_size.into())).ok();
-
-                term.resize(new_size);
+                // A shared terminal keeps the size of its host's screen.
+                if self.remote.is_none() {
+                    self.input_tx.resize(new_size);
+                    term.resize(new_size);
+                }
             }
             InternalEvent::Clear => {
                 // Clear back buffer
@@ -1009,7 +1065,10 @@ impl Terminal {
     }
 
     pub fn clear(&mut self) {
-        self.events.push_back(InternalEvent::Clear)
+        // A shared terminal's screen belongs to its host.
+        if self.remote.is_none() {
+            self.events.push_back(InternalEvent::Clear)
+        }
     }
 
     pub fn scroll_line_up(&mut self) {
@@ -1061,11 +1120,13 @@ impl Terminal {
 
     ///Write the Input payload to the tty.
     fn write_to_pty(&self, input: String) {
-        self.pty_tx.notify(input.into_bytes());
+        self.write_bytes_to_pty(input.into_bytes());
     }
 
     fn write_bytes_to_pty(&self, input: Vec<u8>) {
-        self.pty_tx.notify(input);
+        if !self.is_read_only() {
+            self.input_tx.notify(input);
+        }
     }
 
     pub fn input(&mut self, input: String) {
@@ -1123,6 +1184,10 @@ impl Terminal {
         }
 
         self.last_content = Self::make_content(&terminal, &self.last_content);
+        if let Some(remote) = &self.remote {
+            self.last_content.mode = remote.mode;
+            self.last_content.cursor.shape = remote.cursor_shape;
+        }
     }
 
     fn make_content(term: &Term<ZedListener>, last_content: &TerminalContent) -> TerminalContent {
@@ -1224,7 +1289,7 @@ impl Terminal {
 
             if self.mouse_changed(point, side) {
                 if let Some(bytes) = mouse_moved_report(point, e, self.last_content.mode) {
-                    self.pty_tx.notify(bytes);
+                    self.write_bytes_to_pty(bytes);
                 }
             }
         } else if self.secondary_pressed {
@@ -1303,7 +1368,7 @@ impl Terminal {
             if let Some(bytes) =
                 mouse_button_report(point, e.button, e.modifiers, true, self.last_content.mode)
             {
-                self.pty_tx.notify(bytes);
+                self.write_bytes_to_pty(bytes);
             }
         } else {
             match e.button {
@@ -1362,7 +1427,7 @@ impl Terminal {
             if let Some(bytes) =
                 mouse_button_report(point, e.button, e.modifiers, false, self.last_content.mode)
             {
-                self.pty_tx.notify(bytes);
+                self.write_bytes_to_pty(bytes);
             }
         } else {
             if e.button == MouseButton::Left && setting.copy_on_select {
@@ -1400,7 +1465,7 @@ impl Terminal {
                 if let Some(scrolls) = scroll_report(point, scroll_lines, e, self.last_content.mode)
                 {
                     for scroll in scrolls {
-                        self.pty_tx.notify(scroll);
+                        self.write_bytes_to_pty(scroll);
                     }
                 };
             } else if self
@@ -1409,7 +1474,7 @@ impl Terminal {
                 .contains(TermMode::ALT_SCREEN | TermMode::ALTERNATE_SCROLL)
                 && !e.shift
             {
-                self.pty_tx.notify(alt_scroll(scroll_lines))
+                self.write_bytes_to_pty(alt_scroll(scroll_lines))
             } else {
                 if scroll_lines != 0 {
                     let scroll = AlacScroll::Delta(scroll_lines);
@@ -1466,6 +1531,7 @@ impl Terminal {
 
     pub fn working_directory(&self) -> Option<PathBuf> {
         self.pty_info
+            .as_ref()?
             .current
             .as_ref()
             .map(|process| process.cwd.clone())
@@ -1473,6 +1539,14 @@ impl Terminal {
 
     pub fn title(&self, truncate: bool) -> String {
         const MAX_CHARS: usize = 25;
+        if let Some(remote) = &self.remote {
+            return if truncate {
+                truncate_and_trailoff(&remote.title, MAX_CHARS)
+            } else {
+                remote.title.clone()
+            };
+        }
+
         match &self.task {
             Some(task_state) => {
                 if truncate {
@@ -1483,8 +1557,8 @@ impl Terminal {
             }
             None => self
                 .pty_info
-                .current
                 .as_ref()
+                .and_then(|pty_info| pty_info.current.as_ref())
                 .map(|fpi| {
                     let process_file = fpi
                         .cwd
@@ -1524,6 +1598,155 @@ impl Terminal {
         self.task.as_ref()
     }
 
+    /// Creates a terminal that mirrors one a collaborator shared. It has no process of its
+    /// own: its screen is written by [`Terminal::apply_screen_update`], and its input is sent
+    /// on `input_tx` unless it's read-only.
+    pub fn remote(title: String, read_only: bool, input_tx: UnboundedSender<Vec<u8>>) -> Terminal {
+        let mut terminal = Self::without_process(input_tx);
+        terminal.remote = Some(RemoteState {
+            title: title.clone(),
+            mode: TermMode::default(),
+            cursor_shape: CursorShape::Block,
+            read_only,
+        });
+        terminal.breadcrumb_text = title;
+        terminal
+    }
+
+    /// Creates a terminal without a process, whose input is sent on `input_tx` and whose
+    /// output is written with [`Terminal::write_output`].
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn test(input_tx: UnboundedSender<Vec<u8>>) -> Terminal {
+        Self::without_process(input_tx)
+    }
+
+    /// Writes `output` to the screen, as if this terminal's process printed it.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn write_output(&mut self, output: &[u8], cx: &mut ModelContext<Self>) {
+        let mut processor = alacritty_terminal::vte::ansi::Processor::<
+            alacritty_terminal::vte::ansi::StdSyncHandler,
+        >::new();
+        let term = self.term.clone();
+        let mut term = term.lock();
+        for byte in output {
+            processor.advance(&mut *term, *byte);
+        }
+        drop(term);
+        cx.emit(Event::Wakeup);
+    }
+
+    fn without_process(input_tx: UnboundedSender<Vec<u8>>) -> Terminal {
+        let (events_tx, _) = unbounded();
+        let (completion_tx, _) = smol::channel::unbounded();
+        let term = Term::new(
+            Config::default(),
+            &TerminalSize::default(),
+            ZedListener(events_tx),
+        );
+
+        Terminal {
+            task: None,
+            input_tx: InputSender::Channel(input_tx),
+            completion_tx,
+            term: Arc::new(FairMutex::new(term)),
+            events: VecDeque::with_capacity(10),
+            last_content: Default::default(),
+            last_mouse: None,
+            matches: Vec::new(),
+            selection_head: None,
+            pty_info: None,
+            remote: None,
+            breadcrumb_text: String::new(),
+            scroll_px: px(0.),
+            last_mouse_position: None,
+            next_link_id: 0,
+            selection_phase: SelectionPhase::Ended,
+            secondary_pressed: false,
+            hovered_word: false,
+            url_regex: RegexSearch::new(URL_REGEX).unwrap(),
+            word_regex: RegexSearch::new(WORD_REGEX).unwrap(),
+        }
+    }
+
+    /// Whether this terminal mirrors one a collaborator shared.
+    pub fn is_remote(&self) -> bool {
+        self.remote.is_some()
+    }
+
+    /// Whether input to this terminal is discarded, as the collaborator who shared it
+    /// hasn't allowed guests to write to it.
+    pub fn is_read_only(&self) -> bool {
+        self.remote
+            .as_ref()
+            .map_or(false, |remote| remote.read_only)
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool, cx: &mut ModelContext<Self>) {
+        if let Some(remote) = self.remote.as_mut() {
+            if remote.read_only != read_only {
+                remote.read_only = read_only;
+                cx.emit(Event::TitleChanged);
+            }
+        }
+    }
+
+    /// Captures the screen of this terminal, as it's shown to collaborators.
+    pub fn screen_snapshot(&self) -> ScreenSnapshot {
+        let term = self.term.clone();
+        let term = term.lock_unfair();
+        ScreenSnapshot::new(&term, self.title(false))
+    }
+
+    /// Writes the changes to a collaborator's screen into a terminal created by
+    /// [`Terminal::remote`].
+    pub fn apply_screen_update(&mut self, update: ScreenUpdate, cx: &mut ModelContext<Self>) {
+        let Some(remote) = self.remote.as_mut() else {
+            return;
+        };
+        if update.rows == 0 || update.columns == 0 {
+            return;
+        }
+        let title_changed = remote.title != update.title;
+        remote.title = update.title;
+        remote.mode = update.mode;
+        remote.cursor_shape = update.cursor_shape;
+        if title_changed {
+            self.breadcrumb_text = remote.title.clone();
+        }
+
+        let term = self.term.clone();
+        let mut term = term.lock();
+        if term.screen_lines() != update.rows || term.columns() != update.columns {
+            term.resize(TerminalSize::new(
+                px(1.),
+                px(1.),
+                Size {
+                    width: px(update.columns as f32),
+                    height: px(update.rows as f32),
+                },
+            ));
+        }
+        for (row, cells) in update.changed_rows {
+            if row >= update.rows {
+                continue;
+            }
+            for (column, cell) in cells.into_iter().take(update.columns).enumerate() {
+                term.grid_mut()[screen_point(row, column)] = cell;
+            }
+        }
+        term.grid_mut().cursor.point = screen_point(
+            min(update.cursor.line.0.max(0) as usize, update.rows - 1),
+            min(update.cursor.column.0, update.columns - 1),
+        );
+        drop(term);
+
+        if title_changed {
+            cx.emit(Event::TitleChanged);
+            cx.emit(Event::BreadcrumbsChanged);
+        }
+        cx.emit(Event::Wakeup);
+    }
+
     pub fn wait_for_completed_task(&self, cx: &mut AppContext) -> Task<()> {
         if let Some(task) = self.task() {
             if task.status == TaskStatus::Running {
@@ -1630,7 +1853,7 @@ unsafe fn append_text_to_term(term: &mut Term<ZedListener>, text_lines: &[&str])
 
 impl Drop for Terminal {
     fn drop(&mut self) {
-        self.pty_tx.0.send(Msg::Shutdown).ok();
+        self.input_tx.shutdown();
     }
 }
 
@@ -0,0 +1,706 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{anyhow, Context as _, Result};
+use client::{proto, Client, TypedEnvelope};
+use collections::HashMap;
+use futures::{channel::mpsc, StreamExt};
+use gpui::{
+    AppContext, AsyncAppContext, Context as _, EventEmitter, Model, ModelContext, Subscription,
+    Task, WeakModel,
+};
+use project::Project;
+use terminal::{
+    alacritty_terminal::{
+        index::{Column, Line, Point},
+        term::{
+            cell::{Cell, Flags},
+            TermMode,
+        },
+        vte::ansi::{Color, CursorShape, NamedColor, Rgb},
+    },
+    shared_screen::{ScreenSnapshot, ScreenUpdate},
+    Event as TerminalEvent, Terminal,
+};
+use util::ResultExt;
+
+/// How long changes to a shared terminal are batched before they're sent to guests.
+const SCREEN_UPDATE_DEBOUNCE: Duration = Duration::from_millis(30);
+
+pub fn init(client: &Arc<Client>) {
+    client.add_model_message_handler(SharedTerminals::handle_advertise_shared_terminals);
+    client.add_model_request_handler(SharedTerminals::handle_open_shared_terminal);
+    client.add_model_message_handler(SharedTerminals::handle_update_shared_terminal);
+    client.add_model_message_handler(SharedTerminals::handle_shared_terminal_input);
+}
+
+#[derive(Clone, Debug)]
+pub struct SharedTerminalMetadata {
+    pub id: u64,
+    pub title: String,
+    pub guests_can_write: bool,
+}
+
+pub enum SharedTerminalsEvent {
+    /// The host started or stopped sharing a terminal, or changed who can write to it.
+    HostTerminalsChanged,
+}
+
+/// Shares the host's terminals with the guests of a project, and mirrors them on guests.
+///
+/// The host sends the rows of a shared terminal's screen that changed since guests last saw
+/// it. Guests forward their input to the host, which writes it to the terminal only if the
+/// host allowed guests to write to that terminal.
+pub struct SharedTerminals {
+    project: Model<Project>,
+    client: Arc<Client>,
+    local_terminals: HashMap<u64, LocalSharedTerminal>,
+    next_terminal_id: u64,
+    host_terminals: Vec<SharedTerminalMetadata>,
+    remote_terminals: HashMap<u64, RemoteSharedTerminal>,
+    project_is_shared: bool,
+    client_subscription: Option<client::Subscription>,
+    _project_subscriptions: Vec<Subscription>,
+}
+
+struct LocalSharedTerminal {
+    terminal: WeakModel<Terminal>,
+    guests_can_write: bool,
+    /// The screen as guests last saw it, which the next update is diffed against.
+    last_snapshot: Option<ScreenSnapshot>,
+    pending_update: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+struct RemoteSharedTerminal {
+    terminal: WeakModel<Terminal>,
+    _forward_input: Task<()>,
+}
+
+impl EventEmitter<SharedTerminalsEvent> for SharedTerminals {}
+
+impl SharedTerminals {
+    pub fn new(project: Model<Project>, cx: &mut AppContext) -> Model<Self> {
+        cx.new_model(|cx| {
+            let mut this = Self {
+                client: project.read(cx).client(),
+                local_terminals: HashMap::default(),
+                next_terminal_id: 0,
+                host_terminals: Vec::new(),
+                remote_terminals: HashMap::default(),
+                project_is_shared: false,
+                client_subscription: None,
+                _project_subscriptions: vec![
+                    cx.observe(&project, Self::handle_project_changed),
+                    cx.subscribe(&project, Self::handle_project_event),
+                ],
+                project: project.clone(),
+            };
+            this.handle_project_changed(project, cx);
+            this
+        })
+    }
+
+    /// The terminals that the host of this project shares.
+    pub fn host_terminals(&self) -> &[SharedTerminalMetadata] {
+        &self.host_terminals
+    }
+
+    pub fn is_shared(&self, terminal: &Model<Terminal>) -> bool {
+        self.local_terminal_id(terminal).is_some()
+    }
+
+    pub fn guests_can_write(&self, terminal: &Model<Terminal>) -> bool {
+        self.local_terminal_id(terminal)
+            .and_then(|id| self.local_terminals.get(&id))
+            .map_or(false, |shared| shared.guests_can_write)
+    }
+
+    /// Starts sharing one of the host's terminals with guests, who can't write to it until
+    /// [`SharedTerminals::set_guests_can_write`] is called.
+    pub fn share(&mut self, terminal: &Model<Terminal>, cx: &mut ModelContext<Self>) {
+        if self.project.read(cx).is_remote()
+            || terminal.read(cx).is_remote()
+            || self.is_shared(terminal)
+        {
+            return;
+        }
+
+        let terminal_id = self.next_terminal_id;
+        self.next_terminal_id += 1;
+        let subscriptions = vec![
+            cx.subscribe(terminal, move |this, _, event, cx| match event {
+                TerminalEvent::Wakeup | TerminalEvent::TitleChanged => {
+                    this.schedule_screen_update(terminal_id, cx)
+                }
+                _ => {}
+            }),
+            cx.observe_release(terminal, move |this, _, cx| {
+                this.local_terminals.remove(&terminal_id);
+                this.advertise_terminals(cx);
+            }),
+        ];
+        self.local_terminals.insert(
+            terminal_id,
+            LocalSharedTerminal {
+                terminal: terminal.downgrade(),
+                guests_can_write: false,
+                last_snapshot: None,
+                pending_update: None,
+                _subscriptions: subscriptions,
+            },
+        );
+        self.advertise_terminals(cx);
+        cx.notify();
+    }
+
+    pub fn unshare(&mut self, terminal: &Model<Terminal>, cx: &mut ModelContext<Self>) {
+        if let Some(terminal_id) = self.local_terminal_id(terminal) {
+            self.local_terminals.remove(&terminal_id);
+            self.advertise_terminals(cx);
+            cx.notify();
+        }
+    }
+
+    pub fn set_guests_can_write(
+        &mut self,
+        terminal: &Model<Terminal>,
+        guests_can_write: bool,
+        cx: &mut ModelContext<Self>,
+    ) {
+        let Some(terminal_id) = self.local_terminal_id(terminal) else {
+            return;
+        };
+        if let Some(shared) = self.local_terminals.get_mut(&terminal_id) {
+            shared.guests_can_write = guests_can_write;
+            self.advertise_terminals(cx);
+            cx.notify();
+        }
+    }
+
+    /// Opens a terminal that the host shares, which mirrors the host's screen.
+    pub fn open_remote_terminal(
+        &mut self,
+        terminal_id: u64,
+        cx: &mut ModelContext<Self>,
+    ) -> Task<Result<Model<Terminal>>> {
+        let project = self.project.read(cx);
+        let Some(project_id) = project.remote_id() else {
+            return Task::ready(Err(anyhow!("project was not remote")));
+        };
+        if project.is_local() {
+            return Task::ready(Err(anyhow!("cannot open shared terminals as the host")));
+        }
+
+        if let Some(terminal) = self.loaded_remote_terminal(terminal_id) {
+            return Task::ready(Ok(terminal));
+        }
+
+        let request = self.client.request(proto::OpenSharedTerminal {
+            project_id,
+            terminal_id,
+        });
+        cx.spawn(|this, mut cx| async move {
+            let response = request.await?;
+            let screen = deserialize_screen_update(response.screen.context("invalid screen")?)?;
+            this.update(&mut cx, |this, cx| {
+                if let Some(terminal) = this.loaded_remote_terminal(terminal_id) {
+                    return Ok(terminal);
+                }
+
+                let metadata = this
+                    .host_terminals
+                    .iter()
+                    .find(|metadata| metadata.id == terminal_id)
+                    .context("terminal is no longer shared")?;
+                let title = metadata.title.clone();
+                let read_only = !metadata.guests_can_write;
+                let (input_tx, mut input_rx) = mpsc::unbounded();
+                let terminal = cx.new_model(|cx| {
+                    let mut terminal = Terminal::remote(title, read_only, input_tx);
+                    terminal.apply_screen_update(screen, cx);
+                    terminal
+                });
+
+                let client = this.client.clone();
+                let forward_input = cx.background_executor().spawn(async move {
+                    while let Some(input) = input_rx.next().await {
+                        client
+                            .send(proto::SharedTerminalInput {
+                                project_id,
+                                terminal_id,
+                                input,
+                            })
+                            .log_err();
+                    }
+                });
+                this.remote_terminals.insert(
+                    terminal_id,
+                    RemoteSharedTerminal {
+                        terminal: terminal.downgrade(),
+                        _forward_input: forward_input,
+                    },
+                );
+                Ok(terminal)
+            })?
+        })
+    }
+
+    fn local_terminal_id(&self, terminal: &Model<Terminal>) -> Option<u64> {
+        self.local_terminals.iter().find_map(|(id, shared)| {
+            if shared.terminal == terminal.downgrade() {
+                Some(*id)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn loaded_remote_terminal(&self, terminal_id: u64) -> Option<Model<Terminal>> {
+        self.remote_terminals.get(&terminal_id)?.terminal.upgrade()
+    }
+
+    async fn handle_advertise_shared_terminals(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::AdvertiseSharedTerminals>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            this.host_terminals = envelope
+                .payload
+                .terminals
+                .into_iter()
+                .map(|terminal| SharedTerminalMetadata {
+                    id: terminal.terminal_id,
+                    title: terminal.title,
+                    guests_can_write: terminal.guests_can_write,
+                })
+                .collect();
+
+            let host_terminals = &this.host_terminals;
+            this.remote_terminals.retain(|id, remote| {
+                let Some(terminal) = remote.terminal.upgrade() else {
+                    return false;
+                };
+                let metadata = host_terminals.iter().find(|metadata| metadata.id == *id);
+                terminal.update(cx, |terminal, cx| {
+                    terminal.set_read_only(metadata.map_or(true, |m| !m.guests_can_write), cx)
+                });
+                metadata.is_some()
+            });
+
+            cx.emit(SharedTerminalsEvent::HostTerminalsChanged);
+            cx.notify();
+        })
+    }
+
+    async fn handle_open_shared_terminal(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::OpenSharedTerminal>,
+        mut cx: AsyncAppContext,
+    ) -> Result<proto::OpenSharedTerminalResponse> {
+        this.update(&mut cx, |this, cx| {
+            if this.project.read(cx).is_remote() {
+                return Err(anyhow!("only the host's terminals can be opened"));
+            }
+
+            let shared = this
+                .local_terminals
+                .get_mut(&envelope.payload.terminal_id)
+                .context("terminal is not shared")?;
+            let terminal = shared.terminal.upgrade().context("terminal was closed")?;
+            // Send the screen that the next update will be diffed against, so that the
+            // guest can apply it.
+            let snapshot = shared
+                .last_snapshot
+                .get_or_insert_with(|| terminal.read(cx).screen_snapshot());
+            Ok(proto::OpenSharedTerminalResponse {
+                screen: Some(serialize_screen_update(snapshot.update_since(None))),
+            })
+        })?
+    }
+
+    async fn handle_update_shared_terminal(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::UpdateSharedTerminal>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            if let Some(terminal) = this.loaded_remote_terminal(envelope.payload.terminal_id) {
+                let screen = envelope.payload.screen.context("invalid screen")?;
+                let update = deserialize_screen_update(screen)?;
+                terminal.update(cx, |terminal, cx| terminal.apply_screen_update(update, cx));
+            }
+            Ok(())
+        })?
+    }
+
+    async fn handle_shared_terminal_input(
+        this: Model<Self>,
+        envelope: TypedEnvelope<proto::SharedTerminalInput>,
+        mut cx: AsyncAppContext,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            let shared = this
+                .local_terminals
+                .get(&envelope.payload.terminal_id)
+                .context("terminal is not shared")?;
+            if !shared.guests_can_write {
+                return Err(anyhow!("guests can't write to this terminal"));
+            }
+            if let Some(terminal) = shared.terminal.upgrade() {
+                terminal.update(cx, |terminal, _| {
+                    terminal.input_bytes(envelope.payload.input)
+                });
+            }
+            Ok(())
+        })?
+    }
+
+    fn handle_project_changed(&mut self, _: Model<Project>, cx: &mut ModelContext<Self>) {
+        let is_shared = self.project.read(cx).is_shared();
+        let was_shared = std::mem::replace(&mut self.project_is_shared, is_shared);
+        if is_shared == was_shared {
+            return;
+        }
+
+        if is_shared {
+            let remote_id = self.project.read(cx).remote_id().unwrap();
+            self.client_subscription = self
+                .client
+                .subscribe_to_entity(remote_id)
+                .log_err()
+                .map(|subscription| subscription.set_model(&cx.handle(), &mut cx.to_async()));
+            self.advertise_terminals(cx);
+        } else {
+            self.client_subscription = None;
+        }
+    }
+
+    fn handle_project_event(
+        &mut self,
+        _: Model<Project>,
+        event: &project::Event,
+        cx: &mut ModelContext<Self>,
+    ) {
+        match event {
+            project::Event::Reshared => {
+                self.advertise_terminals(cx);
+            }
+            project::Event::HostReshared | project::Event::Rejoined => {
+                self.reopen_remote_terminals(cx);
+            }
+            project::Event::DisconnectedFromHost => {
+                for remote in self.remote_terminals.values() {
+                    if let Some(terminal) = remote.terminal.upgrade() {
+                        terminal.update(cx, |terminal, cx| terminal.set_read_only(true, cx));
+                    }
+                }
+                self.remote_terminals.clear();
+                self.host_terminals.clear();
+                cx.emit(SharedTerminalsEvent::HostTerminalsChanged);
+                cx.notify();
+            }
+            _ => {}
+        }
+    }
+
+    /// Fetches the whole screen of the terminals this guest has open, as updates may have
+    /// been missed while it was disconnected.
+    fn reopen_remote_terminals(&mut self, cx: &mut ModelContext<Self>) {
+        let Some(project_id) = self.project.read(cx).remote_id() else {
+            return;
+        };
+
+        for (&terminal_id, remote) in &self.remote_terminals {
+            let terminal = remote.terminal.clone();
+            let request = self.client.request(proto::OpenSharedTerminal {
+                project_id,
+                terminal_id,
+            });
+            cx.spawn(|_, mut cx| async move {
+                let response = request.await?;
+                let screen = deserialize_screen_update(response.screen.context("invalid screen")?)?;
+                terminal.update(&mut cx, |terminal, cx| {
+                    terminal.apply_screen_update(screen, cx)
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    fn schedule_screen_update(&mut self, terminal_id: u64, cx: &mut ModelContext<Self>) {
+        let Some(shared) = self.local_terminals.get_mut(&terminal_id) else {
+            return;
+        };
+        if shared.pending_update.is_some() {
+            return;
+        }
+
+        shared.pending_update = Some(cx.spawn(|this, mut cx| async move {
+            cx.background_executor().timer(SCREEN_UPDATE_DEBOUNCE).await;
+            this.update(&mut cx, |this, cx| this.send_screen_update(terminal_id, cx))
+                .ok();
+        }));
+    }
+
+    fn send_screen_update(&mut self, terminal_id: u64, cx: &mut ModelContext<Self>) {
+        let Some(shared) = self.local_terminals.get_mut(&terminal_id) else {
+            return;
+        };
+        shared.pending_update = None;
+        let Some(project_id) = self.project.read(cx).remote_id() else {
+            return;
+        };
+        let Some(terminal) = shared.terminal.upgrade() else {
+            return;
+        };
+
+        let snapshot = terminal.read(cx).screen_snapshot();
+        let update = snapshot.update_since(shared.last_snapshot.as_ref());
+        if shared
+            .last_snapshot
+            .as_ref()
+            .map_or(false, |last_snapshot| update.is_empty(last_snapshot))
+        {
+            return;
+        }
+        let title_changed = shared
+            .last_snapshot
+            .as_ref()
+            .map_or(true, |last_snapshot| last_snapshot.title != snapshot.title);
+        shared.last_snapshot = Some(snapshot);
+
+        self.client
+            .send(proto::UpdateSharedTerminal {
+                project_id,
+                terminal_id,
+                screen: Some(serialize_screen_update(update)),
+            })
+            .log_err();
+        if title_changed {
+            self.advertise_terminals(cx);
+        }
+    }
+
+    fn advertise_terminals(&self, cx: &AppContext) {
+        let Some(project_id) = self.project.read(cx).remote_id() else {
+            return;
+        };
+        if self.project.read(cx).is_remote() {
+            return;
+        }
+
+        let mut terminals = self
+            .local_terminals
+            .iter()
+            .filter_map(|(id, shared)| {
+                let terminal = shared.terminal.upgrade()?;
+                Some(proto::SharedTerminalMetadata {
+                    terminal_id: *id,
+                    title: terminal.read(cx).title(false),
+                    guests_can_write: shared.guests_can_write,
+                })
+            })
+            .collect::<Vec<_>>();
+        terminals.sort_by_key(|terminal| terminal.terminal_id);
+        self.client
+            .send(proto::AdvertiseSharedTerminals {
+                project_id,
+                terminals,
+            })
+            .ok();
+    }
+}
+
+const NAMED_COLORS: [NamedColor; 29] = [
+    NamedColor::Black,
+    NamedColor::Red,
+    NamedColor::Green,
+    NamedColor::Yellow,
+    NamedColor::Blue,
+    NamedColor::Magenta,
+    NamedColor::Cyan,
+    NamedColor::White,
+    NamedColor::BrightBlack,
+    NamedColor::BrightRed,
+    NamedColor::BrightGreen,
+    NamedColor::BrightYellow,
+    NamedColor::BrightBlue,
+    NamedColor::BrightMagenta,
+    NamedColor::BrightCyan,
+    NamedColor::BrightWhite,
+    NamedColor::Foreground,
+    NamedColor::Background,
+    NamedColor::Cursor,
+    NamedColor::DimBlack,
+    NamedColor::DimRed,
+    NamedColor::DimGreen,
+    NamedColor::DimYellow,
+    NamedColor::DimBlue,
+    NamedColor::DimMagenta,
+    NamedColor::DimCyan,
+    NamedColor::DimWhite,
+    NamedColor::BrightForeground,
+    NamedColor::DimForeground,
+];
+
+fn serialize_screen_update(update: ScreenUpdate) -> proto::TerminalScreenUpdate {
+    proto::TerminalScreenUpdate {
+        rows: update.rows as u32,
+        columns: update.columns as u32,
+        changed_rows: update
+            .changed_rows
+            .into_iter()
+            .map(|(row, cells)| proto::TerminalRow {
+                row: row as u32,
+                cells: cells.iter().map(serialize_cell).collect(),
+            })
+            .collect(),
+        cursor_row: update.cursor.line.0.max(0) as u32,
+        cursor_column: update.cursor.column.0 as u32,
+        cursor_shape: serialize_cursor_shape(update.cursor_shape) as i32,
+        mode: update.mode.bits(),
+        title: update.title,
+    }
+}
+
+fn deserialize_screen_update(update: proto::TerminalScreenUpdate) -> Result<ScreenUpdate> {
+    let cursor_shape = proto::TerminalCursorShape::from_i32(update.cursor_shape)
+        .context("invalid cursor shape")?;
+    Ok(ScreenUpdate {
+        rows: update.rows as usize,
+        columns: update.columns as usize,
+        changed_rows: update
+            .changed_rows
+            .into_iter()
+            .map(|row| {
+                let cells = row
+                    .cells
+                    .into_iter()
+                    .map(deserialize_cell)
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((row.row as usize, cells))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        cursor: Point::new(
+            Line(update.cursor_row as i32),
+            Column(update.cursor_column as usize),
+        ),
+        cursor_shape: deserialize_cursor_shape(cursor_shape),
+        mode: TermMode::from_bits_truncate(update.mode),
+        title: update.title,
+    })
+}
+
+fn serialize_cell(cell: &Cell) -> proto::TerminalCell {
+    let mut text = cell.c.to_string();
+    text.extend(cell.zerowidth().into_iter().flatten());
+    proto::TerminalCell {
+        text,
+        foreground: Some(serialize_color(cell.fg)),
+        background: Some(serialize_color(cell.bg)),
+        flags: cell.flags.bits() as u32,
+    }
+}
+
+fn deserialize_cell(cell: proto::TerminalCell) -> Result<Cell> {
+    let mut chars = cell.text.chars();
+    let mut result = Cell {
+        c: chars.next().unwrap_or(' '),
+        fg: deserialize_color(cell.foreground.context("invalid foreground")?)?,
+        bg: deserialize_color(cell.background.context("invalid background")?)?,
+        flags: Flags::from_bits_truncate(cell.flags as u16),
+        ..Default::default()
+    };
+    for c in chars {
+        result.push_zerowidth(c);
+    }
+    Ok(result)
+}
+
+fn serialize_color(color: Color) -> proto::TerminalColor {
+    let color = match color {
+        Color::Named(named) => proto::terminal_color::Color::Named(named as u32),
+        Color::Indexed(index) => proto::terminal_color::Color::Indexed(index as u32),
+        Color::Spec(rgb) => proto::terminal_color::Color::Rgb(
+            (rgb.r as u32) << 16 | (rgb.g as u32) << 8 | rgb.b as u32,
+        ),
+    };
+    proto::TerminalColor { color: Some(color) }
+}
+
+fn deserialize_color(color: proto::TerminalColor) -> Result<Color> {
+    match color.color.context("invalid color")? {
+        proto::terminal_color::Color::Named(named) => NAMED_COLORS
+            .iter()
+            .find(|color| **color as u32 == named)
+            .map(|color| Color::Named(*color))
+            .context("invalid named color"),
+        proto::terminal_color::Color::Indexed(index) => Ok(Color::Indexed(
+            u8::try_from(index).context("invalid color index")?,
+        )),
+        proto::terminal_color::Color::Rgb(rgb) => Ok(Color::Spec(Rgb {
+            r: (rgb >> 16) as u8,
+            g: (rgb >> 8) as u8,
+            b: rgb as u8,
+        })),
+    }
+}
+
+fn serialize_cursor_shape(shape: CursorShape) -> proto::TerminalCursorShape {
+    match shape {
+        CursorShape::Block => proto::TerminalCursorShape::Block,
+        CursorShape::Underline => proto::TerminalCursorShape::Underline,
+        CursorShape::Beam => proto::TerminalCursorShape::Beam,
+        CursorShape::HollowBlock => proto::TerminalCursorShape::HollowBlock,
+        CursorShape::Hidden => proto::TerminalCursorShape::Hidden,
+    }
+}
+
+fn deserialize_cursor_shape(shape: proto::TerminalCursorShape) -> CursorShape {
+    match shape {
+        proto::TerminalCursorShape::Block => CursorShape::Block,
+        proto::TerminalCursorShape::Underline => CursorShape::Underline,
+        proto::TerminalCursorShape::Beam => CursorShape::Beam,
+        proto::TerminalCursorShape::HollowBlock => CursorShape::HollowBlock,
+        proto::TerminalCursorShape::Hidden => CursorShape::Hidden,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_update_round_trip() {
+        let mut wide = Cell {
+            c: 'e',
+            fg: Color::Spec(Rgb {
+                r: 0x12,
+                g: 0x34,
+                b: 0x56,
+            }),
+            bg: Color::Indexed(236),
+            flags: Flags::BOLD | Flags::UNDERLINE,
+            ..Default::default()
+        };
+        wide.push_zerowidth('\u{301}');
+        let plain = Cell {
+            fg: Color::Named(NamedColor::DimForeground),
+            ..Default::default()
+        };
+
+        let update = ScreenUpdate {
+            rows: 3,
+            columns: 2,
+            changed_rows: vec![(1, vec![wide, plain])],
+            cursor: Point::new(Line(1), Column(1)),
+            cursor_shape: CursorShape::Beam,
+            mode: TermMode::SHOW_CURSOR | TermMode::APP_CURSOR,
+            title: "cargo test".to_string(),
+        };
+        let round_tripped =
+            deserialize_screen_update(serialize_screen_update(update.clone())).unwrap();
+        assert_eq!(round_tripped, update);
+    }
+}
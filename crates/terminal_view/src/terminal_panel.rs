@@ -1,6 +1,9 @@
 use std::{ops::ControlFlow, path::PathBuf, sync::Arc};
 
-use crate::TerminalView;
+use crate::{
+    shared_terminals::{SharedTerminals, SharedTerminalsEvent},
+    TerminalView,
+};
 use collections::{HashMap, HashSet};
 use db::kvp::KEY_VALUE_STORE;
 use futures::future::join_all;
@@ -47,7 +50,10 @@ pub fn init(cx: &mut AppContext) {
                 if workspace
                     .panel::<TerminalPanel>(cx)
                     .as_ref()
-                    .is_some_and(|panel| panel.read(cx).enabled)
+                    .is_some_and(|panel| {
+                        let panel = panel.read(cx);
+                        panel.enabled || !panel.has_no_terminals(cx)
+                    })
                 {
                     workspace.toggle_panel_focus::<TerminalPanel>(cx);
                 }
@@ -68,6 +74,10 @@ pub struct TerminalPanel {
     _subscriptions: Vec<Subscription>,
     deferred_tasks: HashMap<TaskId, Task<()>>,
     enabled: bool,
+    shared_terminals: Model<SharedTerminals>,
+    /// The host's terminals that were opened in this panel, so they aren't opened again
+    /// after being closed.
+    opened_host_terminals: HashSet<u64>,
 }
 
 impl TerminalPanel {
@@ -193,9 +203,11 @@ impl TerminalPanel {
                 .update(cx, |toolbar, cx| toolbar.add_item(buffer_search_bar, cx));
             pane
         });
+        let shared_terminals = SharedTerminals::new(workspace.project().clone(), cx);
         let subscriptions = vec![
             cx.observe(&pane, |_, _, cx| cx.notify()),
             cx.subscribe(&pane, Self::handle_pane_event),
+            cx.subscribe(&shared_terminals, Self::handle_shared_terminals_event),
         ];
         let project = workspace.project().read(cx);
         let enabled = project.is_local() || project.supports_remote_terminal(cx);
@@ -210,6 +222,8 @@ impl TerminalPanel {
             deferred_tasks: HashMap::default(),
             _subscriptions: subscriptions,
             enabled,
+            shared_terminals,
+            opened_host_terminals: HashSet::default(),
         };
         this
     }
@@ -325,6 +339,57 @@ impl TerminalPanel {
         }
     }
 
+    fn handle_shared_terminals_event(
+        &mut self,
+        shared_terminals: Model<SharedTerminals>,
+        event: &SharedTerminalsEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            SharedTerminalsEvent::HostTerminalsChanged => {
+                let host_terminals = shared_terminals
+                    .read(cx)
+                    .host_terminals()
+                    .iter()
+                    .map(|terminal| terminal.id)
+                    .collect::<HashSet<_>>();
+                self.opened_host_terminals
+                    .retain(|id| host_terminals.contains(id));
+                for id in host_terminals {
+                    if self.opened_host_terminals.insert(id) {
+                        self.add_host_terminal(id, cx);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Opens a terminal that the host shares in a new tab, without focusing it.
+    fn add_host_terminal(&mut self, terminal_id: u64, cx: &mut ViewContext<Self>) {
+        let open_terminal = self.shared_terminals.update(cx, |shared_terminals, cx| {
+            shared_terminals.open_remote_terminal(terminal_id, cx)
+        });
+        let workspace = self.workspace.clone();
+        cx.spawn(|terminal_panel, mut cx| async move {
+            let terminal = open_terminal.await?;
+            let pane = terminal_panel.update(&mut cx, |this, _| this.pane.clone())?;
+            workspace.update(&mut cx, |workspace, cx| {
+                let terminal_view =
+                    Box::new(cx.new_view(|cx| {
+                        TerminalView::new(terminal, workspace.weak_handle(), None, cx)
+                    }));
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(terminal_view, false, false, None, cx);
+                });
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    pub fn shared_terminals(&self) -> &Model<SharedTerminals> {
+        &self.shared_terminals
+    }
+
     pub fn open_terminal(
         workspace: &mut Workspace,
         action: &workspace::OpenTerminal,
@@ -589,7 +654,8 @@ impl TerminalPanel {
             .items()
             .filter_map(|item| {
                 let terminal_view = item.act_as::<TerminalView>(cx)?;
-                if terminal_view.read(cx).terminal().read(cx).task().is_some() {
+                let terminal = terminal_view.read(cx).terminal().read(cx);
+                if terminal.task().is_some() || terminal.is_remote() {
                     None
                 } else {
                     let id = item.item_id().as_u64();
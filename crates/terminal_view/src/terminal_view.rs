@@ -1,19 +1,22 @@
 mod persistence;
+pub mod shared_terminals;
 pub mod terminal_element;
 pub mod terminal_panel;
 
+use client::Client;
 use collections::HashSet;
 use editor::{scroll::Autoscroll, Editor};
 use futures::{stream::FuturesUnordered, StreamExt};
 use gpui::{
-    anchored, deferred, div, impl_actions, AnyElement, AppContext, DismissEvent, EventEmitter,
-    FocusHandle, FocusableView, KeyContext, KeyDownEvent, Keystroke, Model, MouseButton,
-    MouseDownEvent, Pixels, Render, ScrollWheelEvent, Styled, Subscription, Task, View,
-    VisualContext, WeakView,
+    actions, anchored, deferred, div, impl_actions, AnyElement, AppContext, DismissEvent,
+    EventEmitter, FocusHandle, FocusableView, KeyContext, KeyDownEvent, Keystroke, Model,
+    MouseButton, MouseDownEvent, Pixels, Render, ScrollWheelEvent, Styled, Subscription, Task,
+    View, VisualContext, WeakView,
 };
 use language::Bias;
 use persistence::TERMINAL_DB;
 use project::{search::SearchQuery, Fs, LocalWorktree, Metadata, Project};
+use shared_terminals::SharedTerminals;
 use task::TerminalWorkDir;
 use terminal::{
     alacritty_terminal::{
@@ -26,6 +29,7 @@ use terminal::{
     TerminalSize,
 };
 use terminal_element::{is_blank, TerminalElement};
+use terminal_panel::TerminalPanel;
 use ui::{h_flex, prelude::*, ContextMenu, Icon, IconName, Label, Tooltip};
 use util::{paths::PathLikeWithPosition, ResultExt};
 use workspace::{
@@ -69,9 +73,12 @@ pub struct SendKeystroke(String);
 
 impl_actions!(terminal, [SendText, SendKeystroke]);
 
-pub fn init(cx: &mut AppContext) {
+actions!(terminal, [ToggleSharing, ToggleGuestWriteAccess]);
+
+pub fn init(client: &Arc<Client>, cx: &mut AppContext) {
     terminal_panel::init(cx);
     terminal::init(cx);
+    shared_terminals::init(client);
 
     register_deserializable_item::<TerminalView>(cx);
 
@@ -213,9 +220,44 @@ impl TerminalView {
         position: gpui::Point<Pixels>,
         cx: &mut ViewContext<Self>,
     ) {
+        let sharing = self.shared_terminals(cx).and_then(|shared_terminals| {
+            let shared_terminals = shared_terminals.read(cx);
+            let is_shared = shared_terminals.is_shared(&self.terminal);
+            let project_is_shared = self
+                .workspace
+                .upgrade()?
+                .read(cx)
+                .project()
+                .read(cx)
+                .is_shared();
+            (is_shared || (project_is_shared && !self.terminal.read(cx).is_remote()))
+                .then(|| (is_shared, shared_terminals.guests_can_write(&self.terminal)))
+        });
         let context_menu = ContextMenu::build(cx, |menu, _| {
-            menu.action("Clear", Box::new(Clear))
-                .action("Close", Box::new(CloseActiveItem { save_intent: None }))
+            let mut menu = menu
+                .action("Clear", Box::new(Clear))
+                .action("Close", Box::new(CloseActiveItem { save_intent: None }));
+            if let Some((is_shared, guests_can_write)) = sharing {
+                menu = menu.separator().action(
+                    if is_shared {
+                        "Stop Sharing"
+                    } else {
+                        "Share with Collaborators"
+                    },
+                    Box::new(ToggleSharing),
+                );
+                if is_shared {
+                    menu = menu.action(
+                        if guests_can_write {
+                            "Make Read-Only for Guests"
+                        } else {
+                            "Allow Guests to Type"
+                        },
+                        Box::new(ToggleGuestWriteAccess),
+                    );
+                }
+            }
+            menu
         });
 
         cx.focus_view(&context_menu);
@@ -233,6 +275,46 @@ impl TerminalView {
         self.context_menu = Some((context_menu, position, subscription));
     }
 
+    fn shared_terminals(&self, cx: &WindowContext) -> Option<Model<SharedTerminals>> {
+        let terminal_panel = self
+            .workspace
+            .upgrade()?
+            .read(cx)
+            .panel::<TerminalPanel>(cx)?;
+        let shared_terminals = terminal_panel.read(cx).shared_terminals().clone();
+        Some(shared_terminals)
+    }
+
+    fn toggle_sharing(&mut self, _: &ToggleSharing, cx: &mut ViewContext<Self>) {
+        let Some(shared_terminals) = self.shared_terminals(cx) else {
+            return;
+        };
+        let terminal = self.terminal.clone();
+        shared_terminals.update(cx, |shared_terminals, cx| {
+            if shared_terminals.is_shared(&terminal) {
+                shared_terminals.unshare(&terminal, cx);
+            } else {
+                shared_terminals.share(&terminal, cx);
+            }
+        });
+        cx.emit(ItemEvent::UpdateTab);
+    }
+
+    fn toggle_guest_write_access(
+        &mut self,
+        _: &ToggleGuestWriteAccess,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(shared_terminals) = self.shared_terminals(cx) else {
+            return;
+        };
+        let terminal = self.terminal.clone();
+        shared_terminals.update(cx, |shared_terminals, cx| {
+            let guests_can_write = shared_terminals.guests_can_write(&terminal);
+            shared_terminals.set_guests_can_write(&terminal, !guests_can_write, cx);
+        });
+    }
+
     fn settings_changed(&mut self, cx: &mut ViewContext<Self>) {
         let settings = TerminalSettings::get_global(cx);
         self.show_title = settings.toolbar.title;
@@ -913,6 +995,8 @@ impl Render for TerminalView {
             .on_action(cx.listener(TerminalView::scroll_to_bottom))
             .on_action(cx.listener(TerminalView::show_character_palette))
             .on_action(cx.listener(TerminalView::select_all))
+            .on_action(cx.listener(TerminalView::toggle_sharing))
+            .on_action(cx.listener(TerminalView::toggle_guest_write_access))
             .on_key_down(cx.listener(Self::key_down))
             .on_mouse_down(
                 MouseButton::Right,
@@ -952,7 +1036,12 @@ impl Item for TerminalView {
     type Event = ItemEvent;
 
     fn tab_tooltip_text(&self, cx: &AppContext) -> Option<SharedString> {
-        Some(self.terminal().read(cx).title(false).into())
+        let terminal = self.terminal().read(cx);
+        if terminal.is_read_only() {
+            Some(format!("{} (read-only)", terminal.title(false)).into())
+        } else {
+            Some(terminal.title(false).into())
+        }
     }
 
     fn tab_content(&self, params: TabContentParams, cx: &WindowContext) -> AnyElement {
@@ -985,6 +1074,13 @@ impl Item for TerminalView {
                     }
                 }
             },
+            None if terminal.is_remote()
+                || self
+                    .shared_terminals(cx)
+                    .is_some_and(|shared| shared.read(cx).is_shared(self.terminal())) =>
+            {
+                (IconName::Public, Color::Accent, None)
+            }
             None => (IconName::Terminal, Color::Muted, None),
         };
 
@@ -1200,6 +1296,8 @@ impl SearchableItem for TerminalView {
                 .unwrap()),
             ),
             SearchQuery::Regex { .. } => regex_search_for_query(&query),
+            // Terminal output has no syntax tree to match against.
+            SearchQuery::Structural { .. } => None,
         };
 
         if let Some(s) = searcher {
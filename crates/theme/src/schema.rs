@@ -9,7 +9,9 @@ use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::{StatusColorsRefinement, ThemeColorsRefinement};
+use crate::{
+    Appearance, StatusColors, StatusColorsRefinement, Theme, ThemeColors, ThemeColorsRefinement,
+};
 
 pub(crate) fn try_parse_color(color: &str) -> Result<Hsla> {
     let rgba = gpui::Rgba::try_from(color)?;
@@ -26,6 +28,19 @@ pub(crate) fn try_parse_color(color: &str) -> Result<Hsla> {
     Ok(hsla)
 }
 
+/// Formats a color the way colors are written in themes, as `#rrggbbaa`.
+pub fn color_to_hex(color: Hsla) -> String {
+    let rgba = gpui::Rgba::from(color);
+    let component = |value: f32| (value.clamp(0., 1.) * 255.).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        component(rgba.r),
+        component(rgba.g),
+        component(rgba.b),
+        component(rgba.a)
+    )
+}
+
 #[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AppearanceContent {
@@ -42,6 +57,16 @@ pub enum WindowBackgroundContent {
     Blurred,
 }
 
+impl From<WindowBackgroundAppearance> for WindowBackgroundContent {
+    fn from(value: WindowBackgroundAppearance) -> Self {
+        match value {
+            WindowBackgroundAppearance::Opaque => WindowBackgroundContent::Opaque,
+            WindowBackgroundAppearance::Transparent => WindowBackgroundContent::Transparent,
+            WindowBackgroundAppearance::Blurred => WindowBackgroundContent::Blurred,
+        }
+    }
+}
+
 impl From<WindowBackgroundContent> for WindowBackgroundAppearance {
     fn from(value: WindowBackgroundContent) -> Self {
         match value {
@@ -68,6 +93,56 @@ pub struct ThemeContent {
     pub style: ThemeStyleContent,
 }
 
+impl From<&Theme> for ThemeContent {
+    fn from(theme: &Theme) -> Self {
+        let styles = &theme.styles;
+        Self {
+            name: theme.name.to_string(),
+            appearance: match theme.appearance {
+                Appearance::Light => AppearanceContent::Light,
+                Appearance::Dark => AppearanceContent::Dark,
+            },
+            style: ThemeStyleContent {
+                window_background_appearance: Some(styles.window_background_appearance.into()),
+                accents: styles
+                    .accents
+                    .0
+                    .iter()
+                    .map(|color| AccentContent(Some(color_to_hex(*color))))
+                    .collect(),
+                colors: (&styles.colors).into(),
+                status: (&styles.status).into(),
+                players: styles
+                    .player
+                    .0
+                    .iter()
+                    .map(|player| PlayerColorContent {
+                        cursor: Some(color_to_hex(player.cursor)),
+                        background: Some(color_to_hex(player.background)),
+                        selection: Some(color_to_hex(player.selection)),
+                    })
+                    .collect(),
+                syntax: styles
+                    .syntax
+                    .highlights
+                    .iter()
+                    .map(|(name, style)| {
+                        (
+                            name.clone(),
+                            HighlightStyleContent {
+                                color: style.color.map(color_to_hex),
+                                background_color: style.background_color.map(color_to_hex),
+                                font_style: style.font_style.map(Into::into),
+                                font_weight: style.font_weight.map(Into::into),
+                            },
+                        )
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
 /// The content of a serialized theme.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
@@ -905,6 +980,112 @@ impl ThemeColorsContent {
     }
 }
 
+impl From<&ThemeColors> for ThemeColorsContent {
+    fn from(colors: &ThemeColors) -> Self {
+        Self {
+            border: Some(color_to_hex(colors.border)),
+            border_variant: Some(color_to_hex(colors.border_variant)),
+            border_focused: Some(color_to_hex(colors.border_focused)),
+            border_selected: Some(color_to_hex(colors.border_selected)),
+            border_transparent: Some(color_to_hex(colors.border_transparent)),
+            border_disabled: Some(color_to_hex(colors.border_disabled)),
+            elevated_surface_background: Some(color_to_hex(colors.elevated_surface_background)),
+            surface_background: Some(color_to_hex(colors.surface_background)),
+            background: Some(color_to_hex(colors.background)),
+            element_background: Some(color_to_hex(colors.element_background)),
+            element_hover: Some(color_to_hex(colors.element_hover)),
+            element_active: Some(color_to_hex(colors.element_active)),
+            element_selected: Some(color_to_hex(colors.element_selected)),
+            element_disabled: Some(color_to_hex(colors.element_disabled)),
+            drop_target_background: Some(color_to_hex(colors.drop_target_background)),
+            ghost_element_background: Some(color_to_hex(colors.ghost_element_background)),
+            ghost_element_hover: Some(color_to_hex(colors.ghost_element_hover)),
+            ghost_element_active: Some(color_to_hex(colors.ghost_element_active)),
+            ghost_element_selected: Some(color_to_hex(colors.ghost_element_selected)),
+            ghost_element_disabled: Some(color_to_hex(colors.ghost_element_disabled)),
+            text: Some(color_to_hex(colors.text)),
+            text_muted: Some(color_to_hex(colors.text_muted)),
+            text_placeholder: Some(color_to_hex(colors.text_placeholder)),
+            text_disabled: Some(color_to_hex(colors.text_disabled)),
+            text_accent: Some(color_to_hex(colors.text_accent)),
+            icon: Some(color_to_hex(colors.icon)),
+            icon_muted: Some(color_to_hex(colors.icon_muted)),
+            icon_disabled: Some(color_to_hex(colors.icon_disabled)),
+            icon_placeholder: Some(color_to_hex(colors.icon_placeholder)),
+            icon_accent: Some(color_to_hex(colors.icon_accent)),
+            status_bar_background: Some(color_to_hex(colors.status_bar_background)),
+            title_bar_background: Some(color_to_hex(colors.title_bar_background)),
+            toolbar_background: Some(color_to_hex(colors.toolbar_background)),
+            tab_bar_background: Some(color_to_hex(colors.tab_bar_background)),
+            tab_inactive_background: Some(color_to_hex(colors.tab_inactive_background)),
+            tab_active_background: Some(color_to_hex(colors.tab_active_background)),
+            search_match_background: Some(color_to_hex(colors.search_match_background)),
+            panel_background: Some(color_to_hex(colors.panel_background)),
+            panel_focused_border: Some(color_to_hex(colors.panel_focused_border)),
+            pane_focused_border: Some(color_to_hex(colors.pane_focused_border)),
+            pane_group_border: Some(color_to_hex(colors.pane_group_border)),
+            deprecated_scrollbar_thumb_background: None,
+            scrollbar_thumb_background: Some(color_to_hex(colors.scrollbar_thumb_background)),
+            scrollbar_thumb_hover_background: Some(color_to_hex(
+                colors.scrollbar_thumb_hover_background,
+            )),
+            scrollbar_thumb_border: Some(color_to_hex(colors.scrollbar_thumb_border)),
+            scrollbar_track_background: Some(color_to_hex(colors.scrollbar_track_background)),
+            scrollbar_track_border: Some(color_to_hex(colors.scrollbar_track_border)),
+            editor_foreground: Some(color_to_hex(colors.editor_foreground)),
+            editor_background: Some(color_to_hex(colors.editor_background)),
+            editor_gutter_background: Some(color_to_hex(colors.editor_gutter_background)),
+            editor_subheader_background: Some(color_to_hex(colors.editor_subheader_background)),
+            editor_active_line_background: Some(color_to_hex(colors.editor_active_line_background)),
+            editor_highlighted_line_background: Some(color_to_hex(
+                colors.editor_highlighted_line_background,
+            )),
+            editor_line_number: Some(color_to_hex(colors.editor_line_number)),
+            editor_active_line_number: Some(color_to_hex(colors.editor_active_line_number)),
+            editor_invisible: Some(color_to_hex(colors.editor_invisible)),
+            editor_wrap_guide: Some(color_to_hex(colors.editor_wrap_guide)),
+            editor_active_wrap_guide: Some(color_to_hex(colors.editor_active_wrap_guide)),
+            editor_indent_guide: Some(color_to_hex(colors.editor_indent_guide)),
+            editor_indent_guide_active: Some(color_to_hex(colors.editor_indent_guide_active)),
+            editor_document_highlight_read_background: Some(color_to_hex(
+                colors.editor_document_highlight_read_background,
+            )),
+            editor_document_highlight_write_background: Some(color_to_hex(
+                colors.editor_document_highlight_write_background,
+            )),
+            terminal_background: Some(color_to_hex(colors.terminal_background)),
+            terminal_foreground: Some(color_to_hex(colors.terminal_foreground)),
+            terminal_bright_foreground: Some(color_to_hex(colors.terminal_bright_foreground)),
+            terminal_dim_foreground: Some(color_to_hex(colors.terminal_dim_foreground)),
+            terminal_ansi_black: Some(color_to_hex(colors.terminal_ansi_black)),
+            terminal_ansi_bright_black: Some(color_to_hex(colors.terminal_ansi_bright_black)),
+            terminal_ansi_dim_black: Some(color_to_hex(colors.terminal_ansi_dim_black)),
+            terminal_ansi_red: Some(color_to_hex(colors.terminal_ansi_red)),
+            terminal_ansi_bright_red: Some(color_to_hex(colors.terminal_ansi_bright_red)),
+            terminal_ansi_dim_red: Some(color_to_hex(colors.terminal_ansi_dim_red)),
+            terminal_ansi_green: Some(color_to_hex(colors.terminal_ansi_green)),
+            terminal_ansi_bright_green: Some(color_to_hex(colors.terminal_ansi_bright_green)),
+            terminal_ansi_dim_green: Some(color_to_hex(colors.terminal_ansi_dim_green)),
+            terminal_ansi_yellow: Some(color_to_hex(colors.terminal_ansi_yellow)),
+            terminal_ansi_bright_yellow: Some(color_to_hex(colors.terminal_ansi_bright_yellow)),
+            terminal_ansi_dim_yellow: Some(color_to_hex(colors.terminal_ansi_dim_yellow)),
+            terminal_ansi_blue: Some(color_to_hex(colors.terminal_ansi_blue)),
+            terminal_ansi_bright_blue: Some(color_to_hex(colors.terminal_ansi_bright_blue)),
+            terminal_ansi_dim_blue: Some(color_to_hex(colors.terminal_ansi_dim_blue)),
+            terminal_ansi_magenta: Some(color_to_hex(colors.terminal_ansi_magenta)),
+            terminal_ansi_bright_magenta: Some(color_to_hex(colors.terminal_ansi_bright_magenta)),
+            terminal_ansi_dim_magenta: Some(color_to_hex(colors.terminal_ansi_dim_magenta)),
+            terminal_ansi_cyan: Some(color_to_hex(colors.terminal_ansi_cyan)),
+            terminal_ansi_bright_cyan: Some(color_to_hex(colors.terminal_ansi_bright_cyan)),
+            terminal_ansi_dim_cyan: Some(color_to_hex(colors.terminal_ansi_dim_cyan)),
+            terminal_ansi_white: Some(color_to_hex(colors.terminal_ansi_white)),
+            terminal_ansi_bright_white: Some(color_to_hex(colors.terminal_ansi_bright_white)),
+            terminal_ansi_dim_white: Some(color_to_hex(colors.terminal_ansi_dim_white)),
+            link_text_hover: Some(color_to_hex(colors.link_text_hover)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
 pub struct StatusColorsContent {
@@ -1226,6 +1407,55 @@ impl StatusColorsContent {
     }
 }
 
+impl From<&StatusColors> for StatusColorsContent {
+    fn from(colors: &StatusColors) -> Self {
+        Self {
+            conflict: Some(color_to_hex(colors.conflict)),
+            conflict_background: Some(color_to_hex(colors.conflict_background)),
+            conflict_border: Some(color_to_hex(colors.conflict_border)),
+            created: Some(color_to_hex(colors.created)),
+            created_background: Some(color_to_hex(colors.created_background)),
+            created_border: Some(color_to_hex(colors.created_border)),
+            deleted: Some(color_to_hex(colors.deleted)),
+            deleted_background: Some(color_to_hex(colors.deleted_background)),
+            deleted_border: Some(color_to_hex(colors.deleted_border)),
+            error: Some(color_to_hex(colors.error)),
+            error_background: Some(color_to_hex(colors.error_background)),
+            error_border: Some(color_to_hex(colors.error_border)),
+            hidden: Some(color_to_hex(colors.hidden)),
+            hidden_background: Some(color_to_hex(colors.hidden_background)),
+            hidden_border: Some(color_to_hex(colors.hidden_border)),
+            hint: Some(color_to_hex(colors.hint)),
+            hint_background: Some(color_to_hex(colors.hint_background)),
+            hint_border: Some(color_to_hex(colors.hint_border)),
+            ignored: Some(color_to_hex(colors.ignored)),
+            ignored_background: Some(color_to_hex(colors.ignored_background)),
+            ignored_border: Some(color_to_hex(colors.ignored_border)),
+            info: Some(color_to_hex(colors.info)),
+            info_background: Some(color_to_hex(colors.info_background)),
+            info_border: Some(color_to_hex(colors.info_border)),
+            modified: Some(color_to_hex(colors.modified)),
+            modified_background: Some(color_to_hex(colors.modified_background)),
+            modified_border: Some(color_to_hex(colors.modified_border)),
+            predictive: Some(color_to_hex(colors.predictive)),
+            predictive_background: Some(color_to_hex(colors.predictive_background)),
+            predictive_border: Some(color_to_hex(colors.predictive_border)),
+            renamed: Some(color_to_hex(colors.renamed)),
+            renamed_background: Some(color_to_hex(colors.renamed_background)),
+            renamed_border: Some(color_to_hex(colors.renamed_border)),
+            success: Some(color_to_hex(colors.success)),
+            success_background: Some(color_to_hex(colors.success_background)),
+            success_border: Some(color_to_hex(colors.success_border)),
+            unreachable: Some(color_to_hex(colors.unreachable)),
+            unreachable_background: Some(color_to_hex(colors.unreachable_background)),
+            unreachable_border: Some(color_to_hex(colors.unreachable_border)),
+            warning: Some(color_to_hex(colors.warning)),
+            warning_background: Some(color_to_hex(colors.warning_background)),
+            warning_border: Some(color_to_hex(colors.warning_border)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccentContent(pub Option<String>);
 
@@ -1254,6 +1484,16 @@ impl From<FontStyleContent> for FontStyle {
     }
 }
 
+impl From<FontStyle> for FontStyleContent {
+    fn from(value: FontStyle) -> Self {
+        match value {
+            FontStyle::Normal => FontStyleContent::Normal,
+            FontStyle::Italic => FontStyleContent::Italic,
+            FontStyle::Oblique => FontStyleContent::Oblique,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr)]
 #[repr(u16)]
 pub enum FontWeightContent {
@@ -1296,6 +1536,23 @@ impl JsonSchema for FontWeightContent {
     }
 }
 
+impl From<FontWeight> for FontWeightContent {
+    /// Returns the closest weight that can be written in a theme.
+    fn from(value: FontWeight) -> Self {
+        match (value.0 / 100.).round() as u16 {
+            ..=1 => FontWeightContent::Thin,
+            2 => FontWeightContent::ExtraLight,
+            3 => FontWeightContent::Light,
+            4 => FontWeightContent::Normal,
+            5 => FontWeightContent::Medium,
+            6 => FontWeightContent::Semibold,
+            7 => FontWeightContent::Bold,
+            8 => FontWeightContent::ExtraBold,
+            _ => FontWeightContent::Black,
+        }
+    }
+}
+
 impl From<FontWeightContent> for FontWeight {
     fn from(value: FontWeightContent) -> Self {
         match value {
@@ -1344,3 +1601,37 @@ where
     let value: Value = Deserialize::deserialize(deserializer)?;
     Ok(T::deserialize(value).ok())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{one_themes::one_dark, ThemeRegistry};
+
+    #[test]
+    fn test_theme_content_round_trip() {
+        let theme = one_dark();
+        let content = ThemeContent::from(&theme);
+
+        let registry = ThemeRegistry::new(Box::new(()));
+        registry.insert_user_themes([content.clone()]);
+        let refined = registry.get(&theme.name).unwrap();
+
+        assert_eq!(refined.appearance, theme.appearance);
+        assert_eq!(
+            color_to_hex(refined.colors().editor_background),
+            color_to_hex(theme.colors().editor_background)
+        );
+        assert_eq!(
+            color_to_hex(refined.status().error),
+            color_to_hex(theme.status().error)
+        );
+        assert_eq!(
+            color_to_hex(refined.syntax().color("keyword")),
+            color_to_hex(theme.syntax().color("keyword"))
+        );
+        assert_eq!(
+            serde_json::to_value(ThemeContent::from(refined.as_ref())).unwrap(),
+            serde_json::to_value(&content).unwrap()
+        );
+    }
+}
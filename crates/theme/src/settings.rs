@@ -1,10 +1,11 @@
 use crate::one_themes::one_dark;
 use crate::{Appearance, SyntaxTheme, Theme, ThemeRegistry, ThemeStyleContent};
 use anyhow::Result;
+use collections::HashMap;
 use derive_more::{Deref, DerefMut};
 use gpui::{
-    px, AppContext, Font, FontFeatures, FontStyle, FontWeight, Global, Pixels, Subscription,
-    ViewContext, WindowContext,
+    px, AnyWindowHandle, AppContext, Font, FontFeatures, FontStyle, FontWeight, Global, Pixels,
+    Subscription, ViewContext, WindowContext,
 };
 use refineable::Refineable;
 use schemars::{
@@ -119,9 +120,103 @@ impl ThemeSettings {
                 ThemeSettings::override_global(theme_settings, cx);
             }
         }
+
+        Self::reload_window_themes(cx);
+    }
+
+    /// Overrides the theme used in the given window with the theme of the given name,
+    /// or restores the active theme when `theme_name` is `None`.
+    ///
+    /// A theme that isn't loaded yet, such as one provided by an extension, is used in the
+    /// window as soon as it is loaded.
+    pub fn set_window_theme(
+        window: AnyWindowHandle,
+        theme_name: Option<&str>,
+        cx: &mut AppContext,
+    ) -> Option<Arc<Theme>> {
+        let Some(theme_name) = theme_name else {
+            if let Some(window_themes) = cx.try_global::<GlobalWindowThemes>() {
+                if window_themes.0.contains_key(&window) {
+                    cx.global_mut::<GlobalWindowThemes>().0.remove(&window);
+                    cx.refresh();
+                }
+            }
+            return None;
+        };
+
+        let theme = Self::resolve_window_theme(theme_name, cx);
+        cx.default_global::<GlobalWindowThemes>().0.insert(
+            window,
+            WindowTheme {
+                name: theme_name.to_string(),
+                theme: theme.clone(),
+            },
+        );
+        cx.refresh();
+        theme
+    }
+
+    /// Returns the theme overriding the active theme in the given window, if there is one.
+    pub fn window_theme(window: AnyWindowHandle, cx: &AppContext) -> Option<&Arc<Theme>> {
+        cx.try_global::<GlobalWindowThemes>()?
+            .0
+            .get(&window)?
+            .theme
+            .as_ref()
+    }
+
+    /// Resolves the themes overriding the active theme in individual windows again, so they
+    /// pick up changes to the theme registry and to the user's theme overrides.
+    pub(crate) fn reload_window_themes(cx: &mut AppContext) {
+        let Some(window_themes) = cx.try_global::<GlobalWindowThemes>() else {
+            return;
+        };
+        let windows = window_themes
+            .0
+            .iter()
+            .map(|(window, window_theme)| (*window, window_theme.name.clone()))
+            .collect::<Vec<_>>();
+        if windows.is_empty() {
+            return;
+        }
+
+        for (window, theme_name) in windows {
+            let theme = Self::resolve_window_theme(&theme_name, cx);
+            if let Some(window_theme) = cx.global_mut::<GlobalWindowThemes>().0.get_mut(&window) {
+                window_theme.theme = theme;
+            }
+        }
+        cx.refresh();
+    }
+
+    fn resolve_window_theme(theme_name: &str, cx: &mut AppContext) -> Option<Arc<Theme>> {
+        ThemeRegistry::default_global(cx).get(theme_name).ok()?;
+        let mut theme_settings = ThemeSettings::get_global(cx).clone();
+        let active_syntax = theme_settings.active_theme.syntax().clone();
+        theme_settings.switch_theme(theme_name, cx)?;
+
+        // Languages assign highlight IDs from the active theme's syntax, so the window's syntax
+        // styles have to be listed in the same order for those IDs to resolve to them.
+        let mut theme = (*theme_settings.active_theme).clone();
+        theme.styles.syntax = Arc::new(active_syntax.restyled_with(theme.syntax()));
+        Some(Arc::new(theme))
     }
 }
 
+/// The themes overriding the active theme in individual windows, such as a workspace
+/// with its own theme.
+#[derive(Default)]
+struct GlobalWindowThemes(HashMap<AnyWindowHandle, WindowTheme>);
+
+impl Global for GlobalWindowThemes {}
+
+struct WindowTheme {
+    name: String,
+    /// The resolved theme, with its syntax styles in the order of the active theme's, or
+    /// `None` if no theme with this name is loaded.
+    theme: Option<Arc<Theme>>,
+}
+
 /// The appearance of the system.
 #[derive(Debug, Clone, Copy, Deref)]
 pub struct SystemAppearance(pub Appearance);
@@ -577,3 +672,65 @@ fn merge<T: Copy>(target: &mut T, value: Option<T>) {
         *target = value;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ActiveTheme as _, LoadThemes, ThemeContent};
+    use gpui::TestAppContext;
+    use settings::SettingsStore;
+
+    #[gpui::test]
+    fn test_window_theme_syntax(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            crate::init(LoadThemes::JustBase, cx);
+            ThemeRegistry::global(cx).insert_user_themes([serde_json::from_value::<ThemeContent>(
+                serde_json::json!({
+                    "name": "Window Theme",
+                    "appearance": "dark",
+                    "style": {
+                        "syntax": {
+                            "string": { "color": "#00ff00ff" },
+                            "keyword": { "color": "#ff0000ff" },
+                        }
+                    }
+                }),
+            )
+            .unwrap()]);
+        });
+
+        let cx = cx.add_empty_window();
+        cx.update(|cx| {
+            ThemeSettings::set_window_theme(cx.window_handle(), Some("Window Theme"), cx)
+        });
+
+        cx.update(|cx| {
+            let active_syntax = ThemeSettings::get_global(cx).active_theme.syntax().clone();
+            let window_theme = ThemeRegistry::global(cx).get("Window Theme").unwrap();
+            let syntax = cx.theme().syntax();
+            assert_eq!(cx.theme().name.as_ref(), "Window Theme");
+            assert_eq!(
+                syntax
+                    .highlights
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+                active_syntax
+                    .highlights
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>()
+            );
+
+            for name in ["keyword", "string"] {
+                let highlight_id = active_syntax.highlight_id(name).unwrap();
+                assert_eq!(
+                    syntax.highlights[highlight_id as usize].1.color,
+                    Some(window_theme.syntax().color(name))
+                );
+            }
+        });
+    }
+}
@@ -83,6 +83,21 @@ impl SyntaxTheme {
             highlights: merged_highlights,
         })
     }
+
+    /// Returns a new [`SyntaxTheme`] with the highlight names of this theme, in the same order,
+    /// styled as they are in the given theme.
+    ///
+    /// Highlight IDs index into the highlights of the active theme, so this is how the syntax
+    /// styles of another theme can be used without remapping those IDs.
+    pub fn restyled_with(&self, styles: &SyntaxTheme) -> Self {
+        Self {
+            highlights: self
+                .highlights
+                .iter()
+                .map(|(name, _)| (name.clone(), styles.get(name)))
+                .collect(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -192,4 +207,37 @@ mod tests {
             ]))
         );
     }
+
+    #[test]
+    fn test_syntax_theme_restyled_with() {
+        let syntax_theme = SyntaxTheme::new_test([
+            ("foo", gpui::red()),
+            ("foo.bar", gpui::green()),
+            ("baz", gpui::blue()),
+        ])
+        .restyled_with(&SyntaxTheme::new_test([
+            ("baz", gpui::yellow()),
+            ("foo", gpui::blue()),
+        ]));
+        assert_eq!(
+            syntax_theme,
+            SyntaxTheme::new_test_styles([
+                (
+                    "foo",
+                    HighlightStyle {
+                        color: Some(gpui::blue()),
+                        ..Default::default()
+                    }
+                ),
+                ("foo.bar", HighlightStyle::default()),
+                (
+                    "baz",
+                    HighlightStyle {
+                        color: Some(gpui::yellow()),
+                        ..Default::default()
+                    }
+                ),
+            ])
+        );
+    }
 }
@@ -29,7 +29,7 @@ pub use styles::*;
 
 use gpui::{
     px, AppContext, AssetSource, Hsla, Pixels, SharedString, WindowAppearance,
-    WindowBackgroundAppearance,
+    WindowBackgroundAppearance, WindowContext,
 };
 use serde::Deserialize;
 
@@ -90,6 +90,7 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut AppContext) {
             prev_buffer_font_size = buffer_font_size;
             reset_buffer_font_size(cx);
         }
+        ThemeSettings::reload_window_themes(cx);
     })
     .detach();
 }
@@ -104,6 +105,13 @@ impl ActiveTheme for AppContext {
     }
 }
 
+impl ActiveTheme for WindowContext<'_> {
+    fn theme(&self) -> &Arc<Theme> {
+        ThemeSettings::window_theme(self.window_handle(), self)
+            .unwrap_or_else(|| &ThemeSettings::get_global(self).active_theme)
+    }
+}
+
 pub struct ThemeFamily {
     pub id: String,
     pub name: SharedString,
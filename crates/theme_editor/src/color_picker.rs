@@ -0,0 +1,234 @@
+use editor::{Editor, EditorElement, EditorEvent, EditorStyle};
+use gpui::{
+    hsla, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, FontStyle, Hsla,
+    Render, Rgba, Subscription, TextStyle, View, ViewContext, WhiteSpace,
+};
+use theme::{color_to_hex, ThemeSettings};
+use ui::prelude::*;
+
+const HUE_STEPS: usize = 36;
+const SATURATION_STEPS: usize = 12;
+const LIGHTNESS_STEPS: usize = 9;
+const ALPHA_STEPS: usize = 11;
+
+/// A popover for picking a color, either from swatches of hue, saturation, lightness and
+/// opacity, or by typing it as a hex color.
+pub struct ColorPicker {
+    color: Hsla,
+    hex_editor: View<Editor>,
+    on_change: Box<dyn Fn(Hsla, &mut WindowContext)>,
+    _subscription: Subscription,
+}
+
+impl ColorPicker {
+    pub fn new(
+        color: Hsla,
+        on_change: impl Fn(Hsla, &mut WindowContext) + 'static,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let hex_editor = cx.new_view(|cx| {
+            let mut editor = Editor::single_line(cx);
+            editor.set_text(color_to_hex(color), cx);
+            editor
+        });
+        let subscription = cx.subscribe(&hex_editor, |this, _, event: &EditorEvent, cx| {
+            if let EditorEvent::Edited { .. } = event {
+                this.parse_hex(cx);
+            }
+        });
+        cx.focus_view(&hex_editor);
+
+        Self {
+            color,
+            hex_editor,
+            on_change: Box::new(on_change),
+            _subscription: subscription,
+        }
+    }
+
+    fn parse_hex(&mut self, cx: &mut ViewContext<Self>) {
+        let text = self.hex_editor.read(cx).text(cx);
+        // Ignore the edits made by `set_color`, as parsing the color back would lose precision.
+        if text.trim() == color_to_hex(self.color) {
+            return;
+        }
+        if let Ok(rgba) = Rgba::try_from(text.trim()) {
+            self.color = rgba.into();
+            (self.on_change)(self.color, cx);
+            cx.notify();
+        }
+    }
+
+    fn set_color(&mut self, color: Hsla, cx: &mut ViewContext<Self>) {
+        self.color = color;
+        self.hex_editor
+            .update(cx, |editor, cx| editor.set_text(color_to_hex(color), cx));
+        (self.on_change)(color, cx);
+        cx.notify();
+    }
+
+    fn render_swatch(
+        &self,
+        id: impl Into<ElementId>,
+        color: Hsla,
+        selected: bool,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        div()
+            .id(id.into())
+            .flex_1()
+            .h_4()
+            .bg(color)
+            .border_1()
+            .border_color(if selected {
+                cx.theme().colors().border_focused
+            } else {
+                gpui::transparent_black()
+            })
+            .cursor_pointer()
+            .on_click(cx.listener(move |this, _, cx| this.set_color(color, cx)))
+    }
+
+    fn render_hex_editor(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.buffer_font.family.clone(),
+            font_features: settings.buffer_font.features.clone(),
+            font_size: rems(0.875).into(),
+            font_weight: settings.buffer_font.weight,
+            font_style: FontStyle::Normal,
+            line_height: relative(1.3),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+            white_space: WhiteSpace::Normal,
+        };
+
+        div()
+            .flex_1()
+            .px_2()
+            .py_1()
+            .border_1()
+            .border_color(cx.theme().colors().border)
+            .rounded_md()
+            .child(EditorElement::new(
+                &self.hex_editor,
+                EditorStyle {
+                    background: cx.theme().colors().editor_background,
+                    local_player: cx.theme().players().local(),
+                    text: text_style,
+                    ..Default::default()
+                },
+            ))
+    }
+}
+
+/// Returns `steps` evenly spaced values from `start` to `end`, inclusive.
+fn steps(start: f32, end: f32, steps: usize) -> impl Iterator<Item = f32> {
+    (0..steps).map(move |step| start + (end - start) * step as f32 / (steps - 1) as f32)
+}
+
+impl Render for ColorPicker {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let color = self.color;
+        let is_close = |a: f32, b: f32, steps: usize| (a - b).abs() <= 0.5 / (steps - 1) as f32;
+
+        let hues = (0..HUE_STEPS)
+            .map(|step| {
+                let hue = step as f32 / HUE_STEPS as f32;
+                let selected = (color.h - hue).abs() < 0.5 / HUE_STEPS as f32;
+                self.render_swatch(
+                    ("hue", step),
+                    hsla(hue, color.s.max(0.5), 0.5, 1.),
+                    selected,
+                    cx,
+                )
+                .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        let shades = steps(1., 0., LIGHTNESS_STEPS)
+            .enumerate()
+            .map(|(row, lightness)| {
+                h_flex()
+                    .children(
+                        steps(0., 1., SATURATION_STEPS)
+                            .enumerate()
+                            .map(|(column, saturation)| {
+                                let selected = is_close(color.l, lightness, LIGHTNESS_STEPS)
+                                    && is_close(color.s, saturation, SATURATION_STEPS);
+                                self.render_swatch(
+                                    ("shade", row * SATURATION_STEPS + column),
+                                    hsla(color.h, saturation, lightness, color.a),
+                                    selected,
+                                    cx,
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                    .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        let alphas = steps(0., 1., ALPHA_STEPS)
+            .enumerate()
+            .map(|(step, alpha)| {
+                self.render_swatch(
+                    ("alpha", step),
+                    Hsla { a: alpha, ..color },
+                    is_close(color.a, alpha, ALPHA_STEPS),
+                    cx,
+                )
+                .into_any_element()
+            })
+            .collect::<Vec<_>>();
+
+        v_flex()
+            .key_context("ColorPicker")
+            .elevation_3(cx)
+            .w(rems(20.))
+            .p_2()
+            .gap_2()
+            .on_action(cx.listener(|this, _: &menu::Confirm, cx| {
+                this.parse_hex(cx);
+                cx.emit(DismissEvent);
+            }))
+            .on_action(cx.listener(|_, _: &menu::Cancel, cx| cx.emit(DismissEvent)))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .size_6()
+                            .rounded_md()
+                            .border_1()
+                            .border_color(cx.theme().colors().border)
+                            .bg(color),
+                    )
+                    .child(self.render_hex_editor(cx)),
+            )
+            .child(Label::new("Hue").size(LabelSize::Small).color(Color::Muted))
+            .child(h_flex().children(hues))
+            .child(
+                Label::new("Saturation and lightness")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(v_flex().children(shades))
+            .child(
+                Label::new("Opacity")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            )
+            .child(h_flex().children(alphas))
+    }
+}
+
+impl EventEmitter<DismissEvent> for ColorPicker {}
+
+impl FocusableView for ColorPicker {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.hex_editor.focus_handle(cx)
+    }
+}
@@ -0,0 +1,127 @@
+use gpui::{Hsla, Rgba};
+
+/// The minimum contrast ratio for body text to meet WCAG 2 level AA.
+pub const MINIMUM_TEXT_CONTRAST: f32 = 4.5;
+
+/// The minimum contrast ratio for large text and user interface components to meet
+/// WCAG 2 level AA.
+pub const MINIMUM_UI_CONTRAST: f32 = 3.0;
+
+/// A foreground color that is drawn on a background color, keyed as in theme files.
+#[derive(Debug, Clone, Copy)]
+pub struct ContrastPair {
+    pub foreground: &'static str,
+    pub background: &'static str,
+    pub minimum: f32,
+}
+
+const fn text(foreground: &'static str, background: &'static str) -> ContrastPair {
+    ContrastPair {
+        foreground,
+        background,
+        minimum: MINIMUM_TEXT_CONTRAST,
+    }
+}
+
+const fn ui(foreground: &'static str, background: &'static str) -> ContrastPair {
+    ContrastPair {
+        foreground,
+        background,
+        minimum: MINIMUM_UI_CONTRAST,
+    }
+}
+
+/// The pairs of theme colors whose contrast is checked.
+///
+/// Syntax colors are checked against `editor.background` in addition to these.
+pub const CONTRAST_PAIRS: &[ContrastPair] = &[
+    text("text", "background"),
+    text("text", "surface.background"),
+    text("text", "elevated_surface.background"),
+    text("text", "panel.background"),
+    text("text", "tab.active_background"),
+    text("text", "editor.background"),
+    text("text.muted", "background"),
+    text("text.muted", "surface.background"),
+    text("text.muted", "tab.inactive_background"),
+    text("text.accent", "background"),
+    ui("text.placeholder", "editor.background"),
+    text("editor.foreground", "editor.background"),
+    text("editor.foreground", "editor.active_line.background"),
+    ui("editor.line_number", "editor.gutter.background"),
+    text("editor.active_line_number", "editor.gutter.background"),
+    text("terminal.foreground", "terminal.background"),
+    ui("icon", "background"),
+    ui("icon.muted", "background"),
+    ui("icon.accent", "background"),
+    ui("border", "background"),
+    text("error", "editor.background"),
+    text("warning", "editor.background"),
+    text("info", "editor.background"),
+    text("success", "editor.background"),
+    ui("hint", "editor.background"),
+    ui("predictive", "editor.background"),
+];
+
+/// Returns the relative luminance of an opaque color, as defined by WCAG 2.
+pub fn relative_luminance(color: Rgba) -> f32 {
+    fn linearize(component: f32) -> f32 {
+        if component <= 0.04045 {
+            component / 12.92
+        } else {
+            ((component + 0.055) / 1.055).powf(2.4)
+        }
+    }
+
+    0.2126 * linearize(color.r) + 0.7152 * linearize(color.g) + 0.0722 * linearize(color.b)
+}
+
+/// Returns the WCAG 2 contrast ratio of a foreground color drawn on a background color,
+/// between 1 and 21.
+///
+/// A translucent foreground is blended onto the background first. The background is
+/// treated as opaque.
+pub fn contrast_ratio(foreground: Hsla, background: Hsla) -> f32 {
+    let background = Rgba::from(background);
+    let foreground = Rgba::from(foreground);
+    let alpha = foreground.a.clamp(0., 1.);
+    let foreground = Rgba {
+        r: foreground.r * alpha + background.r * (1. - alpha),
+        g: foreground.g * alpha + background.g * (1. - alpha),
+        b: foreground.b * alpha + background.b * (1. - alpha),
+        a: 1.,
+    };
+
+    let foreground = relative_luminance(foreground);
+    let background = relative_luminance(background);
+    let (lighter, darker) = if foreground > background {
+        (foreground, background)
+    } else {
+        (background, foreground)
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{black, hsla, white};
+
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        assert!((contrast_ratio(black(), white()) - 21.).abs() < 0.01);
+        assert!((contrast_ratio(white(), black()) - 21.).abs() < 0.01);
+        assert!((contrast_ratio(white(), white()) - 1.).abs() < 0.01);
+
+        // #777777 on white is a well-known near miss for AA text.
+        let gray = Rgba::try_from("#777777").unwrap().into();
+        let ratio = contrast_ratio(gray, white());
+        assert!((ratio - 4.48).abs() < 0.01, "ratio was {ratio}");
+        assert!(ratio < MINIMUM_TEXT_CONTRAST);
+
+        // A fully transparent foreground has no contrast with its background.
+        let transparent_black = hsla(0., 0., 0., 0.);
+        assert!((contrast_ratio(transparent_black, white()) - 1.).abs() < 0.01);
+    }
+}
@@ -0,0 +1,750 @@
+mod color_picker;
+mod contrast;
+
+use std::sync::Arc;
+
+use editor::{Editor, EditorElement, EditorEvent, EditorStyle};
+use fs::Fs;
+use gpui::{
+    actions, list, AnyElement, AppContext, EventEmitter, FocusHandle, FocusableView, FontStyle,
+    Hsla, ListAlignment, ListState, Render, Rgba, Subscription, TextStyle, UpdateGlobal, View,
+    ViewContext, WhiteSpace,
+};
+use refineable::Refineable;
+use schemars::{schema::Schema, schema_for};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use settings::{Settings, SettingsStore};
+use theme::{
+    color_to_hex, AppearanceContent, StatusColorsContent, SyntaxTheme, Theme, ThemeColorsContent,
+    ThemeContent, ThemeFamilyContent, ThemeSettings, ThemeStyleContent,
+};
+use ui::{prelude::*, ButtonLike, Checkbox, PopoverMenu, Selection, Tooltip};
+use util::ResultExt;
+use workspace::{
+    item::{Item, ItemEvent, TabContentParams},
+    Workspace, WorkspaceId,
+};
+
+use crate::{
+    color_picker::ColorPicker,
+    contrast::{contrast_ratio, CONTRAST_PAIRS, MINIMUM_TEXT_CONTRAST},
+};
+
+actions!(theme_editor, [OpenThemeEditor]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(|workspace: &mut Workspace, _| {
+        workspace.register_action(|workspace, _: &OpenThemeEditor, cx| {
+            let existing = workspace
+                .active_pane()
+                .read(cx)
+                .items()
+                .find_map(|item| item.downcast::<ThemeEditor>());
+
+            if let Some(existing) = existing {
+                workspace.activate_item(&existing, cx);
+            } else {
+                let theme_editor = ThemeEditor::new(workspace, cx);
+                workspace.add_item_to_active_pane(Box::new(theme_editor), None, cx)
+            }
+        });
+    })
+    .detach();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorGroup {
+    Theme,
+    Status,
+    Syntax,
+}
+
+impl ColorGroup {
+    fn label(self) -> &'static str {
+        match self {
+            ColorGroup::Theme => "Theme Colors",
+            ColorGroup::Status => "Status Colors",
+            ColorGroup::Syntax => "Syntax",
+        }
+    }
+}
+
+/// A color of the theme, keyed as in theme files.
+struct ColorEntry {
+    group: ColorGroup,
+    key: String,
+    description: Option<SharedString>,
+}
+
+/// The contrast of a color with one of the backgrounds it is drawn on.
+struct ContrastCheck {
+    background: &'static str,
+    ratio: f32,
+    minimum: f32,
+}
+
+impl ContrastCheck {
+    fn passes(&self) -> bool {
+        self.ratio >= self.minimum
+    }
+}
+
+enum Row {
+    Group(ColorGroup),
+    Color(usize),
+}
+
+/// Edits a copy of the active theme, previewing the changes in every window, and exports it
+/// as a theme family.
+pub struct ThemeEditor {
+    fs: Arc<dyn Fs>,
+    base_theme: Arc<Theme>,
+    appearance: AppearanceContent,
+    /// The edited style, serialized as in theme files.
+    style: Map<String, Value>,
+    /// The style of the theme that is being edited, serialized as in theme files.
+    base_style: Map<String, Value>,
+    entries: Vec<ColorEntry>,
+    rows: Vec<Row>,
+    list_state: ListState,
+    query_editor: View<Editor>,
+    name_editor: View<Editor>,
+    failures_only: bool,
+    /// The theme with the edits applied, while it is previewed in place of the active theme.
+    preview: Option<Arc<Theme>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ThemeEditor {
+    pub fn new(workspace: &Workspace, cx: &mut ViewContext<Workspace>) -> View<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let base_theme = ThemeSettings::get_global(cx).active_theme.clone();
+
+        cx.new_view(|cx: &mut ViewContext<Self>| {
+            let content = ThemeContent::from(&*base_theme);
+            let style = match serde_json::to_value(&content.style) {
+                Ok(Value::Object(style)) => style,
+                _ => Map::new(),
+            };
+
+            let mut entries = color_entries(
+                ColorGroup::Theme,
+                &content.style.colors,
+                schema_for!(ThemeColorsContent).schema.object,
+            );
+            entries.extend(color_entries(
+                ColorGroup::Status,
+                &content.style.status,
+                schema_for!(StatusColorsContent).schema.object,
+            ));
+            entries.extend(content.style.syntax.keys().map(|key| ColorEntry {
+                group: ColorGroup::Syntax,
+                key: key.clone(),
+                description: None,
+            }));
+
+            let query_editor = cx.new_view(|cx| {
+                let mut input = Editor::single_line(cx);
+                input.set_placeholder_text("Search colors...", cx);
+                input
+            });
+            let name_editor = cx.new_view(|cx| {
+                let mut input = Editor::single_line(cx);
+                input.set_text(format!("{} Custom", base_theme.name), cx);
+                input
+            });
+
+            let view = cx.view().downgrade();
+            let list_state = ListState::new(0, ListAlignment::Top, px(1000.), move |ix, cx| {
+                if let Some(view) = view.upgrade() {
+                    view.update(cx, |this, cx| this.render_row(ix, cx))
+                } else {
+                    div().into_any()
+                }
+            });
+
+            let subscriptions = vec![
+                cx.subscribe(&query_editor, |this, _, event: &EditorEvent, cx| {
+                    if let EditorEvent::Edited { .. } = event {
+                        this.update_matches(cx);
+                    }
+                }),
+                // Other changes to the settings reload the active theme, so restore the preview.
+                cx.observe_global::<SettingsStore>(|this, cx| {
+                    if let Some(preview) = this.preview.clone() {
+                        let active_theme = &ThemeSettings::get_global(cx).active_theme;
+                        if !Arc::ptr_eq(active_theme, &preview) {
+                            set_active_theme(preview, cx);
+                        }
+                    }
+                }),
+                cx.on_release(|this, _, cx| {
+                    if this.preview.is_some() {
+                        ThemeSettings::reload_current_theme(cx);
+                    }
+                }),
+            ];
+
+            let mut this = Self {
+                fs,
+                appearance: content.appearance,
+                base_theme,
+                base_style: style.clone(),
+                style,
+                entries,
+                rows: Vec::new(),
+                list_state,
+                query_editor,
+                name_editor,
+                failures_only: false,
+                preview: None,
+                _subscriptions: subscriptions,
+            };
+            this.update_matches(cx);
+            this
+        })
+    }
+
+    fn update_matches(&mut self, cx: &mut ViewContext<Self>) {
+        let query = self.query_editor.read(cx).text(cx).to_lowercase();
+        let query = query.trim();
+
+        self.rows.clear();
+        let mut current_group = None;
+        for (ix, entry) in self.entries.iter().enumerate() {
+            let matches_query = query.is_empty()
+                || entry.key.to_lowercase().contains(query)
+                || entry.description.as_ref().map_or(false, |description| {
+                    description.to_lowercase().contains(query)
+                });
+            if !matches_query {
+                continue;
+            }
+            if self.failures_only
+                && self
+                    .contrast_checks(entry)
+                    .iter()
+                    .all(ContrastCheck::passes)
+            {
+                continue;
+            }
+
+            if current_group != Some(entry.group) {
+                current_group = Some(entry.group);
+                self.rows.push(Row::Group(entry.group));
+            }
+            self.rows.push(Row::Color(ix));
+        }
+        self.list_state.reset(self.rows.len());
+        cx.notify();
+    }
+
+    fn color_value<'a>(style: &'a Map<String, Value>, entry: &ColorEntry) -> Option<&'a str> {
+        match entry.group {
+            ColorGroup::Syntax => style.get("syntax")?.get(&entry.key)?.get("color")?.as_str(),
+            ColorGroup::Theme | ColorGroup::Status => style.get(&entry.key)?.as_str(),
+        }
+    }
+
+    fn color(&self, entry: &ColorEntry) -> Option<Hsla> {
+        parse_color(Self::color_value(&self.style, entry)?)
+    }
+
+    /// Returns the edited value of a theme or status color.
+    fn color_for_key(&self, key: &str) -> Option<Hsla> {
+        parse_color(self.style.get(key)?.as_str()?)
+    }
+
+    fn is_modified(&self, entry: &ColorEntry) -> bool {
+        Self::color_value(&self.style, entry) != Self::color_value(&self.base_style, entry)
+    }
+
+    fn set_color_value(&mut self, entry_ix: usize, value: Value, cx: &mut ViewContext<Self>) {
+        let entry = &self.entries[entry_ix];
+        let slot = match entry.group {
+            ColorGroup::Syntax => self
+                .style
+                .entry("syntax")
+                .or_insert_with(|| Value::Object(Map::new()))
+                .as_object_mut()
+                .and_then(|syntax| {
+                    syntax
+                        .entry(entry.key.clone())
+                        .or_insert_with(|| Value::Object(Map::new()))
+                        .as_object_mut()
+                })
+                .map(|style| style.entry("color").or_insert(Value::Null)),
+            ColorGroup::Theme | ColorGroup::Status => {
+                Some(self.style.entry(entry.key.clone()).or_insert(Value::Null))
+            }
+        };
+        if let Some(slot) = slot {
+            *slot = value;
+        }
+        self.update_preview(cx);
+    }
+
+    fn set_color(&mut self, entry_ix: usize, color: Hsla, cx: &mut ViewContext<Self>) {
+        self.set_color_value(entry_ix, Value::String(color_to_hex(color)), cx);
+    }
+
+    fn reset_color(&mut self, entry_ix: usize, cx: &mut ViewContext<Self>) {
+        let value = Self::color_value(&self.base_style, &self.entries[entry_ix])
+            .map_or(Value::Null, |value| Value::String(value.to_string()));
+        self.set_color_value(entry_ix, value, cx);
+    }
+
+    fn edited_style(&self) -> Option<ThemeStyleContent> {
+        serde_json::from_value(Value::Object(self.style.clone())).log_err()
+    }
+
+    fn update_preview(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(style) = self.edited_style() else {
+            return;
+        };
+
+        let mut theme = (*self.base_theme).clone();
+        theme.styles.colors.refine(&style.theme_colors_refinement());
+        theme
+            .styles
+            .status
+            .refine(&style.status_colors_refinement());
+        // The edited style has every syntax style of the theme, in the same order.
+        theme.styles.syntax =
+            SyntaxTheme::merge(Arc::new(SyntaxTheme::default()), style.syntax_overrides());
+
+        let theme = Arc::new(theme);
+        self.preview = Some(theme.clone());
+        set_active_theme(theme, cx);
+        cx.notify();
+    }
+
+    fn contrast_checks(&self, entry: &ColorEntry) -> Vec<ContrastCheck> {
+        let Some(foreground) = self.color(entry) else {
+            return Vec::new();
+        };
+
+        let pairs = match entry.group {
+            ColorGroup::Syntax => vec![("editor.background", MINIMUM_TEXT_CONTRAST)],
+            ColorGroup::Theme | ColorGroup::Status => CONTRAST_PAIRS
+                .iter()
+                .filter(|pair| pair.foreground == entry.key)
+                .map(|pair| (pair.background, pair.minimum))
+                .collect(),
+        };
+
+        pairs
+            .into_iter()
+            .filter_map(|(background, minimum)| {
+                Some(ContrastCheck {
+                    background,
+                    ratio: contrast_ratio(foreground, self.color_for_key(background)?),
+                    minimum,
+                })
+            })
+            .collect()
+    }
+
+    fn contrast_failure_count(&self) -> usize {
+        self.entries
+            .iter()
+            .map(|entry| {
+                self.contrast_checks(entry)
+                    .iter()
+                    .filter(|check| !check.passes())
+                    .count()
+            })
+            .sum()
+    }
+
+    fn export(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(style) = self.edited_style() else {
+            return;
+        };
+        let name = self.name_editor.read(cx).text(cx).trim().to_string();
+        let name = if name.is_empty() {
+            self.base_theme.name.to_string()
+        } else {
+            name
+        };
+
+        let family = ThemeFamilyContent {
+            name: name.clone(),
+            author: String::new(),
+            themes: vec![ThemeContent {
+                name,
+                appearance: self.appearance,
+                style,
+            }],
+        };
+        let Some(mut json) = serde_json::to_value(&family).log_err() else {
+            return;
+        };
+        remove_nulls(&mut json);
+        let Some(text) = serde_json::to_string_pretty(&json).log_err() else {
+            return;
+        };
+
+        let fs = self.fs.clone();
+        let path = cx.prompt_for_new_path(paths::themes_dir());
+        cx.spawn(|_, _| async move {
+            let Some(path) = path.await? else {
+                return anyhow::Ok(());
+            };
+            fs.atomic_write(path, text).await
+        })
+        .detach_and_log_err(cx);
+    }
+
+    fn render_row(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        match self.rows.get(ix) {
+            Some(Row::Group(group)) => h_flex()
+                .pt_6()
+                .pb_1()
+                .child(Headline::new(group.label()).size(HeadlineSize::Small))
+                .into_any_element(),
+            Some(Row::Color(entry_ix)) => self.render_color(*entry_ix, cx),
+            None => div().into_any_element(),
+        }
+    }
+
+    fn render_color(&mut self, entry_ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
+        let entry = &self.entries[entry_ix];
+        let color = self.color(entry);
+        let value = Self::color_value(&self.style, entry).map(str::to_string);
+        let modified = self.is_modified(entry);
+        let checks = self.contrast_checks(entry);
+        let description = entry.description.clone();
+        let key = entry.key.clone();
+
+        let editor = cx.view().downgrade();
+        let initial_color = color.unwrap_or(cx.theme().colors().text);
+        let swatch = PopoverMenu::new(("color-picker", entry_ix))
+            .menu(move |cx| {
+                let editor = editor.clone();
+                Some(cx.new_view(|cx| {
+                    ColorPicker::new(
+                        initial_color,
+                        move |color, cx| {
+                            editor
+                                .update(cx, |editor, cx| editor.set_color(entry_ix, color, cx))
+                                .ok();
+                        },
+                        cx,
+                    )
+                }))
+            })
+            .trigger(
+                ButtonLike::new(("color-swatch", entry_ix)).child(
+                    div()
+                        .size_6()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(cx.theme().colors().border)
+                        .when_some(color, |this, color| this.bg(color)),
+                ),
+            );
+
+        h_flex()
+            .id(("color", entry_ix))
+            .w_full()
+            .py_2()
+            .gap_4()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(swatch)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .min_w_0()
+                    .gap_0p5()
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new(key))
+                            .child(
+                                Label::new(value.unwrap_or_else(|| "unset".to_string()))
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .when(modified, |this| {
+                                this.child(
+                                    IconButton::new(("reset-color", entry_ix), IconName::RotateCcw)
+                                        .icon_size(IconSize::Small)
+                                        .tooltip(|cx| {
+                                            Tooltip::text("Reset to the original color", cx)
+                                        })
+                                        .on_click(cx.listener(move |this, _, cx| {
+                                            this.reset_color(entry_ix, cx)
+                                        })),
+                                )
+                            }),
+                    )
+                    .children(description.map(|description| {
+                        Label::new(description)
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                    })),
+            )
+            .child(
+                h_flex().flex_none().gap_2().children(
+                    checks
+                        .into_iter()
+                        .enumerate()
+                        .map(|(ix, check)| render_contrast_check(entry_ix, ix, check)),
+                ),
+            )
+            .into_any_element()
+    }
+
+    fn render_input(&self, input: &View<Editor>, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let settings = ThemeSettings::get_global(cx);
+        let text_style = TextStyle {
+            color: cx.theme().colors().text,
+            font_family: settings.ui_font.family.clone(),
+            font_features: settings.ui_font.features.clone(),
+            font_size: rems(0.875).into(),
+            font_weight: settings.ui_font.weight,
+            font_style: FontStyle::Normal,
+            line_height: relative(1.3),
+            background_color: None,
+            underline: None,
+            strikethrough: None,
+            white_space: WhiteSpace::Normal,
+        };
+
+        EditorElement::new(
+            input,
+            EditorStyle {
+                background: cx.theme().colors().editor_background,
+                local_player: cx.theme().players().local(),
+                text: text_style,
+                ..Default::default()
+            },
+        )
+    }
+}
+
+fn render_contrast_check(entry_ix: usize, ix: usize, check: ContrastCheck) -> impl IntoElement {
+    let passes = check.passes();
+    let tooltip = format!(
+        "Contrast with {} is {:.2}:1, {} the {:.1}:1 required by WCAG AA",
+        check.background,
+        check.ratio,
+        if passes { "meeting" } else { "below" },
+        check.minimum,
+    );
+
+    h_flex()
+        .id(("contrast", entry_ix * CONTRAST_PAIRS.len() + ix))
+        .gap_1()
+        .when(!passes, |this| {
+            this.child(
+                Icon::new(IconName::ExclamationTriangle)
+                    .size(IconSize::Small)
+                    .color(Color::Warning),
+            )
+        })
+        .child(
+            Label::new(format!("{:.1}:1", check.ratio))
+                .size(LabelSize::Small)
+                .color(if passes { Color::Muted } else { Color::Warning }),
+        )
+        .tooltip(move |cx| Tooltip::text(tooltip.clone(), cx))
+}
+
+/// Returns the colors of a group, in the order they are serialized in theme files.
+fn color_entries(
+    group: ColorGroup,
+    content: &impl Serialize,
+    schema: Option<Box<schemars::schema::ObjectValidation>>,
+) -> Vec<ColorEntry> {
+    let Ok(Value::Object(colors)) = serde_json::to_value(content) else {
+        return Vec::new();
+    };
+
+    colors
+        .keys()
+        .map(|key| {
+            let description = schema
+                .as_ref()
+                .and_then(|schema| schema.properties.get(key))
+                .and_then(|schema| match schema {
+                    Schema::Object(schema) => schema.metadata.as_ref()?.description.clone(),
+                    Schema::Bool(_) => None,
+                });
+            ColorEntry {
+                group,
+                key: key.clone(),
+                description: description.map(Into::into),
+            }
+        })
+        .collect()
+}
+
+fn parse_color(color: &str) -> Option<Hsla> {
+    Rgba::try_from(color).ok().map(Into::into)
+}
+
+fn set_active_theme(theme: Arc<Theme>, cx: &mut AppContext) {
+    SettingsStore::update_global(cx, |store, cx| {
+        let mut theme_settings = store.get::<ThemeSettings>(None).clone();
+        theme_settings.active_theme = theme;
+        store.override_global(theme_settings);
+        cx.refresh();
+    });
+}
+
+/// Removes the `null` values from objects, which theme files leave out.
+fn remove_nulls(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            object.retain(|_, value| !value.is_null());
+            object.values_mut().for_each(remove_nulls);
+        }
+        Value::Array(array) => array.iter_mut().for_each(remove_nulls),
+        _ => {}
+    }
+}
+
+impl Render for ThemeEditor {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let failure_count = self.contrast_failure_count();
+
+        v_flex()
+            .key_context("ThemeEditor")
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .gap_4()
+                    .p_4()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_2()
+                            .justify_between()
+                            .child(Headline::new("Theme Editor").size(HeadlineSize::XLarge))
+                            .child(
+                                Button::new("export-theme", "Export Theme…")
+                                    .style(ButtonStyle::Filled)
+                                    .size(ButtonSize::Large)
+                                    .on_click(cx.listener(|this, _, cx| this.export(cx))),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(Label::new("Name"))
+                            .child(
+                                div()
+                                    .w(rems(20.))
+                                    .px_2()
+                                    .py_1()
+                                    .border_1()
+                                    .border_color(cx.theme().colors().border)
+                                    .rounded_md()
+                                    .child(self.render_input(&self.name_editor, cx)),
+                            )
+                            .child(
+                                Label::new(format!("Based on {}", self.base_theme.name))
+                                    .color(Color::Muted),
+                            ),
+                    )
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .gap_4()
+                            .child(
+                                h_flex()
+                                    .flex_1()
+                                    .px_2()
+                                    .py_1()
+                                    .gap_2()
+                                    .border_1()
+                                    .border_color(cx.theme().colors().border)
+                                    .rounded_lg()
+                                    .child(Icon::new(IconName::MagnifyingGlass))
+                                    .child(self.render_input(&self.query_editor, cx)),
+                            )
+                            .child(
+                                h_flex()
+                                    .gap_2()
+                                    .child(
+                                        Checkbox::new(
+                                            "contrast-failures-only",
+                                            if self.failures_only {
+                                                Selection::Selected
+                                            } else {
+                                                Selection::Unselected
+                                            },
+                                        )
+                                        .on_click(
+                                            cx.listener(|this, selection: &Selection, cx| {
+                                                this.failures_only =
+                                                    *selection == Selection::Selected;
+                                                this.update_matches(cx);
+                                            }),
+                                        ),
+                                    )
+                                    .child(Label::new(format!(
+                                        "Only contrast failures ({failure_count})"
+                                    ))),
+                            ),
+                    ),
+            )
+            .child(v_flex().px_4().size_full().map(|this| {
+                if self.rows.is_empty() {
+                    this.py_4()
+                        .child(Label::new("No colors that match your search."))
+                } else {
+                    this.child(list(self.list_state.clone()).size_full())
+                }
+            }))
+    }
+}
+
+impl EventEmitter<ItemEvent> for ThemeEditor {}
+
+impl FocusableView for ThemeEditor {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.query_editor.focus_handle(cx)
+    }
+}
+
+impl Item for ThemeEditor {
+    type Event = ItemEvent;
+
+    fn tab_content(&self, params: TabContentParams, _: &WindowContext) -> AnyElement {
+        Label::new("Theme Editor")
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("theme editor")
+    }
+
+    fn show_toolbar(&self) -> bool {
+        false
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        _: &mut ViewContext<Self>,
+    ) -> Option<View<Self>> {
+        None
+    }
+
+    fn to_item_events(event: &Self::Event, mut f: impl FnMut(ItemEvent)) {
+        f(*event)
+    }
+}
@@ -0,0 +1,6 @@
+mod converter;
+mod scheme;
+mod syntax;
+
+pub use converter::*;
+pub use scheme::*;
@@ -0,0 +1,329 @@
+use anyhow::Result;
+use indexmap::IndexMap;
+use strum::IntoEnumIterator;
+use theme::{
+    FontStyleContent, FontWeightContent, HighlightStyleContent, PlayerColorContent,
+    StatusColorsContent, ThemeColorsContent, ThemeContent, ThemeStyleContent,
+};
+
+use crate::jetbrains::JetBrainsScheme;
+use crate::vscode::ZedSyntaxToken;
+use crate::{ImportReport, ThemeMetadata};
+
+/// The keys of the editor colors that are mapped onto Zed theme colors.
+const MAPPED_COLORS: &[&str] = &[
+    "ADDED_LINES_COLOR",
+    "CARET_COLOR",
+    "CARET_ROW_COLOR",
+    "CONSOLE_BACKGROUND_KEY",
+    "DELETED_LINES_COLOR",
+    "GUTTER_BACKGROUND",
+    "INDENT_GUIDE",
+    "LINE_NUMBERS_COLOR",
+    "LINE_NUMBER_ON_CARET_ROW_COLOR",
+    "MODIFIED_LINES_COLOR",
+    "RIGHT_MARGIN_COLOR",
+    "SELECTED_INDENT_GUIDE",
+    "SELECTION_BACKGROUND",
+    "WHITESPACES",
+];
+
+/// The keys of the text attributes that are mapped onto Zed theme colors, rather than
+/// syntax styles.
+const MAPPED_ATTRIBUTES: &[&str] = &[
+    "CONSOLE_BLACK_OUTPUT",
+    "CONSOLE_BLUE_BRIGHT_OUTPUT",
+    "CONSOLE_BLUE_OUTPUT",
+    "CONSOLE_CYAN_BRIGHT_OUTPUT",
+    "CONSOLE_CYAN_OUTPUT",
+    "CONSOLE_DARKGRAY_OUTPUT",
+    "CONSOLE_GRAY_OUTPUT",
+    "CONSOLE_GREEN_BRIGHT_OUTPUT",
+    "CONSOLE_GREEN_OUTPUT",
+    "CONSOLE_MAGENTA_BRIGHT_OUTPUT",
+    "CONSOLE_MAGENTA_OUTPUT",
+    "CONSOLE_NORMAL_OUTPUT",
+    "CONSOLE_RED_BRIGHT_OUTPUT",
+    "CONSOLE_RED_OUTPUT",
+    "CONSOLE_WHITE_OUTPUT",
+    "CONSOLE_YELLOW_BRIGHT_OUTPUT",
+    "CONSOLE_YELLOW_OUTPUT",
+    "ERRORS_ATTRIBUTES",
+    "HYPERLINK_ATTRIBUTES",
+    "IDENTIFIER_UNDER_CARET_ATTRIBUTES",
+    "INFO_ATTRIBUTES",
+    "TEXT",
+    "TEXT_SEARCH_RESULT_ATTRIBUTES",
+    "WARNING_ATTRIBUTES",
+    "WRITE_IDENTIFIER_UNDER_CARET_ATTRIBUTES",
+];
+
+pub struct JetBrainsSchemeConverter {
+    scheme: JetBrainsScheme,
+    theme_metadata: ThemeMetadata,
+}
+
+impl JetBrainsSchemeConverter {
+    pub fn new(scheme: JetBrainsScheme, theme_metadata: ThemeMetadata) -> Self {
+        Self {
+            scheme,
+            theme_metadata,
+        }
+    }
+
+    pub fn convert(self) -> Result<(ThemeContent, ImportReport)> {
+        let appearance = self.theme_metadata.appearance.into();
+
+        let status_colors = self.convert_status_colors();
+        let theme_colors = self.convert_theme_colors();
+        let players = self.convert_player_colors();
+        let (syntax_theme, syntax_keys) = self.convert_syntax_theme();
+
+        let unmapped_colors = self
+            .scheme
+            .colors
+            .keys()
+            .filter(|key| !MAPPED_COLORS.contains(&key.as_str()));
+        let unmapped_attributes = self
+            .scheme
+            .attributes
+            .iter()
+            // Attributes that only inherit from others don't define any colors of their own.
+            .filter(|(_, attributes)| {
+                attributes.foreground.is_some()
+                    || attributes.background.is_some()
+                    || attributes.effect_color.is_some()
+            })
+            .map(|(key, _)| key)
+            .filter(|key| {
+                !MAPPED_ATTRIBUTES.contains(&key.as_str()) && !syntax_keys.contains(&key.as_str())
+            });
+        let unmapped_keys = unmapped_colors
+            .chain(unmapped_attributes)
+            .cloned()
+            .collect();
+
+        let theme = ThemeContent {
+            name: self.theme_metadata.name,
+            appearance,
+            style: ThemeStyleContent {
+                window_background_appearance: Some(theme::WindowBackgroundContent::Opaque),
+                accents: Vec::new(),
+                colors: theme_colors,
+                status: status_colors,
+                players,
+                syntax: syntax_theme,
+            },
+        };
+
+        Ok((
+            theme,
+            ImportReport {
+                unmapped_keys,
+                ..Default::default()
+            },
+        ))
+    }
+
+    fn color(&self, key: &str) -> Option<String> {
+        self.scheme.colors.get(key).cloned()
+    }
+
+    fn foreground(&self, key: &str) -> Option<String> {
+        self.scheme.resolved_attributes(key)?.foreground
+    }
+
+    fn background(&self, key: &str) -> Option<String> {
+        self.scheme.resolved_attributes(key)?.background
+    }
+
+    fn effect_color(&self, key: &str) -> Option<String> {
+        let attributes = self.scheme.resolved_attributes(key)?;
+        attributes.effect_color.or(attributes.foreground)
+    }
+
+    fn convert_status_colors(&self) -> StatusColorsContent {
+        StatusColorsContent {
+            created: self.color("ADDED_LINES_COLOR"),
+            modified: self.color("MODIFIED_LINES_COLOR"),
+            deleted: self.color("DELETED_LINES_COLOR"),
+            error: self.effect_color("ERRORS_ATTRIBUTES"),
+            warning: self.effect_color("WARNING_ATTRIBUTES"),
+            info: self.effect_color("INFO_ATTRIBUTES"),
+            ..Default::default()
+        }
+    }
+
+    fn convert_theme_colors(&self) -> ThemeColorsContent {
+        let background = self.background("TEXT");
+        let foreground = self.foreground("TEXT");
+        let right_margin = self.color("RIGHT_MARGIN_COLOR");
+
+        ThemeColorsContent {
+            background: background.clone(),
+            text: foreground.clone(),
+            editor_background: background.clone(),
+            editor_foreground: foreground,
+            editor_gutter_background: self.color("GUTTER_BACKGROUND").or(background),
+            editor_active_line_background: self.color("CARET_ROW_COLOR"),
+            editor_line_number: self.color("LINE_NUMBERS_COLOR"),
+            editor_active_line_number: self.color("LINE_NUMBER_ON_CARET_ROW_COLOR"),
+            editor_invisible: self.color("WHITESPACES"),
+            editor_indent_guide: self.color("INDENT_GUIDE"),
+            editor_indent_guide_active: self.color("SELECTED_INDENT_GUIDE"),
+            editor_wrap_guide: right_margin.clone(),
+            editor_active_wrap_guide: right_margin,
+            editor_document_highlight_read_background: self
+                .background("IDENTIFIER_UNDER_CARET_ATTRIBUTES"),
+            editor_document_highlight_write_background: self
+                .background("WRITE_IDENTIFIER_UNDER_CARET_ATTRIBUTES"),
+            search_match_background: self.background("TEXT_SEARCH_RESULT_ATTRIBUTES"),
+            link_text_hover: self.foreground("HYPERLINK_ATTRIBUTES"),
+            terminal_background: self.color("CONSOLE_BACKGROUND_KEY"),
+            terminal_foreground: self.foreground("CONSOLE_NORMAL_OUTPUT"),
+            terminal_ansi_black: self.foreground("CONSOLE_BLACK_OUTPUT"),
+            terminal_ansi_bright_black: self.foreground("CONSOLE_DARKGRAY_OUTPUT"),
+            terminal_ansi_red: self.foreground("CONSOLE_RED_OUTPUT"),
+            terminal_ansi_bright_red: self.foreground("CONSOLE_RED_BRIGHT_OUTPUT"),
+            terminal_ansi_green: self.foreground("CONSOLE_GREEN_OUTPUT"),
+            terminal_ansi_bright_green: self.foreground("CONSOLE_GREEN_BRIGHT_OUTPUT"),
+            terminal_ansi_yellow: self.foreground("CONSOLE_YELLOW_OUTPUT"),
+            terminal_ansi_bright_yellow: self.foreground("CONSOLE_YELLOW_BRIGHT_OUTPUT"),
+            terminal_ansi_blue: self.foreground("CONSOLE_BLUE_OUTPUT"),
+            terminal_ansi_bright_blue: self.foreground("CONSOLE_BLUE_BRIGHT_OUTPUT"),
+            terminal_ansi_magenta: self.foreground("CONSOLE_MAGENTA_OUTPUT"),
+            terminal_ansi_bright_magenta: self.foreground("CONSOLE_MAGENTA_BRIGHT_OUTPUT"),
+            terminal_ansi_cyan: self.foreground("CONSOLE_CYAN_OUTPUT"),
+            terminal_ansi_bright_cyan: self.foreground("CONSOLE_CYAN_BRIGHT_OUTPUT"),
+            terminal_ansi_white: self.foreground("CONSOLE_GRAY_OUTPUT"),
+            terminal_ansi_bright_white: self.foreground("CONSOLE_WHITE_OUTPUT"),
+            ..Default::default()
+        }
+    }
+
+    fn convert_player_colors(&self) -> Vec<PlayerColorContent> {
+        let caret = self.color("CARET_COLOR");
+        let selection = self.color("SELECTION_BACKGROUND");
+        if caret.is_none() && selection.is_none() {
+            return Vec::new();
+        }
+
+        vec![PlayerColorContent {
+            cursor: caret.clone(),
+            background: caret,
+            selection,
+        }]
+    }
+
+    /// Returns the syntax styles along with the keys of the attributes they were derived from.
+    fn convert_syntax_theme(&self) -> (IndexMap<String, HighlightStyleContent>, Vec<&'static str>) {
+        let mut highlight_styles = IndexMap::new();
+        let mut used_keys = Vec::new();
+
+        for syntax_token in ZedSyntaxToken::iter() {
+            let best_match = std::iter::once(&syntax_token)
+                .chain(syntax_token.fallbacks())
+                .flat_map(|token| token.to_jetbrains())
+                .find_map(|key| {
+                    let attributes = self.scheme.resolved_attributes(key)?;
+                    attributes
+                        .foreground
+                        .is_some()
+                        .then_some((*key, attributes))
+                });
+
+            let Some((key, attributes)) = best_match else {
+                log::warn!("No matching text attributes found for '{syntax_token}'");
+                continue;
+            };
+
+            log::info!("Matched '{syntax_token}' to '{key}'");
+
+            used_keys.push(key);
+            highlight_styles.insert(
+                syntax_token.to_string(),
+                HighlightStyleContent {
+                    color: attributes.foreground,
+                    background_color: attributes.background,
+                    font_style: attributes
+                        .font_type
+                        .italic
+                        .then_some(FontStyleContent::Italic),
+                    font_weight: attributes.font_type.bold.then_some(FontWeightContent::Bold),
+                },
+            );
+        }
+
+        (highlight_styles, used_keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ThemeAppearanceJson;
+
+    #[test]
+    fn test_convert_jetbrains_scheme() {
+        let scheme = JetBrainsScheme::parse(
+            r#"<scheme name="Test" version="142" parent_scheme="Darcula">
+                <colors>
+                    <option name="CARET_ROW_COLOR" value="323232" />
+                    <option name="TEARLINE_COLOR" value="555555" />
+                </colors>
+                <attributes>
+                    <option name="TEXT">
+                        <value>
+                            <option name="FOREGROUND" value="a9b7c6" />
+                            <option name="BACKGROUND" value="2b2b2b" />
+                        </value>
+                    </option>
+                    <option name="DEFAULT_BLOCK_COMMENT">
+                        <value>
+                            <option name="FOREGROUND" value="808080" />
+                            <option name="FONT_TYPE" value="2" />
+                        </value>
+                    </option>
+                    <option name="DEFAULT_DOC_COMMENT" baseAttributes="DEFAULT_BLOCK_COMMENT" />
+                    <option name="TODO_DEFAULT_ATTRIBUTES">
+                        <value>
+                            <option name="FOREGROUND" value="a8c023" />
+                        </value>
+                    </option>
+                </attributes>
+            </scheme>"#,
+        )
+        .unwrap();
+        let metadata = ThemeMetadata {
+            name: "Test".to_string(),
+            file_name: String::new(),
+            appearance: ThemeAppearanceJson::Dark,
+        };
+        let (theme, report) = JetBrainsSchemeConverter::new(scheme, metadata)
+            .convert()
+            .unwrap();
+
+        assert_eq!(
+            theme.style.colors.editor_background.as_deref(),
+            Some("#2b2b2b")
+        );
+        assert_eq!(
+            theme.style.colors.editor_active_line_background.as_deref(),
+            Some("#323232")
+        );
+        let comment = &theme.style.syntax["comment"];
+        assert_eq!(comment.color.as_deref(), Some("#808080"));
+        assert!(matches!(comment.font_style, Some(FontStyleContent::Italic)));
+        assert_eq!(
+            theme.style.syntax["comment.doc"].color.as_deref(),
+            Some("#808080")
+        );
+        assert_eq!(
+            report.unmapped_keys,
+            vec![
+                "TEARLINE_COLOR".to_string(),
+                "TODO_DEFAULT_ATTRIBUTES".to_string()
+            ]
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use anyhow::{anyhow, Context, Result};
+use indexmap::IndexMap;
+
+/// A JetBrains editor color scheme, as exported to `.icls` files.
+#[derive(Debug, Default)]
+pub struct JetBrainsScheme {
+    pub name: String,
+    /// The built-in scheme this scheme is derived from, such as `Darcula`.
+    pub parent_scheme: Option<String>,
+    /// The editor colors, by key, as `#rrggbb` colors.
+    pub colors: IndexMap<String, String>,
+    /// The text attributes, by key.
+    pub attributes: IndexMap<String, JetBrainsTextAttributes>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct JetBrainsTextAttributes {
+    pub foreground: Option<String>,
+    pub background: Option<String>,
+    /// The color of the underline, wave or border drawn around the text.
+    pub effect_color: Option<String>,
+    pub font_type: JetBrainsFontType,
+    /// The key of the attributes these attributes inherit from.
+    pub base_attributes: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct JetBrainsFontType {
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl JetBrainsScheme {
+    pub fn parse(xml: &str) -> Result<Self> {
+        let document = roxmltree::Document::parse(xml).context("failed to parse scheme XML")?;
+        let root = document.root_element();
+        if !root.has_tag_name("scheme") {
+            return Err(anyhow!(
+                "expected a <scheme> element, found <{}>",
+                root.tag_name().name()
+            ));
+        }
+
+        let mut scheme = JetBrainsScheme {
+            name: root.attribute("name").unwrap_or_default().to_string(),
+            parent_scheme: root.attribute("parent_scheme").map(ToString::to_string),
+            ..Default::default()
+        };
+
+        for section in root.children().filter(|node| node.is_element()) {
+            match section.tag_name().name() {
+                "colors" => {
+                    for option in options(section) {
+                        let (Some(name), Some(color)) = (
+                            option.attribute("name"),
+                            option.attribute("value").and_then(parse_color),
+                        ) else {
+                            continue;
+                        };
+                        scheme.colors.insert(name.to_string(), color);
+                    }
+                }
+                "attributes" => {
+                    for option in options(section) {
+                        let Some(name) = option.attribute("name") else {
+                            continue;
+                        };
+
+                        let mut attributes = JetBrainsTextAttributes {
+                            base_attributes: option
+                                .attribute("baseAttributes")
+                                .map(ToString::to_string),
+                            ..Default::default()
+                        };
+                        let values = option
+                            .children()
+                            .filter(|node| node.has_tag_name("value"))
+                            .flat_map(options);
+                        for value in values {
+                            let Some(value_text) = value.attribute("value") else {
+                                continue;
+                            };
+                            match value.attribute("name") {
+                                Some("FOREGROUND") => {
+                                    attributes.foreground = parse_color(value_text)
+                                }
+                                Some("BACKGROUND") => {
+                                    attributes.background = parse_color(value_text)
+                                }
+                                Some("EFFECT_COLOR") => {
+                                    attributes.effect_color = parse_color(value_text)
+                                }
+                                Some("FONT_TYPE") => {
+                                    let font_type = value_text.parse::<u32>().unwrap_or(0);
+                                    attributes.font_type = JetBrainsFontType {
+                                        bold: font_type & 1 != 0,
+                                        italic: font_type & 2 != 0,
+                                    };
+                                }
+                                _ => {}
+                            }
+                        }
+                        scheme.attributes.insert(name.to_string(), attributes);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(scheme)
+    }
+
+    /// Returns the attributes with the given key, resolving the attributes they inherit from
+    /// within this scheme.
+    pub fn resolved_attributes(&self, key: &str) -> Option<JetBrainsTextAttributes> {
+        let mut attributes = self.attributes.get(key)?.clone();
+        let mut base_key = attributes.base_attributes.clone();
+        // Guard against inheritance cycles in malformed schemes.
+        for _ in 0..self.attributes.len() {
+            let Some(base) = base_key.and_then(|key| self.attributes.get(&key)) else {
+                break;
+            };
+            if attributes.foreground.is_none() && attributes.background.is_none() {
+                attributes.foreground = base.foreground.clone();
+                attributes.background = base.background.clone();
+                attributes.font_type = base.font_type;
+            }
+            attributes.effect_color = attributes.effect_color.or(base.effect_color.clone());
+            base_key = base.base_attributes.clone();
+        }
+        Some(attributes)
+    }
+}
+
+fn options<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children().filter(|node| node.has_tag_name("option"))
+}
+
+/// Parses a color as written in schemes, which omit the leading `#` and any leading zeros.
+fn parse_color(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || value.len() > 8 || !value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let width = if value.len() > 6 { 8 } else { 6 };
+    Some(format!("#{value:0>width$}").to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scheme() {
+        let scheme = JetBrainsScheme::parse(
+            r#"<scheme name="Test" version="142" parent_scheme="Darcula">
+                <colors>
+                    <option name="CARET_ROW_COLOR" value="323232" />
+                    <option name="LINE_NUMBERS_COLOR" value="0" />
+                </colors>
+                <attributes>
+                    <option name="DEFAULT_KEYWORD">
+                        <value>
+                            <option name="FOREGROUND" value="cc7832" />
+                            <option name="FONT_TYPE" value="1" />
+                        </value>
+                    </option>
+                    <option name="KOTLIN_KEYWORD" baseAttributes="DEFAULT_KEYWORD" />
+                </attributes>
+            </scheme>"#,
+        )
+        .unwrap();
+
+        assert_eq!(scheme.name, "Test");
+        assert_eq!(scheme.parent_scheme.as_deref(), Some("Darcula"));
+        assert_eq!(scheme.colors["CARET_ROW_COLOR"], "#323232");
+        assert_eq!(scheme.colors["LINE_NUMBERS_COLOR"], "#000000");
+
+        let keyword = scheme.resolved_attributes("KOTLIN_KEYWORD").unwrap();
+        assert_eq!(keyword.foreground.as_deref(), Some("#cc7832"));
+        assert_eq!(
+            keyword.font_type,
+            JetBrainsFontType {
+                bold: true,
+                italic: false
+            }
+        );
+    }
+}
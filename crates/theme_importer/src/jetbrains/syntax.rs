@@ -0,0 +1,55 @@
+use crate::vscode::ZedSyntaxToken;
+
+impl ZedSyntaxToken {
+    /// Returns the keys of the JetBrains text attributes that correspond to this token, in
+    /// order of preference.
+    pub fn to_jetbrains(&self) -> &'static [&'static str] {
+        match self {
+            ZedSyntaxToken::Attribute => &["DEFAULT_METADATA", "DEFAULT_ATTRIBUTE"],
+            ZedSyntaxToken::Boolean => &["DEFAULT_KEYWORD"],
+            ZedSyntaxToken::Comment => &["DEFAULT_LINE_COMMENT", "DEFAULT_BLOCK_COMMENT"],
+            ZedSyntaxToken::CommentDoc => &["DEFAULT_DOC_COMMENT"],
+            ZedSyntaxToken::Constant => &["DEFAULT_CONSTANT"],
+            ZedSyntaxToken::Constructor => &["DEFAULT_FUNCTION_CALL"],
+            ZedSyntaxToken::Embedded => &[],
+            ZedSyntaxToken::Emphasis => &["MARKDOWN_ITALIC"],
+            ZedSyntaxToken::EmphasisStrong => &["MARKDOWN_BOLD"],
+            ZedSyntaxToken::Enum => &["DEFAULT_CLASS_NAME"],
+            ZedSyntaxToken::Function => &["DEFAULT_FUNCTION_DECLARATION", "DEFAULT_FUNCTION_CALL"],
+            ZedSyntaxToken::Hint => &[],
+            ZedSyntaxToken::Keyword => &["DEFAULT_KEYWORD"],
+            ZedSyntaxToken::Label => &["DEFAULT_LABEL"],
+            ZedSyntaxToken::LinkText => &["MARKDOWN_LINK_TEXT"],
+            ZedSyntaxToken::LinkUri => &["MARKDOWN_LINK_DESTINATION"],
+            ZedSyntaxToken::Number => &["DEFAULT_NUMBER"],
+            ZedSyntaxToken::Operator => &["DEFAULT_OPERATION_SIGN"],
+            ZedSyntaxToken::Predictive => &[],
+            ZedSyntaxToken::Preproc => &["DEFAULT_METADATA"],
+            ZedSyntaxToken::Primary => &[],
+            ZedSyntaxToken::Property => &["DEFAULT_INSTANCE_FIELD", "DEFAULT_STATIC_FIELD"],
+            ZedSyntaxToken::Punctuation => &["DEFAULT_DOT", "DEFAULT_COMMA", "DEFAULT_SEMICOLON"],
+            ZedSyntaxToken::PunctuationBracket => {
+                &["DEFAULT_PARENTHS", "DEFAULT_BRACES", "DEFAULT_BRACKETS"]
+            }
+            ZedSyntaxToken::PunctuationDelimiter => &["DEFAULT_COMMA", "DEFAULT_SEMICOLON"],
+            ZedSyntaxToken::PunctuationListMarker => &["MARKDOWN_LIST_ITEM"],
+            ZedSyntaxToken::PunctuationSpecial => &[],
+            ZedSyntaxToken::String => &["DEFAULT_STRING"],
+            ZedSyntaxToken::StringEscape => &["DEFAULT_VALID_STRING_ESCAPE"],
+            ZedSyntaxToken::StringRegex => &["REGEXP.CHAR_CLASS"],
+            ZedSyntaxToken::StringSpecial => &[],
+            ZedSyntaxToken::StringSpecialSymbol => &[],
+            ZedSyntaxToken::Tag => &["DEFAULT_TAG", "HTML_TAG_NAME", "XML_TAG_NAME"],
+            ZedSyntaxToken::TextLiteral => &["MARKDOWN_CODE_SPAN"],
+            ZedSyntaxToken::Title => &["MARKDOWN_HEADER_LEVEL_1"],
+            ZedSyntaxToken::Type => &[
+                "DEFAULT_CLASS_NAME",
+                "DEFAULT_CLASS_REFERENCE",
+                "DEFAULT_INTERFACE_NAME",
+            ],
+            ZedSyntaxToken::Variable => &["DEFAULT_LOCAL_VARIABLE", "DEFAULT_IDENTIFIER"],
+            ZedSyntaxToken::VariableSpecial => &["DEFAULT_PREDEFINED_SYMBOL"],
+            ZedSyntaxToken::Variant => &[],
+        }
+    }
+}
@@ -1,5 +1,8 @@
 mod assets;
 mod color;
+mod jetbrains;
+mod report;
+mod tmtheme;
 mod vscode;
 
 use std::fs::File;
@@ -16,6 +19,10 @@ use simplelog::ColorChoice;
 use simplelog::{TermLogger, TerminalMode};
 use theme::{Appearance, AppearanceContent, ThemeFamilyContent};
 
+use crate::color::try_parse_color;
+use crate::jetbrains::{JetBrainsScheme, JetBrainsSchemeConverter};
+use crate::report::ImportReport;
+use crate::tmtheme::{TmTheme, TmThemeConverter};
 use crate::vscode::VsCodeTheme;
 use crate::vscode::VsCodeThemeConverter;
 
@@ -41,6 +48,17 @@ pub enum ThemeAppearanceJson {
     Dark,
 }
 
+impl ThemeAppearanceJson {
+    /// Infers the appearance of a theme from its background color, for theme formats that
+    /// don't specify it.
+    fn from_background(background: Option<&str>) -> Self {
+        match background.and_then(|background| try_parse_color(background).ok()) {
+            Some(background) if background.l > 0.5 => Self::Light,
+            _ => Self::Dark,
+        }
+    }
+}
+
 impl From<ThemeAppearanceJson> for AppearanceContent {
     fn from(value: ThemeAppearanceJson) -> Self {
         match value {
@@ -70,6 +88,9 @@ pub struct ThemeMetadata {
 #[command(author, version, about, long_about = None)]
 struct Args {
     /// The path to the theme to import.
+    ///
+    /// VS Code JSON themes, TextMate `.tmTheme` themes, and JetBrains `.icls` color
+    /// schemes are supported.
     theme_path: PathBuf,
 
     /// Whether to warn when values are missing from the theme.
@@ -136,18 +157,60 @@ fn main() -> Result<()> {
         }
     };
 
-    let vscode_theme: VsCodeTheme = serde_json_lenient::from_reader(theme_file)
-        .context(format!("failed to parse theme {theme_file_path:?}"))?;
+    let extension = theme_file_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+
+    let (theme, report) = match extension.as_deref() {
+        Some("tmtheme") => {
+            let tm_theme: TmTheme = plist::from_reader(theme_file)
+                .context(format!("failed to parse theme {theme_file_path:?}"))?;
+
+            let background = tm_theme
+                .global_settings()
+                .and_then(|settings| settings.get("background"));
+            let theme_metadata = ThemeMetadata {
+                name: tm_theme.name.clone().unwrap_or("".to_string()),
+                appearance: ThemeAppearanceJson::from_background(background.map(|s| s.as_str())),
+                file_name: "".to_string(),
+            };
+
+            TmThemeConverter::new(tm_theme, theme_metadata, IndexMap::new()).convert()?
+        }
+        Some("icls") => {
+            let scheme_xml = std::io::read_to_string(theme_file)?;
+            let scheme = JetBrainsScheme::parse(&scheme_xml)
+                .context(format!("failed to parse scheme {theme_file_path:?}"))?;
+
+            let background = scheme
+                .resolved_attributes("TEXT")
+                .and_then(|text| text.background);
+            let theme_metadata = ThemeMetadata {
+                name: scheme.name.clone(),
+                appearance: ThemeAppearanceJson::from_background(background.as_deref()),
+                file_name: "".to_string(),
+            };
+
+            JetBrainsSchemeConverter::new(scheme, theme_metadata).convert()?
+        }
+        _ => {
+            let vscode_theme: VsCodeTheme = serde_json_lenient::from_reader(theme_file)
+                .context(format!("failed to parse theme {theme_file_path:?}"))?;
 
-    let theme_metadata = ThemeMetadata {
-        name: vscode_theme.name.clone().unwrap_or("".to_string()),
-        appearance: ThemeAppearanceJson::Dark,
-        file_name: "".to_string(),
-    };
+            let theme_metadata = ThemeMetadata {
+                name: vscode_theme.name.clone().unwrap_or("".to_string()),
+                appearance: ThemeAppearanceJson::Dark,
+                file_name: "".to_string(),
+            };
 
-    let converter = VsCodeThemeConverter::new(vscode_theme, theme_metadata, IndexMap::new());
+            VsCodeThemeConverter::new(vscode_theme, theme_metadata, IndexMap::new()).convert()?
+        }
+    };
 
-    let theme = converter.convert()?;
+    if !report.is_empty() {
+        log::warn!("Some parts of the theme could not be imported:\n{report}");
+    }
 
     let theme_json = serde_json::to_string_pretty(&theme).unwrap();
 
@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// The parts of an imported theme that couldn't be mapped onto a Zed theme.
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// The scopes of syntax styles that weren't used for any Zed syntax token.
+    pub unmapped_scopes: Vec<String>,
+    /// The keys of colors that don't correspond to any Zed theme color.
+    pub unmapped_keys: Vec<String>,
+}
+
+impl ImportReport {
+    pub fn is_empty(&self) -> bool {
+        self.unmapped_scopes.is_empty() && self.unmapped_keys.is_empty()
+    }
+}
+
+impl fmt::Display for ImportReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.unmapped_scopes.is_empty() {
+            writeln!(f, "Unmapped scopes ({}):", self.unmapped_scopes.len())?;
+            for scope in &self.unmapped_scopes {
+                writeln!(f, "  {scope}")?;
+            }
+        }
+
+        if !self.unmapped_keys.is_empty() {
+            writeln!(f, "Unmapped keys ({}):", self.unmapped_keys.len())?;
+            for key in &self.unmapped_keys {
+                writeln!(f, "  {key}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
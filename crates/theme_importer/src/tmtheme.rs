@@ -0,0 +1,5 @@
+mod converter;
+mod theme;
+
+pub use converter::*;
+pub use theme::*;
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use indexmap::IndexMap;
+use theme::{
+    PlayerColorContent, StatusColorsContent, ThemeColorsContent, ThemeContent, ThemeStyleContent,
+};
+
+use crate::tmtheme::TmTheme;
+use crate::vscode::{
+    convert_token_colors, VsCodeTokenColor, VsCodeTokenColorSettings, VsCodeTokenScope,
+};
+use crate::{ImportReport, ThemeMetadata};
+
+/// The keys of the global settings of a TextMate theme that are mapped onto Zed theme colors.
+const MAPPED_GLOBAL_SETTINGS: &[&str] = &[
+    "activeGuide",
+    "background",
+    "caret",
+    "findHighlight",
+    "foreground",
+    "guide",
+    "gutter",
+    "gutterForeground",
+    "invisibles",
+    "lineHighlight",
+    "selection",
+];
+
+pub struct TmThemeConverter {
+    theme: TmTheme,
+    theme_metadata: ThemeMetadata,
+    syntax_overrides: IndexMap<String, Vec<String>>,
+}
+
+impl TmThemeConverter {
+    pub fn new(
+        theme: TmTheme,
+        theme_metadata: ThemeMetadata,
+        syntax_overrides: IndexMap<String, Vec<String>>,
+    ) -> Self {
+        Self {
+            theme,
+            theme_metadata,
+            syntax_overrides,
+        }
+    }
+
+    pub fn convert(self) -> Result<(ThemeContent, ImportReport)> {
+        let appearance = self.theme_metadata.appearance.into();
+
+        let theme_colors = self.convert_theme_colors();
+        let players = self.convert_player_colors();
+        let (syntax_theme, unmapped_scopes) =
+            convert_token_colors(&self.token_colors(), &self.syntax_overrides);
+
+        let unmapped_keys = self
+            .theme
+            .global_settings()
+            .into_iter()
+            .flat_map(|settings| settings.keys())
+            .filter(|key| !MAPPED_GLOBAL_SETTINGS.contains(&key.as_str()))
+            .cloned()
+            .collect();
+
+        let theme = ThemeContent {
+            name: self.theme_metadata.name,
+            appearance,
+            style: ThemeStyleContent {
+                window_background_appearance: Some(theme::WindowBackgroundContent::Opaque),
+                accents: Vec::new(),
+                colors: theme_colors,
+                status: StatusColorsContent::default(),
+                players,
+                syntax: syntax_theme,
+            },
+        };
+
+        Ok((
+            theme,
+            ImportReport {
+                unmapped_scopes,
+                unmapped_keys,
+            },
+        ))
+    }
+
+    fn global_setting(&self, key: &str) -> Option<String> {
+        self.theme.global_settings()?.get(key).cloned()
+    }
+
+    fn convert_theme_colors(&self) -> ThemeColorsContent {
+        let background = self.global_setting("background");
+        let foreground = self.global_setting("foreground");
+
+        ThemeColorsContent {
+            background: background.clone(),
+            text: foreground.clone(),
+            editor_background: background.clone(),
+            editor_foreground: foreground,
+            editor_gutter_background: self.global_setting("gutter").or(background),
+            editor_active_line_background: self.global_setting("lineHighlight"),
+            editor_line_number: self.global_setting("gutterForeground"),
+            editor_invisible: self.global_setting("invisibles"),
+            editor_indent_guide: self.global_setting("guide"),
+            editor_indent_guide_active: self.global_setting("activeGuide"),
+            search_match_background: self.global_setting("findHighlight"),
+            ..Default::default()
+        }
+    }
+
+    fn convert_player_colors(&self) -> Vec<PlayerColorContent> {
+        let caret = self.global_setting("caret");
+        let selection = self.global_setting("selection");
+        if caret.is_none() && selection.is_none() {
+            return Vec::new();
+        }
+
+        vec![PlayerColorContent {
+            cursor: caret.clone(),
+            background: caret,
+            selection,
+        }]
+    }
+
+    /// Returns the syntax styles of the theme as VS Code token colors, which use the same
+    /// TextMate scopes.
+    fn token_colors(&self) -> Vec<VsCodeTokenColor> {
+        self.theme
+            .settings
+            .iter()
+            .filter_map(|setting| {
+                let scopes = setting
+                    .scope
+                    .as_ref()?
+                    .split(',')
+                    .map(|scope| scope.trim().to_string())
+                    .filter(|scope| !scope.is_empty())
+                    .collect();
+
+                Some(VsCodeTokenColor {
+                    name: setting.name.clone(),
+                    scope: Some(VsCodeTokenScope::Many(scopes)),
+                    settings: VsCodeTokenColorSettings {
+                        foreground: setting.settings.get("foreground").cloned(),
+                        background: setting.settings.get("background").cloned(),
+                        font_style: setting.settings.get("fontStyle").cloned(),
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ThemeAppearanceJson;
+
+    #[test]
+    fn test_convert_tmtheme() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+            <plist version="1.0">
+            <dict>
+                <key>name</key>
+                <string>Test</string>
+                <key>settings</key>
+                <array>
+                    <dict>
+                        <key>settings</key>
+                        <dict>
+                            <key>background</key>
+                            <string>#272822</string>
+                            <key>foreground</key>
+                            <string>#F8F8F2</string>
+                            <key>bracketsForeground</key>
+                            <string>#F8F8F2</string>
+                        </dict>
+                    </dict>
+                    <dict>
+                        <key>name</key>
+                        <string>Comment</string>
+                        <key>scope</key>
+                        <string>comment, punctuation.definition.comment</string>
+                        <key>settings</key>
+                        <dict>
+                            <key>foreground</key>
+                            <string>#75715E</string>
+                            <key>fontStyle</key>
+                            <string>italic</string>
+                        </dict>
+                    </dict>
+                    <dict>
+                        <key>name</key>
+                        <string>Diff header</string>
+                        <key>scope</key>
+                        <string>meta.diff.header</string>
+                        <key>settings</key>
+                        <dict>
+                            <key>foreground</key>
+                            <string>#75715E</string>
+                        </dict>
+                    </dict>
+                </array>
+            </dict>
+            </plist>"#;
+
+        let theme: TmTheme = plist::from_bytes(plist.as_bytes()).unwrap();
+        let metadata = ThemeMetadata {
+            name: "Test".to_string(),
+            file_name: String::new(),
+            appearance: ThemeAppearanceJson::Dark,
+        };
+        let (theme, report) = TmThemeConverter::new(theme, metadata, IndexMap::new())
+            .convert()
+            .unwrap();
+
+        assert_eq!(
+            theme.style.colors.editor_background.as_deref(),
+            Some("#272822")
+        );
+        let comment = &theme.style.syntax["comment"];
+        assert_eq!(comment.color.as_deref(), Some("#75715E"));
+        assert!(matches!(
+            comment.font_style,
+            Some(theme::FontStyleContent::Italic)
+        ));
+        assert_eq!(report.unmapped_scopes, vec!["meta.diff.header".to_string()]);
+        assert_eq!(report.unmapped_keys, vec!["bracketsForeground".to_string()]);
+    }
+}
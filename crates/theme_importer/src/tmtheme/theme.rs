@@ -0,0 +1,34 @@
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+/// A TextMate theme, as used by TextMate and Sublime Text.
+///
+/// These are property lists, usually stored in `.tmTheme` files.
+#[derive(Deserialize, Debug)]
+pub struct TmTheme {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub settings: Vec<TmThemeSetting>,
+}
+
+/// An entry in the settings of a TextMate theme.
+///
+/// The entry without a scope holds the colors of the editor itself, while the others hold
+/// the styles of the syntax scopes they apply to.
+#[derive(Deserialize, Debug)]
+pub struct TmThemeSetting {
+    pub name: Option<String>,
+    /// A comma-separated list of scope selectors.
+    pub scope: Option<String>,
+    pub settings: IndexMap<String, String>,
+}
+
+impl TmTheme {
+    /// Returns the colors of the editor itself.
+    pub fn global_settings(&self) -> Option<&IndexMap<String, String>> {
+        self.settings
+            .iter()
+            .find(|setting| setting.scope.is_none())
+            .map(|setting| &setting.settings)
+    }
+}
@@ -6,8 +6,8 @@ use theme::{
     ThemeColorsContent, ThemeContent, ThemeStyleContent,
 };
 
-use crate::vscode::{VsCodeTheme, VsCodeTokenScope};
-use crate::ThemeMetadata;
+use crate::vscode::{VsCodeTheme, VsCodeTokenColor, VsCodeTokenScope};
+use crate::{ImportReport, ThemeMetadata};
 
 use super::ZedSyntaxToken;
 
@@ -45,14 +45,14 @@ impl VsCodeThemeConverter {
         }
     }
 
-    pub fn convert(self) -> Result<ThemeContent> {
+    pub fn convert(self) -> Result<(ThemeContent, ImportReport)> {
         let appearance = self.theme_metadata.appearance.into();
 
         let status_colors = self.convert_status_colors()?;
         let theme_colors = self.convert_theme_colors()?;
-        let syntax_theme = self.convert_syntax_theme()?;
+        let (syntax_theme, unmapped_scopes) = self.convert_syntax_theme();
 
-        Ok(ThemeContent {
+        let theme = ThemeContent {
             name: self.theme_metadata.name,
             appearance,
             style: ThemeStyleContent {
@@ -63,7 +63,15 @@ impl VsCodeThemeConverter {
                 players: Vec::new(),
                 syntax: syntax_theme,
             },
-        })
+        };
+
+        Ok((
+            theme,
+            ImportReport {
+                unmapped_scopes,
+                ..Default::default()
+            },
+        ))
     }
 
     fn convert_status_colors(&self) -> Result<StatusColorsContent> {
@@ -201,66 +209,92 @@ impl VsCodeThemeConverter {
         })
     }
 
-    fn convert_syntax_theme(&self) -> Result<IndexMap<String, HighlightStyleContent>> {
-        let mut highlight_styles = IndexMap::new();
+    fn convert_syntax_theme(&self) -> (IndexMap<String, HighlightStyleContent>, Vec<String>) {
+        convert_token_colors(&self.theme.token_colors, &self.syntax_overrides)
+    }
+}
 
-        for syntax_token in ZedSyntaxToken::iter() {
-            let override_match = self
-                .syntax_overrides
-                .get(&syntax_token.to_string())
-                .and_then(|scope| {
-                    self.theme.token_colors.iter().find(|token_color| {
-                        token_color.scope == Some(VsCodeTokenScope::Many(scope.clone()))
-                    })
-                });
+/// Converts TextMate token colors to Zed syntax styles.
+///
+/// Returns the syntax styles along with the scopes of the token colors that weren't used
+/// for any syntax token.
+pub(crate) fn convert_token_colors(
+    token_colors: &[VsCodeTokenColor],
+    syntax_overrides: &IndexMap<String, Vec<String>>,
+) -> (IndexMap<String, HighlightStyleContent>, Vec<String>) {
+    let mut highlight_styles = IndexMap::new();
+    let mut used_token_colors = Vec::new();
 
-            let best_match = override_match
-                .or_else(|| syntax_token.find_best_token_color_match(&self.theme.token_colors))
-                .or_else(|| {
-                    syntax_token.fallbacks().iter().find_map(|fallback| {
-                        fallback.find_best_token_color_match(&self.theme.token_colors)
-                    })
-                });
+    for syntax_token in ZedSyntaxToken::iter() {
+        let override_match = syntax_overrides
+            .get(&syntax_token.to_string())
+            .and_then(|scope| {
+                token_colors.iter().find(|token_color| {
+                    token_color.scope == Some(VsCodeTokenScope::Many(scope.clone()))
+                })
+            });
 
-            let Some(token_color) = best_match else {
-                log::warn!("No matching token color found for '{syntax_token}'");
-                continue;
-            };
+        let best_match = override_match
+            .or_else(|| syntax_token.find_best_token_color_match(token_colors))
+            .or_else(|| {
+                syntax_token
+                    .fallbacks()
+                    .iter()
+                    .find_map(|fallback| fallback.find_best_token_color_match(token_colors))
+            });
 
-            log::info!(
-                "Matched '{syntax_token}' to '{}'",
-                token_color
-                    .name
-                    .clone()
-                    .or_else(|| token_color
-                        .scope
-                        .as_ref()
-                        .map(|scope| format!("{:?}", scope)))
-                    .unwrap_or_else(|| "no identifier".to_string())
-            );
+        let Some(token_color) = best_match else {
+            log::warn!("No matching token color found for '{syntax_token}'");
+            continue;
+        };
 
-            let highlight_style = HighlightStyleContent {
-                color: token_color.settings.foreground.clone(),
-                background_color: token_color.settings.background.clone(),
-                font_style: token_color
-                    .settings
-                    .font_style
-                    .as_ref()
-                    .and_then(|style| try_parse_font_style(&style)),
-                font_weight: token_color
-                    .settings
-                    .font_style
+        log::info!(
+            "Matched '{syntax_token}' to '{}'",
+            token_color
+                .name
+                .clone()
+                .or_else(|| token_color
+                    .scope
                     .as_ref()
-                    .and_then(|style| try_parse_font_weight(&style)),
-            };
+                    .map(|scope| format!("{:?}", scope)))
+                .unwrap_or_else(|| "no identifier".to_string())
+        );
 
-            if highlight_style.is_empty() {
-                continue;
-            }
+        let highlight_style = HighlightStyleContent {
+            color: token_color.settings.foreground.clone(),
+            background_color: token_color.settings.background.clone(),
+            font_style: token_color
+                .settings
+                .font_style
+                .as_ref()
+                .and_then(|style| try_parse_font_style(&style)),
+            font_weight: token_color
+                .settings
+                .font_style
+                .as_ref()
+                .and_then(|style| try_parse_font_weight(&style)),
+        };
 
-            highlight_styles.insert(syntax_token.to_string(), highlight_style);
+        if highlight_style.is_empty() {
+            continue;
         }
 
-        Ok(highlight_styles)
+        used_token_colors.push(token_color);
+        highlight_styles.insert(syntax_token.to_string(), highlight_style);
     }
+
+    let unmapped_scopes = token_colors
+        .iter()
+        .filter(|token_color| {
+            !used_token_colors
+                .iter()
+                .any(|used| std::ptr::eq(*used, *token_color))
+        })
+        .filter_map(|token_color| match token_color.scope.as_ref()? {
+            VsCodeTokenScope::One(scope) => Some(scope.clone()),
+            VsCodeTokenScope::Many(scopes) => Some(scopes.join(", ")),
+        })
+        .collect();
+
+    (highlight_styles, unmapped_scopes)
 }
@@ -24,12 +24,20 @@ pub struct Toggle {
 }
 
 impl_actions!(theme_selector, [Toggle]);
-actions!(theme_selector, [Reload]);
+actions!(
+    theme_selector,
+    [Reload, ToggleWorkspaceTheme, ResetWorkspaceTheme]
+);
 
 pub fn init(cx: &mut AppContext) {
     cx.observe_new_views(
         |workspace: &mut Workspace, _cx: &mut ViewContext<Workspace>| {
-            workspace.register_action(toggle);
+            workspace
+                .register_action(toggle)
+                .register_action(toggle_workspace_theme)
+                .register_action(|workspace, _: &ResetWorkspaceTheme, cx| {
+                    workspace.set_theme_override(None, cx);
+                });
         },
     )
     .detach();
@@ -44,6 +52,33 @@ pub fn toggle(workspace: &mut Workspace, toggle: &Toggle, cx: &mut ViewContext<W
             fs,
             telemetry,
             toggle.themes_filter.as_ref(),
+            None,
+            cx,
+        );
+        ThemeSelector::new(delegate, cx)
+    });
+}
+
+/// Selects the theme used in this workspace instead of the active theme.
+pub fn toggle_workspace_theme(
+    workspace: &mut Workspace,
+    _: &ToggleWorkspaceTheme,
+    cx: &mut ViewContext<Workspace>,
+) {
+    let fs = workspace.app_state().fs.clone();
+    let telemetry = workspace.client().telemetry().clone();
+    let workspace_handle = cx.view().downgrade();
+    let original_theme_override = workspace.theme_override().map(ToString::to_string);
+    workspace.toggle_modal(cx, |cx| {
+        let delegate = ThemeSelectorDelegate::new(
+            cx.view().downgrade(),
+            fs,
+            telemetry,
+            None,
+            Some(WorkspaceThemeOverride {
+                workspace: workspace_handle,
+                original_theme_override,
+            }),
             cx,
         );
         ThemeSelector::new(delegate, cx)
@@ -86,6 +121,13 @@ pub struct ThemeSelectorDelegate {
     selected_index: usize,
     telemetry: Arc<Telemetry>,
     view: WeakView<ThemeSelector>,
+    /// Set when selecting the theme of a single workspace rather than the active theme.
+    workspace_override: Option<WorkspaceThemeOverride>,
+}
+
+struct WorkspaceThemeOverride {
+    workspace: WeakView<Workspace>,
+    original_theme_override: Option<String>,
 }
 
 impl ThemeSelectorDelegate {
@@ -94,9 +136,14 @@ impl ThemeSelectorDelegate {
         fs: Arc<dyn Fs>,
         telemetry: Arc<Telemetry>,
         themes_filter: Option<&Vec<String>>,
+        workspace_override: Option<WorkspaceThemeOverride>,
         cx: &mut ViewContext<ThemeSelector>,
     ) -> Self {
-        let original_theme = cx.theme().clone();
+        let original_theme = if workspace_override.is_some() {
+            cx.theme().clone()
+        } else {
+            ThemeSettings::get_global(cx).active_theme.clone()
+        };
 
         let staff_mode = cx.is_staff();
         let registry = ThemeRegistry::global(cx);
@@ -136,6 +183,7 @@ impl ThemeSelectorDelegate {
             selection_completed: false,
             telemetry,
             view: weak_view,
+            workspace_override,
         };
 
         this.select_if_matching(&original_theme.name);
@@ -146,6 +194,9 @@ impl ThemeSelectorDelegate {
         if let Some(mat) = self.matches.get(self.selected_index) {
             let registry = ThemeRegistry::global(cx);
             match registry.get(&mat.string) {
+                Ok(_) if self.workspace_override.is_some() => {
+                    ThemeSettings::set_window_theme(cx.window_handle(), Some(&mat.string), cx);
+                }
                 Ok(theme) => {
                     Self::set_theme(theme, cx);
                 }
@@ -179,7 +230,11 @@ impl PickerDelegate for ThemeSelectorDelegate {
     type ListItem = ui::ListItem;
 
     fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
-        "Select Theme...".into()
+        if self.workspace_override.is_some() {
+            "Select Workspace Theme...".into()
+        } else {
+            "Select Theme...".into()
+        }
     }
 
     fn match_count(&self) -> usize {
@@ -189,7 +244,25 @@ impl PickerDelegate for ThemeSelectorDelegate {
     fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<ThemeSelectorDelegate>>) {
         self.selection_completed = true;
 
-        let theme_name = cx.theme().name.clone();
+        if let Some(workspace_override) = &self.workspace_override {
+            if let Some(mat) = self.matches.get(self.selected_index) {
+                let theme_name = mat.string.clone();
+                workspace_override
+                    .workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.set_theme_override(Some(theme_name), cx)
+                    })
+                    .log_err();
+            }
+            self.view
+                .update(cx, |_, cx| {
+                    cx.emit(DismissEvent);
+                })
+                .ok();
+            return;
+        }
+
+        let theme_name = ThemeSettings::get_global(cx).active_theme.name.clone();
 
         self.telemetry
             .report_setting_event("theme", theme_name.to_string());
@@ -225,7 +298,15 @@ impl PickerDelegate for ThemeSelectorDelegate {
 
     fn dismissed(&mut self, cx: &mut ViewContext<Picker<ThemeSelectorDelegate>>) {
         if !self.selection_completed {
-            Self::set_theme(self.original_theme.clone(), cx);
+            if let Some(workspace_override) = &self.workspace_override {
+                ThemeSettings::set_window_theme(
+                    cx.window_handle(),
+                    workspace_override.original_theme_override.as_deref(),
+                    cx,
+                );
+            } else {
+                Self::set_theme(self.original_theme.clone(), cx);
+            }
             self.selection_completed = true;
         }
 
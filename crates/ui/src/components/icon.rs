@@ -114,6 +114,7 @@ pub enum IconName {
     Bolt,
     Book,
     BookCopy,
+    Bookmark,
     BookPlus,
     CaseSensitive,
     Check,
@@ -188,6 +189,7 @@ pub enum IconName {
     PageUp,
     Pencil,
     Person,
+    Pin,
     Play,
     Plus,
     Public,
@@ -266,6 +268,7 @@ impl IconName {
             IconName::Bolt => "icons/bolt.svg",
             IconName::Book => "icons/book.svg",
             IconName::BookCopy => "icons/book_copy.svg",
+            IconName::Bookmark => "icons/bookmark.svg",
             IconName::BookPlus => "icons/book_plus.svg",
             IconName::CaseSensitive => "icons/case_insensitive.svg",
             IconName::Check => "icons/check.svg",
@@ -340,6 +343,7 @@ impl IconName {
             IconName::PageUp => "icons/page_up.svg",
             IconName::Pencil => "icons/pencil.svg",
             IconName::Person => "icons/person.svg",
+            IconName::Pin => "icons/pin.svg",
             IconName::Play => "icons/play.svg",
             IconName::Plus => "icons/plus.svg",
             IconName::Public => "icons/public.svg",
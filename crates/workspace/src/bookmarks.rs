@@ -0,0 +1,341 @@
+use std::{
+    cmp::Ordering,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use collections::HashMap;
+use gpui::{
+    AppContext, EntityId, EventEmitter, Model, ModelContext, SharedString, Subscription, WeakModel,
+};
+use language::{Anchor, Buffer, Point};
+
+use crate::persistence::model::SerializedBookmark;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BookmarkId(usize);
+
+/// A line of a file marked by the user for quick navigation.
+///
+/// While the file's buffer is open, the bookmark is tracked with an anchor so it
+/// follows its line across edits. Otherwise, only the last known row is kept.
+pub struct Bookmark {
+    pub id: BookmarkId,
+    pub abs_path: Arc<Path>,
+    pub label: Option<SharedString>,
+    row: u32,
+    anchor: Option<(WeakModel<Buffer>, Anchor)>,
+}
+
+impl Bookmark {
+    /// The zero-based row of the bookmarked line, as of the last edit of its buffer.
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    /// Returns the anchor tracking this bookmark, if it belongs to the given buffer.
+    pub fn anchor_in(&self, buffer: &Model<Buffer>) -> Option<Anchor> {
+        let (bookmark_buffer, anchor) = self.anchor.as_ref()?;
+        (bookmark_buffer.entity_id() == buffer.entity_id()).then_some(*anchor)
+    }
+
+    fn cmp_position(&self, abs_path: &Path, row: u32) -> Ordering {
+        self.abs_path
+            .as_ref()
+            .cmp(abs_path)
+            .then(self.row.cmp(&row))
+    }
+}
+
+/// Holds the bookmarks of a workspace, across both open and closed files.
+#[derive(Default)]
+pub struct BookmarkStore {
+    bookmarks: Vec<Bookmark>,
+    next_id: usize,
+    registered_buffers: HashMap<EntityId, RegisteredBuffer>,
+}
+
+/// Emitted when bookmarks are added, removed, relabeled or moved to another file.
+///
+/// Rows that shift as their buffer is edited don't emit it, and are persisted along with
+/// the next change to the workspace.
+pub enum BookmarkStoreEvent {
+    BookmarksChanged,
+}
+
+impl EventEmitter<BookmarkStoreEvent> for BookmarkStore {}
+
+struct RegisteredBuffer {
+    buffer: WeakModel<Buffer>,
+    _subscriptions: [Subscription; 2],
+}
+
+impl BookmarkStore {
+    /// Replaces the bookmarks with the given ones, tracking them with anchors in the
+    /// buffers that are already open.
+    pub(crate) fn load(&mut self, bookmarks: Vec<SerializedBookmark>, cx: &mut ModelContext<Self>) {
+        self.bookmarks.clear();
+        for bookmark in bookmarks {
+            let id = self.next_id();
+            self.bookmarks.push(Bookmark {
+                id,
+                abs_path: bookmark.abs_path.into(),
+                label: bookmark.label.map(SharedString::from),
+                row: bookmark.row,
+                anchor: None,
+            });
+        }
+        let buffers = self
+            .registered_buffers
+            .values()
+            .filter_map(|registered| registered.buffer.upgrade())
+            .collect::<Vec<_>>();
+        for buffer in buffers {
+            self.anchor_bookmarks(&buffer, cx);
+        }
+        self.sort();
+        cx.notify();
+    }
+
+    pub(crate) fn serialize(&self) -> Vec<SerializedBookmark> {
+        self.bookmarks
+            .iter()
+            .map(|bookmark| SerializedBookmark {
+                abs_path: bookmark.abs_path.to_path_buf(),
+                row: bookmark.row,
+                label: bookmark.label.as_ref().map(|label| label.to_string()),
+            })
+            .collect()
+    }
+
+    /// Serializes the bookmarks and loads them back, as when the workspace is restored
+    /// after its items were deserialized.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn reload(&mut self, cx: &mut ModelContext<Self>) {
+        let bookmarks = self.serialize();
+        self.load(bookmarks, cx);
+    }
+
+    /// Returns all bookmarks, ordered by path and row.
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn bookmark(&self, id: BookmarkId) -> Option<&Bookmark> {
+        self.bookmarks.iter().find(|bookmark| bookmark.id == id)
+    }
+
+    /// Returns the bookmarks that are tracked by anchors in the given buffer.
+    pub fn bookmarks_in_buffer<'a>(
+        &'a self,
+        buffer: &'a Model<Buffer>,
+    ) -> impl 'a + Iterator<Item = (&'a Bookmark, Anchor)> {
+        self.bookmarks
+            .iter()
+            .filter_map(move |bookmark| Some((bookmark, bookmark.anchor_in(buffer)?)))
+    }
+
+    /// Starts tracking the bookmarks of the given buffer's file with anchors, so that
+    /// they follow edits made to the buffer.
+    pub fn register_buffer(&mut self, buffer: &Model<Buffer>, cx: &mut ModelContext<Self>) {
+        let buffer_id = buffer.entity_id();
+        if self.registered_buffers.contains_key(&buffer_id)
+            || buffer_abs_path(buffer.read(cx), cx).is_none()
+        {
+            return;
+        }
+
+        self.registered_buffers.insert(
+            buffer_id,
+            RegisteredBuffer {
+                buffer: buffer.downgrade(),
+                _subscriptions: [
+                    cx.subscribe(buffer, |this, buffer, event, cx| match event {
+                        language::Event::Edited | language::Event::Reloaded => {
+                            this.refresh_rows(&buffer, cx)
+                        }
+                        language::Event::FileHandleChanged => this.refresh_path(&buffer, cx),
+                        _ => {}
+                    }),
+                    cx.observe_release(buffer, move |this, buffer, cx| {
+                        this.buffer_released(buffer_id, buffer, cx)
+                    }),
+                ],
+            },
+        );
+        self.anchor_bookmarks(buffer, cx);
+    }
+
+    /// Tracks the bookmarks of the buffer's file that aren't tracked yet with anchors.
+    fn anchor_bookmarks(&mut self, buffer: &Model<Buffer>, cx: &AppContext) {
+        let Some(abs_path) = buffer_abs_path(buffer.read(cx), cx) else {
+            return;
+        };
+        let buffer_handle = buffer.downgrade();
+        let buffer = buffer.read(cx);
+        let max_row = buffer.max_point().row;
+        for bookmark in &mut self.bookmarks {
+            if bookmark.anchor.is_none() && bookmark.abs_path == abs_path {
+                bookmark.row = bookmark.row.min(max_row);
+                let anchor = buffer.anchor_before(Point::new(bookmark.row, 0));
+                bookmark.anchor = Some((buffer_handle.clone(), anchor));
+            }
+        }
+    }
+
+    /// Adds a bookmark on the given row of the buffer, or removes the bookmark already there.
+    pub fn toggle(
+        &mut self,
+        buffer: &Model<Buffer>,
+        row: u32,
+        label: Option<SharedString>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        self.register_buffer(buffer, cx);
+        if let Some(ix) = self
+            .bookmarks
+            .iter()
+            .position(|bookmark| bookmark.anchor_in(buffer).is_some() && bookmark.row == row)
+        {
+            self.bookmarks.remove(ix);
+        } else {
+            let Some(abs_path) = buffer_abs_path(buffer.read(cx), cx) else {
+                return;
+            };
+            let anchor = buffer.read(cx).anchor_before(Point::new(row, 0));
+            let id = self.next_id();
+            self.bookmarks.push(Bookmark {
+                id,
+                abs_path,
+                label,
+                row,
+                anchor: Some((buffer.downgrade(), anchor)),
+            });
+            self.sort();
+        }
+        cx.emit(BookmarkStoreEvent::BookmarksChanged);
+        cx.notify();
+    }
+
+    pub fn set_label(
+        &mut self,
+        id: BookmarkId,
+        label: Option<SharedString>,
+        cx: &mut ModelContext<Self>,
+    ) {
+        if let Some(bookmark) = self.bookmarks.iter_mut().find(|bookmark| bookmark.id == id) {
+            bookmark.label = label;
+            cx.emit(BookmarkStoreEvent::BookmarksChanged);
+            cx.notify();
+        }
+    }
+
+    pub fn remove(&mut self, id: BookmarkId, cx: &mut ModelContext<Self>) {
+        self.bookmarks.retain(|bookmark| bookmark.id != id);
+        cx.emit(BookmarkStoreEvent::BookmarksChanged);
+        cx.notify();
+    }
+
+    pub fn clear(&mut self, cx: &mut ModelContext<Self>) {
+        self.bookmarks.clear();
+        cx.emit(BookmarkStoreEvent::BookmarksChanged);
+        cx.notify();
+    }
+
+    /// Returns the first bookmark after the given position, wrapping around to the first
+    /// bookmark of the workspace. Without a position, the first bookmark is returned.
+    pub fn next_bookmark(&self, position: Option<(&Path, u32)>) -> Option<&Bookmark> {
+        position
+            .and_then(|(abs_path, row)| {
+                self.bookmarks
+                    .iter()
+                    .find(|bookmark| bookmark.cmp_position(abs_path, row) == Ordering::Greater)
+            })
+            .or_else(|| self.bookmarks.first())
+    }
+
+    /// Returns the last bookmark before the given position, wrapping around to the last
+    /// bookmark of the workspace. Without a position, the last bookmark is returned.
+    pub fn previous_bookmark(&self, position: Option<(&Path, u32)>) -> Option<&Bookmark> {
+        position
+            .and_then(|(abs_path, row)| {
+                self.bookmarks
+                    .iter()
+                    .rev()
+                    .find(|bookmark| bookmark.cmp_position(abs_path, row) == Ordering::Less)
+            })
+            .or_else(|| self.bookmarks.last())
+    }
+
+    fn refresh_rows(&mut self, buffer: &Model<Buffer>, cx: &mut ModelContext<Self>) {
+        let mut changed = false;
+        let buffer_snapshot = buffer.read(cx);
+        for bookmark in &mut self.bookmarks {
+            if let Some(anchor) = bookmark.anchor_in(buffer) {
+                let row = buffer_snapshot.summary_for_anchor::<Point>(&anchor).row;
+                changed |= bookmark.row != row;
+                bookmark.row = row;
+            }
+        }
+        if changed {
+            self.sort();
+            cx.notify();
+        }
+    }
+
+    fn refresh_path(&mut self, buffer: &Model<Buffer>, cx: &mut ModelContext<Self>) {
+        let Some(abs_path) = buffer_abs_path(buffer.read(cx), cx) else {
+            return;
+        };
+        let mut changed = false;
+        for bookmark in &mut self.bookmarks {
+            if bookmark.anchor_in(buffer).is_some() && bookmark.abs_path != abs_path {
+                bookmark.abs_path = abs_path.clone();
+                changed = true;
+            }
+        }
+        if changed {
+            self.sort();
+            cx.emit(BookmarkStoreEvent::BookmarksChanged);
+            cx.notify();
+        }
+    }
+
+    fn buffer_released(
+        &mut self,
+        buffer_id: EntityId,
+        buffer: &mut Buffer,
+        cx: &mut ModelContext<Self>,
+    ) {
+        self.registered_buffers.remove(&buffer_id);
+        for bookmark in &mut self.bookmarks {
+            if let Some((bookmark_buffer, anchor)) = bookmark.anchor.take() {
+                if bookmark_buffer.entity_id() == buffer_id {
+                    bookmark.row = buffer.summary_for_anchor::<Point>(&anchor).row;
+                } else {
+                    bookmark.anchor = Some((bookmark_buffer, anchor));
+                }
+            }
+        }
+        self.sort();
+        cx.notify();
+    }
+
+    fn next_id(&mut self) -> BookmarkId {
+        let id = BookmarkId(self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    fn sort(&mut self) {
+        self.bookmarks.sort_by(|a, b| a.cmp_position(&b.abs_path, b.row));
+    }
+}
+
+/// Returns the absolute path of the file backing the given buffer, used to identify
+/// bookmarks across sessions.
+pub fn buffer_abs_path(buffer: &Buffer, cx: &AppContext) -> Option<Arc<Path>> {
+    let file = project::File::from_dyn(buffer.file())?;
+    let abs_path: PathBuf = file.worktree.read(cx).absolutize(&file.path).ok()?;
+    Some(abs_path.into())
+}
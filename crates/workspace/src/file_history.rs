@@ -0,0 +1,103 @@
+use crate::{persistence::DB, Workspace, WorkspaceId};
+use collections::HashMap;
+use gpui::ViewContext;
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use util::ResultExt;
+
+/// The number of seconds after which the opening of a file counts half as much.
+const HALF_LIFE_SECONDS: f64 = 7. * 24. * 60. * 60.;
+
+/// The number of most recently opened files whose history is kept per workspace.
+const MAX_FILES: usize = 1000;
+
+/// Decays the score of a file that was last opened `elapsed_seconds` ago.
+pub(crate) fn decayed_score(score: f64, elapsed_seconds: i64) -> f64 {
+    score * 0.5f64.powf(elapsed_seconds.max(0) as f64 / HALF_LIFE_SECONDS)
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
+
+impl Workspace {
+    /// Loads the file history of the workspace from the database in the background,
+    /// so that reading the frecencies never blocks on it.
+    pub(crate) fn load_file_history(workspace_id: WorkspaceId, cx: &mut ViewContext<Self>) {
+        cx.spawn(|this, mut cx| async move {
+            let file_history = cx
+                .background_executor()
+                .spawn(async move { DB.file_history(workspace_id) })
+                .await
+                .log_err()
+                .unwrap_or_default();
+            this.update(&mut cx, |this, _| {
+                for (abs_path, score, last_opened) in file_history {
+                    // Files opened while the history was loading are already up to date.
+                    this.file_history
+                        .entry(abs_path)
+                        .or_insert((score, last_opened));
+                }
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Returns how frequently and recently the files of this workspace were opened,
+    /// keyed by their absolute paths. Each opening of a file adds one to its score,
+    /// which halves every week.
+    pub fn file_frecencies(&self) -> HashMap<PathBuf, f64> {
+        let now = now();
+        self.file_history
+            .iter()
+            .map(|(abs_path, (score, last_opened))| {
+                (abs_path.clone(), decayed_score(*score, now - last_opened))
+            })
+            .collect()
+    }
+
+    /// Records the opening of the active item's file, unless it was already the last
+    /// file that was opened.
+    pub(crate) fn record_file_opened(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(workspace_id) = self.database_id() else {
+            return;
+        };
+        let Some(project_path) = self.active_project_path(cx) else {
+            return;
+        };
+        if self.last_opened_path.as_ref() == Some(&project_path) {
+            return;
+        }
+        let Some(abs_path) = self.project.read(cx).absolute_path(&project_path, cx) else {
+            return;
+        };
+        self.last_opened_path = Some(project_path);
+
+        let now = now();
+        let (score, last_opened) = self
+            .file_history
+            .entry(abs_path.clone())
+            .or_insert((0., now));
+        *score = decayed_score(*score, now - *last_opened) + 1.;
+        *last_opened = now;
+        if self.file_history.len() > MAX_FILES {
+            let least_recent = self
+                .file_history
+                .iter()
+                .min_by_key(|(_, (_, last_opened))| *last_opened)
+                .map(|(abs_path, _)| abs_path.clone());
+            if let Some(least_recent) = least_recent {
+                self.file_history.remove(&least_recent);
+            }
+        }
+
+        cx.background_executor()
+            .spawn(DB.record_file_opened(workspace_id, abs_path, now, MAX_FILES))
+            .detach_and_log_err(cx);
+    }
+}
@@ -0,0 +1,419 @@
+use crate::{
+    dock::Dock,
+    item::ItemHandle,
+    pane_group::{Member, PaneAxis},
+    persistence::{
+        model::{
+            SerializedLayout, SerializedLayoutDock, SerializedLayoutGroup, SerializedLayoutItem,
+        },
+        DB,
+    },
+    Event, ItemDeserializers, Pane, PaneGroup, Workspace, WorkspaceId,
+};
+use anyhow::{anyhow, Result};
+use async_recursion::async_recursion;
+use gpui::{px, AppContext, AsyncWindowContext, Model, Task, View, ViewContext, WeakView};
+use project::Project;
+use std::path::PathBuf;
+use util::ResultExt;
+
+/// An item that was open in the workspace's center before switching layouts, which
+/// can be moved into the new layout instead of being reopened.
+struct OpenItem {
+    pane: View<Pane>,
+    item: Box<dyn ItemHandle>,
+    abs_path: Option<PathBuf>,
+}
+
+struct LayoutContext {
+    project: Model<Project>,
+    workspace: WeakView<Workspace>,
+    workspace_id: WorkspaceId,
+}
+
+#[derive(Default)]
+struct LayoutPanes {
+    active: Option<View<Pane>>,
+    zoomed: Option<View<Pane>>,
+}
+
+impl Workspace {
+    /// The names of the layouts that were saved for this workspace, in alphabetical order.
+    pub fn layout_names(&self) -> Result<Vec<String>> {
+        match self.database_id() {
+            Some(workspace_id) => DB.layout_names(workspace_id),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Saves the workspace's panes, their items and the state of its docks as a layout
+    /// with the given name, replacing the layout that had that name.
+    pub fn save_layout(&self, name: String, cx: &AppContext) -> Task<Result<()>> {
+        let Some(workspace_id) = self.database_id() else {
+            return Task::ready(Err(anyhow!("workspace has not been saved")));
+        };
+        let layout = self.serialize_layout(cx);
+        cx.background_executor()
+            .spawn(async move { DB.save_layout(workspace_id, name, layout).await })
+    }
+
+    pub fn delete_layout(&self, name: String, cx: &AppContext) -> Task<Result<()>> {
+        let Some(workspace_id) = self.database_id() else {
+            return Task::ready(Err(anyhow!("workspace has not been saved")));
+        };
+        cx.background_executor()
+            .spawn(async move { DB.delete_layout(workspace_id, name).await })
+    }
+
+    /// Rearranges the workspace into the layout with the given name. Items that are
+    /// already open are moved into the new panes, and those that aren't part of the
+    /// layout are closed, unless they have unsaved changes.
+    pub fn switch_to_layout(
+        &mut self,
+        name: String,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        let Some(workspace_id) = self.database_id() else {
+            return Task::ready(Err(anyhow!("workspace has not been saved")));
+        };
+        let layout = match DB.layout(workspace_id, name.clone()) {
+            Ok(Some(layout)) => layout,
+            Ok(None) => return Task::ready(Err(anyhow!("no layout named {name:?}"))),
+            Err(error) => return Task::ready(Err(error)),
+        };
+
+        self.restore_layout(layout, workspace_id, cx)
+    }
+
+    /// Rearranges the workspace into the given layout, deserializing the items without
+    /// a file from the given workspace's state.
+    pub(crate) fn restore_layout(
+        &mut self,
+        layout: SerializedLayout,
+        workspace_id: WorkspaceId,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        let project = self.project.clone();
+        cx.spawn(|workspace, mut cx| async move {
+            let context = LayoutContext {
+                project,
+                workspace: workspace.clone(),
+                workspace_id,
+            };
+            apply_layout(layout, &context, &mut cx).await
+        })
+    }
+
+    pub(crate) fn serialize_layout(&self, cx: &AppContext) -> SerializedLayout {
+        fn serialize_member(
+            member: &Member,
+            workspace: &Workspace,
+            cx: &AppContext,
+        ) -> SerializedLayoutGroup {
+            match member {
+                Member::Axis(PaneAxis {
+                    axis,
+                    members,
+                    flexes,
+                    bounding_boxes: _,
+                }) => SerializedLayoutGroup::Group {
+                    axis: *axis,
+                    flexes: flexes.lock().clone(),
+                    children: members
+                        .iter()
+                        .map(|member| serialize_member(member, workspace, cx))
+                        .collect(),
+                },
+                Member::Pane(pane_handle) => {
+                    let pane = pane_handle.read(cx);
+                    let project = workspace.project.read(cx);
+                    let active_item_id = pane.active_item().map(|item| item.item_id());
+                    SerializedLayoutGroup::Pane {
+                        active: *pane_handle == workspace.active_pane,
+                        zoomed: pane.is_zoomed(),
+                        items: pane
+                            .items()
+                            .map(|item| SerializedLayoutItem {
+                                kind: item.serialized_item_kind().map(str::to_string),
+                                item_id: item.item_id().as_u64(),
+                                abs_path: item
+                                    .project_path(cx)
+                                    .and_then(|path| project.absolute_path(&path, cx)),
+                                active: Some(item.item_id()) == active_item_id,
+                            })
+                            .collect(),
+                    }
+                }
+            }
+        }
+
+        fn serialize_dock(dock: &View<Dock>, cx: &AppContext) -> SerializedLayoutDock {
+            let dock = dock.read(cx);
+            let active_panel = dock.active_panel();
+            SerializedLayoutDock {
+                visible: dock.is_open(),
+                active_panel: active_panel.map(|panel| panel.persistent_name().to_string()),
+                zoom: dock.zoomed_panel(cx).is_some(),
+                size: active_panel.map(|panel| panel.size(cx).0),
+            }
+        }
+
+        SerializedLayout {
+            center_group: serialize_member(&self.center.root, self, cx),
+            left_dock: serialize_dock(&self.left_dock, cx),
+            right_dock: serialize_dock(&self.right_dock, cx),
+            bottom_dock: serialize_dock(&self.bottom_dock, cx),
+        }
+    }
+}
+
+async fn apply_layout(
+    layout: SerializedLayout,
+    context: &LayoutContext,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    let mut open_items = context.workspace.update(cx, |workspace, cx| {
+        let project = workspace.project.read(cx);
+        workspace
+            .center
+            .panes()
+            .into_iter()
+            .flat_map(|pane| {
+                pane.read(cx).items().map(|item| OpenItem {
+                    pane: pane.clone(),
+                    item: item.boxed_clone(),
+                    abs_path: item
+                        .project_path(cx)
+                        .and_then(|path| project.absolute_path(&path, cx)),
+                })
+            })
+            .collect::<Vec<_>>()
+    })?;
+
+    let mut panes = LayoutPanes::default();
+    let center_group = build_layout_group(
+        layout.center_group,
+        &mut open_items,
+        &mut panes,
+        context,
+        cx,
+    )
+    .await?;
+
+    context.workspace.update(cx, |workspace, cx| {
+        let center_group = center_group.unwrap_or_else(|| Member::Pane(workspace.add_pane(cx)));
+        let center = PaneGroup::with_root(center_group);
+        let active_pane = panes.active.unwrap_or_else(|| center.first_pane());
+
+        // Items that weren't part of the layout are closed, unless closing them would lose
+        // unsaved changes, in which case they're kept in the active pane.
+        for OpenItem { pane, item, .. } in open_items {
+            pane.update(cx, |pane, cx| {
+                if let Some(ix) = pane.index_for_item(&*item) {
+                    pane.remove_item(ix, false, false, cx);
+                }
+            });
+            if item.is_dirty(cx) {
+                active_pane.update(cx, |pane, cx| pane.add_item(item, false, false, None, cx));
+            }
+        }
+
+        workspace.remove_panes(workspace.center.root.clone(), cx);
+        workspace.center = center;
+        workspace.last_active_center_pane = Some(active_pane.downgrade());
+        workspace.active_pane = active_pane.clone();
+        cx.focus_view(&active_pane);
+
+        if let Some(zoomed_pane) = panes.zoomed {
+            zoomed_pane.update(cx, |pane, cx| pane.set_zoomed(true, cx));
+            workspace.zoomed = Some(zoomed_pane.downgrade().into());
+            workspace.zoomed_position = None;
+            cx.emit(Event::ZoomChanged);
+        } else if workspace.zoomed.is_some() && workspace.zoomed_position.is_none() {
+            workspace.zoomed = None;
+            cx.emit(Event::ZoomChanged);
+        }
+
+        for (dock, serialized_dock) in [
+            (workspace.left_dock.clone(), layout.left_dock),
+            (workspace.right_dock.clone(), layout.right_dock),
+            (workspace.bottom_dock.clone(), layout.bottom_dock),
+        ] {
+            dock.update(cx, |dock, cx| {
+                if let Some(ix) = serialized_dock
+                    .active_panel
+                    .as_deref()
+                    .and_then(|name| dock.panel_index_for_persistent_name(name, cx))
+                {
+                    dock.activate_panel(ix, cx);
+                }
+                dock.set_open(serialized_dock.visible, cx);
+                if let Some(size) = serialized_dock.size {
+                    dock.resize_active_panel(Some(px(size)), cx);
+                }
+                if let Some(panel) = dock.active_panel().cloned() {
+                    let zoom = serialized_dock.zoom && serialized_dock.visible;
+                    if panel.is_zoomed(cx) != zoom {
+                        panel.set_zoomed(zoom, cx);
+                    }
+                }
+            });
+        }
+
+        cx.notify();
+        workspace.serialize_workspace(cx);
+    })
+}
+
+#[async_recursion(?Send)]
+async fn build_layout_group(
+    group: SerializedLayoutGroup,
+    open_items: &mut Vec<OpenItem>,
+    panes: &mut LayoutPanes,
+    context: &LayoutContext,
+    cx: &mut AsyncWindowContext,
+) -> Result<Option<Member>> {
+    match group {
+        SerializedLayoutGroup::Group {
+            axis,
+            flexes,
+            children,
+        } => {
+            let mut members = Vec::new();
+            let mut member_flexes = Vec::new();
+            for (ix, child) in children.into_iter().enumerate() {
+                if let Some(member) =
+                    build_layout_group(child, open_items, panes, context, cx).await?
+                {
+                    members.push(member);
+                    member_flexes.push(flexes.get(ix).copied().unwrap_or(1.));
+                }
+            }
+
+            if members.len() <= 1 {
+                return Ok(members.pop());
+            }
+            Ok(Some(Member::Axis(PaneAxis::load(
+                axis,
+                members,
+                Some(member_flexes),
+            ))))
+        }
+        SerializedLayoutGroup::Pane {
+            active,
+            zoomed,
+            items,
+        } => {
+            let pane = context
+                .workspace
+                .update(cx, |workspace, cx| workspace.add_pane(cx))?;
+            let mut active_item = None;
+            for item in items {
+                if let Some(item_handle) = open_layout_item(&item, &pane, open_items, context, cx)
+                    .await
+                    .log_err()
+                {
+                    if item.active {
+                        active_item = Some(item_handle);
+                    }
+                }
+            }
+
+            let is_empty = pane.update(cx, |pane, cx| {
+                if let Some(ix) = active_item.and_then(|item| pane.index_for_item(&*item)) {
+                    pane.activate_item(ix, false, false, cx);
+                }
+                pane.items_len() == 0
+            })?;
+            if is_empty {
+                context.workspace.update(cx, |workspace, cx| {
+                    workspace.force_remove_pane(&pane, cx);
+                })?;
+                return Ok(None);
+            }
+
+            if active {
+                panes.active = Some(pane.clone());
+            }
+            if zoomed {
+                panes.zoomed = Some(pane.clone());
+            }
+            Ok(Some(Member::Pane(pane)))
+        }
+    }
+}
+
+/// Adds an item of a layout to `pane`, moving it there if it's already open, and
+/// otherwise reopening its file or deserializing it.
+async fn open_layout_item(
+    item: &SerializedLayoutItem,
+    pane: &View<Pane>,
+    open_items: &mut Vec<OpenItem>,
+    context: &LayoutContext,
+    cx: &mut AsyncWindowContext,
+) -> Result<Box<dyn ItemHandle>> {
+    let open_item_ix = open_items
+        .iter()
+        .position(|open_item| match &item.abs_path {
+            Some(abs_path) => open_item.abs_path.as_ref() == Some(abs_path),
+            None => open_item.item.item_id().as_u64() == item.item_id,
+        });
+    if let Some(ix) = open_item_ix {
+        let OpenItem {
+            pane: source,
+            item: item_handle,
+            ..
+        } = open_items.remove(ix);
+        cx.update(|cx| {
+            source.update(cx, |source, cx| {
+                if let Some(ix) = source.index_for_item(&*item_handle) {
+                    source.remove_item(ix, false, false, cx);
+                }
+            });
+            pane.update(cx, |pane, cx| {
+                pane.add_item(item_handle.boxed_clone(), true, false, None, cx)
+            });
+        })?;
+        return Ok(item_handle);
+    }
+
+    if let Some(abs_path) = &item.abs_path {
+        let project_path = cx
+            .update(|cx| {
+                context
+                    .project
+                    .read(cx)
+                    .project_path_for_absolute_path(abs_path, cx)
+            })?
+            .ok_or_else(|| anyhow!("{abs_path:?} is not part of the project"))?;
+        let open_path = context.workspace.update(cx, |workspace, cx| {
+            workspace.open_path(project_path, Some(pane.downgrade()), false, cx)
+        })?;
+        return open_path.await;
+    }
+
+    let kind = item
+        .kind
+        .as_deref()
+        .ok_or_else(|| anyhow!("item {} can't be restored", item.item_id))?;
+    let deserialize = pane.update(cx, |_, cx| {
+        if let Some(deserializer) = cx.global::<ItemDeserializers>().get(kind) {
+            deserializer(
+                context.project.clone(),
+                context.workspace.clone(),
+                context.workspace_id,
+                item.item_id,
+                cx,
+            )
+        } else {
+            Task::ready(Err(anyhow!(
+                "Deserializer does not exist for item kind: {kind}"
+            )))
+        }
+    })?;
+    let item_handle = deserialize.await?;
+    pane.update(cx, |pane, cx| {
+        pane.add_item(item_handle.boxed_clone(), true, false, None, cx)
+    })?;
+    Ok(item_handle)
+}
@@ -27,7 +27,7 @@ use settings::{Settings, SettingsStore};
 use std::{
     any::Any,
     cmp, fmt, mem,
-    ops::ControlFlow,
+    ops::{ControlFlow, Range},
     path::PathBuf,
     rc::Rc,
     sync::{
@@ -146,11 +146,14 @@ actions!(
         CloseItemsToTheRight,
         GoBack,
         GoForward,
+        MoveItemToNewWindow,
+        MovePaneToNewWindow,
         ReopenClosedItem,
         SplitLeft,
         SplitUp,
         SplitRight,
         SplitDown,
+        TogglePinTab,
         TogglePreviewTab,
     ]
 );
@@ -171,6 +174,8 @@ pub enum Event {
     Remove,
     RemoveItem { item_id: EntityId },
     Split(SplitDirection),
+    MoveToNewWindow { item_id: Option<EntityId> },
+    RearrangeTabs,
     ChangeItemTitle,
     Focus,
     ZoomIn,
@@ -197,6 +202,11 @@ impl fmt::Debug for Event {
                 .debug_struct("Split")
                 .field("direction", direction)
                 .finish(),
+            Event::MoveToNewWindow { item_id } => f
+                .debug_struct("MoveToNewWindow")
+                .field("item_id", item_id)
+                .finish(),
+            Event::RearrangeTabs => f.write_str("RearrangeTabs"),
             Event::ChangeItemTitle => f.write_str("ChangeItemTitle"),
             Event::Focus => f.write_str("Focus"),
             Event::ZoomIn => f.write_str("ZoomIn"),
@@ -222,6 +232,11 @@ pub struct Pane {
     was_focused: bool,
     active_item_index: usize,
     preview_item_id: Option<EntityId>,
+    /// Pinned items are always the first `pinned_tab_count` items of the pane.
+    pinned_tab_count: usize,
+    tab_groups: Vec<TabGroup>,
+    tab_group_by_item: HashMap<EntityId, TabGroupId>,
+    next_tab_group_id: TabGroupId,
     last_focus_handle_by_item: HashMap<EntityId, WeakFocusHandle>,
     nav_history: NavHistory,
     toolbar: View<Toolbar>,
@@ -246,6 +261,17 @@ pub struct Pane {
     save_modals_spawned: HashSet<EntityId>,
 }
 
+pub type TabGroupId = u64;
+
+/// A colored group of adjacent tabs, which can be collapsed and moved together.
+/// Pinned tabs never belong to a group.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TabGroup {
+    pub id: TabGroupId,
+    pub color_index: u32,
+    pub collapsed: bool,
+}
+
 pub struct ActivationHistoryEntry {
     pub entity_id: EntityId,
     pub timestamp: usize,
@@ -333,6 +359,10 @@ impl Pane {
             zoomed: false,
             active_item_index: 0,
             preview_item_id: None,
+            pinned_tab_count: 0,
+            tab_groups: Vec::new(),
+            tab_group_by_item: Default::default(),
+            next_tab_group_id: 0,
             last_focus_handle_by_item: Default::default(),
             nav_history: NavHistory(Arc::new(Mutex::new(NavHistoryState {
                 mode: NavigationMode::Normal,
@@ -829,8 +859,16 @@ impl Pane {
                         self.active_item_index -= 1;
                     }
                     insertion_index = insertion_index.min(self.items.len());
+                    // Pinned items can only be moved among the pinned items, and other
+                    // items can't be moved in between them.
+                    if existing_item_index < self.pinned_tab_count {
+                        insertion_index = insertion_index.min(self.pinned_tab_count - 1);
+                    } else {
+                        insertion_index = insertion_index.max(self.pinned_tab_count);
+                    }
 
                     self.items.insert(insertion_index, item.clone());
+                    self.update_tab_group_of_inserted_item(insertion_index);
 
                     if existing_item_is_active {
                         self.active_item_index = insertion_index;
@@ -844,7 +882,9 @@ impl Pane {
 
             self.activate_item(insertion_index, activate_pane, focus_item, cx);
         } else {
+            insertion_index = insertion_index.max(self.pinned_tab_count);
             self.items.insert(insertion_index, item.clone());
+            self.update_tab_group_of_inserted_item(insertion_index);
 
             if insertion_index <= self.active_item_index
                 && self.preview_item_idx() != Some(self.active_item_index)
@@ -907,6 +947,339 @@ impl Pane {
         self.items.get(ix).map(|i| i.as_ref())
     }
 
+    fn index_for_item_id(&self, item_id: EntityId) -> Option<usize> {
+        self.items.iter().position(|item| item.item_id() == item_id)
+    }
+
+    pub fn pinned_tab_count(&self) -> usize {
+        self.pinned_tab_count
+    }
+
+    pub fn is_tab_pinned(&self, ix: usize) -> bool {
+        ix < self.pinned_tab_count
+    }
+
+    fn pinned_item_ids(&self) -> Vec<EntityId> {
+        self.items[..self.pinned_tab_count]
+            .iter()
+            .map(|item| item.item_id())
+            .collect()
+    }
+
+    fn toggle_pin_tab(&mut self, _: &TogglePinTab, cx: &mut ViewContext<Self>) {
+        if let Some(active_item_id) = self.active_item().map(|item| item.item_id()) {
+            if self.is_tab_pinned(self.active_item_index) {
+                self.unpin_item(active_item_id, cx);
+            } else {
+                self.pin_item(active_item_id, cx);
+            }
+        }
+    }
+
+    /// Moves the item after the other pinned items. Pinned items stay leftmost, and aren't
+    /// closed when closing other, clean or all items.
+    pub fn pin_item(&mut self, item_id: EntityId, cx: &mut ViewContext<Self>) {
+        let Some(ix) = self.index_for_item_id(item_id) else {
+            return;
+        };
+        if self.is_tab_pinned(ix) {
+            return;
+        }
+        if self.tab_group_by_item.remove(&item_id).is_some() {
+            self.remove_empty_tab_groups();
+        }
+        if self.is_active_preview_item(item_id) {
+            self.set_preview_item_id(None, cx);
+        }
+        self.move_tab(ix, self.pinned_tab_count);
+        self.pinned_tab_count += 1;
+        cx.emit(Event::RearrangeTabs);
+        cx.notify();
+    }
+
+    pub fn unpin_item(&mut self, item_id: EntityId, cx: &mut ViewContext<Self>) {
+        let Some(ix) = self.index_for_item_id(item_id) else {
+            return;
+        };
+        if !self.is_tab_pinned(ix) {
+            return;
+        }
+        self.pinned_tab_count -= 1;
+        self.move_tab(ix, self.pinned_tab_count);
+        cx.emit(Event::RearrangeTabs);
+        cx.notify();
+    }
+
+    fn move_tab(&mut self, from: usize, to: usize) {
+        let active_item_id = self.active_item().map(|item| item.item_id());
+        let item = self.items.remove(from);
+        self.items.insert(to, item);
+        self.restore_active_item_index(active_item_id);
+    }
+
+    fn restore_active_item_index(&mut self, active_item_id: Option<EntityId>) {
+        if let Some(ix) = active_item_id.and_then(|item_id| self.index_for_item_id(item_id)) {
+            self.active_item_index = ix;
+        }
+    }
+
+    pub fn tab_groups(&self) -> &[TabGroup] {
+        &self.tab_groups
+    }
+
+    pub fn tab_group_for_item(&self, item_id: EntityId) -> Option<TabGroupId> {
+        self.tab_group_by_item.get(&item_id).copied()
+    }
+
+    fn tab_group_at(&self, ix: usize) -> Option<TabGroupId> {
+        self.tab_group_for_item(self.items.get(ix)?.item_id())
+    }
+
+    /// The indices of the group's items, which are always adjacent.
+    fn tab_group_range(&self, group_id: TabGroupId) -> Option<Range<usize>> {
+        let start = (0..self.items.len()).find(|ix| self.tab_group_at(*ix) == Some(group_id))?;
+        let end = (start..self.items.len())
+            .find(|ix| self.tab_group_at(*ix) != Some(group_id))
+            .unwrap_or(self.items.len());
+        Some(start..end)
+    }
+
+    /// The indices of the tab at `ix` along with the rest of its group, if it has one.
+    fn tab_block_at(&self, ix: usize) -> Range<usize> {
+        self.tab_group_at(ix)
+            .and_then(|group_id| self.tab_group_range(group_id))
+            .unwrap_or(ix..ix + 1)
+    }
+
+    fn remove_empty_tab_groups(&mut self) {
+        let tab_group_by_item = &self.tab_group_by_item;
+        self.tab_groups.retain(|group| {
+            tab_group_by_item
+                .values()
+                .any(|group_id| *group_id == group.id)
+        });
+    }
+
+    /// Keeps the items of each group adjacent after inserting an item at `ix`: an item
+    /// inserted in between the items of a group joins it, and an item moved away from its
+    /// group leaves it.
+    fn update_tab_group_of_inserted_item(&mut self, ix: usize) {
+        let item_id = self.items[ix].item_id();
+        let left = ix.checked_sub(1).and_then(|ix| self.tab_group_at(ix));
+        let right = self.tab_group_at(ix + 1);
+        let current = self.tab_group_for_item(item_id);
+        let group_id = if self.is_tab_pinned(ix) {
+            None
+        } else if left.is_some() && left == right {
+            left
+        } else if current.is_some() && (current == left || current == right) {
+            current
+        } else {
+            None
+        };
+
+        if group_id != current {
+            if let Some(group_id) = group_id {
+                self.tab_group_by_item.insert(item_id, group_id);
+            } else {
+                self.tab_group_by_item.remove(&item_id);
+            }
+            self.remove_empty_tab_groups();
+        }
+    }
+
+    /// Takes the item at `ix` out of its group, moving it after the rest of the group if it
+    /// was in the middle of it. Returns the new index of the item.
+    fn detach_from_tab_group(&mut self, ix: usize) -> usize {
+        let item_id = self.items[ix].item_id();
+        let Some(group_id) = self.tab_group_by_item.remove(&item_id) else {
+            return ix;
+        };
+        let new_ix = match self.tab_group_range(group_id) {
+            Some(range) if range.start < ix && ix < range.end => {
+                self.move_tab(ix, range.end - 1);
+                range.end - 1
+            }
+            _ => ix,
+        };
+        self.remove_empty_tab_groups();
+        new_ix
+    }
+
+    /// Puts the item into a new group of its own. Pinned items can't be grouped.
+    pub fn add_item_to_new_tab_group(
+        &mut self,
+        item_id: EntityId,
+        cx: &mut ViewContext<Self>,
+    ) -> Option<TabGroupId> {
+        let ix = self.index_for_item_id(item_id)?;
+        if self.is_tab_pinned(ix) {
+            return None;
+        }
+        self.detach_from_tab_group(ix);
+
+        let group_id = self.next_tab_group_id;
+        self.next_tab_group_id += 1;
+        self.tab_groups.push(TabGroup {
+            id: group_id,
+            color_index: group_id as u32,
+            collapsed: false,
+        });
+        self.tab_group_by_item.insert(item_id, group_id);
+        cx.emit(Event::RearrangeTabs);
+        cx.notify();
+        Some(group_id)
+    }
+
+    /// Moves the item to the end of the given group, and adds it to the group.
+    pub fn add_item_to_tab_group(
+        &mut self,
+        item_id: EntityId,
+        group_id: TabGroupId,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(ix) = self.index_for_item_id(item_id) else {
+            return;
+        };
+        if self.is_tab_pinned(ix) || self.tab_group_for_item(item_id) == Some(group_id) {
+            return;
+        }
+        let ix = self.detach_from_tab_group(ix);
+        let Some(range) = self.tab_group_range(group_id) else {
+            return;
+        };
+        let destination = if ix < range.start {
+            range.end - 1
+        } else {
+            range.end
+        };
+        self.move_tab(ix, destination);
+        self.tab_group_by_item.insert(item_id, group_id);
+        cx.emit(Event::RearrangeTabs);
+        cx.notify();
+    }
+
+    pub fn remove_item_from_tab_group(&mut self, item_id: EntityId, cx: &mut ViewContext<Self>) {
+        if let Some(ix) = self.index_for_item_id(item_id) {
+            self.detach_from_tab_group(ix);
+            cx.emit(Event::RearrangeTabs);
+            cx.notify();
+        }
+    }
+
+    /// Hides the tabs of a group behind its label, except for the active one.
+    pub fn toggle_tab_group_collapsed(&mut self, group_id: TabGroupId, cx: &mut ViewContext<Self>) {
+        if let Some(group) = self
+            .tab_groups
+            .iter_mut()
+            .find(|group| group.id == group_id)
+        {
+            group.collapsed = !group.collapsed;
+            cx.emit(Event::RearrangeTabs);
+            cx.notify();
+        }
+    }
+
+    pub fn set_tab_group_color(
+        &mut self,
+        group_id: TabGroupId,
+        color_index: u32,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(group) = self
+            .tab_groups
+            .iter_mut()
+            .find(|group| group.id == group_id)
+        {
+            group.color_index = color_index;
+            cx.emit(Event::RearrangeTabs);
+            cx.notify();
+        }
+    }
+
+    /// Moves all of the group's tabs before the tab or group to their left.
+    pub fn move_tab_group_to_the_left(&mut self, group_id: TabGroupId, cx: &mut ViewContext<Self>) {
+        let Some(range) = self.tab_group_range(group_id) else {
+            return;
+        };
+        if range.start <= self.pinned_tab_count {
+            return;
+        }
+        let neighbor = self.tab_block_at(range.start - 1);
+        let active_item_id = self.active_item().map(|item| item.item_id());
+        self.items[neighbor.start..range.end].rotate_left(neighbor.len());
+        self.restore_active_item_index(active_item_id);
+        cx.emit(Event::RearrangeTabs);
+        cx.notify();
+    }
+
+    /// Moves all of the group's tabs after the tab or group to their right.
+    pub fn move_tab_group_to_the_right(
+        &mut self,
+        group_id: TabGroupId,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(range) = self.tab_group_range(group_id) else {
+            return;
+        };
+        if range.end >= self.items.len() {
+            return;
+        }
+        let neighbor = self.tab_block_at(range.end);
+        let active_item_id = self.active_item().map(|item| item.item_id());
+        self.items[range.start..neighbor.end].rotate_right(neighbor.len());
+        self.restore_active_item_index(active_item_id);
+        cx.emit(Event::RearrangeTabs);
+        cx.notify();
+    }
+
+    /// Removes the group, leaving its tabs open.
+    pub fn ungroup_tabs(&mut self, group_id: TabGroupId, cx: &mut ViewContext<Self>) {
+        self.tab_group_by_item.retain(|_, id| *id != group_id);
+        self.tab_groups.retain(|group| group.id != group_id);
+        cx.emit(Event::RearrangeTabs);
+        cx.notify();
+    }
+
+    pub fn close_tab_group(
+        &mut self,
+        group_id: TabGroupId,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<()>> {
+        let item_ids: Vec<_> = self
+            .tab_group_by_item
+            .iter()
+            .filter(|(_, id)| **id == group_id)
+            .map(|(item_id, _)| *item_id)
+            .collect();
+        self.close_items(cx, SaveIntent::Close, move |item_id| {
+            item_ids.contains(&item_id)
+        })
+    }
+
+    /// Restores groups of items that were added to the pane in the order they were saved.
+    pub(crate) fn restore_tab_groups(
+        &mut self,
+        tab_groups: Vec<TabGroup>,
+        tab_group_by_item: impl IntoIterator<Item = (EntityId, TabGroupId)>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        for (item_id, group_id) in tab_group_by_item {
+            let Some(ix) = self.index_for_item_id(item_id) else {
+                continue;
+            };
+            if !self.is_tab_pinned(ix) && tab_groups.iter().any(|group| group.id == group_id) {
+                self.tab_group_by_item.insert(item_id, group_id);
+            }
+        }
+        for group in tab_groups {
+            self.next_tab_group_id = self.next_tab_group_id.max(group.id + 1);
+            self.tab_groups.push(group);
+        }
+        self.remove_empty_tab_groups();
+        cx.notify();
+    }
+
     pub fn toggle_zoom(&mut self, _: &ToggleZoom, cx: &mut ViewContext<Self>) {
         if self.zoomed {
             cx.emit(Event::ZoomOut);
@@ -1026,10 +1399,11 @@ impl Pane {
         }
 
         let active_item_id = self.items[self.active_item_index].item_id();
+        let pinned_item_ids = self.pinned_item_ids();
         Some(self.close_items(
             cx,
             action.save_intent.unwrap_or(SaveIntent::Close),
-            move |item_id| item_id != active_item_id,
+            move |item_id| item_id != active_item_id && !pinned_item_ids.contains(&item_id),
         ))
     }
 
@@ -1040,6 +1414,7 @@ impl Pane {
     ) -> Option<Task<Result<()>>> {
         let item_ids: Vec<_> = self
             .items()
+            .skip(self.pinned_tab_count)
             .filter(|item| !item.is_dirty(cx))
             .map(|item| item.item_id())
             .collect();
@@ -1067,8 +1442,10 @@ impl Pane {
     ) -> Task<Result<()>> {
         let item_ids: Vec<_> = self
             .items()
-            .take_while(|item| item.item_id() != item_id)
-            .map(|item| item.item_id())
+            .enumerate()
+            .take_while(|(_, item)| item.item_id() != item_id)
+            .filter(|(ix, _)| !self.is_tab_pinned(*ix))
+            .map(|(_, item)| item.item_id())
             .collect();
         self.close_items(cx, SaveIntent::Close, move |item_id| {
             item_ids.contains(&item_id)
@@ -1094,9 +1471,11 @@ impl Pane {
     ) -> Task<Result<()>> {
         let item_ids: Vec<_> = self
             .items()
+            .enumerate()
             .rev()
-            .take_while(|item| item.item_id() != item_id)
-            .map(|item| item.item_id())
+            .take_while(|(_, item)| item.item_id() != item_id)
+            .filter(|(ix, _)| !self.is_tab_pinned(*ix))
+            .map(|(_, item)| item.item_id())
             .collect();
         self.close_items(cx, SaveIntent::Close, move |item_id| {
             item_ids.contains(&item_id)
@@ -1112,11 +1491,12 @@ impl Pane {
             return None;
         }
 
-        Some(
-            self.close_items(cx, action.save_intent.unwrap_or(SaveIntent::Close), |_| {
-                true
-            }),
-        )
+        let pinned_item_ids = self.pinned_item_ids();
+        Some(self.close_items(
+            cx,
+            action.save_intent.unwrap_or(SaveIntent::Close),
+            move |item_id| !pinned_item_ids.contains(&item_id),
+        ))
     }
 
     pub(super) fn file_names_for_prompt(
@@ -1304,6 +1684,12 @@ impl Pane {
         }
 
         let item = self.items.remove(item_index);
+        if item_index < self.pinned_tab_count {
+            self.pinned_tab_count -= 1;
+        }
+        if self.tab_group_by_item.remove(&item.item_id()).is_some() {
+            self.remove_empty_tab_groups();
+        }
 
         cx.emit(Event::RemoveItem {
             item_id: item.item_id(),
@@ -1518,6 +1904,12 @@ impl Pane {
         cx.emit(Event::Split(direction));
     }
 
+    /// Moves the given item into a new window, or all of the pane's items when `item_id`
+    /// is `None`.
+    pub fn move_to_new_window(&mut self, item_id: Option<EntityId>, cx: &mut ViewContext<Self>) {
+        cx.emit(Event::MoveToNewWindow { item_id });
+    }
+
     pub fn toolbar(&self) -> &View<Toolbar> {
         &self.toolbar
     }
@@ -1591,6 +1983,15 @@ impl Pane {
         let close_side = &ItemSettings::get_global(cx).close_position;
         let indicator = render_item_indicator(item.boxed_clone(), cx);
         let item_id = item.item_id();
+        let is_pinned = self.is_tab_pinned(ix);
+        let tab_group_id = self.tab_group_for_item(item_id);
+        let other_tab_groups = self
+            .tab_groups
+            .iter()
+            .enumerate()
+            .filter(|(_, group)| Some(group.id) != tab_group_id)
+            .map(|(position, group)| (position + 1, group.id))
+            .collect::<Vec<_>>();
         let is_first_item = ix == 0;
         let is_last_item = ix == self.items.len() - 1;
         let position_relative_to_active_item = ix.cmp(&self.active_item_index);
@@ -1675,6 +2076,13 @@ impl Pane {
                             .detach_and_log_err(cx);
                     })),
             )
+            .when(is_pinned, |tab| {
+                tab.child(
+                    Icon::new(IconName::Pin)
+                        .size(IconSize::XSmall)
+                        .color(Color::Muted),
+                )
+            })
             .child(label);
 
         let single_entry_to_resolve = {
@@ -1689,6 +2097,7 @@ impl Pane {
         let pane = cx.view().downgrade();
         right_click_menu(ix).trigger(tab).menu(move |cx| {
             let pane = pane.clone();
+            let other_tab_groups = other_tab_groups.clone();
             ContextMenu::build(cx, move |mut menu, cx| {
                 if let Some(pane) = pane.upgrade() {
                     menu = menu
@@ -1704,8 +2113,11 @@ impl Pane {
                             "Close Others",
                             Some(Box::new(CloseInactiveItems { save_intent: None })),
                             cx.handler_for(&pane, move |pane, cx| {
-                                pane.close_items(cx, SaveIntent::Close, |id| id != item_id)
-                                    .detach_and_log_err(cx);
+                                let pinned_item_ids = pane.pinned_item_ids();
+                                pane.close_items(cx, SaveIntent::Close, move |id| {
+                                    id != item_id && !pinned_item_ids.contains(&id)
+                                })
+                                .detach_and_log_err(cx);
                             }),
                         )
                         .separator()
@@ -1745,7 +2157,55 @@ impl Pane {
                                     task.detach_and_log_err(cx)
                                 }
                             }),
+                        )
+                        .separator()
+                        .entry(
+                            if is_pinned { "Unpin Tab" } else { "Pin Tab" },
+                            Some(Box::new(TogglePinTab)),
+                            cx.handler_for(&pane, move |pane, cx| {
+                                if is_pinned {
+                                    pane.unpin_item(item_id, cx);
+                                } else {
+                                    pane.pin_item(item_id, cx);
+                                }
+                            }),
+                        );
+
+                    if !is_pinned {
+                        menu = menu.entry(
+                            "Add to New Group",
+                            None,
+                            cx.handler_for(&pane, move |pane, cx| {
+                                pane.add_item_to_new_tab_group(item_id, cx);
+                            }),
                         );
+                        for (position, group_id) in other_tab_groups.iter().copied() {
+                            menu = menu.entry(
+                                format!("Add to Group {position}"),
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.add_item_to_tab_group(item_id, group_id, cx);
+                                }),
+                            );
+                        }
+                        if tab_group_id.is_some() {
+                            menu = menu.entry(
+                                "Remove from Group",
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.remove_item_from_tab_group(item_id, cx);
+                                }),
+                            );
+                        }
+                    }
+
+                    menu = menu.separator().entry(
+                        "Move to New Window",
+                        Some(Box::new(MoveItemToNewWindow)),
+                        cx.handler_for(&pane, move |pane, cx| {
+                            pane.move_to_new_window(Some(item_id), cx);
+                        }),
+                    );
 
                     if let Some(entry) = single_entry_to_resolve {
                         let parent_abs_path = pane
@@ -1850,7 +2310,43 @@ impl Pane {
                     .iter()
                     .enumerate()
                     .zip(tab_details(&self.items, cx))
-                    .map(|((ix, item), detail)| self.render_tab(ix, item, detail, cx)),
+                    .map(|((ix, item), detail)| {
+                        let Some(group) = self.tab_group_at(ix).and_then(|group_id| {
+                            self.tab_groups.iter().find(|group| group.id == group_id)
+                        }) else {
+                            return self.render_tab(ix, item, detail, cx).into_any_element();
+                        };
+
+                        // Every item gets exactly one child, so that the scroll handle's
+                        // children line up with the items.
+                        let is_first_in_group =
+                            ix == 0 || self.tab_group_at(ix - 1) != Some(group.id);
+                        let is_hidden = group.collapsed && ix != self.active_item_index;
+                        h_flex()
+                            .when(is_first_in_group, |this| {
+                                this.child(self.render_tab_group_label(*group, cx))
+                            })
+                            .when(!is_hidden, |this| {
+                                this.child(
+                                    div()
+                                        .relative()
+                                        .child(self.render_tab(ix, item, detail, cx))
+                                        .child(
+                                            div()
+                                                .absolute()
+                                                .left_0()
+                                                .right_0()
+                                                .bottom_0()
+                                                .h(px(2.))
+                                                .bg(cx
+                                                    .theme()
+                                                    .accents()
+                                                    .color_for_index(group.color_index)),
+                                        ),
+                                )
+                            })
+                            .into_any_element()
+                    }),
             )
             .child(
                 div()
@@ -1887,6 +2383,120 @@ impl Pane {
             )
     }
 
+    fn render_tab_group_label(
+        &self,
+        group: TabGroup,
+        cx: &mut ViewContext<'_, Pane>,
+    ) -> impl IntoElement {
+        let group_id = group.id;
+        let position = self
+            .tab_groups
+            .iter()
+            .position(|group| group.id == group_id)
+            .unwrap_or_default()
+            + 1;
+        let item_count = self
+            .tab_group_by_item
+            .values()
+            .filter(|id| **id == group_id)
+            .count();
+
+        let label = h_flex()
+            .id(("tab-group", group_id))
+            .h(rems(Tab::CONTAINER_HEIGHT_IN_REMS))
+            .px_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .bg(cx.theme().colors().tab_inactive_background)
+            .cursor_pointer()
+            .child(
+                div()
+                    .px_1()
+                    .rounded_sm()
+                    .bg(cx.theme().accents().color_for_index(group.color_index))
+                    .child(
+                        Label::new(item_count.to_string())
+                            .size(LabelSize::XSmall)
+                            .color(Color::Custom(cx.theme().colors().editor_background)),
+                    ),
+            )
+            .on_click(cx.listener(move |pane, _, cx| {
+                pane.toggle_tab_group_collapsed(group_id, cx);
+            }))
+            .tooltip(move |cx| {
+                if group.collapsed {
+                    Tooltip::text(format!("Expand Group {position}"), cx)
+                } else {
+                    Tooltip::text(format!("Collapse Group {position}"), cx)
+                }
+            });
+
+        let pane = cx.view().downgrade();
+        right_click_menu(("tab-group-menu", group_id))
+            .trigger(label)
+            .menu(move |cx| {
+                let pane = pane.clone();
+                ContextMenu::build(cx, move |mut menu, cx| {
+                    if let Some(pane) = pane.upgrade() {
+                        menu = menu
+                            .entry(
+                                if group.collapsed {
+                                    "Expand Group"
+                                } else {
+                                    "Collapse Group"
+                                },
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.toggle_tab_group_collapsed(group_id, cx);
+                                }),
+                            )
+                            .entry(
+                                "Change Color",
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.set_tab_group_color(
+                                        group_id,
+                                        group.color_index.wrapping_add(1),
+                                        cx,
+                                    );
+                                }),
+                            )
+                            .separator()
+                            .entry(
+                                "Move Group Left",
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.move_tab_group_to_the_left(group_id, cx);
+                                }),
+                            )
+                            .entry(
+                                "Move Group Right",
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.move_tab_group_to_the_right(group_id, cx);
+                                }),
+                            )
+                            .separator()
+                            .entry(
+                                "Ungroup",
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.ungroup_tabs(group_id, cx);
+                                }),
+                            )
+                            .entry(
+                                "Close Group",
+                                None,
+                                cx.handler_for(&pane, move |pane, cx| {
+                                    pane.close_tab_group(group_id, cx).detach_and_log_err(cx);
+                                }),
+                            );
+                    }
+                    menu
+                })
+            })
+    }
+
     pub fn render_menu_overlay(menu: &View<ContextMenu>) -> Div {
         div().absolute().bottom_0().right_0().size_0().child(
             deferred(
@@ -2126,6 +2736,14 @@ impl Render for Pane {
                 cx.listener(|pane, _: &SplitRight, cx| pane.split(SplitDirection::Right, cx)),
             )
             .on_action(cx.listener(|pane, _: &SplitDown, cx| pane.split(SplitDirection::Down, cx)))
+            .on_action(cx.listener(|pane, _: &MoveItemToNewWindow, cx| {
+                if let Some(item) = pane.active_item() {
+                    pane.move_to_new_window(Some(item.item_id()), cx);
+                }
+            }))
+            .on_action(cx.listener(|pane, _: &MovePaneToNewWindow, cx| {
+                pane.move_to_new_window(None, cx);
+            }))
             .on_action(cx.listener(|pane, _: &GoBack, cx| pane.navigate_backward(cx)))
             .on_action(cx.listener(|pane, _: &GoForward, cx| pane.navigate_forward(cx)))
             .on_action(cx.listener(Pane::toggle_zoom))
@@ -2141,6 +2759,7 @@ impl Render for Pane {
             .on_action(cx.listener(|pane: &mut Pane, _: &ActivateNextItem, cx| {
                 pane.activate_next_item(true, cx);
             }))
+            .on_action(cx.listener(Pane::toggle_pin_tab))
             .when(PreviewTabsSettings::get_global(cx).enabled, |this| {
                 this.on_action(cx.listener(|pane: &mut Pane, _: &TogglePreviewTab, cx| {
                     if let Some(active_item_id) = pane.active_item().map(|i| i.item_id()) {
@@ -2990,6 +3609,99 @@ mod tests {
         assert_item_labels(&pane, [], cx);
     }
 
+    #[gpui::test]
+    async fn test_pinned_tabs(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let [_, _, _, d, _] = set_labeled_items(&pane, ["A", "B", "C*", "D", "E"], cx);
+
+        // Pinned items are moved to the left.
+        pane.update(cx, |pane, cx| pane.pin_item(d.item_id(), cx));
+        assert_item_labels(&pane, ["D", "A", "B", "C*", "E"], cx);
+
+        // Pinned items aren't closed by "close all".
+        pane.update(cx, |pane, cx| {
+            pane.close_all_items(&CloseAllItems { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["D*"], cx);
+
+        // Other items can't be moved before pinned items.
+        let f = add_labeled_item(&pane, "F", false, cx);
+        assert_item_labels(&pane, ["D", "F*"], cx);
+        pane.update(cx, |pane, cx| {
+            pane.add_item(f.boxed_clone(), false, false, Some(0), cx)
+        });
+        assert_item_labels(&pane, ["D", "F*"], cx);
+
+        // Pinned items aren't closed by "close others", until they're unpinned.
+        pane.update(cx, |pane, cx| {
+            pane.close_inactive_items(&CloseInactiveItems { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["D", "F*"], cx);
+
+        pane.update(cx, |pane, cx| pane.unpin_item(d.item_id(), cx));
+        pane.update(cx, |pane, cx| {
+            pane.close_inactive_items(&CloseInactiveItems { save_intent: None }, cx)
+        })
+        .unwrap()
+        .await
+        .unwrap();
+        assert_item_labels(&pane, ["F*"], cx);
+    }
+
+    #[gpui::test]
+    async fn test_tab_groups(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+
+        let project = Project::test(fs, None, cx).await;
+        let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+        let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+
+        let [a, _, c, d, _] = set_labeled_items(&pane, ["A", "B", "C*", "D", "E"], cx);
+
+        // Items added to a group are moved next to its other items.
+        let group_id = pane
+            .update(cx, |pane, cx| {
+                pane.add_item_to_new_tab_group(a.item_id(), cx)
+            })
+            .unwrap();
+        pane.update(cx, |pane, cx| {
+            pane.add_item_to_tab_group(c.item_id(), group_id, cx)
+        });
+        assert_item_labels(&pane, ["A", "C*", "B", "D", "E"], cx);
+
+        // Groups are moved as a whole.
+        pane.update(cx, |pane, cx| {
+            pane.move_tab_group_to_the_right(group_id, cx)
+        });
+        assert_item_labels(&pane, ["B", "A", "C*", "D", "E"], cx);
+
+        // Items moved in between the items of a group join it.
+        pane.update(cx, |pane, cx| {
+            pane.add_item(d.boxed_clone(), false, false, Some(2), cx);
+            assert_eq!(pane.tab_group_for_item(d.item_id()), Some(group_id));
+        });
+        assert_item_labels(&pane, ["B", "A", "D*", "C", "E"], cx);
+
+        pane.update(cx, |pane, cx| pane.close_tab_group(group_id, cx))
+            .await
+            .unwrap();
+        assert_item_labels(&pane, ["B", "E*"], cx);
+        pane.update(cx, |pane, _| assert!(pane.tab_groups().is_empty()));
+    }
+
     fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
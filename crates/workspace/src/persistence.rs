@@ -1,6 +1,6 @@
 pub mod model;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, bail, Context, Result};
 use client::DevServerProjectId;
@@ -16,10 +16,11 @@ use ui::px;
 use util::ResultExt;
 use uuid::Uuid;
 
-use crate::WorkspaceId;
+use crate::{file_history, WorkspaceId};
 
 use model::{
-    GroupId, LocalPaths, PaneId, SerializedItem, SerializedPane, SerializedPaneGroup,
+    GroupId, LocalPaths, PaneId, SerializedBookmark, SerializedItem, SerializedLayout,
+    SerializedPane, SerializedPaneGroup, SerializedSecondaryWindow, SerializedTabGroup,
     SerializedWorkspace,
 };
 
@@ -170,6 +171,7 @@ define_connection! {
     //   display: Option<Uuid>, // Display id
     //   fullscreen: Option<bool>, // Is the window fullscreen?
     //   centered_layout: Option<bool>, // Is the Centered Layout mode activated?
+    //   theme: Option<String>, // Name of the theme overriding the active theme in this workspace
     // )
     //
     // pane_groups(
@@ -185,6 +187,7 @@ define_connection! {
     //     pane_id: usize, // Primary key for panes
     //     workspace_id: usize, // References workspaces table
     //     active: bool,
+    //     tab_groups: Option<String>, // A JSON array of SerializedTabGroup
     // )
     //
     // center_panes(
@@ -200,7 +203,43 @@ define_connection! {
     //     kind: String, // Indicates which view this connects to. This is the key in the item_deserializers global
     //     position: usize, // Position of the item in the parent pane. This is equivalent to panes' position column
     //     active: bool, // Indicates if this item is the active one in the pane
-    //     preview: bool // Indicates if this item is a preview item
+    //     preview: bool, // Indicates if this item is a preview item
+    //     pinned: bool, // Indicates if this item is pinned
+    //     tab_group: Option<u64>, // The id of the pane's tab group this item is in
+    // )
+    //
+    // bookmarks(
+    //     workspace_id: usize, // References workspaces table
+    //     position: usize, // Position of the bookmark in the workspace's bookmark list
+    //     abs_path: PathBuf, // Absolute path of the bookmarked file
+    //     row: u32, // Zero-based row of the bookmarked line
+    //     label: Option<String>, // User-provided label
+    // )
+    //
+    // workspace_layouts(
+    //     workspace_id: usize, // References workspaces table
+    //     name: String, // Name given to the layout by the user
+    //     layout: String, // A JSON serialized SerializedLayout
+    //     timestamp: String, // UTC YYYY-MM-DD HH:MM:SS
+    // )
+    //
+    // secondary_windows(
+    //     workspace_id: usize, // References workspaces table
+    //     position: usize, // Order in which the windows were opened
+    //     layout: String, // A JSON serialized SerializedLayout of the window's panes and docks
+    //     window_state: String, // WindowBounds Discriminant
+    //     window_x: Option<f32>, // WindowBounds::Fixed RectF x
+    //     window_y: Option<f32>, // WindowBounds::Fixed RectF y
+    //     window_width: Option<f32>, // WindowBounds::Fixed RectF width
+    //     window_height: Option<f32>, // WindowBounds::Fixed RectF height
+    //     display: Option<Uuid>, // Display id
+    // )
+    //
+    // file_history(
+    //     workspace_id: usize, // References workspaces table
+    //     abs_path: PathBuf, // Absolute path of the opened file
+    //     score: f64, // Number of times the file was opened, decayed as of last_opened
+    //     last_opened: i64, // Seconds since the Unix epoch
     // )
     pub static ref DB: WorkspaceDb<()> =
     &[sql!(
@@ -344,6 +383,68 @@ define_connection! {
     sql!(
         ALTER TABLE workspaces ADD COLUMN local_paths_order BLOB;
     ),
+    sql!(
+        CREATE TABLE bookmarks(
+            workspace_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            abs_path BLOB NOT NULL,
+            row INTEGER NOT NULL,
+            label TEXT,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+            PRIMARY KEY(workspace_id, position)
+        ) STRICT;
+    ),
+    sql!(
+        ALTER TABLE workspaces ADD COLUMN theme TEXT;
+    ),
+    sql!(
+        CREATE TABLE workspace_layouts(
+            workspace_id INTEGER NOT NULL,
+            name TEXT NOT NULL,
+            layout TEXT NOT NULL,
+            timestamp TEXT DEFAULT CURRENT_TIMESTAMP NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+            PRIMARY KEY(workspace_id, name)
+        ) STRICT;
+    ),
+    sql!(
+        CREATE TABLE secondary_windows(
+            workspace_id INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            layout TEXT NOT NULL,
+            window_state TEXT,
+            window_x REAL,
+            window_y REAL,
+            window_width REAL,
+            window_height REAL,
+            display BLOB,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+            PRIMARY KEY(workspace_id, position)
+        ) STRICT;
+    ),
+    sql!(
+        ALTER TABLE items ADD COLUMN pinned INTEGER; //bool
+        ALTER TABLE items ADD COLUMN tab_group INTEGER;
+        ALTER TABLE panes ADD COLUMN tab_groups TEXT;
+    ),
+    sql!(
+        CREATE TABLE file_history(
+            workspace_id INTEGER NOT NULL,
+            abs_path BLOB NOT NULL,
+            score REAL NOT NULL,
+            last_opened INTEGER NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE,
+            PRIMARY KEY(workspace_id, abs_path)
+        ) STRICT;
+    ),
     ];
 }
 
@@ -367,6 +468,7 @@ impl WorkspaceDb {
             window_bounds,
             display,
             centered_layout,
+            theme,
             docks,
         ): (
             WorkspaceId,
@@ -376,6 +478,7 @@ impl WorkspaceDb {
             Option<SerializedWindowBounds>,
             Option<Uuid>,
             Option<bool>,
+            Option<String>,
             DockStructure,
         ) = self
             .select_row_bound(sql! {
@@ -391,6 +494,7 @@ impl WorkspaceDb {
                     window_height,
                     display,
                     centered_layout,
+                    theme,
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
@@ -441,8 +545,19 @@ impl WorkspaceDb {
                 .log_err()?,
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
+            theme,
             display,
             docks,
+            bookmarks: self
+                .get_bookmarks(workspace_id)
+                .context("Getting bookmarks")
+                .log_err()
+                .unwrap_or_default(),
+            secondary_windows: self
+                .get_secondary_windows(workspace_id)
+                .context("Getting secondary windows")
+                .log_err()
+                .unwrap_or_default(),
         })
     }
 
@@ -460,6 +575,7 @@ impl WorkspaceDb {
             window_bounds,
             display,
             centered_layout,
+            theme,
             docks,
         ): (
             WorkspaceId,
@@ -469,6 +585,7 @@ impl WorkspaceDb {
             Option<SerializedWindowBounds>,
             Option<Uuid>,
             Option<bool>,
+            Option<String>,
             DockStructure,
         ) = self
             .select_row_bound(sql! {
@@ -484,6 +601,7 @@ impl WorkspaceDb {
                     window_height,
                     display,
                     centered_layout,
+                    theme,
                     left_dock_visible,
                     left_dock_active_panel,
                     left_dock_zoom,
@@ -534,8 +652,19 @@ impl WorkspaceDb {
                 .log_err()?,
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
+            theme,
             display,
             docks,
+            bookmarks: self
+                .get_bookmarks(workspace_id)
+                .context("Getting bookmarks")
+                .log_err()
+                .unwrap_or_default(),
+            secondary_windows: self
+                .get_secondary_windows(workspace_id)
+                .context("Getting secondary windows")
+                .log_err()
+                .unwrap_or_default(),
         })
     }
 
@@ -653,6 +782,12 @@ impl WorkspaceDb {
                 Self::save_pane_group(conn, workspace.id, &workspace.center_group, None)
                     .context("save pane group in save workspace")?;
 
+                Self::save_bookmarks(conn, workspace.id, &workspace.bookmarks)
+                    .context("save bookmarks in save workspace")?;
+
+                Self::save_secondary_windows(conn, workspace.id, &workspace.secondary_windows)
+                    .context("save secondary windows in save workspace")?;
+
                 Ok(())
             })
             .log_err();
@@ -779,6 +914,7 @@ impl WorkspaceDb {
                 SerializedPaneGroup::Pane(SerializedPane {
                     active: true,
                     children: vec![],
+                    tab_groups: vec![],
                 })
             }))
     }
@@ -836,10 +972,11 @@ impl WorkspaceDb {
                     flexes,
                 })
             } else if let Some((pane_id, active)) = pane_id.zip(active) {
-                Ok(SerializedPaneGroup::Pane(SerializedPane::new(
-                    self.get_items(pane_id)?,
+                Ok(SerializedPaneGroup::Pane(SerializedPane {
+                    children: self.get_items(pane_id)?,
                     active,
-                )))
+                    tab_groups: self.get_tab_groups(pane_id)?,
+                }))
             } else {
                 bail!("Pane Group Child was neither a pane group or a pane");
             }
@@ -909,11 +1046,16 @@ impl WorkspaceDb {
         pane: &SerializedPane,
         parent: Option<(GroupId, usize)>,
     ) -> Result<PaneId> {
+        let tab_groups = if pane.tab_groups.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&pane.tab_groups)?)
+        };
         let pane_id = conn.select_row_bound::<_, i64>(sql!(
-            INSERT INTO panes(workspace_id, active)
-            VALUES (?, ?)
+            INSERT INTO panes(workspace_id, active, tab_groups)
+            VALUES (?, ?, ?)
             RETURNING pane_id
-        ))?((workspace_id, pane.active))?
+        ))?((workspace_id, pane.active, tab_groups))?
         .ok_or_else(|| anyhow!("Could not retrieve inserted pane_id"))?;
 
         let (parent_id, order) = parent.unzip();
@@ -929,12 +1071,24 @@ impl WorkspaceDb {
 
     fn get_items(&self, pane_id: PaneId) -> Result<Vec<SerializedItem>> {
         self.select_bound(sql!(
-            SELECT kind, item_id, active, preview FROM items
+            SELECT kind, item_id, active, preview, pinned, tab_group FROM items
             WHERE pane_id = ?
                 ORDER BY position
         ))?(pane_id)
     }
 
+    fn get_tab_groups(&self, pane_id: PaneId) -> Result<Vec<SerializedTabGroup>> {
+        let tab_groups = self.select_row_bound::<_, Option<String>>(sql!(
+            SELECT tab_groups FROM panes
+            WHERE pane_id = ?
+        ))?(pane_id)?
+        .flatten();
+        Ok(tab_groups
+            .map(|tab_groups| serde_json::from_str(&tab_groups))
+            .transpose()?
+            .unwrap_or_default())
+    }
+
     fn save_items(
         conn: &Connection,
         workspace_id: WorkspaceId,
@@ -942,7 +1096,7 @@ impl WorkspaceDb {
         items: &[SerializedItem],
     ) -> Result<()> {
         let mut insert = conn.exec_bound(sql!(
-            INSERT INTO items(workspace_id, pane_id, position, kind, item_id, active, preview) VALUES (?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO items(workspace_id, pane_id, position, kind, item_id, active, preview, pinned, tab_group) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
         )).context("Preparing insertion")?;
         for (position, item) in items.iter().enumerate() {
             insert((workspace_id, pane_id, position, item))?;
@@ -951,6 +1105,78 @@ impl WorkspaceDb {
         Ok(())
     }
 
+    fn get_bookmarks(&self, workspace_id: WorkspaceId) -> Result<Vec<SerializedBookmark>> {
+        self.select_bound(sql!(
+            SELECT abs_path, row, label FROM bookmarks
+            WHERE workspace_id = ?
+                ORDER BY position
+        ))?(workspace_id)
+    }
+
+    fn save_bookmarks(
+        conn: &Connection,
+        workspace_id: WorkspaceId,
+        bookmarks: &[SerializedBookmark],
+    ) -> Result<()> {
+        conn.exec_bound(sql!(
+            DELETE FROM bookmarks WHERE workspace_id = ?
+        ))?(workspace_id)
+        .context("Clearing old bookmarks")?;
+
+        let mut insert = conn.exec_bound(sql!(
+            INSERT INTO bookmarks(workspace_id, position, abs_path, row, label) VALUES (?, ?, ?, ?, ?)
+        )).context("Preparing insertion")?;
+        for (position, bookmark) in bookmarks.iter().enumerate() {
+            insert((workspace_id, position, bookmark))?;
+        }
+
+        Ok(())
+    }
+
+    fn get_secondary_windows(
+        &self,
+        workspace_id: WorkspaceId,
+    ) -> Result<Vec<SerializedSecondaryWindow>> {
+        self.select_bound(sql!(
+            SELECT layout, window_state, window_x, window_y, window_width, window_height, display
+            FROM secondary_windows
+            WHERE workspace_id = ?
+                ORDER BY position
+        ))?(workspace_id)
+    }
+
+    fn save_secondary_windows(
+        conn: &Connection,
+        workspace_id: WorkspaceId,
+        windows: &[SerializedSecondaryWindow],
+    ) -> Result<()> {
+        conn.exec_bound(sql!(
+            DELETE FROM secondary_windows WHERE workspace_id = ?
+        ))?(workspace_id)
+        .context("Clearing old secondary windows")?;
+
+        let mut insert = conn
+            .exec_bound(sql!(
+                INSERT INTO secondary_windows(
+                    workspace_id,
+                    position,
+                    layout,
+                    window_state,
+                    window_x,
+                    window_y,
+                    window_width,
+                    window_height,
+                    display
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ))
+            .context("Preparing insertion")?;
+        for (position, window) in windows.iter().enumerate() {
+            insert((workspace_id, position, window))?;
+        }
+
+        Ok(())
+    }
+
     query! {
         pub async fn update_timestamp(workspace_id: WorkspaceId) -> Result<()> {
             UPDATE workspaces
@@ -979,6 +1205,90 @@ impl WorkspaceDb {
             WHERE workspace_id = ?1
         }
     }
+
+    query! {
+        pub(crate) async fn set_theme(workspace_id: WorkspaceId, theme: Option<String>) -> Result<()> {
+            UPDATE workspaces
+            SET theme = ?2
+            WHERE workspace_id = ?1
+        }
+    }
+
+    query! {
+        pub(crate) fn layout_names(workspace_id: WorkspaceId) -> Result<Vec<String>> {
+            SELECT name FROM workspace_layouts
+            WHERE workspace_id = ?
+            ORDER BY name
+        }
+    }
+
+    query! {
+        pub(crate) fn layout(workspace_id: WorkspaceId, name: String) -> Result<Option<SerializedLayout>> {
+            SELECT layout FROM workspace_layouts
+            WHERE workspace_id = ? AND name = ?
+        }
+    }
+
+    query! {
+        pub(crate) async fn save_layout(workspace_id: WorkspaceId, name: String, layout: SerializedLayout) -> Result<()> {
+            INSERT INTO workspace_layouts(workspace_id, name, layout)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT DO UPDATE SET
+                layout = ?3,
+                timestamp = CURRENT_TIMESTAMP
+        }
+    }
+
+    query! {
+        pub(crate) async fn delete_layout(workspace_id: WorkspaceId, name: String) -> Result<()> {
+            DELETE FROM workspace_layouts
+            WHERE workspace_id = ? AND name = ?
+        }
+    }
+
+    query! {
+        pub(crate) fn file_history(workspace_id: WorkspaceId) -> Result<Vec<(PathBuf, f64, i64)>> {
+            SELECT abs_path, score, last_opened FROM file_history
+            WHERE workspace_id = ?
+        }
+    }
+
+    /// Records that the given file was opened at `timestamp`, adding one to its decayed
+    /// score. Only the `max_files` most recently opened files of the workspace are kept.
+    pub(crate) async fn record_file_opened(
+        &self,
+        workspace_id: WorkspaceId,
+        abs_path: PathBuf,
+        timestamp: i64,
+        max_files: usize,
+    ) -> Result<()> {
+        self.write(move |conn| {
+            conn.with_savepoint("record_file_opened", || {
+                let previous = conn.select_row_bound::<(WorkspaceId, &Path), (f64, i64)>(sql!(
+                    SELECT score, last_opened FROM file_history
+                    WHERE workspace_id = ? AND abs_path = ?
+                ))?((workspace_id, abs_path.as_path()))?;
+                let score = previous.map_or(0., |(score, last_opened)| {
+                    file_history::decayed_score(score, timestamp - last_opened)
+                }) + 1.;
+
+                conn.exec_bound(sql!(
+                    INSERT OR REPLACE INTO file_history(workspace_id, abs_path, score, last_opened)
+                    VALUES (?, ?, ?, ?)
+                ))?((workspace_id, abs_path.as_path(), score, timestamp))?;
+                conn.exec_bound(sql!(
+                    DELETE FROM file_history
+                    WHERE workspace_id = ?1 AND abs_path NOT IN (
+                        SELECT abs_path FROM file_history
+                        WHERE workspace_id = ?1
+                        ORDER BY last_opened DESC
+                        LIMIT ?2
+                    )
+                ))?((workspace_id, max_files))
+            })
+        })
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -986,6 +1296,7 @@ mod tests {
     use super::*;
     use db::open_test_db;
     use gpui;
+    use model::{SerializedLayoutDock, SerializedLayoutGroup, SerializedLayoutItem};
 
     #[gpui::test]
     async fn test_next_id_stability() {
@@ -1065,6 +1376,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            theme: None,
+            bookmarks: Default::default(),
+            secondary_windows: Default::default(),
         };
 
         let workspace_2 = SerializedWorkspace {
@@ -1075,6 +1389,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            theme: None,
+            bookmarks: Default::default(),
+            secondary_windows: Default::default(),
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -1177,6 +1494,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            theme: None,
+            bookmarks: Default::default(),
+            secondary_windows: Default::default(),
         };
 
         db.save_workspace(workspace.clone()).await;
@@ -1192,6 +1512,84 @@ mod tests {
         assert_eq!(workspace, round_trip_workspace.unwrap());
     }
 
+    #[gpui::test]
+    async fn test_workspace_theme() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_workspace_theme").await);
+
+        let workspace = default_workspace(&["/tmp"], &Default::default());
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap().theme, None);
+
+        db.set_theme(workspace.id, Some("One Light".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(
+            db.workspace_for_roots(&["/tmp"]).unwrap().theme.as_deref(),
+            Some("One Light")
+        );
+
+        // Saving the workspace again keeps its theme.
+        db.save_workspace(workspace.clone()).await;
+        assert_eq!(
+            db.workspace_for_roots(&["/tmp"]).unwrap().theme.as_deref(),
+            Some("One Light")
+        );
+
+        db.set_theme(workspace.id, None).await.unwrap();
+        assert_eq!(db.workspace_for_roots(&["/tmp"]).unwrap().theme, None);
+    }
+
+    #[gpui::test]
+    async fn test_file_history() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_file_history").await);
+
+        let workspace = default_workspace(&["/tmp"], &Default::default());
+        db.save_workspace(workspace.clone()).await;
+        let week = 7 * 24 * 60 * 60;
+        let history = |db: &WorkspaceDb| {
+            let mut history = db.file_history(workspace.id).unwrap();
+            history.sort_by(|a, b| a.0.cmp(&b.0));
+            history
+        };
+
+        db.record_file_opened(workspace.id, "/tmp/a.rs".into(), 0, 2)
+            .await
+            .unwrap();
+        db.record_file_opened(workspace.id, "/tmp/a.rs".into(), week, 2)
+            .await
+            .unwrap();
+        db.record_file_opened(workspace.id, "/tmp/b.rs".into(), week / 2, 2)
+            .await
+            .unwrap();
+        // Scores of earlier openings halve every week.
+        assert_eq!(
+            history(&db),
+            vec![
+                ("/tmp/a.rs".into(), 1.5, week),
+                ("/tmp/b.rs".into(), 1., week / 2),
+            ]
+        );
+
+        // Only the most recently opened files are kept.
+        db.record_file_opened(workspace.id, "/tmp/c.rs".into(), 2 * week, 2)
+            .await
+            .unwrap();
+        db.record_file_opened(workspace.id, "/tmp/a.rs".into(), 3 * week, 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            history(&db),
+            vec![
+                ("/tmp/a.rs".into(), 1.375, 3 * week),
+                ("/tmp/c.rs".into(), 1., 2 * week),
+            ]
+        );
+    }
+
     #[gpui::test]
     async fn test_workspace_assignment() {
         env_logger::try_init().ok();
@@ -1209,6 +1607,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            theme: None,
+            bookmarks: Default::default(),
+            secondary_windows: Default::default(),
         };
 
         let mut workspace_2 = SerializedWorkspace {
@@ -1219,6 +1620,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            theme: None,
+            bookmarks: Default::default(),
+            secondary_windows: Default::default(),
         };
 
         db.save_workspace(workspace_1.clone()).await;
@@ -1259,6 +1663,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            theme: None,
+            bookmarks: Default::default(),
+            secondary_windows: Default::default(),
         };
 
         db.save_workspace(workspace_3.clone()).await;
@@ -1293,6 +1700,9 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
+            theme: None,
+            bookmarks: Default::default(),
+            secondary_windows: Default::default(),
         }
     }
 
@@ -1418,4 +1828,207 @@ mod tests {
 
         assert_eq!(workspace.center_group, new_workspace.center_group);
     }
+
+    #[gpui::test]
+    async fn test_pinned_and_grouped_items() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_pinned_and_grouped_items").await);
+
+        let pinned_item = SerializedItem {
+            pinned: true,
+            ..SerializedItem::new("Terminal", 1, false, false)
+        };
+        let grouped_item = |item_id, active| SerializedItem {
+            tab_group: Some(3),
+            ..SerializedItem::new("Terminal", item_id, active, false)
+        };
+        let center_pane = SerializedPaneGroup::Pane(SerializedPane {
+            children: vec![
+                pinned_item,
+                grouped_item(2, true),
+                grouped_item(3, false),
+                SerializedItem::new("Terminal", 4, false, false),
+            ],
+            active: true,
+            tab_groups: vec![SerializedTabGroup {
+                id: 3,
+                color_index: 5,
+                collapsed: true,
+            }],
+        });
+
+        let id = &["/tmp"];
+        let workspace = default_workspace(id, &center_pane);
+        db.save_workspace(workspace.clone()).await;
+
+        let new_workspace = db.workspace_for_roots(id).unwrap();
+        assert_eq!(workspace.center_group, new_workspace.center_group);
+    }
+
+    #[gpui::test]
+    async fn test_bookmarks() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_bookmarks").await);
+
+        let id = &["/tmp"];
+        let mut workspace = default_workspace(id, &Default::default());
+        workspace.bookmarks = vec![
+            SerializedBookmark {
+                abs_path: "/tmp/a.rs".into(),
+                row: 10,
+                label: None,
+            },
+            SerializedBookmark {
+                abs_path: "/tmp/b.rs".into(),
+                row: 2,
+                label: Some("entry point".to_string()),
+            },
+        ];
+
+        db.save_workspace(workspace.clone()).await;
+        let new_workspace = db.workspace_for_roots(id).unwrap();
+        assert_eq!(workspace.bookmarks, new_workspace.bookmarks);
+
+        workspace.bookmarks.remove(0);
+        db.save_workspace(workspace.clone()).await;
+        let new_workspace = db.workspace_for_roots(id).unwrap();
+        assert_eq!(workspace.bookmarks, new_workspace.bookmarks);
+    }
+
+    #[gpui::test]
+    async fn test_secondary_windows() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_secondary_windows").await);
+
+        let id = &["/tmp"];
+        let mut workspace = default_workspace(id, &Default::default());
+        let layout = SerializedLayout {
+            center_group: SerializedLayoutGroup::Pane {
+                active: true,
+                zoomed: false,
+                items: vec![SerializedLayoutItem {
+                    kind: Some("Editor".to_string()),
+                    item_id: 3,
+                    abs_path: Some("/tmp/tests.rs".into()),
+                    active: true,
+                }],
+            },
+            left_dock: SerializedLayoutDock::default(),
+            right_dock: SerializedLayoutDock::default(),
+            bottom_dock: SerializedLayoutDock::default(),
+        };
+        workspace.secondary_windows = vec![
+            SerializedSecondaryWindow {
+                layout: layout.clone(),
+                window_bounds: Some(SerializedWindowBounds(WindowBounds::Windowed(Bounds {
+                    origin: point(px(1920.), px(0.)),
+                    size: size(px(800.), px(600.)),
+                }))),
+                display: Some(Uuid::new_v4()),
+            },
+            SerializedSecondaryWindow {
+                layout,
+                window_bounds: None,
+                display: None,
+            },
+        ];
+
+        db.save_workspace(workspace.clone()).await;
+        let new_workspace = db.workspace_for_roots(id).unwrap();
+        assert_eq!(workspace.secondary_windows, new_workspace.secondary_windows);
+
+        workspace.secondary_windows.remove(0);
+        db.save_workspace(workspace.clone()).await;
+        let new_workspace = db.workspace_for_roots(id).unwrap();
+        assert_eq!(workspace.secondary_windows, new_workspace.secondary_windows);
+    }
+
+    #[gpui::test]
+    async fn test_layouts() {
+        env_logger::try_init().ok();
+
+        let db = WorkspaceDb(open_test_db("test_layouts").await);
+
+        let id = &["/tmp"];
+        let workspace = default_workspace(id, &Default::default());
+        db.save_workspace(workspace.clone()).await;
+
+        let review = SerializedLayout {
+            center_group: SerializedLayoutGroup::Group {
+                axis: gpui::Axis::Horizontal,
+                flexes: vec![1.5, 0.5],
+                children: vec![
+                    SerializedLayoutGroup::Pane {
+                        active: true,
+                        zoomed: false,
+                        items: vec![SerializedLayoutItem {
+                            kind: Some("Editor".to_string()),
+                            item_id: 5,
+                            abs_path: Some("/tmp/a.rs".into()),
+                            active: true,
+                        }],
+                    },
+                    SerializedLayoutGroup::Pane {
+                        active: false,
+                        zoomed: false,
+                        items: vec![SerializedLayoutItem {
+                            kind: Some("Terminal".to_string()),
+                            item_id: 6,
+                            abs_path: None,
+                            active: true,
+                        }],
+                    },
+                ],
+            },
+            left_dock: SerializedLayoutDock {
+                visible: true,
+                active_panel: Some("ProjectPanel".to_string()),
+                zoom: false,
+                size: Some(240.),
+            },
+            right_dock: SerializedLayoutDock::default(),
+            bottom_dock: SerializedLayoutDock::default(),
+        };
+        let mut debug = review.clone();
+        debug.bottom_dock = SerializedLayoutDock {
+            visible: true,
+            active_panel: Some("TerminalPanel".to_string()),
+            zoom: true,
+            size: None,
+        };
+
+        db.save_layout(workspace.id, "review".to_string(), review.clone())
+            .await
+            .unwrap();
+        db.save_layout(workspace.id, "debug".to_string(), review.clone())
+            .await
+            .unwrap();
+        db.save_layout(workspace.id, "debug".to_string(), debug.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            db.layout_names(workspace.id).unwrap(),
+            vec!["debug".to_string(), "review".to_string()]
+        );
+        assert_eq!(
+            db.layout(workspace.id, "review".to_string()).unwrap(),
+            Some(review)
+        );
+        assert_eq!(
+            db.layout(workspace.id, "debug".to_string()).unwrap(),
+            Some(debug)
+        );
+
+        db.delete_layout(workspace.id, "review".to_string())
+            .await
+            .unwrap();
+        assert_eq!(
+            db.layout_names(workspace.id).unwrap(),
+            vec!["debug".to_string()]
+        );
+        assert_eq!(db.layout(workspace.id, "review".to_string()).unwrap(), None);
+    }
 }
@@ -1,5 +1,9 @@
 use super::{SerializedAxis, SerializedWindowBounds};
-use crate::{item::ItemHandle, ItemDeserializers, Member, Pane, PaneAxis, Workspace, WorkspaceId};
+use crate::{
+    item::ItemHandle,
+    pane::{TabGroup, TabGroupId},
+    ItemDeserializers, Member, Pane, PaneAxis, Workspace, WorkspaceId,
+};
 use anyhow::{Context, Result};
 use async_recursion::async_recursion;
 use client::DevServerProjectId;
@@ -152,8 +156,148 @@ pub(crate) struct SerializedWorkspace {
     pub(crate) center_group: SerializedPaneGroup,
     pub(crate) window_bounds: Option<SerializedWindowBounds>,
     pub(crate) centered_layout: bool,
+    pub(crate) theme: Option<String>,
     pub(crate) display: Option<Uuid>,
     pub(crate) docks: DockStructure,
+    pub(crate) bookmarks: Vec<SerializedBookmark>,
+    pub(crate) secondary_windows: Vec<SerializedSecondaryWindow>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct SerializedBookmark {
+    pub(crate) abs_path: PathBuf,
+    pub(crate) row: u32,
+    pub(crate) label: Option<String>,
+}
+
+impl StaticColumnCount for SerializedBookmark {
+    fn column_count() -> usize {
+        3
+    }
+}
+
+impl Bind for &SerializedBookmark {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        let next_index = statement.bind(&self.abs_path, start_index)?;
+        let next_index = statement.bind(&self.row, next_index)?;
+        statement.bind(&self.label, next_index)
+    }
+}
+
+impl Column for SerializedBookmark {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (abs_path, next_index) = PathBuf::column(statement, start_index)?;
+        let (row, next_index) = u32::column(statement, next_index)?;
+        let (label, next_index) = Option::<String>::column(statement, next_index)?;
+        Ok((
+            Self {
+                abs_path,
+                row,
+                label,
+            },
+            next_index,
+        ))
+    }
+}
+
+/// A named arrangement of a workspace's panes, their items and its docks, which can be
+/// switched to without reopening the workspace. It's stored as JSON, because unlike the
+/// workspace's own layout, it doesn't need to be updated on every change.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct SerializedLayout {
+    pub(crate) center_group: SerializedLayoutGroup,
+    pub(crate) left_dock: SerializedLayoutDock,
+    pub(crate) right_dock: SerializedLayoutDock,
+    pub(crate) bottom_dock: SerializedLayoutDock,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) enum SerializedLayoutGroup {
+    Group {
+        axis: gpui::Axis,
+        flexes: Vec<f32>,
+        children: Vec<SerializedLayoutGroup>,
+    },
+    Pane {
+        active: bool,
+        zoomed: bool,
+        items: Vec<SerializedLayoutItem>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub(crate) struct SerializedLayoutItem {
+    pub(crate) kind: Option<String>,
+    pub(crate) item_id: ItemId,
+    /// The file shown by the item, which is reopened rather than deserializing the item
+    /// by its id, as item ids are only unique within a session.
+    pub(crate) abs_path: Option<PathBuf>,
+    pub(crate) active: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct SerializedLayoutDock {
+    pub(crate) visible: bool,
+    pub(crate) active_panel: Option<String>,
+    pub(crate) zoom: bool,
+    pub(crate) size: Option<f32>,
+}
+
+impl StaticColumnCount for SerializedLayout {}
+impl Bind for SerializedLayout {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        statement.bind(&serde_json::to_string(self)?, start_index)
+    }
+}
+
+impl Column for SerializedLayout {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (json, next_index) = String::column(statement, start_index)?;
+        let layout = serde_json::from_str(&json).context("deserializing workspace layout")?;
+        Ok((layout, next_index))
+    }
+}
+
+/// A window that was opened from a workspace's window to show more of its project's
+/// items. It's restored along with the workspace.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct SerializedSecondaryWindow {
+    pub(crate) layout: SerializedLayout,
+    pub(crate) window_bounds: Option<SerializedWindowBounds>,
+    pub(crate) display: Option<Uuid>,
+}
+
+impl StaticColumnCount for SerializedSecondaryWindow {
+    fn column_count() -> usize {
+        SerializedLayout::column_count()
+            + Option::<SerializedWindowBounds>::column_count()
+            + Option::<Uuid>::column_count()
+    }
+}
+
+impl Bind for &SerializedSecondaryWindow {
+    fn bind(&self, statement: &Statement, start_index: i32) -> Result<i32> {
+        let next_index = statement.bind(&self.layout, start_index)?;
+        let next_index = statement.bind(&self.window_bounds, next_index)?;
+        statement.bind(&self.display, next_index)
+    }
+}
+
+impl Column for SerializedSecondaryWindow {
+    fn column(statement: &mut Statement, start_index: i32) -> Result<(Self, i32)> {
+        let (layout, next_index) = SerializedLayout::column(statement, start_index)?;
+        let (window_bounds, next_index) =
+            Option::<SerializedWindowBounds>::column(statement, next_index)?;
+        let (display, next_index) = Option::<Uuid>::column(statement, next_index)?;
+        Ok((
+            Self {
+                layout,
+                window_bounds,
+                display,
+            },
+            next_index,
+        ))
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -234,6 +378,7 @@ impl Default for SerializedPaneGroup {
         Self::Pane(SerializedPane {
             children: vec![SerializedItem::default()],
             active: false,
+            tab_groups: Vec::new(),
         })
     }
 }
@@ -310,11 +455,16 @@ impl SerializedPaneGroup {
 pub struct SerializedPane {
     pub(crate) active: bool,
     pub(crate) children: Vec<SerializedItem>,
+    pub(crate) tab_groups: Vec<SerializedTabGroup>,
 }
 
 impl SerializedPane {
     pub fn new(children: Vec<SerializedItem>, active: bool) -> Self {
-        SerializedPane { children, active }
+        SerializedPane {
+            children,
+            active,
+            tab_groups: Vec::new(),
+        }
     }
 
     pub async fn deserialize_to(
@@ -349,17 +499,43 @@ impl SerializedPane {
         }
 
         let mut items = Vec::new();
-        for item_handle in futures::future::join_all(item_tasks).await {
+        let mut tab_group_by_item = Vec::new();
+        for (item_handle, item) in futures::future::join_all(item_tasks)
+            .await
+            .into_iter()
+            .zip(&self.children)
+        {
             let item_handle = item_handle.log_err();
             items.push(item_handle.clone());
 
             if let Some(item_handle) = item_handle {
                 pane.update(cx, |pane, cx| {
                     pane.add_item(item_handle.clone(), true, true, None, cx);
+                    if item.pinned {
+                        pane.pin_item(item_handle.item_id(), cx);
+                    }
                 })?;
+                if let Some(tab_group) = item.tab_group {
+                    tab_group_by_item.push((item_handle.item_id(), tab_group));
+                }
             }
         }
 
+        if !self.tab_groups.is_empty() {
+            let tab_groups = self
+                .tab_groups
+                .iter()
+                .map(|group| TabGroup {
+                    id: group.id,
+                    color_index: group.color_index,
+                    collapsed: group.collapsed,
+                })
+                .collect();
+            pane.update(cx, |pane, cx| {
+                pane.restore_tab_groups(tab_groups, tab_group_by_item, cx);
+            })?;
+        }
+
         if let Some(active_item_index) = active_item_index {
             pane.update(cx, |pane, cx| {
                 pane.activate_item(active_item_index, false, false, cx);
@@ -388,6 +564,8 @@ pub struct SerializedItem {
     pub item_id: ItemId,
     pub active: bool,
     pub preview: bool,
+    pub pinned: bool,
+    pub tab_group: Option<TabGroupId>,
 }
 
 impl SerializedItem {
@@ -397,6 +575,8 @@ impl SerializedItem {
             item_id,
             active,
             preview,
+            pinned: false,
+            tab_group: None,
         }
     }
 }
@@ -409,13 +589,15 @@ impl Default for SerializedItem {
             item_id: 100000,
             active: false,
             preview: false,
+            pinned: false,
+            tab_group: None,
         }
     }
 }
 
 impl StaticColumnCount for SerializedItem {
     fn column_count() -> usize {
-        4
+        6
     }
 }
 impl Bind for &SerializedItem {
@@ -423,7 +605,9 @@ impl Bind for &SerializedItem {
         let next_index = statement.bind(&self.kind, start_index)?;
         let next_index = statement.bind(&self.item_id, next_index)?;
         let next_index = statement.bind(&self.active, next_index)?;
-        statement.bind(&self.preview, next_index)
+        let next_index = statement.bind(&self.preview, next_index)?;
+        let next_index = statement.bind(&self.pinned, next_index)?;
+        statement.bind(&self.tab_group, next_index)
     }
 }
 
@@ -433,14 +617,26 @@ impl Column for SerializedItem {
         let (item_id, next_index) = ItemId::column(statement, next_index)?;
         let (active, next_index) = bool::column(statement, next_index)?;
         let (preview, next_index) = bool::column(statement, next_index)?;
+        let (pinned, next_index) = bool::column(statement, next_index)?;
+        let (tab_group, next_index) = Option::<TabGroupId>::column(statement, next_index)?;
         Ok((
             SerializedItem {
                 kind,
                 item_id,
                 active,
                 preview,
+                pinned,
+                tab_group,
             },
             next_index,
         ))
     }
 }
+
+/// A group of tabs in a pane, stored as JSON alongside the pane.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct SerializedTabGroup {
+    pub id: TabGroupId,
+    pub color_index: u32,
+    pub collapsed: bool,
+}
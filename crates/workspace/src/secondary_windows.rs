@@ -0,0 +1,231 @@
+use crate::{
+    item::ItemHandle,
+    notifications::NotificationId,
+    persistence::{
+        model::{
+            SerializedLayout, SerializedLayoutDock, SerializedLayoutGroup, SerializedLayoutItem,
+            SerializedSecondaryWindow,
+        },
+        SerializedWindowBounds,
+    },
+    Pane, Toast, Workspace,
+};
+use anyhow::Result;
+use gpui::{
+    EntityId, Task, View, ViewContext, VisualContext as _, WindowBounds, WindowContext,
+    WindowHandle,
+};
+use uuid::Uuid;
+
+struct ItemsNotMovable;
+
+impl Workspace {
+    /// Moves an item of `pane` into a new window showing the same project, or every item
+    /// of the pane when `item_id` is `None`.
+    ///
+    /// Views can't be shown in more than one window, so the moved items are reopened in
+    /// the new window. Only items showing a file of the project can be moved, and since
+    /// their buffers are shared, they keep their unsaved changes.
+    pub fn move_to_new_window(
+        &mut self,
+        pane: View<Pane>,
+        item_id: Option<EntityId>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let project = self.project.read(cx);
+        let pane_state = pane.read(cx);
+        let active_item_id = pane_state.active_item().map(|item| item.item_id());
+        let mut movable_items = Vec::new();
+        let mut layout_items = Vec::new();
+        let mut has_unmovable_items = false;
+        for item in pane_state.items() {
+            if item_id.map_or(false, |item_id| item_id != item.item_id()) {
+                continue;
+            }
+            let abs_path = item
+                .project_path(cx)
+                .and_then(|path| project.absolute_path(&path, cx));
+            if abs_path.is_none() {
+                has_unmovable_items = true;
+                continue;
+            }
+            layout_items.push(SerializedLayoutItem {
+                kind: item.serialized_item_kind().map(str::to_string),
+                item_id: item.item_id().as_u64(),
+                abs_path,
+                active: item_id.is_some() || Some(item.item_id()) == active_item_id,
+            });
+            movable_items.push(item.boxed_clone());
+        }
+
+        if has_unmovable_items {
+            self.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ItemsNotMovable>(),
+                    "Only files can be moved to another window",
+                )
+                .autohide(),
+                cx,
+            );
+        }
+        if movable_items.is_empty() {
+            return;
+        }
+
+        let layout = SerializedLayout {
+            center_group: SerializedLayoutGroup::Pane {
+                active: true,
+                zoomed: false,
+                items: layout_items,
+            },
+            left_dock: SerializedLayoutDock::default(),
+            right_dock: SerializedLayoutDock::default(),
+            bottom_dock: SerializedLayoutDock::default(),
+        };
+        let open_window = self.open_secondary_window(layout, None, None, cx);
+        cx.spawn(|_, mut cx| async move {
+            open_window.await?;
+            pane.update(&mut cx, |pane, cx| {
+                for item in movable_items {
+                    if let Some(ix) = pane.index_for_item(&*item) {
+                        pane.remove_item(ix, false, true, cx);
+                    }
+                }
+            })
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Moves an item that was dragged from a pane of another window into `destination`,
+    /// by reopening its file there.
+    pub(crate) fn move_item_from_other_window(
+        &mut self,
+        source: View<Pane>,
+        destination: View<Pane>,
+        item_id: EntityId,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(item) = source
+            .read(cx)
+            .items()
+            .find(|item| item.item_id() == item_id)
+            .map(|item| item.boxed_clone())
+        else {
+            return;
+        };
+        let Some(project_path) = item.project_path(cx) else {
+            self.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ItemsNotMovable>(),
+                    "Only files can be moved to another window",
+                )
+                .autohide(),
+                cx,
+            );
+            return;
+        };
+
+        let source_window = cx
+            .windows()
+            .into_iter()
+            .filter_map(|window| window.downcast::<Workspace>())
+            .find(|window| {
+                window
+                    .read(cx)
+                    .map_or(false, |workspace| workspace.panes.contains(&source))
+            });
+        let open_path = self.open_path(project_path, Some(destination.downgrade()), true, cx);
+        cx.spawn(|_, mut cx| async move {
+            open_path.await?;
+            if let Some(source_window) = source_window {
+                source_window.update(&mut cx, |_, cx| {
+                    source.update(cx, |pane, cx| {
+                        if let Some(ix) = pane.index_for_item(&*item) {
+                            pane.remove_item(ix, false, true, cx);
+                        }
+                    })
+                })?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Opens a window that shows the same project as this workspace, arranged according
+    /// to `layout`. The window is saved and restored along with the workspace that this
+    /// window was first opened from.
+    pub(crate) fn open_secondary_window(
+        &mut self,
+        layout: SerializedLayout,
+        window_bounds: Option<WindowBounds>,
+        display: Option<Uuid>,
+        cx: &mut ViewContext<Self>,
+    ) -> Task<Result<WindowHandle<Workspace>>> {
+        let primary_window = self
+            .primary_window
+            .or_else(|| cx.window_handle().downcast::<Workspace>());
+        let workspace_id = self.database_id().unwrap_or_default();
+        let project = self.project.clone();
+        let app_state = self.app_state.clone();
+        cx.spawn(|_, mut cx| async move {
+            let mut options = cx.update(|cx| (app_state.build_window_options)(display, cx))?;
+            options.window_bounds = window_bounds;
+            let window = cx.update(|cx| {
+                cx.open_window(options, |cx| {
+                    cx.new_view(|cx| {
+                        let mut workspace = Workspace::new(None, project, app_state, cx);
+                        workspace.primary_window = primary_window;
+                        workspace
+                    })
+                })
+            })??;
+
+            if let Some(primary_window) = primary_window {
+                primary_window.update(&mut cx, |primary, cx| {
+                    primary.secondary_windows.push(window);
+                    primary.serialize_workspace(cx);
+                })?;
+            }
+            window
+                .update(&mut cx, |workspace, cx| {
+                    workspace.restore_layout(layout, workspace_id, cx)
+                })?
+                .await?;
+            Ok(window)
+        })
+    }
+
+    pub(crate) fn restore_secondary_windows(
+        &mut self,
+        windows: Vec<SerializedSecondaryWindow>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        for window in windows {
+            self.open_secondary_window(
+                window.layout,
+                window.window_bounds.map(|bounds| bounds.0),
+                window.display,
+                cx,
+            )
+            .detach_and_log_err(cx);
+        }
+    }
+
+    pub(crate) fn serialize_secondary_windows(
+        &self,
+        cx: &mut WindowContext,
+    ) -> Vec<SerializedSecondaryWindow> {
+        self.secondary_windows
+            .iter()
+            .filter_map(|window| {
+                window
+                    .update(cx, |workspace, cx| SerializedSecondaryWindow {
+                        layout: workspace.serialize_layout(cx),
+                        window_bounds: Some(SerializedWindowBounds(cx.window_bounds())),
+                        display: cx.display().and_then(|display| display.uuid().ok()),
+                    })
+                    .ok()
+            })
+            .collect()
+    }
+}
@@ -1,11 +1,15 @@
+pub mod bookmarks;
 pub mod dock;
+mod file_history;
 pub mod item;
+mod layouts;
 mod modal_layer;
 pub mod notifications;
 pub mod pane;
 pub mod pane_group;
 mod persistence;
 pub mod searchable;
+mod secondary_windows;
 pub mod shared_screen;
 mod status_bar;
 pub mod tasks;
@@ -13,6 +17,7 @@ mod toolbar;
 mod workspace_settings;
 
 use anyhow::{anyhow, Context as _, Result};
+use bookmarks::{BookmarkStore, BookmarkStoreEvent};
 use call::{call_settings::CallSettings, ActiveCall};
 use client::{
     proto::{self, ErrorCode, PeerId},
@@ -92,7 +97,10 @@ pub use workspace_settings::{
 };
 
 use crate::persistence::{
-    model::{DockData, DockStructure, SerializedItem, SerializedPane, SerializedPaneGroup},
+    model::{
+        DockData, DockStructure, SerializedItem, SerializedPane, SerializedPaneGroup,
+        SerializedTabGroup,
+    },
     SerializedAxis,
 };
 use crate::{notifications::NotificationId, persistence::model::LocalPathsOrder};
@@ -593,12 +601,17 @@ pub struct Workspace {
     titlebar_item: Option<AnyView>,
     notifications: Vec<(NotificationId, Box<dyn NotificationHandle>)>,
     project: Model<Project>,
+    bookmark_store: Model<BookmarkStore>,
     follower_states: HashMap<View<Pane>, FollowerState>,
     last_leaders_by_pane: HashMap<WeakView<Pane>, PeerId>,
     window_edited: bool,
     active_call: Option<(Model<ActiveCall>, Vec<Subscription>)>,
     leader_updates_tx: mpsc::UnboundedSender<(PeerId, proto::UpdateFollowers)>,
     database_id: Option<WorkspaceId>,
+    /// The window this window was opened from with `MoveItemToNewWindow`, which saves it
+    /// along with its own state.
+    primary_window: Option<WindowHandle<Workspace>>,
+    secondary_windows: Vec<WindowHandle<Workspace>>,
     app_state: Arc<AppState>,
     dispatching_keystrokes: Rc<RefCell<Vec<Keystroke>>>,
     _subscriptions: Vec<Subscription>,
@@ -606,8 +619,14 @@ pub struct Workspace {
     _observe_current_user: Task<Result<()>>,
     _schedule_serialize: Option<Task<()>>,
     pane_history_timestamp: Arc<AtomicUsize>,
+    /// The path of the last file whose opening was recorded in the file history.
+    last_opened_path: Option<ProjectPath>,
+    /// The score and last opening time of each file in the file history, keyed by
+    /// absolute path. It's loaded in the background and kept up to date as files are opened.
+    file_history: HashMap<PathBuf, (f64, i64)>,
     bounds: Bounds<Pixels>,
     centered_layout: bool,
+    theme_override: Option<String>,
     bounds_save_task_queued: Option<Task<()>>,
     on_prompt_for_new_path: Option<PromptForNewPath>,
     render_disconnected_overlay:
@@ -784,6 +803,7 @@ impl Workspace {
         });
 
         let modal_layer = cx.new_view(|_| ModalLayer::new());
+        let bookmark_store = cx.new_model(|_| BookmarkStore::default());
 
         let mut active_call = None;
         if let Some(call) = ActiveCall::try_global(cx) {
@@ -814,6 +834,8 @@ impl Workspace {
                                             display_uuid,
                                         ))
                                         .detach_and_log_err(cx);
+                                } else if this.primary_window.is_some() {
+                                    this.serialize_workspace(cx);
                                 }
                             }
                         }
@@ -842,7 +864,27 @@ impl Workspace {
                 this.serialize_workspace(cx);
                 cx.notify();
             }),
+            cx.observe(&bookmark_store, |_, _, cx| cx.notify()),
+            cx.subscribe(&bookmark_store, |this, _, event, cx| match event {
+                BookmarkStoreEvent::BookmarksChanged => this.serialize_workspace(cx),
+            }),
             cx.on_release(|this, window, cx| {
+                if this.theme_override.is_some() {
+                    ThemeSettings::set_window_theme(window, None, cx);
+                }
+                for secondary_window in this.secondary_windows.drain(..) {
+                    secondary_window.update(cx, |_, cx| cx.remove_window()).ok();
+                }
+                if let Some(primary_window) = this.primary_window {
+                    primary_window
+                        .update(cx, |primary, cx| {
+                            primary.secondary_windows.retain(|secondary_window| {
+                                Some(*secondary_window) != window.downcast()
+                            });
+                            primary.serialize_workspace(cx);
+                        })
+                        .ok();
+                }
                 this.app_state.workspace_store.update(cx, |store, _| {
                     let window = window.downcast::<Self>().unwrap();
                     store.workspaces.remove(&window);
@@ -853,6 +895,9 @@ impl Workspace {
         cx.defer(|this, cx| {
             this.update_window_title(cx);
         });
+        if let Some(workspace_id) = workspace_id {
+            Self::load_file_history(workspace_id, cx);
+        }
         Workspace {
             weak_self: weak_handle.clone(),
             zoomed: None,
@@ -871,12 +916,15 @@ impl Workspace {
             bottom_dock,
             right_dock,
             project: project.clone(),
+            bookmark_store,
             follower_states: Default::default(),
             last_leaders_by_pane: Default::default(),
             dispatching_keystrokes: Default::default(),
             window_edited: false,
             active_call,
             database_id: workspace_id,
+            primary_window: None,
+            secondary_windows: Vec::new(),
             app_state,
             _observe_current_user,
             _apply_leader_updates,
@@ -884,10 +932,13 @@ impl Workspace {
             leader_updates_tx,
             _subscriptions: subscriptions,
             pane_history_timestamp,
+            last_opened_path: None,
+            file_history: HashMap::default(),
             workspace_actions: Default::default(),
             // This data will be incorrect, but it will be overwritten by the time it needs to be used.
             bounds: Default::default(),
             centered_layout: false,
+            theme_override: None,
             bounds_save_task_queued: None,
             on_prompt_for_new_path: None,
             render_disconnected_overlay: None,
@@ -1022,6 +1073,14 @@ impl Workspace {
             };
 
             notify_if_database_failed(window, &mut cx);
+            let theme_override = serialized_workspace
+                .as_ref()
+                .and_then(|workspace| workspace.theme.clone());
+            if theme_override.is_some() {
+                window.update(&mut cx, |workspace, cx| {
+                    workspace.apply_theme_override(theme_override, cx)
+                })?;
+            }
             let opened_items = window
                 .update(&mut cx, |_workspace, cx| {
                     open_items(serialized_workspace, project_paths, app_state, cx)
@@ -1084,6 +1143,10 @@ impl Workspace {
         &self.project
     }
 
+    pub fn bookmark_store(&self) -> &Model<BookmarkStore> {
+        &self.bookmark_store
+    }
+
     pub fn recent_navigation_history(
         &self,
         limit: Option<usize>,
@@ -1415,6 +1478,7 @@ impl Workspace {
     ) -> Task<Result<bool>> {
         let active_call = self.active_call().cloned();
         let window = cx.window_handle();
+        let secondary_windows = self.secondary_windows.clone();
 
         cx.spawn(|this, mut cx| async move {
             let workspace_count = (*cx).update(|cx| {
@@ -1449,6 +1513,21 @@ impl Workspace {
                 }
             }
 
+            // Secondary windows are closed along with this window, so their unsaved
+            // changes need to be handled first.
+            if !quitting {
+                for secondary_window in secondary_windows {
+                    let Ok(save) = secondary_window.update(&mut cx, |workspace, cx| {
+                        workspace.save_all_internal(SaveIntent::Close, cx)
+                    }) else {
+                        continue;
+                    };
+                    if !save.await? {
+                        return Ok(false);
+                    }
+                }
+            }
+
             this.update(&mut cx, |this, cx| {
                 this.save_all_internal(SaveIntent::Close, cx)
             })?
@@ -2571,7 +2650,11 @@ impl Workspace {
             pane::Event::Split(direction) => {
                 self.split_and_clone(pane, *direction, cx);
             }
+            pane::Event::MoveToNewWindow { item_id } => {
+                self.move_to_new_window(pane, *item_id, cx);
+            }
             pane::Event::Remove => self.remove_pane(pane, cx),
+            pane::Event::RearrangeTabs => {}
             pane::Event::ActivateItem { local } => {
                 pane.model.update(cx, |pane, _| {
                     pane.track_alternate_file_items();
@@ -2723,6 +2806,11 @@ impl Workspace {
 
         let item_handle = item_handle.clone();
 
+        if !self.panes.contains(&source) {
+            self.move_item_from_other_window(source, destination, item_id_to_move, cx);
+            return;
+        }
+
         if source != destination {
             // Close item from previous pane
             source.update(cx, |source, cx| {
@@ -2968,6 +3056,7 @@ impl Workspace {
         let active_entry = self.active_project_path(cx);
         self.project
             .update(cx, |project, cx| project.set_active_path(active_entry, cx));
+        self.record_file_opened(cx);
 
         self.update_window_title(cx);
     }
@@ -3580,6 +3669,12 @@ impl Workspace {
     }
 
     fn serialize_workspace(&mut self, cx: &mut ViewContext<Self>) {
+        if let Some(primary_window) = self.primary_window {
+            primary_window
+                .update(cx, |primary, cx| primary.serialize_workspace(cx))
+                .ok();
+            return;
+        }
         if self._schedule_serialize.is_none() {
             self._schedule_serialize = Some(cx.spawn(|this, mut cx| async move {
                 cx.background_executor()
@@ -3600,25 +3695,40 @@ impl Workspace {
         };
 
         fn serialize_pane_handle(pane_handle: &View<Pane>, cx: &WindowContext) -> SerializedPane {
-            let (items, active) = {
+            let (items, active, tab_groups) = {
                 let pane = pane_handle.read(cx);
                 let active_item_id = pane.active_item().map(|item| item.item_id());
                 (
                     pane.items()
-                        .filter_map(|item_handle| {
+                        .enumerate()
+                        .filter_map(|(ix, item_handle)| {
                             Some(SerializedItem {
                                 kind: Arc::from(item_handle.serialized_item_kind()?),
                                 item_id: item_handle.item_id().as_u64(),
                                 active: Some(item_handle.item_id()) == active_item_id,
                                 preview: pane.is_active_preview_item(item_handle.item_id()),
+                                pinned: pane.is_tab_pinned(ix),
+                                tab_group: pane.tab_group_for_item(item_handle.item_id()),
                             })
                         })
                         .collect::<Vec<_>>(),
                     pane.has_focus(cx),
+                    pane.tab_groups()
+                        .iter()
+                        .map(|group| SerializedTabGroup {
+                            id: group.id,
+                            color_index: group.color_index,
+                            collapsed: group.collapsed,
+                        })
+                        .collect(),
                 )
             };
 
-            SerializedPane::new(items, active)
+            SerializedPane {
+                children: items,
+                active,
+                tab_groups,
+            }
         }
 
         fn build_serialized_pane_group(
@@ -3741,6 +3851,9 @@ impl Workspace {
                 display: Default::default(),
                 docks,
                 centered_layout: self.centered_layout,
+                theme: self.theme_override.clone(),
+                bookmarks: self.bookmark_store.read(cx).serialize(),
+                secondary_windows: self.serialize_secondary_windows(cx),
             };
             return cx.spawn(|_| persistence::DB.save_workspace(serialized_workspace));
         }
@@ -3809,6 +3922,11 @@ impl Workspace {
                     }
                 }
 
+                workspace.bookmark_store.update(cx, |bookmark_store, cx| {
+                    bookmark_store.load(serialized_workspace.bookmarks, cx)
+                });
+                workspace.restore_secondary_windows(serialized_workspace.secondary_windows, cx);
+
                 let docks = serialized_workspace.docks;
 
                 for (dock, serialized_dock) in [
@@ -3990,6 +4108,28 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Returns the name of the theme used in this workspace instead of the active theme, if any.
+    pub fn theme_override(&self) -> Option<&str> {
+        self.theme_override.as_deref()
+    }
+
+    /// Uses the theme of the given name in this workspace instead of the active theme, or
+    /// goes back to the active theme when `theme` is `None`.
+    pub fn set_theme_override(&mut self, theme: Option<String>, cx: &mut ViewContext<Self>) {
+        self.apply_theme_override(theme, cx);
+        if let Some(database_id) = self.database_id() {
+            cx.background_executor()
+                .spawn(DB.set_theme(database_id, self.theme_override.clone()))
+                .detach_and_log_err(cx);
+        }
+    }
+
+    fn apply_theme_override(&mut self, theme: Option<String>, cx: &mut ViewContext<Self>) {
+        ThemeSettings::set_window_theme(cx.window_handle(), theme.as_deref(), cx);
+        self.theme_override = theme;
+        cx.notify();
+    }
+
     fn adjust_padding(padding: Option<f32>) -> f32 {
         padding
             .unwrap_or(Self::DEFAULT_PADDING)
@@ -6469,6 +6609,162 @@ mod tests {
         }
     }
 
+    mod secondary_window_tests {
+        use ui::Context as _;
+
+        use super::*;
+
+        struct TestFile {
+            project_path: ProjectPath,
+        }
+
+        impl project::Item for TestFile {
+            fn try_open(
+                _project: &Model<Project>,
+                path: &ProjectPath,
+                cx: &mut AppContext,
+            ) -> Option<Task<gpui::Result<Model<Self>>>> {
+                let project_path = path.clone();
+                Some(cx.spawn(|mut cx| async move { cx.new_model(|_| TestFile { project_path }) }))
+            }
+
+            fn entry_id(&self, _: &AppContext) -> Option<ProjectEntryId> {
+                None
+            }
+
+            fn project_path(&self, _: &AppContext) -> Option<ProjectPath> {
+                Some(self.project_path.clone())
+            }
+        }
+
+        struct TestFileView {
+            file: Model<TestFile>,
+            focus_handle: FocusHandle,
+        }
+
+        impl Item for TestFileView {
+            type Event = ();
+
+            fn for_each_project_item(
+                &self,
+                cx: &AppContext,
+                f: &mut dyn FnMut(EntityId, &dyn project::Item),
+            ) {
+                f(self.file.entity_id(), self.file.read(cx))
+            }
+
+            fn is_singleton(&self, _: &AppContext) -> bool {
+                true
+            }
+        }
+        impl EventEmitter<()> for TestFileView {}
+        impl FocusableView for TestFileView {
+            fn focus_handle(&self, _cx: &AppContext) -> FocusHandle {
+                self.focus_handle.clone()
+            }
+        }
+
+        impl Render for TestFileView {
+            fn render(&mut self, _cx: &mut ViewContext<Self>) -> impl IntoElement {
+                Empty
+            }
+        }
+
+        impl ProjectItem for TestFileView {
+            type Item = TestFile;
+
+            fn for_project_item(
+                _project: Model<Project>,
+                file: Model<Self::Item>,
+                cx: &mut ViewContext<Self>,
+            ) -> Self
+            where
+                Self: Sized,
+            {
+                Self {
+                    file,
+                    focus_handle: cx.focus_handle(),
+                }
+            }
+        }
+
+        fn pane_paths(pane: &View<Pane>, cx: &AppContext) -> Vec<String> {
+            pane.read(cx)
+                .items()
+                .filter_map(|item| item.project_path(cx))
+                .map(|path| path.path.to_string_lossy().into_owned())
+                .collect()
+        }
+
+        #[gpui::test]
+        async fn test_move_to_new_window_and_restore(cx: &mut TestAppContext) {
+            init_test(cx);
+            cx.update(register_project_item::<TestFileView>);
+
+            let fs = FakeFs::new(cx.executor());
+            fs.insert_tree("/root1", json!({ "one.txt": "", "two.txt": "" }))
+                .await;
+
+            let project = Project::test(fs, ["root1".as_ref()], cx).await;
+            let (workspace, cx) = cx.add_window_view(|cx| Workspace::test_new(project.clone(), cx));
+            let worktree_id = project.update(cx, |project, cx| {
+                project.worktrees().next().unwrap().read(cx).id()
+            });
+
+            for path in ["one.txt", "two.txt"] {
+                workspace
+                    .update(cx, |workspace, cx| {
+                        workspace.open_path((worktree_id, path), None, true, cx)
+                    })
+                    .await
+                    .unwrap();
+            }
+            let pane = workspace.update(cx, |workspace, _| workspace.active_pane().clone());
+            let item_id = pane.update(cx, |pane, _| pane.active_item().unwrap().item_id());
+
+            // The item is reopened in a new window and removed from its pane.
+            workspace.update(cx, |workspace, cx| {
+                workspace.move_to_new_window(pane.clone(), Some(item_id), cx)
+            });
+            cx.run_until_parked();
+            assert_eq!(cx.update(|cx| pane_paths(&pane, cx)), ["one.txt"]);
+            let secondary_window = workspace.update(cx, |workspace, _| {
+                assert_eq!(workspace.secondary_windows.len(), 1);
+                workspace.secondary_windows[0]
+            });
+            let secondary_paths = secondary_window
+                .update(cx, |workspace, cx| pane_paths(workspace.active_pane(), cx))
+                .unwrap();
+            assert_eq!(secondary_paths, ["two.txt"]);
+
+            // Restoring the serialized windows reopens the item in its own window.
+            let serialized_windows = workspace.update(cx, |workspace, cx| {
+                workspace.serialize_secondary_windows(cx)
+            });
+            secondary_window
+                .update(cx, |_, cx| cx.remove_window())
+                .unwrap();
+            cx.run_until_parked();
+            workspace.update(cx, |workspace, _| {
+                assert!(workspace.secondary_windows.is_empty())
+            });
+
+            workspace.update(cx, |workspace, cx| {
+                workspace.restore_secondary_windows(serialized_windows, cx)
+            });
+            cx.run_until_parked();
+            assert_eq!(cx.update(|cx| pane_paths(&pane, cx)), ["one.txt"]);
+            let secondary_window = workspace.update(cx, |workspace, _| {
+                assert_eq!(workspace.secondary_windows.len(), 1);
+                workspace.secondary_windows[0]
+            });
+            let secondary_paths = secondary_window
+                .update(cx, |workspace, cx| pane_paths(workspace.active_pane(), cx))
+                .unwrap();
+            assert_eq!(secondary_paths, ["two.txt"]);
+        }
+    }
+
     pub fn init_test(cx: &mut TestAppContext) {
         cx.update(|cx| {
             let settings_store = SettingsStore::test(cx);
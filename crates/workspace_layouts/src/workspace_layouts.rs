@@ -0,0 +1,251 @@
+use std::sync::Arc;
+
+use fuzzy::{match_strings, StringMatch, StringMatchCandidate};
+use gpui::{
+    actions, AppContext, DismissEvent, EventEmitter, FocusHandle, FocusableView, Render, Task,
+    View, ViewContext, VisualContext, WeakView,
+};
+use picker::{Picker, PickerDelegate};
+use ui::{prelude::*, HighlightedLabel, ListItem, ListItemSpacing};
+use util::ResultExt;
+use workspace::{notifications::DetachAndPromptErr, ModalView, Workspace};
+
+actions!(workspace_layouts, [Toggle, DeleteSelected]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.observe_new_views(WorkspaceLayouts::register).detach();
+}
+
+/// Lists the layouts saved for the workspace, switching to the one that's confirmed.
+/// Confirming a new name saves the workspace's current layout under it.
+pub struct WorkspaceLayouts {
+    picker: View<Picker<WorkspaceLayoutsDelegate>>,
+}
+
+impl WorkspaceLayouts {
+    fn register(workspace: &mut Workspace, _: &mut ViewContext<Workspace>) {
+        workspace.register_action(|workspace, _: &Toggle, cx| {
+            let names = workspace.layout_names().log_err().unwrap_or_default();
+            let handle = cx.view().downgrade();
+            workspace.toggle_modal(cx, move |cx| WorkspaceLayouts::new(handle, names, cx));
+        });
+    }
+
+    fn new(workspace: WeakView<Workspace>, names: Vec<String>, cx: &mut ViewContext<Self>) -> Self {
+        let delegate = WorkspaceLayoutsDelegate::new(cx.view().downgrade(), workspace, names);
+        let picker = cx.new_view(|cx| Picker::uniform_list(delegate, cx));
+        Self { picker }
+    }
+
+    fn delete_selected(&mut self, _: &DeleteSelected, cx: &mut ViewContext<Self>) {
+        self.picker.update(cx, |picker, cx| {
+            let Some(name) = picker.delegate.selected_name().map(str::to_string) else {
+                return;
+            };
+            let Some(delete) = picker
+                .delegate
+                .workspace
+                .update(cx, |workspace, cx| {
+                    workspace.delete_layout(name.clone(), cx)
+                })
+                .log_err()
+            else {
+                return;
+            };
+            cx.spawn(|picker, mut cx| async move {
+                delete.await?;
+                picker.update(&mut cx, |picker, cx| {
+                    picker.delegate.names.retain(|existing| *existing != name);
+                    picker.refresh(cx);
+                })
+            })
+            .detach_and_log_err(cx);
+        });
+    }
+}
+
+impl Render for WorkspaceLayouts {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .key_context("WorkspaceLayouts")
+            .w(rems(34.))
+            .on_action(cx.listener(Self::delete_selected))
+            .child(self.picker.clone())
+    }
+}
+
+impl FocusableView for WorkspaceLayouts {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl EventEmitter<DismissEvent> for WorkspaceLayouts {}
+impl ModalView for WorkspaceLayouts {}
+
+pub struct WorkspaceLayoutsDelegate {
+    workspace_layouts: WeakView<WorkspaceLayouts>,
+    workspace: WeakView<Workspace>,
+    names: Vec<String>,
+    matches: Vec<StringMatch>,
+    selected_index: usize,
+    last_query: String,
+}
+
+impl WorkspaceLayoutsDelegate {
+    fn new(
+        workspace_layouts: WeakView<WorkspaceLayouts>,
+        workspace: WeakView<Workspace>,
+        names: Vec<String>,
+    ) -> Self {
+        Self {
+            workspace_layouts,
+            workspace,
+            names,
+            matches: Vec::new(),
+            selected_index: 0,
+            last_query: String::new(),
+        }
+    }
+
+    fn selected_name(&self) -> Option<&str> {
+        let mat = self.matches.get(self.selected_index)?;
+        self.names.get(mat.candidate_id).map(String::as_str)
+    }
+
+    fn save_layout(&mut self, name: String, cx: &mut ViewContext<Picker<Self>>) {
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.save_layout(name, cx).detach_and_prompt_err(
+                    "Failed to save layout",
+                    cx,
+                    |_, _| None,
+                );
+            })
+            .log_err();
+        self.dismissed(cx);
+    }
+}
+
+impl PickerDelegate for WorkspaceLayoutsDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _cx: &mut WindowContext) -> Arc<str> {
+        "Switch to a layout, or type a name and press alt-enter to save one...".into()
+    }
+
+    fn no_matches_text(&self, _cx: &mut WindowContext) -> SharedString {
+        if self.last_query.trim().is_empty() {
+            "No saved layouts".into()
+        } else {
+            "Press enter to save the current layout".into()
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(&mut self, ix: usize, _: &mut ViewContext<Picker<Self>>) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(&mut self, query: String, cx: &mut ViewContext<Picker<Self>>) -> Task<()> {
+        self.last_query = query.clone();
+        let candidates = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| StringMatchCandidate::new(id, name.clone()))
+            .collect::<Vec<_>>();
+        let background = cx.background_executor().clone();
+        cx.spawn(|this, mut cx| async move {
+            let matches = if query.is_empty() {
+                candidates
+                    .into_iter()
+                    .map(|candidate| StringMatch {
+                        candidate_id: candidate.id,
+                        string: candidate.string,
+                        positions: Vec::new(),
+                        score: 0.0,
+                    })
+                    .collect()
+            } else {
+                match_strings(
+                    &candidates,
+                    &query,
+                    false,
+                    100,
+                    &Default::default(),
+                    background,
+                )
+                .await
+            };
+
+            this.update(&mut cx, |this, cx| {
+                let delegate = &mut this.delegate;
+                delegate.matches = matches;
+                delegate.selected_index = delegate
+                    .selected_index
+                    .min(delegate.matches.len().saturating_sub(1));
+                cx.notify();
+            })
+            .log_err();
+        })
+    }
+
+    fn confirm(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let Some(name) = self.selected_name().map(str::to_string) else {
+            let name = self.last_query.trim().to_string();
+            if !name.is_empty() {
+                self.save_layout(name, cx);
+            }
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                workspace.switch_to_layout(name, cx).detach_and_prompt_err(
+                    "Failed to switch layout",
+                    cx,
+                    |_, _| None,
+                );
+            })
+            .log_err();
+        self.dismissed(cx);
+    }
+
+    fn confirm_input(&mut self, _: bool, cx: &mut ViewContext<Picker<Self>>) {
+        let name = self.last_query.trim().to_string();
+        if !name.is_empty() {
+            self.save_layout(name, cx);
+        }
+    }
+
+    fn dismissed(&mut self, cx: &mut ViewContext<Picker<Self>>) {
+        self.workspace_layouts
+            .update(cx, |_, cx| cx.emit(DismissEvent))
+            .log_err();
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _cx: &mut ViewContext<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let mat = self.matches.get(ix)?;
+        let name = self.names.get(mat.candidate_id)?;
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .selected(selected)
+                .child(HighlightedLabel::new(name.clone(), mat.positions.clone())),
+        )
+    }
+}
@@ -184,6 +184,8 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     editor::init(cx);
     image_viewer::init(cx);
     diagnostics::init(cx);
+    diff_view::init(cx);
+    local_history::init(cx);
 
     audio::init(Assets, cx);
     workspace::init(app_state.clone(), cx);
@@ -193,6 +195,8 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     file_finder::init(cx);
     tab_switcher::init(cx);
     outline::init(cx);
+    bookmarks::init(cx);
+    workspace_layouts::init(cx);
     project_symbols::init(cx);
     project_panel::init(Assets, cx);
     outline_panel::init(Assets, cx);
@@ -200,11 +204,12 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
     search::init(cx);
     vim::init(cx);
-    terminal_view::init(cx);
+    terminal_view::init(&app_state.client, cx);
 
     journal::init(app_state.clone(), cx);
     language_selector::init(cx);
     theme_selector::init(cx);
+    theme_editor::init(cx);
     language_tools::init(cx);
     call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
     notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -213,6 +218,7 @@ fn init_ui(app_state: Arc<AppState>, cx: &mut AppContext) -> Result<()> {
     markdown_preview::init(cx);
     welcome::init(cx);
     extensions_ui::init(cx);
+    settings_ui::init(cx);
     snippet_provider::init(cx);
 
     // Initialize each completion provider. Settings are used for toggling between them.
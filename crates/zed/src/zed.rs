@@ -3279,7 +3279,7 @@ mod tests {
             collab_ui::init(&app_state, cx);
             project_panel::init((), cx);
             outline_panel::init((), cx);
-            terminal_view::init(cx);
+            terminal_view::init(&app_state.client, cx);
             assistant::init(app_state.fs.clone(), app_state.client.clone(), cx);
             repl::init(cx);
             tasks_ui::init(cx);